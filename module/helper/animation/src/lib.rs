@@ -33,4 +33,13 @@ mod private
 
   /// Tools for managing [`AnimatablePlayer`] playback in every time moment
   layer sequencer;
+
+  /// Spring, decay and retarget players driven by velocity
+  layer physics;
+
+  /// Keyframe tracks with per-segment easing and markers
+  layer track;
+
+  /// Timelines composing seekable players at time offsets
+  layer timeline;
 }
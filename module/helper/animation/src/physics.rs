@@ -0,0 +1,859 @@
+//! Physics-based players driven by velocity instead of duration.
+//!
+//! [`Spring`] pulls a value towards a target like a damped spring, [`Decay`]
+//! lets a value glide with its initial velocity until friction stops it and
+//! [`Retarget`] reaches a target in a fixed time starting with the current
+//! velocity. Every player can get a new target while running without jumps in
+//! value or velocity. Spring and decay motion is solved analytically, so it
+//! doesn't depend on the frame rate.
+
+mod private
+{
+  use crate::traits::{ AnimatablePlayer, Kinematic };
+  use crate::AnimationState;
+  use mingl::MatEl;
+
+  /// Default distance to target when motion is considered finished
+  const REST_DISTANCE : f64 = 1e-3;
+  /// Default speed when motion is considered finished
+  const REST_SPEED : f64 = 1e-3;
+  /// Time step used to estimate how long a spring needs to settle
+  const SETTLE_STEP : f64 = 1.0 / 60.0;
+  /// Longest spring settle time estimated by [`Spring::duration_get`]
+  const SETTLE_LIMIT : f64 = 60.0;
+
+  /// Coefficients of a solved spring step.
+  ///
+  /// Displacement and velocity after the step are
+  /// `x = x0 * position.0 + v0 * position.1` and `v = x0 * velocity.0 + v0 * velocity.1`.
+  #[ derive( Debug, Clone, Copy ) ]
+  struct SpringStep
+  {
+    position : ( f64, f64 ),
+    velocity : ( f64, f64 ),
+  }
+
+  /// Player that moves a value to a target like a damped spring.
+  ///
+  /// With the default damping ratio of 1.0 the spring is critically damped: it
+  /// reaches the target as fast as possible without overshooting.
+  #[ derive( Debug, Clone ) ]
+  pub struct Spring< T >
+  {
+    /// Value the player started with
+    start_value : T,
+    /// Velocity the player started with
+    start_velocity : T,
+    /// Current value
+    value : T,
+    /// Current velocity in value units per second
+    velocity : T,
+    /// Value the spring pulls to
+    target : T,
+    /// Spring stiffness
+    stiffness : f64,
+    /// Mass of the moved value
+    mass : f64,
+    /// Damping ratio ( < 1.0 oscillates, 1.0 is critically damped, > 1.0 is overdamped )
+    damping_ratio : f64,
+    /// Distance to target when motion is considered finished
+    rest_distance : f64,
+    /// Speed when motion is considered finished
+    rest_speed : f64,
+    /// Current elapsed time
+    elapsed : f64,
+    /// Current animation state
+    state : AnimationState,
+  }
+
+  impl< T > Spring< T >
+  where T : Kinematic + 'static
+  {
+    /// Creates a critically damped spring that moves `value` to `target`.
+    pub fn new( value : T, target : T ) -> Self
+    {
+      let velocity = value.scale( 0.0 );
+      Self
+      {
+        start_value : value.clone(),
+        start_velocity : velocity.clone(),
+        value,
+        velocity,
+        target,
+        stiffness : 100.0,
+        mass : 1.0,
+        damping_ratio : 1.0,
+        rest_distance : REST_DISTANCE,
+        rest_speed : REST_SPEED,
+        elapsed : 0.0,
+        state : AnimationState::Pending,
+      }
+    }
+
+    /// Sets initial velocity in value units per second.
+    pub fn with_velocity( mut self, velocity : T ) -> Self
+    {
+      self.start_velocity = velocity.clone();
+      self.velocity = velocity;
+      self
+    }
+
+    /// Sets spring stiffness, higher values make motion faster.
+    pub fn with_stiffness( mut self, stiffness : f64 ) -> Self
+    {
+      self.stiffness = stiffness.max( f64::EPSILON );
+      self
+    }
+
+    /// Sets mass of the moved value, higher values make motion slower.
+    pub fn with_mass( mut self, mass : f64 ) -> Self
+    {
+      self.mass = mass.max( f64::EPSILON );
+      self
+    }
+
+    /// Sets damping ratio: below 1.0 the spring oscillates, above 1.0 it approaches the target slower.
+    pub fn with_damping_ratio( mut self, damping_ratio : f64 ) -> Self
+    {
+      self.damping_ratio = damping_ratio.max( 0.0 );
+      self
+    }
+
+    /// Sets distance to target and speed below which the spring stops.
+    pub fn with_rest_threshold( mut self, distance : f64, speed : f64 ) -> Self
+    {
+      self.rest_distance = distance.max( 0.0 );
+      self.rest_speed = speed.max( 0.0 );
+      self
+    }
+
+    /// Moves target keeping current value and velocity, so motion stays smooth.
+    pub fn retarget( &mut self, target : T )
+    {
+      self.target = target;
+      if self.state == AnimationState::Completed
+      {
+        self.state = AnimationState::Running;
+      }
+    }
+
+    /// Returns current value.
+    pub fn value_get( &self ) -> T
+    {
+      self.value.clone()
+    }
+
+    /// Returns current velocity in value units per second.
+    pub fn velocity_get( &self ) -> T
+    {
+      self.velocity.clone()
+    }
+
+    /// Returns value the spring pulls to.
+    pub fn target_get( &self ) -> T
+    {
+      self.target.clone()
+    }
+
+    /// Gets the current animation state.
+    pub fn state( &self ) -> AnimationState
+    {
+      self.state
+    }
+
+    /// Gets elapsed time
+    pub fn time( &self ) -> f64
+    {
+      self.elapsed
+    }
+
+    /// Solves spring motion for `time` seconds
+    fn step_get( &self, time : f64 ) -> SpringStep
+    {
+      let omega = ( self.stiffness / self.mass ).sqrt();
+      let zeta = self.damping_ratio;
+      if ( zeta - 1.0 ).abs() < 1e-6
+      {
+        let decay = ( -omega * time ).exp();
+        SpringStep
+        {
+          position : ( decay * ( 1.0 + omega * time ), decay * time ),
+          velocity : ( -decay * omega * omega * time, decay * ( 1.0 - omega * time ) ),
+        }
+      }
+      else if zeta < 1.0
+      {
+        let omega_d = omega * ( 1.0 - zeta * zeta ).sqrt();
+        let decay = ( -zeta * omega * time ).exp();
+        let ( sin, cos ) = ( omega_d * time ).sin_cos();
+        SpringStep
+        {
+          position : ( decay * ( cos + zeta * omega / omega_d * sin ), decay * sin / omega_d ),
+          velocity : ( -decay * omega * omega / omega_d * sin, decay * ( cos - zeta * omega / omega_d * sin ) ),
+        }
+      }
+      else
+      {
+        let root = ( zeta * zeta - 1.0 ).sqrt();
+        let slow = -omega * ( zeta - root );
+        let fast = -omega * ( zeta + root );
+        let ( slow_decay, fast_decay ) = ( ( slow * time ).exp(), ( fast * time ).exp() );
+        let span = slow - fast;
+        SpringStep
+        {
+          position : ( ( slow * fast_decay - fast * slow_decay ) / span, ( slow_decay - fast_decay ) / span ),
+          velocity : ( slow * fast * ( fast_decay - slow_decay ) / span, ( slow * slow_decay - fast * fast_decay ) / span ),
+        }
+      }
+    }
+
+    /// Whether displacement and velocity are small enough to stop
+    fn is_at_rest( &self, distance : f64, speed : f64 ) -> bool
+    {
+      distance <= self.rest_distance && speed <= self.rest_speed
+    }
+
+    /// Estimates time the spring needs to come to rest from its current state
+    fn settle_time( &self ) -> f64
+    {
+      let distance = self.value.sub( &self.target ).magnitude();
+      let speed = self.velocity.magnitude();
+      let mut time = 0.0;
+      while time < SETTLE_LIMIT
+      {
+        let step = self.step_get( time );
+        let distance_bound = step.position.0.abs() * distance + step.position.1.abs() * speed;
+        let speed_bound = step.velocity.0.abs() * distance + step.velocity.1.abs() * speed;
+        if self.is_at_rest( distance_bound, speed_bound )
+        {
+          return time;
+        }
+        time += SETTLE_STEP;
+      }
+
+      SETTLE_LIMIT
+    }
+  }
+
+  impl< T > AnimatablePlayer for Spring< T >
+  where T : Kinematic + 'static
+  {
+    fn update( &mut self, delta_time : f64 )
+    {
+      match self.state
+      {
+        AnimationState::Paused | AnimationState::Completed => return,
+        AnimationState::Pending => self.state = AnimationState::Running,
+        AnimationState::Running => {}
+      }
+
+      let delta_time = delta_time.max( 0.0 );
+      self.elapsed += delta_time;
+
+      let step = self.step_get( delta_time );
+      let displacement = self.value.sub( &self.target );
+      let moved = displacement.scale( step.position.0 ).add( &self.velocity.scale( step.position.1 ) );
+      self.velocity = displacement.scale( step.velocity.0 ).add( &self.velocity.scale( step.velocity.1 ) );
+      self.value = self.target.add( &moved );
+
+      if self.is_at_rest( moved.magnitude(), self.velocity.magnitude() )
+      {
+        self.value = self.target.clone();
+        self.velocity = self.velocity.scale( 0.0 );
+        self.state = AnimationState::Completed;
+      }
+    }
+
+    fn is_completed( &self ) -> bool
+    {
+      self.state == AnimationState::Completed
+    }
+
+    fn pause( &mut self )
+    {
+      if self.state == AnimationState::Running
+      {
+        self.state = AnimationState::Paused;
+      }
+    }
+
+    fn resume( &mut self )
+    {
+      if self.state == AnimationState::Paused
+      {
+        self.state = AnimationState::Running;
+      }
+    }
+
+    /// Starts motion again from the initial value and velocity to the current target
+    fn reset( &mut self )
+    {
+      self.value = self.start_value.clone();
+      self.velocity = self.start_velocity.clone();
+      self.elapsed = 0.0;
+      self.state = AnimationState::Running;
+    }
+
+    fn as_any( &self ) -> &dyn core::any::Any
+    {
+      self
+    }
+
+    fn as_any_mut( &mut self ) -> &mut dyn core::any::Any
+    {
+      self
+    }
+
+    /// Elapsed time plus estimated time to come to rest
+    fn duration_get( &self ) -> f64
+    {
+      if self.state == AnimationState::Completed
+      {
+        self.elapsed
+      }
+      else
+      {
+        self.elapsed + self.settle_time()
+      }
+    }
+
+    fn delay_get( &self ) -> f64
+    {
+      0.0
+    }
+
+    fn progress( &self ) -> f64
+    {
+      let duration = self.duration_get();
+      if duration > 0.0 { ( self.elapsed / duration ).clamp( 0.0, 1.0 ) } else { 1.0 }
+    }
+  }
+
+  /// Player that moves a value with its initial velocity until friction stops it.
+  ///
+  /// Velocity decays exponentially, so the value stops at [`Decay::target_get`],
+  /// which is known in advance and can be used for snapping.
+  #[ derive( Debug, Clone ) ]
+  pub struct Decay< T >
+  {
+    /// Value the player started with
+    start_value : T,
+    /// Velocity the player started with
+    start_velocity : T,
+    /// Current value
+    value : T,
+    /// Current velocity in value units per second
+    velocity : T,
+    /// Time in seconds for velocity to drop by e times
+    time_constant : f64,
+    /// Speed when motion is considered finished
+    rest_speed : f64,
+    /// Current elapsed time
+    elapsed : f64,
+    /// Current animation state
+    state : AnimationState,
+  }
+
+  impl< T > Decay< T >
+  where T : Kinematic + 'static
+  {
+    /// Creates a decay that moves `value` with initial `velocity` in value units per second.
+    pub fn new( value : T, velocity : T ) -> Self
+    {
+      Self
+      {
+        start_value : value.clone(),
+        start_velocity : velocity.clone(),
+        value,
+        velocity,
+        time_constant : 0.325,
+        rest_speed : REST_SPEED,
+        elapsed : 0.0,
+        state : AnimationState::Pending,
+      }
+    }
+
+    /// Sets time in seconds for velocity to drop by e times, higher values make motion longer.
+    pub fn with_time_constant( mut self, time_constant : f64 ) -> Self
+    {
+      self.time_constant = time_constant.max( f64::EPSILON );
+      self
+    }
+
+    /// Sets speed below which the motion stops.
+    pub fn with_rest_speed( mut self, speed : f64 ) -> Self
+    {
+      self.rest_speed = speed.max( 0.0 );
+      self
+    }
+
+    /// Replaces velocity keeping current value, for example on a new fling.
+    pub fn velocity_set( &mut self, velocity : T )
+    {
+      self.velocity = velocity;
+      if self.state == AnimationState::Completed
+      {
+        self.state = AnimationState::Running;
+      }
+    }
+
+    /// Returns current value.
+    pub fn value_get( &self ) -> T
+    {
+      self.value.clone()
+    }
+
+    /// Returns current velocity in value units per second.
+    pub fn velocity_get( &self ) -> T
+    {
+      self.velocity.clone()
+    }
+
+    /// Returns value where motion stops.
+    pub fn target_get( &self ) -> T
+    {
+      self.value.add( &self.velocity.scale( self.time_constant ) )
+    }
+
+    /// Gets the current animation state.
+    pub fn state( &self ) -> AnimationState
+    {
+      self.state
+    }
+
+    /// Gets elapsed time
+    pub fn time( &self ) -> f64
+    {
+      self.elapsed
+    }
+  }
+
+  impl< T > AnimatablePlayer for Decay< T >
+  where T : Kinematic + 'static
+  {
+    fn update( &mut self, delta_time : f64 )
+    {
+      match self.state
+      {
+        AnimationState::Paused | AnimationState::Completed => return,
+        AnimationState::Pending => self.state = AnimationState::Running,
+        AnimationState::Running => {}
+      }
+
+      let delta_time = delta_time.max( 0.0 );
+      self.elapsed += delta_time;
+
+      let decay = ( -delta_time / self.time_constant ).exp();
+      self.value = self.value.add( &self.velocity.scale( self.time_constant * ( 1.0 - decay ) ) );
+      self.velocity = self.velocity.scale( decay );
+
+      if self.velocity.magnitude() <= self.rest_speed
+      {
+        self.value = self.target_get();
+        self.velocity = self.velocity.scale( 0.0 );
+        self.state = AnimationState::Completed;
+      }
+    }
+
+    fn is_completed( &self ) -> bool
+    {
+      self.state == AnimationState::Completed
+    }
+
+    fn pause( &mut self )
+    {
+      if self.state == AnimationState::Running
+      {
+        self.state = AnimationState::Paused;
+      }
+    }
+
+    fn resume( &mut self )
+    {
+      if self.state == AnimationState::Paused
+      {
+        self.state = AnimationState::Running;
+      }
+    }
+
+    fn reset( &mut self )
+    {
+      self.value = self.start_value.clone();
+      self.velocity = self.start_velocity.clone();
+      self.elapsed = 0.0;
+      self.state = AnimationState::Running;
+    }
+
+    fn as_any( &self ) -> &dyn core::any::Any
+    {
+      self
+    }
+
+    fn as_any_mut( &mut self ) -> &mut dyn core::any::Any
+    {
+      self
+    }
+
+    /// Elapsed time plus time for speed to drop below rest speed
+    fn duration_get( &self ) -> f64
+    {
+      let speed = self.velocity.magnitude();
+      if self.state == AnimationState::Completed || speed <= self.rest_speed
+      {
+        return self.elapsed;
+      }
+      let rest_speed = self.rest_speed.max( f64::EPSILON );
+      self.elapsed + self.time_constant * ( speed / rest_speed ).ln()
+    }
+
+    fn delay_get( &self ) -> f64
+    {
+      0.0
+    }
+
+    fn progress( &self ) -> f64
+    {
+      let duration = self.duration_get();
+      if duration > 0.0 { ( self.elapsed / duration ).clamp( 0.0, 1.0 ) } else { 1.0 }
+    }
+  }
+
+  /// Player that reaches a target in fixed time and can be retargeted keeping velocity.
+  ///
+  /// Motion follows a cubic Hermite curve that starts with the current velocity
+  /// and stops at the target. [`Retarget::retarget`] starts a new curve from the
+  /// current value and velocity, so a value chasing a moving target never jumps.
+  #[ derive( Debug, Clone ) ]
+  pub struct Retarget< T >
+  {
+    /// Value the player started with
+    start_value : T,
+    /// Velocity the player started with
+    start_velocity : T,
+    /// Value at the start of the current curve
+    from : T,
+    /// Velocity at the start of the current curve
+    from_velocity : T,
+    /// Value the curve stops at
+    target : T,
+    /// Duration of one curve in seconds
+    duration : f64,
+    /// Time elapsed on the current curve
+    elapsed : f64,
+    /// Current animation state
+    state : AnimationState,
+  }
+
+  impl< T > Retarget< T >
+  where T : Kinematic + 'static
+  {
+    /// Creates a player that moves `value` to `target` in `duration` seconds.
+    pub fn new( value : T, target : T, duration : f64 ) -> Self
+    {
+      let velocity = value.scale( 0.0 );
+      Self
+      {
+        start_value : value.clone(),
+        start_velocity : velocity.clone(),
+        from : value,
+        from_velocity : velocity,
+        target,
+        duration : duration.max( 0.001 ),
+        elapsed : 0.0,
+        state : AnimationState::Pending,
+      }
+    }
+
+    /// Sets initial velocity in value units per second.
+    pub fn with_velocity( mut self, velocity : T ) -> Self
+    {
+      self.start_velocity = velocity.clone();
+      self.from_velocity = velocity;
+      self
+    }
+
+    /// Starts a new curve from current value and velocity to `target`, taking the full duration.
+    pub fn retarget( &mut self, target : T )
+    {
+      let velocity = self.velocity_get();
+      self.from = self.value_get();
+      self.from_velocity = velocity;
+      self.target = target;
+      self.elapsed = 0.0;
+      if self.state == AnimationState::Completed
+      {
+        self.state = AnimationState::Running;
+      }
+    }
+
+    /// Returns current value.
+    pub fn value_get( &self ) -> T
+    {
+      let ( position, _ ) = self.basis_get();
+      self.from.sub( &self.target ).scale( position.0 )
+      .add( &self.from_velocity.scale( position.1 ) )
+      .add( &self.target )
+    }
+
+    /// Returns current velocity in value units per second.
+    pub fn velocity_get( &self ) -> T
+    {
+      let ( _, velocity ) = self.basis_get();
+      self.from.sub( &self.target ).scale( velocity.0 )
+      .add( &self.from_velocity.scale( velocity.1 ) )
+    }
+
+    /// Returns value the curve stops at.
+    pub fn target_get( &self ) -> T
+    {
+      self.target.clone()
+    }
+
+    /// Gets the current animation state.
+    pub fn state( &self ) -> AnimationState
+    {
+      self.state
+    }
+
+    /// Gets time elapsed on the current curve
+    pub fn time( &self ) -> f64
+    {
+      self.elapsed
+    }
+
+    /// Hermite basis for displacement and start velocity, for value and for velocity
+    fn basis_get( &self ) -> ( ( f64, f64 ), ( f64, f64 ) )
+    {
+      let time = ( self.elapsed / self.duration ).clamp( 0.0, 1.0 );
+      let time2 = time * time;
+      let time3 = time2 * time;
+      let position =
+      (
+        2.0 * time3 - 3.0 * time2 + 1.0,
+        ( time3 - 2.0 * time2 + time ) * self.duration,
+      );
+      let velocity =
+      (
+        ( 6.0 * time2 - 6.0 * time ) / self.duration,
+        3.0 * time2 - 4.0 * time + 1.0,
+      );
+      ( position, velocity )
+    }
+  }
+
+  impl< T > AnimatablePlayer for Retarget< T >
+  where T : Kinematic + 'static
+  {
+    fn update( &mut self, delta_time : f64 )
+    {
+      match self.state
+      {
+        AnimationState::Paused | AnimationState::Completed => return,
+        AnimationState::Pending => self.state = AnimationState::Running,
+        AnimationState::Running => {}
+      }
+
+      self.elapsed += delta_time.max( 0.0 );
+      if self.elapsed >= self.duration
+      {
+        self.elapsed = self.duration;
+        self.state = AnimationState::Completed;
+      }
+    }
+
+    fn is_completed( &self ) -> bool
+    {
+      self.state == AnimationState::Completed
+    }
+
+    fn pause( &mut self )
+    {
+      if self.state == AnimationState::Running
+      {
+        self.state = AnimationState::Paused;
+      }
+    }
+
+    fn resume( &mut self )
+    {
+      if self.state == AnimationState::Paused
+      {
+        self.state = AnimationState::Running;
+      }
+    }
+
+    /// Starts motion again from the initial value and velocity to the current target
+    fn reset( &mut self )
+    {
+      self.from = self.start_value.clone();
+      self.from_velocity = self.start_velocity.clone();
+      self.elapsed = 0.0;
+      self.state = AnimationState::Running;
+    }
+
+    fn as_any( &self ) -> &dyn core::any::Any
+    {
+      self
+    }
+
+    fn as_any_mut( &mut self ) -> &mut dyn core::any::Any
+    {
+      self
+    }
+
+    fn duration_get( &self ) -> f64
+    {
+      self.duration
+    }
+
+    fn delay_get( &self ) -> f64
+    {
+      0.0
+    }
+
+    fn progress( &self ) -> f64
+    {
+      ( self.elapsed / self.duration ).clamp( 0.0, 1.0 )
+    }
+  }
+
+  // === KINEMATIC IMPLEMENTATIONS ===
+
+  impl Kinematic for f32
+  {
+    fn add( &self, other : &Self ) -> Self
+    {
+      self + other
+    }
+
+    fn scale( &self, factor : f64 ) -> Self
+    {
+      self * factor as f32
+    }
+
+    fn magnitude( &self ) -> f64
+    {
+      f64::from( self.abs() )
+    }
+  }
+
+  impl Kinematic for f64
+  {
+    fn add( &self, other : &Self ) -> Self
+    {
+      self + other
+    }
+
+    fn scale( &self, factor : f64 ) -> Self
+    {
+      self * factor
+    }
+
+    fn magnitude( &self ) -> f64
+    {
+      self.abs()
+    }
+  }
+
+  impl Kinematic for ( f32, f32 )
+  {
+    fn add( &self, other : &Self ) -> Self
+    {
+      ( self.0 + other.0, self.1 + other.1 )
+    }
+
+    fn scale( &self, factor : f64 ) -> Self
+    {
+      ( self.0.scale( factor ), self.1.scale( factor ) )
+    }
+
+    fn magnitude( &self ) -> f64
+    {
+      f64::from( self.0 ).hypot( f64::from( self.1 ) )
+    }
+  }
+
+  impl Kinematic for ( f64, f64 )
+  {
+    fn add( &self, other : &Self ) -> Self
+    {
+      ( self.0 + other.0, self.1 + other.1 )
+    }
+
+    fn scale( &self, factor : f64 ) -> Self
+    {
+      ( self.0 * factor, self.1 * factor )
+    }
+
+    fn magnitude( &self ) -> f64
+    {
+      self.0.hypot( self.1 )
+    }
+  }
+
+  impl< E, const N : usize > Kinematic for mingl::Vector< E, N >
+  where E : MatEl + Kinematic
+  {
+    fn add( &self, other : &Self ) -> Self
+    {
+      let mut copy = *self;
+      copy.iter_mut().zip( other.iter() )
+      .for_each( | ( lhs, rhs ) | *lhs = lhs.add( rhs ) );
+
+      copy
+    }
+
+    fn scale( &self, factor : f64 ) -> Self
+    {
+      let mut copy = *self;
+      copy.iter_mut()
+      .for_each( | element | *element = element.scale( factor ) );
+
+      copy
+    }
+
+    fn magnitude( &self ) -> f64
+    {
+      self.iter()
+      .map( | element | element.magnitude().powi( 2 ) )
+      .sum::< f64 >()
+      .sqrt()
+    }
+  }
+
+  impl< E > Kinematic for Vec< E >
+  where E : MatEl + Kinematic
+  {
+    fn add( &self, other : &Self ) -> Self
+    {
+      self.iter().zip( other.iter() )
+      .map( | ( lhs, rhs ) | lhs.add( rhs ) )
+      .collect::< Vec< _ > >()
+    }
+
+    fn scale( &self, factor : f64 ) -> Self
+    {
+      self.iter()
+      .map( | element | element.scale( factor ) )
+      .collect::< Vec< _ > >()
+    }
+
+    fn magnitude( &self ) -> f64
+    {
+      self.iter()
+      .map( | element | element.magnitude().powi( 2 ) )
+      .sum::< f64 >()
+      .sqrt()
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use
+  {
+    Spring,
+    Decay,
+    Retarget
+  };
+}
@@ -0,0 +1,379 @@
+//! Timelines that place players at time offsets.
+//!
+//! [`Timeline`] drives every contained [`Seekable`] player by seeking it to the
+//! timeline time shifted by the player offset, so the whole composition can be
+//! scrubbed to any moment. Players can run in parallel at chosen offsets or one
+//! after another, and a timeline is itself seekable, so timelines nest.
+
+mod private
+{
+  use crate::traits::{ AnimatablePlayer, Seekable };
+  use crate::{ AnimationState, Marker };
+
+  /// Player placed on [`Timeline`]
+  #[ derive( Debug ) ]
+  struct Entry
+  {
+    name : Box< str >,
+    offset : f64,
+    player : Box< dyn Seekable >,
+  }
+
+  impl Clone for Entry
+  {
+    fn clone( &self ) -> Self
+    {
+      Self
+      {
+        name : self.name.clone(),
+        offset : self.offset,
+        player : clone_dyn_types::clone_into_box( &*self.player ),
+      }
+    }
+  }
+
+  /// Section of [`Timeline`] played repeatedly.
+  #[ non_exhaustive ]
+  #[ derive( Debug, Clone, Copy, PartialEq ) ]
+  pub struct LoopSection
+  {
+    /// Start of the section in seconds
+    pub start : f64,
+    /// End of the section in seconds
+    pub end : f64,
+    /// Number of times the section is repeated ( -1 = infinite )
+    pub repeat_count : i32,
+  }
+
+  /// Composition of players placed at time offsets.
+  #[ derive( Debug, Clone ) ]
+  pub struct Timeline
+  {
+    /// Players in insertion order
+    entries : Vec< Entry >,
+    /// Markers of the timeline itself sorted by time
+    markers : Vec< Marker >,
+    /// Playhead time
+    time : f64,
+    /// Current animation state
+    state : AnimationState,
+    /// Section played repeatedly
+    loop_section : Option< LoopSection >,
+    /// Repeats of the loop section done
+    current_repeat : i32,
+    /// Markers crossed by playback and not taken yet
+    events : Vec< Marker >,
+  }
+
+  impl Timeline
+  {
+    /// Creates an empty timeline.
+    pub fn new() -> Self
+    {
+      Self
+      {
+        entries : Vec::new(),
+        markers : Vec::new(),
+        time : 0.0,
+        state : AnimationState::Pending,
+        loop_section : None,
+        current_repeat : 0,
+        events : Vec::new(),
+      }
+    }
+
+    /// Repeats section from `start` to `end` `repeat_count` times ( -1 = infinite ) before playing on.
+    /// Loop section works only while this timeline is updated itself, not when it is seeked by a parent timeline.
+    pub fn with_loop( mut self, start : f64, end : f64, repeat_count : i32 ) -> Self
+    {
+      let start = start.max( 0.0 );
+      self.loop_section = ( end > start ).then_some( LoopSection { start, end, repeat_count } );
+      self
+    }
+
+    /// Adds a marker of the timeline itself.
+    pub fn with_marker( mut self, time : f64, name : &str ) -> Self
+    {
+      let time = time.max( 0.0 );
+      let index = self.markers.partition_point( | marker | marker.time <= time );
+      self.markers.insert( index, Marker::new( time, name ) );
+      self
+    }
+
+    /// Places player to start at `offset` seconds, in parallel with other players.
+    pub fn insert< T >( &mut self, name : &str, offset : f64, player : T )
+    where T : Seekable + 'static
+    {
+      let offset = offset.max( 0.0 );
+      let mut player : Box< dyn Seekable > = Box::new( player );
+      player.seek( self.time - offset );
+      self.entries.push( Entry { name : name.into(), offset, player } );
+      if self.state == AnimationState::Pending
+      {
+        self.state = AnimationState::Running;
+      }
+    }
+
+    /// Places player to start when the timeline content ends, after all other players.
+    pub fn append< T >( &mut self, name : &str, player : T )
+    where T : Seekable + 'static
+    {
+      let offset = self.duration_get();
+      self.insert( name, offset, player );
+    }
+
+    /// Gets reference to named player.
+    pub fn get< T >( &self, name : &str ) -> Option< &T >
+    where T : Seekable + 'static
+    {
+      let entry = self.entries.iter().find( | entry | &*entry.name == name )?;
+      entry.player.as_any().downcast_ref::< T >()
+    }
+
+    /// Gets mutable reference to named player.
+    pub fn get_mut< T >( &mut self, name : &str ) -> Option< &mut T >
+    where T : Seekable + 'static
+    {
+      let entry = self.entries.iter_mut().find( | entry | &*entry.name == name )?;
+      entry.player.as_any_mut().downcast_mut::< T >()
+    }
+
+    /// Returns offset of named player.
+    pub fn offset_get( &self, name : &str ) -> Option< f64 >
+    {
+      self.entries.iter().find( | entry | &*entry.name == name ).map( | entry | entry.offset )
+    }
+
+    /// Returns names of contained players in insertion order.
+    pub fn keys( &self ) -> Vec< Box< str > >
+    {
+      self.entries.iter().map( | entry | entry.name.clone() ).collect()
+    }
+
+    /// Moves playhead to `time` without firing markers, seeking every player.
+    pub fn seek( &mut self, time : f64 )
+    {
+      let duration = self.duration_get();
+      self.time = time.clamp( 0.0, duration );
+      if self.state != AnimationState::Paused
+      {
+        self.state = if self.time >= duration { AnimationState::Completed } else { AnimationState::Running };
+      }
+      self.players_seek();
+    }
+
+    /// Returns playhead time.
+    pub fn time( &self ) -> f64
+    {
+      self.time
+    }
+
+    /// Gets the current animation state.
+    pub fn state( &self ) -> AnimationState
+    {
+      self.state
+    }
+
+    /// Gets the loop section.
+    pub fn loop_section_get( &self ) -> Option< LoopSection >
+    {
+      self.loop_section
+    }
+
+    /// Gets the current repeat of the loop section.
+    pub fn current_repeat( &self ) -> i32
+    {
+      self.current_repeat
+    }
+
+    /// Takes markers crossed by [`AnimatablePlayer::update`] since the last call, in time order.
+    /// Markers of players are named `player/marker`, nested timelines add their own prefixes.
+    pub fn events_take( &mut self ) -> Vec< Marker >
+    {
+      core::mem::take( &mut self.events )
+    }
+
+    /// Returns markers of the timeline and its players with time in [ from, to ), sorted by time.
+    pub fn markers_in( &self, from : f64, to : f64 ) -> Vec< Marker >
+    {
+      let mut markers : Vec< Marker > = self.markers.iter().filter( | marker | marker.time >= from && marker.time < to ).cloned().collect();
+      for entry in &self.entries
+      {
+        let nested = entry.player.markers_in( from - entry.offset, to - entry.offset );
+        markers.extend
+        (
+          nested.into_iter()
+          .map( | marker | Marker { time : marker.time + entry.offset, name : format!( "{}/{}", entry.name, marker.name ).into() } )
+        );
+      }
+      markers.sort_by( | lhs, rhs | lhs.time.total_cmp( &rhs.time ) );
+      markers
+    }
+
+    /// Whether loop section still has repeats to play
+    fn loop_active( &self ) -> Option< LoopSection >
+    {
+      self.loop_section
+      .filter( | section | section.repeat_count == -1 || self.current_repeat < section.repeat_count )
+    }
+
+    fn players_seek( &mut self )
+    {
+      for entry in &mut self.entries
+      {
+        entry.player.seek( self.time - entry.offset );
+      }
+    }
+  }
+
+  impl Default for Timeline
+  {
+    fn default() -> Self
+    {
+      Self::new()
+    }
+  }
+
+  impl AnimatablePlayer for Timeline
+  {
+    fn update( &mut self, delta_time : f64 )
+    {
+      match self.state
+      {
+        AnimationState::Paused | AnimationState::Completed => return,
+        AnimationState::Pending => self.state = AnimationState::Running,
+        AnimationState::Running => {}
+      }
+
+      let duration = self.duration_get();
+      let mut remaining = delta_time.max( 0.0 );
+      loop
+      {
+        let target = self.time + remaining;
+        let section = self.loop_active().filter( | section | self.time < section.end );
+        let limit = section.map_or( duration, | section | section.end.min( duration ) );
+
+        if target < limit
+        {
+          let crossed = self.markers_in( self.time, target );
+          self.events.extend( crossed );
+          self.time = target;
+          break;
+        }
+
+        match section
+        {
+          Some( section ) if limit > section.start =>
+          {
+            let crossed = self.markers_in( self.time, limit );
+            self.events.extend( crossed );
+            remaining = target - limit;
+            self.time = section.start;
+            self.current_repeat += 1;
+          }
+          _ =>
+          {
+            // Markers at the very end are reached too
+            let crossed = self.markers_in( self.time, f64::INFINITY );
+            self.events.extend( crossed );
+            self.time = duration;
+            self.state = AnimationState::Completed;
+            break;
+          }
+        }
+      }
+
+      self.players_seek();
+    }
+
+    fn is_completed( &self ) -> bool
+    {
+      self.state == AnimationState::Completed
+    }
+
+    fn pause( &mut self )
+    {
+      if self.state == AnimationState::Running
+      {
+        self.state = AnimationState::Paused;
+      }
+    }
+
+    fn resume( &mut self )
+    {
+      if self.state == AnimationState::Paused
+      {
+        self.state = AnimationState::Running;
+      }
+    }
+
+    fn reset( &mut self )
+    {
+      self.time = 0.0;
+      self.current_repeat = 0;
+      self.events.clear();
+      self.state = AnimationState::Running;
+      for entry in &mut self.entries
+      {
+        entry.player.reset();
+      }
+      self.players_seek();
+    }
+
+    fn as_any( &self ) -> &dyn core::any::Any
+    {
+      self
+    }
+
+    fn as_any_mut( &mut self ) -> &mut dyn core::any::Any
+    {
+      self
+    }
+
+    /// Time when the last player or marker ends, loop repeats aren't counted
+    fn duration_get( &self ) -> f64
+    {
+      let players = self.entries.iter().map( | entry | entry.offset + entry.player.duration_get() );
+      let markers = self.markers.last().map( | marker | marker.time );
+      players.chain( markers ).fold( 0.0, f64::max )
+    }
+
+    fn delay_get( &self ) -> f64
+    {
+      0.0
+    }
+
+    fn progress( &self ) -> f64
+    {
+      let duration = self.duration_get();
+      if duration > 0.0 { ( self.time / duration ).clamp( 0.0, 1.0 ) } else { 1.0 }
+    }
+  }
+
+  impl Seekable for Timeline
+  {
+    fn seek( &mut self, time : f64 )
+    {
+      self.seek( time );
+    }
+
+    fn time_get( &self ) -> f64
+    {
+      self.time
+    }
+
+    fn markers_in( &self, from : f64, to : f64 ) -> Vec< Marker >
+    {
+      self.markers_in( from, to )
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use
+  {
+    LoopSection,
+    Timeline
+  };
+}
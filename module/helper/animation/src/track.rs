@@ -0,0 +1,345 @@
+//! Keyframe tracks of animatable values.
+//!
+//! [`Track`] holds any number of keyframes, and every keyframe has its own easing
+//! for the segment that goes to the next keyframe. Tracks can be sampled at any
+//! time without changing them, and markers placed on them are reported when
+//! playback crosses them.
+
+mod private
+{
+  use crate::traits::{ Animatable, AnimatablePlayer, Seekable };
+  use crate::easing::base::EasingFunction;
+  use crate::AnimationState;
+
+  /// Named point in time reported when playback crosses it.
+  #[ non_exhaustive ]
+  #[ derive( Debug, Clone, PartialEq ) ]
+  pub struct Marker
+  {
+    /// Time of the marker in seconds
+    pub time : f64,
+    /// Marker name
+    pub name : Box< str >,
+  }
+
+  impl Marker
+  {
+    /// Creates a new marker.
+    pub fn new( time : f64, name : &str ) -> Self
+    {
+      Self { time, name : name.into() }
+    }
+  }
+
+  /// Value at a moment of [`Track`].
+  #[ non_exhaustive ]
+  #[ derive( Debug ) ]
+  pub struct Keyframe< T >
+  {
+    /// Time of the keyframe in seconds
+    pub time : f64,
+    /// Value at the keyframe
+    pub value : T,
+    /// Easing of the segment from this keyframe to the next one
+    pub easing : Box< dyn EasingFunction< AnimatableType = T > >,
+  }
+
+  impl< T > Clone for Keyframe< T >
+  where T : Animatable + 'static
+  {
+    fn clone( &self ) -> Self
+    {
+      Self
+      {
+        time : self.time,
+        value : self.value.clone(),
+        easing : clone_dyn_types::clone_into_box( &*self.easing ),
+      }
+    }
+  }
+
+  /// Animation of one value through a sequence of keyframes.
+  #[ derive( Debug ) ]
+  pub struct Track< T >
+  {
+    /// Keyframes sorted by time
+    keyframes : Vec< Keyframe< T > >,
+    /// Markers sorted by time
+    markers : Vec< Marker >,
+    /// Playhead time
+    time : f64,
+    /// Current animation state
+    state : AnimationState,
+    /// Markers crossed by playback and not taken yet
+    events : Vec< Marker >,
+  }
+
+  impl< T > Clone for Track< T >
+  where T : Animatable + 'static
+  {
+    fn clone( &self ) -> Self
+    {
+      Self
+      {
+        keyframes : self.keyframes.clone(),
+        markers : self.markers.clone(),
+        time : self.time,
+        state : self.state,
+        events : self.events.clone(),
+      }
+    }
+  }
+
+  impl< T > Track< T >
+  where T : Animatable + 'static
+  {
+    /// Creates a track without keyframes.
+    pub fn new() -> Self
+    {
+      Self
+      {
+        keyframes : Vec::new(),
+        markers : Vec::new(),
+        time : 0.0,
+        state : AnimationState::Pending,
+        events : Vec::new(),
+      }
+    }
+
+    /// Adds a keyframe, see [`Self::keyframe_insert`].
+    pub fn with_keyframe( mut self, time : f64, value : T, easing : Box< dyn EasingFunction< AnimatableType = T > > ) -> Self
+    {
+      self.keyframe_insert( time, value, easing );
+      self
+    }
+
+    /// Adds a marker, see [`Self::marker_insert`].
+    pub fn with_marker( mut self, time : f64, name : &str ) -> Self
+    {
+      self.marker_insert( time, name );
+      self
+    }
+
+    /// Inserts a keyframe keeping keyframes sorted by time.
+    /// Keyframe at the same time is replaced. `easing` is used for the segment to the next keyframe.
+    pub fn keyframe_insert( &mut self, time : f64, value : T, easing : Box< dyn EasingFunction< AnimatableType = T > > )
+    {
+      let time = time.max( 0.0 );
+      let keyframe = Keyframe { time, value, easing };
+      let index = self.keyframes.partition_point( | keyframe | keyframe.time < time );
+      match self.keyframes.get_mut( index )
+      {
+        Some( existing ) if ( existing.time - time ).abs() < f64::EPSILON => *existing = keyframe,
+        _ => self.keyframes.insert( index, keyframe ),
+      }
+    }
+
+    /// Inserts a marker keeping markers sorted by time.
+    pub fn marker_insert( &mut self, time : f64, name : &str )
+    {
+      let time = time.max( 0.0 );
+      let index = self.markers.partition_point( | marker | marker.time <= time );
+      self.markers.insert( index, Marker::new( time, name ) );
+    }
+
+    /// Returns keyframes sorted by time.
+    pub fn keyframes( &self ) -> &[ Keyframe< T > ]
+    {
+      &self.keyframes
+    }
+
+    /// Returns markers sorted by time.
+    pub fn markers( &self ) -> &[ Marker ]
+    {
+      &self.markers
+    }
+
+    /// Returns value at `time` without changing the track.
+    /// Before the first keyframe and after the last one the value holds still.
+    /// Returns `None` if the track has no keyframes.
+    pub fn sample( &self, time : f64 ) -> Option< T >
+    {
+      let next = self.keyframes.partition_point( | keyframe | keyframe.time <= time );
+      if next == 0
+      {
+        return self.keyframes.first().map( | keyframe | keyframe.value.clone() );
+      }
+      let from = &self.keyframes[ next - 1 ];
+      let Some( to ) = self.keyframes.get( next )
+      else
+      {
+        return Some( from.value.clone() );
+      };
+
+      let local = ( time - from.time ) / ( to.time - from.time );
+      Some( from.easing.apply( from.value.clone(), to.value.clone(), local ) )
+    }
+
+    /// Returns value at playhead.
+    pub fn value_get( &self ) -> Option< T >
+    {
+      self.sample( self.time )
+    }
+
+    /// Moves playhead to `time` without firing markers.
+    pub fn seek( &mut self, time : f64 )
+    {
+      let duration = self.duration_get();
+      self.time = time.clamp( 0.0, duration );
+      if self.state != AnimationState::Paused
+      {
+        self.state = if self.time >= duration { AnimationState::Completed } else { AnimationState::Running };
+      }
+    }
+
+    /// Returns playhead time.
+    pub fn time( &self ) -> f64
+    {
+      self.time
+    }
+
+    /// Gets the current animation state.
+    pub fn state( &self ) -> AnimationState
+    {
+      self.state
+    }
+
+    /// Takes markers crossed by [`AnimatablePlayer::update`] since the last call, in time order.
+    pub fn events_take( &mut self ) -> Vec< Marker >
+    {
+      core::mem::take( &mut self.events )
+    }
+
+    /// Returns markers with time in [ from, to ).
+    pub fn markers_in( &self, from : f64, to : f64 ) -> Vec< Marker >
+    {
+      self.markers.iter().filter( | marker | marker.time >= from && marker.time < to ).cloned().collect()
+    }
+  }
+
+  impl< T > Default for Track< T >
+  where T : Animatable + 'static
+  {
+    fn default() -> Self
+    {
+      Self::new()
+    }
+  }
+
+  impl< T > AnimatablePlayer for Track< T >
+  where T : Animatable + 'static
+  {
+    fn update( &mut self, delta_time : f64 )
+    {
+      match self.state
+      {
+        AnimationState::Paused | AnimationState::Completed => return,
+        AnimationState::Pending => self.state = AnimationState::Running,
+        AnimationState::Running => {}
+      }
+
+      let duration = self.duration_get();
+      let target = self.time + delta_time.max( 0.0 );
+      if target >= duration
+      {
+        // Markers at the very end are reached too
+        let crossed = self.markers_in( self.time, f64::INFINITY );
+        self.events.extend( crossed );
+        self.time = duration;
+        self.state = AnimationState::Completed;
+      }
+      else
+      {
+        let crossed = self.markers_in( self.time, target );
+        self.events.extend( crossed );
+        self.time = target;
+      }
+    }
+
+    fn is_completed( &self ) -> bool
+    {
+      self.state == AnimationState::Completed
+    }
+
+    fn pause( &mut self )
+    {
+      if self.state == AnimationState::Running
+      {
+        self.state = AnimationState::Paused;
+      }
+    }
+
+    fn resume( &mut self )
+    {
+      if self.state == AnimationState::Paused
+      {
+        self.state = AnimationState::Running;
+      }
+    }
+
+    fn reset( &mut self )
+    {
+      self.time = 0.0;
+      self.events.clear();
+      self.state = AnimationState::Running;
+    }
+
+    fn as_any( &self ) -> &dyn core::any::Any
+    {
+      self
+    }
+
+    fn as_any_mut( &mut self ) -> &mut dyn core::any::Any
+    {
+      self
+    }
+
+    /// Time of the last keyframe or marker
+    fn duration_get( &self ) -> f64
+    {
+      let keyframes = self.keyframes.last().map_or( 0.0, | keyframe | keyframe.time );
+      let markers = self.markers.last().map_or( 0.0, | marker | marker.time );
+      keyframes.max( markers )
+    }
+
+    fn delay_get( &self ) -> f64
+    {
+      0.0
+    }
+
+    fn progress( &self ) -> f64
+    {
+      let duration = self.duration_get();
+      if duration > 0.0 { ( self.time / duration ).clamp( 0.0, 1.0 ) } else { 1.0 }
+    }
+  }
+
+  impl< T > Seekable for Track< T >
+  where T : Animatable + 'static
+  {
+    fn seek( &mut self, time : f64 )
+    {
+      self.seek( time );
+    }
+
+    fn time_get( &self ) -> f64
+    {
+      self.time
+    }
+
+    fn markers_in( &self, from : f64, to : f64 ) -> Vec< Marker >
+    {
+      self.markers_in( from, to )
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use
+  {
+    Marker,
+    Keyframe,
+    Track
+  };
+}
@@ -7,6 +7,24 @@ mod private
     fn interpolate( &self, other : &Self, t : f64 ) -> Self;
   }
 
+  /// Trait for animatable values that can be added and scaled, so they can have a velocity.
+  /// Used by physics-based players such as [`crate::Spring`].
+  pub trait Kinematic : Animatable
+  {
+    /// Returns sum of `self` and `other`.
+    fn add( &self, other : &Self ) -> Self;
+    /// Returns `self` multiplied by `factor`.
+    fn scale( &self, factor : f64 ) -> Self;
+    /// Returns euclidean length of the value.
+    fn magnitude( &self ) -> f64;
+
+    /// Returns `self` minus `other`.
+    fn sub( &self, other : &Self ) -> Self
+    {
+      self.add( &other.scale( -1.0 ) )
+    }
+  }
+
   /// Trait for type-erased animatable values in Sequencer.
   pub trait AnimatablePlayer : core::fmt::Debug + clone_dyn_types::CloneDyn
   {
@@ -31,6 +49,17 @@ mod private
     /// Gets the progress of the animated value ( 0.0 to 1.0 ).
     fn progress( &self ) -> f64;
   }
+
+  /// Trait for players that can jump to any time moment, so they can be placed on [`crate::Timeline`].
+  pub trait Seekable : AnimatablePlayer
+  {
+    /// Moves playhead to `time` without firing markers.
+    fn seek( &mut self, time : f64 );
+    /// Returns playhead time.
+    fn time_get( &self ) -> f64;
+    /// Returns markers with time in [ from, to ) sorted by time.
+    fn markers_in( &self, from : f64, to : f64 ) -> Vec< crate::Marker >;
+  }
 }
 
 crate::mod_interface!
@@ -38,6 +67,8 @@ crate::mod_interface!
   orphan use
   {
     Animatable,
-    AnimatablePlayer
+    AnimatablePlayer,
+    Kinematic,
+    Seekable
   };
 }
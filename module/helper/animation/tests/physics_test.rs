@@ -0,0 +1,176 @@
+//! Integration tests related to physics-based players
+
+#![ allow( clippy::float_cmp ) ]
+
+#[ cfg( test ) ]
+mod tests
+{
+  use animation::
+  {
+    Spring,
+    Decay,
+    Retarget,
+    Sequencer,
+    AnimatablePlayer,
+    AnimationState
+  };
+
+  const FRAME : f64 = 1.0 / 60.0;
+
+  #[ test ]
+  fn test_spring_critically_damped()
+  {
+    let mut spring = Spring::new( 0.0_f64, 10.0 );
+    assert_eq!( spring.state(), AnimationState::Pending );
+
+    let mut previous = spring.value_get();
+    for _ in 0..600
+    {
+      spring.update( FRAME );
+      let value = spring.value_get();
+      assert!( value >= previous && value <= 10.0 );
+      previous = value;
+    }
+
+    assert!( spring.is_completed() );
+    assert_eq!( spring.value_get(), 10.0 );
+    assert_eq!( spring.velocity_get(), 0.0 );
+  }
+
+  #[ test ]
+  fn test_spring_frame_rate_independent()
+  {
+    let mut coarse = Spring::new( 0.0_f64, 10.0 ).with_damping_ratio( 0.5 );
+    let mut fine = coarse.clone();
+
+    coarse.update( 0.25 );
+    for _ in 0..15
+    {
+      fine.update( FRAME );
+    }
+
+    assert!( ( coarse.value_get() - fine.value_get() ).abs() < 1e-9 );
+    assert!( ( coarse.velocity_get() - fine.velocity_get() ).abs() < 1e-9 );
+  }
+
+  #[ test ]
+  fn test_spring_underdamped_overshoots()
+  {
+    let mut spring = Spring::new( 0.0_f64, 1.0 ).with_damping_ratio( 0.2 );
+    let mut max = 0.0_f64;
+    while !spring.is_completed()
+    {
+      spring.update( FRAME );
+      max = max.max( spring.value_get() );
+    }
+
+    assert!( max > 1.0 );
+    assert_eq!( spring.value_get(), 1.0 );
+  }
+
+  #[ test ]
+  fn test_spring_retarget()
+  {
+    let mut spring = Spring::new( ( 0.0_f32, 0.0_f32 ), ( 10.0, 0.0 ) ).with_stiffness( 200.0 );
+    for _ in 0..10
+    {
+      spring.update( FRAME );
+    }
+
+    let value = spring.value_get();
+    let velocity = spring.velocity_get();
+    spring.retarget( ( 10.0, 10.0 ) );
+    assert_eq!( spring.value_get(), value );
+    assert_eq!( spring.velocity_get(), velocity );
+
+    while !spring.is_completed()
+    {
+      spring.update( FRAME );
+    }
+    assert_eq!( spring.value_get(), ( 10.0, 10.0 ) );
+
+    spring.retarget( ( 0.0, 0.0 ) );
+    assert_eq!( spring.state(), AnimationState::Running );
+  }
+
+  #[ test ]
+  fn test_spring_duration_estimate()
+  {
+    let mut spring = Spring::new( 0.0_f64, 10.0 );
+    let estimate = spring.duration_get();
+    assert!( estimate > 0.0 );
+
+    let mut elapsed = 0.0;
+    while !spring.is_completed()
+    {
+      spring.update( FRAME );
+      elapsed += FRAME;
+    }
+
+    assert!( elapsed <= estimate + FRAME );
+    assert_eq!( spring.progress(), 1.0 );
+  }
+
+  #[ test ]
+  fn test_decay()
+  {
+    let mut decay = Decay::new( 0.0_f64, 100.0 ).with_time_constant( 0.5 ).with_rest_speed( 0.1 );
+    let target = decay.target_get();
+    assert!( ( target - 50.0 ).abs() < 1e-9 );
+
+    let expected = decay.duration_get();
+    let mut elapsed = 0.0;
+    while !decay.is_completed()
+    {
+      decay.update( FRAME );
+      elapsed += FRAME;
+    }
+
+    assert!( ( decay.value_get() - target ).abs() < 1e-9 );
+    assert!( ( elapsed - expected ).abs() <= FRAME );
+
+    decay.reset();
+    assert_eq!( decay.value_get(), 0.0 );
+    assert_eq!( decay.velocity_get(), 100.0 );
+  }
+
+  #[ test ]
+  fn test_retarget_player()
+  {
+    let mut player = Retarget::new( 0.0_f64, 10.0, 1.0 );
+
+    player.update( 0.5 );
+    assert!( ( player.value_get() - 5.0 ).abs() < 1e-9 );
+
+    let value = player.value_get();
+    let velocity = player.velocity_get();
+    player.retarget( -10.0 );
+    assert!( ( player.value_get() - value ).abs() < 1e-9 );
+    assert!( ( player.velocity_get() - velocity ).abs() < 1e-9 );
+    assert_eq!( player.time(), 0.0 );
+
+    player.update( 2.0 );
+    assert!( player.is_completed() );
+    assert_eq!( player.value_get(), -10.0 );
+    assert_eq!( player.velocity_get(), 0.0 );
+  }
+
+  #[ test ]
+  fn test_physics_in_sequencer()
+  {
+    let mut sequencer = Sequencer::new();
+    sequencer.insert( "spring", Spring::new( 0.0_f32, 1.0 ) );
+    sequencer.insert( "decay", Decay::new( 0.0_f32, 1.0 ) );
+
+    for _ in 0..600
+    {
+      sequencer.update( FRAME );
+    }
+
+    assert!( sequencer.is_completed() );
+    assert_eq!( sequencer.get::< Spring< f32 > >( "spring" ).unwrap().value_get(), 1.0 );
+
+    sequencer.get_mut::< Spring< f32 > >( "spring" ).unwrap().retarget( 2.0 );
+    assert!( !sequencer.get::< Spring< f32 > >( "spring" ).unwrap().is_completed() );
+  }
+}
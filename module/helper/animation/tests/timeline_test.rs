@@ -0,0 +1,171 @@
+//! Integration tests related to Timeline struct
+
+#![ allow( clippy::float_cmp ) ]
+
+#[ cfg( test ) ]
+mod tests
+{
+  use animation::
+  {
+    Track,
+    Timeline,
+    Marker,
+    AnimatablePlayer,
+    AnimationState,
+    easing::
+    {
+      base::EasingBuilder,
+      Linear
+    }
+  };
+
+  fn ramp( name : &str ) -> Track< f32 >
+  {
+    Track::new()
+    .with_keyframe( 0.0, 0.0_f32, Linear::new() )
+    .with_keyframe( 1.0, 10.0_f32, Linear::new() )
+    .with_marker( 0.5, name )
+  }
+
+  fn value( timeline : &Timeline, name : &str ) -> f32
+  {
+    timeline.get::< Track< f32 > >( name ).unwrap().value_get().unwrap()
+  }
+
+  #[ test ]
+  fn test_timeline_parallel_and_serial()
+  {
+    let mut timeline = Timeline::new();
+    assert_eq!( timeline.state(), AnimationState::Pending );
+
+    timeline.insert( "a", 0.0, ramp( "a" ) );
+    timeline.insert( "b", 0.5, ramp( "b" ) );
+    timeline.append( "c", ramp( "c" ) );
+
+    assert_eq!( timeline.state(), AnimationState::Running );
+    assert_eq!( timeline.offset_get( "c" ), Some( 1.5 ) );
+    assert_eq!( timeline.duration_get(), 2.5 );
+    assert_eq!( timeline.keys(), vec![ "a".into(), "b".into(), "c".into() ] );
+
+    timeline.update( 1.0 );
+    assert_eq!( value( &timeline, "a" ), 10.0 );
+    assert_eq!( value( &timeline, "b" ), 5.0 );
+    assert_eq!( value( &timeline, "c" ), 0.0 );
+
+    timeline.update( 1.0 );
+    assert_eq!( value( &timeline, "c" ), 5.0 );
+
+    timeline.update( 1.0 );
+    assert!( timeline.is_completed() );
+    assert_eq!( timeline.time(), 2.5 );
+    assert_eq!( value( &timeline, "c" ), 10.0 );
+  }
+
+  #[ test ]
+  fn test_timeline_seek()
+  {
+    let mut timeline = Timeline::new();
+    timeline.insert( "a", 0.0, ramp( "a" ) );
+    timeline.append( "b", ramp( "b" ) );
+
+    timeline.seek( 1.25 );
+    assert_eq!( value( &timeline, "a" ), 10.0 );
+    assert_eq!( value( &timeline, "b" ), 2.5 );
+    assert!( timeline.events_take().is_empty() );
+
+    timeline.seek( 0.5 );
+    assert_eq!( value( &timeline, "a" ), 5.0 );
+    assert_eq!( value( &timeline, "b" ), 0.0 );
+    assert_eq!( timeline.state(), AnimationState::Running );
+  }
+
+  #[ test ]
+  fn test_timeline_markers()
+  {
+    let mut timeline = Timeline::new().with_marker( 0.25, "start" );
+    timeline.insert( "a", 0.0, ramp( "hit" ) );
+    timeline.insert( "b", 1.0, ramp( "hit" ) );
+
+    timeline.update( 0.75 );
+    assert_eq!
+    (
+      timeline.events_take(),
+      vec![ Marker::new( 0.25, "start" ), Marker::new( 0.5, "a/hit" ) ]
+    );
+
+    timeline.update( 5.0 );
+    assert_eq!( timeline.events_take(), vec![ Marker::new( 1.5, "b/hit" ) ] );
+  }
+
+  #[ test ]
+  fn test_timeline_nested()
+  {
+    let mut inner = Timeline::new();
+    inner.insert( "a", 0.0, ramp( "hit" ) );
+    inner.append( "b", ramp( "hit" ) );
+
+    let mut outer = Timeline::new();
+    outer.insert( "inner", 1.0, inner );
+    assert_eq!( outer.duration_get(), 3.0 );
+
+    outer.update( 2.75 );
+    assert_eq!
+    (
+      outer.events_take(),
+      vec![ Marker::new( 1.5, "inner/a/hit" ), Marker::new( 2.5, "inner/b/hit" ) ]
+    );
+
+    let inner = outer.get::< Timeline >( "inner" ).unwrap();
+    assert_eq!( inner.time(), 1.75 );
+    assert_eq!( inner.get::< Track< f32 > >( "b" ).unwrap().value_get(), Some( 7.5 ) );
+
+    outer.seek( 1.5 );
+    let inner = outer.get::< Timeline >( "inner" ).unwrap();
+    assert_eq!( inner.get::< Track< f32 > >( "a" ).unwrap().value_get(), Some( 5.0 ) );
+  }
+
+  #[ test ]
+  fn test_timeline_loop()
+  {
+    let mut timeline = Timeline::new().with_loop( 0.0, 1.0, 2 );
+    timeline.insert( "a", 0.0, ramp( "hit" ) );
+    timeline.append( "b", ramp( "hit" ) );
+
+    timeline.update( 1.25 );
+    assert_eq!( timeline.time(), 0.25 );
+    assert_eq!( timeline.current_repeat(), 1 );
+    assert_eq!( value( &timeline, "a" ), 2.5 );
+
+    timeline.update( 1.0 );
+    assert_eq!( timeline.time(), 0.25 );
+    assert_eq!( timeline.current_repeat(), 2 );
+    assert_eq!( timeline.events_take().len(), 2 );
+
+    timeline.update( 1.0 );
+    assert_eq!( timeline.time(), 1.25 );
+    assert_eq!( value( &timeline, "b" ), 2.5 );
+
+    timeline.update( 1.0 );
+    assert!( timeline.is_completed() );
+    assert_eq!( timeline.events_take().len(), 2 );
+
+    timeline.reset();
+    assert_eq!( timeline.time(), 0.0 );
+    assert_eq!( timeline.current_repeat(), 0 );
+    assert_eq!( value( &timeline, "b" ), 0.0 );
+  }
+
+  #[ test ]
+  fn test_timeline_infinite_loop()
+  {
+    let mut timeline = Timeline::new().with_loop( 0.5, 1.0, -1 );
+    timeline.insert( "a", 0.0, ramp( "hit" ) );
+
+    for _ in 0..100
+    {
+      timeline.update( 0.3 );
+    }
+    assert!( !timeline.is_completed() );
+    assert!( timeline.time() >= 0.5 && timeline.time() < 1.0 );
+  }
+}
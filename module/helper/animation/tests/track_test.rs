@@ -0,0 +1,118 @@
+//! Integration tests related to Track struct
+
+#![ allow( clippy::float_cmp ) ]
+
+#[ cfg( test ) ]
+mod tests
+{
+  use animation::
+  {
+    Track,
+    Marker,
+    AnimatablePlayer,
+    AnimationState,
+    easing::
+    {
+      base::EasingBuilder,
+      Linear
+    }
+  };
+
+  fn track() -> Track< f32 >
+  {
+    Track::new()
+    .with_keyframe( 0.0, 0.0_f32, Linear::new() )
+    .with_keyframe( 1.0, 10.0_f32, Linear::new() )
+    .with_keyframe( 3.0, 0.0_f32, Linear::new() )
+  }
+
+  #[ test ]
+  fn test_track_sample()
+  {
+    let track = track();
+
+    assert_eq!( track.sample( -1.0 ), Some( 0.0 ) );
+    assert_eq!( track.sample( 0.5 ), Some( 5.0 ) );
+    assert_eq!( track.sample( 1.0 ), Some( 10.0 ) );
+    assert_eq!( track.sample( 2.0 ), Some( 5.0 ) );
+    assert_eq!( track.sample( 4.0 ), Some( 0.0 ) );
+    assert_eq!( track.duration_get(), 3.0 );
+    assert_eq!( track.time(), 0.0 );
+
+    assert_eq!( Track::< f32 >::new().sample( 1.0 ), None );
+  }
+
+  #[ test ]
+  fn test_track_keyframe_order()
+  {
+    let track = Track::new()
+    .with_keyframe( 2.0, 20.0_f32, Linear::new() )
+    .with_keyframe( 0.0, 0.0_f32, Linear::new() )
+    .with_keyframe( 1.0, 5.0_f32, Linear::new() )
+    .with_keyframe( 1.0, 10.0_f32, Linear::new() );
+
+    let times = track.keyframes().iter().map( | keyframe | keyframe.time ).collect::< Vec< _ > >();
+    assert_eq!( times, vec![ 0.0, 1.0, 2.0 ] );
+    assert_eq!( track.sample( 1.0 ), Some( 10.0 ) );
+  }
+
+  #[ test ]
+  fn test_track_update_and_markers()
+  {
+    let mut track = track()
+    .with_marker( 0.5, "half" )
+    .with_marker( 1.0, "peak" )
+    .with_marker( 3.0, "end" );
+
+    track.update( 0.75 );
+    assert_eq!( track.value_get(), Some( 7.5 ) );
+    assert_eq!( track.events_take(), vec![ Marker::new( 0.5, "half" ) ] );
+    assert!( track.events_take().is_empty() );
+
+    track.update( 0.25 );
+    assert!( track.events_take().is_empty() );
+
+    track.update( 5.0 );
+    assert!( track.is_completed() );
+    assert_eq!( track.time(), 3.0 );
+    assert_eq!( track.events_take(), vec![ Marker::new( 1.0, "peak" ), Marker::new( 3.0, "end" ) ] );
+  }
+
+  #[ test ]
+  fn test_track_seek()
+  {
+    let mut track = track().with_marker( 0.5, "half" );
+
+    track.seek( 2.0 );
+    assert_eq!( track.value_get(), Some( 5.0 ) );
+    assert_eq!( track.state(), AnimationState::Running );
+    assert!( track.events_take().is_empty() );
+
+    track.seek( 0.25 );
+    assert_eq!( track.value_get(), Some( 2.5 ) );
+
+    track.seek( 10.0 );
+    assert_eq!( track.time(), 3.0 );
+    assert!( track.is_completed() );
+
+    track.reset();
+    assert_eq!( track.time(), 0.0 );
+    assert_eq!( track.state(), AnimationState::Running );
+  }
+
+  #[ test ]
+  fn test_track_pause()
+  {
+    let mut track = track();
+
+    track.update( 0.5 );
+    track.pause();
+    track.update( 0.5 );
+    assert_eq!( track.time(), 0.5 );
+    assert_eq!( track.state(), AnimationState::Paused );
+
+    track.resume();
+    track.update( 0.5 );
+    assert_eq!( track.time(), 1.0 );
+  }
+}
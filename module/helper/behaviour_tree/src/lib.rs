@@ -15,8 +15,8 @@
 //!
 //! - **Action Nodes**: Perform specific actions (move, attack, patrol)
 //! - **Condition Nodes**: Check game state conditions (health low, enemy near)
-//! - **Composite Nodes**: Control execution flow (sequence, selector, parallel)
-//! - **Decorator Nodes**: Modify child behavior (repeat, invert, cooldown)
+//! - **Composite Nodes**: Control execution flow (sequence, selector, random selector, parallel)
+//! - **Decorator Nodes**: Modify child behavior (repeat, invert, cooldown, conditional, timeout, retry)
 //!
 //! ## Execution Flow
 //!
@@ -49,11 +49,22 @@
 //! let mut context = BehaviorContext::new();
 //! let status = patrol_tree.execute( &mut context );
 //! ```
+//!
+//! # Debugging
+//!
+//! [`BehaviorTree::execute_traced`] records node statuses and blackboard accesses of a tick into
+//! [`Tracer`], which can export them as JSON or render the tree as Graphviz DOT.
+//!
+//! # Data-driven trees
+//!
+//! With `ron` or `json` feature trees can be loaded from descriptions, so they are changed
+//! without recompiling. Custom nodes are registered in [`NodeRegistry`] under a name.
 
 #![ allow( clippy::doc_markdown ) ]
 #![ allow( clippy::exhaustive_enums ) ]
 #![ allow( clippy::exhaustive_structs ) ]
 #![ allow( clippy::std_instead_of_core ) ]
+#![ allow( clippy::std_instead_of_alloc ) ]
 #![ allow( clippy::must_use_candidate ) ]
 #![ allow( clippy::missing_inline_in_public_items ) ]
 #![ allow( clippy::implicit_return ) ]
@@ -65,6 +76,16 @@
 use std::collections::HashMap;
 use core::time::Duration;
 use std::time::Instant;
+use core::cell::RefCell;
+use core::hash::BuildHasher;
+
+mod trace;
+pub use trace::*;
+
+#[ cfg( feature = "serde" ) ]
+mod registry;
+#[ cfg( feature = "serde" ) ]
+pub use registry::*;
 
 /// Status returned by behavior tree nodes during execution.
 #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
@@ -94,6 +115,8 @@ pub struct BehaviorContext
   pub blackboard : HashMap< String, BehaviorValue >,
   /// Custom properties for game-specific data
   pub properties : HashMap< String, BehaviorValue >,
+  /// Records of the tick being traced, blackboard reads are recorded through shared reference
+  trace : RefCell< Option< TraceRecorder > >,
 }
 
 impl BehaviorContext
@@ -110,6 +133,7 @@ impl BehaviorContext
       delta_time : Duration::from_secs_f32( 1.0 / 60.0 ), // Default 60 FPS
       blackboard : HashMap::new(),
       properties : HashMap::new(),
+      trace : RefCell::new( None ),
     }
   }
 
@@ -135,7 +159,12 @@ impl BehaviorContext
   #[ inline ]
   pub fn set_blackboard< T : Into< BehaviorValue > >( &mut self, key : &str, value : T )
   {
-    self.blackboard.insert( key.to_string(), value.into() );
+    let value = value.into();
+    if let Some( recorder ) = self.trace.get_mut()
+    {
+      recorder.access( BlackboardAccessKind::Write, key, Some( value.clone() ) );
+    }
+    self.blackboard.insert( key.to_string(), value );
   }
 
   /// Gets a value from the blackboard.
@@ -143,7 +172,49 @@ impl BehaviorContext
   #[ must_use ]
   pub fn get_blackboard( &self, key : &str ) -> Option< &BehaviorValue >
   {
-    self.blackboard.get( key )
+    let value = self.blackboard.get( key );
+    if let Some( recorder ) = self.trace.borrow_mut().as_mut()
+    {
+      recorder.access( BlackboardAccessKind::Read, key, value.cloned() );
+    }
+    value
+  }
+
+  /// Executes child at `index` of the currently executing node.
+  /// Composite and decorator nodes run their children through this, so the children appear in traces.
+  #[ inline ]
+  pub fn tick_child( &mut self, index : usize, child : &mut dyn BehaviorNode ) -> BehaviorStatus
+  {
+    self.tick( Some( index ), child )
+  }
+
+  /// Re-checks condition of child at `index` of the currently executing node, see [`BehaviorNode::observe`].
+  /// Nodes the child ticks while observing appear in traces under the child.
+  #[ inline ]
+  pub fn observe_child( &mut self, index : usize, child : &mut dyn BehaviorNode ) -> Option< bool >
+  {
+    if let Some( recorder ) = self.trace.get_mut()
+    {
+      recorder.descend( index );
+    }
+    let holds = child.observe( self );
+    if let Some( recorder ) = self.trace.get_mut()
+    {
+      recorder.ascend();
+    }
+    holds
+  }
+
+  fn tick( &mut self, index : Option< usize >, node : &mut dyn BehaviorNode ) -> BehaviorStatus
+  {
+    let Some( recorder ) = self.trace.get_mut() else { return node.execute( self ); };
+    recorder.enter( index, node.name() );
+    let status = node.execute( self );
+    if let Some( recorder ) = self.trace.get_mut()
+    {
+      recorder.exit( status );
+    }
+    status
   }
 
   /// Sets a property value.
@@ -173,6 +244,7 @@ impl Default for BehaviorContext
 
 /// Flexible value type for blackboard and property storage.
 #[ derive( Debug, Clone, PartialEq ) ]
+#[ cfg_attr( feature = "serde", derive( serde::Serialize, serde::Deserialize ) ) ]
 #[ non_exhaustive ]
 pub enum BehaviorValue
 {
@@ -273,6 +345,23 @@ pub trait BehaviorNode : core::fmt::Debug
 
   /// Gets a human-readable name for this node (for debugging).
   fn name( &self ) -> &str;
+
+  /// Re-checks the condition of a node that aborts lower priority siblings.
+  /// Returns `None` for nodes that don't observe, otherwise whether the condition holds now.
+  /// Sequences and selectors call it through [`BehaviorContext::observe_child`] for completed children
+  /// while a later child is running.
+  #[ inline ]
+  fn observe( &mut self, _context : &mut BehaviorContext ) -> Option< bool >
+  {
+    None
+  }
+
+  /// Gets children of this node in the order of their indices (for debugging).
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    Vec::new()
+  }
 }
 
 /// Root behavior tree structure containing the tree hierarchy.
@@ -303,6 +392,24 @@ impl BehaviorTree
     self.root.execute( context )
   }
 
+  /// Executes the behavior tree recording the tick into `tracer`.
+  #[ inline ]
+  pub fn execute_traced( &mut self, context : &mut BehaviorContext, tracer : &mut Tracer ) -> BehaviorStatus
+  {
+    *context.trace.get_mut() = Some( TraceRecorder::default() );
+    let status = context.tick( None, self.root.as_mut() );
+    tracer.record( status, context.trace.get_mut().take().unwrap_or_default() );
+    status
+  }
+
+  /// Gets the root node of this behavior tree.
+  #[ inline ]
+  #[ must_use ]
+  pub fn root( &self ) -> &dyn BehaviorNode
+  {
+    self.root.as_ref()
+  }
+
   /// Resets the entire behavior tree to its initial state.
   #[ inline ]
   pub fn reset( &mut self )
@@ -322,6 +429,10 @@ impl BehaviorTree
 // === COMPOSITE NODES ===
 
 /// Executes children in sequence until one fails or all succeed.
+///
+/// While a child is running, completed children that observe lower priority are re-checked first.
+/// If the condition of one of them no longer holds, the running child is aborted and the sequence
+/// restarts from the observer.
 #[ derive( Debug ) ]
 pub struct SequenceNode
 {
@@ -359,9 +470,16 @@ impl BehaviorNode for SequenceNode
   #[ inline ]
   fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
   {
+    let aborted = ( 0..self.current_child ).find( | &index | context.observe_child( index, self.children[ index ].as_mut() ) == Some( false ) );
+    if let Some( index ) = aborted
+    {
+      abort_children( &mut self.children[ index..=self.current_child ] );
+      self.current_child = index;
+    }
+
     while self.current_child < self.children.len()
     {
-      match self.children[ self.current_child ].execute( context )
+      match context.tick_child( self.current_child, self.children[ self.current_child ].as_mut() )
       {
         BehaviorStatus::Success =>
         {
@@ -398,9 +516,19 @@ impl BehaviorNode for SequenceNode
   {
     &self.name
   }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    self.children.iter().map( AsRef::as_ref ).collect()
+  }
 }
 
 /// Executes children until one succeeds or all fail.
+///
+/// While a child is running, failed children that observe lower priority are re-checked first.
+/// If the condition of one of them holds now, the running child is aborted and the selector
+/// switches to the observer.
 #[ derive( Debug ) ]
 pub struct SelectorNode
 {
@@ -438,9 +566,16 @@ impl BehaviorNode for SelectorNode
   #[ inline ]
   fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
   {
+    let aborted = ( 0..self.current_child ).find( | &index | context.observe_child( index, self.children[ index ].as_mut() ) == Some( true ) );
+    if let Some( index ) = aborted
+    {
+      abort_children( &mut self.children[ index..=self.current_child ] );
+      self.current_child = index;
+    }
+
     while self.current_child < self.children.len()
     {
-      match self.children[ self.current_child ].execute( context )
+      match context.tick_child( self.current_child, self.children[ self.current_child ].as_mut() )
       {
         BehaviorStatus::Success =>
         {
@@ -477,78 +612,678 @@ impl BehaviorNode for SelectorNode
   {
     &self.name
   }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    self.children.iter().map( AsRef::as_ref ).collect()
+  }
+}
+
+/// Number of children that must reach a status for a parallel node to finish with it.
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+#[ non_exhaustive ]
+pub enum ParallelPolicy
+{
+  /// Any child is enough
+  RequireOne,
+  /// Every child is needed
+  RequireAll,
+}
+
+/// Executes all children in parallel until its success or failure policy is met.
+///
+/// Children are ticked in order, and each finished child keeps its status until the node
+/// finishes. Once a policy is met, still running children are aborted. If every child has
+/// finished but neither policy is met, the node fails.
+#[ derive( Debug ) ]
+pub struct ParallelNode
+{
+  children : Vec< Box< dyn BehaviorNode > >,
+  statuses : Vec< BehaviorStatus >,
+  success_policy : ParallelPolicy,
+  failure_policy : ParallelPolicy,
+  name : String,
+}
+
+impl ParallelNode
+{
+  /// Creates a new parallel node with the given children.
+  /// It succeeds when all children succeed and fails when any child fails.
+  #[ inline ]
+  #[ must_use ]
+  pub fn new( children : Vec< Box< dyn BehaviorNode > > ) -> Self
+  {
+    Self::named( children, "Parallel".to_string() )
+  }
+
+  /// Creates a named parallel node.
+  #[ inline ]
+  #[ must_use ]
+  pub fn named( children : Vec< Box< dyn BehaviorNode > >, name : String ) -> Self
+  {
+    Self
+    {
+      statuses : vec![ BehaviorStatus::Running; children.len() ],
+      children,
+      success_policy : ParallelPolicy::RequireAll,
+      failure_policy : ParallelPolicy::RequireOne,
+      name,
+    }
+  }
+
+  /// Creates a parallel node with the given success and failure policies.
+  #[ inline ]
+  #[ must_use ]
+  pub fn with_policies( children : Vec< Box< dyn BehaviorNode > >, success_policy : ParallelPolicy, failure_policy : ParallelPolicy ) -> Self
+  {
+    Self { success_policy, failure_policy, ..Self::new( children ) }
+  }
+
+  fn finish( &mut self, status : BehaviorStatus ) -> BehaviorStatus
+  {
+    self.reset();
+    status
+  }
+}
+
+impl BehaviorNode for ParallelNode
+{
+  #[ inline ]
+  fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
+  {
+    let count = self.children.len();
+    let mut success_count = 0;
+    let mut failure_count = 0;
+
+    for index in 0..count
+    {
+      if self.statuses[ index ] == BehaviorStatus::Running
+      {
+        self.statuses[ index ] = context.tick_child( index, self.children[ index ].as_mut() );
+      }
+      match self.statuses[ index ]
+      {
+        BehaviorStatus::Success => success_count += 1,
+        BehaviorStatus::Failure => failure_count += 1,
+        BehaviorStatus::Running => {}
+      }
+
+      let failed = match self.failure_policy
+      {
+        ParallelPolicy::RequireOne => failure_count > 0,
+        ParallelPolicy::RequireAll => failure_count == count,
+      };
+      if failed
+      {
+        return self.finish( BehaviorStatus::Failure );
+      }
+      let succeeded = match self.success_policy
+      {
+        ParallelPolicy::RequireOne => success_count > 0,
+        ParallelPolicy::RequireAll => success_count == count,
+      };
+      if succeeded
+      {
+        return self.finish( BehaviorStatus::Success );
+      }
+    }
+
+    if count == 0
+    {
+      BehaviorStatus::Success
+    }
+    else if success_count + failure_count == count
+    {
+      self.finish( BehaviorStatus::Failure )
+    }
+    else
+    {
+      BehaviorStatus::Running
+    }
+  }
+
+  #[ inline ]
+  fn reset( &mut self )
+  {
+    self.statuses.fill( BehaviorStatus::Running );
+    for child in &mut self.children
+    {
+      child.reset();
+    }
+  }
+
+  #[ inline ]
+  fn name( &self ) -> &str
+  {
+    &self.name
+  }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    self.children.iter().map( AsRef::as_ref ).collect()
+  }
+}
+
+/// Selector that tries children in random order, drawn by their weights when the node starts.
+/// Children with zero weight are never tried.
+#[ derive( Debug ) ]
+pub struct RandomSelectorNode
+{
+  children : Vec< Box< dyn BehaviorNode > >,
+  weights : Vec< f32 >,
+  order : Vec< usize >,
+  current : usize,
+  rng : u64,
+  name : String,
+}
+
+impl RandomSelectorNode
+{
+  /// Creates a random selector where every child is equally likely to be tried first.
+  #[ inline ]
+  #[ must_use ]
+  pub fn new( children : Vec< Box< dyn BehaviorNode > > ) -> Self
+  {
+    Self::weighted( children.into_iter().map( | child | ( child, 1.0 ) ).collect() )
+  }
+
+  /// Creates a random selector from children paired with their weights.
+  #[ inline ]
+  #[ must_use ]
+  pub fn weighted( children : Vec< ( Box< dyn BehaviorNode >, f32 ) > ) -> Self
+  {
+    let ( children, weights ) = children.into_iter().unzip();
+    Self
+    {
+      children,
+      weights,
+      order : Vec::new(),
+      current : 0,
+      rng : std::collections::hash_map::RandomState::new().hash_one( 0_u8 ),
+      name : "RandomSelector".to_string(),
+    }
+  }
+
+  /// Seeds random order, so it is the same on every run.
+  #[ inline ]
+  #[ must_use ]
+  pub fn with_seed( mut self, seed : u64 ) -> Self
+  {
+    self.rng = seed;
+    self
+  }
+
+  /// Returns random number in `[ 0, 1 )`
+  fn next_unit( &mut self ) -> f32
+  {
+    // SplitMix64
+    self.rng = self.rng.wrapping_add( 0x9E37_79B9_7F4A_7C15 );
+    let mut z = self.rng;
+    z = ( z ^ ( z >> 30 ) ).wrapping_mul( 0xBF58_476D_1CE4_E5B9 );
+    z = ( z ^ ( z >> 27 ) ).wrapping_mul( 0x94D0_49BB_1331_11EB );
+    z ^= z >> 31;
+    f32::from( u16::try_from( z >> 48 ).unwrap_or( 0 ) ) / 65536.0
+  }
+
+  fn draw_order( &mut self )
+  {
+    let mut remaining : Vec< usize > = ( 0..self.children.len() ).filter( | &index | self.weights[ index ] > 0.0 ).collect();
+    self.order.clear();
+    while !remaining.is_empty()
+    {
+      let total : f32 = remaining.iter().map( | &index | self.weights[ index ] ).sum();
+      let mut target = self.next_unit() * total;
+      let mut picked = remaining.len() - 1;
+      for ( position, &index ) in remaining.iter().enumerate()
+      {
+        if target < self.weights[ index ]
+        {
+          picked = position;
+          break;
+        }
+        target -= self.weights[ index ];
+      }
+      self.order.push( remaining.remove( picked ) );
+    }
+  }
+}
+
+impl BehaviorNode for RandomSelectorNode
+{
+  #[ inline ]
+  fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
+  {
+    if self.current == 0 && self.order.is_empty()
+    {
+      self.draw_order();
+    }
+
+    while let Some( &index ) = self.order.get( self.current )
+    {
+      match context.tick_child( index, self.children[ index ].as_mut() )
+      {
+        BehaviorStatus::Success =>
+        {
+          self.reset();
+          return BehaviorStatus::Success;
+        }
+        BehaviorStatus::Failure =>
+        {
+          self.current += 1;
+        }
+        BehaviorStatus::Running =>
+        {
+          return BehaviorStatus::Running;
+        }
+      }
+    }
+
+    self.reset();
+    BehaviorStatus::Failure
+  }
+
+  #[ inline ]
+  fn reset( &mut self )
+  {
+    self.current = 0;
+    self.order.clear();
+    for child in &mut self.children
+    {
+      child.reset();
+    }
+  }
+
+  #[ inline ]
+  fn name( &self ) -> &str
+  {
+    &self.name
+  }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    self.children.iter().map( AsRef::as_ref ).collect()
+  }
+}
+
+/// Resets children aborted by a composite, from the observer up to the running child
+fn abort_children( children : &mut [ Box< dyn BehaviorNode > ] )
+{
+  for child in children
+  {
+    child.reset();
+  }
+}
+
+// === DECORATOR NODES ===
+
+/// Repeats a child node a specified number of times or indefinitely.
+#[ derive( Debug ) ]
+pub struct RepeatNode
+{
+  child : Box< dyn BehaviorNode >,
+  max_repeats : Option< u32 >,
+  current_repeats : u32,
+  name : String,
+}
+
+impl RepeatNode
+{
+  /// Creates a repeat node that runs indefinitely.
+  #[ inline ]
+  #[ must_use ]
+  pub fn infinite( child : Box< dyn BehaviorNode > ) -> Self
+  {
+    Self
+    {
+      child,
+      max_repeats : None,
+      current_repeats : 0,
+      name : "Repeat(∞)".to_string(),
+    }
+  }
+
+  /// Creates a repeat node that runs a specific number of times.
+  #[ inline ]
+  #[ must_use ]
+  pub fn times( child : Box< dyn BehaviorNode >, count : u32 ) -> Self
+  {
+    Self
+    {
+      child,
+      max_repeats : Some( count ),
+      current_repeats : 0,
+      name : format!( "Repeat({count})" ),
+    }
+  }
+}
+
+impl BehaviorNode for RepeatNode
+{
+  #[ inline ]
+  fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
+  {
+    loop
+    {
+      match context.tick_child( 0, self.child.as_mut() )
+      {
+        BehaviorStatus::Running => return BehaviorStatus::Running,
+        BehaviorStatus::Success | BehaviorStatus::Failure =>
+        {
+          self.current_repeats += 1;
+          self.child.reset();
+
+          if let Some( max ) = self.max_repeats && self.current_repeats >= max
+          {
+            self.reset();
+            return BehaviorStatus::Success;
+          }
+          // Continue looping for infinite repeat or more iterations
+        }
+      }
+    }
+  }
+
+  #[ inline ]
+  fn reset( &mut self )
+  {
+    self.current_repeats = 0;
+    self.child.reset();
+  }
+
+  #[ inline ]
+  fn name( &self ) -> &str
+  {
+    &self.name
+  }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    vec![ self.child.as_ref() ]
+  }
+}
+
+/// Inverts the success/failure status of its child.
+#[ derive( Debug ) ]
+pub struct InvertNode
+{
+  child : Box< dyn BehaviorNode >,
+  name : String,
+}
+
+impl InvertNode
+{
+  /// Creates a new invert decorator.
+  #[ inline ]
+  #[ must_use ]
+  pub fn new( child : Box< dyn BehaviorNode > ) -> Self
+  {
+    Self
+    {
+      child,
+      name : "Invert".to_string(),
+    }
+  }
+}
+
+impl BehaviorNode for InvertNode
+{
+  #[ inline ]
+  fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
+  {
+    match context.tick_child( 0, self.child.as_mut() )
+    {
+      BehaviorStatus::Success => BehaviorStatus::Failure,
+      BehaviorStatus::Failure => BehaviorStatus::Success,
+      BehaviorStatus::Running => BehaviorStatus::Running,
+    }
+  }
+
+  #[ inline ]
+  fn reset( &mut self )
+  {
+    self.child.reset();
+  }
+
+  #[ inline ]
+  fn name( &self ) -> &str
+  {
+    &self.name
+  }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    vec![ self.child.as_ref() ]
+  }
+}
+
+/// Adds a cooldown period before allowing child execution.
+#[ derive( Debug ) ]
+pub struct CooldownNode
+{
+  child : Box< dyn BehaviorNode >,
+  cooldown_duration : Duration,
+  last_execution : Option< Instant >,
+  name : String,
+}
+
+impl CooldownNode
+{
+  /// Creates a new cooldown decorator.
+  #[ inline ]
+  #[ must_use ]
+  pub fn new( child : Box< dyn BehaviorNode >, cooldown_duration : Duration ) -> Self
+  {
+    Self
+    {
+      child,
+      cooldown_duration,
+      last_execution : None,
+      name : format!( "Cooldown({:.1}s)", cooldown_duration.as_secs_f32() ),
+    }
+  }
+}
+
+impl BehaviorNode for CooldownNode
+{
+  #[ inline ]
+  fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
+  {
+    if let Some( last ) = self.last_execution && context.current_time.duration_since( last ) < self.cooldown_duration
+    {
+      return BehaviorStatus::Failure;
+    }
+
+    let result = context.tick_child( 0, self.child.as_mut() );
+    if result != BehaviorStatus::Running
+    {
+      self.last_execution = Some( context.current_time );
+    }
+    result
+  }
+
+  #[ inline ]
+  fn reset( &mut self )
+  {
+    self.child.reset();
+    // Note: Don't reset last_execution as cooldown persists across resets
+  }
+
+  #[ inline ]
+  fn name( &self ) -> &str
+  {
+    &self.name
+  }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    vec![ self.child.as_ref() ]
+  }
+}
+
+/// Which running branches a [`ConditionalNode`] interrupts when its condition changes.
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+#[ non_exhaustive ]
+pub enum AbortMode
+{
+  /// Condition is checked only before the child starts
+  None,
+  /// Running child is aborted once the condition stops holding
+  SelfBranch,
+  /// Running lower priority siblings are aborted when the condition changes,
+  /// see [`SequenceNode`] and [`SelectorNode`]
+  LowerPriority,
+  /// Both `SelfBranch` and `LowerPriority`
+  Both,
+}
+
+/// Executes child only while a condition holds, optionally aborting running branches when it changes.
+///
+/// Condition is the child at index 0 and holds when it returns `Success`, the guarded child is at index 1.
+#[ derive( Debug ) ]
+pub struct ConditionalNode
+{
+  condition : Box< dyn BehaviorNode >,
+  child : Box< dyn BehaviorNode >,
+  aborts : AbortMode,
+  running : bool,
+  name : String,
+}
+
+impl ConditionalNode
+{
+  /// Creates a new conditional decorator.
+  #[ inline ]
+  #[ must_use ]
+  pub fn new( condition : Box< dyn BehaviorNode >, child : Box< dyn BehaviorNode >, aborts : AbortMode ) -> Self
+  {
+    Self
+    {
+      name : format!( "If({})", condition.name() ),
+      condition,
+      child,
+      aborts,
+      running : false,
+    }
+  }
+
+  fn holds( status : BehaviorStatus ) -> bool
+  {
+    status == BehaviorStatus::Success
+  }
+}
+
+impl BehaviorNode for ConditionalNode
+{
+  #[ inline ]
+  fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
+  {
+    if !self.running || matches!( self.aborts, AbortMode::SelfBranch | AbortMode::Both )
+    {
+      let holds = Self::holds( context.tick_child( 0, self.condition.as_mut() ) );
+      self.condition.reset();
+      if !holds
+      {
+        self.reset();
+        return BehaviorStatus::Failure;
+      }
+    }
+
+    let status = context.tick_child( 1, self.child.as_mut() );
+    self.running = status == BehaviorStatus::Running;
+    status
+  }
+
+  #[ inline ]
+  fn observe( &mut self, context : &mut BehaviorContext ) -> Option< bool >
+  {
+    if !matches!( self.aborts, AbortMode::LowerPriority | AbortMode::Both )
+    {
+      return None;
+    }
+    let holds = Self::holds( context.tick_child( 0, self.condition.as_mut() ) );
+    self.condition.reset();
+    Some( holds )
+  }
+
+  #[ inline ]
+  fn reset( &mut self )
+  {
+    self.running = false;
+    self.condition.reset();
+    self.child.reset();
+  }
+
+  #[ inline ]
+  fn name( &self ) -> &str
+  {
+    &self.name
+  }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    vec![ self.condition.as_ref(), self.child.as_ref() ]
+  }
 }
 
-/// Executes all children in parallel, succeeding when all succeed.
+/// Fails and aborts its child if the child keeps running longer than a time limit.
 #[ derive( Debug ) ]
-pub struct ParallelNode
+pub struct TimeoutNode
 {
-  children : Vec< Box< dyn BehaviorNode > >,
+  child : Box< dyn BehaviorNode >,
+  timeout : Duration,
+  start_time : Option< Instant >,
   name : String,
 }
 
-impl ParallelNode
+impl TimeoutNode
 {
-  /// Creates a new parallel node with the given children.
+  /// Creates a new timeout decorator.
   #[ inline ]
   #[ must_use ]
-  pub fn new( children : Vec< Box< dyn BehaviorNode > > ) -> Self
+  pub fn new( child : Box< dyn BehaviorNode >, timeout : Duration ) -> Self
   {
     Self
     {
-      children,
-      name : "Parallel".to_string(),
+      child,
+      timeout,
+      start_time : None,
+      name : format!( "Timeout({:.1}s)", timeout.as_secs_f32() ),
     }
   }
-
-  /// Creates a named parallel node.
-  #[ inline ]
-  #[ must_use ]
-  pub fn named( children : Vec< Box< dyn BehaviorNode > >, name : String ) -> Self
-  {
-    Self { children, name }
-  }
 }
 
-impl BehaviorNode for ParallelNode
+impl BehaviorNode for TimeoutNode
 {
   #[ inline ]
   fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
   {
-    let mut running_count = 0;
-    let mut success_count = 0;
-
-    for child in &mut self.children
+    let start = *self.start_time.get_or_insert( context.current_time );
+    if context.current_time.duration_since( start ) >= self.timeout
     {
-      match child.execute( context )
-      {
-        BehaviorStatus::Success => success_count += 1,
-        BehaviorStatus::Failure => return BehaviorStatus::Failure,
-        BehaviorStatus::Running => running_count += 1,
-      }
+      self.reset();
+      return BehaviorStatus::Failure;
     }
 
-    if running_count > 0
-    {
-      BehaviorStatus::Running
-    }
-    else if success_count == self.children.len()
-    {
-      BehaviorStatus::Success
-    }
-    else
+    let result = context.tick_child( 0, self.child.as_mut() );
+    if result != BehaviorStatus::Running
     {
-      BehaviorStatus::Failure
+      self.start_time = None;
     }
+    result
   }
 
   #[ inline ]
   fn reset( &mut self )
   {
-    for child in &mut self.children
-    {
-      child.reset();
-    }
+    self.start_time = None;
+    self.child.reset();
   }
 
   #[ inline ]
@@ -556,72 +1291,66 @@ impl BehaviorNode for ParallelNode
   {
     &self.name
   }
-}
 
-// === DECORATOR NODES ===
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    vec![ self.child.as_ref() ]
+  }
+}
 
-/// Repeats a child node a specified number of times or indefinitely.
+/// Executes its child again after failures, up to a number of attempts within the same tick.
 #[ derive( Debug ) ]
-pub struct RepeatNode
+pub struct RetryNode
 {
   child : Box< dyn BehaviorNode >,
-  max_repeats : Option< u32 >,
-  current_repeats : u32,
+  max_attempts : u32,
+  failures : u32,
   name : String,
 }
 
-impl RepeatNode
+impl RetryNode
 {
-  /// Creates a repeat node that runs indefinitely.
-  #[ inline ]
-  #[ must_use ]
-  pub fn infinite( child : Box< dyn BehaviorNode > ) -> Self
-  {
-    Self
-    {
-      child,
-      max_repeats : None,
-      current_repeats : 0,
-      name : "Repeat(∞)".to_string(),
-    }
-  }
-
-  /// Creates a repeat node that runs a specific number of times.
+  /// Creates a retry decorator that gives the child `attempts` tries in total, at least one.
   #[ inline ]
   #[ must_use ]
-  pub fn times( child : Box< dyn BehaviorNode >, count : u32 ) -> Self
+  pub fn new( child : Box< dyn BehaviorNode >, attempts : u32 ) -> Self
   {
+    let max_attempts = attempts.max( 1 );
     Self
     {
       child,
-      max_repeats : Some( count ),
-      current_repeats : 0,
-      name : format!( "Repeat({count})" ),
+      max_attempts,
+      failures : 0,
+      name : format!( "Retry({max_attempts})" ),
     }
   }
 }
 
-impl BehaviorNode for RepeatNode
+impl BehaviorNode for RetryNode
 {
   #[ inline ]
   fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
   {
     loop
     {
-      match self.child.execute( context )
+      match context.tick_child( 0, self.child.as_mut() )
       {
         BehaviorStatus::Running => return BehaviorStatus::Running,
-        BehaviorStatus::Success | BehaviorStatus::Failure =>
+        BehaviorStatus::Success =>
         {
-          self.current_repeats += 1;
-          self.child.reset();
-
-          if let Some( max ) = self.max_repeats && self.current_repeats >= max
+          self.reset();
+          return BehaviorStatus::Success;
+        }
+        BehaviorStatus::Failure =>
+        {
+          self.failures += 1;
+          if self.failures >= self.max_attempts
           {
             self.reset();
-            return BehaviorStatus::Success;
+            return BehaviorStatus::Failure;
           }
-          // Continue looping for infinite repeat or more iterations
+          self.child.reset();
         }
       }
     }
@@ -630,7 +1359,7 @@ impl BehaviorNode for RepeatNode
   #[ inline ]
   fn reset( &mut self )
   {
-    self.current_repeats = 0;
+    self.failures = 0;
     self.child.reset();
   }
 
@@ -639,41 +1368,61 @@ impl BehaviorNode for RepeatNode
   {
     &self.name
   }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    vec![ self.child.as_ref() ]
+  }
 }
 
-/// Inverts the success/failure status of its child.
+/// Executes its child on every tick until it returns the awaited status.
+/// Other results restart the child on the next tick and are reported as `Running`.
 #[ derive( Debug ) ]
-pub struct InvertNode
+pub struct UntilNode
 {
   child : Box< dyn BehaviorNode >,
+  until : BehaviorStatus,
   name : String,
 }
 
-impl InvertNode
+impl UntilNode
 {
-  /// Creates a new invert decorator.
+  /// Creates a decorator that repeats its child until it succeeds.
   #[ inline ]
   #[ must_use ]
-  pub fn new( child : Box< dyn BehaviorNode > ) -> Self
+  pub fn success( child : Box< dyn BehaviorNode > ) -> Self
   {
-    Self
-    {
-      child,
-      name : "Invert".to_string(),
-    }
+    Self { child, until : BehaviorStatus::Success, name : "UntilSuccess".to_string() }
+  }
+
+  /// Creates a decorator that repeats its child until it fails, and then succeeds.
+  #[ inline ]
+  #[ must_use ]
+  pub fn failure( child : Box< dyn BehaviorNode > ) -> Self
+  {
+    Self { child, until : BehaviorStatus::Failure, name : "UntilFailure".to_string() }
   }
 }
 
-impl BehaviorNode for InvertNode
+impl BehaviorNode for UntilNode
 {
   #[ inline ]
   fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
   {
-    match self.child.execute( context )
+    match context.tick_child( 0, self.child.as_mut() )
     {
-      BehaviorStatus::Success => BehaviorStatus::Failure,
-      BehaviorStatus::Failure => BehaviorStatus::Success,
       BehaviorStatus::Running => BehaviorStatus::Running,
+      status if status == self.until =>
+      {
+        self.child.reset();
+        BehaviorStatus::Success
+      }
+      _ =>
+      {
+        self.child.reset();
+        BehaviorStatus::Running
+      }
     }
   }
 
@@ -688,58 +1437,53 @@ impl BehaviorNode for InvertNode
   {
     &self.name
   }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    vec![ self.child.as_ref() ]
+  }
 }
 
-/// Adds a cooldown period before allowing child execution.
+/// Succeeds whenever its child finishes, regardless of the result.
 #[ derive( Debug ) ]
-pub struct CooldownNode
+pub struct ForceSuccessNode
 {
   child : Box< dyn BehaviorNode >,
-  cooldown_duration : Duration,
-  last_execution : Option< Instant >,
   name : String,
 }
 
-impl CooldownNode
+impl ForceSuccessNode
 {
-  /// Creates a new cooldown decorator.
+  /// Creates a new force success decorator.
   #[ inline ]
   #[ must_use ]
-  pub fn new( child : Box< dyn BehaviorNode >, cooldown_duration : Duration ) -> Self
+  pub fn new( child : Box< dyn BehaviorNode > ) -> Self
   {
     Self
     {
       child,
-      cooldown_duration,
-      last_execution : None,
-      name : format!( "Cooldown({:.1}s)", cooldown_duration.as_secs_f32() ),
+      name : "ForceSuccess".to_string(),
     }
   }
 }
 
-impl BehaviorNode for CooldownNode
+impl BehaviorNode for ForceSuccessNode
 {
   #[ inline ]
   fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
   {
-    if let Some( last ) = self.last_execution && context.current_time.duration_since( last ) < self.cooldown_duration
-    {
-      return BehaviorStatus::Failure;
-    }
-
-    let result = self.child.execute( context );
-    if result != BehaviorStatus::Running
+    match context.tick_child( 0, self.child.as_mut() )
     {
-      self.last_execution = Some( context.current_time );
+      BehaviorStatus::Running => BehaviorStatus::Running,
+      BehaviorStatus::Success | BehaviorStatus::Failure => BehaviorStatus::Success,
     }
-    result
   }
 
   #[ inline ]
   fn reset( &mut self )
   {
     self.child.reset();
-    // Note: Don't reset last_execution as cooldown persists across resets
   }
 
   #[ inline ]
@@ -747,6 +1491,12 @@ impl BehaviorNode for CooldownNode
   {
     &self.name
   }
+
+  #[ inline ]
+  fn children( &self ) -> Vec< &dyn BehaviorNode >
+  {
+    vec![ self.child.as_ref() ]
+  }
 }
 
 // === CONDITION NODES ===
@@ -1027,6 +1777,30 @@ pub fn parallel( children : Vec< Box< dyn BehaviorNode > > ) -> Box< dyn Behavio
   Box::new( ParallelNode::new( children ) )
 }
 
+/// Creates a parallel node with the given success and failure policies.
+#[ inline ]
+#[ must_use ]
+pub fn parallel_with( children : Vec< Box< dyn BehaviorNode > >, success : ParallelPolicy, failure : ParallelPolicy ) -> Box< dyn BehaviorNode >
+{
+  Box::new( ParallelNode::with_policies( children, success, failure ) )
+}
+
+/// Creates a random selector with equally weighted children.
+#[ inline ]
+#[ must_use ]
+pub fn random_selector( children : Vec< Box< dyn BehaviorNode > > ) -> Box< dyn BehaviorNode >
+{
+  Box::new( RandomSelectorNode::new( children ) )
+}
+
+/// Creates a random selector from children paired with their weights.
+#[ inline ]
+#[ must_use ]
+pub fn weighted_selector( children : Vec< ( Box< dyn BehaviorNode >, f32 ) > ) -> Box< dyn BehaviorNode >
+{
+  Box::new( RandomSelectorNode::weighted( children ) )
+}
+
 /// Creates a repeat decorator.
 #[ inline ]
 #[ must_use ]
@@ -1059,6 +1833,54 @@ pub fn cooldown( child : Box< dyn BehaviorNode >, seconds : f32 ) -> Box< dyn Be
   Box::new( CooldownNode::new( child, Duration::from_secs_f32( seconds ) ) )
 }
 
+/// Creates a conditional decorator.
+#[ inline ]
+#[ must_use ]
+pub fn conditional( condition : Box< dyn BehaviorNode >, child : Box< dyn BehaviorNode >, aborts : AbortMode ) -> Box< dyn BehaviorNode >
+{
+  Box::new( ConditionalNode::new( condition, child, aborts ) )
+}
+
+/// Creates a timeout decorator.
+#[ inline ]
+#[ must_use ]
+pub fn timeout( child : Box< dyn BehaviorNode >, seconds : f32 ) -> Box< dyn BehaviorNode >
+{
+  Box::new( TimeoutNode::new( child, Duration::from_secs_f32( seconds ) ) )
+}
+
+/// Creates a retry decorator.
+#[ inline ]
+#[ must_use ]
+pub fn retry( child : Box< dyn BehaviorNode >, attempts : u32 ) -> Box< dyn BehaviorNode >
+{
+  Box::new( RetryNode::new( child, attempts ) )
+}
+
+/// Creates a decorator repeating its child until it succeeds.
+#[ inline ]
+#[ must_use ]
+pub fn until_success( child : Box< dyn BehaviorNode > ) -> Box< dyn BehaviorNode >
+{
+  Box::new( UntilNode::success( child ) )
+}
+
+/// Creates a decorator repeating its child until it fails.
+#[ inline ]
+#[ must_use ]
+pub fn until_failure( child : Box< dyn BehaviorNode > ) -> Box< dyn BehaviorNode >
+{
+  Box::new( UntilNode::failure( child ) )
+}
+
+/// Creates a force success decorator.
+#[ inline ]
+#[ must_use ]
+pub fn force_success( child : Box< dyn BehaviorNode > ) -> Box< dyn BehaviorNode >
+{
+  Box::new( ForceSuccessNode::new( child ) )
+}
+
 /// Creates a wait action.
 #[ inline ]
 #[ must_use ]
@@ -1087,6 +1909,8 @@ mod tests
 {
   use super::*;
   use std::time::Duration;
+  use std::rc::Rc;
+  use core::cell::Cell;
 
   #[ test ]
   fn test_behavior_context_creation()
@@ -1328,4 +2152,208 @@ mod tests
     let status3 = cooldown.execute( &mut context );
     assert_eq!( status3, BehaviorStatus::Success );
   }
+
+  type Counter = Rc< Cell< usize > >;
+
+  /// Returns statuses from a script, the last one repeats. Counts ticks and resets
+  #[ derive( Debug ) ]
+  struct Scripted
+  {
+    script : Vec< BehaviorStatus >,
+    ticks : Counter,
+    resets : Counter,
+  }
+
+  impl BehaviorNode for Scripted
+  {
+    fn execute( &mut self, _context : &mut BehaviorContext ) -> BehaviorStatus
+    {
+      let status = self.script[ self.ticks.get().min( self.script.len() - 1 ) ];
+      self.ticks.set( self.ticks.get() + 1 );
+      status
+    }
+
+    fn reset( &mut self )
+    {
+      self.resets.set( self.resets.get() + 1 );
+    }
+
+    fn name( &self ) -> &'static str
+    {
+      "Scripted"
+    }
+  }
+
+  fn scripted( script : &[ BehaviorStatus ] ) -> ( Box< dyn BehaviorNode >, Counter, Counter )
+  {
+    let ticks = Rc::new( Cell::new( 0 ) );
+    let resets = Rc::new( Cell::new( 0 ) );
+    ( Box::new( Scripted { script : script.to_vec(), ticks : ticks.clone(), resets : resets.clone() } ), ticks, resets )
+  }
+
+  #[ test ]
+  fn test_selector_lower_priority_abort()
+  {
+    let ( patrol, _, patrol_resets ) = scripted( &[ BehaviorStatus::Running ] );
+    let mut tree = selector( vec!
+    [
+      conditional( condition( "enemy", true ), set_blackboard( "state", "fight" ), AbortMode::LowerPriority ),
+      patrol,
+    ]);
+    let mut context = BehaviorContext::new();
+
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Running );
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Running );
+    assert_eq!( patrol_resets.get(), 0 );
+
+    // higher priority branch interrupts the running patrol
+    context.set_blackboard( "enemy", true );
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Success );
+    assert_eq!( context.get_blackboard( "state" ), Some( &BehaviorValue::from( "fight" ) ) );
+    assert!( patrol_resets.get() >= 1 );
+  }
+
+  #[ test ]
+  fn test_sequence_aborts()
+  {
+    // condition of completed child stops holding, so running child is aborted
+    let ( work, work_ticks, work_resets ) = scripted( &[ BehaviorStatus::Running ] );
+    let mut tree = sequence( vec!
+    [
+      conditional( condition( "armed", true ), set_blackboard( "ready", true ), AbortMode::LowerPriority ),
+      work,
+    ]);
+    let mut context = BehaviorContext::new();
+    context.set_blackboard( "armed", true );
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Running );
+    context.set_blackboard( "armed", false );
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Failure );
+    assert_eq!( work_ticks.get(), 1 );
+    assert!( work_resets.get() >= 1 );
+
+    // self abort interrupts the guarded child itself
+    let ( work, work_ticks, work_resets ) = scripted( &[ BehaviorStatus::Running ] );
+    let mut guarded = ConditionalNode::new( condition( "alive", true ), work, AbortMode::SelfBranch );
+    context.set_blackboard( "alive", true );
+    assert_eq!( guarded.execute( &mut context ), BehaviorStatus::Running );
+    context.set_blackboard( "alive", false );
+    assert_eq!( guarded.execute( &mut context ), BehaviorStatus::Failure );
+    assert_eq!( work_ticks.get(), 1 );
+    assert_eq!( work_resets.get(), 1 );
+
+    // without aborts the condition is checked only before the child starts
+    let ( work, work_ticks, _ ) = scripted( &[ BehaviorStatus::Running ] );
+    let mut guarded = ConditionalNode::new( condition( "alive", true ), work, AbortMode::None );
+    context.set_blackboard( "alive", true );
+    assert_eq!( guarded.execute( &mut context ), BehaviorStatus::Running );
+    context.set_blackboard( "alive", false );
+    assert_eq!( guarded.execute( &mut context ), BehaviorStatus::Running );
+    assert_eq!( work_ticks.get(), 2 );
+  }
+
+  #[ test ]
+  fn test_parallel_policies()
+  {
+    let ( long, long_ticks, long_resets ) = scripted( &[ BehaviorStatus::Running ] );
+    let mut any = ParallelNode::with_policies
+    (
+      vec![ long, set_blackboard( "done", true ) ],
+      ParallelPolicy::RequireOne,
+      ParallelPolicy::RequireAll,
+    );
+    let mut context = BehaviorContext::new();
+    assert_eq!( any.execute( &mut context ), BehaviorStatus::Success );
+    assert_eq!( long_ticks.get(), 1 );
+    assert_eq!( long_resets.get(), 1 );
+
+    // finished children are not ticked again, and one failure isn't enough to fail
+    let ( quick, quick_ticks, _ ) = scripted( &[ BehaviorStatus::Failure ] );
+    let ( slow, _, _ ) = scripted( &[ BehaviorStatus::Running, BehaviorStatus::Running, BehaviorStatus::Failure ] );
+    let mut all = ParallelNode::with_policies( vec![ quick, slow ], ParallelPolicy::RequireAll, ParallelPolicy::RequireAll );
+    assert_eq!( all.execute( &mut context ), BehaviorStatus::Running );
+    assert_eq!( all.execute( &mut context ), BehaviorStatus::Running );
+    assert_eq!( all.execute( &mut context ), BehaviorStatus::Failure );
+    assert_eq!( quick_ticks.get(), 1 );
+
+    // mixed results meet neither policy
+    let ( fail, _, _ ) = scripted( &[ BehaviorStatus::Failure ] );
+    let mut mixed = ParallelNode::with_policies
+    (
+      vec![ fail, set_blackboard( "x", 1 ) ],
+      ParallelPolicy::RequireAll,
+      ParallelPolicy::RequireAll,
+    );
+    assert_eq!( mixed.execute( &mut context ), BehaviorStatus::Failure );
+  }
+
+  #[ test ]
+  fn test_timeout_and_retry()
+  {
+    let ( slow, _, slow_resets ) = scripted( &[ BehaviorStatus::Running ] );
+    let mut timeout = TimeoutNode::new( slow, Duration::from_millis( 50 ) );
+    let mut context = BehaviorContext::new();
+    assert_eq!( timeout.execute( &mut context ), BehaviorStatus::Running );
+    std::thread::sleep( Duration::from_millis( 60 ) );
+    context.update( Duration::from_millis( 60 ) );
+    assert_eq!( timeout.execute( &mut context ), BehaviorStatus::Failure );
+    assert_eq!( slow_resets.get(), 1 );
+
+    let script = [ BehaviorStatus::Failure, BehaviorStatus::Failure, BehaviorStatus::Success ];
+    let ( flaky, flaky_ticks, _ ) = scripted( &script );
+    let mut retry = RetryNode::new( flaky, 3 );
+    assert_eq!( retry.execute( &mut context ), BehaviorStatus::Success );
+    assert_eq!( flaky_ticks.get(), 3 );
+
+    let ( flaky, flaky_ticks, _ ) = scripted( &script );
+    let mut retry = RetryNode::new( flaky, 2 );
+    assert_eq!( retry.execute( &mut context ), BehaviorStatus::Failure );
+    assert_eq!( flaky_ticks.get(), 2 );
+  }
+
+  #[ test ]
+  fn test_until_and_force_success()
+  {
+    let mut context = BehaviorContext::new();
+    let ( flaky, _, _ ) = scripted( &[ BehaviorStatus::Failure, BehaviorStatus::Failure, BehaviorStatus::Success ] );
+    let mut until = UntilNode::success( flaky );
+    assert_eq!( until.execute( &mut context ), BehaviorStatus::Running );
+    assert_eq!( until.execute( &mut context ), BehaviorStatus::Running );
+    assert_eq!( until.execute( &mut context ), BehaviorStatus::Success );
+
+    let ( steady, _, _ ) = scripted( &[ BehaviorStatus::Success, BehaviorStatus::Failure ] );
+    let mut until = UntilNode::failure( steady );
+    assert_eq!( until.execute( &mut context ), BehaviorStatus::Running );
+    assert_eq!( until.execute( &mut context ), BehaviorStatus::Success );
+
+    let mut forced = force_success( condition( "missing", true ) );
+    assert_eq!( forced.execute( &mut context ), BehaviorStatus::Success );
+  }
+
+  #[ test ]
+  fn test_random_selector()
+  {
+    let mut context = BehaviorContext::new();
+    let mut counts = [ 0, 0, 0 ];
+    for seed in 0..400
+    {
+      let ( first, first_ticks, _ ) = scripted( &[ BehaviorStatus::Success ] );
+      let ( second, second_ticks, _ ) = scripted( &[ BehaviorStatus::Success ] );
+      let ( never, never_ticks, _ ) = scripted( &[ BehaviorStatus::Success ] );
+      let mut node = RandomSelectorNode::weighted( vec![ ( first, 3.0 ), ( second, 1.0 ), ( never, 0.0 ) ] ).with_seed( seed );
+      assert_eq!( node.execute( &mut context ), BehaviorStatus::Success );
+      counts[ 0 ] += first_ticks.get();
+      counts[ 1 ] += second_ticks.get();
+      counts[ 2 ] += never_ticks.get();
+    }
+    assert_eq!( counts[ 0 ] + counts[ 1 ], 400 );
+    assert_eq!( counts[ 2 ], 0 );
+    assert!( counts[ 0 ] > 250 && counts[ 0 ] < 350, "{counts:?}" );
+
+    // failing children are tried in turn, zero weight ones are skipped
+    let ( fail, fail_ticks, _ ) = scripted( &[ BehaviorStatus::Failure ] );
+    let mut node = RandomSelectorNode::weighted( vec![ ( fail, 1.0 ), ( condition( "missing", 1 ), 1.0 ), ( set_blackboard( "x", 1 ), 0.0 ) ] );
+    assert_eq!( node.execute( &mut context ), BehaviorStatus::Failure );
+    assert_eq!( fail_ticks.get(), 1 );
+    assert_eq!( context.get_blackboard( "x" ), None );
+  }
 }
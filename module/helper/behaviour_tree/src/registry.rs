@@ -0,0 +1,946 @@
+//! Data-driven behavior trees.
+//!
+//! Trees are described in any serde format as nested nodes. Every node has a `type`, which
+//! is looked up in [`NodeRegistry`], optional `name`, `params` with [`BehaviorValue`] literals
+//! and `children`. Errors are reported by the format deserializer, so they carry the position
+//! of the node that failed.
+//!
+//! ```ron
+//! (
+//!   name : "Patrol",
+//!   root :
+//!   (
+//!     type : "Sequence",
+//!     children :
+//!     [
+//!       ( type : "SetBlackboard", params : { "key" : String( "target_x" ), "value" : Int( 10 ) } ),
+//!       ( type : "Wait", params : { "seconds" : Float( 2.0 ) } ),
+//!     ],
+//!   ),
+//! )
+//! ```
+
+use crate::
+{
+  BehaviorNode, BehaviorTree, BehaviorValue, SequenceNode, SelectorNode, ParallelNode, ParallelPolicy, RandomSelectorNode,
+  RepeatNode, InvertNode, CooldownNode, ConditionalNode, AbortMode, TimeoutNode, RetryNode, UntilNode, ForceSuccessNode,
+  WaitAction, BlackboardCondition, SetBlackboardAction,
+};
+use std::collections::{ HashMap, HashSet };
+use core::time::Duration;
+use core::fmt;
+use serde::de::{ self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor };
+
+/// Creates node from arguments of its description.
+type NodeFactory = Box< dyn Fn( &mut NodeArgs ) -> Result< Box< dyn BehaviorNode >, String > >;
+
+const NODE_FIELDS : &[ &str ] = &[ "type", "name", "params", "children" ];
+const TREE_FIELDS : &[ &str ] = &[ "name", "root" ];
+
+/// Arguments of a node description passed to its factory.
+///
+/// Factories take parameters and children they understand. Parameters and children
+/// left untaken are reported as errors, so typos in descriptions don't go unnoticed.
+#[ derive( Debug ) ]
+pub struct NodeArgs
+{
+  kind : String,
+  name : Option< String >,
+  params : HashMap< String, BehaviorValue >,
+  used : HashSet< String >,
+  children : Vec< Box< dyn BehaviorNode > >,
+}
+
+impl NodeArgs
+{
+  /// Creates arguments for node of type `kind`.
+  #[ inline ]
+  #[ must_use ]
+  pub fn new
+  (
+    kind : &str,
+    name : Option< String >,
+    params : HashMap< String, BehaviorValue >,
+    children : Vec< Box< dyn BehaviorNode > >,
+  ) -> Self
+  {
+    Self { kind : kind.to_string(), name, params, used : HashSet::new(), children }
+  }
+
+  /// Type of the node as written in description.
+  #[ inline ]
+  pub fn kind( &self ) -> &str
+  {
+    &self.kind
+  }
+
+  /// Name given to the node in description.
+  #[ inline ]
+  pub fn name( &self ) -> Option< &str >
+  {
+    self.name.as_deref()
+  }
+
+  /// Takes optional parameter.
+  #[ inline ]
+  pub fn optional( &mut self, key : &str ) -> Option< BehaviorValue >
+  {
+    let value = self.params.get( key ).cloned();
+    if value.is_some()
+    {
+      self.used.insert( key.to_string() );
+    }
+    value
+  }
+
+  /// Takes required parameter.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if parameter is missing.
+  #[ inline ]
+  pub fn value( &mut self, key : &str ) -> Result< BehaviorValue, String >
+  {
+    self.optional( key ).ok_or_else( || format!( "`{}` requires parameter `{key}`", self.kind ) )
+  }
+
+  /// Takes required number parameter, integers are converted.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if parameter is missing or isn't a number.
+  #[ inline ]
+  #[ allow( clippy::cast_precision_loss ) ]
+  pub fn float( &mut self, key : &str ) -> Result< f32, String >
+  {
+    match self.value( key )?
+    {
+      BehaviorValue::Float( value ) => Ok( value ),
+      BehaviorValue::Int( value ) => Ok( value as f32 ),
+      BehaviorValue::UInt( value ) => Ok( value as f32 ),
+      other => Err( self.mismatch( key, "number", &other ) ),
+    }
+  }
+
+  /// Takes required non-negative integer parameter.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if parameter is missing or isn't a non-negative integer.
+  #[ inline ]
+  pub fn uint( &mut self, key : &str ) -> Result< u32, String >
+  {
+    match self.value( key )?
+    {
+      BehaviorValue::UInt( value ) => Ok( value ),
+      BehaviorValue::Int( value ) if value >= 0 => Ok( value.unsigned_abs() ),
+      other => Err( self.mismatch( key, "non-negative integer", &other ) ),
+    }
+  }
+
+  /// Takes required string parameter.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if parameter is missing or isn't a string.
+  #[ inline ]
+  pub fn string( &mut self, key : &str ) -> Result< String, String >
+  {
+    match self.value( key )?
+    {
+      BehaviorValue::String( value ) => Ok( value ),
+      other => Err( self.mismatch( key, "string", &other ) ),
+    }
+  }
+
+  /// Takes all children.
+  #[ inline ]
+  pub fn children( &mut self ) -> Vec< Box< dyn BehaviorNode > >
+  {
+    core::mem::take( &mut self.children )
+  }
+
+  /// Takes the only child of a decorator.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if there isn't exactly one child.
+  #[ inline ]
+  pub fn child( &mut self ) -> Result< Box< dyn BehaviorNode >, String >
+  {
+    if self.children.len() != 1
+    {
+      return Err( format!( "`{}` takes exactly one child, found {}", self.kind, self.children.len() ) );
+    }
+    Ok( self.children.remove( 0 ) )
+  }
+
+  fn mismatch( &self, key : &str, expected : &str, found : &BehaviorValue ) -> String
+  {
+    format!( "`{}` parameter `{key}` must be {expected}, found {found:?}", self.kind )
+  }
+
+  /// Fails on parameters and children no factory has taken.
+  fn finish( &self ) -> Result< (), String >
+  {
+    let mut unknown : Vec< _ > = self.params.keys().filter( | key | !self.used.contains( *key ) ).collect();
+    unknown.sort();
+    if let Some( key ) = unknown.first()
+    {
+      return Err( format!( "`{}` has no parameter `{key}`", self.kind ) );
+    }
+    if !self.children.is_empty()
+    {
+      return Err( format!( "`{}` takes no children", self.kind ) );
+    }
+    Ok( () )
+  }
+}
+
+/// Maps node type names to factories that create nodes from descriptions.
+pub struct NodeRegistry
+{
+  factories : HashMap< String, NodeFactory >,
+}
+
+impl fmt::Debug for NodeRegistry
+{
+  #[ inline ]
+  fn fmt( &self, formatter : &mut fmt::Formatter< '_ > ) -> fmt::Result
+  {
+    let mut names : Vec< _ > = self.factories.keys().collect();
+    names.sort();
+    formatter.debug_struct( "NodeRegistry" ).field( "nodes", &names ).finish()
+  }
+}
+
+impl NodeRegistry
+{
+  /// Creates registry without any nodes.
+  #[ inline ]
+  #[ must_use ]
+  pub fn empty() -> Self
+  {
+    Self { factories : HashMap::new() }
+  }
+
+  /// Creates registry with built-in nodes: `Sequence`, `Selector`, `Parallel`, `RandomSelector`,
+  /// `Repeat`, `Invert`, `Cooldown`, `Conditional`, `Timeout`, `Retry`, `UntilSuccess`, `UntilFailure`,
+  /// `ForceSuccess`, `Wait`, `Condition` and `SetBlackboard`.
+  ///
+  /// `Parallel` takes optional `success` and `failure` policies, `"RequireOne"` or `"RequireAll"`.
+  /// `RandomSelector` takes optional `weights` as comma separated numbers, one per child, and `seed`.
+  /// `Conditional` takes condition and guarded child, and optional `aborts` mode:
+  /// `"None"`, `"Self"`, `"LowerPriority"` or `"Both"`.
+  #[ inline ]
+  #[ must_use ]
+  pub fn new() -> Self
+  {
+    let mut registry = Self::empty();
+    registry.register( "Sequence", | args |
+    {
+      let children = args.children();
+      Ok( Box::new( match args.name()
+      {
+        Some( name ) => SequenceNode::named( children, name.to_string() ),
+        None => SequenceNode::new( children ),
+      }))
+    });
+    registry.register( "Selector", | args |
+    {
+      let children = args.children();
+      Ok( Box::new( match args.name()
+      {
+        Some( name ) => SelectorNode::named( children, name.to_string() ),
+        None => SelectorNode::new( children ),
+      }))
+    });
+    registry.register( "Parallel", | args |
+    {
+      let success_policy = parallel_policy( args, "success" )?.unwrap_or( ParallelPolicy::RequireAll );
+      let failure_policy = parallel_policy( args, "failure" )?.unwrap_or( ParallelPolicy::RequireOne );
+      let children = args.children();
+      let node = match args.name()
+      {
+        Some( name ) => ParallelNode::named( children, name.to_string() ),
+        None => ParallelNode::new( children ),
+      };
+      Ok( Box::new( ParallelNode { success_policy, failure_policy, ..node } ) )
+    });
+    registry.register( "RandomSelector", random_selector_node );
+    registry.register( "Repeat", | args |
+    {
+      let count = if args.optional( "count" ).is_some() { Some( args.uint( "count" )? ) } else { None };
+      let child = args.child()?;
+      Ok( Box::new( match count
+      {
+        Some( count ) => RepeatNode::times( child, count ),
+        None => RepeatNode::infinite( child ),
+      }))
+    });
+    registry.register( "Invert", | args | Ok( Box::new( InvertNode::new( args.child()? ) ) ) );
+    registry.register( "Cooldown", | args |
+    {
+      let duration = non_negative_seconds( args )?;
+      Ok( Box::new( CooldownNode::new( args.child()?, duration ) ) )
+    });
+    registry.register( "Conditional", conditional_node );
+    registry.register( "Timeout", | args |
+    {
+      let duration = non_negative_seconds( args )?;
+      Ok( Box::new( TimeoutNode::new( args.child()?, duration ) ) )
+    });
+    registry.register( "Retry", | args |
+    {
+      let attempts = args.uint( "attempts" )?;
+      Ok( Box::new( RetryNode::new( args.child()?, attempts ) ) )
+    });
+    registry.register( "UntilSuccess", | args | Ok( Box::new( UntilNode::success( args.child()? ) ) ) );
+    registry.register( "UntilFailure", | args | Ok( Box::new( UntilNode::failure( args.child()? ) ) ) );
+    registry.register( "ForceSuccess", | args | Ok( Box::new( ForceSuccessNode::new( args.child()? ) ) ) );
+    registry.register( "Wait", | args | Ok( Box::new( WaitAction::new( non_negative_seconds( args )?.as_secs_f32() ) ) ) );
+    registry.register( "Condition", | args |
+    {
+      let key = args.string( "key" )?;
+      Ok( Box::new( BlackboardCondition::new( &key, args.value( "value" )? ) ) )
+    });
+    registry.register( "SetBlackboard", | args |
+    {
+      let key = args.string( "key" )?;
+      Ok( Box::new( SetBlackboardAction::new( &key, args.value( "value" )? ) ) )
+    });
+    registry
+  }
+
+  /// Registers node type `name`, replacing previous registration with the same name.
+  #[ inline ]
+  pub fn register< F >( &mut self, name : &str, factory : F )
+  where
+    F : Fn( &mut NodeArgs ) -> Result< Box< dyn BehaviorNode >, String > + 'static,
+  {
+    self.factories.insert( name.to_string(), Box::new( factory ) );
+  }
+
+  /// Checks whether node type `name` is registered.
+  #[ inline ]
+  #[ must_use ]
+  pub fn contains( &self, name : &str ) -> bool
+  {
+    self.factories.contains_key( name )
+  }
+
+  /// Creates node of registered type from its arguments.
+  ///
+  /// # Errors
+  ///
+  /// Returns error if type is unknown, factory fails or arguments are left unused.
+  #[ inline ]
+  pub fn create( &self, mut args : NodeArgs ) -> Result< Box< dyn BehaviorNode >, String >
+  {
+    let factory = self.factories.get( args.kind() ).ok_or_else( || unknown_node( args.kind() ) )?;
+    let node = factory( &mut args )?;
+    args.finish()?;
+    Ok( node )
+  }
+
+  /// Seed that deserializes a single node description.
+  #[ inline ]
+  #[ must_use ]
+  pub fn node_seed( &self ) -> NodeSeed< '_ >
+  {
+    NodeSeed { registry : self }
+  }
+
+  /// Seed that deserializes a tree description `( name, root )`.
+  #[ inline ]
+  #[ must_use ]
+  pub fn tree_seed( &self ) -> TreeSeed< '_ >
+  {
+    TreeSeed { registry : self }
+  }
+
+  /// Loads behavior tree from RON description.
+  ///
+  /// # Errors
+  ///
+  /// Returns error with position in `source` if it is malformed or describes unknown nodes or bad parameters.
+  #[ cfg( feature = "ron" ) ]
+  #[ inline ]
+  pub fn load_ron( &self, source : &str ) -> Result< BehaviorTree, LoadError >
+  {
+    Ok( ron::Options::default().from_str_seed( source, self.tree_seed() )? )
+  }
+
+  /// Loads behavior tree from JSON description.
+  ///
+  /// # Errors
+  ///
+  /// Returns error with position in `source` if it is malformed or describes unknown nodes or bad parameters.
+  #[ cfg( feature = "json" ) ]
+  #[ inline ]
+  pub fn load_json( &self, source : &str ) -> Result< BehaviorTree, LoadError >
+  {
+    let mut deserializer = serde_json::Deserializer::from_str( source );
+    let tree = self.tree_seed().deserialize( &mut deserializer )?;
+    deserializer.end()?;
+    Ok( tree )
+  }
+}
+
+impl Default for NodeRegistry
+{
+  #[ inline ]
+  fn default() -> Self
+  {
+    Self::new()
+  }
+}
+
+fn non_negative_seconds( args : &mut NodeArgs ) -> Result< Duration, String >
+{
+  let seconds = args.float( "seconds" )?;
+  Duration::try_from_secs_f32( seconds )
+  .map_err( | _ | format!( "`{}` parameter `seconds` must be a non-negative duration, found {seconds}", args.kind() ) )
+}
+
+/// Creates `RandomSelector`, weights are parsed from a string because values have no lists
+fn random_selector_node( args : &mut NodeArgs ) -> Result< Box< dyn BehaviorNode >, String >
+{
+  let seed = if args.optional( "seed" ).is_some() { Some( args.uint( "seed" )? ) } else { None };
+  let weights = optional_string( args, "weights" )?;
+  let children = args.children();
+  let mut node = match weights
+  {
+    Some( weights ) =>
+    {
+      let weights = weights
+      .split( ',' )
+      .map( | weight | weight.trim().parse::< f32 >().map_err( | _ | format!( "`RandomSelector` weight `{}` isn't a number", weight.trim() ) ) )
+      .collect::< Result< Vec< _ >, _ > >()?;
+      if weights.len() != children.len()
+      {
+        return Err( format!( "`RandomSelector` has {} weights for {} children", weights.len(), children.len() ) );
+      }
+      RandomSelectorNode::weighted( children.into_iter().zip( weights ).collect() )
+    }
+    None => RandomSelectorNode::new( children ),
+  };
+  if let Some( seed ) = seed
+  {
+    node = node.with_seed( u64::from( seed ) );
+  }
+  Ok( Box::new( node ) )
+}
+
+fn conditional_node( args : &mut NodeArgs ) -> Result< Box< dyn BehaviorNode >, String >
+{
+  let aborts = match optional_string( args, "aborts" )?.as_deref()
+  {
+    None | Some( "None" ) => AbortMode::None,
+    Some( "Self" ) => AbortMode::SelfBranch,
+    Some( "LowerPriority" ) => AbortMode::LowerPriority,
+    Some( "Both" ) => AbortMode::Both,
+    Some( other ) => return Err( format!( "`Conditional` parameter `aborts` has unknown mode `{other}`" ) ),
+  };
+  let mut children = args.children();
+  if children.len() != 2
+  {
+    return Err( format!( "`Conditional` takes condition and child, found {} children", children.len() ) );
+  }
+  let child = children.remove( 1 );
+  Ok( Box::new( ConditionalNode::new( children.remove( 0 ), child, aborts ) ) )
+}
+
+fn optional_string( args : &mut NodeArgs, key : &str ) -> Result< Option< String >, String >
+{
+  if args.optional( key ).is_some() { args.string( key ).map( Some ) } else { Ok( None ) }
+}
+
+fn parallel_policy( args : &mut NodeArgs, key : &str ) -> Result< Option< ParallelPolicy >, String >
+{
+  match optional_string( args, key )?.as_deref()
+  {
+    None => Ok( None ),
+    Some( "RequireOne" ) => Ok( Some( ParallelPolicy::RequireOne ) ),
+    Some( "RequireAll" ) => Ok( Some( ParallelPolicy::RequireAll ) ),
+    Some( other ) => Err( format!( "`{}` parameter `{key}` has unknown policy `{other}`", args.kind() ) ),
+  }
+}
+
+fn unknown_node( kind : &str ) -> String
+{
+  format!( "unknown node type `{kind}`" )
+}
+
+/// Deserializes node description into node using registry.
+#[ derive( Debug, Clone, Copy ) ]
+pub struct NodeSeed< 'r >
+{
+  registry : &'r NodeRegistry,
+}
+
+impl< 'de > DeserializeSeed< 'de > for NodeSeed< '_ >
+{
+  type Value = Box< dyn BehaviorNode >;
+
+  #[ inline ]
+  fn deserialize< D >( self, deserializer : D ) -> Result< Self::Value, D::Error >
+  where
+    D : Deserializer< 'de >,
+  {
+    deserializer.deserialize_struct( "Node", NODE_FIELDS, self )
+  }
+}
+
+impl< 'de > Visitor< 'de > for NodeSeed< '_ >
+{
+  type Value = Box< dyn BehaviorNode >;
+
+  #[ inline ]
+  fn expecting( &self, formatter : &mut fmt::Formatter< '_ > ) -> fmt::Result
+  {
+    formatter.write_str( "behavior tree node" )
+  }
+
+  #[ inline ]
+  fn visit_map< A >( self, mut map : A ) -> Result< Self::Value, A::Error >
+  where
+    A : MapAccess< 'de >,
+  {
+    let mut kind : Option< String > = None;
+    let mut name = None;
+    let mut params = None;
+    let mut children = None;
+
+    while let Some( key ) = map.next_key::< String >()?
+    {
+      match key.as_str()
+      {
+        "type" =>
+        {
+          let value : String = map.next_value()?;
+          // Checked right away, so error points to the type rather than to the end of node
+          if !self.registry.contains( &value )
+          {
+            return Err( de::Error::custom( unknown_node( &value ) ) );
+          }
+          kind = Some( value );
+        }
+        "name" => name = Some( map.next_value()? ),
+        "params" => params = Some( map.next_value()? ),
+        "children" => children = Some( map.next_value_seed( ChildrenSeed { registry : self.registry } )? ),
+        other => return Err( de::Error::unknown_field( other, NODE_FIELDS ) ),
+      }
+    }
+
+    let kind = kind.ok_or_else( || de::Error::missing_field( "type" ) )?;
+    let args = NodeArgs::new( &kind, name, params.unwrap_or_default(), children.unwrap_or_default() );
+    self.registry.create( args ).map_err( de::Error::custom )
+  }
+}
+
+/// Deserializes list of child nodes.
+struct ChildrenSeed< 'r >
+{
+  registry : &'r NodeRegistry,
+}
+
+impl< 'de > DeserializeSeed< 'de > for ChildrenSeed< '_ >
+{
+  type Value = Vec< Box< dyn BehaviorNode > >;
+
+  fn deserialize< D >( self, deserializer : D ) -> Result< Self::Value, D::Error >
+  where
+    D : Deserializer< 'de >,
+  {
+    deserializer.deserialize_seq( self )
+  }
+}
+
+impl< 'de > Visitor< 'de > for ChildrenSeed< '_ >
+{
+  type Value = Vec< Box< dyn BehaviorNode > >;
+
+  fn expecting( &self, formatter : &mut fmt::Formatter< '_ > ) -> fmt::Result
+  {
+    formatter.write_str( "list of behavior tree nodes" )
+  }
+
+  fn visit_seq< A >( self, mut seq : A ) -> Result< Self::Value, A::Error >
+  where
+    A : SeqAccess< 'de >,
+  {
+    let mut children = vec![];
+    while let Some( child ) = seq.next_element_seed( self.registry.node_seed() )?
+    {
+      children.push( child );
+    }
+    Ok( children )
+  }
+}
+
+/// Deserializes tree description into behavior tree using registry.
+#[ derive( Debug, Clone, Copy ) ]
+pub struct TreeSeed< 'r >
+{
+  registry : &'r NodeRegistry,
+}
+
+impl< 'de > DeserializeSeed< 'de > for TreeSeed< '_ >
+{
+  type Value = BehaviorTree;
+
+  #[ inline ]
+  fn deserialize< D >( self, deserializer : D ) -> Result< Self::Value, D::Error >
+  where
+    D : Deserializer< 'de >,
+  {
+    deserializer.deserialize_struct( "Tree", TREE_FIELDS, self )
+  }
+}
+
+impl< 'de > Visitor< 'de > for TreeSeed< '_ >
+{
+  type Value = BehaviorTree;
+
+  #[ inline ]
+  fn expecting( &self, formatter : &mut fmt::Formatter< '_ > ) -> fmt::Result
+  {
+    formatter.write_str( "behavior tree" )
+  }
+
+  #[ inline ]
+  fn visit_map< A >( self, mut map : A ) -> Result< Self::Value, A::Error >
+  where
+    A : MapAccess< 'de >,
+  {
+    let mut name = None;
+    let mut root = None;
+    while let Some( key ) = map.next_key::< String >()?
+    {
+      match key.as_str()
+      {
+        "name" => name = Some( map.next_value()? ),
+        "root" => root = Some( map.next_value_seed( self.registry.node_seed() )? ),
+        other => return Err( de::Error::unknown_field( other, TREE_FIELDS ) ),
+      }
+    }
+    let root = root.ok_or_else( || de::Error::missing_field( "root" ) )?;
+    Ok( BehaviorTree::new( root, name.unwrap_or_else( || "BehaviorTree".to_string() ) ) )
+  }
+}
+
+/// Error of loading behavior tree description.
+#[ cfg( any( feature = "ron", feature = "json" ) ) ]
+#[ derive( Debug ) ]
+#[ non_exhaustive ]
+pub enum LoadError
+{
+  /// Error of RON description
+  #[ cfg( feature = "ron" ) ]
+  Ron( ron::error::SpannedError ),
+  /// Error of JSON description
+  #[ cfg( feature = "json" ) ]
+  Json( serde_json::Error ),
+}
+
+#[ cfg( any( feature = "ron", feature = "json" ) ) ]
+impl LoadError
+{
+  /// Line of description where error occurred, starting from 1.
+  #[ inline ]
+  #[ must_use ]
+  pub fn line( &self ) -> usize
+  {
+    match self
+    {
+      #[ cfg( feature = "ron" ) ]
+      Self::Ron( error ) => error.span.start.line,
+      #[ cfg( feature = "json" ) ]
+      Self::Json( error ) => error.line(),
+    }
+  }
+
+  /// Column of description where error occurred, starting from 1.
+  #[ inline ]
+  #[ must_use ]
+  pub fn column( &self ) -> usize
+  {
+    match self
+    {
+      #[ cfg( feature = "ron" ) ]
+      Self::Ron( error ) => error.span.start.col,
+      #[ cfg( feature = "json" ) ]
+      Self::Json( error ) => error.column(),
+    }
+  }
+}
+
+#[ cfg( any( feature = "ron", feature = "json" ) ) ]
+impl fmt::Display for LoadError
+{
+  #[ inline ]
+  fn fmt( &self, formatter : &mut fmt::Formatter< '_ > ) -> fmt::Result
+  {
+    match self
+    {
+      #[ cfg( feature = "ron" ) ]
+      Self::Ron( error ) => write!( formatter, "{}:{}: {}", self.line(), self.column(), error.code ),
+      #[ cfg( feature = "json" ) ]
+      Self::Json( error ) => write!( formatter, "{}:{}: {}", self.line(), self.column(), error ),
+    }
+  }
+}
+
+#[ cfg( any( feature = "ron", feature = "json" ) ) ]
+impl core::error::Error for LoadError {}
+
+#[ cfg( feature = "ron" ) ]
+impl From< ron::error::SpannedError > for LoadError
+{
+  #[ inline ]
+  fn from( value : ron::error::SpannedError ) -> Self
+  {
+    Self::Ron( value )
+  }
+}
+
+#[ cfg( feature = "json" ) ]
+impl From< serde_json::Error > for LoadError
+{
+  #[ inline ]
+  fn from( value : serde_json::Error ) -> Self
+  {
+    Self::Json( value )
+  }
+}
+
+#[ cfg( test ) ]
+mod tests
+{
+  use super::*;
+  use crate::{ BehaviorContext, BehaviorStatus, wait };
+
+  /// Increments integer in blackboard by `step`
+  #[ derive( Debug ) ]
+  struct Increment
+  {
+    key : String,
+    step : i32,
+  }
+
+  impl BehaviorNode for Increment
+  {
+    fn execute( &mut self, context : &mut BehaviorContext ) -> BehaviorStatus
+    {
+      let value = match context.get_blackboard( &self.key )
+      {
+        Some( BehaviorValue::Int( value ) ) => *value,
+        _ => 0,
+      };
+      context.set_blackboard( &self.key, value + self.step );
+      BehaviorStatus::Success
+    }
+
+    fn name( &self ) -> &'static str
+    {
+      "Increment"
+    }
+  }
+
+  fn registry() -> NodeRegistry
+  {
+    let mut registry = NodeRegistry::new();
+    registry.register( "Increment", | args |
+    {
+      let key = args.string( "key" )?;
+      let step = match args.optional( "step" )
+      {
+        Some( BehaviorValue::Int( step ) ) => step,
+        Some( other ) => return Err( format!( "`step` must be integer, found {other:?}" ) ),
+        None => 1,
+      };
+      Ok( Box::new( Increment { key, step } ) )
+    });
+    registry
+  }
+
+  #[ test ]
+  fn test_create_from_args()
+  {
+    let registry = registry();
+    let mut params = HashMap::new();
+    params.insert( "key".to_string(), BehaviorValue::from( "count" ) );
+    let mut node = registry.create( NodeArgs::new( "Increment", None, params, vec![] ) ).unwrap();
+
+    let mut context = BehaviorContext::new();
+    assert_eq!( node.execute( &mut context ), BehaviorStatus::Success );
+    assert_eq!( context.get_blackboard( "count" ), Some( &BehaviorValue::Int( 1 ) ) );
+
+    let error = registry.create( NodeArgs::new( "Missing", None, HashMap::new(), vec![] ) ).unwrap_err();
+    assert_eq!( error, "unknown node type `Missing`" );
+  }
+
+  #[ test ]
+  fn test_unused_arguments()
+  {
+    let registry = registry();
+    let mut params = HashMap::new();
+    params.insert( "seconds".to_string(), BehaviorValue::Float( 1.0 ) );
+    params.insert( "second".to_string(), BehaviorValue::Float( 1.0 ) );
+    let error = registry.create( NodeArgs::new( "Wait", None, params, vec![] ) ).unwrap_err();
+    assert_eq!( error, "`Wait` has no parameter `second`" );
+
+    let mut params = HashMap::new();
+    params.insert( "seconds".to_string(), BehaviorValue::Float( 1.0 ) );
+    let error = registry.create( NodeArgs::new( "Wait", None, params, vec![ wait( 1.0 ) ] ) ).unwrap_err();
+    assert_eq!( error, "`Wait` takes no children" );
+  }
+
+  #[ test ]
+  fn test_out_of_range_seconds()
+  {
+    let registry = registry();
+    for seconds in [ -1.0, f32::NAN, 1e20 ]
+    {
+      for kind in [ "Wait", "Cooldown", "Timeout" ]
+      {
+        let mut params = HashMap::new();
+        params.insert( "seconds".to_string(), BehaviorValue::Float( seconds ) );
+        let children = if kind == "Wait" { vec![] } else { vec![ wait( 1.0 ) ] };
+        let error = registry.create( NodeArgs::new( kind, None, params, children ) ).unwrap_err();
+        assert!( error.starts_with( &format!( "`{kind}` parameter `seconds` must be a non-negative duration" ) ), "{error}" );
+      }
+    }
+  }
+
+  #[ cfg( feature = "ron" ) ]
+  #[ test ]
+  fn test_load_ron()
+  {
+    let source = r#"
+(
+  name : "Counter",
+  root :
+  (
+    type : "Sequence",
+    name : "Steps",
+    children :
+    [
+      ( type : "Increment", params : { "key" : String( "count" ), "step" : Int( 2 ) } ),
+      ( type : "Repeat", params : { "count" : UInt( 3 ) }, children : [ ( type : "Increment", params : { "key" : String( "count" ) } ) ] ),
+      ( type : "Condition", params : { "key" : String( "count" ), "value" : Int( 5 ) } ),
+    ],
+  ),
+)
+"#;
+    let mut tree = registry().load_ron( source ).unwrap();
+    assert_eq!( tree.name(), "Counter" );
+
+    let mut context = BehaviorContext::new();
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Success );
+    assert_eq!( context.get_blackboard( "count" ), Some( &BehaviorValue::Int( 5 ) ) );
+  }
+
+  #[ cfg( feature = "ron" ) ]
+  #[ test ]
+  fn test_ron_errors_point_to_node()
+  {
+    let source = r#"(
+  root :
+  (
+    type : "Sequence",
+    children :
+    [
+      ( type : "Wait", params : { "seconds" : Float( 1.0 ) } ),
+      ( type : "Patrol" ),
+    ],
+  ),
+)"#;
+    let error = registry().load_ron( source ).unwrap_err();
+    assert_eq!( error.line(), 8 );
+    assert!( error.to_string().contains( "unknown node type `Patrol`" ) );
+
+    let source = r#"(
+  root :
+  (
+    type : "Sequence",
+    children :
+    [
+      ( type : "Wait", params : { "seconds" : String( "long" ) } ),
+    ],
+  ),
+)"#;
+    let error = registry().load_ron( source ).unwrap_err();
+    assert_eq!( error.line(), 7 );
+    assert!( error.to_string().contains( "`Wait` parameter `seconds` must be number" ) );
+  }
+
+  #[ cfg( feature = "ron" ) ]
+  #[ test ]
+  fn test_load_aborts_and_policies()
+  {
+    let source = r#"(
+  root :
+  (
+    type : "Selector",
+    children :
+    [
+      (
+        type : "Conditional",
+        params : { "aborts" : String( "LowerPriority" ) },
+        children :
+        [
+          ( type : "Condition", params : { "key" : String( "enemy" ), "value" : Bool( true ) } ),
+          ( type : "SetBlackboard", params : { "key" : String( "state" ), "value" : String( "fight" ) } ),
+        ],
+      ),
+      (
+        type : "Parallel",
+        params : { "success" : String( "RequireOne" ) },
+        children : [ ( type : "Wait", params : { "seconds" : Float( 100.0 ) } ), ( type : "Timeout", params : { "seconds" : Int( 100 ) }, children : [ ( type : "Wait", params : { "seconds" : Float( 100.0 ) } ) ] ) ],
+      ),
+    ],
+  ),
+)"#;
+    let mut tree = registry().load_ron( source ).unwrap();
+    let mut context = BehaviorContext::new();
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Running );
+    context.set_blackboard( "enemy", true );
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Success );
+    assert_eq!( context.get_blackboard( "state" ), Some( &BehaviorValue::from( "fight" ) ) );
+
+    let source = r#"( root : ( type : "RandomSelector", params : { "weights" : String( "1, 2" ) }, children : [ ( type : "Wait", params : { "seconds" : Float( 1.0 ) } ) ] ) )"#;
+    let error = registry().load_ron( source ).unwrap_err();
+    assert!( error.to_string().contains( "`RandomSelector` has 2 weights for 1 children" ) );
+  }
+
+  #[ cfg( feature = "json" ) ]
+  #[ test ]
+  fn test_load_json()
+  {
+    let source = r#"{
+  "root" : {
+    "type" : "Selector",
+    "children" : [
+      { "type" : "Condition", "params" : { "key" : { "String" : "alert" }, "value" : { "Bool" : true } } },
+      { "type" : "SetBlackboard", "params" : { "key" : { "String" : "state" }, "value" : { "String" : "idle" } } }
+    ]
+  }
+}"#;
+    let mut tree = registry().load_json( source ).unwrap();
+    let mut context = BehaviorContext::new();
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Success );
+    assert_eq!( context.get_blackboard( "state" ), Some( &BehaviorValue::from( "idle" ) ) );
+
+    let source = r#"{
+  "root" : {
+    "type" : "Invert",
+    "children" : []
+  }
+}"#;
+    let error = registry().load_json( source ).unwrap_err();
+    assert_eq!( error.line(), 5 );
+    assert!( error.to_string().contains( "`Invert` takes exactly one child, found 0" ) );
+  }
+}
@@ -0,0 +1,516 @@
+//! Execution tracing of behavior trees.
+//!
+//! Tracing is opt-in: ticks executed with [`BehaviorTree::execute_traced`] are recorded into a
+//! [`Tracer`], which keeps only the last few of them. Every recorded tick lists the status each
+//! visited node returned and the blackboard accesses made through [`BehaviorContext`].
+//!
+//! Nodes are identified by paths of child indices from the root, so the root has an empty path
+//! and `[ 1, 0 ]` is the first child of the second child of the root. Children appear in traces
+//! only when their parents execute them through [`BehaviorContext::tick_child`].
+
+use crate::{ BehaviorNode, BehaviorStatus, BehaviorTree, BehaviorValue };
+use std::collections::{ HashMap, VecDeque };
+use core::fmt::Write;
+
+/// Status a node returned during a tick.
+#[ derive( Debug, Clone, PartialEq, Eq ) ]
+pub struct NodeRecord
+{
+  /// Indices of children leading from the root to the node
+  pub path : Vec< usize >,
+  /// Name of the node
+  pub name : String,
+  /// Status returned by the node
+  pub status : BehaviorStatus,
+}
+
+/// Kind of blackboard access.
+#[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+#[ non_exhaustive ]
+pub enum BlackboardAccessKind
+{
+  /// Value was read
+  Read,
+  /// Value was written
+  Write,
+}
+
+/// Blackboard access made by a node during a tick.
+#[ derive( Debug, Clone, PartialEq ) ]
+pub struct BlackboardAccess
+{
+  /// Path of the node that was executing
+  pub path : Vec< usize >,
+  /// Whether value was read or written
+  pub kind : BlackboardAccessKind,
+  /// Blackboard key
+  pub key : String,
+  /// Value read or written, `None` when a missing key was read
+  pub value : Option< BehaviorValue >,
+}
+
+/// Everything recorded during a single tick of a tree.
+#[ derive( Debug, Clone, PartialEq ) ]
+pub struct TickTrace
+{
+  /// Number of the tick, counted by the tracer
+  pub tick : u64,
+  /// Status returned by the tree
+  pub status : BehaviorStatus,
+  /// Visited nodes in the order they were entered
+  pub nodes : Vec< NodeRecord >,
+  /// Blackboard accesses in the order they were made
+  pub blackboard : Vec< BlackboardAccess >,
+}
+
+impl TickTrace
+{
+  /// Deepest nodes that returned `Running`, that is ends of the paths the tree is waiting on.
+  #[ inline ]
+  #[ must_use ]
+  pub fn running_nodes( &self ) -> Vec< &NodeRecord >
+  {
+    self.deepest( BehaviorStatus::Running )
+  }
+
+  /// Deepest nodes that returned `Failure`. When the tree failed, these are the nodes the failure
+  /// originated from, while their ancestors only propagated it.
+  #[ inline ]
+  #[ must_use ]
+  pub fn failed_nodes( &self ) -> Vec< &NodeRecord >
+  {
+    self.deepest( BehaviorStatus::Failure )
+  }
+
+  /// Status the node at `path` returned last during the tick, if it was visited.
+  #[ inline ]
+  #[ must_use ]
+  pub fn status_of( &self, path : &[ usize ] ) -> Option< BehaviorStatus >
+  {
+    self.nodes.iter().rev().find( | node | node.path == path ).map( | node | node.status )
+  }
+
+  fn deepest( &self, status : BehaviorStatus ) -> Vec< &NodeRecord >
+  {
+    self.nodes
+    .iter()
+    .filter( | node | node.status == status )
+    .filter( | node |
+    {
+      !self.nodes.iter().any( | other |
+      {
+        other.status == status && other.path.len() > node.path.len() && other.path.starts_with( &node.path )
+      })
+    })
+    .collect()
+  }
+}
+
+/// Collects records of the tick being executed.
+#[ derive( Debug, Default ) ]
+pub( crate ) struct TraceRecorder
+{
+  path : Vec< usize >,
+  open : Vec< usize >,
+  nodes : Vec< NodeRecord >,
+  blackboard : Vec< BlackboardAccess >,
+}
+
+impl TraceRecorder
+{
+  /// Starts node record. Root is entered without index
+  pub( crate ) fn enter( &mut self, index : Option< usize >, name : &str )
+  {
+    self.path.extend( index );
+    self.open.push( self.nodes.len() );
+    self.nodes.push( NodeRecord { path : self.path.clone(), name : name.to_string(), status : BehaviorStatus::Running } );
+  }
+
+  /// Completes record of the most recently entered node
+  pub( crate ) fn exit( &mut self, status : BehaviorStatus )
+  {
+    if let Some( open ) = self.open.pop()
+    {
+      self.nodes[ open ].status = status;
+    }
+    self.path.pop();
+  }
+
+  /// Moves into child at `index` without recording it, for children that are observed rather than executed
+  pub( crate ) fn descend( &mut self, index : usize )
+  {
+    self.path.push( index );
+  }
+
+  /// Leaves child entered by `descend`
+  pub( crate ) fn ascend( &mut self )
+  {
+    self.path.pop();
+  }
+
+  pub( crate ) fn access( &mut self, kind : BlackboardAccessKind, key : &str, value : Option< BehaviorValue > )
+  {
+    self.blackboard.push( BlackboardAccess { path : self.path.clone(), kind, key : key.to_string(), value } );
+  }
+}
+
+/// Ring buffer of the last traced ticks.
+#[ derive( Debug, Clone ) ]
+pub struct Tracer
+{
+  capacity : usize,
+  ticks : VecDeque< TickTrace >,
+  next_tick : u64,
+}
+
+impl Tracer
+{
+  /// Creates tracer that keeps last `capacity` ticks, at least one.
+  #[ inline ]
+  #[ must_use ]
+  pub fn new( capacity : usize ) -> Self
+  {
+    let capacity = capacity.max( 1 );
+    Self { capacity, ticks : VecDeque::with_capacity( capacity ), next_tick : 0 }
+  }
+
+  /// Maximum number of kept ticks.
+  #[ inline ]
+  #[ must_use ]
+  pub fn capacity( &self ) -> usize
+  {
+    self.capacity
+  }
+
+  /// Kept ticks from the oldest to the newest.
+  #[ inline ]
+  pub fn ticks( &self ) -> impl Iterator< Item = &TickTrace >
+  {
+    self.ticks.iter()
+  }
+
+  /// The most recent tick.
+  #[ inline ]
+  #[ must_use ]
+  pub fn last( &self ) -> Option< &TickTrace >
+  {
+    self.ticks.back()
+  }
+
+  /// Drops kept ticks. Tick numbering continues.
+  #[ inline ]
+  pub fn clear( &mut self )
+  {
+    self.ticks.clear();
+  }
+
+  pub( crate ) fn record( &mut self, status : BehaviorStatus, recorder : TraceRecorder )
+  {
+    if self.ticks.len() == self.capacity
+    {
+      self.ticks.pop_front();
+    }
+    self.ticks.push_back( TickTrace { tick : self.next_tick, status, nodes : recorder.nodes, blackboard : recorder.blackboard } );
+    self.next_tick += 1;
+  }
+
+  /// Exports kept ticks as JSON document `{ "ticks" : [ ... ] }`.
+  /// Blackboard values are written as single-key objects like `{ "Int" : 3 }`.
+  #[ inline ]
+  #[ must_use ]
+  pub fn to_json( &self ) -> String
+  {
+    let mut out = String::from( "{\"ticks\":[" );
+    for ( i, tick ) in self.ticks.iter().enumerate()
+    {
+      if i > 0
+      {
+        out.push( ',' );
+      }
+      let _ = write!( out, "{{\"tick\":{},\"status\":\"{:?}\",\"nodes\":[", tick.tick, tick.status );
+      for ( j, node ) in tick.nodes.iter().enumerate()
+      {
+        if j > 0
+        {
+          out.push( ',' );
+        }
+        out.push_str( "{\"path\":" );
+        write_json_path( &mut out, &node.path );
+        out.push_str( ",\"name\":" );
+        write_json_string( &mut out, &node.name );
+        let _ = write!( out, ",\"status\":\"{:?}\"}}", node.status );
+      }
+      out.push_str( "],\"blackboard\":[" );
+      for ( j, access ) in tick.blackboard.iter().enumerate()
+      {
+        if j > 0
+        {
+          out.push( ',' );
+        }
+        out.push_str( "{\"path\":" );
+        write_json_path( &mut out, &access.path );
+        let _ = write!( out, ",\"access\":\"{:?}\",\"key\":", access.kind );
+        write_json_string( &mut out, &access.key );
+        out.push_str( ",\"value\":" );
+        match &access.value
+        {
+          Some( value ) => write_json_value( &mut out, value ),
+          None => out.push_str( "null" ),
+        }
+        out.push( '}' );
+      }
+      out.push_str( "]}" );
+    }
+    out.push_str( "]}" );
+    out
+  }
+
+  /// Renders `tree` as Graphviz DOT graph. Nodes are filled with the colour of the status they
+  /// returned the last time they were visited within kept ticks: green for success, red for
+  /// failure and yellow for running. Nodes not visited in the newest tick are dashed,
+  /// nodes not visited at all are grey.
+  #[ inline ]
+  #[ must_use ]
+  pub fn to_dot( &self, tree : &BehaviorTree ) -> String
+  {
+    let mut last_status : HashMap< &[ usize ], ( BehaviorStatus, bool ) > = HashMap::new();
+    for ( i, tick ) in self.ticks.iter().enumerate()
+    {
+      let newest = i + 1 == self.ticks.len();
+      for node in &tick.nodes
+      {
+        last_status.insert( &node.path, ( node.status, newest ) );
+      }
+    }
+
+    let mut out = String::from( "digraph " );
+    write_json_string( &mut out, tree.name() );
+    out.push_str( "\n{\n  node [shape=box, style=\"rounded,filled\", fontname=\"Helvetica\"];\n" );
+    let mut path = vec![];
+    write_dot_node( &mut out, tree.root(), &mut path, &last_status );
+    out.push_str( "}\n" );
+    out
+  }
+}
+
+fn write_dot_node
+(
+  out : &mut String,
+  node : &dyn BehaviorNode,
+  path : &mut Vec< usize >,
+  last_status : &HashMap< &[ usize ], ( BehaviorStatus, bool ) >,
+)
+{
+  let id = dot_id( path );
+  let ( color, style ) = match last_status.get( path.as_slice() )
+  {
+    Some( &( status, newest ) ) =>
+    {
+      let color = match status
+      {
+        BehaviorStatus::Success => "#9be29b",
+        BehaviorStatus::Failure => "#f29191",
+        BehaviorStatus::Running => "#f5d76e",
+      };
+      ( color, if newest { "rounded,filled" } else { "rounded,filled,dashed" } )
+    }
+    None => ( "#e0e0e0", "rounded,filled" ),
+  };
+  let _ = write!( out, "  {id} [label=" );
+  write_json_string( out, node.name() );
+  let _ = writeln!( out, ", fillcolor=\"{color}\", style=\"{style}\"];" );
+
+  for ( i, child ) in node.children().into_iter().enumerate()
+  {
+    path.push( i );
+    let _ = writeln!( out, "  {id} -> {};", dot_id( path ) );
+    write_dot_node( out, child, path, last_status );
+    path.pop();
+  }
+}
+
+fn dot_id( path : &[ usize ] ) -> String
+{
+  let mut id = String::from( "n" );
+  for index in path
+  {
+    let _ = write!( id, "_{index}" );
+  }
+  id
+}
+
+fn write_json_path( out : &mut String, path : &[ usize ] )
+{
+  out.push( '[' );
+  for ( i, index ) in path.iter().enumerate()
+  {
+    if i > 0
+    {
+      out.push( ',' );
+    }
+    let _ = write!( out, "{index}" );
+  }
+  out.push( ']' );
+}
+
+/// Writes quoted string with JSON escapes, which are also understood by DOT
+fn write_json_string( out : &mut String, value : &str )
+{
+  out.push( '"' );
+  for character in value.chars()
+  {
+    match character
+    {
+      '"' => out.push_str( "\\\"" ),
+      '\\' => out.push_str( "\\\\" ),
+      '\n' => out.push_str( "\\n" ),
+      control if control.is_control() => { let _ = write!( out, "\\u{:04x}", u32::from( control ) ); },
+      other => out.push( other ),
+    }
+  }
+  out.push( '"' );
+}
+
+fn write_json_value( out : &mut String, value : &BehaviorValue )
+{
+  match value
+  {
+    BehaviorValue::Bool( value ) => { let _ = write!( out, "{{\"Bool\":{value}}}" ); },
+    BehaviorValue::Int( value ) => { let _ = write!( out, "{{\"Int\":{value}}}" ); },
+    BehaviorValue::UInt( value ) => { let _ = write!( out, "{{\"UInt\":{value}}}" ); },
+    BehaviorValue::EntityId( value ) => { let _ = write!( out, "{{\"EntityId\":{value}}}" ); },
+    // JSON has no infinities and NaN
+    BehaviorValue::Float( value ) if !value.is_finite() => out.push_str( "{\"Float\":null}" ),
+    BehaviorValue::Float( value ) => { let _ = write!( out, "{{\"Float\":{value}}}" ); },
+    BehaviorValue::String( text ) =>
+    {
+      out.push_str( "{\"String\":" );
+      write_json_string( out, text );
+      out.push( '}' );
+    }
+    BehaviorValue::Position2D { x, y } => { let _ = write!( out, "{{\"Position2D\":{{\"x\":{x},\"y\":{y}}}}}" ); },
+  }
+}
+
+#[ cfg( test ) ]
+mod tests
+{
+  use super::*;
+  use crate::{ AbortMode, BehaviorContext, condition, conditional, invert, selector, sequence, set_blackboard, wait };
+
+  fn guard_tree() -> BehaviorTree
+  {
+    BehaviorTree::new
+    (
+      sequence( vec!
+      [
+        set_blackboard( "alert", true ),
+        selector( vec![ condition( "enemy", 1 ), invert( condition( "alert", true ) ) ] ),
+      ]),
+      "Guard".to_string(),
+    )
+  }
+
+  #[ test ]
+  fn test_trace_finds_failed_node()
+  {
+    let mut tree = guard_tree();
+    let mut context = BehaviorContext::new();
+    let mut tracer = Tracer::new( 4 );
+    assert_eq!( tree.execute_traced( &mut context, &mut tracer ), BehaviorStatus::Failure );
+
+    let tick = tracer.last().unwrap();
+    assert_eq!( tick.status, BehaviorStatus::Failure );
+    let paths : Vec< &[ usize ] > = tick.nodes.iter().map( | node | node.path.as_slice() ).collect();
+    assert_eq!( paths, vec![ &[][ .. ], &[ 0 ], &[ 1 ], &[ 1, 0 ], &[ 1, 1 ], &[ 1, 1, 0 ] ] );
+    assert_eq!( tick.status_of( &[ 1, 1, 0 ] ), Some( BehaviorStatus::Success ) );
+
+    // the failing condition and the inverter are the deepest failures
+    let failed : Vec< &[ usize ] > = tick.failed_nodes().iter().map( | node | node.path.as_slice() ).collect();
+    assert_eq!( failed, vec![ &[ 1, 0 ][ .. ], &[ 1, 1 ] ] );
+    assert_eq!( tick.failed_nodes()[ 0 ].name, "Check(enemy)" );
+
+    let accesses : Vec< _ > = tick.blackboard.iter().map( | access | ( access.kind, access.key.as_str(), access.value.clone() ) ).collect();
+    assert_eq!
+    (
+      accesses,
+      vec!
+      [
+        ( BlackboardAccessKind::Write, "alert", Some( BehaviorValue::Bool( true ) ) ),
+        ( BlackboardAccessKind::Read, "enemy", None ),
+        ( BlackboardAccessKind::Read, "alert", Some( BehaviorValue::Bool( true ) ) ),
+      ]
+    );
+    assert_eq!( tick.blackboard[ 1 ].path, vec![ 1, 0 ] );
+
+    // tracing is opt-in, untraced ticks leave tracer alone
+    assert_eq!( tree.execute( &mut context ), BehaviorStatus::Failure );
+    assert_eq!( tracer.ticks().count(), 1 );
+  }
+
+  #[ test ]
+  fn test_ring_buffer_and_running_path()
+  {
+    let mut tree = BehaviorTree::new( sequence( vec![ set_blackboard( "step", 1 ), wait( 100.0 ) ] ), "Slow".to_string() );
+    let mut context = BehaviorContext::new();
+    let mut tracer = Tracer::new( 2 );
+    for _ in 0..5
+    {
+      assert_eq!( tree.execute_traced( &mut context, &mut tracer ), BehaviorStatus::Running );
+    }
+
+    let ticks : Vec< u64 > = tracer.ticks().map( | tick | tick.tick ).collect();
+    assert_eq!( ticks, vec![ 3, 4 ] );
+    let running = tracer.last().unwrap().running_nodes();
+    assert_eq!( running.len(), 1 );
+    assert_eq!( running[ 0 ].path, vec![ 1 ] );
+    assert_eq!( running[ 0 ].name, "Wait(100.0s)" );
+  }
+
+  #[ test ]
+  fn test_trace_records_observed_conditions()
+  {
+    let mut tree = BehaviorTree::new
+    (
+      selector( vec!
+      [
+        conditional( condition( "enemy", true ), set_blackboard( "state", "fight" ), AbortMode::LowerPriority ),
+        wait( 100.0 ),
+      ]),
+      "Guard".to_string(),
+    );
+    let mut context = BehaviorContext::new();
+    let mut tracer = Tracer::new( 4 );
+    assert_eq!( tree.execute_traced( &mut context, &mut tracer ), BehaviorStatus::Running );
+    assert_eq!( tree.execute_traced( &mut context, &mut tracer ), BehaviorStatus::Running );
+
+    // condition re-checked while the wait runs is recorded under the conditional
+    let tick = tracer.last().unwrap();
+    assert_eq!( tick.nodes[ 1 ].path, vec![ 0, 0 ] );
+    assert_eq!( tick.nodes[ 1 ].name, "Check(enemy)" );
+    assert_eq!( tick.nodes[ 1 ].status, BehaviorStatus::Failure );
+    assert_eq!( tick.blackboard[ 0 ].path, vec![ 0, 0 ] );
+    assert_eq!( tick.running_nodes()[ 0 ].path, vec![ 1 ] );
+  }
+
+  #[ test ]
+  fn test_export()
+  {
+    let mut tree = guard_tree();
+    let mut context = BehaviorContext::new();
+    let mut tracer = Tracer::new( 4 );
+    let _ = tree.execute_traced( &mut context, &mut tracer );
+
+    let json = tracer.to_json();
+    assert!( json.starts_with( "{\"ticks\":[{\"tick\":0,\"status\":\"Failure\",\"nodes\":[{\"path\":[],\"name\":\"Sequence\"" ) );
+    assert!( json.contains( "{\"path\":[0],\"access\":\"Write\",\"key\":\"alert\",\"value\":{\"Bool\":true}}" ) );
+    assert!( json.contains( "\"key\":\"enemy\",\"value\":null" ) );
+
+    let dot = tracer.to_dot( &tree );
+    assert!( dot.starts_with( "digraph \"Guard\"\n{" ) );
+    assert!( dot.contains( "  n [label=\"Sequence\", fillcolor=\"#f29191\", style=\"rounded,filled\"];" ) );
+    assert!( dot.contains( "  n_1_1_0 [label=\"Check(alert)\", fillcolor=\"#9be29b\"" ) );
+    assert!( dot.contains( "  n_1 -> n_1_0;" ) );
+    assert!( dot.ends_with( "}\n" ) );
+  }
+}
@@ -0,0 +1,149 @@
+//!
+//! # Generation of stitches from vector shapes
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use format::{ pec::PEC_MAX_MOVE, split_move };
+  use stitch_instruction::{ Instruction, Stitch };
+
+  /// Point of vector shape in 0.1 mm, Y axis points down
+  pub type Point = ( f32, f32 );
+
+  /// Limits applied when generated stitches are added to embroidery file
+  #[ derive( Debug, Clone, Copy ) ]
+  pub struct StitchLimits
+  {
+    /// Longer stitches are split, in 0.1 mm. Can't exceed `PEC_MAX_MOVE`
+    pub max_stitch : f32,
+    /// Moves to the start of a run longer than this are trimmed and jumped, shorter ones are stitched, in 0.1 mm
+    pub trim_distance : f32,
+  }
+
+  impl Default for StitchLimits
+  {
+    fn default() -> Self
+    {
+      Self { max_stitch : 121.0, trim_distance : 30.0 }
+    }
+  }
+
+  /// Sews run of needle points into `emb`, starting from the current needle position.
+  /// The needle gets to the first point with a stitch if it is close, otherwise the thread is trimmed
+  /// and the needle jumps there. Stitches and jumps longer than the limits are split
+  pub fn sew( emb : &mut EmbroideryFile, points : &[ Point ], limits : &StitchLimits )
+  {
+    let Some( &first ) = points.first() else { return; };
+    let max_stitch = ( limits.max_stitch.round() as i32 ).clamp( 1, PEC_MAX_MOVE );
+    let first = round( first );
+
+    match emb.stitches().last().map( | s | ( s.x, s.y ) )
+    {
+      // Machine starts at origin, there is no thread to trim yet
+      None => move_to( emb, ( 0, 0 ), first, PEC_MAX_MOVE, Instruction::Jump ),
+      Some( needle ) =>
+      {
+        let distance = ( ( first.0 - needle.0 ) as f32 ).hypot( ( first.1 - needle.1 ) as f32 );
+        if distance > limits.trim_distance
+        {
+          emb.trim();
+          move_to( emb, needle, first, PEC_MAX_MOVE, Instruction::Jump );
+        }
+        else
+        {
+          move_to( emb, needle, first, max_stitch, Instruction::Stitch );
+        }
+      }
+    }
+    // Run always starts with a penetration
+    if emb.stitches().last().map_or( true, | s | s.instruction != Instruction::Stitch )
+    {
+      emb.add_stitch_absolute( Stitch { x : first.0, y : first.1, instruction : Instruction::Stitch } );
+    }
+
+    let mut needle = first;
+    for &point in &points[ 1.. ]
+    {
+      let point = round( point );
+      move_to( emb, needle, point, max_stitch, Instruction::Stitch );
+      needle = point;
+    }
+  }
+
+  /// Adds `instruction` moves from `from` to `to`, none of which is longer than `max` along either axis
+  fn move_to( emb : &mut EmbroideryFile, from : ( i32, i32 ), to : ( i32, i32 ), max : i32, instruction : Instruction )
+  {
+    if from == to
+    {
+      return;
+    }
+    let ( mut x, mut y ) = from;
+    for ( dx, dy ) in split_move( to.0 - from.0, to.1 - from.1, max )
+    {
+      x += dx;
+      y += dy;
+      emb.add_stitch_absolute( Stitch { x, y, instruction } );
+    }
+  }
+
+  fn round( p : Point ) -> ( i32, i32 )
+  {
+    ( p.0.round() as i32, p.1.round() as i32 )
+  }
+
+  /// Distance between two points
+  pub fn distance( a : Point, b : Point ) -> f32
+  {
+    ( b.0 - a.0 ).hypot( b.1 - a.1 )
+  }
+
+  /// Point between `a` and `b` at fraction `t` of the way
+  pub fn lerp( a : Point, b : Point, t : f32 ) -> Point
+  {
+    ( a.0 + ( b.0 - a.0 ) * t, a.1 + ( b.1 - a.1 ) * t )
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::*;
+
+    #[ test ]
+    fn test_sew()
+    {
+      let mut emb = EmbroideryFile::new();
+      let limits = StitchLimits::default();
+      sew( &mut emb, &[ ( 10.0, 0.0 ), ( 300.0, 0.0 ) ], &limits );
+      // close run is reached by stitching, far one by trim and jump
+      sew( &mut emb, &[ ( 310.0, 0.0 ), ( 310.0, 10.0 ) ], &limits );
+      sew( &mut emb, &[ ( 310.0, 500.0 ) ], &limits );
+
+      let stitches = emb.stitches();
+      assert_eq!( stitches[ 0 ], Stitch { x : 10, y : 0, instruction : Instruction::Jump } );
+      assert_eq!( stitches[ 1 ], Stitch { x : 10, y : 0, instruction : Instruction::Stitch } );
+      // 290 units long stitch is split in three
+      assert_eq!( stitches[ 4 ], Stitch { x : 300, y : 0, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 5 ], Stitch { x : 310, y : 0, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 6 ], Stitch { x : 310, y : 10, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 7 ], Stitch { x : 310, y : 10, instruction : Instruction::Trim } );
+      assert_eq!( stitches[ 8 ], Stitch { x : 310, y : 500, instruction : Instruction::Jump } );
+      assert_eq!( stitches[ 9 ], Stitch { x : 310, y : 500, instruction : Instruction::Stitch } );
+      assert_eq!( stitches.len(), 10 );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  layer running;
+  layer fill;
+  layer satin;
+
+  own use Point;
+  own use StitchLimits;
+  own use sew;
+  own use distance;
+  own use lerp;
+}
@@ -0,0 +1,332 @@
+//!
+//! # Tatami fill of closed shapes
+//!
+
+mod private
+{
+  use crate::*;
+  use digitize::Point;
+
+  /// Sparse fill sewn before the top layer to stabilize fabric
+  #[ derive( Debug, Clone, Copy ) ]
+  pub struct FillUnderlay
+  {
+    /// Angle of underlay rows relative to the top fill rows, in degrees
+    pub angle : f32,
+    /// Distance between underlay rows, in 0.1 mm
+    pub spacing : f32,
+    /// Distance underlay rows keep from the edges of the shape, in 0.1 mm
+    pub inset : f32,
+  }
+
+  impl Default for FillUnderlay
+  {
+    fn default() -> Self
+    {
+      Self { angle : 90.0, spacing : 20.0, inset : 5.0 }
+    }
+  }
+
+  /// Parameters of tatami fill
+  #[ derive( Debug, Clone, Copy ) ]
+  pub struct FillOptions
+  {
+    /// Direction of rows, in degrees, counted from X axis towards Y axis
+    pub angle : f32,
+    /// Distance between rows, in 0.1 mm. Smaller values give denser fill
+    pub spacing : f32,
+    /// Length of stitches along rows, in 0.1 mm
+    pub stitch_length : f32,
+    /// Underlay sewn before the fill, if any
+    pub underlay : Option< FillUnderlay >,
+  }
+
+  impl Default for FillOptions
+  {
+    fn default() -> Self
+    {
+      Self
+      {
+        angle : 0.0,
+        spacing : 4.0,
+        stitch_length : 30.0,
+        underlay : Some( FillUnderlay::default() ),
+      }
+    }
+  }
+
+  /// Fills closed shape with tatami stitches.
+  /// `shape` is a list of rings, where the first is outline and the others are holes,
+  /// rings don't need to repeat their first point at the end.
+  /// Points where stitches pierce neighbouring rows are shifted, so they don't line up into visible lines.
+  /// # Returns
+  /// Runs of needle points, which are sewn continuously. Underlay runs go first
+  pub fn tatami_fill( shape : &[ Vec< Point > ], options : &FillOptions ) -> Vec< Vec< Point > >
+  {
+    let mut ret = vec![];
+    if let Some( underlay ) = options.underlay
+    {
+      let rows = RowParams
+      {
+        angle : options.angle + underlay.angle,
+        spacing : underlay.spacing,
+        stitch_length : options.stitch_length,
+        inset : underlay.inset,
+      };
+      ret.extend( fill_rows( shape, &rows ) );
+    }
+    let rows = RowParams
+    {
+      angle : options.angle,
+      spacing : options.spacing,
+      stitch_length : options.stitch_length,
+      inset : 0.0,
+    };
+    ret.extend( fill_rows( shape, &rows ) );
+
+    ret
+  }
+
+  struct RowParams
+  {
+    angle : f32,
+    spacing : f32,
+    stitch_length : f32,
+    inset : f32,
+  }
+
+  /// Segment of a row inside of the shape
+  #[ derive( Clone, Copy ) ]
+  struct Span
+  {
+    row : usize,
+    start : f32,
+    end : f32,
+  }
+
+  /// Fills shape with parallel rows, grouping rows into sections that are sewn back and forth
+  fn fill_rows( shape : &[ Vec< Point > ], params : &RowParams ) -> Vec< Vec< Point > >
+  {
+    let angle = params.angle.to_radians();
+    let spacing = params.spacing.max( 1.0 );
+    let stitch_length = params.stitch_length.max( 1.0 );
+    // Shape is rotated so rows go along X axis
+    let rings : Vec< Vec< Point > > = shape.iter().map( | r | r.iter().map( | p | rotate( *p, -angle ) ).collect() ).collect();
+    let ( min_y, max_y ) = rings
+    .iter()
+    .flatten()
+    .fold( ( f32::MAX, f32::MIN ), | ( min, max ), p | ( min.min( p.1 ), max.max( p.1 ) ) );
+    if min_y > max_y
+    {
+      return vec![];
+    }
+
+    // Sections are runs of spans where each next span is in the next row and overlaps previous one
+    let mut sections : Vec< Vec< Span > > = vec![];
+    let mut open : Vec< usize > = vec![];
+    let row_count = ( ( max_y - min_y ) / spacing ).floor() as usize + 1;
+    for row in 0..row_count
+    {
+      let y = min_y + spacing * ( row as f32 + 0.5 );
+      let mut extended = vec![];
+      for ( start, end ) in inset_spans( &rings, y, params.inset )
+      {
+        let span = Span { row, start, end };
+        let continued = open.iter().position( | &s |
+        {
+          let last = sections[ s ].last().unwrap();
+          last.row + 1 == row && last.start < end && last.end > start
+        });
+        match continued
+        {
+          Some( i ) =>
+          {
+            let section = open.swap_remove( i );
+            sections[ section ].push( span );
+            extended.push( section );
+          },
+          None =>
+          {
+            sections.push( vec![ span ] );
+            extended.push( sections.len() - 1 );
+          }
+        }
+      }
+      open = extended;
+    }
+
+    sections
+    .iter()
+    .map( | section |
+    {
+      section
+      .iter()
+      .enumerate()
+      .flat_map( | ( i, span ) |
+      {
+        let y = min_y + spacing * ( span.row as f32 + 0.5 );
+        let mut xs = row_stitches( span, stitch_length );
+        if i % 2 == 1
+        {
+          xs.reverse();
+        }
+        xs.into_iter().map( move | x | rotate( ( x, y ), angle ) )
+      })
+      .collect()
+    })
+    .collect()
+  }
+
+  /// Intersections of horizontal line at `y` with the shape, paired into spans by even-odd rule
+  fn row_spans( rings : &[ Vec< Point > ], y : f32 ) -> Vec< ( f32, f32 ) >
+  {
+    let mut xs = vec![];
+    for ring in rings
+    {
+      for i in 0..ring.len()
+      {
+        let a = ring[ i ];
+        let b = ring[ ( i + 1 ) % ring.len() ];
+        if ( a.1 <= y ) != ( b.1 <= y )
+        {
+          xs.push( a.0 + ( y - a.1 ) * ( b.0 - a.0 ) / ( b.1 - a.1 ) );
+        }
+      }
+    }
+    xs.sort_by( f32::total_cmp );
+    xs.chunks_exact( 2 ).map( | c | ( c[ 0 ], c[ 1 ] ) ).collect()
+  }
+
+  /// Spans of the row at `y` that keep `inset` away from the edges of the shape.
+  /// Along the row spans are shrunk at both ends, across rows they are cut to the parts
+  /// that stay inside of the shape `inset` above and below, so rows near edges running along them are dropped
+  fn inset_spans( rings : &[ Vec< Point > ], y : f32, inset : f32 ) -> Vec< ( f32, f32 ) >
+  {
+    let shrink = | spans : Vec< ( f32, f32 ) > |
+    {
+      spans
+      .into_iter()
+      .map( | ( start, end ) | ( start + inset, end - inset ) )
+      .filter( | ( start, end ) | start < end )
+      .collect::< Vec< _ > >()
+    };
+    let mut spans = shrink( row_spans( rings, y ) );
+    if inset <= 0.0
+    {
+      return spans;
+    }
+    for neighbour in [ y - inset, y + inset ]
+    {
+      let other = shrink( row_spans( rings, neighbour ) );
+      spans = spans
+      .iter()
+      .flat_map( | &( start, end ) |
+      {
+        other
+        .iter()
+        .map( move | &( other_start, other_end ) | ( start.max( other_start ), end.min( other_end ) ) )
+        .filter( | ( start, end ) | start < end )
+      })
+      .collect();
+    }
+    spans
+  }
+
+  /// Needle positions along a span. Grid of positions is shifted by a third of stitch length every row
+  fn row_stitches( span : &Span, stitch_length : f32 ) -> Vec< f32 >
+  {
+    let offset = ( span.row % 3 ) as f32 * stitch_length / 3.0;
+    // Stitches much shorter than this are merged into neighbouring ones
+    let min_length = stitch_length / 4.0;
+    let mut xs = vec![ span.start ];
+    let mut x = ( ( span.start - offset ) / stitch_length ).ceil() * stitch_length + offset;
+    while x < span.end - min_length
+    {
+      if x > span.start + min_length
+      {
+        xs.push( x );
+      }
+      x += stitch_length;
+    }
+    xs.push( span.end );
+    xs
+  }
+
+  fn rotate( p : Point, angle : f32 ) -> Point
+  {
+    let ( sin, cos ) = angle.sin_cos();
+    ( p.0 * cos - p.1 * sin, p.0 * sin + p.1 * cos )
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::*;
+    use digitize::distance;
+
+    fn square( min : f32, max : f32 ) -> Vec< Point >
+    {
+      vec![ ( min, min ), ( max, min ), ( max, max ), ( min, max ) ]
+    }
+
+    #[ test ]
+    fn test_square_fill()
+    {
+      let options = FillOptions { underlay : None, ..Default::default() };
+      let runs = tatami_fill( &[ square( 0.0, 100.0 ) ], &options );
+
+      assert_eq!( runs.len(), 1 );
+      let points = &runs[ 0 ];
+      assert!( points.iter().all( | p | ( -0.01..=100.01 ).contains( &p.0 ) && ( 0.0..=100.0 ).contains( &p.1 ) ) );
+      // 25 rows, each sewn from edge to edge
+      let mut rows : Vec< _ > = points.iter().map( | p | ( p.1 * 100.0 ).round() as i32 ).collect();
+      rows.dedup();
+      assert_eq!( rows.len(), 25 );
+      // stitches along rows don't exceed stitch length
+      assert!( points.windows( 2 ).all( | s | distance( s[ 0 ], s[ 1 ] ) <= 30.0 + 1e-3 ) );
+    }
+
+    #[ test ]
+    fn test_fill_angle_and_underlay()
+    {
+      let options = FillOptions { angle : 90.0, ..Default::default() };
+      let runs = tatami_fill( &[ square( 0.0, 100.0 ) ], &options );
+      assert_eq!( runs.len(), 2 );
+      // underlay rows are perpendicular to the fill, so they are horizontal and kept away from edges
+      assert!( runs[ 0 ].iter().all( | p | p.0 >= 5.0 - 1e-3 && p.0 <= 95.0 + 1e-3 ) );
+      // fill rows are vertical
+      assert!( ( runs[ 1 ][ 0 ].0 - runs[ 1 ][ 1 ].0 ).abs() < 1e-3 );
+    }
+
+    #[ test ]
+    fn test_underlay_inset_across_rows()
+    {
+      let options = FillOptions { underlay : Some( FillUnderlay { angle : 0.0, spacing : 2.0, inset : 10.0 } ), ..Default::default() };
+      let runs = tatami_fill( &[ square( 0.0, 100.0 ) ], &options );
+      let underlay = &runs[ 0 ];
+
+      // rows closer than the inset to the top and bottom edges are not sewn
+      assert!( underlay.iter().all( | p | p.0 >= 10.0 - 1e-3 && p.0 <= 90.0 + 1e-3 ) );
+      assert!( underlay.iter().all( | p | p.1 >= 10.0 - 1e-3 && p.1 <= 90.0 + 1e-3 ) );
+    }
+
+    #[ test ]
+    fn test_fill_with_hole()
+    {
+      let options = FillOptions { underlay : None, ..Default::default() };
+      let runs = tatami_fill( &[ square( 0.0, 100.0 ), square( 40.0, 60.0 ) ], &options );
+
+      // rows split around the hole form separate sections
+      assert!( runs.len() > 1 );
+      let inside_hole = | p : &&Point | p.0 > 40.5 && p.0 < 59.5 && p.1 > 40.5 && p.1 < 59.5;
+      assert!( runs.iter().flatten().find( inside_hole ).is_none() );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use FillUnderlay;
+  orphan use FillOptions;
+  orphan use tatami_fill;
+}
@@ -0,0 +1,94 @@
+//!
+//! # Running and bean stitches along a path
+//!
+
+mod private
+{
+  use crate::*;
+  use digitize::{ Point, distance, lerp };
+
+  /// Places stitches along `path` so that none is longer than `length`.
+  /// Corners of the path are always penetrated, segments are divided evenly
+  pub fn running_stitch( path : &[ Point ], length : f32 ) -> Vec< Point >
+  {
+    let Some( &first ) = path.first() else { return vec![]; };
+    let length = length.max( 1.0 );
+    let mut ret = vec![ first ];
+
+    for segment in path.windows( 2 )
+    {
+      let ( a, b ) = ( segment[ 0 ], segment[ 1 ] );
+      let d = distance( a, b );
+      if d == 0.0
+      {
+        continue;
+      }
+      let steps = ( d / length ).ceil() as usize;
+      for i in 1..=steps
+      {
+        ret.push( lerp( a, b, i as f32 / steps as f32 ) );
+      }
+    }
+
+    ret
+  }
+
+  /// Running stitch where every stitch is sewn `repeats` times back and forth,
+  /// which gives thicker line. Even `repeats` are rounded up to the next odd count, so the needle always moves forward
+  pub fn bean_stitch( path : &[ Point ], length : f32, repeats : usize ) -> Vec< Point >
+  {
+    let base = running_stitch( path, length );
+    let Some( &first ) = base.first() else { return vec![]; };
+    let back_and_forth = repeats.max( 1 ) / 2;
+    let mut ret = vec![ first ];
+
+    for stitch in base.windows( 2 )
+    {
+      ret.push( stitch[ 1 ] );
+      for _ in 0..back_and_forth
+      {
+        ret.push( stitch[ 0 ] );
+        ret.push( stitch[ 1 ] );
+      }
+    }
+
+    ret
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::*;
+
+    #[ test ]
+    fn test_running_stitch()
+    {
+      let path = [ ( 0.0, 0.0 ), ( 100.0, 0.0 ), ( 100.0, 25.0 ) ];
+      let points = running_stitch( &path, 30.0 );
+
+      assert_eq!( points.len(), 1 + 4 + 1 );
+      assert!( points.contains( &( 100.0, 0.0 ) ) );
+      assert_eq!( points.last(), Some( &( 100.0, 25.0 ) ) );
+      assert!( points.windows( 2 ).all( | s | distance( s[ 0 ], s[ 1 ] ) <= 30.0 ) );
+    }
+
+    #[ test ]
+    fn test_bean_stitch()
+    {
+      let path = [ ( 0.0, 0.0 ), ( 60.0, 0.0 ) ];
+      let points = bean_stitch( &path, 30.0, 3 );
+      assert_eq!
+      (
+        points,
+        vec![ ( 0.0, 0.0 ), ( 30.0, 0.0 ), ( 0.0, 0.0 ), ( 30.0, 0.0 ), ( 60.0, 0.0 ), ( 30.0, 0.0 ), ( 60.0, 0.0 ) ]
+      );
+      assert_eq!( bean_stitch( &path, 30.0, 1 ), running_stitch( &path, 30.0 ) );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use running_stitch;
+  orphan use bean_stitch;
+}
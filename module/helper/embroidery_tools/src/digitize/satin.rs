@@ -0,0 +1,171 @@
+//!
+//! # Satin columns along a path
+//!
+
+mod private
+{
+  use crate::*;
+  use digitize::{ Point, distance, lerp, running_stitch };
+
+  // Length of center walk underlay stitches, in 0.1 mm
+  const UNDERLAY_STITCH_LENGTH : f32 = 20.0;
+
+  /// Parameters of satin column
+  #[ derive( Debug, Clone, Copy ) ]
+  pub struct SatinOptions
+  {
+    /// Width of column, in 0.1 mm
+    pub width : f32,
+    /// Distance between neighbouring zigzags along the path, in 0.1 mm
+    pub spacing : f32,
+    /// Extra width added to the column, because sewn thread pulls fabric in and narrows it, in 0.1 mm
+    pub pull_compensation : f32,
+    /// Whether column is sewn over running stitch along its center
+    pub center_underlay : bool,
+  }
+
+  impl Default for SatinOptions
+  {
+    fn default() -> Self
+    {
+      Self
+      {
+        width : 30.0,
+        spacing : 4.0,
+        pull_compensation : 2.0,
+        center_underlay : true,
+      }
+    }
+  }
+
+  /// Sews zigzag across `path`, which is the center line of satin column.
+  /// With center underlay, the needle first walks the path to its end and the column is sewn back
+  pub fn satin_column( path : &[ Point ], options : &SatinOptions ) -> Vec< Point >
+  {
+    let mut path : Vec< Point > = path.to_vec();
+    path.dedup();
+    if path.len() < 2
+    {
+      return path;
+    }
+
+    let mut ret = vec![];
+    if options.center_underlay
+    {
+      ret = running_stitch( &path, UNDERLAY_STITCH_LENGTH );
+      path.reverse();
+    }
+
+    let half_width = ( options.width + options.pull_compensation ) / 2.0;
+    for ( center, normal ) in samples( &path, options.spacing.max( 1.0 ) )
+    {
+      ret.push( ( center.0 + normal.0 * half_width, center.1 + normal.1 * half_width ) );
+      ret.push( ( center.0 - normal.0 * half_width, center.1 - normal.1 * half_width ) );
+    }
+
+    ret
+  }
+
+  /// Points along the path `spacing` apart with unit normals at them.
+  /// Normals at corners are averaged from both adjacent segments
+  fn samples( path : &[ Point ], spacing : f32 ) -> Vec< ( Point, Point ) >
+  {
+    let lengths : Vec< f32 > = path.windows( 2 ).map( | s | distance( s[ 0 ], s[ 1 ] ) ).collect();
+    let total : f32 = lengths.iter().sum();
+    let count = ( total / spacing ).ceil().max( 1.0 ) as usize;
+
+    let mut ret = Vec::with_capacity( count + 1 );
+    let mut segment = 0;
+    let mut segment_start = 0.0;
+    for i in 0..=count
+    {
+      let at = total * i as f32 / count as f32;
+      while segment + 1 < lengths.len() && at > segment_start + lengths[ segment ]
+      {
+        segment_start += lengths[ segment ];
+        segment += 1;
+      }
+      let t = ( ( at - segment_start ) / lengths[ segment ] ).clamp( 0.0, 1.0 );
+      let center = lerp( path[ segment ], path[ segment + 1 ], t );
+
+      let mut normal = segment_normal( path, segment );
+      // Near the ends of inner segments the column turns, so normal is blended with the neighbour
+      if at <= segment_start && segment > 0
+      {
+        normal = average( normal, segment_normal( path, segment - 1 ) );
+      }
+      else if at >= segment_start + lengths[ segment ] && segment + 2 < path.len()
+      {
+        normal = average( normal, segment_normal( path, segment + 1 ) );
+      }
+      ret.push( ( center, normal ) );
+    }
+
+    ret
+  }
+
+  fn segment_normal( path : &[ Point ], segment : usize ) -> Point
+  {
+    let ( a, b ) = ( path[ segment ], path[ segment + 1 ] );
+    let length = distance( a, b );
+    ( -( b.1 - a.1 ) / length, ( b.0 - a.0 ) / length )
+  }
+
+  fn average( a : Point, b : Point ) -> Point
+  {
+    let sum = ( a.0 + b.0, a.1 + b.1 );
+    let length = sum.0.hypot( sum.1 );
+    if length < 1e-6
+    {
+      a
+    }
+    else
+    {
+      ( sum.0 / length, sum.1 / length )
+    }
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::*;
+
+    #[ test ]
+    fn test_straight_column()
+    {
+      let options = SatinOptions { center_underlay : false, ..Default::default() };
+      let points = satin_column( &[ ( 0.0, 0.0 ), ( 100.0, 0.0 ) ], &options );
+
+      // 26 zigzags, each goes across the column
+      assert_eq!( points.len(), 52 );
+      for pair in points.chunks_exact( 2 )
+      {
+        assert!( ( pair[ 0 ].1 - 16.0 ).abs() < 1e-3 );
+        assert!( ( pair[ 1 ].1 + 16.0 ).abs() < 1e-3 );
+        assert!( ( pair[ 0 ].0 - pair[ 1 ].0 ).abs() < 1e-3 );
+      }
+      assert!( ( points[ 2 ].0 - points[ 0 ].0 - 4.0 ).abs() < 1e-3 );
+    }
+
+    #[ test ]
+    fn test_column_with_underlay()
+    {
+      let path = [ ( 0.0, 0.0 ), ( 100.0, 0.0 ), ( 100.0, 100.0 ) ];
+      let points = satin_column( &path, &SatinOptions::default() );
+
+      // underlay walks to the end, then the column comes back to the start
+      let underlay = running_stitch( &path, UNDERLAY_STITCH_LENGTH );
+      assert_eq!( &points[ ..underlay.len() ], underlay.as_slice() );
+      let last = points.last().unwrap();
+      assert!( distance( *last, ( 0.0, 0.0 ) ) <= 16.0 + 1e-3 );
+      // every stitch of the column is within its width
+      assert!( points.iter().all( | p | p.0 >= -16.01 && p.0 <= 116.01 && p.1 >= -16.01 && p.1 <= 100.01 ) );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use SatinOptions;
+  orphan use satin_column;
+}
@@ -11,6 +11,10 @@ mod private
   use thread::*;
   use metadata::Metadata;
   use stitch_instruction::{ Instruction, Stitch };
+  use error::EmbroideryError;
+  use format::Format;
+  use std::{ io, path::Path };
+  use io::{ BufReader, BufWriter, Cursor, Read, Seek, SeekFrom, Write };
 
   /// Represents embroidery file. Stores embroidery instructions, threads and metadata
   #[ derive( Debug, Clone ) ]
@@ -286,6 +290,107 @@ mod private
     }
   }
 
+  impl EmbroideryFile
+  {
+    /// Reads embroidery file at `path`.
+    /// Format is recognized by leading bytes of the file, extension is used
+    /// only for formats without signature, like EXP
+    pub fn read_file< P >( path : P ) -> Result< Self, EmbroideryError >
+    where
+      P : AsRef< Path >
+    {
+      let path = path.as_ref();
+      let mut reader = BufReader::new( std::fs::File::open( path )? );
+      let format = sniff_format( &mut reader )?
+      .or_else( || path.extension().and_then( | e | e.to_str() ).and_then( Format::from_extension ) )
+      .ok_or_else( || unsupported_format( path.display() ) )?;
+
+      Self::read_as( &mut reader, format )
+    }
+
+    /// Reads embroidery file from byte slice. Format is recognized by leading bytes
+    pub fn read_memory( mem : &[ u8 ] ) -> Result< Self, EmbroideryError >
+    {
+      Self::read( &mut Cursor::new( mem ) )
+    }
+
+    /// Reads embroidery file from `reader`. Format is recognized by leading bytes,
+    /// which are read from current position of `reader`
+    pub fn read< R >( reader : &mut R ) -> Result< Self, EmbroideryError >
+    where
+      R : Read + Seek
+    {
+      let format = sniff_format( reader )?.ok_or_else( || unsupported_format( "data" ) )?;
+      Self::read_as( reader, format )
+    }
+
+    /// Reads embroidery file of known `format` from `reader`
+    pub fn read_as< R >( reader : &mut R, format : Format ) -> Result< Self, EmbroideryError >
+    where
+      R : Read + Seek
+    {
+      match format
+      {
+        Format::Pec => format::pec::read( reader ),
+        Format::Pes( _ ) => format::pes::read( reader ),
+        Format::Dst => format::dst::read( reader ),
+        Format::Exp => format::exp::read( reader ),
+        Format::Jef => format::jef::read( reader ),
+        Format::Vp3 => format::vp3::read( reader ),
+      }
+    }
+
+    /// Writes embroidery file in `format` into `writer`.
+    /// Works on a copy, so colors are fixed and stops are turned into duplicate colors
+    /// without modifying `self`
+    pub fn write_as< W >( &self, writer : &mut W, format : Format ) -> Result< (), EmbroideryError >
+    where
+      W : Write + Seek
+    {
+      let mut emb = self.clone();
+      emb.fix_color_count();
+      emb.interpolate_stop_as_duplicate_color();
+
+      match format
+      {
+        Format::Pec => format::pec::write( &mut emb, writer ),
+        Format::Pes( version ) => format::pes::write( &mut emb, writer, version ),
+        Format::Dst => format::dst::write( &mut emb, writer ),
+        Format::Exp => format::exp::write( &mut emb, writer ),
+        Format::Jef => format::jef::write( &mut emb, writer ),
+        Format::Vp3 => format::vp3::write( &mut emb, writer ),
+      }
+    }
+
+    /// Writes embroidery file in `format` at `path`
+    pub fn write_file_as< P >( &self, path : P, format : Format ) -> Result< (), EmbroideryError >
+    where
+      P : AsRef< Path >
+    {
+      let mut writer = BufWriter::new( std::fs::File::create( path )? );
+      self.write_as( &mut writer, format )?;
+      writer.flush()?;
+      Ok( () )
+    }
+  }
+
+  /// Detects format by leading bytes and rewinds `reader` back to where it was
+  fn sniff_format< R >( reader : &mut R ) -> Result< Option< Format >, EmbroideryError >
+  where
+    R : Read + Seek
+  {
+    let start = reader.stream_position()?;
+    let mut header = Vec::with_capacity( Format::SIGNATURE_LEN );
+    reader.by_ref().take( Format::SIGNATURE_LEN as u64 ).read_to_end( &mut header )?;
+    reader.seek( SeekFrom::Start( start ) )?;
+    Ok( Format::detect( &header ) )
+  }
+
+  fn unsupported_format( source : impl core::fmt::Display ) -> EmbroideryError
+  {
+    EmbroideryError::UnsupportedFormatError( format!( "Can't recognize embroidery format of {source}" ).into() )
+  }
+
   impl Default for EmbroideryFile
   {
     fn default() -> Self
@@ -325,6 +430,58 @@ mod private
       assert_eq!( stitches[ 0 ], Stitch { x : 10, y : 20, instruction: Instruction::Stitch } );
       assert_eq!( stitches[ 1 ], Stitch { x : 30, y : 40, instruction: Instruction::Stitch } );
     }
+
+    #[ test ]
+    fn test_convert_between_formats()
+    {
+      let emb = EmbroideryFile::read_file( "test_files/pes_test_v6.pes" ).unwrap();
+      let expected_stitches : Vec< _ > = emb.stitches()
+      .iter()
+      .filter( | s | s.instruction == Instruction::Stitch )
+      .map( | s | ( s.x, s.y ) )
+      .collect();
+
+      let formats =
+      [
+        Format::Pec,
+        Format::Pes( format::pes::PESVersion::V1 ),
+        Format::Pes( format::pes::PESVersion::V6 ),
+        Format::Dst,
+        Format::Jef,
+        Format::Vp3,
+      ];
+      for format in formats
+      {
+        let mut memory = vec![];
+        emb.write_as( &mut Cursor::new( &mut memory ), format ).unwrap();
+
+        let converted = EmbroideryFile::read_memory( &memory ).unwrap();
+        let stitches : Vec< _ > = converted.stitches()
+        .iter()
+        .filter( | s | s.instruction == Instruction::Stitch )
+        .map( | s | ( s.x, s.y ) )
+        .collect();
+        assert_eq!( stitches.first(), expected_stitches.first(), "{format:?}" );
+        assert_eq!( stitches.last(), expected_stitches.last(), "{format:?}" );
+      }
+    }
+
+    #[ test ]
+    fn test_exp_needs_format_hint()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.stitch( 10, 10 );
+      emb.end();
+
+      let mut memory = vec![];
+      emb.write_as( &mut Cursor::new( &mut memory ), Format::Exp ).unwrap();
+
+      let result = EmbroideryFile::read_memory( &memory );
+      assert!( matches!( result, Err( EmbroideryError::UnsupportedFormatError( _ ) ) ) );
+
+      let emb = EmbroideryFile::read_as( &mut Cursor::new( &memory ), Format::Exp ).unwrap();
+      assert_eq!( emb.stitches()[ 0 ], Stitch { x : 10, y : 10, instruction : Instruction::Stitch } );
+    }
   }
 }
 
@@ -2,10 +2,189 @@
 //! # Readers and writers for embroidery formats
 //!
 
-mod private {}
+mod private
+{
+  use crate::*;
+  use format::pes::PESVersion;
+
+  /// Supported embroidery formats
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+  #[ non_exhaustive ]
+  pub enum Format
+  {
+    /// Brother PEC
+    Pec,
+    /// Brother PES of specific version
+    Pes( PESVersion ),
+    /// Tajima DST
+    Dst,
+    /// Melco EXP
+    Exp,
+    /// Janome JEF
+    Jef,
+    /// Pfaff VP3
+    Vp3,
+  }
+
+  impl Format
+  {
+    /// Amount of leading bytes `detect` needs to recognize any format
+    pub const SIGNATURE_LEN : usize = 28;
+
+    /// Recognizes format by leading bytes of a file.
+    /// EXP has no signature, so it is never detected this way
+    pub fn detect( header : &[ u8 ] ) -> Option< Self >
+    {
+      if header.starts_with( b"#PEC0001" )
+      {
+        Some( Self::Pec )
+      }
+      else if header.starts_with( b"#PES0001" )
+      {
+        Some( Self::Pes( PESVersion::V1 ) )
+      }
+      else if header.starts_with( b"#PES" )
+      {
+        Some( Self::Pes( PESVersion::V6 ) )
+      }
+      else if header.starts_with( b"%vsm%" )
+      {
+        Some( Self::Vp3 )
+      }
+      else if header.starts_with( b"LA:" )
+      {
+        Some( Self::Dst )
+      }
+      else if is_jef_header( header )
+      {
+        Some( Self::Jef )
+      }
+      else
+      {
+        None
+      }
+    }
+
+    /// Recognizes format by file extension, case insensitive. PES files are assumed to be version 6
+    pub fn from_extension( extension : &str ) -> Option< Self >
+    {
+      match extension.to_ascii_lowercase().as_str()
+      {
+        "pec" => Some( Self::Pec ),
+        "pes" => Some( Self::Pes( PESVersion::V6 ) ),
+        "dst" => Some( Self::Dst ),
+        "exp" => Some( Self::Exp ),
+        "jef" => Some( Self::Jef ),
+        "vp3" => Some( Self::Vp3 ),
+        _ => None,
+      }
+    }
+
+    /// Conventional file extension of the format
+    pub fn extension( self ) -> &'static str
+    {
+      match self
+      {
+        Self::Pec => "pec",
+        Self::Pes( _ ) => "pes",
+        Self::Dst => "dst",
+        Self::Exp => "exp",
+        Self::Jef => "jef",
+        Self::Vp3 => "vp3",
+      }
+    }
+  }
+
+  /// JEF has no magic bytes, but its first field is offset of instructions,
+  /// which is determined by size of fixed header and amount of threads
+  fn is_jef_header( header : &[ u8 ] ) -> bool
+  {
+    if header.len() < Format::SIGNATURE_LEN
+    {
+      return false;
+    }
+    let offset = u32::from_le_bytes( [ header[ 0 ], header[ 1 ], header[ 2 ], header[ 3 ] ] ) as u64;
+    let color_count = u32::from_le_bytes( [ header[ 24 ], header[ 25 ], header[ 26 ], header[ 27 ] ] ) as u64;
+    offset == 0x74 + color_count * 8
+  }
+
+  /// Splits relative move into steps which don't exceed `max` along either axis.
+  /// Steps are spread evenly, so the needle keeps to the line of the original move.
+  /// Used by formats with short displacement fields
+  pub fn split_move( dx : i32, dy : i32, max : i32 ) -> Vec< ( i32, i32 ) >
+  {
+    let longest = dx.abs().max( dy.abs() );
+    let steps = ( longest + max - 1 ) / max;
+    if steps <= 1
+    {
+      return vec![ ( dx, dy ) ];
+    }
+
+    let mut ret = Vec::with_capacity( steps as usize );
+    let mut prev_x = 0;
+    let mut prev_y = 0;
+    for i in 1..=steps
+    {
+      let x = dx * i / steps;
+      let y = dy * i / steps;
+      ret.push( ( x - prev_x, y - prev_y ) );
+      prev_x = x;
+      prev_y = y;
+    }
+
+    ret
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::{ split_move, Format };
+    use crate::format::pes::PESVersion;
+
+    #[ test ]
+    fn test_split_move()
+    {
+      assert_eq!( split_move( 10, -5, 121 ), vec![ ( 10, -5 ) ] );
+      assert_eq!( split_move( 0, 0, 121 ), vec![ ( 0, 0 ) ] );
+
+      let steps = split_move( 300, -100, 121 );
+      assert_eq!( steps.len(), 3 );
+      assert!( steps.iter().all( | ( x, y ) | x.abs() <= 121 && y.abs() <= 121 ) );
+      assert_eq!( steps.iter().map( | s | s.0 ).sum::< i32 >(), 300 );
+      assert_eq!( steps.iter().map( | s | s.1 ).sum::< i32 >(), -100 );
+    }
+
+    #[ test ]
+    fn test_detect_format()
+    {
+      assert_eq!( Format::detect( b"#PEC0001LA:" ), Some( Format::Pec ) );
+      assert_eq!( Format::detect( b"#PES0001\0\0\0\0" ), Some( Format::Pes( PESVersion::V1 ) ) );
+      assert_eq!( Format::detect( b"#PES0060\0\0\0\0" ), Some( Format::Pes( PESVersion::V6 ) ) );
+      assert_eq!( Format::detect( b"%vsm%\0" ), Some( Format::Vp3 ) );
+      assert_eq!( Format::detect( b"LA:Untitled        \r" ), Some( Format::Dst ) );
+
+      let mut jef = vec![ 0; Format::SIGNATURE_LEN ];
+      jef[ ..4 ].copy_from_slice( &( 0x74_u32 + 2 * 8 ).to_le_bytes() );
+      jef[ 24.. ].copy_from_slice( &2_u32.to_le_bytes() );
+      assert_eq!( Format::detect( &jef ), Some( Format::Jef ) );
+      jef[ 24 ] = 3;
+      assert_eq!( Format::detect( &jef ), None );
+
+      assert_eq!( Format::from_extension( "EXP" ), Some( Format::Exp ) );
+      assert_eq!( Format::from_extension( "svg" ), None );
+    }
+  }
+}
 
 crate::mod_interface!
 {
   layer pec;
   layer pes;
+  layer dst;
+  layer exp;
+  layer jef;
+  layer vp3;
+
+  own use Format;
+  own use split_move;
 }
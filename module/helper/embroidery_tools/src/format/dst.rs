@@ -0,0 +1,11 @@
+//!
+//! # DST format reader and writer
+//!
+
+mod private {}
+
+crate::mod_interface!
+{
+  layer reader;
+  layer writer;
+}
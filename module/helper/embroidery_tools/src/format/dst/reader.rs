@@ -0,0 +1,256 @@
+//!
+//! # DST format reader.
+//! Original implementation refers to https://github.com/EmbroidePy/pyembroidery/blob/main/pyembroidery/DstReader.py
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use error::EmbroideryError;
+  use thread::{ Color, Thread };
+  use std::{ io, path::Path };
+  use io::{ BufReader, Cursor, Read, Seek };
+
+  // DST header is a fixed block of `\r` separated text fields
+  const HEADER_SIZE : usize = 512;
+  // Tajima machines cut the thread after this many jumps in a row
+  const JUMPS_PER_TRIM : usize = 3;
+  // ( byte, bit, value ) of every displacement flag in a 3-byte record
+  const X_BITS : [ ( usize, u8, i32 ); 10 ] =
+  [
+    ( 2, 2, 81 ), ( 2, 3, -81 ),
+    ( 1, 2, 27 ), ( 1, 3, -27 ),
+    ( 0, 2, 9 ), ( 0, 3, -9 ),
+    ( 1, 0, 3 ), ( 1, 1, -3 ),
+    ( 0, 0, 1 ), ( 0, 1, -1 ),
+  ];
+  const Y_BITS : [ ( usize, u8, i32 ); 10 ] =
+  [
+    ( 2, 5, 81 ), ( 2, 4, -81 ),
+    ( 1, 5, 27 ), ( 1, 4, -27 ),
+    ( 0, 5, 9 ), ( 0, 4, -9 ),
+    ( 1, 7, 3 ), ( 1, 6, -3 ),
+    ( 0, 7, 1 ), ( 0, 6, -1 ),
+  ];
+
+  /// Kind of a 3-byte DST record
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+  enum Record
+  {
+    Stitch,
+    Jump,
+    ColorChange,
+    End,
+  }
+
+  /// Reads DST file at `path`
+  pub fn read_file< P >( path : P ) -> Result< EmbroideryFile, EmbroideryError >
+  where
+    P : AsRef< Path >
+  {
+    let file = std::fs::File::open( path )?;
+    let mut reader = BufReader::new( file );
+    read( &mut reader )
+  }
+
+  /// Reads DST file from byte slice
+  pub fn read_memory( mem : &[ u8 ] ) -> Result< EmbroideryFile, EmbroideryError >
+  {
+    let mut reader = Cursor::new( mem );
+    read( &mut reader )
+  }
+
+  /// Reads DST file from `reader`.
+  /// DST has no trim instruction, so every run of three or more jumps is read as a trim followed by these jumps.
+  /// Sequin commands have no counterpart in `Instruction` and are read as jumps
+  pub fn read< R >( reader : &mut R ) -> Result< EmbroideryFile, EmbroideryError >
+  where
+    R : Read + Seek
+  {
+    let mut header = [ 0; HEADER_SIZE ];
+    reader.read_exact( &mut header )?;
+    if !header.starts_with( b"LA:" )
+    {
+      return Err( EmbroideryError::DecodingError( "Not DST header encountered".into() ) );
+    }
+
+    let mut emb = EmbroideryFile::new();
+    read_header( &mut emb, &header );
+
+    let mut records = vec![];
+    reader.read_to_end( &mut records )?;
+    read_dst_instructions( &mut emb, &records );
+
+    // Threads are present only in extended header, in which case
+    // stops are encoded as repeated colors like in other formats
+    if !emb.threads().is_empty()
+    {
+      emb.interpolate_duplicate_color_as_stop();
+    }
+
+    Ok( emb )
+  }
+
+  /// Reads known fields of DST header
+  fn read_header( emb : &mut EmbroideryFile, header : &[ u8 ] )
+  {
+    // Header text ends with 0x1A, the rest is padding
+    let end = header.iter().position( | b | *b == 0x1A ).unwrap_or( header.len() );
+    let text = String::from_utf8_lossy( &header[ ..end ] );
+
+    for field in text.split( '\r' )
+    {
+      let Some( ( key, value ) ) = field.split_once( ':' ) else { continue; };
+      let value = value.trim();
+      match key
+      {
+        "LA" if !value.is_empty() => emb.get_mut_metadata().set_name( Some( value.to_owned() ) ),
+        "AU" => emb.get_mut_metadata().insert_text( "author", value.to_owned() ),
+        "CP" => emb.get_mut_metadata().insert_text( "copyright", value.to_owned() ),
+        "TC" =>
+        {
+          if let Some( thread ) = parse_thread( value )
+          {
+            emb.add_thread( thread );
+          }
+        }
+        _ => {}
+      }
+    }
+  }
+
+  /// Parses thread of extended header. Its format is `#RRGGBB,Description,Catalog`
+  fn parse_thread( value : &str ) -> Option< Thread >
+  {
+    let mut parts = value.split( ',' );
+    let hex = parts.next()?.trim().strip_prefix( '#' )?;
+    if hex.len() != 6
+    {
+      return None;
+    }
+    let rgb = u32::from_str_radix( hex, 16 ).ok()?;
+    let description = parts.next().unwrap_or_default().trim().to_owned();
+    let catalog_number = parts.next().unwrap_or_default().trim().to_owned();
+
+    Some
+    (
+      Thread
+      {
+        color : Color { r : ( rgb >> 16 ) as u8, g : ( rgb >> 8 ) as u8, b : rgb as u8 },
+        description : description.into(),
+        catalog_number : catalog_number.into(),
+        ..Default::default()
+      }
+    )
+  }
+
+  /// Reads machine instructions section
+  fn read_dst_instructions( emb : &mut EmbroideryFile, records : &[ u8 ] )
+  {
+    let records : Vec< _ > = records.chunks_exact( 3 ).collect();
+    let kinds : Vec< _ > = records.iter().map( | r | record_kind( r[ 2 ] ) ).collect();
+
+    for ( i, record ) in records.iter().enumerate()
+    {
+      let dx = decode( record, &X_BITS );
+      // DST Y axis points up
+      let dy = -decode( record, &Y_BITS );
+
+      match kinds[ i ]
+      {
+        Record::Stitch => emb.stitch( dx, dy ),
+        Record::Jump =>
+        {
+          let run_start = i == 0 || kinds[ i - 1 ] != Record::Jump;
+          if run_start && kinds[ i.. ].iter().take_while( | k | **k == Record::Jump ).count() >= JUMPS_PER_TRIM
+          {
+            emb.trim();
+          }
+          emb.jump( dx, dy );
+        }
+        Record::ColorChange => emb.color_change( dx, dy ),
+        Record::End => break,
+      }
+    }
+    emb.end();
+  }
+
+  /// Determines kind of a record by its control byte
+  fn record_kind( b2 : u8 ) -> Record
+  {
+    if b2 & 0b1111_0011 == 0b1111_0011
+    {
+      Record::End
+    }
+    else if b2 & 0b1100_0011 == 0b1100_0011
+    {
+      Record::ColorChange
+    }
+    // Second condition covers sequin mode switch
+    else if b2 & 0b1100_0011 == 0b1000_0011 || b2 & 0b1100_0011 == 0b0100_0011
+    {
+      Record::Jump
+    }
+    else
+    {
+      Record::Stitch
+    }
+  }
+
+  /// Sums values of flags set in a record
+  fn decode( record : &[ u8 ], bits : &[ ( usize, u8, i32 ) ] ) -> i32
+  {
+    bits
+    .iter()
+    .filter( | ( byte, bit, _ ) | record[ *byte ] >> bit & 1 != 0 )
+    .map( | ( _, _, value ) | value )
+    .sum()
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use crate::*;
+    use stitch_instruction::{ Instruction, Stitch };
+    use super::{ read_memory, HEADER_SIZE };
+
+    #[ test ]
+    fn test_read_records()
+    {
+      let mut memory = b"LA:Sample         \rTC:#FF8000,Orange,1234\r\x1A".to_vec();
+      memory.resize( HEADER_SIZE, b' ' );
+      memory.extend_from_slice
+      (
+        &[
+          0b0101_0101, 0b0101_0101, 0b0001_0111, // stitch 121, -121 (Y points up)
+          0b0000_0000, 0b0000_0000, 0b1000_0011, // jump 0, 0
+          0b0000_0010, 0b0000_0000, 0b0000_0011, // stitch -1, 0
+          0b0000_0000, 0b0000_0000, 0b1100_0011, // color change
+          0b0000_0000, 0b0000_0000, 0b1111_0011, // end
+        ]
+      );
+
+      let emb = read_memory( &memory ).unwrap();
+      let stitches = emb.stitches();
+
+      assert_eq!( emb.get_metadata().get_name(), Some( "Sample" ) );
+      assert_eq!( emb.threads()[ 0 ].color, thread::Color { r : 255, g : 128, b : 0 } );
+      assert_eq!( emb.threads()[ 0 ].description, "Orange" );
+      assert_eq!( emb.threads()[ 0 ].catalog_number, "1234" );
+
+      assert_eq!( stitches[ 0 ], Stitch { x : 121, y : 121, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 1 ], Stitch { x : 121, y : 121, instruction : Instruction::Jump } );
+      assert_eq!( stitches[ 2 ], Stitch { x : 120, y : 121, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 3 ], Stitch { x : 120, y : 121, instruction : Instruction::ColorChange } );
+      assert_eq!( stitches[ 4 ], Stitch { x : 120, y : 121, instruction : Instruction::End } );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use read_file;
+  orphan use read_memory;
+  orphan use read;
+}
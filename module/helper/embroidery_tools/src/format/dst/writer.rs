@@ -0,0 +1,310 @@
+//!
+//! # DST format writer.
+//! Original implementation refers to https://github.com/EmbroidePy/pyembroidery/blob/main/pyembroidery/DstWriter.py
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use error::EmbroideryError;
+  use format::split_move;
+  use stitch_instruction::Instruction;
+  use std::io::{ Seek, Write };
+
+  const HEADER_SIZE : usize = 512;
+  // Longest displacement that fits into one record
+  const MAX_MOVE : i32 = 121;
+  // Control bytes
+  const STITCH_CODE : u8 = 0b0000_0011;
+  const JUMP_CODE : u8 = 0b1000_0011;
+  const COLOR_CHANGE_CODE : u8 = 0b1100_0011;
+  const END_CODE : u8 = 0b1111_0011;
+  // ( byte, bit, value ) of every displacement flag in a 3-byte record
+  const X_BITS : [ ( usize, u8, i32 ); 10 ] =
+  [
+    ( 2, 2, 81 ), ( 2, 3, -81 ),
+    ( 1, 2, 27 ), ( 1, 3, -27 ),
+    ( 0, 2, 9 ), ( 0, 3, -9 ),
+    ( 1, 0, 3 ), ( 1, 1, -3 ),
+    ( 0, 0, 1 ), ( 0, 1, -1 ),
+  ];
+  const Y_BITS : [ ( usize, u8, i32 ); 10 ] =
+  [
+    ( 2, 5, 81 ), ( 2, 4, -81 ),
+    ( 1, 5, 27 ), ( 1, 4, -27 ),
+    ( 0, 5, 9 ), ( 0, 4, -9 ),
+    ( 1, 7, 3 ), ( 1, 6, -3 ),
+    ( 0, 7, 1 ), ( 0, 6, -1 ),
+  ];
+
+  /// Writes DST format into `writer`.
+  /// Moves longer than 12.1 mm are split into several records, trims are written as three jumps
+  /// which return the needle to its position. Stops are written as color changes.
+  /// Returns `EmbroideryError::CompatibilityError` for instructions that DST can't express
+  pub fn write< W >( emb : &mut EmbroideryFile, writer : &mut W )
+  -> Result< (), EmbroideryError >
+  where
+    W : Write + Seek
+  {
+    if emb.stitches().is_empty()
+    {
+      emb.end();
+    }
+
+    emb.fix_color_count();
+    emb.interpolate_stop_as_duplicate_color();
+
+    let mut records = vec![];
+    let color_changes = write_dst_instructions( emb, &mut records )?;
+    write_dst_header( emb, writer, records.len() / 3, color_changes )?;
+    writer.write_all( &records )?;
+
+    Ok( () )
+  }
+
+  /// Writes 512-byte header. Threads and author are written as extended header fields while there is space left
+  fn write_dst_header< W >( emb : &EmbroideryFile, writer : &mut W, record_count : usize, color_changes : usize )
+  -> Result< (), EmbroideryError >
+  where
+    W : Write
+  {
+    let ( min_x, min_y, max_x, max_y ) = emb.bounds();
+    let ( last_x, last_y ) = emb.stitches().last().map_or( ( 0, 0 ), | s | ( s.x, s.y ) );
+    // DST Y axis points up
+    let last_y = -last_y;
+    // `LA:` is a fixed 16-byte field: anything but printable ASCII would shift every field after it
+    let mut name = [ b' '; 16 ];
+    let printable = emb.get_metadata().get_name().unwrap_or( "Untitled" ).chars()
+    .map( | c | if c == ' ' || c.is_ascii_graphic() { c as u8 } else { b'_' } );
+    for ( slot, byte ) in name.iter_mut().zip( printable )
+    {
+      *slot = byte;
+    }
+    let sign = | v : i32 | if v < 0 { '-' } else { '+' };
+
+    let mut header = b"LA:".to_vec();
+    header.extend_from_slice( &name );
+    header.extend_from_slice( format!
+    (
+      "\rST:{record_count:>7}\rCO:{color_changes:>3}\r\
+      +X:{:>5}\r-X:{:>5}\r+Y:{:>5}\r-Y:{:>5}\r\
+      AX:{}{:>5}\rAY:{}{:>5}\rMX:+{:>5}\rMY:+{:>5}\rPD:******\r",
+      max_x.abs(), min_x.abs(), min_y.abs(), max_y.abs(),
+      sign( last_x ), last_x.abs(), sign( last_y ), last_y.abs(), 0, 0,
+    )
+    .as_bytes() );
+
+    let metadata = emb.get_metadata();
+    let mut extended = vec![];
+    if let Some( author ) = metadata.get_text( "author" )
+    {
+      extended.push( format!( "AU:{author}\r" ) );
+    }
+    if let Some( copyright ) = metadata.get_text( "copyright" )
+    {
+      extended.push( format!( "CP:{copyright}\r" ) );
+    }
+    for thread in emb.threads()
+    {
+      let c = thread.color;
+      extended.push( format!( "TC:#{:02X}{:02X}{:02X},{},{}\r", c.r, c.g, c.b, thread.description, thread.catalog_number ) );
+    }
+    for field in extended
+    {
+      // One byte is reserved for the terminator
+      if header.len() + field.len() >= HEADER_SIZE
+      {
+        break;
+      }
+      header.extend_from_slice( field.as_bytes() );
+    }
+
+    header.push( 0x1A );
+    header.resize( HEADER_SIZE, b' ' );
+    writer.write_all( &header )?;
+
+    Ok( () )
+  }
+
+  /// Encodes instructions into `out`.
+  /// # Returns
+  /// Amount of color changes
+  fn write_dst_instructions( emb : &EmbroideryFile, out : &mut Vec< u8 > )
+  -> Result< usize, EmbroideryError >
+  {
+    let mut color_changes = 0;
+    let mut xx = 0;
+    let mut yy = 0;
+
+    for stitch in emb.stitches()
+    {
+      let dx = stitch.x - xx;
+      let dy = stitch.y - yy;
+
+      match stitch.instruction
+      {
+        Instruction::Stitch =>
+        {
+          for ( x, y ) in split_move( dx, dy, MAX_MOVE )
+          {
+            out.extend_from_slice( &encode_record( x, y, STITCH_CODE ) );
+          }
+        },
+        Instruction::Jump => write_jumps( out, dx, dy ),
+        Instruction::Trim =>
+        {
+          write_jumps( out, dx, dy );
+          out.extend_from_slice( &encode_record( 2, 2, JUMP_CODE ) );
+          out.extend_from_slice( &encode_record( -4, -4, JUMP_CODE ) );
+          out.extend_from_slice( &encode_record( 2, 2, JUMP_CODE ) );
+        },
+        Instruction::ColorChange | Instruction::Stop =>
+        {
+          write_jumps( out, dx, dy );
+          out.extend_from_slice( &encode_record( 0, 0, COLOR_CHANGE_CODE ) );
+          color_changes += 1;
+        },
+        Instruction::End => break,
+        Instruction::NoInstruction => continue,
+        instruction =>
+        {
+          let msg = format!( "{instruction:?} instruction is not supported by DST" );
+          return Err( EmbroideryError::CompatibilityError( msg.into() ) );
+        }
+      }
+
+      xx = stitch.x;
+      yy = stitch.y;
+    }
+    out.extend_from_slice( &encode_record( 0, 0, END_CODE ) );
+
+    Ok( color_changes )
+  }
+
+  /// Writes jumps that move needle by `dx`, `dy`. Nothing is written for zero displacement
+  fn write_jumps( out : &mut Vec< u8 >, dx : i32, dy : i32 )
+  {
+    if dx == 0 && dy == 0
+    {
+      return;
+    }
+    for ( x, y ) in split_move( dx, dy, MAX_MOVE )
+    {
+      out.extend_from_slice( &encode_record( x, y, JUMP_CODE ) );
+    }
+  }
+
+  /// Encodes one record. Displacement must not exceed `MAX_MOVE`
+  fn encode_record( dx : i32, dy : i32, code : u8 ) -> [ u8; 3 ]
+  {
+    let mut record = [ 0, 0, code ];
+    encode_axis( &mut record, dx, &X_BITS );
+    // DST Y axis points up
+    encode_axis( &mut record, -dy, &Y_BITS );
+    record
+  }
+
+  /// Sets flags of one axis. Each pair of flags is a balanced ternary digit
+  fn encode_axis( record : &mut [ u8; 3 ], mut value : i32, bits : &[ ( usize, u8, i32 ) ] )
+  {
+    for &( byte, bit, weight ) in bits
+    {
+      let half = ( weight.abs() - 1 ) / 2;
+      if ( weight > 0 && value > half ) || ( weight < 0 && value < -half )
+      {
+        record[ byte ] |= 1 << bit;
+        value -= weight;
+      }
+    }
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use crate::*;
+    use format::dst::{ read_memory, write };
+    use embroidery_file::EmbroideryFile;
+    use error::EmbroideryError;
+    use stitch_instruction::{ Stitch, Instruction };
+    use thread::{ Color, Thread };
+    use std::io::Cursor;
+
+    #[ test ]
+    fn test_dst_encoding()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.get_mut_metadata().set_name( Some( "Tajima".into() ) );
+      emb.stitch( 0, 0 );
+      emb.stitch( -2, -3 );
+      emb.stitch( 300, 100 );
+      emb.color_change( 0, 0 );
+      emb.trim();
+      emb.jump( 40, 30 );
+      emb.stitch( 0, 0 );
+      emb.stitch( 1, 1 );
+      emb.end();
+
+      let red = Thread { color : Color { r : 255, g : 0, b : 0 }, description : "Red".into(), ..Default::default() };
+      let blue = Thread { color : Color { r : 0, g : 0, b : 255 }, description : "Blue".into(), ..Default::default() };
+      emb.add_thread( red.clone() );
+      emb.add_thread( blue.clone() );
+
+      let mut memory = vec![];
+      write( &mut emb, &mut Cursor::new( &mut memory ) ).unwrap();
+      assert_eq!( memory.len() % 3, 512 % 3 );
+
+      let emb = read_memory( &memory ).unwrap();
+      assert_eq!( emb.get_metadata().get_name(), Some( "Tajima" ) );
+      assert_eq!( emb.threads()[ 0 ].color, red.color );
+      assert_eq!( emb.threads()[ 1 ].color, blue.color );
+
+      let stitches : Vec< _ > = emb.stitches().iter().filter( | s | s.instruction != Instruction::Jump ).copied().collect();
+      assert_eq!( stitches[ 0 ], Stitch { x : 0, y : 0, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 1 ], Stitch { x : -2, y : -3, instruction : Instruction::Stitch } );
+      // long stitch is split into three records
+      assert_eq!( stitches[ 2 ].instruction, Instruction::Stitch );
+      assert_eq!( stitches[ 3 ].instruction, Instruction::Stitch );
+      assert_eq!( stitches[ 4 ], Stitch { x : 298, y : 97, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 5 ], Stitch { x : 298, y : 97, instruction : Instruction::ColorChange } );
+      assert_eq!( stitches[ 6 ], Stitch { x : 298, y : 97, instruction : Instruction::Trim } );
+      assert_eq!( stitches[ 7 ], Stitch { x : 338, y : 127, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 8 ], Stitch { x : 339, y : 128, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 9 ], Stitch { x : 339, y : 128, instruction : Instruction::End } );
+    }
+
+    #[ test ]
+    fn test_dst_header_name_is_sixteen_ascii_bytes()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.get_mut_metadata().set_name( Some( "Über\rRosé garden design".into() ) );
+      emb.stitch( 0, 0 );
+      emb.end();
+
+      let mut memory = vec![];
+      write( &mut emb, &mut Cursor::new( &mut memory ) ).unwrap();
+      assert_eq!( &memory[ ..19 ], b"LA:_ber_Ros_ garden" );
+      assert_eq!( &memory[ 19..23 ], b"\rST:" );
+
+      let emb = read_memory( &memory ).unwrap();
+      assert_eq!( emb.get_metadata().get_name(), Some( "_ber_Ros_ garden" ) );
+    }
+
+    #[ test ]
+    fn test_dst_incompatible_instruction()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.stitch( 10, 10 );
+      emb.add_stitch_relative( Stitch { x : 0, y : 0, instruction : Instruction::NeedleSet } );
+      emb.end();
+
+      let result = write( &mut emb, &mut Cursor::new( vec![] ) );
+      assert!( matches!( result, Err( EmbroideryError::CompatibilityError( _ ) ) ) );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use write;
+}
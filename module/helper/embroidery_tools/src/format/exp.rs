@@ -0,0 +1,11 @@
+//!
+//! # EXP format reader and writer
+//!
+
+mod private {}
+
+crate::mod_interface!
+{
+  layer reader;
+  layer writer;
+}
@@ -0,0 +1,142 @@
+//!
+//! # EXP format reader.
+//! Original implementation refers to https://github.com/EmbroidePy/pyembroidery/blob/main/pyembroidery/ExpReader.py
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use error::EmbroideryError;
+  use std::{ io, path::Path };
+  use io::{ BufReader, Cursor, Read, Seek };
+
+  // Byte that starts two-word control instruction
+  const CONTROL : u8 = 0x80;
+  // Control codes
+  const COLOR_CHANGE_CODE : u8 = 0x01;
+  const STITCH_CODE : u8 = 0x02;
+  const JUMP_CODE : u8 = 0x04;
+  const TRIM_CODE : u8 = 0x80;
+
+  /// Reads EXP file at `path`
+  pub fn read_file< P >( path : P ) -> Result< EmbroideryFile, EmbroideryError >
+  where
+    P : AsRef< Path >
+  {
+    let file = std::fs::File::open( path )?;
+    let mut reader = BufReader::new( file );
+    read( &mut reader )
+  }
+
+  /// Reads EXP file from byte slice
+  pub fn read_memory( mem : &[ u8 ] ) -> Result< EmbroideryFile, EmbroideryError >
+  {
+    let mut reader = Cursor::new( mem );
+    read( &mut reader )
+  }
+
+  /// Reads EXP file from `reader`. EXP has neither header nor threads, only instructions
+  pub fn read< R >( reader : &mut R ) -> Result< EmbroideryFile, EmbroideryError >
+  where
+    R : Read + Seek
+  {
+    let mut bytes = vec![];
+    reader.read_to_end( &mut bytes )?;
+
+    let mut emb = EmbroideryFile::new();
+    let mut words = bytes.chunks_exact( 2 );
+    while let Some( word ) = words.next()
+    {
+      if word[ 0 ] != CONTROL
+      {
+        // EXP Y axis points up
+        emb.stitch( signed8( word[ 0 ] ), -signed8( word[ 1 ] ) );
+        continue;
+      }
+
+      let Some( delta ) = words.next() else { break; };
+      let x = signed8( delta[ 0 ] );
+      let y = -signed8( delta[ 1 ] );
+      match word[ 1 ]
+      {
+        TRIM_CODE => emb.trim(),
+        STITCH_CODE => emb.stitch( x, y ),
+        JUMP_CODE => emb.jump( x, y ),
+        COLOR_CHANGE_CODE =>
+        {
+          emb.color_change( 0, 0 );
+          if x != 0 || y != 0
+          {
+            emb.jump( x, y );
+          }
+        }
+        code =>
+        {
+          let msg = format!( "Unknown EXP control code: {code:#04X}" );
+          return Err( EmbroideryError::DecodingError( msg.into() ) );
+        }
+      }
+    }
+    emb.end();
+
+    Ok( emb )
+  }
+
+  /// Extracts signed integer stored in `u8` into `i32`
+  fn signed8( b : u8 ) -> i32
+  {
+    if b > 127
+    {
+      -256 + b as i32
+    }
+    else
+    {
+      b as i32
+    }
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use crate::*;
+    use stitch_instruction::{ Instruction, Stitch };
+    use super::read_memory;
+
+    #[ test ]
+    fn test_read_words()
+    {
+      let memory =
+      [
+        0x0A, 0xF6,             // stitch 10, 10
+        0x80, 0x04, 0x7F, 0x00, // jump 127, 0
+        0x80, 0x80, 0x07, 0x00, // trim
+        0x80, 0x01, 0x00, 0x00, // color change
+        0xFF, 0x01,             // stitch -1, -1
+      ];
+
+      let emb = read_memory( &memory ).unwrap();
+      let stitches = emb.stitches();
+
+      assert_eq!( stitches[ 0 ], Stitch { x : 10, y : 10, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 1 ], Stitch { x : 137, y : 10, instruction : Instruction::Jump } );
+      assert_eq!( stitches[ 2 ], Stitch { x : 137, y : 10, instruction : Instruction::Trim } );
+      assert_eq!( stitches[ 3 ], Stitch { x : 137, y : 10, instruction : Instruction::ColorChange } );
+      assert_eq!( stitches[ 4 ], Stitch { x : 136, y : 9, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 5 ], Stitch { x : 136, y : 9, instruction : Instruction::End } );
+    }
+
+    #[ test ]
+    fn test_unknown_control_code()
+    {
+      assert!( read_memory( &[ 0x80, 0x55, 0x00, 0x00 ] ).is_err() );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use read_file;
+  orphan use read_memory;
+  orphan use read;
+}
@@ -0,0 +1,151 @@
+//!
+//! # EXP format writer.
+//! Original implementation refers to https://github.com/EmbroidePy/pyembroidery/blob/main/pyembroidery/ExpWriter.py
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use error::EmbroideryError;
+  use format::split_move;
+  use stitch_instruction::Instruction;
+  use std::io::{ Seek, Write };
+
+  // Longest displacement that fits into one signed byte without clashing with `CONTROL`
+  const MAX_MOVE : i32 = 127;
+  const CONTROL : u8 = 0x80;
+  // Control codes
+  const COLOR_CHANGE_CODE : u8 = 0x01;
+  const JUMP_CODE : u8 = 0x04;
+  const TRIM_CODE : u8 = 0x80;
+
+  /// Writes EXP format into `writer`.
+  /// EXP doesn't store threads, so stops are written as color changes.
+  /// Returns `EmbroideryError::CompatibilityError` for instructions that EXP can't express
+  pub fn write< W >( emb : &mut EmbroideryFile, writer : &mut W )
+  -> Result< (), EmbroideryError >
+  where
+    W : Write + Seek
+  {
+    let mut xx = 0;
+    let mut yy = 0;
+
+    for stitch in emb.stitches()
+    {
+      let dx = stitch.x - xx;
+      let dy = stitch.y - yy;
+
+      match stitch.instruction
+      {
+        Instruction::Stitch =>
+        {
+          for ( x, y ) in split_move( dx, dy, MAX_MOVE )
+          {
+            // EXP Y axis points up
+            writer.write_all( &[ x as u8, -y as u8 ] )?;
+          }
+        },
+        Instruction::Jump => write_jumps( writer, dx, dy )?,
+        Instruction::Trim =>
+        {
+          write_jumps( writer, dx, dy )?;
+          writer.write_all( &[ CONTROL, TRIM_CODE, 0x07, 0x00 ] )?;
+        },
+        Instruction::ColorChange | Instruction::Stop =>
+        {
+          write_jumps( writer, dx, dy )?;
+          writer.write_all( &[ CONTROL, COLOR_CHANGE_CODE, 0x00, 0x00 ] )?;
+        },
+        Instruction::End => break,
+        Instruction::NoInstruction => continue,
+        instruction =>
+        {
+          let msg = format!( "{instruction:?} instruction is not supported by EXP" );
+          return Err( EmbroideryError::CompatibilityError( msg.into() ) );
+        }
+      }
+
+      xx = stitch.x;
+      yy = stitch.y;
+    }
+
+    Ok( () )
+  }
+
+  /// Writes jumps that move needle by `dx`, `dy`. Nothing is written for zero displacement
+  fn write_jumps< W >( writer : &mut W, dx : i32, dy : i32 )
+  -> Result< (), std::io::Error >
+  where
+    W : Write
+  {
+    if dx == 0 && dy == 0
+    {
+      return Ok( () );
+    }
+    for ( x, y ) in split_move( dx, dy, MAX_MOVE )
+    {
+      writer.write_all( &[ CONTROL, JUMP_CODE, x as u8, -y as u8 ] )?;
+    }
+
+    Ok( () )
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use crate::*;
+    use format::exp::{ read_memory, write };
+    use embroidery_file::EmbroideryFile;
+    use error::EmbroideryError;
+    use stitch_instruction::{ Stitch, Instruction };
+    use std::io::Cursor;
+
+    #[ test ]
+    fn test_exp_encoding()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.stitch( 0, 0 );
+      emb.stitch( -2, -3 );
+      emb.color_change( 0, 0 );
+      emb.stitch( 200, 0 );
+      emb.trim();
+      emb.jump( 40, 30 );
+      emb.stitch( 0, 0 );
+      emb.stitch( 1, 1 );
+      emb.end();
+
+      let mut memory = vec![];
+      write( &mut emb, &mut Cursor::new( &mut memory ) ).unwrap();
+
+      let emb = read_memory( &memory ).unwrap();
+      let stitches = emb.stitches();
+      assert_eq!( stitches[ 0 ], Stitch { x : 0, y : 0, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 1 ], Stitch { x : -2, y : -3, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 2 ], Stitch { x : -2, y : -3, instruction : Instruction::ColorChange } );
+      // long stitch is split in two
+      assert_eq!( stitches[ 3 ], Stitch { x : 98, y : -3, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 4 ], Stitch { x : 198, y : -3, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 5 ], Stitch { x : 198, y : -3, instruction : Instruction::Trim } );
+      assert_eq!( stitches[ 6 ], Stitch { x : 238, y : 27, instruction : Instruction::Jump } );
+      assert_eq!( stitches[ 7 ], Stitch { x : 238, y : 27, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 8 ], Stitch { x : 239, y : 28, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 9 ], Stitch { x : 239, y : 28, instruction : Instruction::End } );
+    }
+
+    #[ test ]
+    fn test_exp_incompatible_instruction()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.add_stitch_relative( Stitch { x : 5, y : 5, instruction : Instruction::SewTo } );
+
+      let result = write( &mut emb, &mut Cursor::new( vec![] ) );
+      assert!( matches!( result, Err( EmbroideryError::CompatibilityError( _ ) ) ) );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use write;
+}
@@ -0,0 +1,126 @@
+//!
+//! # JEF format reader and writer
+//!
+
+mod private
+{
+  use crate::*;
+  use thread::{ Thread, Color };
+
+  /// Default Janome thread palette. JEF stores indices into it instead of colors
+  pub fn jef_threads() -> [ Thread; 79 ]
+  {
+    macro_rules! jef_thread
+    {
+      ( $rgb:expr, $desc:expr, $catnum:expr ) =>
+      {
+        Thread
+        {
+          color : Color { r : ( $rgb >> 16 ) as u8, g : ( $rgb >> 8 ) as u8, b : $rgb as u8 },
+          description : $desc.into(),
+          catalog_number : $catnum.into(),
+          brand : "Janome".into(),
+          chart : "Janome".into(),
+          ..Default::default()
+        }
+      };
+    }
+
+    [
+      // This one is for indicating invalid value
+      Thread
+      {
+        color : Color { r : 0, g : 0, b : 0 },
+        description : "Unknown".into(),
+        catalog_number : "0".into(),
+        ..Default::default()
+      },
+      jef_thread!( 0x000000_u32, "Black", "002" ),
+      jef_thread!( 0xFFFFFF_u32, "White", "001" ),
+      jef_thread!( 0xFFFF17_u32, "Yellow", "204" ),
+      jef_thread!( 0xFF6600_u32, "Orange", "203" ),
+      jef_thread!( 0x2F5933_u32, "Olive Green", "219" ),
+      jef_thread!( 0x237336_u32, "Green", "226" ),
+      jef_thread!( 0x65C2C8_u32, "Sky", "217" ),
+      jef_thread!( 0xAB5A96_u32, "Purple", "208" ),
+      jef_thread!( 0xF669A0_u32, "Pink", "201" ),
+      jef_thread!( 0xFF0000_u32, "Red", "225" ),
+      jef_thread!( 0xB1704E_u32, "Brown", "214" ),
+      jef_thread!( 0x0B2F84_u32, "Blue", "207" ),
+      jef_thread!( 0xE4C35D_u32, "Gold", "003" ),
+      jef_thread!( 0x481A05_u32, "Dark Brown", "205" ),
+      jef_thread!( 0xAC9CC7_u32, "Pale Violet", "209" ),
+      jef_thread!( 0xFCF294_u32, "Pale Yellow", "210" ),
+      jef_thread!( 0xF999B7_u32, "Pale Pink", "211" ),
+      jef_thread!( 0xFAB381_u32, "Peach", "212" ),
+      jef_thread!( 0xC9A480_u32, "Beige", "213" ),
+      jef_thread!( 0x970533_u32, "Wine Red", "215" ),
+      jef_thread!( 0xA0B8CC_u32, "Pale Sky", "216" ),
+      jef_thread!( 0x7FC21C_u32, "Yellow Green", "218" ),
+      jef_thread!( 0xE5E5E5_u32, "Silver Gray", "220" ),
+      jef_thread!( 0x889B9B_u32, "Gray", "221" ),
+      jef_thread!( 0x98D6BD_u32, "Pale Aqua", "227" ),
+      jef_thread!( 0xB2E1E3_u32, "Baby Blue", "228" ),
+      jef_thread!( 0x368BA0_u32, "Powder Blue", "229" ),
+      jef_thread!( 0x4F83AB_u32, "Bright Blue", "230" ),
+      jef_thread!( 0x386A91_u32, "Slate Blue", "231" ),
+      jef_thread!( 0x071650_u32, "Navy Blue", "232" ),
+      jef_thread!( 0xF999A2_u32, "Salmon Pink", "233" ),
+      jef_thread!( 0xF9676B_u32, "Coral", "234" ),
+      jef_thread!( 0xE3311F_u32, "Burnt Orange", "235" ),
+      jef_thread!( 0xE2A188_u32, "Cinnamon", "236" ),
+      jef_thread!( 0xB59474_u32, "Umber", "237" ),
+      jef_thread!( 0xE4CF99_u32, "Blond", "238" ),
+      jef_thread!( 0xFFCB00_u32, "Sunflower", "239" ),
+      jef_thread!( 0xE1ADD4_u32, "Orchid Pink", "240" ),
+      jef_thread!( 0xC3007E_u32, "Peony Purple", "241" ),
+      jef_thread!( 0x80004B_u32, "Burgundy", "242" ),
+      jef_thread!( 0x540571_u32, "Royal Purple", "243" ),
+      jef_thread!( 0xB10525_u32, "Cardinal Red", "244" ),
+      jef_thread!( 0xCAE0C0_u32, "Opal Green", "245" ),
+      jef_thread!( 0x899856_u32, "Moss Green", "246" ),
+      jef_thread!( 0x5C941A_u32, "Meadow Green", "247" ),
+      jef_thread!( 0x003114_u32, "Dark Green", "248" ),
+      jef_thread!( 0x5DAE94_u32, "Aquamarine", "249" ),
+      jef_thread!( 0x4CBF8F_u32, "Emerald Green", "250" ),
+      jef_thread!( 0x007772_u32, "Peacock Green", "251" ),
+      jef_thread!( 0x595B61_u32, "Dark Gray", "252" ),
+      jef_thread!( 0xFFFFF2_u32, "Ivory White", "253" ),
+      jef_thread!( 0xB15818_u32, "Hazel", "254" ),
+      jef_thread!( 0xCB8A07_u32, "Toast", "255" ),
+      jef_thread!( 0x986C80_u32, "Salmon", "256" ),
+      jef_thread!( 0x98692D_u32, "Cocoa Brown", "257" ),
+      jef_thread!( 0x4D3419_u32, "Sienna", "258" ),
+      jef_thread!( 0x4C330B_u32, "Sepia", "259" ),
+      jef_thread!( 0x33200A_u32, "Dark Sepia", "260" ),
+      jef_thread!( 0x523A97_u32, "Violet Blue", "261" ),
+      jef_thread!( 0x0D217E_u32, "Blue Ink", "262" ),
+      jef_thread!( 0x1E77AC_u32, "Sola Blue", "263" ),
+      jef_thread!( 0xB2DD53_u32, "Green Dust", "264" ),
+      jef_thread!( 0xF33689_u32, "Crimson", "265" ),
+      jef_thread!( 0xDE649E_u32, "Floral Pink", "266" ),
+      jef_thread!( 0x984161_u32, "Wine", "267" ),
+      jef_thread!( 0x4C5612_u32, "Olive Drab", "268" ),
+      jef_thread!( 0x4C881F_u32, "Meadow", "269" ),
+      jef_thread!( 0xE4DE79_u32, "Mustard", "270" ),
+      jef_thread!( 0xCB8A1A_u32, "Yellow Ochre", "271" ),
+      jef_thread!( 0xCBA21C_u32, "Old Gold", "272" ),
+      jef_thread!( 0xFF9805_u32, "Honey Dew", "273" ),
+      jef_thread!( 0xFCB257_u32, "Tangerine", "274" ),
+      jef_thread!( 0xFFE505_u32, "Canary Yellow", "275" ),
+      jef_thread!( 0xF0331F_u32, "Vermilion", "202" ),
+      jef_thread!( 0x1A842D_u32, "Bright Green", "206" ),
+      jef_thread!( 0x386CAE_u32, "Ocean Blue", "222" ),
+      jef_thread!( 0xE3C4B4_u32, "Beige Gray", "223" ),
+      jef_thread!( 0xE3AC81_u32, "Bamboo", "224" ),
+    ]
+  }
+}
+
+crate::mod_interface!
+{
+  layer reader;
+  layer writer;
+
+  own use jef_threads;
+}
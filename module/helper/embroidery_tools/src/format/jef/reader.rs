@@ -0,0 +1,183 @@
+//!
+//! # JEF format reader.
+//! Original implementation refers to https://github.com/EmbroidePy/pyembroidery/blob/main/pyembroidery/JefReader.py
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use error::EmbroideryError;
+  use format::jef::jef_threads;
+  use std::{ io, path::Path };
+  use io::{ BufReader, Cursor, Read, Seek, SeekFrom };
+  use byteorder::{ ReadBytesExt as _, LE };
+
+  // Size of fixed part of header, thread table goes right after it
+  const HEADER_SIZE : u64 = 0x74;
+  // Byte that starts control instruction
+  const CONTROL : u8 = 0x80;
+  // Control codes
+  const COLOR_CHANGE_CODE : u8 = 0x01;
+  const JUMP_CODE : u8 = 0x02;
+  const END_CODE : u8 = 0x10;
+
+  /// Reads JEF file at `path`
+  pub fn read_file< P >( path : P ) -> Result< EmbroideryFile, EmbroideryError >
+  where
+    P : AsRef< Path >
+  {
+    let file = std::fs::File::open( path )?;
+    let mut reader = BufReader::new( file );
+    read( &mut reader )
+  }
+
+  /// Reads JEF file from byte slice
+  pub fn read_memory( mem : &[ u8 ] ) -> Result< EmbroideryFile, EmbroideryError >
+  {
+    let mut reader = Cursor::new( mem );
+    read( &mut reader )
+  }
+
+  /// Reads JEF file from `reader`. Zero-length jumps are read as trims
+  pub fn read< R >( reader : &mut R ) -> Result< EmbroideryFile, EmbroideryError >
+  where
+    R : Read + Seek
+  {
+    let mut emb = EmbroideryFile::new();
+
+    let stitch_offset = reader.read_u32::< LE >()? as u64;
+    reader.seek( SeekFrom::Current( 4 ) )?; // flags
+    let mut date = [ 0; 14 ];
+    reader.read_exact( &mut date )?;
+    if date.iter().any( | b | *b != b'0' && *b != 0 )
+    {
+      emb.get_mut_metadata().insert_text( "date", String::from_utf8_lossy( &date ).into_owned() );
+    }
+    reader.seek( SeekFrom::Current( 2 ) )?;
+    let color_count = reader.read_u32::< LE >()? as u64;
+
+    // Every thread takes two 4-byte fields: palette index and type
+    if stitch_offset < HEADER_SIZE + color_count * 8
+    {
+      return Err( EmbroideryError::DecodingError( "Not JEF header encountered".into() ) );
+    }
+
+    reader.seek( SeekFrom::Start( HEADER_SIZE ) )?;
+    let palette = jef_threads();
+    for _ in 0..color_count
+    {
+      let index = reader.read_i32::< LE >()?.unsigned_abs() as usize;
+      emb.add_thread( palette[ index % palette.len() ].clone() );
+    }
+
+    reader.seek( SeekFrom::Start( stitch_offset ) )?;
+    let mut bytes = vec![];
+    reader.read_to_end( &mut bytes )?;
+    read_jef_instructions( &mut emb, &bytes );
+
+    emb.interpolate_duplicate_color_as_stop();
+
+    Ok( emb )
+  }
+
+  /// Reads machine instructions section
+  fn read_jef_instructions( emb : &mut EmbroideryFile, bytes : &[ u8 ] )
+  {
+    let mut words = bytes.chunks_exact( 2 );
+    while let Some( word ) = words.next()
+    {
+      if word[ 0 ] != CONTROL
+      {
+        // JEF Y axis points up
+        emb.stitch( signed8( word[ 0 ] ), -signed8( word[ 1 ] ) );
+        continue;
+      }
+
+      // End has no displacement word
+      if word[ 1 ] == END_CODE
+      {
+        break;
+      }
+
+      let Some( delta ) = words.next() else { break; };
+      let x = signed8( delta[ 0 ] );
+      let y = -signed8( delta[ 1 ] );
+      match word[ 1 ]
+      {
+        JUMP_CODE if x == 0 && y == 0 => emb.trim(),
+        JUMP_CODE => emb.jump( x, y ),
+        COLOR_CHANGE_CODE => emb.color_change( x, y ),
+        _ => break,
+      }
+    }
+    emb.end();
+  }
+
+  /// Extracts signed integer stored in `u8` into `i32`
+  fn signed8( b : u8 ) -> i32
+  {
+    if b > 127
+    {
+      -256 + b as i32
+    }
+    else
+    {
+      b as i32
+    }
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use crate::*;
+    use format::jef::jef_threads;
+    use stitch_instruction::{ Instruction, Stitch };
+    use super::{ read_memory, HEADER_SIZE };
+
+    #[ test ]
+    fn test_read_threads_and_words()
+    {
+      let mut memory = vec![];
+      memory.extend_from_slice( &( HEADER_SIZE as u32 + 16 ).to_le_bytes() );
+      memory.extend_from_slice( &0x14_u32.to_le_bytes() );
+      memory.extend_from_slice( b"20240808120000\0\0" );
+      memory.extend_from_slice( &2_u32.to_le_bytes() );
+      memory.resize( HEADER_SIZE as usize, 0 );
+      memory.extend_from_slice( &10_i32.to_le_bytes() );
+      memory.extend_from_slice( &( -12_i32 ).to_le_bytes() );
+      memory.extend_from_slice( &[ 0x0D, 0, 0, 0, 0x0D, 0, 0, 0 ] );
+      memory.extend_from_slice
+      (
+        &[
+          0x0A, 0xF6,             // stitch 10, 10
+          0x80, 0x01, 0x00, 0x00, // color change
+          0x80, 0x02, 0x00, 0x00, // trim
+          0x80, 0x02, 0x14, 0xEC, // jump 20, 20
+          0x01, 0x01,             // stitch 1, -1
+          0x80, 0x10,             // end
+        ]
+      );
+
+      let emb = read_memory( &memory ).unwrap();
+      let palette = jef_threads();
+      assert_eq!( emb.get_metadata().get_text( "date" ), Some( "20240808120000" ) );
+      assert_eq!( emb.threads(), &[ palette[ 10 ].clone(), palette[ 12 ].clone() ] );
+
+      let stitches = emb.stitches();
+      assert_eq!( stitches[ 0 ], Stitch { x : 10, y : 10, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 1 ], Stitch { x : 10, y : 10, instruction : Instruction::ColorChange } );
+      assert_eq!( stitches[ 2 ], Stitch { x : 10, y : 10, instruction : Instruction::Trim } );
+      assert_eq!( stitches[ 3 ], Stitch { x : 30, y : 30, instruction : Instruction::Jump } );
+      assert_eq!( stitches[ 4 ], Stitch { x : 31, y : 29, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 5 ], Stitch { x : 31, y : 29, instruction : Instruction::End } );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use read_file;
+  orphan use read_memory;
+  orphan use read;
+}
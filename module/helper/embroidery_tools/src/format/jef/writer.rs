@@ -0,0 +1,286 @@
+//!
+//! # JEF format writer.
+//! Original implementation refers to https://github.com/EmbroidePy/pyembroidery/blob/main/pyembroidery/JefWriter.py
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use error::EmbroideryError;
+  use format::{ jef::jef_threads, split_move };
+  use stitch_instruction::Instruction;
+  use thread::build_unique_palette;
+  use std::io::{ Seek, Write };
+  use byteorder::{ WriteBytesExt as _, LE };
+
+  // Size of fixed part of header, thread table goes right after it
+  const HEADER_SIZE : u32 = 0x74;
+  // Longest displacement that fits into one signed byte without clashing with `CONTROL`
+  const MAX_MOVE : i32 = 127;
+  const CONTROL : u8 = 0x80;
+  // Control codes
+  const COLOR_CHANGE_CODE : u8 = 0x01;
+  const JUMP_CODE : u8 = 0x02;
+  const END_CODE : u8 = 0x10;
+  // Hoop codes
+  const HOOP_110X110 : u32 = 0;
+  const HOOP_50X50 : u32 = 2;
+  const HOOP_126X110 : u32 = 3;
+  const HOOP_140X200 : u32 = 1;
+  const HOOP_200X200 : u32 = 4;
+
+  /// Writes JEF format into `writer`.
+  /// Threads are mapped to the closest colors of Janome palette, trims are written as zero-length jumps.
+  /// Returns `EmbroideryError::CompatibilityError` for instructions that JEF can't express
+  pub fn write< W >( emb : &mut EmbroideryFile, writer : &mut W )
+  -> Result< (), EmbroideryError >
+  where
+    W : Write + Seek
+  {
+    if emb.stitches().is_empty()
+    {
+      emb.end();
+    }
+
+    emb.fix_color_count();
+    emb.interpolate_stop_as_duplicate_color();
+
+    let mut instructions = vec![];
+    let point_count = write_jef_instructions( emb, &mut instructions )?;
+    write_jef_header( emb, writer, point_count )?;
+    writer.write_all( &instructions )?;
+
+    Ok( () )
+  }
+
+  /// Writes header and thread table
+  fn write_jef_header< W >( emb : &EmbroideryFile, writer : &mut W, point_count : u32 )
+  -> Result< (), EmbroideryError >
+  where
+    W : Write
+  {
+    // Specs: https://edutechwiki.unige.ch/en/Embroidery_format_JEF
+    let color_count = emb.threads().len() as u32;
+    writer.write_u32::< LE >( HEADER_SIZE + color_count * 8 )?; // offset of instructions
+    writer.write_u32::< LE >( 0x14 )?; // flags
+
+    let date = emb.get_metadata().get_text( "date" ).filter( | d | d.len() == 14 ).unwrap_or( "00000000000000" );
+    writer.write_all( date.as_bytes() )?;
+    writer.write_all( &[ 0, 0 ] )?;
+
+    writer.write_u32::< LE >( color_count )?;
+    writer.write_u32::< LE >( point_count )?;
+
+    let ( min_x, min_y, max_x, max_y ) = emb.bounds();
+    let width = max_x - min_x;
+    let height = max_y - min_y;
+    writer.write_u32::< LE >( hoop_code( width, height ) )?;
+
+    let half_width = width / 2;
+    let half_height = height / 2;
+    // Distance from center of hoop
+    writer.write_i32::< LE >( half_width )?;
+    writer.write_i32::< LE >( half_height )?;
+    writer.write_i32::< LE >( half_width )?;
+    writer.write_i32::< LE >( half_height )?;
+    // Distances to the edges of 110x110, 50x50, 140x200 and 200x200 hoops
+    write_hoop_edge_distance( writer, 550 - half_width, 550 - half_height )?;
+    write_hoop_edge_distance( writer, 250 - half_width, 250 - half_height )?;
+    write_hoop_edge_distance( writer, 700 - half_width, 1000 - half_height )?;
+    write_hoop_edge_distance( writer, 1000 - half_width, 1000 - half_height )?;
+
+    // Palette skips first "Unknown" thread
+    let palette = jef_threads();
+    let color_indices = build_unique_palette( &palette[ 1.. ], emb.threads() );
+    for index in color_indices
+    {
+      writer.write_u32::< LE >( index as u32 + 1 )?;
+    }
+    for _ in 0..color_count
+    {
+      writer.write_u32::< LE >( 0x0D )?; // thread type
+    }
+
+    Ok( () )
+  }
+
+  /// Writes distances to the hoop edges or -1 if the design doesn't fit into it
+  fn write_hoop_edge_distance< W >( writer : &mut W, x : i32, y : i32 )
+  -> Result< (), std::io::Error >
+  where
+    W : Write
+  {
+    let ( x, y ) = if x.min( y ) >= 0 { ( x, y ) } else { ( -1, -1 ) };
+    writer.write_i32::< LE >( x )?;
+    writer.write_i32::< LE >( y )?;
+    writer.write_i32::< LE >( x )?;
+    writer.write_i32::< LE >( y )
+  }
+
+  /// Picks the smallest hoop the design fits into
+  fn hoop_code( width : i32, height : i32 ) -> u32
+  {
+    if width < 500 && height < 500
+    {
+      HOOP_50X50
+    }
+    else if width < 1260 && height < 1100
+    {
+      HOOP_126X110
+    }
+    else if width < 1400 && height < 2000
+    {
+      HOOP_140X200
+    }
+    else if width < 2000 && height < 2000
+    {
+      HOOP_200X200
+    }
+    else
+    {
+      HOOP_110X110
+    }
+  }
+
+  /// Encodes instructions into `out`.
+  /// # Returns
+  /// Amount of points, as counted by Janome software
+  fn write_jef_instructions( emb : &EmbroideryFile, out : &mut Vec< u8 > )
+  -> Result< u32, EmbroideryError >
+  {
+    // End takes one point
+    let mut point_count = 1;
+    let mut xx = 0;
+    let mut yy = 0;
+
+    for stitch in emb.stitches()
+    {
+      let dx = stitch.x - xx;
+      let dy = stitch.y - yy;
+
+      match stitch.instruction
+      {
+        Instruction::Stitch =>
+        {
+          for ( x, y ) in split_move( dx, dy, MAX_MOVE )
+          {
+            // JEF Y axis points up
+            out.extend_from_slice( &[ x as u8, -y as u8 ] );
+            point_count += 1;
+          }
+        },
+        Instruction::Jump => point_count += write_jumps( out, dx, dy ),
+        Instruction::Trim =>
+        {
+          point_count += write_jumps( out, dx, dy );
+          out.extend_from_slice( &[ CONTROL, JUMP_CODE, 0, 0 ] );
+          point_count += 2;
+        },
+        Instruction::ColorChange | Instruction::Stop =>
+        {
+          point_count += write_jumps( out, dx, dy );
+          out.extend_from_slice( &[ CONTROL, COLOR_CHANGE_CODE, 0, 0 ] );
+          point_count += 2;
+        },
+        Instruction::End => break,
+        Instruction::NoInstruction => continue,
+        instruction =>
+        {
+          let msg = format!( "{instruction:?} instruction is not supported by JEF" );
+          return Err( EmbroideryError::CompatibilityError( msg.into() ) );
+        }
+      }
+
+      xx = stitch.x;
+      yy = stitch.y;
+    }
+    out.extend_from_slice( &[ CONTROL, END_CODE ] );
+
+    Ok( point_count )
+  }
+
+  /// Writes jumps that move needle by `dx`, `dy`. Nothing is written for zero displacement,
+  /// because zero-length jump means trim in JEF.
+  /// # Returns
+  /// Amount of written jumps
+  fn write_jumps( out : &mut Vec< u8 >, dx : i32, dy : i32 ) -> u32
+  {
+    if dx == 0 && dy == 0
+    {
+      return 0;
+    }
+    let steps = split_move( dx, dy, MAX_MOVE );
+    for ( x, y ) in &steps
+    {
+      out.extend_from_slice( &[ CONTROL, JUMP_CODE, *x as u8, -*y as u8 ] );
+    }
+    steps.len() as u32
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use crate::*;
+    use format::jef::{ jef_threads, read_memory, write };
+    use embroidery_file::EmbroideryFile;
+    use error::EmbroideryError;
+    use stitch_instruction::{ Stitch, Instruction };
+    use std::io::Cursor;
+
+    #[ test ]
+    fn test_jef_encoding()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.stitch( 0, 0 );
+      emb.stitch( -2, -3 );
+      emb.color_change( 0, 0 );
+      emb.stitch( 2, 3 );
+      emb.trim();
+      emb.jump( 40, 300 );
+      emb.stitch( 0, 0 );
+      emb.stitch( 1, 1 );
+      emb.end();
+
+      let palette = jef_threads();
+      emb.add_thread( palette[ 3 ].clone() );
+      emb.add_thread( palette[ 12 ].clone() );
+
+      let mut memory = vec![];
+      write( &mut emb, &mut Cursor::new( &mut memory ) ).unwrap();
+
+      let emb = read_memory( &memory ).unwrap();
+      assert_eq!( emb.threads(), &[ palette[ 3 ].clone(), palette[ 12 ].clone() ] );
+
+      let stitches = emb.stitches();
+      assert_eq!( stitches[ 0 ], Stitch { x : 0, y : 0, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 1 ], Stitch { x : -2, y : -3, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 2 ], Stitch { x : -2, y : -3, instruction : Instruction::ColorChange } );
+      assert_eq!( stitches[ 3 ], Stitch { x : 0, y : 0, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 4 ], Stitch { x : 0, y : 0, instruction : Instruction::Trim } );
+      // long jump is split in three
+      assert_eq!( stitches[ 5 ].instruction, Instruction::Jump );
+      assert_eq!( stitches[ 6 ].instruction, Instruction::Jump );
+      assert_eq!( stitches[ 7 ], Stitch { x : 40, y : 300, instruction : Instruction::Jump } );
+      assert_eq!( stitches[ 8 ], Stitch { x : 40, y : 300, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 9 ], Stitch { x : 41, y : 301, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 10 ], Stitch { x : 41, y : 301, instruction : Instruction::End } );
+    }
+
+    #[ test ]
+    fn test_jef_incompatible_instruction()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.stitch( 10, 10 );
+      emb.add_stitch_relative( Stitch { x : 0, y : 0, instruction : Instruction::ColorBreak } );
+
+      let result = write( &mut emb, &mut Cursor::new( vec![] ) );
+      assert!( matches!( result, Err( EmbroideryError::CompatibilityError( _ ) ) ) );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use write;
+}
@@ -7,6 +7,10 @@ mod private
   use crate::*;
   use thread::{ Thread, Color };
 
+  /// Longest move PEC can encode along either axis, in 0.1 mm.
+  /// Long form of PEC instruction stores displacement in 12 bits
+  pub const PEC_MAX_MOVE : i32 = 2047;
+
   /// Default PEC thread palette
   pub fn pec_threads() -> [ Thread; 65 ]
   {
@@ -110,4 +114,5 @@ crate::mod_interface!
   layer writer;
 
   own use pec_threads;
+  own use PEC_MAX_MOVE;
 }
@@ -118,6 +118,9 @@ mod private
     read_pec_graphics( emb, reader, byte_size, pec_graphics_byte_stride, &threads );
 
     emb.interpolate_duplicate_color_as_stop();
+    // Marks read thumbnails as drawn from the read stitches
+    let key = render::pec_graphics_key( emb );
+    emb.get_mut_metadata().insert_text( render::PEC_GRAPHICS_KEY, key );
 
     Ok( () )
   }
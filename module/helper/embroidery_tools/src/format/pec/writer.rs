@@ -10,6 +10,7 @@ mod private
   use stitch_instruction::Instruction;
   use format::pec::pec_threads;
   use thread::build_unique_palette;
+  use metadata::Graphics;
   use byteorder::{ WriteBytesExt as _, LE };
   use std::io::{ Seek, SeekFrom, Write };
   use error::EmbroideryError;
@@ -308,18 +309,32 @@ mod private
     }
   }
 
-  /// This currently writes zeroes, not the actual thumbnails
+  /// Writes thumbnails stored in metadata if they were drawn from the current stitches,
+  /// otherwise thumbnails are rendered anew
   fn write_pec_graphics< W >( emb : &EmbroideryFile, writer : &mut W )
   -> Result< (), std::io::Error >
   where
     W : Write
   {
     // Thumbnail is bit image so 8 pixels is 1 byte
-    let size = ( PEC_ICON_WIDTH / 8 * PEC_ICON_HEIGHT ) as usize;
-    let zeroes = vec![ 0_u8; size ];
-    for _ in 0..( emb.threads().len() + 1 )
+    let size = ( PEC_ICON_WIDTH / 8 ) as usize * PEC_ICON_HEIGHT as usize;
+    let up_to_date = emb.get_metadata().get_text( render::PEC_GRAPHICS_KEY ) == Some( render::pec_graphics_key( emb ).as_str() );
+    let mut rendered = None;
+    for i in 0..( emb.threads().len() + 1 )
     {
-      writer.write_all( &zeroes )?;
+      match emb.get_metadata().get_graphics( &format!( "pec_graphic_{i}" ) )
+      {
+        Some( Graphics::PecGraphics { image, stride, .. } ) if up_to_date && *stride == PEC_ICON_WIDTH / 8 && image.len() == size =>
+        {
+          writer.write_all( image )?;
+        }
+        _ =>
+        {
+          let thumbnails = rendered.get_or_insert_with( || render::pec_thumbnails( emb ) );
+          let Graphics::PecGraphics { image, .. } = &thumbnails[ i ];
+          writer.write_all( image )?;
+        }
+      }
     }
 
     Ok( () )
@@ -375,6 +390,32 @@ mod private
       assert_eq!( emb.threads()[ 0 ], threads[ 2 ] );
       // assert_eq!( emb.threads()[ 1 ], threads[ 2 ] );
     }
+
+    #[ test ]
+    fn test_pec_graphics_follow_stitch_edits()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.stitch( 0, 0 );
+      emb.stitch( 100, 0 );
+      emb.end();
+      emb.add_thread( pec_threads()[ 0 ].clone() );
+      emb.add_thread( pec_threads()[ 1 ].clone() );
+      render::fill_pec_graphics( &mut emb );
+      let stale = emb.get_metadata().get_graphics( "pec_graphic_0" ).cloned().unwrap();
+
+      // Stored thumbnails no longer match the design once stitches change
+      emb.stitch( 100, 100 );
+      let mut memory = vec![];
+      write( &mut emb, &mut Cursor::new( &mut memory ) ).unwrap();
+      let read = read_memory( &memory ).unwrap();
+
+      let expected = render::pec_thumbnails( &emb );
+      let metadata::Graphics::PecGraphics { image : written, .. } = read.get_metadata().get_graphics( "pec_graphic_0" ).unwrap();
+      let metadata::Graphics::PecGraphics { image : fresh, .. } = &expected[ 0 ];
+      let metadata::Graphics::PecGraphics { image : stale, .. } = &stale;
+      assert_eq!( written, fresh );
+      assert_ne!( written, stale );
+    }
   }
 }
 
@@ -0,0 +1,274 @@
+//!
+//! # VP3 format reader.
+//! Original implementation refers to https://github.com/EmbroidePy/pyembroidery/blob/main/pyembroidery/Vp3Reader.py
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use error::EmbroideryError;
+  use stitch_instruction::{ Instruction, Stitch };
+  use thread::{ Color, Thread };
+  use std::{ io, path::Path };
+  use io::{ BufReader, Cursor, Read, Seek, SeekFrom };
+  use byteorder::{ ReadBytesExt as _, BE };
+
+  const MAGIC : &[ u8 ] = b"%vsm%\0";
+  const COLOR_BLOCK : [ u8; 3 ] = [ 0x00, 0x05, 0x00 ];
+  // Byte that starts control instruction
+  const CONTROL : u8 = 0x80;
+  // Control codes
+  const LONG_START_CODE : u8 = 0x01;
+  const TRIM_CODE : u8 = 0x03;
+  // Long moves further than this are jumps even without preceding trim
+  const TRIM_DISTANCE : i32 = 255;
+
+  /// Reads VP3 file at `path`
+  pub fn read_file< P >( path : P ) -> Result< EmbroideryFile, EmbroideryError >
+  where
+    P : AsRef< Path >
+  {
+    let file = std::fs::File::open( path )?;
+    let mut reader = BufReader::new( file );
+    read( &mut reader )
+  }
+
+  /// Reads VP3 file from byte slice
+  pub fn read_memory( mem : &[ u8 ] ) -> Result< EmbroideryFile, EmbroideryError >
+  {
+    let mut reader = Cursor::new( mem );
+    read( &mut reader )
+  }
+
+  /// Reads VP3 file from `reader`.
+  /// VP3 has no jump instruction, so long moves are read as jumps when they follow a trim
+  /// or are longer than 25.5 mm, and as stitches otherwise
+  pub fn read< R >( reader : &mut R ) -> Result< EmbroideryFile, EmbroideryError >
+  where
+    R : Read + Seek
+  {
+    let mut magic = [ 0; 6 ];
+    reader.read_exact( &mut magic )?;
+    if magic != MAGIC
+    {
+      return Err( EmbroideryError::DecodingError( "Not VP3 header encountered".into() ) );
+    }
+
+    let mut emb = EmbroideryFile::new();
+    _ = read_string16( reader )?; // software vendor
+
+    // File block: marker, length, notes, bounds, thread length and color count
+    reader.seek( SeekFrom::Current( 7 ) )?;
+    let comments = read_string16( reader )?;
+    if !comments.is_empty()
+    {
+      emb.get_mut_metadata().insert_text( "comments", comments );
+    }
+    reader.seek( SeekFrom::Current( 25 ) )?;
+
+    // Design block: marker, length, center
+    reader.seek( SeekFrom::Current( 7 ) )?;
+    let center_x = reader.read_i32::< BE >()? / 100;
+    let center_y = -reader.read_i32::< BE >()? / 100;
+    // extents and size
+    reader.seek( SeekFrom::Current( 27 ) )?;
+    _ = read_string16( reader )?;
+    // scale, transformation matrix and "xxPP" magic
+    reader.seek( SeekFrom::Current( 24 ) )?;
+    _ = read_string16( reader )?;
+
+    let count_colors = reader.read_u16::< BE >()?;
+    for i in 0..count_colors
+    {
+      read_color_block( &mut emb, reader, center_x, center_y )?;
+      if i + 1 < count_colors
+      {
+        emb.color_change( 0, 0 );
+      }
+    }
+    emb.end();
+
+    emb.interpolate_duplicate_color_as_stop();
+
+    Ok( emb )
+  }
+
+  /// Reads block of stitches sewn with the same thread
+  fn read_color_block< R >( emb : &mut EmbroideryFile, reader : &mut R, center_x : i32, center_y : i32 )
+  -> Result< (), EmbroideryError >
+  where
+    R : Read + Seek
+  {
+    let mut marker = [ 0; 3 ];
+    reader.read_exact( &mut marker )?;
+    if marker != COLOR_BLOCK
+    {
+      return Err( EmbroideryError::DecodingError( "VP3 color block expected".into() ) );
+    }
+    let block_len = reader.read_u32::< BE >()? as u64;
+    let block_end = reader.stream_position()? + block_len;
+
+    // Start position is relative to design center, Y axis points up
+    let x = reader.read_i32::< BE >()? / 100 + center_x;
+    let y = -reader.read_i32::< BE >()? / 100 + center_y;
+    emb.add_thread( read_vp3_thread( reader )? );
+
+    // block shift and stitch block marker
+    reader.seek( SeekFrom::Current( 11 ) )?;
+    let stitches_len = reader.read_u32::< BE >()? as usize;
+    let mut bytes = vec![ 0; stitches_len ];
+    reader.read_exact( &mut bytes )?;
+
+    if emb.stitches().last().map_or( ( 0, 0 ), | s | ( s.x, s.y ) ) != ( x, y )
+    {
+      emb.add_stitch_absolute( Stitch { x, y, instruction : Instruction::Jump } );
+    }
+    // First 3 bytes are unknown constant
+    read_vp3_instructions( emb, bytes.get( 3.. ).unwrap_or_default() );
+
+    reader.seek( SeekFrom::Start( block_end ) )?;
+    Ok( () )
+  }
+
+  /// Reads thread description of color block
+  fn read_vp3_thread< R >( reader : &mut R ) -> Result< Thread, EmbroideryError >
+  where
+    R : Read
+  {
+    let colors = reader.read_u8()?;
+    _ = reader.read_u8()?; // color transition
+    let mut color = Color::default();
+    for i in 0..colors
+    {
+      let rgb = reader.read_u24::< BE >()?;
+      _ = reader.read_u8()?; // parts
+      _ = reader.read_u16::< BE >()?; // color length
+      if i == 0
+      {
+        color = Color { r : ( rgb >> 16 ) as u8, g : ( rgb >> 8 ) as u8, b : rgb as u8 };
+      }
+    }
+    _ = reader.read_u8()?; // thread type
+    let weight = reader.read_u8()?;
+
+    Ok
+    (
+      Thread
+      {
+        color,
+        catalog_number : read_string8( reader )?.into(),
+        description : read_string8( reader )?.into(),
+        brand : read_string8( reader )?.into(),
+        weight : weight.to_string().into(),
+        ..Default::default()
+      }
+    )
+  }
+
+  /// Reads machine instructions of color block
+  fn read_vp3_instructions( emb : &mut EmbroideryFile, bytes : &[ u8 ] )
+  {
+    let mut bytes = bytes.iter().copied();
+    let mut trimmed = false;
+
+    while let ( Some( b0 ), Some( b1 ) ) = ( bytes.next(), bytes.next() )
+    {
+      if b0 != CONTROL
+      {
+        emb.stitch( signed8( b0 ), signed8( b1 ) );
+        trimmed = false;
+        continue;
+      }
+
+      match b1
+      {
+        LONG_START_CODE =>
+        {
+          let ( Some( x ), Some( y ) ) = ( read_i16( &mut bytes ), read_i16( &mut bytes ) ) else { break; };
+          if trimmed || x.abs() > TRIM_DISTANCE || y.abs() > TRIM_DISTANCE
+          {
+            if !trimmed
+            {
+              emb.trim();
+              trimmed = true;
+            }
+            emb.jump( x, y );
+          }
+          else
+          {
+            emb.stitch( x, y );
+          }
+        }
+        TRIM_CODE =>
+        {
+          emb.trim();
+          trimmed = true;
+        }
+        // End of long stitch and unknown codes carry no data
+        _ => {}
+      }
+    }
+  }
+
+  /// Reads big-endian `i16` from byte iterator
+  fn read_i16( bytes : &mut impl Iterator< Item = u8 > ) -> Option< i32 >
+  {
+    Some( i16::from_be_bytes( [ bytes.next()?, bytes.next()? ] ) as i32 )
+  }
+
+  /// Extracts signed integer stored in `u8` into `i32`
+  fn signed8( b : u8 ) -> i32
+  {
+    if b > 127
+    {
+      -256 + b as i32
+    }
+    else
+    {
+      b as i32
+    }
+  }
+
+  /// Reads UTF-16 string prefixed with its length in bytes
+  fn read_string16< R >( reader : &mut R ) -> Result< String, EmbroideryError >
+  where
+    R : Read
+  {
+    let len = reader.read_u16::< BE >()? as usize;
+    let mut bytes = vec![ 0; len ];
+    reader.read_exact( &mut bytes )?;
+    let units : Vec< _ > = bytes.chunks_exact( 2 ).map( | c | u16::from_be_bytes( [ c[ 0 ], c[ 1 ] ] ) ).collect();
+    Ok( String::from_utf16_lossy( &units ) )
+  }
+
+  /// Reads UTF-8 string prefixed with its length in bytes
+  fn read_string8< R >( reader : &mut R ) -> Result< String, EmbroideryError >
+  where
+    R : Read
+  {
+    let len = reader.read_u16::< BE >()? as usize;
+    let mut bytes = vec![ 0; len ];
+    reader.read_exact( &mut bytes )?;
+    Ok( String::from_utf8_lossy( &bytes ).into_owned() )
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::read_memory;
+
+    #[ test ]
+    fn test_wrong_magic()
+    {
+      assert!( read_memory( b"%pes%\0\0\0" ).is_err() );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use read_file;
+  orphan use read_memory;
+  orphan use read;
+}
@@ -0,0 +1,362 @@
+//!
+//! # VP3 format writer.
+//! Original implementation refers to https://github.com/EmbroidePy/pyembroidery/blob/main/pyembroidery/Vp3Writer.py
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use error::EmbroideryError;
+  use format::split_move;
+  use stitch_instruction::{ Instruction, Stitch };
+  use thread::Thread;
+  use std::io::{ Seek, SeekFrom, Write };
+  use byteorder::{ WriteBytesExt as _, BE };
+
+  const MAGIC : &[ u8 ] = b"%vsm%\0";
+  // Block markers, each one is followed by length of the block
+  const FILE_BLOCK : [ u8; 3 ] = [ 0x00, 0x02, 0x00 ];
+  const DESIGN_BLOCK : [ u8; 3 ] = [ 0x00, 0x03, 0x00 ];
+  const COLOR_BLOCK : [ u8; 3 ] = [ 0x00, 0x05, 0x00 ];
+  const STITCH_BLOCK : [ u8; 3 ] = [ 0x00, 0x01, 0x00 ];
+  const CONTROL : u8 = 0x80;
+  // Control codes
+  const LONG_START_CODE : u8 = 0x01;
+  const LONG_END_CODE : u8 = 0x02;
+  const TRIM_CODE : u8 = 0x03;
+  // Longest displacement of short stitch
+  const MAX_SHORT_MOVE : i32 = 127;
+  // Longer stitches are read back as jumps, so they are split
+  const MAX_LONG_STITCH : i32 = 255;
+  const MAX_LONG_MOVE : i32 = i16::MAX as i32;
+  // Positions are stored in micrometers
+  const POSITION_SCALE : i32 = 100;
+
+  /// Writes VP3 format into `writer`.
+  /// Every color is written as separate block that starts at the end of previous one.
+  /// VP3 has no jump instruction, so jumps are written as long stitches, which are read back as jumps only after a trim.
+  /// Returns `EmbroideryError::CompatibilityError` for instructions that VP3 can't express
+  pub fn write< W >( emb : &mut EmbroideryFile, writer : &mut W )
+  -> Result< (), EmbroideryError >
+  where
+    W : Write + Seek
+  {
+    if emb.stitches().is_empty()
+    {
+      emb.end();
+    }
+
+    emb.fix_color_count();
+    emb.interpolate_stop_as_duplicate_color();
+
+    let blocks : Vec< _ > = emb.stitches()
+    .split( | s | s.instruction == Instruction::ColorChange || s.instruction == Instruction::Stop )
+    .filter( | block | block.iter().any( | s | s.instruction == Instruction::Stitch ) )
+    .collect();
+
+    writer.write_all( MAGIC )?;
+    write_string16( writer, "Produced by     Software Ltd" )?;
+
+    let file_block = begin_block( writer, FILE_BLOCK )?;
+    write_string16( writer, emb.get_metadata().get_text( "comments" ).unwrap_or_default() )?;
+    let ( min_x, min_y, max_x, max_y ) = emb.bounds();
+    // Y axis points up
+    writer.write_i32::< BE >( max_x * POSITION_SCALE )?;
+    writer.write_i32::< BE >( -min_y * POSITION_SCALE )?;
+    writer.write_i32::< BE >( min_x * POSITION_SCALE )?;
+    writer.write_i32::< BE >( -max_y * POSITION_SCALE )?;
+    writer.write_u32::< BE >( 0 )?; // thread length
+    writer.write_u8( 0 )?;
+    writer.write_u8( blocks.len().min( u8::MAX as usize ) as u8 )?;
+    writer.write_all( &[ 0x0C, 0x00, 0x01 ] )?; // unknown
+
+    let design_block = begin_block( writer, DESIGN_BLOCK )?;
+    let center_x = ( min_x + max_x ) / 2;
+    let center_y = ( min_y + max_y ) / 2;
+    let half_width = ( max_x - min_x ) / 2;
+    let half_height = ( max_y - min_y ) / 2;
+    writer.write_i32::< BE >( center_x * POSITION_SCALE )?;
+    writer.write_i32::< BE >( -center_y * POSITION_SCALE )?;
+    writer.write_all( &[ 0x00, 0x00, 0x00 ] )?;
+    writer.write_i32::< BE >( -half_width * POSITION_SCALE )?;
+    writer.write_i32::< BE >( half_width * POSITION_SCALE )?;
+    writer.write_i32::< BE >( -half_height * POSITION_SCALE )?;
+    writer.write_i32::< BE >( half_height * POSITION_SCALE )?;
+    writer.write_i32::< BE >( ( max_x - min_x ) * POSITION_SCALE )?;
+    writer.write_i32::< BE >( ( max_y - min_y ) * POSITION_SCALE )?;
+    write_string16( writer, "" )?;
+    writer.write_u8( 100 )?; // scale
+    writer.write_u8( 100 )?;
+    // Identity transformation matrix
+    writer.write_u32::< BE >( 4096 )?;
+    writer.write_u32::< BE >( 0 )?;
+    writer.write_u32::< BE >( 0 )?;
+    writer.write_u32::< BE >( 4096 )?;
+    writer.write_all( b"xxPP\x01\x00" )?;
+    write_string16( writer, "" )?;
+    writer.write_u16::< BE >( blocks.len() as u16 )?;
+
+    let mut start = ( 0, 0 );
+    for ( i, block ) in blocks.iter().enumerate()
+    {
+      start = write_color_block( writer, block, start, ( center_x, center_y ), &emb.get_thread_or_filler( i ) )?;
+    }
+
+    end_block( writer, design_block )?;
+    end_block( writer, file_block )?;
+
+    Ok( () )
+  }
+
+  /// Writes stitches of one thread.
+  /// # Returns
+  /// Position where the block ends
+  fn write_color_block< W >
+  (
+    writer : &mut W,
+    stitches : &[ Stitch ],
+    start : ( i32, i32 ),
+    center : ( i32, i32 ),
+    thread : &Thread
+  )
+  -> Result< ( i32, i32 ), EmbroideryError >
+  where
+    W : Write + Seek
+  {
+    let block = begin_block( writer, COLOR_BLOCK )?;
+    writer.write_i32::< BE >( ( start.0 - center.0 ) * POSITION_SCALE )?;
+    writer.write_i32::< BE >( -( start.1 - center.1 ) * POSITION_SCALE )?;
+    write_vp3_thread( writer, thread )?;
+
+    let mut instructions = vec![];
+    let end = write_vp3_instructions( &mut instructions, stitches, start )?;
+    writer.write_i32::< BE >( ( end.0 - start.0 ) * POSITION_SCALE )?;
+    writer.write_i32::< BE >( -( end.1 - start.1 ) * POSITION_SCALE )?;
+
+    let stitch_block = begin_block( writer, STITCH_BLOCK )?;
+    writer.write_all( &[ 0x0A, 0xF6, 0x00 ] )?;
+    writer.write_all( &instructions )?;
+    end_block( writer, stitch_block )?;
+    writer.write_u8( 0 )?;
+
+    end_block( writer, block )?;
+    Ok( end )
+  }
+
+  /// Writes thread description of color block
+  fn write_vp3_thread< W >( writer : &mut W, thread : &Thread ) -> Result< (), EmbroideryError >
+  where
+    W : Write
+  {
+    writer.write_all( &[ 0x01, 0x00 ] )?; // single color without transition
+    let color = thread.color;
+    writer.write_u24::< BE >( ( color.r as u32 ) << 16 | ( color.g as u32 ) << 8 | color.b as u32 )?;
+    writer.write_all( &[ 0x00, 0x00, 0x00 ] )?; // no parts, no color length
+    writer.write_u8( 0x05 )?; // thread type
+    writer.write_u8( thread.weight.parse().unwrap_or( 40 ) )?;
+    write_string8( writer, &thread.catalog_number )?;
+    write_string8( writer, &thread.description )?;
+    write_string8( writer, &thread.brand )?;
+    Ok( () )
+  }
+
+  /// Writes instructions of color block starting from `start`.
+  /// # Returns
+  /// Position of last written instruction
+  fn write_vp3_instructions< W >( writer : &mut W, stitches : &[ Stitch ], start : ( i32, i32 ) )
+  -> Result< ( i32, i32 ), EmbroideryError >
+  where
+    W : Write
+  {
+    let ( mut xx, mut yy ) = start;
+
+    for stitch in stitches
+    {
+      let dx = stitch.x - xx;
+      let dy = stitch.y - yy;
+
+      match stitch.instruction
+      {
+        Instruction::Stitch =>
+        {
+          for ( x, y ) in split_move( dx, dy, MAX_LONG_STITCH )
+          {
+            if x.abs() <= MAX_SHORT_MOVE && y.abs() <= MAX_SHORT_MOVE
+            {
+              writer.write_all( &[ x as u8, y as u8 ] )?;
+            }
+            else
+            {
+              write_long( writer, x, y )?;
+            }
+          }
+        },
+        Instruction::Jump => write_jumps( writer, dx, dy )?,
+        Instruction::Trim =>
+        {
+          write_jumps( writer, dx, dy )?;
+          writer.write_all( &[ CONTROL, TRIM_CODE ] )?;
+        },
+        Instruction::End => break,
+        Instruction::NoInstruction => continue,
+        instruction =>
+        {
+          let msg = format!( "{instruction:?} instruction is not supported by VP3" );
+          return Err( EmbroideryError::CompatibilityError( msg.into() ) );
+        }
+      }
+
+      xx = stitch.x;
+      yy = stitch.y;
+    }
+
+    Ok( ( xx, yy ) )
+  }
+
+  /// Writes moves by `dx`, `dy` in long form. Nothing is written for zero displacement
+  fn write_jumps< W >( writer : &mut W, dx : i32, dy : i32 ) -> Result< (), std::io::Error >
+  where
+    W : Write
+  {
+    if dx == 0 && dy == 0
+    {
+      return Ok( () );
+    }
+    for ( x, y ) in split_move( dx, dy, MAX_LONG_MOVE )
+    {
+      write_long( writer, x, y )?;
+    }
+    Ok( () )
+  }
+
+  /// Writes long move with 16-bit coordinates
+  fn write_long< W >( writer : &mut W, dx : i32, dy : i32 ) -> Result< (), std::io::Error >
+  where
+    W : Write
+  {
+    writer.write_all( &[ CONTROL, LONG_START_CODE ] )?;
+    writer.write_i16::< BE >( dx as i16 )?;
+    writer.write_i16::< BE >( dy as i16 )?;
+    writer.write_all( &[ CONTROL, LONG_END_CODE ] )
+  }
+
+  /// Writes block marker and placeholder for its length.
+  /// # Returns
+  /// Position of the placeholder
+  fn begin_block< W >( writer : &mut W, marker : [ u8; 3 ] ) -> Result< u64, std::io::Error >
+  where
+    W : Write + Seek
+  {
+    writer.write_all( &marker )?;
+    let placeholder = writer.stream_position()?;
+    writer.write_u32::< BE >( 0 )?;
+    Ok( placeholder )
+  }
+
+  /// Patches length of the block which placeholder is at `placeholder`
+  fn end_block< W >( writer : &mut W, placeholder : u64 ) -> Result< (), std::io::Error >
+  where
+    W : Write + Seek
+  {
+    let current_pos = writer.stream_position()?;
+    writer.seek( SeekFrom::Start( placeholder ) )?;
+    writer.write_u32::< BE >( ( current_pos - placeholder - 4 ) as u32 )?;
+    writer.seek( SeekFrom::Start( current_pos ) )?;
+    Ok( () )
+  }
+
+  /// Writes UTF-16 string prefixed with its length in bytes
+  fn write_string16< W >( writer : &mut W, string : &str ) -> Result< (), std::io::Error >
+  where
+    W : Write
+  {
+    let bytes : Vec< _ > = string.encode_utf16().flat_map( u16::to_be_bytes ).collect();
+    writer.write_u16::< BE >( bytes.len() as u16 )?;
+    writer.write_all( &bytes )
+  }
+
+  /// Writes UTF-8 string prefixed with its length in bytes
+  fn write_string8< W >( writer : &mut W, string : &str ) -> Result< (), std::io::Error >
+  where
+    W : Write
+  {
+    writer.write_u16::< BE >( string.len() as u16 )?;
+    writer.write_all( string.as_bytes() )
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use crate::*;
+    use format::vp3::{ read_memory, write };
+    use embroidery_file::EmbroideryFile;
+    use error::EmbroideryError;
+    use stitch_instruction::{ Stitch, Instruction };
+    use thread::{ Color, Thread };
+    use std::io::Cursor;
+
+    #[ test ]
+    fn test_vp3_encoding()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.get_mut_metadata().insert_text( "comments", "Pfaff".into() );
+      emb.stitch( 0, 0 );
+      emb.stitch( -2, -3 );
+      emb.stitch( 200, 0 );
+      emb.color_change( 0, 0 );
+      emb.trim();
+      emb.jump( 40, 30 );
+      emb.stitch( 0, 0 );
+      emb.stitch( 1, 1 );
+      emb.end();
+
+      let red = Thread
+      {
+        color : Color { r : 255, g : 0, b : 0 },
+        description : "Red".into(),
+        catalog_number : "1037".into(),
+        brand : "Robison-Anton".into(),
+        weight : "40".into(),
+        ..Default::default()
+      };
+      let blue = Thread { color : Color { r : 0, g : 0, b : 255 }, weight : "40".into(), ..Default::default() };
+      emb.add_thread( red.clone() );
+      emb.add_thread( blue.clone() );
+
+      let mut memory = vec![];
+      write( &mut emb, &mut Cursor::new( &mut memory ) ).unwrap();
+
+      let emb = read_memory( &memory ).unwrap();
+      assert_eq!( emb.get_metadata().get_text( "comments" ), Some( "Pfaff" ) );
+      assert_eq!( emb.threads(), &[ red, blue ] );
+
+      let stitches = emb.stitches();
+      assert_eq!( stitches[ 0 ], Stitch { x : 0, y : 0, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 1 ], Stitch { x : -2, y : -3, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 2 ], Stitch { x : 198, y : -3, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 3 ], Stitch { x : 198, y : -3, instruction : Instruction::ColorChange } );
+      assert_eq!( stitches[ 4 ], Stitch { x : 198, y : -3, instruction : Instruction::Trim } );
+      assert_eq!( stitches[ 5 ], Stitch { x : 238, y : 27, instruction : Instruction::Jump } );
+      assert_eq!( stitches[ 6 ], Stitch { x : 238, y : 27, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 7 ], Stitch { x : 239, y : 28, instruction : Instruction::Stitch } );
+      assert_eq!( stitches[ 8 ], Stitch { x : 239, y : 28, instruction : Instruction::End } );
+    }
+
+    #[ test ]
+    fn test_vp3_incompatible_instruction()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.stitch( 10, 10 );
+      emb.add_stitch_relative( Stitch { x : 0, y : 0, instruction : Instruction::NeedleAt } );
+
+      let result = write( &mut emb, &mut Cursor::new( vec![] ) );
+      assert!( matches!( result, Err( EmbroideryError::CompatibilityError( _ ) ) ) );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  orphan use write;
+}
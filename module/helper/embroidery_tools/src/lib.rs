@@ -16,4 +16,7 @@ mod_interface!
   layer thread;
   layer metadata;
   layer error;
+  layer render;
+  layer digitize;
+  layer optimize;
 }
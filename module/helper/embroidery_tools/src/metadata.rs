@@ -51,6 +51,12 @@ mod private
       _ = self.text.insert( key.into(), value );
     }
 
+    /// Returns graphics data stored by `key`
+    pub fn get_graphics( &self, key : &str ) -> Option< &Graphics >
+    {
+      self.graphics.get( key )
+    }
+
     /// Inserts graphics data by `key`
     pub fn insert_graphics( &mut self, key : &str, graphics : Graphics )
     {
@@ -0,0 +1,451 @@
+//!
+//! # Optimization of stitch order
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use format::{ pec::PEC_MAX_MOVE, split_move };
+  use stitch_instruction::{ Instruction, Stitch };
+  use thread::build_unique_palette;
+
+  /// Parameters of stitch path optimization
+  #[ derive( Debug, Clone, Copy ) ]
+  pub struct OptimizeOptions
+  {
+    /// Stitches longer than this along either axis are replaced with jumps and a final stitch, in 0.1 mm
+    pub max_stitch : i32,
+    /// Travel between runs longer than this gets a trim before the jump, in 0.1 mm
+    pub trim_distance : f32,
+    /// Maximum amount of 2-opt passes over runs of one color
+    pub two_opt_passes : usize,
+  }
+
+  impl Default for OptimizeOptions
+  {
+    fn default() -> Self
+    {
+      Self { max_stitch : 121, trim_distance : 30.0, two_opt_passes : 8 }
+    }
+  }
+
+  /// Statistics of embroidery file relevant to its sewing time
+  #[ derive( Debug, Clone, Copy, Default, PartialEq ) ]
+  pub struct StitchStats
+  {
+    /// Amount of jump instructions
+    pub jumps : usize,
+    /// Amount of trim instructions
+    pub trims : usize,
+    /// Amount of color change instructions
+    pub color_changes : usize,
+    /// Length of thread used by stitches and by jumps that are not trimmed, in 0.1 mm
+    pub thread_length : f32,
+    /// Length of all jumps, in 0.1 mm
+    pub travel_length : f32,
+  }
+
+  /// Statistics before and after optimization
+  #[ derive( Debug, Clone, Copy, Default, PartialEq ) ]
+  pub struct OptimizeReport
+  {
+    /// Statistics of original file
+    pub before : StitchStats,
+    /// Statistics of optimized file
+    pub after : StitchStats,
+  }
+
+  /// Collects statistics of `emb`
+  pub fn stitch_stats( emb : &EmbroideryFile ) -> StitchStats
+  {
+    let mut stats = StitchStats::default();
+    let mut needle = ( 0, 0 );
+    let mut trimmed = true;
+    for stitch in emb.stitches()
+    {
+      let length = ( ( stitch.x - needle.0 ) as f32 ).hypot( ( stitch.y - needle.1 ) as f32 );
+      match stitch.instruction
+      {
+        Instruction::Stitch =>
+        {
+          stats.thread_length += length;
+          trimmed = false;
+        },
+        Instruction::Jump =>
+        {
+          stats.jumps += 1;
+          stats.travel_length += length;
+          if !trimmed
+          {
+            stats.thread_length += length;
+          }
+        },
+        Instruction::Trim =>
+        {
+          stats.trims += 1;
+          trimmed = true;
+        },
+        Instruction::ColorChange =>
+        {
+          stats.color_changes += 1;
+          trimmed = true;
+        },
+        _ => {},
+      }
+      needle = ( stitch.x, stitch.y );
+    }
+    stats
+  }
+
+  /// Stitches sewn with one thread between color changes or stops
+  struct Section
+  {
+    thread : usize,
+    // Whether machine stops before the section instead of changing thread
+    stop : bool,
+    // Continuous runs of stitches, the first point of a run is where needle pierces first
+    runs : Vec< Vec< ( i32, i32 ) > >,
+  }
+
+  /// Reduces travel of `emb`. Neighbouring sections with matching threads are merged,
+  /// runs of stitches are reordered inside of each thread, and the file is rebuilt
+  /// with jumps between runs and trims before long jumps.
+  /// Runs are never reversed, so layering inside of a run, like underlay below satin, is kept
+  pub fn optimize( emb : &mut EmbroideryFile, options : &OptimizeOptions ) -> OptimizeReport
+  {
+    let before = stitch_stats( emb );
+    let sections = merge_sections( emb, collect_sections( emb ) );
+
+    let mut optimized = EmbroideryFile::new();
+    *optimized.get_mut_metadata() = emb.get_metadata().clone();
+    let mut needle = ( 0, 0 );
+    for ( i, section ) in sections.iter().enumerate()
+    {
+      if i > 0
+      {
+        let instruction = if section.stop { Instruction::Stop } else { Instruction::ColorChange };
+        optimized.add_stitch_relative( Stitch { x : 0, y : 0, instruction } );
+      }
+      if let Some( thread ) = emb.threads().get( section.thread )
+      {
+        optimized.add_thread( thread.clone() );
+      }
+
+      for run in order_runs( &section.runs, needle, options.two_opt_passes )
+      {
+        needle = sew_run( &mut optimized, &section.runs[ run ], needle, options );
+      }
+    }
+    optimized.end();
+
+    *emb = optimized;
+    OptimizeReport { before, after : stitch_stats( emb ) }
+  }
+
+  /// Splits stitches of `emb` into sections and runs
+  fn collect_sections( emb : &EmbroideryFile ) -> Vec< Section >
+  {
+    let mut sections = vec![ Section { thread : 0, stop : false, runs : vec![] } ];
+    let mut needle = None;
+    for block in emb.as_command_blocks()
+    {
+      let Some( first ) = block.first() else { continue; };
+      match first.instruction
+      {
+        Instruction::Stitch =>
+        {
+          // Stitch from the end of a jump to the first stitch of the block is part of the run
+          let mut run : Vec< _ > = needle.into_iter().collect();
+          run.extend( block.iter().map( | s | ( s.x, s.y ) ) );
+          run.dedup();
+          sections.last_mut().unwrap().runs.push( run );
+        },
+        Instruction::ColorChange =>
+        {
+          let thread = sections.last().unwrap().thread + block.len();
+          sections.push( Section { thread, stop : false, runs : vec![] } );
+        },
+        Instruction::Stop =>
+        {
+          let thread = sections.last().unwrap().thread;
+          sections.push( Section { thread, stop : true, runs : vec![] } );
+        },
+        Instruction::End => break,
+        _ => {},
+      }
+      needle = block.last().map( | s | ( s.x, s.y ) );
+    }
+    sections
+  }
+
+  /// Drops empty sections and merges neighbours sewn with the same thread, unless machine stops between them
+  fn merge_sections( emb : &EmbroideryFile, sections : Vec< Section > ) -> Vec< Section >
+  {
+    let unique = build_unique_palette( emb.threads(), emb.threads() );
+    let same_thread = | a : usize, b : usize | a == b || matches!( ( unique.get( a ), unique.get( b ) ), ( Some( x ), Some( y ) ) if x == y );
+
+    let mut ret : Vec< Section > = vec![];
+    let mut stop = false;
+    for mut section in sections
+    {
+      stop |= section.stop;
+      if section.runs.is_empty()
+      {
+        continue;
+      }
+      match ret.last_mut()
+      {
+        Some( last ) if !stop && same_thread( last.thread, section.thread ) => last.runs.append( &mut section.runs ),
+        _ =>
+        {
+          section.stop = stop;
+          ret.push( section );
+        },
+      }
+      stop = false;
+    }
+    ret
+  }
+
+  /// Finds order of runs with short travel between them.
+  /// Nearest neighbour tour is improved by 2-opt, which reverses order of runs in a range
+  fn order_runs( runs : &[ Vec< ( i32, i32 ) > ], start : ( i32, i32 ), passes : usize ) -> Vec< usize >
+  {
+    let entry = | i : usize | runs[ i ][ 0 ];
+    let exit = | i : usize | *runs[ i ].last().unwrap();
+
+    // Greedy tour
+    let mut order = Vec::with_capacity( runs.len() );
+    let mut visited = vec![ false; runs.len() ];
+    let mut needle = start;
+    for _ in 0..runs.len()
+    {
+      let next = ( 0..runs.len() )
+      .filter( | &i | !visited[ i ] )
+      .min_by( | &a, &b | distance( needle, entry( a ) ).total_cmp( &distance( needle, entry( b ) ) ) )
+      .unwrap();
+      visited[ next ] = true;
+      order.push( next );
+      needle = exit( next );
+    }
+
+    // Travel from the end of run at `from` to the start of run at `to`.
+    // Position before the first run is the start, there is no travel after the last one
+    let travel = | order : &[ usize ], from : Option< usize >, to : usize |
+    {
+      if to >= order.len()
+      {
+        return 0.0;
+      }
+      let from = from.map_or( start, | f | exit( order[ f ] ) );
+      distance( from, entry( order[ to ] ) )
+    };
+
+    let n = order.len();
+    for _ in 0..passes
+    {
+      // Prefix sums of travel along the tour and along the tour walked backwards
+      let mut forward = vec![ 0.0; n ];
+      let mut backward = vec![ 0.0; n ];
+      for k in 1..n
+      {
+        forward[ k ] = forward[ k - 1 ] + distance( exit( order[ k - 1 ] ), entry( order[ k ] ) );
+        backward[ k ] = backward[ k - 1 ] + distance( exit( order[ k ] ), entry( order[ k - 1 ] ) );
+      }
+
+      let mut improved = false;
+      'search : for i in 0..n
+      {
+        for j in i + 1..n
+        {
+          let before = i.checked_sub( 1 );
+          let old = travel( &order, before, i ) + forward[ j ] - forward[ i ] + travel( &order, Some( j ), j + 1 );
+          let reversed_inner = backward[ j ] - backward[ i ];
+          let from = before.map_or( start, | b | exit( order[ b ] ) );
+          let mut new = distance( from, entry( order[ j ] ) ) + reversed_inner;
+          if j + 1 < n
+          {
+            new += distance( exit( order[ i ] ), entry( order[ j + 1 ] ) );
+          }
+          if new + 1e-3 < old
+          {
+            order[ i..=j ].reverse();
+            improved = true;
+            break 'search;
+          }
+        }
+      }
+      if !improved
+      {
+        break;
+      }
+    }
+
+    order
+  }
+
+  /// Moves needle to the start of `run` and sews it.
+  /// # Returns
+  /// Position of needle after the run
+  fn sew_run( emb : &mut EmbroideryFile, run : &[ ( i32, i32 ) ], needle : ( i32, i32 ), options : &OptimizeOptions ) -> ( i32, i32 )
+  {
+    let start = run[ 0 ];
+    if start != needle
+    {
+      if distance( needle, start ) > options.trim_distance && !emb.stitches().is_empty()
+      {
+        emb.trim();
+      }
+      add_moves( emb, needle, start, PEC_MAX_MOVE, Instruction::Jump );
+    }
+    emb.add_stitch_absolute( Stitch { x : start.0, y : start.1, instruction : Instruction::Stitch } );
+
+    let max_stitch = options.max_stitch.max( 1 );
+    for pair in run.windows( 2 )
+    {
+      let ( from, to ) = ( pair[ 0 ], pair[ 1 ] );
+      // Overlong stitch becomes jumps followed by a stitch of allowed length at its end
+      let steps = split_move( to.0 - from.0, to.1 - from.1, max_stitch );
+      let ( mut x, mut y ) = from;
+      for ( i, ( dx, dy ) ) in steps.iter().enumerate()
+      {
+        x += dx;
+        y += dy;
+        let instruction = if i + 1 < steps.len() { Instruction::Jump } else { Instruction::Stitch };
+        emb.add_stitch_absolute( Stitch { x, y, instruction } );
+      }
+    }
+
+    *run.last().unwrap()
+  }
+
+  fn add_moves( emb : &mut EmbroideryFile, from : ( i32, i32 ), to : ( i32, i32 ), max : i32, instruction : Instruction )
+  {
+    let ( mut x, mut y ) = from;
+    for ( dx, dy ) in split_move( to.0 - from.0, to.1 - from.1, max )
+    {
+      x += dx;
+      y += dy;
+      emb.add_stitch_absolute( Stitch { x, y, instruction } );
+    }
+  }
+
+  fn distance( a : ( i32, i32 ), b : ( i32, i32 ) ) -> f32
+  {
+    ( ( b.0 - a.0 ) as f32 ).hypot( ( b.1 - a.1 ) as f32 )
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::*;
+    use thread::{ Color, Thread };
+
+    fn thread( r : u8 ) -> Thread
+    {
+      Thread { color : Color { r, g : 0, b : 0 }, ..Default::default() }
+    }
+
+    /// Sews short horizontal run at `x`, `y` after a trim and jump
+    fn run_at( emb : &mut EmbroideryFile, x : i32, y : i32 )
+    {
+      if !emb.stitches().is_empty()
+      {
+        emb.trim();
+      }
+      emb.add_stitch_absolute( Stitch { x, y, instruction : Instruction::Jump } );
+      emb.add_stitch_absolute( Stitch { x, y, instruction : Instruction::Stitch } );
+      emb.add_stitch_absolute( Stitch { x : x + 10, y, instruction : Instruction::Stitch } );
+    }
+
+    #[ test ]
+    fn test_reorder_runs()
+    {
+      let mut emb = EmbroideryFile::new();
+      for x in [ 0, 1000, 20, 1020, 40 ]
+      {
+        run_at( &mut emb, x, 0 );
+      }
+      emb.end();
+      emb.add_thread( thread( 255 ) );
+
+      let report = optimize( &mut emb, &OptimizeOptions::default() );
+      assert_eq!( report.before.jumps, 5 );
+      assert_eq!( report.before.trims, 4 );
+      // runs near origin are sewn together, then far ones
+      assert_eq!( report.after.trims, 1 );
+      assert!( report.after.travel_length < report.before.travel_length );
+
+      let xs : Vec< _ > = emb.stitches().iter().filter( | s | s.instruction == Instruction::Stitch ).map( | s | s.x ).collect();
+      assert_eq!( xs, vec![ 0, 10, 20, 30, 40, 50, 1000, 1010, 1020, 1030 ] );
+    }
+
+    #[ test ]
+    fn test_merge_color_changes()
+    {
+      let mut emb = EmbroideryFile::new();
+      run_at( &mut emb, 0, 0 );
+      emb.color_change( 0, 0 );
+      run_at( &mut emb, 100, 0 );
+      emb.color_change( 0, 0 );
+      run_at( &mut emb, 200, 0 );
+      emb.end();
+      emb.add_thread( thread( 255 ) );
+      emb.add_thread( thread( 255 ) );
+      emb.add_thread( thread( 0 ) );
+
+      let report = optimize( &mut emb, &OptimizeOptions::default() );
+      assert_eq!( report.before.color_changes, 2 );
+      assert_eq!( report.after.color_changes, 1 );
+      assert_eq!( emb.threads(), &[ thread( 255 ), thread( 0 ) ] );
+    }
+
+    #[ test ]
+    fn test_split_long_stitch()
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.stitch( 0, 0 );
+      emb.stitch( 300, 0 );
+      emb.end();
+
+      let report = optimize( &mut emb, &OptimizeOptions::default() );
+      assert_eq!( report.before.jumps, 0 );
+      assert_eq!( report.after.jumps, 2 );
+      let stitches = emb.stitches();
+      assert_eq!( stitches[ 1 ].instruction, Instruction::Jump );
+      assert_eq!( stitches[ 2 ].instruction, Instruction::Jump );
+      assert_eq!( stitches[ 3 ], Stitch { x : 300, y : 0, instruction : Instruction::Stitch } );
+    }
+
+    #[ test ]
+    fn test_two_opt()
+    {
+      // Greedy goes right first and has to come back for the run on the left
+      let runs = vec!
+      [
+        vec![ ( 10, 0 ), ( 20, 0 ) ],
+        vec![ ( 30, 0 ), ( 40, 0 ) ],
+        vec![ ( -15, 0 ), ( -25, 0 ) ],
+        vec![ ( 200, 0 ), ( 210, 0 ) ],
+      ];
+      let greedy = order_runs( &runs, ( 0, 0 ), 0 );
+      let optimized = order_runs( &runs, ( 0, 0 ), 8 );
+      let length = | order : &[ usize ] |
+      {
+        let mut needle = ( 0, 0 );
+        order.iter().map( | &i | { let d = distance( needle, runs[ i ][ 0 ] ); needle = runs[ i ][ 1 ]; d } ).sum::< f32 >()
+      };
+      assert!( length( &optimized ) < length( &greedy ) );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  own use OptimizeOptions;
+  own use StitchStats;
+  own use OptimizeReport;
+  own use stitch_stats;
+  own use optimize;
+}
@@ -0,0 +1,595 @@
+//!
+//! # Renders stitch plan of embroidery file into previews
+//!
+
+mod private
+{
+  use crate::*;
+  use embroidery_file::EmbroideryFile;
+  use metadata::Graphics;
+  use stitch_instruction::Instruction;
+  use thread::Color;
+  use core::fmt::Write as _;
+
+  // Sizes of PEC thumbnail images
+  const PEC_ICON_WIDTH : i32 = 48;
+  const PEC_ICON_HEIGHT : i32 = 38;
+  // Distance in pixels between the design and the edges of thumbnail
+  const PEC_ICON_PADDING : f32 = 5.0;
+  // Lengths of dashes and gaps of travel lines, in pixels
+  const DASH : f32 = 4.0;
+  const GAP : f32 = 3.0;
+  /// Metadata text key under which the design key of stored PEC thumbnails is kept
+  pub const PEC_GRAPHICS_KEY : &str = "pec_graphic_key";
+
+  /// How needle moves without sewing are drawn
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+  pub enum TravelStyle
+  {
+    /// Jumps and trims are not drawn
+    Hidden,
+    /// Jumps and trims are drawn with thin dashed line
+    Dashed,
+  }
+
+  /// Parameters of stitch plan rendering
+  #[ derive( Debug, Clone, Copy ) ]
+  pub struct RenderOptions
+  {
+    /// Size of a millimeter in pixels of raster image
+    pub pixels_per_mm : f32,
+    /// Width of thread in 0.1 mm
+    pub thread_width : f32,
+    /// How jumps and trims are drawn
+    pub travel : TravelStyle,
+    /// Background color, transparent if `None`
+    pub background : Option< Color >,
+    /// Empty space around design in 0.1 mm
+    pub margin : i32,
+  }
+
+  impl Default for RenderOptions
+  {
+    fn default() -> Self
+    {
+      Self
+      {
+        pixels_per_mm : 4.0,
+        thread_width : 4.0,
+        travel : TravelStyle::Hidden,
+        background : None,
+        margin : 20,
+      }
+    }
+  }
+
+  /// Kind of needle path
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+  pub enum PathKind
+  {
+    /// Needle sews with thread
+    Stitch,
+    /// Needle moves without sewing
+    Travel,
+  }
+
+  /// Continuous needle path in absolute coordinates
+  #[ derive( Debug, Clone, PartialEq, Eq ) ]
+  pub struct StitchPath
+  {
+    /// Whether thread is sewn along the path
+    pub kind : PathKind,
+    /// Index of thread in the list of threads of embroidery file
+    pub thread : usize,
+    /// Points the needle goes through, at least two
+    pub points : Vec< ( i32, i32 ) >,
+  }
+
+  /// Splits stitches of embroidery file into continuous paths of sewing and travel.
+  /// Stitch paths start at the point where needle was before the first stitch,
+  /// so they include the stitch that goes from end of a jump
+  pub fn stitch_paths( emb : &EmbroideryFile ) -> Vec< StitchPath >
+  {
+    let mut paths = vec![];
+    let mut thread = 0;
+    // Needle position, there is none before the first stitch
+    let mut needle : Option< ( i32, i32 ) > = None;
+
+    for block in emb.as_command_blocks()
+    {
+      let Some( first ) = block.first() else { continue; };
+      let kind = match first.instruction
+      {
+        Instruction::Stitch => PathKind::Stitch,
+        Instruction::Jump | Instruction::Trim => PathKind::Travel,
+        Instruction::ColorChange =>
+        {
+          thread += block.len();
+          PathKind::Travel
+        },
+        _ => continue,
+      };
+
+      let mut points : Vec< _ > = needle.into_iter().collect();
+      points.extend( block.iter().map( | s | ( s.x, s.y ) ) );
+      points.dedup();
+      if kind == PathKind::Stitch || needle.is_some()
+      {
+        needle = points.last().copied();
+      }
+      if points.len() > 1
+      {
+        paths.push( StitchPath { kind, thread, points } );
+      }
+    }
+
+    paths
+  }
+
+  /// Renders stitch plan as SVG document. Coordinates are kept in 0.1 mm,
+  /// so the document has real size of the design
+  pub fn to_svg( emb : &EmbroideryFile, options : &RenderOptions ) -> String
+  {
+    let ( min_x, min_y, width, height ) = frame( emb, options.margin );
+    let mut svg = String::new();
+    _ = writeln!
+    (
+      svg,
+      r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}mm" height="{}mm" viewBox="{min_x} {min_y} {width} {height}">"#,
+      width as f32 / 10.0,
+      height as f32 / 10.0,
+    );
+    if let Some( background ) = options.background
+    {
+      _ = writeln!
+      (
+        svg,
+        r#"<rect x="{min_x}" y="{min_y}" width="{width}" height="{height}" fill="{}"/>"#,
+        hex( background ),
+      );
+    }
+
+    for path in stitch_paths( emb )
+    {
+      let points = path.points.iter().map( | ( x, y ) | format!( "{x},{y}" ) ).collect::< Vec< _ > >().join( " " );
+      let color = hex( thread_color( emb, path.thread ) );
+      match path.kind
+      {
+        PathKind::Stitch =>
+        {
+          _ = writeln!
+          (
+            svg,
+            r#"<polyline points="{points}" fill="none" stroke="{color}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+            options.thread_width,
+          );
+        },
+        PathKind::Travel if options.travel == TravelStyle::Dashed =>
+        {
+          let width = options.thread_width / 4.0;
+          _ = writeln!
+          (
+            svg,
+            r#"<polyline points="{points}" fill="none" stroke="{color}" stroke-opacity="0.6" stroke-width="{width}" stroke-dasharray="{} {}"/>"#,
+            width * DASH,
+            width * GAP,
+          );
+        },
+        PathKind::Travel => {},
+      }
+    }
+    svg.push_str( "</svg>\n" );
+
+    svg
+  }
+
+  /// RGBA image with straight alpha, rows go from top to bottom
+  #[ derive( Debug, Clone, PartialEq, Eq ) ]
+  pub struct Raster
+  {
+    /// Width in pixels
+    pub width : u32,
+    /// Height in pixels
+    pub height : u32,
+    /// Four bytes per pixel
+    pub pixels : Vec< u8 >,
+  }
+
+  impl Raster
+  {
+    /// Creates image filled with `color`
+    pub fn new( width : u32, height : u32, color : [ u8; 4 ] ) -> Self
+    {
+      let pixels = color.repeat( width as usize * height as usize );
+      Self { width, height, pixels }
+    }
+
+    /// Returns color of pixel at `x`, `y`
+    pub fn pixel( &self, x : u32, y : u32 ) -> [ u8; 4 ]
+    {
+      let i = ( y as usize * self.width as usize + x as usize ) * 4;
+      [ self.pixels[ i ], self.pixels[ i + 1 ], self.pixels[ i + 2 ], self.pixels[ i + 3 ] ]
+    }
+
+    /// Blends `color` over pixel at `x`, `y` with `coverage` in range [0, 1]
+    fn blend( &mut self, x : i32, y : i32, color : Color, coverage : f32 )
+    {
+      let ( Ok( x ), Ok( y ) ) = ( u32::try_from( x ), u32::try_from( y ) ) else { return; };
+      if x >= self.width || y >= self.height || coverage <= 0.0
+      {
+        return;
+      }
+      let i = ( y as usize * self.width as usize + x as usize ) * 4;
+      let dst_a = self.pixels[ i + 3 ] as f32 / 255.0;
+      let out_a = coverage + dst_a * ( 1.0 - coverage );
+      for ( c, src ) in [ color.r, color.g, color.b ].into_iter().enumerate()
+      {
+        let dst = self.pixels[ i + c ] as f32;
+        let value = ( src as f32 * coverage + dst * dst_a * ( 1.0 - coverage ) ) / out_a;
+        self.pixels[ i + c ] = value.round() as u8;
+      }
+      self.pixels[ i + 3 ] = ( out_a * 255.0 ).round() as u8;
+    }
+
+    /// Draws antialiased line of `width` pixels
+    fn line( &mut self, from : ( f32, f32 ), to : ( f32, f32 ), width : f32, color : Color, opacity : f32 )
+    {
+      let radius = width / 2.0;
+      let min_x = ( from.0.min( to.0 ) - radius ).floor() as i32;
+      let max_x = ( from.0.max( to.0 ) + radius ).ceil() as i32;
+      let min_y = ( from.1.min( to.1 ) - radius ).floor() as i32;
+      let max_y = ( from.1.max( to.1 ) + radius ).ceil() as i32;
+      for y in min_y..=max_y
+      {
+        for x in min_x..=max_x
+        {
+          let distance = distance_to_segment( ( x as f32 + 0.5, y as f32 + 0.5 ), from, to );
+          let coverage = ( radius - distance + 0.5 ).clamp( 0.0, 1.0 );
+          self.blend( x, y, color, coverage * opacity );
+        }
+      }
+    }
+
+    /// Encodes image as PNG into `writer`
+    #[ cfg( feature = "png" ) ]
+    pub fn write_png< W >( &self, writer : &mut W ) -> Result< (), error::EmbroideryError >
+    where
+      W : std::io::Write + std::io::Seek
+    {
+      let image = image::RgbaImage::from_raw( self.width, self.height, self.pixels.clone() )
+      .ok_or_else( || error::EmbroideryError::CompatibilityError( "Raster size doesn't match its pixels".into() ) )?;
+      image.write_to( writer, image::ImageFormat::Png )
+      .map_err( | e | error::EmbroideryError::CompatibilityError( e.to_string().into() ) )
+    }
+  }
+
+  /// Renders stitch plan into RGBA image, sized according to `options.pixels_per_mm`
+  pub fn to_raster( emb : &EmbroideryFile, options : &RenderOptions ) -> Raster
+  {
+    let ( min_x, min_y, width, height ) = frame( emb, options.margin );
+    // coordinates are in 0.1 mm
+    let scale = options.pixels_per_mm / 10.0;
+    let background = options.background.map_or( [ 0, 0, 0, 0 ], | c | [ c.r, c.g, c.b, 255 ] );
+    let mut raster = Raster::new
+    (
+      ( width as f32 * scale ).ceil().max( 1.0 ) as u32,
+      ( height as f32 * scale ).ceil().max( 1.0 ) as u32,
+      background,
+    );
+    let project = | ( x, y ) : ( i32, i32 ) | ( ( x - min_x ) as f32 * scale, ( y - min_y ) as f32 * scale );
+    let thread_width = ( options.thread_width * scale ).max( 1.0 );
+
+    for path in stitch_paths( emb )
+    {
+      let color = thread_color( emb, path.thread );
+      let points : Vec< _ > = path.points.iter().copied().map( project ).collect();
+      for segment in points.windows( 2 )
+      {
+        match path.kind
+        {
+          PathKind::Stitch => raster.line( segment[ 0 ], segment[ 1 ], thread_width, color, 1.0 ),
+          PathKind::Travel if options.travel == TravelStyle::Dashed =>
+          {
+            for ( from, to ) in dashes( segment[ 0 ], segment[ 1 ] )
+            {
+              raster.line( from, to, 1.0, color, 0.6 );
+            }
+          },
+          PathKind::Travel => {},
+        }
+      }
+    }
+
+    raster
+  }
+
+  /// Renders PEC thumbnails: the whole design first, then one per thread.
+  /// Thumbnails are 48x38 one bit images with a frame, like the ones drawn by Brother software
+  pub fn pec_thumbnails( emb : &EmbroideryFile ) -> Vec< Graphics >
+  {
+    let paths = stitch_paths( emb );
+    let ( min_x, min_y, max_x, max_y ) = emb.bounds();
+    let width = ( max_x - min_x ).max( 1 ) as f32;
+    let height = ( max_y - min_y ).max( 1 ) as f32;
+    let scale = ( ( PEC_ICON_WIDTH as f32 - 2.0 * PEC_ICON_PADDING ) / width )
+    .min( ( PEC_ICON_HEIGHT as f32 - 2.0 * PEC_ICON_PADDING ) / height );
+    // Design is centered inside of the frame
+    let offset_x = ( PEC_ICON_WIDTH as f32 - width * scale ) / 2.0;
+    let offset_y = ( PEC_ICON_HEIGHT as f32 - height * scale ) / 2.0;
+    let project = | ( x, y ) : ( i32, i32 ) |
+    (
+      ( ( x - min_x ) as f32 * scale + offset_x ) as i32,
+      ( ( y - min_y ) as f32 * scale + offset_y ) as i32,
+    );
+
+    let draw = | thread : Option< usize > |
+    {
+      let mut image = pec_frame();
+      let paths = paths.iter().filter( | p | p.kind == PathKind::Stitch && thread.map_or( true, | t | t == p.thread ) );
+      for path in paths
+      {
+        for segment in path.points.windows( 2 )
+        {
+          draw_bit_line( &mut image, project( segment[ 0 ] ), project( segment[ 1 ] ) );
+        }
+      }
+      image
+    };
+
+    let mut ret = vec![ Graphics::PecGraphics { image : draw( None ), stride : ( PEC_ICON_WIDTH / 8 ) as u8, thread : None } ];
+    for ( i, thread ) in emb.threads().iter().enumerate()
+    {
+      ret.push( Graphics::PecGraphics { image : draw( Some( i ) ), stride : ( PEC_ICON_WIDTH / 8 ) as u8, thread : Some( thread.clone() ) } );
+    }
+
+    ret
+  }
+
+  /// Stores PEC thumbnails in metadata under the keys used by PEC reader and writer,
+  /// together with the key of the stitches they were drawn from
+  pub fn fill_pec_graphics( emb : &mut EmbroideryFile )
+  {
+    for ( i, graphics ) in pec_thumbnails( emb ).into_iter().enumerate()
+    {
+      emb.get_mut_metadata().insert_graphics( &format!( "pec_graphic_{i}" ), graphics );
+    }
+    let key = pec_graphics_key( emb );
+    emb.get_mut_metadata().insert_text( PEC_GRAPHICS_KEY, key );
+  }
+
+  /// Hash of stitches and thread count, stored thumbnails are valid only while it is unchanged
+  pub fn pec_graphics_key( emb : &EmbroideryFile ) -> String
+  {
+    use core::hash::{ Hash, Hasher };
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    emb.stitches().hash( &mut hasher );
+    emb.threads().len().hash( &mut hasher );
+    format!( "{:016x}", hasher.finish() )
+  }
+
+  /// Empty thumbnail with rounded frame along its edges
+  fn pec_frame() -> Vec< u8 >
+  {
+    let mut image = vec![ 0; ( PEC_ICON_WIDTH / 8 * PEC_ICON_HEIGHT ) as usize ];
+    let last_x = PEC_ICON_WIDTH - 1;
+    let last_y = PEC_ICON_HEIGHT - 1;
+    // Horizontal edges are shorter to leave place for rounded corners
+    draw_bit_line( &mut image, ( 4, 1 ), ( last_x - 4, 1 ) );
+    draw_bit_line( &mut image, ( 4, last_y - 1 ), ( last_x - 4, last_y - 1 ) );
+    draw_bit_line( &mut image, ( 1, 4 ), ( 1, last_y - 4 ) );
+    draw_bit_line( &mut image, ( last_x - 1, 4 ), ( last_x - 1, last_y - 4 ) );
+    for ( x, y ) in [ ( 3, 2 ), ( 2, 3 ) ]
+    {
+      set_bit( &mut image, x, y );
+      set_bit( &mut image, last_x - x, y );
+      set_bit( &mut image, x, last_y - y );
+      set_bit( &mut image, last_x - x, last_y - y );
+    }
+    image
+  }
+
+  /// Draws line on one bit image with Bresenham's algorithm
+  fn draw_bit_line( image : &mut [ u8 ], from : ( i32, i32 ), to : ( i32, i32 ) )
+  {
+    let ( mut x, mut y ) = from;
+    let dx = ( to.0 - x ).abs();
+    let dy = -( to.1 - y ).abs();
+    let step_x = if x < to.0 { 1 } else { -1 };
+    let step_y = if y < to.1 { 1 } else { -1 };
+    let mut error = dx + dy;
+    loop
+    {
+      set_bit( image, x, y );
+      if ( x, y ) == to
+      {
+        break;
+      }
+      let e2 = 2 * error;
+      if e2 >= dy
+      {
+        error += dy;
+        x += step_x;
+      }
+      if e2 <= dx
+      {
+        error += dx;
+        y += step_y;
+      }
+    }
+  }
+
+  /// Sets pixel of PEC thumbnail. The leftmost pixel is the lowest bit of a byte
+  fn set_bit( image : &mut [ u8 ], x : i32, y : i32 )
+  {
+    if x < 0 || y < 0 || x >= PEC_ICON_WIDTH || y >= PEC_ICON_HEIGHT
+    {
+      return;
+    }
+    image[ ( y * PEC_ICON_WIDTH / 8 + x / 8 ) as usize ] |= 1 << ( x % 8 );
+  }
+
+  /// Splits segment into dashes
+  fn dashes( from : ( f32, f32 ), to : ( f32, f32 ) ) -> Vec< ( ( f32, f32 ), ( f32, f32 ) ) >
+  {
+    let length = ( to.0 - from.0 ).hypot( to.1 - from.1 );
+    let at = | t : f32 | ( from.0 + ( to.0 - from.0 ) * t / length, from.1 + ( to.1 - from.1 ) * t / length );
+    let mut ret = vec![];
+    let mut t = 0.0;
+    while t < length
+    {
+      ret.push( ( at( t ), at( ( t + DASH ).min( length ) ) ) );
+      t += DASH + GAP;
+    }
+    ret
+  }
+
+  fn distance_to_segment( p : ( f32, f32 ), a : ( f32, f32 ), b : ( f32, f32 ) ) -> f32
+  {
+    let ( abx, aby ) = ( b.0 - a.0, b.1 - a.1 );
+    let length_sq = abx * abx + aby * aby;
+    let t = if length_sq == 0.0
+    {
+      0.0
+    }
+    else
+    {
+      ( ( ( p.0 - a.0 ) * abx + ( p.1 - a.1 ) * aby ) / length_sq ).clamp( 0.0, 1.0 )
+    };
+    ( p.0 - a.0 - abx * t ).hypot( p.1 - a.1 - aby * t )
+  }
+
+  /// Area of the design with margin.
+  /// # Returns
+  /// Min X, min Y, width and height
+  fn frame( emb : &EmbroideryFile, margin : i32 ) -> ( i32, i32, i32, i32 )
+  {
+    let ( min_x, min_y, max_x, max_y ) = if emb.stitches().is_empty() { ( 0, 0, 0, 0 ) } else { emb.bounds() };
+    ( min_x - margin, min_y - margin, max_x - min_x + 2 * margin, max_y - min_y + 2 * margin )
+  }
+
+  /// Color of thread with `index`, black for missing threads
+  fn thread_color( emb : &EmbroideryFile, index : usize ) -> Color
+  {
+    emb.threads().get( index ).map( | t | t.color ).unwrap_or_default()
+  }
+
+  fn hex( color : Color ) -> String
+  {
+    format!( "#{:02x}{:02x}{:02x}", color.r, color.g, color.b )
+  }
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::*;
+    use thread::Thread;
+
+    fn two_color_design() -> EmbroideryFile
+    {
+      let mut emb = EmbroideryFile::new();
+      emb.jump( 10, 10 );
+      emb.stitch( 0, 0 );
+      emb.stitch( 100, 0 );
+      emb.trim();
+      emb.jump( 0, 100 );
+      emb.color_change( 0, 0 );
+      emb.stitch( -100, 0 );
+      emb.end();
+      emb.add_thread( Thread { color : Color { r : 255, g : 0, b : 0 }, ..Default::default() } );
+      emb.add_thread( Thread { color : Color { r : 0, g : 0, b : 255 }, ..Default::default() } );
+      emb
+    }
+
+    #[ test ]
+    fn test_stitch_paths()
+    {
+      let paths = stitch_paths( &two_color_design() );
+      assert_eq!
+      (
+        paths,
+        vec!
+        [
+          StitchPath { kind : PathKind::Stitch, thread : 0, points : vec![ ( 10, 10 ), ( 110, 10 ) ] },
+          StitchPath { kind : PathKind::Travel, thread : 0, points : vec![ ( 110, 10 ), ( 110, 110 ) ] },
+          StitchPath { kind : PathKind::Stitch, thread : 1, points : vec![ ( 110, 110 ), ( 10, 110 ) ] },
+        ]
+      );
+    }
+
+    #[ test ]
+    fn test_svg()
+    {
+      let emb = two_color_design();
+      let svg = to_svg( &emb, &RenderOptions::default() );
+      assert!( svg.contains( "viewBox=\"-10 -10 140 140\"" ) );
+      assert!( svg.contains( "stroke=\"#ff0000\"" ) );
+      assert!( svg.contains( "stroke=\"#0000ff\"" ) );
+      assert!( !svg.contains( "stroke-dasharray" ) );
+
+      let options = RenderOptions { travel : TravelStyle::Dashed, ..Default::default() };
+      assert_eq!( to_svg( &emb, &options ).matches( "stroke-dasharray" ).count(), 1 );
+    }
+
+    #[ test ]
+    fn test_raster()
+    {
+      let emb = two_color_design();
+      let options = RenderOptions { pixels_per_mm : 1.0, thread_width : 20.0, margin : 0, ..Default::default() };
+      let raster = to_raster( &emb, &options );
+      assert_eq!( ( raster.width, raster.height ), ( 10, 10 ) );
+      assert_eq!( raster.pixel( 5, 0 ), [ 255, 0, 0, 255 ] );
+      assert_eq!( raster.pixel( 5, 9 ), [ 0, 0, 255, 255 ] );
+      // travel line is hidden by default
+      assert_eq!( raster.pixel( 9, 5 )[ 3 ], 0 );
+    }
+
+    #[ cfg( feature = "png" ) ]
+    #[ test ]
+    fn test_png()
+    {
+      let raster = to_raster( &two_color_design(), &RenderOptions::default() );
+      let mut png = vec![];
+      raster.write_png( &mut std::io::Cursor::new( &mut png ) ).unwrap();
+      assert!( png.starts_with( b"\x89PNG" ) );
+    }
+
+    #[ test ]
+    fn test_pec_thumbnails()
+    {
+      let emb = two_color_design();
+      let thumbnails = pec_thumbnails( &emb );
+      assert_eq!( thumbnails.len(), 3 );
+
+      let pixel_count = | g : &Graphics |
+      {
+        let Graphics::PecGraphics { image, stride, .. } = g;
+        assert_eq!( *stride, 6 );
+        assert_eq!( image.len(), 6 * 38 );
+        image.iter().map( | b | b.count_ones() ).sum::< u32 >()
+      };
+      let frame = pec_frame().iter().map( | b | b.count_ones() ).sum::< u32 >();
+      let all = pixel_count( &thumbnails[ 0 ] );
+      let first = pixel_count( &thumbnails[ 1 ] );
+      let second = pixel_count( &thumbnails[ 2 ] );
+      assert!( first > frame && second > frame );
+      assert_eq!( all - frame, ( first - frame ) + ( second - frame ) );
+    }
+  }
+}
+
+crate::mod_interface!
+{
+  own use TravelStyle;
+  own use RenderOptions;
+  own use PathKind;
+  own use StitchPath;
+  own use Raster;
+  own use stitch_paths;
+  own use to_svg;
+  own use to_raster;
+  own use pec_thumbnails;
+  own use fill_pec_graphics;
+  own use pec_graphics_key;
+  own use PEC_GRAPHICS_KEY;
+}
@@ -9,7 +9,7 @@ mod private
   /// Format encoders and decoders are responsible 
   /// of mapping actual binary instructions to these more general ones
   #[ non_exhaustive ]
-  #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
   pub enum Instruction
   {
     /// Instruction absence
@@ -37,7 +37,7 @@ mod private
   }
 
   /// Stores instruction and coordinates of its appliance
-  #[ derive( Debug, Clone, Copy, PartialEq, Eq ) ]
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
   pub struct Stitch
   {
     /// X coordinate of instruction
@@ -10,6 +10,9 @@ mod_interface::mod_interface!
   #[ cfg( feature = "adapter-terminal" ) ]
   layer terminal;
 
+  #[ cfg( feature = "adapter-raster" ) ]
+  layer raster;
+
   #[ cfg( all( feature = "adapter-webgl" ) ) ]
   layer webgl;
 }
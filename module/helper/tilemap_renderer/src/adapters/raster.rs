@@ -0,0 +1,1533 @@
+//! Software raster backend adapter.
+//!
+//! Renders the full command set on the CPU into an RGBA8 pixel buffer,
+//! returned as [`Output::Bitmap`]. Needs no window, GPU or browser, so it
+//! serves headless use: server-side thumbnails, offline exports and
+//! pixel-diff regression tests.
+//!
+//! Internally every draw is a polygon set in pixel space, filled with the
+//! non-zero rule and analytic horizontal coverage over several sub-scanlines
+//! (see [`RenderConfig::antialias`]). Paths, strokes, text glyphs, sprite
+//! quads and mesh triangles all go through the same scanline filler; only the
+//! per-pixel shading differs. Colours are kept premultiplied in `f32` until
+//! output.
+//!
+//! Groups render into their own transparent layer, which is filtered by the
+//! group's [`Effect`], masked by its clip and composited onto the parent at
+//! `EndGroup` — the same model as SVG `<g filter clip-path>`.
+
+mod private
+{
+  use crate::assets::*;
+  use crate::backend::*;
+  use crate::commands::*;
+  use crate::types::*;
+  use super::raster_helpers::
+  {
+    Mat3,
+    mat_mul,
+    mat_apply,
+    mat_apply_vector,
+    mat_invert,
+    mat_scale,
+    Canvas,
+    Mask,
+    premultiply,
+    RasterImage,
+    gradient_color,
+    Subpath,
+    PathBuilder,
+    StrokeStyle,
+    stroke_polygons,
+    signed_area,
+    fill_polygons,
+    gaussian_blur,
+    drop_shadow,
+    color_matrix,
+    apply_opacity,
+    GLYPH_ADVANCE,
+    GLYPH_HEIGHT,
+    GLYPH_LINE,
+    text_squares,
+  };
+  use nohash_hasher::IntMap;
+
+  // ============================================================================
+  // Raster resource handles
+  // ============================================================================
+
+  /// Internal storage for loaded raster resources.
+  struct RasterResources
+  {
+    /// Decoded images, premultiplied.
+    images : IntMap< ResourceId< asset::Image >, RasterImage >,
+    /// Sprite regions `( sheet, [x, y, w, h] )`.
+    sprites : IntMap< ResourceId< asset::Sprite >, ( ResourceId< asset::Image >, [ f32; 4 ] ) >,
+    /// Decoded geometries.
+    geometries : IntMap< ResourceId< asset::Geometry >, RasterGeometry >,
+    /// Gradient definitions.
+    gradients : IntMap< ResourceId< asset::Gradient >, ( GradientKind, Vec< GradientStop > ) >,
+    /// Pattern definitions `( image, tile width, tile height )`.
+    patterns : IntMap< ResourceId< asset::Pattern >, ( ResourceId< asset::Image >, f32, f32 ) >,
+    /// Clip mask outlines.
+    clip_masks : IntMap< ResourceId< asset::ClipMask >, Vec< PathSegment > >,
+    /// Created batches.
+    batches : IntMap< ResourceId< Batch >, RasterBatch >,
+  }
+
+  impl RasterResources
+  {
+    fn new() -> Self
+    {
+      Self
+      {
+        images : IntMap::default(),
+        sprites : IntMap::default(),
+        geometries : IntMap::default(),
+        gradients : IntMap::default(),
+        patterns : IntMap::default(),
+        clip_masks : IntMap::default(),
+        batches : IntMap::default(),
+      }
+    }
+
+    /// Resolves a fill reference. `Ok( None )` means "nothing to paint".
+    fn paint( &self, fill : &FillRef ) -> Result< Option< Paint< '_ > >, RenderError >
+    {
+      match *fill
+      {
+        FillRef::None => Ok( None ),
+        FillRef::Solid( color ) => Ok( Some( Paint::Solid( premultiply( color ) ) ) ),
+        FillRef::Gradient( id ) =>
+        {
+          let ( kind, stops ) = self.gradients.get( &id ).ok_or( RenderError::MissingAsset( id.inner() ) )?;
+          Ok( Some( Paint::Gradient( kind, stops ) ) )
+        }
+        FillRef::Pattern( id ) =>
+        {
+          let &( content, width, height ) = self.patterns.get( &id ).ok_or( RenderError::MissingAsset( id.inner() ) )?;
+          let image = self.images.get( &content ).ok_or( RenderError::MissingAsset( content.inner() ) )?;
+          Ok( Some( Paint::Pattern { image, width, height } ) )
+        }
+      }
+    }
+  }
+
+  /// Decoded geometry.
+  struct RasterGeometry
+  {
+    /// Flattened vertex positions [x0, y0, x1, y1, ...].
+    positions : Vec< f32 >,
+    /// Optional flattened UVs [u0, v0, u1, v1, ...].
+    uvs : Option< Vec< f32 > >,
+    /// Optional vertex indices.
+    indices : Option< Vec< u32 > >,
+  }
+
+  impl RasterGeometry
+  {
+    /// Vertex positions and UVs in draw order (indices resolved).
+    fn vertices( &self ) -> Vec< ( [ f32; 2 ], Option< [ f32; 2 ] > ) >
+    {
+      let vertex = | i : usize |
+      {
+        let position = [ *self.positions.get( i * 2 )?, *self.positions.get( i * 2 + 1 )? ];
+        let uv = self.uvs.as_ref().and_then( | uvs | Some( [ *uvs.get( i * 2 )?, *uvs.get( i * 2 + 1 )? ] ) );
+        Some( ( position, uv ) )
+      };
+      match &self.indices
+      {
+        Some( indices ) => indices.iter().filter_map( | &i | vertex( i as usize ) ).collect(),
+        None => ( 0..self.positions.len() / 2 ).filter_map( vertex ).collect(),
+      }
+    }
+  }
+
+  /// Internal representation of a batch.
+  enum RasterBatch
+  {
+    /// A sprite batch.
+    Sprite
+    {
+      /// Instances currently in the batch.
+      instances : Vec< AddSpriteInstance >,
+      /// Parameters common to all instances.
+      params : SpriteBatchParams,
+    },
+    /// A mesh batch.
+    Mesh
+    {
+      /// Instances currently in the batch.
+      instances : Vec< AddMeshInstance >,
+      /// Parameters common to all instances.
+      params : MeshBatchParams,
+    },
+  }
+
+  /// A resolved fill, evaluated in the element's local (user) space.
+  enum Paint< 'a >
+  {
+    /// Premultiplied solid colour.
+    Solid( [ f32; 4 ] ),
+    /// Linear or radial gradient.
+    Gradient( &'a GradientKind, &'a [ GradientStop ] ),
+    /// Tiled image.
+    Pattern
+    {
+      image : &'a RasterImage,
+      width : f32,
+      height : f32,
+    },
+  }
+
+  impl Paint< '_ >
+  {
+    /// Premultiplied colour at local point `p`.
+    fn shade( &self, p : [ f32; 2 ] ) -> [ f32; 4 ]
+    {
+      match self
+      {
+        Self::Solid( color ) => *color,
+        Self::Gradient( kind, stops ) => premultiply( gradient_color( kind, stops, p ) ),
+        Self::Pattern { image, width, height } =>
+        {
+          if *width <= 0.0 || *height <= 0.0
+          {
+            return [ 0.0; 4 ];
+          }
+          // Tiles start at the local origin; image row 0 is the top of each tile.
+          let u = p[ 0 ].rem_euclid( *width ) / width;
+          let v = 1.0 - p[ 1 ].rem_euclid( *height ) / height;
+          image.sample( u * image.width as f32, v * image.height as f32, image.bounds() )
+        }
+      }
+    }
+  }
+
+  /// One entry of the group stack. The bottom layer is the frame itself.
+  struct Layer
+  {
+    /// Pixels drawn inside the group.
+    canvas : Canvas,
+    /// Viewport → pixel matrix including all enclosing group transforms.
+    matrix : Mat3,
+    /// Group clip, applied when compositing onto the parent.
+    clip : Option< Mask >,
+    /// Group effect, applied before compositing.
+    effect : Option< Effect >,
+  }
+
+  /// Path being recorded between `BeginPath` and `EndPath`.
+  struct PathState
+  {
+    style : BeginPath,
+    /// Local → pixel matrix captured at `BeginPath`.
+    matrix : Mat3,
+    builder : PathBuilder,
+  }
+
+  // ============================================================================
+  // Backend struct
+  // ============================================================================
+
+  /// CPU software rasterizer backend.
+  ///
+  /// ```ignore
+  /// let config = RenderConfig { width : 256, height : 256, ..Default::default() };
+  /// let mut raster = RasterBackend::new( config );
+  /// raster.load_assets( &assets )?;
+  /// raster.submit( &commands )?;
+  /// let Output::Bitmap( bitmap ) = raster.output()? else { unreachable!() };
+  /// // bitmap.bytes: straight-alpha RGBA8, row 0 at the top
+  /// ```
+  ///
+  /// # Known limitations
+  ///
+  /// - Text uses a built-in 3×5 bitmap font (ASCII letters, digits and common
+  ///   punctuation); `BeginText::font` is ignored and text-on-path is drawn straight.
+  /// - `Transform::depth` is ignored; draws land in submission order.
+  /// - `ImageAsset::mipmap` is ignored; downscaled images alias under `Nearest`
+  ///   and blur only over a 2×2 footprint under `Linear`.
+  pub struct RasterBackend
+  {
+    config : RenderConfig,
+    resources : RasterResources,
+    /// Group stack; `layers[ 0 ]` is the frame and is never popped.
+    layers : Vec< Layer >,
+    // -- streaming state --
+    path : Option< PathState >,
+    text_buf : String,
+    text_style : Option< BeginText >,
+    /// Currently bound batch for recording instances.
+    recording_batch : Option< ResourceId< Batch > >,
+  }
+
+  impl RasterBackend
+  {
+    /// Creates a raster backend that renders `config.width × config.height` pixels.
+    #[ must_use ]
+    pub fn new( config : RenderConfig ) -> Self
+    {
+      let mut backend = Self
+      {
+        config,
+        resources : RasterResources::new(),
+        layers : Vec::new(),
+        path : None,
+        text_buf : String::new(),
+        text_style : None,
+        recording_batch : None,
+      };
+      backend.reset_frame();
+      backend
+    }
+
+    /// Drops all layers and starts a fresh frame filled with the background.
+    fn reset_frame( &mut self )
+    {
+      let ( width, height ) = ( self.config.width, self.config.height );
+      let mut canvas = Canvas::new( width, height );
+      canvas.fill( self.config.background );
+      // Viewport (Y-up) → pixels (Y-down, row 0 at the top).
+      let flip = [ 1.0, 0.0, 0.0, 0.0, -1.0, 0.0, 0.0, height as f32, 1.0 ];
+      self.layers = vec![ Layer { canvas, matrix : flip, clip : None, effect : None } ];
+    }
+
+    /// Sub-scanlines per pixel row for the configured antialiasing.
+    fn samples( &self ) -> u32
+    {
+      match self.config.antialias
+      {
+        Antialias::None => 1,
+        Antialias::Default => 4,
+        Antialias::High => 16,
+      }
+    }
+
+    /// Viewport → pixel matrix of the current group.
+    fn layer_matrix( &self ) -> Mat3
+    {
+      self.layers.last().map_or( [ 0.0; 9 ], | l | l.matrix )
+    }
+
+    /// Rasterizes a clip mask asset under `matrix` (clip local → pixels).
+    fn clip_mask( &self, clip : Option< ResourceId< asset::ClipMask > >, matrix : &Mat3 ) -> Result< Option< Mask >, RenderError >
+    {
+      let Some( id ) = clip else { return Ok( None ) };
+      let segments = self.resources.clip_masks.get( &id ).ok_or( RenderError::MissingAsset( id.inner() ) )?;
+      let polygons : Vec< _ > = PathBuilder::from_segments( segments, mat_scale( matrix ) )
+        .into_iter()
+        .map( | s | s.points.iter().map( | &p | mat_apply( matrix, p ) ).collect() )
+        .collect();
+      Ok( Some( Mask::from_polygons( &polygons, self.config.width, self.config.height, self.samples() ) ) )
+    }
+
+    // -- drawing --
+
+    /// Draws one sprite quad of `region` size under `matrix` (local → pixels).
+    fn draw_sprite
+    (
+      &mut self,
+      sprite : ResourceId< asset::Sprite >,
+      matrix : &Mat3,
+      tint : [ f32; 4 ],
+      blend : BlendMode,
+      clip : Option< &Mask >,
+    ) -> Result< (), RenderError >
+    {
+      let samples = self.samples();
+      let &( sheet, [ rx, ry, rw, rh ] ) = self.resources.sprites.get( &sprite ).ok_or( RenderError::MissingAsset( sprite.inner() ) )?;
+      let image = self.resources.images.get( &sheet ).ok_or( RenderError::MissingAsset( sheet.inner() ) )?;
+      let Some( inverse ) = mat_invert( matrix ) else { return Ok( () ) };
+      let quad = [ [ 0.0, 0.0 ], [ rw, 0.0 ], [ rw, rh ], [ 0.0, rh ] ].map( | p | mat_apply( matrix, p ) );
+      let bounds = [ rx.floor() as i64, ry.floor() as i64, ( rx + rw ).ceil() as i64, ( ry + rh ).ceil() as i64 ];
+      let tint = premultiply( tint );
+      let Some( layer ) = self.layers.last_mut() else { return Ok( () ) };
+      fill_polygons( &mut layer.canvas, &[ quad.to_vec() ], samples, blend, clip, | p |
+      {
+        // Local Y-up: the top of the quad (ly = rh) is region row `ry`.
+        let [ lx, ly ] = mat_apply( &inverse, p );
+        let texel = image.sample( rx + lx, ry + ( rh - ly ), bounds );
+        [ 0, 1, 2, 3 ].map( | i | texel[ i ] * tint[ i ] )
+      });
+      Ok( () )
+    }
+
+    /// Draws a geometry asset under `matrix` (local → pixels).
+    fn draw_geometry
+    (
+      &mut self,
+      geometry : ResourceId< asset::Geometry >,
+      matrix : &Mat3,
+      topology : Topology,
+      fill : &FillRef,
+      texture : Option< ResourceId< asset::Image > >,
+      tint : [ f32; 4 ],
+      blend : BlendMode,
+      clip : Option< &Mask >,
+    ) -> Result< (), RenderError >
+    {
+      let samples = self.samples();
+      let resources = &self.resources;
+      let geom = resources.geometries.get( &geometry ).ok_or( RenderError::MissingAsset( geometry.inner() ) )?;
+      let paint = resources.paint( fill )?;
+      let image = texture.map( | t | resources.images.get( &t ).ok_or( RenderError::MissingAsset( t.inner() ) ) ).transpose()?;
+      if paint.is_none() && image.is_none()
+      {
+        return Ok( () );
+      }
+      let Some( inverse ) = mat_invert( matrix ) else { return Ok( () ) };
+      let vertices : Vec< _ > = geom.vertices().into_iter().map( | ( p, uv ) | ( mat_apply( matrix, p ), uv ) ).collect();
+      let tint = premultiply( tint );
+      let base = | p : [ f32; 2 ] | paint.as_ref().map_or( [ 1.0; 4 ], | paint | paint.shade( mat_apply( &inverse, p ) ) );
+      let Some( layer ) = self.layers.last_mut() else { return Ok( () ) };
+
+      match topology
+      {
+        Topology::TriangleList | Topology::TriangleStrip =>
+        {
+          let triangles : Vec< [ usize; 3 ] > = if let Topology::TriangleList = topology
+          {
+            ( 0..vertices.len() / 3 ).map( | t | [ t * 3, t * 3 + 1, t * 3 + 2 ] ).collect()
+          }
+          else
+          {
+            ( 0..vertices.len().saturating_sub( 2 ) ).map( | t | [ t, t + 1, t + 2 ] ).collect()
+          };
+          // Same orientation for every triangle, so overlaps never cancel under non-zero.
+          let polygons : Vec< Vec< [ f32; 2 ] > > = triangles.iter().map( | tri |
+          {
+            let mut polygon = tri.map( | i | vertices[ i ].0 ).to_vec();
+            if signed_area( &polygon ) < 0.0
+            {
+              polygon.reverse();
+            }
+            polygon
+          }).collect();
+          let textured = image.filter( | _ | vertices.iter().all( | v | v.1.is_some() ) );
+          fill_polygons( &mut layer.canvas, &polygons, samples, blend, clip, | p |
+          {
+            let mut color = base( p );
+            if let Some( image ) = textured
+            {
+              let uv = interpolate_uv( &vertices, &triangles, p );
+              let texel = image.sample( uv[ 0 ] * image.width as f32, ( 1.0 - uv[ 1 ] ) * image.height as f32, image.bounds() );
+              color = [ 0, 1, 2, 3 ].map( | i | color[ i ] * texel[ i ] );
+            }
+            [ 0, 1, 2, 3 ].map( | i | color[ i ] * tint[ i ] )
+          });
+        }
+        Topology::LineList | Topology::LineStrip =>
+        {
+          let points : Vec< [ f32; 2 ] > = vertices.iter().map( | v | v.0 ).collect();
+          let subpaths : Vec< Subpath > = if let Topology::LineList = topology
+          {
+            points.chunks_exact( 2 ).map( | pair | Subpath { points : pair.to_vec(), closed : false } ).collect()
+          }
+          else
+          {
+            vec![ Subpath { points, closed : false } ]
+          };
+          // One local unit wide, like the SVG backend's default stroke.
+          let style = StrokeStyle { width : mat_scale( matrix ), cap : LineCap::Butt, join : LineJoin::Miter, dash : DashStyle::default() };
+          fill_polygons( &mut layer.canvas, &stroke_polygons( &subpaths, &style ), samples, blend, clip, | p |
+          {
+            let color = base( p );
+            [ 0, 1, 2, 3 ].map( | i | color[ i ] * tint[ i ] )
+          });
+        }
+      }
+      Ok( () )
+    }
+
+    // -- path commands --
+
+    fn cmd_begin_path( &mut self, bp : &BeginPath )
+    {
+      let matrix = mat_mul( &self.layer_matrix(), &bp.transform.to_mat3() );
+      self.path = Some( PathState { style : *bp, matrix, builder : PathBuilder::new( mat_scale( &matrix ) ) } );
+    }
+
+    fn cmd_end_path( &mut self ) -> Result< (), RenderError >
+    {
+      let Some( PathState { style, matrix, builder } ) = self.path.take() else { return Ok( () ) };
+      let samples = self.samples();
+      let clip = self.clip_mask( style.clip, &matrix )?;
+      let paint = self.resources.paint( &style.fill )?;
+      let Some( inverse ) = mat_invert( &matrix ) else { return Ok( () ) };
+      let subpaths : Vec< Subpath > = builder.finish().into_iter().map( | s | Subpath
+      {
+        points : s.points.iter().map( | &p | mat_apply( &matrix, p ) ).collect(),
+        closed : s.closed,
+      }).collect();
+      let Some( layer ) = self.layers.last_mut() else { return Ok( () ) };
+
+      if let Some( paint ) = paint
+      {
+        // Fills close every subpath implicitly.
+        let polygons : Vec< _ > = subpaths.iter().filter( | s | s.points.len() > 2 ).map( | s | s.points.clone() ).collect();
+        fill_polygons( &mut layer.canvas, &polygons, samples, style.blend, clip.as_ref(), | p | paint.shade( mat_apply( &inverse, p ) ) );
+      }
+
+      if style.stroke_color[ 3 ] > 0.0 && style.stroke_width > 0.0
+      {
+        let scale = mat_scale( &matrix );
+        let dash = DashStyle { pattern : style.stroke_dash.pattern.map( | d | d * scale ), offset : style.stroke_dash.offset * scale };
+        let stroke = StrokeStyle { width : style.stroke_width * scale, cap : style.stroke_cap, join : style.stroke_join, dash };
+        let color = premultiply( style.stroke_color );
+        fill_polygons( &mut layer.canvas, &stroke_polygons( &subpaths, &stroke ), samples, style.blend, clip.as_ref(), | _ | color );
+      }
+      Ok( () )
+    }
+
+    // -- text --
+
+    fn cmd_end_text( &mut self ) -> Result< (), RenderError >
+    {
+      let Some( style ) = self.text_style.take() else { return Ok( () ) };
+      let text = core::mem::take( &mut self.text_buf );
+      let count = text.chars().count();
+      if count == 0 || style.size <= 0.0
+      {
+        return Ok( () );
+      }
+      let matrix = self.layer_matrix();
+      let samples = self.samples();
+      let clip = self.clip_mask( style.clip, &matrix )?;
+
+      // Anchor offsets in font units; glyphs hang down from y = 0 (Y-up).
+      let width = count as f32 * GLYPH_ADVANCE - 1.0;
+      let dx = match style.anchor
+      {
+        TextAnchor::TopLeft | TextAnchor::CenterLeft | TextAnchor::BottomLeft => 0.0,
+        TextAnchor::TopCenter | TextAnchor::Center | TextAnchor::BottomCenter => -width * 0.5,
+        TextAnchor::TopRight | TextAnchor::CenterRight | TextAnchor::BottomRight => -width,
+      };
+      let dy = match style.anchor
+      {
+        TextAnchor::TopLeft | TextAnchor::TopCenter | TextAnchor::TopRight => 0.0,
+        TextAnchor::CenterLeft | TextAnchor::Center | TextAnchor::CenterRight => GLYPH_HEIGHT * 0.5,
+        TextAnchor::BottomLeft | TextAnchor::BottomCenter | TextAnchor::BottomRight => GLYPH_HEIGHT,
+      };
+      let unit = style.size / GLYPH_LINE;
+      let [ px, py ] = style.position;
+      let polygons : Vec< Vec< [ f32; 2 ] > > = text_squares( &text ).into_iter().map( | [ sx, sy ] |
+      {
+        let ( x0, y0 ) = ( px + ( sx + dx ) * unit, py + ( sy + dy ) * unit );
+        [ [ x0, y0 ], [ x0 + unit, y0 ], [ x0 + unit, y0 + unit ], [ x0, y0 + unit ] ]
+          .iter()
+          .map( | &p | mat_apply( &matrix, p ) )
+          .collect()
+      }).collect();
+      let color = premultiply( style.color );
+      let Some( layer ) = self.layers.last_mut() else { return Ok( () ) };
+      fill_polygons( &mut layer.canvas, &polygons, samples, BlendMode::Normal, clip.as_ref(), | _ | color );
+      Ok( () )
+    }
+
+    // -- single draws --
+
+    fn cmd_clear( &mut self, c : &Clear )
+    {
+      if let Some( layer ) = self.layers.last_mut()
+      {
+        layer.canvas.fill( c.color );
+      }
+    }
+
+    fn cmd_mesh( &mut self, m : &Mesh ) -> Result< (), RenderError >
+    {
+      let matrix = mat_mul( &self.layer_matrix(), &m.transform.to_mat3() );
+      let clip = self.clip_mask( m.clip, &matrix )?;
+      self.draw_geometry( m.geometry, &matrix, m.topology, &m.fill, m.texture, [ 1.0; 4 ], m.blend, clip.as_ref() )
+    }
+
+    fn cmd_sprite( &mut self, s : &Sprite ) -> Result< (), RenderError >
+    {
+      let matrix = mat_mul( &self.layer_matrix(), &s.transform.to_mat3() );
+      let clip = self.clip_mask( s.clip, &matrix )?;
+      self.draw_sprite( s.sprite, &matrix, s.tint, s.blend, clip.as_ref() )
+    }
+
+    // -- batches --
+
+    fn bound_batch( &mut self ) -> Option< &mut RasterBatch >
+    {
+      let id = self.recording_batch?;
+      self.resources.batches.get_mut( &id )
+    }
+
+    fn cmd_add_sprite_instance( &mut self, si : &AddSpriteInstance )
+    {
+      if let Some( RasterBatch::Sprite { instances, .. } ) = self.bound_batch()
+      {
+        instances.push( *si );
+      }
+    }
+
+    fn cmd_add_mesh_instance( &mut self, mi : &AddMeshInstance )
+    {
+      if let Some( RasterBatch::Mesh { instances, .. } ) = self.bound_batch()
+      {
+        instances.push( *mi );
+      }
+    }
+
+    fn cmd_set_sprite_instance( &mut self, si : &SetSpriteInstance )
+    {
+      if let Some( RasterBatch::Sprite { instances, .. } ) = self.bound_batch()
+        && let Some( slot ) = instances.get_mut( si.index as usize )
+      {
+        *slot = AddSpriteInstance { transform : si.transform, sprite : si.sprite, tint : si.tint, effects : si.effects };
+      }
+    }
+
+    fn cmd_set_mesh_instance( &mut self, mi : &SetMeshInstance )
+    {
+      if let Some( RasterBatch::Mesh { instances, .. } ) = self.bound_batch()
+        && let Some( slot ) = instances.get_mut( mi.index as usize )
+      {
+        *slot = AddMeshInstance { transform : mi.transform, tint : mi.tint };
+      }
+    }
+
+    fn cmd_remove_instance( &mut self, ri : &RemoveInstance )
+    {
+      let index = ri.index as usize;
+      match self.bound_batch()
+      {
+        Some( RasterBatch::Sprite { instances, .. } ) if index < instances.len() => { instances.swap_remove( index ); }
+        Some( RasterBatch::Mesh { instances, .. } ) if index < instances.len() => { instances.swap_remove( index ); }
+        _ => {}
+      }
+    }
+
+    fn cmd_draw_batch( &mut self, db : &DrawBatch ) -> Result< (), RenderError >
+    {
+      let group = self.layer_matrix();
+      // Temporarily take the batch out so drawing can borrow `self` mutably.
+      let Some( batch ) = self.resources.batches.remove( &db.batch ) else { return Ok( () ) };
+      let result = match &batch
+      {
+        RasterBatch::Sprite { instances, params } =>
+        {
+          let parent = mat_mul( &group, &params.transform.to_mat3() );
+          self.clip_mask( params.clip, &parent ).and_then( | clip |
+          {
+            instances.iter().try_for_each( | inst |
+            {
+              self.draw_sprite( inst.sprite, &mat_mul( &parent, &inst.transform.to_mat3() ), inst.tint, params.blend, clip.as_ref() )
+            })
+          })
+        }
+        RasterBatch::Mesh { instances, params } =>
+        {
+          let parent = mat_mul( &group, &params.transform.to_mat3() );
+          self.clip_mask( params.clip, &parent ).and_then( | clip |
+          {
+            instances.iter().try_for_each( | inst |
+            {
+              self.draw_geometry
+              (
+                params.geometry,
+                &mat_mul( &parent, &inst.transform.to_mat3() ),
+                params.topology,
+                &params.fill,
+                params.texture,
+                inst.tint,
+                params.blend,
+                clip.as_ref(),
+              )
+            })
+          })
+        }
+      };
+      self.resources.batches.insert( db.batch, batch );
+      result
+    }
+
+    // -- groups --
+
+    fn cmd_begin_group( &mut self, bg : &BeginGroup ) -> Result< (), RenderError >
+    {
+      let matrix = mat_mul( &self.layer_matrix(), &bg.transform.to_mat3() );
+      let clip = self.clip_mask( bg.clip, &matrix )?;
+      let canvas = Canvas::new( self.config.width, self.config.height );
+      self.layers.push( Layer { canvas, matrix, clip, effect : bg.effect } );
+      Ok( () )
+    }
+
+    /// Filters the top layer and composites it onto its parent.
+    /// An unmatched `EndGroup` is ignored.
+    fn cmd_end_group( &mut self )
+    {
+      if self.layers.len() < 2
+      {
+        return;
+      }
+      let Some( Layer { mut canvas, matrix, clip, effect } ) = self.layers.pop() else { return };
+      let scale = mat_scale( &matrix );
+      match effect
+      {
+        Some( Effect::Blur { radius } ) => gaussian_blur( &mut canvas, radius * scale ),
+        Some( Effect::DropShadow { dx, dy, blur, color } ) =>
+        {
+          drop_shadow( &mut canvas, mat_apply_vector( &matrix, [ dx, dy ] ), blur * scale, color );
+        }
+        Some( Effect::ColorMatrix( m ) ) => color_matrix( &mut canvas, &m ),
+        Some( Effect::Opacity( opacity ) ) => apply_opacity( &mut canvas, opacity ),
+        None => {}
+      }
+      if let Some( parent ) = self.layers.last_mut()
+      {
+        parent.canvas.composite( &canvas, clip.as_ref() );
+      }
+    }
+
+    // -- asset loading --
+
+    fn load_images( &mut self, images : &[ ImageAsset ] ) -> Result< (), RenderError >
+    {
+      for img in images
+      {
+        let decoded = match &img.source
+        {
+          ImageSource::Bitmap { bytes, width, height, format } => RasterImage::from_bitmap( bytes, *width, *height, *format ),
+          ImageSource::Encoded( bytes ) =>
+          {
+            let decoded = image::load_from_memory( bytes )
+              .map_err( | e | RenderError::BackendError( format!( "image {}: {e}", img.id.inner() ) ) )?
+              .to_rgba8();
+            RasterImage::from_bitmap( decoded.as_raw(), decoded.width(), decoded.height(), PixelFormat::Rgba8 )
+          }
+          ImageSource::Path( path ) =>
+          {
+            let decoded = image::open( path )
+              .map_err( | e | RenderError::BackendError( format!( "image {} ({}): {e}", img.id.inner(), path.display() ) ) )?
+              .to_rgba8();
+            RasterImage::from_bitmap( decoded.as_raw(), decoded.width(), decoded.height(), PixelFormat::Rgba8 )
+          }
+        };
+        let mut decoded = decoded.ok_or_else( || RenderError::BackendError
+        (
+          format!( "image {}: pixel data does not match its dimensions", img.id.inner() )
+        ))?;
+        decoded.filter = img.filter;
+        decoded.wrap = img.wrap;
+        self.resources.images.insert( img.id, decoded );
+      }
+      Ok( () )
+    }
+
+    fn load_geometries( &mut self, geometries : &[ GeometryAsset ] ) -> Result< (), RenderError >
+    {
+      let floats = | bytes : Vec< u8 > | -> Vec< f32 >
+      {
+        bytes.chunks_exact( 4 ).map( | b | f32::from_le_bytes( [ b[ 0 ], b[ 1 ], b[ 2 ], b[ 3 ] ] ) ).collect()
+      };
+      for geom in geometries
+      {
+        let positions = floats( read_source( &geom.positions )? );
+        let uvs = geom.uvs.as_ref().map( read_source ).transpose()?.map( floats );
+        let indices = match geom.indices.as_ref().map( read_source ).transpose()?
+        {
+          Some( ibytes ) => match geom.data_type
+          {
+            DataType::U8 => Some( ibytes.iter().map( | &i | u32::from( i ) ).collect() ),
+            DataType::U16 => Some( ibytes.chunks_exact( 2 ).map( | b | u32::from( u16::from_le_bytes( [ b[ 0 ], b[ 1 ] ] ) ) ).collect() ),
+            DataType::U32 => Some( ibytes.chunks_exact( 4 ).map( | b | u32::from_le_bytes( [ b[ 0 ], b[ 1 ], b[ 2 ], b[ 3 ] ] ) ).collect() ),
+            DataType::F32 => return Err( RenderError::BackendError( format!( "geometry {}: F32 is not a valid index type", geom.id.inner() ) ) ),
+          },
+          None => None,
+        };
+        self.resources.geometries.insert( geom.id, RasterGeometry { positions, uvs, indices } );
+      }
+      Ok( () )
+    }
+  }
+
+  /// Reads a geometry buffer from memory or disk.
+  fn read_source( source : &Source ) -> Result< Vec< u8 >, RenderError >
+  {
+    match source
+    {
+      Source::Bytes( bytes ) => Ok( bytes.clone() ),
+      Source::Path( path ) => std::fs::read( path ).map_err( | e | RenderError::BackendError( format!( "{}: {e}", path.display() ) ) ),
+    }
+  }
+
+  /// UV at pixel `p`, interpolated in the triangle that contains it (or the
+  /// nearest one, for antialiased edge pixels whose centre lies just outside).
+  fn interpolate_uv( vertices : &[ ( [ f32; 2 ], Option< [ f32; 2 ] > ) ], triangles : &[ [ usize; 3 ] ], p : [ f32; 2 ] ) -> [ f32; 2 ]
+  {
+    let mut best = ( f32::NEG_INFINITY, [ 0.0, 0.0 ] );
+    for tri in triangles
+    {
+      let [ a, b, c ] = tri.map( | i | vertices[ i ].0 );
+      let det = ( b[ 1 ] - c[ 1 ] ) * ( a[ 0 ] - c[ 0 ] ) + ( c[ 0 ] - b[ 0 ] ) * ( a[ 1 ] - c[ 1 ] );
+      if det.abs() < 1e-12
+      {
+        continue;
+      }
+      let w0 = ( ( b[ 1 ] - c[ 1 ] ) * ( p[ 0 ] - c[ 0 ] ) + ( c[ 0 ] - b[ 0 ] ) * ( p[ 1 ] - c[ 1 ] ) ) / det;
+      let w1 = ( ( c[ 1 ] - a[ 1 ] ) * ( p[ 0 ] - c[ 0 ] ) + ( a[ 0 ] - c[ 0 ] ) * ( p[ 1 ] - c[ 1 ] ) ) / det;
+      let w2 = 1.0 - w0 - w1;
+      let inside = w0.min( w1 ).min( w2 );
+      if inside > best.0
+      {
+        let [ ua, ub, uc ] = tri.map( | i | vertices[ i ].1.unwrap_or_default() );
+        best = ( inside, [ 0, 1 ].map( | k | w0 * ua[ k ] + w1 * ub[ k ] + w2 * uc[ k ] ) );
+        if inside >= 0.0
+        {
+          break;
+        }
+      }
+    }
+    best.1
+  }
+
+  // ============================================================================
+  // Backend trait impl
+  // ============================================================================
+
+  impl Backend for RasterBackend
+  {
+    fn load_assets( &mut self, assets : &Assets ) -> Result< (), RenderError >
+    {
+      self.resources = RasterResources::new();
+      self.recording_batch = None;
+      self.load_images( &assets.images )?;
+      for sprite in &assets.sprites
+      {
+        self.resources.sprites.insert( sprite.id, ( sprite.sheet, sprite.region ) );
+      }
+      self.load_geometries( &assets.geometries )?;
+      for gradient in &assets.gradients
+      {
+        let mut stops = gradient.stops.clone();
+        stops.sort_by( | a, b | a.offset.total_cmp( &b.offset ) );
+        self.resources.gradients.insert( gradient.id, ( gradient.kind, stops ) );
+      }
+      for pattern in &assets.patterns
+      {
+        self.resources.patterns.insert( pattern.id, ( pattern.content, pattern.width, pattern.height ) );
+      }
+      for clip in &assets.clip_masks
+      {
+        self.resources.clip_masks.insert( clip.id, clip.segments.clone() );
+      }
+      Ok( () )
+    }
+
+    fn submit( &mut self, commands : &[ RenderCommand ] ) -> Result< (), RenderError >
+    {
+      self.reset_frame();
+      self.recording_batch = None;
+      self.path = None;
+      self.text_style = None;
+      self.text_buf.clear();
+
+      for cmd in commands
+      {
+        match cmd
+        {
+          RenderCommand::Clear( c ) => self.cmd_clear( c ),
+          // Sprite effects are not rasterized; sprites draw with their static tint.
+          RenderCommand::SetTime( _ ) => {},
+          RenderCommand::BeginPath( bp ) => self.cmd_begin_path( bp ),
+          RenderCommand::MoveTo( m ) =>
+          {
+            if let Some( path ) = &mut self.path { path.builder.move_to( [ m.0, m.1 ] ); }
+          }
+          RenderCommand::LineTo( l ) =>
+          {
+            if let Some( path ) = &mut self.path { path.builder.line_to( [ l.0, l.1 ] ); }
+          }
+          RenderCommand::QuadTo( q ) =>
+          {
+            if let Some( path ) = &mut self.path { path.builder.quad_to( [ q.cx, q.cy ], [ q.x, q.y ] ); }
+          }
+          RenderCommand::CubicTo( c ) =>
+          {
+            if let Some( path ) = &mut self.path { path.builder.cubic_to( [ c.c1x, c.c1y ], [ c.c2x, c.c2y ], [ c.x, c.y ] ); }
+          }
+          RenderCommand::ArcTo( a ) =>
+          {
+            if let Some( path ) = &mut self.path { path.builder.arc_to( a.rx, a.ry, a.rotation, a.large_arc, a.sweep, [ a.x, a.y ] ); }
+          }
+          RenderCommand::ClosePath( _ ) =>
+          {
+            if let Some( path ) = &mut self.path { path.builder.close(); }
+          }
+          RenderCommand::EndPath( _ ) => self.cmd_end_path()?,
+          RenderCommand::BeginText( bt ) =>
+          {
+            self.text_buf.clear();
+            self.text_style = Some( *bt );
+          }
+          RenderCommand::Char( ch ) => self.text_buf.push( ch.0 ),
+          RenderCommand::EndText( _ ) => self.cmd_end_text()?,
+          RenderCommand::Mesh( m ) => self.cmd_mesh( m )?,
+          // There is no camera here, so screen-space sprites draw like world-space ones.
+          RenderCommand::Sprite( s ) | RenderCommand::ScreenSpaceSprite( s ) => self.cmd_sprite( s )?,
+          RenderCommand::CreateSpriteBatch( cb ) =>
+          {
+            self.resources.batches.insert( cb.batch, RasterBatch::Sprite { instances : Vec::new(), params : cb.params } );
+          }
+          RenderCommand::CreateMeshBatch( cb ) =>
+          {
+            self.resources.batches.insert( cb.batch, RasterBatch::Mesh { instances : Vec::new(), params : cb.params } );
+          }
+          RenderCommand::BindBatch( bb ) => self.recording_batch = Some( bb.batch ),
+          RenderCommand::AddSpriteInstance( si ) => self.cmd_add_sprite_instance( si ),
+          RenderCommand::AddMeshInstance( mi ) => self.cmd_add_mesh_instance( mi ),
+          RenderCommand::SetSpriteInstance( si ) => self.cmd_set_sprite_instance( si ),
+          RenderCommand::SetMeshInstance( mi ) => self.cmd_set_mesh_instance( mi ),
+          RenderCommand::RemoveInstance( ri ) => self.cmd_remove_instance( ri ),
+          RenderCommand::SetSpriteBatchParams( sp ) =>
+          {
+            if let Some( RasterBatch::Sprite { params, .. } ) = self.bound_batch()
+            {
+              *params = sp.params;
+            }
+          }
+          RenderCommand::SetMeshBatchParams( mp ) =>
+          {
+            if let Some( RasterBatch::Mesh { params, .. } ) = self.bound_batch()
+            {
+              *params = mp.params;
+            }
+          }
+          RenderCommand::UnbindBatch( _ ) => self.recording_batch = None,
+          RenderCommand::DrawBatch( db ) => self.cmd_draw_batch( db )?,
+          RenderCommand::DeleteBatch( db ) =>
+          {
+            self.resources.batches.remove( &db.batch );
+            if self.recording_batch == Some( db.batch )
+            {
+              self.recording_batch = None;
+            }
+          }
+          RenderCommand::BeginGroup( bg ) => self.cmd_begin_group( bg )?,
+          RenderCommand::EndGroup( _ ) => self.cmd_end_group(),
+        }
+      }
+
+      // Groups left open by the caller are closed so their content isn't lost.
+      while self.layers.len() > 1
+      {
+        self.cmd_end_group();
+      }
+      Ok( () )
+    }
+
+    /// Reallocates the pixel buffer at `width × height`; the viewport is
+    /// resized with it.
+    fn resize( &mut self, width : u32, height : u32 )
+    {
+      self.config.width = width;
+      self.config.height = height;
+      self.reset_frame();
+    }
+
+    fn output( &self ) -> Result< Output, RenderError >
+    {
+      let frame = self.layers.first().ok_or_else( || RenderError::BackendError( "no frame".into() ) )?;
+      Ok( Output::Bitmap( Bitmap
+      {
+        bytes : frame.canvas.to_rgba8(),
+        width : frame.canvas.width,
+        height : frame.canvas.height,
+        channels : 4,
+      }))
+    }
+
+    fn capabilities( &self ) -> Capabilities
+    {
+      Capabilities
+      {
+        paths : true,
+        text : true,
+        meshes : true,
+        sprites : true,
+        batches : true,
+        gradients : true,
+        patterns : true,
+        clip_masks : true,
+        effects : true,
+        sprite_effects : false,
+        blend_modes : true,
+        text_on_path : false,
+        max_texture_size : 0,
+        supported_blend_modes : &[ BlendMode::Normal, BlendMode::Multiply, BlendMode::Screen, BlendMode::Overlay, BlendMode::Add ],
+      }
+    }
+  }
+  // ============================================================================
+  // Tests
+  // ============================================================================
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::*;
+
+    // -- helpers --
+
+    const WHITE : [ f32; 4 ] = [ 1.0, 1.0, 1.0, 1.0 ];
+    const RED : [ f32; 4 ] = [ 1.0, 0.0, 0.0, 1.0 ];
+
+    /// `w × h` pixels on black, without antialiasing unless asked for.
+    fn raster( w : u32, h : u32, antialias : Antialias ) -> RasterBackend
+    {
+      let mut raster = RasterBackend::new( RenderConfig { width : w, height : h, antialias, ..Default::default() } );
+      raster.load_assets( &empty_assets() ).unwrap();
+      raster
+    }
+
+    fn empty_assets() -> Assets
+    {
+      Assets
+      {
+        fonts : vec![],
+        images : vec![],
+        sprites : vec![],
+        geometries : vec![],
+        gradients : vec![],
+        patterns : vec![],
+        clip_masks : vec![],
+        paths : vec![],
+      }
+    }
+
+    fn bitmap( raster : &RasterBackend ) -> Bitmap
+    {
+      match raster.output().unwrap()
+      {
+        Output::Bitmap( b ) => b,
+        _ => panic!( "expected bitmap output" ),
+      }
+    }
+
+    /// RGBA of pixel `( x, row )`, row 0 at the top of the image.
+    fn pixel( bitmap : &Bitmap, x : u32, row : u32 ) -> [ u8; 4 ]
+    {
+      let i = ( ( row * bitmap.width + x ) * 4 ) as usize;
+      [ bitmap.bytes[ i ], bitmap.bytes[ i + 1 ], bitmap.bytes[ i + 2 ], bitmap.bytes[ i + 3 ] ]
+    }
+
+    fn path_style( fill : FillRef, stroke : [ f32; 4 ], stroke_width : f32 ) -> BeginPath
+    {
+      BeginPath
+      {
+        transform : Transform::default(),
+        fill,
+        stroke_color : stroke,
+        stroke_width,
+        stroke_cap : LineCap::default(),
+        stroke_join : LineJoin::default(),
+        stroke_dash : DashStyle::default(),
+        blend : BlendMode::Normal,
+        clip : None,
+      }
+    }
+
+    /// Closed axis-aligned rectangle path in viewport (Y-up) units.
+    fn rect( style : BeginPath, x0 : f32, y0 : f32, x1 : f32, y1 : f32 ) -> Vec< RenderCommand >
+    {
+      vec!
+      [
+        RenderCommand::BeginPath( style ),
+        RenderCommand::MoveTo( MoveTo( x0, y0 ) ),
+        RenderCommand::LineTo( LineTo( x1, y0 ) ),
+        RenderCommand::LineTo( LineTo( x1, y1 ) ),
+        RenderCommand::LineTo( LineTo( x0, y1 ) ),
+        RenderCommand::ClosePath( ClosePath ),
+        RenderCommand::EndPath( EndPath ),
+      ]
+    }
+
+    /// 2×2 sheet: top row red, bottom row blue.
+    fn red_blue_sheet() -> Assets
+    {
+      let bytes = vec!
+      [
+        255, 0, 0, 255,  255, 0, 0, 255,
+        0, 0, 255, 255,  0, 0, 255, 255,
+      ];
+      Assets
+      {
+        images : vec![ ImageAsset
+        {
+          id : ResourceId::new( 0 ),
+          source : ImageSource::Bitmap { bytes, width : 2, height : 2, format : PixelFormat::Rgba8 },
+          filter : SamplerFilter::Nearest,
+          mipmap : MipmapMode::Off,
+          wrap : WrapMode::Clamp,
+        }],
+        sprites : vec![ SpriteAsset { id : ResourceId::new( 0 ), sheet : ResourceId::new( 0 ), region : [ 0.0, 0.0, 2.0, 2.0 ] } ],
+        ..empty_assets()
+      }
+    }
+
+    fn scaled( s : f32 ) -> Transform
+    {
+      Transform { scale : [ s, s ], ..Default::default() }
+    }
+
+    // -- frame --
+
+    #[ test ]
+    fn output_is_rgba_bitmap_of_viewport_size()
+    {
+      let mut raster = RasterBackend::new( RenderConfig { width : 6, height : 4, background : [ 0.0, 0.0, 1.0, 1.0 ], ..Default::default() } );
+      raster.submit( &[] ).unwrap();
+      let out = bitmap( &raster );
+      assert_eq!( ( out.width, out.height, out.channels ), ( 6, 4, 4 ) );
+      assert_eq!( out.bytes.len(), 6 * 4 * 4 );
+      assert_eq!( pixel( &out, 5, 3 ), [ 0, 0, 255, 255 ] );
+    }
+
+    #[ test ]
+    fn resize_reallocates_canvas()
+    {
+      let mut raster = raster( 4, 4, Antialias::None );
+      raster.resize( 10, 3 );
+      raster.submit( &[ RenderCommand::Clear( Clear { color : WHITE } ) ] ).unwrap();
+      let out = bitmap( &raster );
+      assert_eq!( ( out.width, out.height ), ( 10, 3 ) );
+      assert_eq!( pixel( &out, 9, 2 ), [ 255; 4 ] );
+    }
+
+    // -- paths --
+
+    #[ test ]
+    fn path_fill_is_y_up()
+    {
+      let mut raster = raster( 8, 8, Antialias::None );
+      raster.submit( &rect( path_style( FillRef::Solid( RED ), [ 0.0; 4 ], 0.0 ), 0.0, 0.0, 4.0, 4.0 ) ).unwrap();
+      let out = bitmap( &raster );
+      // Bottom-left quadrant in Y-up is the lower-left of the image.
+      assert_eq!( pixel( &out, 0, 7 ), [ 255, 0, 0, 255 ] );
+      assert_eq!( pixel( &out, 3, 4 ), [ 255, 0, 0, 255 ] );
+      assert_eq!( pixel( &out, 0, 3 ), [ 0, 0, 0, 255 ] );
+      assert_eq!( pixel( &out, 4, 7 ), [ 0, 0, 0, 255 ] );
+    }
+
+    #[ test ]
+    fn antialiased_edge_has_partial_coverage()
+    {
+      let mut raster = raster( 8, 4, Antialias::Default );
+      raster.submit( &rect( path_style( FillRef::Solid( WHITE ), [ 0.0; 4 ], 0.0 ), 0.0, 0.0, 2.5, 4.0 ) ).unwrap();
+      let out = bitmap( &raster );
+      assert_eq!( pixel( &out, 1, 1 )[ 0 ], 255 );
+      let edge = pixel( &out, 2, 1 )[ 0 ];
+      assert!( ( 120..=135 ).contains( &edge ), "half-covered pixel should be ~50% grey, got {edge}" );
+      assert_eq!( pixel( &out, 3, 1 )[ 0 ], 0 );
+    }
+
+    #[ test ]
+    fn stroke_honours_width_and_dash()
+    {
+      let mut raster = raster( 20, 8, Antialias::None );
+      let mut style = path_style( FillRef::None, WHITE, 2.0 );
+      style.stroke_dash = DashStyle { pattern : [ 4.0, 4.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0 ], offset : 0.0 };
+      raster.submit( &[
+        RenderCommand::BeginPath( style ),
+        RenderCommand::MoveTo( MoveTo( 0.0, 4.0 ) ),
+        RenderCommand::LineTo( LineTo( 16.0, 4.0 ) ),
+        RenderCommand::EndPath( EndPath ),
+      ] ).unwrap();
+      let out = bitmap( &raster );
+      // Width 2 around y = 4 covers image rows 3 and 4.
+      assert_eq!( pixel( &out, 1, 3 )[ 0 ], 255 );
+      assert_eq!( pixel( &out, 1, 4 )[ 0 ], 255 );
+      assert_eq!( pixel( &out, 1, 2 )[ 0 ], 0 );
+      // Second dash starts at x = 8; x = 5 lies in the first gap.
+      assert_eq!( pixel( &out, 5, 3 )[ 0 ], 0 );
+      assert_eq!( pixel( &out, 9, 3 )[ 0 ], 255 );
+    }
+
+    #[ test ]
+    fn arc_path_fills_a_disc()
+    {
+      let mut raster = raster( 20, 20, Antialias::None );
+      raster.submit( &[
+        RenderCommand::BeginPath( path_style( FillRef::Solid( WHITE ), [ 0.0; 4 ], 0.0 ) ),
+        RenderCommand::MoveTo( MoveTo( 2.0, 10.0 ) ),
+        RenderCommand::ArcTo( ArcTo { rx : 8.0, ry : 8.0, rotation : 0.0, large_arc : false, sweep : true, x : 18.0, y : 10.0 } ),
+        RenderCommand::ArcTo( ArcTo { rx : 8.0, ry : 8.0, rotation : 0.0, large_arc : false, sweep : true, x : 2.0, y : 10.0 } ),
+        RenderCommand::ClosePath( ClosePath ),
+        RenderCommand::EndPath( EndPath ),
+      ] ).unwrap();
+      let out = bitmap( &raster );
+      assert_eq!( pixel( &out, 10, 10 )[ 0 ], 255 );
+      assert_eq!( pixel( &out, 10, 3 )[ 0 ], 255 );
+      assert_eq!( pixel( &out, 1, 1 )[ 0 ], 0 );
+      assert_eq!( pixel( &out, 18, 18 )[ 0 ], 0 );
+    }
+
+    // -- paint --
+
+    #[ test ]
+    fn linear_gradient_interpolates_across_the_shape()
+    {
+      let mut raster = raster( 10, 2, Antialias::None );
+      raster.load_assets( &Assets
+      {
+        gradients : vec![ GradientAsset
+        {
+          id : ResourceId::new( 0 ),
+          kind : GradientKind::Linear { start : [ 0.0, 0.0 ], end : [ 10.0, 0.0 ] },
+          stops : vec![ GradientStop { offset : 0.0, color : [ 0.0, 0.0, 0.0, 1.0 ] }, GradientStop { offset : 1.0, color : WHITE } ],
+        }],
+        ..empty_assets()
+      }).unwrap();
+      raster.submit( &rect( path_style( FillRef::Gradient( ResourceId::new( 0 ) ), [ 0.0; 4 ], 0.0 ), 0.0, 0.0, 10.0, 2.0 ) ).unwrap();
+      let out = bitmap( &raster );
+      let row : Vec< u8 > = ( 0..10 ).map( | x | pixel( &out, x, 0 )[ 0 ] ).collect();
+      assert!( row.windows( 2 ).all( | w | w[ 0 ] < w[ 1 ] ), "gradient must increase left to right: {row:?}" );
+      // Pixel centre x = 5.5 → t = 0.55.
+      assert_eq!( pixel( &out, 5, 0 )[ 0 ], 140 );
+    }
+
+    #[ test ]
+    fn unknown_gradient_is_missing_asset()
+    {
+      let mut raster = raster( 4, 4, Antialias::None );
+      let result = raster.submit( &rect( path_style( FillRef::Gradient( ResourceId::new( 9 ) ), [ 0.0; 4 ], 0.0 ), 0.0, 0.0, 4.0, 4.0 ) );
+      assert!( matches!( result, Err( RenderError::MissingAsset( 9 ) ) ) );
+    }
+
+    #[ test ]
+    fn clip_mask_limits_fill()
+    {
+      let mut raster = raster( 8, 8, Antialias::None );
+      raster.load_assets( &Assets
+      {
+        clip_masks : vec![ ClipMaskAsset
+        {
+          id : ResourceId::new( 0 ),
+          segments : vec!
+          [
+            PathSegment::MoveTo( 0.0, 0.0 ),
+            PathSegment::LineTo( 4.0, 0.0 ),
+            PathSegment::LineTo( 4.0, 8.0 ),
+            PathSegment::LineTo( 0.0, 8.0 ),
+            PathSegment::Close,
+          ],
+        }],
+        ..empty_assets()
+      }).unwrap();
+      let mut style = path_style( FillRef::Solid( WHITE ), [ 0.0; 4 ], 0.0 );
+      style.clip = Some( ResourceId::new( 0 ) );
+      raster.submit( &rect( style, 0.0, 0.0, 8.0, 8.0 ) ).unwrap();
+      let out = bitmap( &raster );
+      assert_eq!( pixel( &out, 3, 4 )[ 0 ], 255 );
+      assert_eq!( pixel( &out, 4, 4 )[ 0 ], 0 );
+    }
+
+    // -- blending --
+
+    #[ test ]
+    fn blend_modes_follow_w3c_formulas()
+    {
+      let cases =
+      [
+        ( BlendMode::Multiply, [ 0, 255, 0, 255 ] ),
+        ( BlendMode::Screen, [ 255, 255, 255, 255 ] ),
+        ( BlendMode::Add, [ 255, 255, 255, 255 ] ),
+        ( BlendMode::Normal, [ 0, 255, 255, 255 ] ),
+      ];
+      for ( mode, expected ) in cases
+      {
+        // Yellow background, cyan source.
+        let mut raster = RasterBackend::new( RenderConfig { width : 2, height : 2, antialias : Antialias::None, background : [ 1.0, 1.0, 0.0, 1.0 ], ..Default::default() } );
+        let mut style = path_style( FillRef::Solid( [ 0.0, 1.0, 1.0, 1.0 ] ), [ 0.0; 4 ], 0.0 );
+        style.blend = mode;
+        raster.submit( &rect( style, 0.0, 0.0, 2.0, 2.0 ) ).unwrap();
+        assert_eq!( pixel( &bitmap( &raster ), 0, 0 ), expected, "{mode:?}" );
+      }
+    }
+
+    // -- sprites --
+
+    #[ test ]
+    fn sprite_draws_top_row_first()
+    {
+      let mut raster = raster( 4, 4, Antialias::None );
+      raster.load_assets( &red_blue_sheet() ).unwrap();
+      raster.submit( &[ RenderCommand::Sprite( Sprite
+      {
+        transform : scaled( 2.0 ),
+        sprite : ResourceId::new( 0 ),
+        tint : WHITE,
+        blend : BlendMode::Normal,
+        clip : None,
+        effects : SpriteEffects::default(),
+      })]).unwrap();
+      let out = bitmap( &raster );
+      assert_eq!( pixel( &out, 0, 0 ), [ 255, 0, 0, 255 ] );
+      assert_eq!( pixel( &out, 3, 1 ), [ 255, 0, 0, 255 ] );
+      assert_eq!( pixel( &out, 0, 2 ), [ 0, 0, 255, 255 ] );
+      assert_eq!( pixel( &out, 3, 3 ), [ 0, 0, 255, 255 ] );
+    }
+
+    #[ test ]
+    fn sprite_tint_multiplies_texels()
+    {
+      let mut raster = raster( 2, 2, Antialias::None );
+      raster.load_assets( &red_blue_sheet() ).unwrap();
+      raster.submit( &[ RenderCommand::Sprite( Sprite
+      {
+        transform : Transform::default(),
+        sprite : ResourceId::new( 0 ),
+        tint : [ 0.5, 1.0, 1.0, 1.0 ],
+        blend : BlendMode::Normal,
+        clip : None,
+        effects : SpriteEffects::default(),
+      })]).unwrap();
+      assert_eq!( pixel( &bitmap( &raster ), 0, 0 ), [ 128, 0, 0, 255 ] );
+    }
+
+    #[ test ]
+    fn sprite_unknown_id_is_missing_asset()
+    {
+      let mut raster = raster( 2, 2, Antialias::None );
+      let result = raster.submit( &[ RenderCommand::Sprite( Sprite
+      {
+        transform : Transform::default(),
+        sprite : ResourceId::new( 7 ),
+        tint : WHITE,
+        blend : BlendMode::Normal,
+        clip : None,
+        effects : SpriteEffects::default(),
+      })]);
+      assert!( matches!( result, Err( RenderError::MissingAsset( 7 ) ) ) );
+    }
+
+    #[ test ]
+    fn encoded_png_sheet_is_decoded()
+    {
+      let mut png = Vec::new();
+      image::RgbaImage::from_pixel( 2, 2, image::Rgba( [ 0, 255, 0, 255 ] ) )
+        .write_to( &mut std::io::Cursor::new( &mut png ), image::ImageFormat::Png )
+        .unwrap();
+      let mut assets = red_blue_sheet();
+      assets.images[ 0 ].source = ImageSource::Encoded( png );
+      let mut raster = raster( 2, 2, Antialias::None );
+      raster.load_assets( &assets ).unwrap();
+      raster.submit( &[ RenderCommand::Sprite( Sprite
+      {
+        transform : Transform::default(),
+        sprite : ResourceId::new( 0 ),
+        tint : WHITE,
+        blend : BlendMode::Normal,
+        clip : None,
+        effects : SpriteEffects::default(),
+      })]).unwrap();
+      assert_eq!( pixel( &bitmap( &raster ), 1, 1 ), [ 0, 255, 0, 255 ] );
+    }
+
+    #[ test ]
+    fn undecodable_image_is_backend_error()
+    {
+      let mut assets = red_blue_sheet();
+      assets.images[ 0 ].source = ImageSource::Encoded( vec![ 1, 2, 3 ] );
+      let mut raster = raster( 2, 2, Antialias::None );
+      assert!( matches!( raster.load_assets( &assets ), Err( RenderError::BackendError( _ ) ) ) );
+    }
+
+    #[ test ]
+    fn sprite_batch_draws_every_instance()
+    {
+      let mut raster = raster( 8, 2, Antialias::None );
+      raster.load_assets( &red_blue_sheet() ).unwrap();
+      let batch = ResourceId::new( 0 );
+      let params = SpriteBatchParams { transform : Transform::default(), sheet : ResourceId::new( 0 ), blend : BlendMode::Normal, clip : None };
+      let instance = | x : f32 | RenderCommand::AddSpriteInstance( AddSpriteInstance
+      {
+        transform : Transform { position : [ x, 0.0 ], ..Default::default() },
+        sprite : ResourceId::new( 0 ),
+        tint : WHITE,
+        effects : SpriteEffects::default(),
+      });
+      raster.submit( &[
+        RenderCommand::CreateSpriteBatch( CreateSpriteBatch { batch, params } ),
+        RenderCommand::BindBatch( BindBatch { batch } ),
+        instance( 0.0 ),
+        instance( 6.0 ),
+        RenderCommand::UnbindBatch( UnbindBatch ),
+        RenderCommand::DrawBatch( DrawBatch { batch } ),
+      ] ).unwrap();
+      let out = bitmap( &raster );
+      assert_eq!( pixel( &out, 0, 0 ), [ 255, 0, 0, 255 ] );
+      assert_eq!( pixel( &out, 7, 1 ), [ 0, 0, 255, 255 ] );
+      assert_eq!( pixel( &out, 4, 0 ), [ 0, 0, 0, 255 ] );
+    }
+
+    // -- meshes --
+
+    #[ test ]
+    fn textured_mesh_maps_uvs_with_v_up()
+    {
+      let floats = | v : &[ f32 ] | v.iter().flat_map( | f | f.to_le_bytes() ).collect::< Vec< u8 > >();
+      let mut assets = red_blue_sheet();
+      assets.geometries.push( GeometryAsset
+      {
+        id : ResourceId::new( 0 ),
+        positions : Source::Bytes( floats( &[ 0.0, 0.0, 4.0, 0.0, 4.0, 4.0, 0.0, 4.0 ] ) ),
+        uvs : Some( Source::Bytes( floats( &[ 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0 ] ) ) ),
+        indices : Some( Source::Bytes( vec![ 0, 1, 2, 0, 2, 3 ] ) ),
+        data_type : DataType::U8,
+      });
+      let mut raster = raster( 4, 4, Antialias::None );
+      raster.load_assets( &assets ).unwrap();
+      raster.submit( &[ RenderCommand::Mesh( Mesh
+      {
+        transform : Transform::default(),
+        geometry : ResourceId::new( 0 ),
+        fill : FillRef::None,
+        texture : Some( ResourceId::new( 0 ) ),
+        topology : Topology::TriangleList,
+        blend : BlendMode::Normal,
+        clip : None,
+      })]).unwrap();
+      let out = bitmap( &raster );
+      // v = 1 is the image's top row (red) and sits at the top of the quad.
+      assert_eq!( pixel( &out, 1, 0 ), [ 255, 0, 0, 255 ] );
+      assert_eq!( pixel( &out, 2, 3 ), [ 0, 0, 255, 255 ] );
+    }
+
+    // -- groups and effects --
+
+    #[ test ]
+    fn group_opacity_and_transform_apply_to_children()
+    {
+      let mut raster = raster( 8, 8, Antialias::None );
+      let mut cmds = vec![ RenderCommand::BeginGroup( BeginGroup
+      {
+        transform : Transform { position : [ 4.0, 4.0 ], ..Default::default() },
+        clip : None,
+        effect : Some( Effect::Opacity( 0.5 ) ),
+      })];
+      cmds.extend( rect( path_style( FillRef::Solid( WHITE ), [ 0.0; 4 ], 0.0 ), 0.0, 0.0, 4.0, 4.0 ) );
+      cmds.push( RenderCommand::EndGroup( EndGroup ) );
+      raster.submit( &cmds ).unwrap();
+      let out = bitmap( &raster );
+      assert_eq!( pixel( &out, 6, 1 ), [ 128, 128, 128, 255 ] );
+      assert_eq!( pixel( &out, 1, 6 ), [ 0, 0, 0, 255 ] );
+    }
+
+    #[ test ]
+    fn blur_spreads_beyond_the_shape()
+    {
+      let mut raster = raster( 20, 20, Antialias::None );
+      let mut cmds = vec![ RenderCommand::BeginGroup( BeginGroup { transform : Transform::default(), clip : None, effect : Some( Effect::Blur { radius : 2.0 } ) } ) ];
+      cmds.extend( rect( path_style( FillRef::Solid( WHITE ), [ 0.0; 4 ], 0.0 ), 8.0, 8.0, 12.0, 12.0 ) );
+      cmds.push( RenderCommand::EndGroup( EndGroup ) );
+      raster.submit( &cmds ).unwrap();
+      let out = bitmap( &raster );
+      let centre = pixel( &out, 10, 10 )[ 0 ];
+      let outside = pixel( &out, 10, 13 )[ 0 ];
+      assert!( centre < 255 && centre > outside && outside > 0, "centre {centre}, outside {outside}" );
+      assert_eq!( pixel( &out, 0, 0 )[ 0 ], 0 );
+    }
+
+    #[ test ]
+    fn drop_shadow_is_offset_in_y_up()
+    {
+      let mut raster = raster( 12, 12, Antialias::None );
+      let mut cmds = vec![ RenderCommand::BeginGroup( BeginGroup
+      {
+        transform : Transform::default(),
+        clip : None,
+        effect : Some( Effect::DropShadow { dx : 4.0, dy : 4.0, blur : 0.0, color : RED } ),
+      })];
+      cmds.extend( rect( path_style( FillRef::Solid( WHITE ), [ 0.0; 4 ], 0.0 ), 0.0, 0.0, 4.0, 4.0 ) );
+      cmds.push( RenderCommand::EndGroup( EndGroup ) );
+      raster.submit( &cmds ).unwrap();
+      let out = bitmap( &raster );
+      // Shape in the bottom-left corner, shadow up and to the right of it.
+      assert_eq!( pixel( &out, 1, 10 ), [ 255, 255, 255, 255 ] );
+      assert_eq!( pixel( &out, 5, 6 ), [ 255, 0, 0, 255 ] );
+    }
+
+    #[ test ]
+    fn color_matrix_converts_to_grey()
+    {
+      let mut raster = raster( 2, 2, Antialias::None );
+      let third = 1.0 / 3.0;
+      let grey =
+      [
+        third, third, third, 0.0, 0.0,
+        third, third, third, 0.0, 0.0,
+        third, third, third, 0.0, 0.0,
+        0.0, 0.0, 0.0, 1.0, 0.0,
+      ];
+      let mut cmds = vec![ RenderCommand::BeginGroup( BeginGroup { transform : Transform::default(), clip : None, effect : Some( Effect::ColorMatrix( grey ) ) } ) ];
+      cmds.extend( rect( path_style( FillRef::Solid( [ 0.0, 0.0, 1.0, 1.0 ] ), [ 0.0; 4 ], 0.0 ), 0.0, 0.0, 2.0, 2.0 ) );
+      cmds.push( RenderCommand::EndGroup( EndGroup ) );
+      raster.submit( &cmds ).unwrap();
+      assert_eq!( pixel( &bitmap( &raster ), 0, 0 ), [ 85, 85, 85, 255 ] );
+    }
+
+    #[ test ]
+    fn unclosed_group_is_flushed_at_end_of_submit()
+    {
+      let mut raster = raster( 2, 2, Antialias::None );
+      let mut cmds = vec![ RenderCommand::BeginGroup( BeginGroup { transform : Transform::default(), clip : None, effect : None } ) ];
+      cmds.extend( rect( path_style( FillRef::Solid( WHITE ), [ 0.0; 4 ], 0.0 ), 0.0, 0.0, 2.0, 2.0 ) );
+      raster.submit( &cmds ).unwrap();
+      assert_eq!( pixel( &bitmap( &raster ), 0, 0 ), [ 255; 4 ] );
+    }
+
+    // -- text --
+
+    #[ test ]
+    fn text_is_drawn_below_a_top_left_anchor()
+    {
+      let mut raster = raster( 20, 20, Antialias::None );
+      raster.submit( &[
+        RenderCommand::BeginText( BeginText
+        {
+          font : ResourceId::new( 0 ),
+          size : 12.0,
+          color : WHITE,
+          anchor : TextAnchor::TopLeft,
+          position : [ 2.0, 18.0 ],
+          along_path : None,
+          clip : None,
+        }),
+        RenderCommand::Char( Char( 'T' ) ),
+        RenderCommand::EndText( EndText ),
+      ] ).unwrap();
+      let out = bitmap( &raster );
+      // 'T' at 2 px per font unit: top bar spans x 2..8 on image rows 2..4,
+      // the stem x 4..6 down to row 12.
+      assert_eq!( pixel( &out, 2, 2 )[ 0 ], 255 );
+      assert_eq!( pixel( &out, 7, 3 )[ 0 ], 255 );
+      assert_eq!( pixel( &out, 5, 11 )[ 0 ], 255 );
+      assert_eq!( pixel( &out, 2, 11 )[ 0 ], 0 );
+      assert_eq!( pixel( &out, 5, 12 )[ 0 ], 0 );
+      assert_eq!( pixel( &out, 2, 1 )[ 0 ], 0 );
+    }
+
+    #[ test ]
+    fn capabilities_report_full_support()
+    {
+      let caps = raster( 1, 1, Antialias::None ).capabilities();
+      assert!( caps.paths && caps.text && caps.gradients && caps.patterns && caps.clip_masks && caps.effects && caps.blend_modes );
+      assert!( !caps.text_on_path );
+      assert_eq!( caps.supported_blend_modes.len(), 5 );
+    }
+  }
+
+}
+
+mod_interface::mod_interface!
+{
+  layer raster_helpers;
+
+  own use RasterBackend;
+}
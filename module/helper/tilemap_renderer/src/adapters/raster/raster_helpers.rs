@@ -0,0 +1,1288 @@
+//! Raster adapter helpers.
+//!
+//! The pixel pipeline behind `RasterBackend`: canvas and mask buffers, curve
+//! flattening, stroke expansion, scanline coverage, gradient evaluation,
+//! blend modes, filter effects and the built-in bitmap font.
+//!
+//! All geometry here is in **pixel space**: Y-down, `(0, 0)` at the top-left
+//! corner, pixel `( x, y )` covering `[x, x + 1) × [y, y + 1)`. Colours are
+//! premultiplied RGBA in `0.0..=1.0` unless a function says otherwise.
+
+mod private
+{
+  use crate::assets::{ GradientKind, GradientStop, PathSegment, PixelFormat };
+  use crate::types::{ BlendMode, DashStyle, LineCap, LineJoin, SamplerFilter, WrapMode };
+
+  // ============================================================================
+  // Affine matrices
+  // ============================================================================
+
+  /// Column-major 3x3 affine matrix, same layout as [`crate::types::Transform::to_mat3`].
+  pub type Mat3 = [ f32; 9 ];
+
+  /// The identity matrix.
+  pub const IDENTITY : Mat3 = [ 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0 ];
+
+  /// Affine product `a * b` (apply `b` first, then `a`).
+  #[ must_use ]
+  pub fn mat_mul( a : &Mat3, b : &Mat3 ) -> Mat3
+  {
+    let mut out = [ 0.0; 9 ];
+    for column in 0..3
+    {
+      for row in 0..3
+      {
+        out[ column * 3 + row ] = ( 0..3 ).map( | k | a[ k * 3 + row ] * b[ column * 3 + k ] ).sum();
+      }
+    }
+    out
+  }
+
+  /// Applies an affine matrix to a point.
+  #[ must_use ]
+  pub fn mat_apply( m : &Mat3, p : [ f32; 2 ] ) -> [ f32; 2 ]
+  {
+    [ m[ 0 ] * p[ 0 ] + m[ 3 ] * p[ 1 ] + m[ 6 ], m[ 1 ] * p[ 0 ] + m[ 4 ] * p[ 1 ] + m[ 7 ] ]
+  }
+
+  /// Applies only the linear part of an affine matrix (no translation).
+  #[ must_use ]
+  pub fn mat_apply_vector( m : &Mat3, v : [ f32; 2 ] ) -> [ f32; 2 ]
+  {
+    [ m[ 0 ] * v[ 0 ] + m[ 3 ] * v[ 1 ], m[ 1 ] * v[ 0 ] + m[ 4 ] * v[ 1 ] ]
+  }
+
+  /// Inverts an affine matrix. Returns `None` when it is singular.
+  #[ must_use ]
+  pub fn mat_invert( m : &Mat3 ) -> Option< Mat3 >
+  {
+    let det = m[ 0 ] * m[ 4 ] - m[ 3 ] * m[ 1 ];
+    if det.abs() < 1e-12
+    {
+      return None;
+    }
+    let inv = 1.0 / det;
+    let a = m[ 4 ] * inv;
+    let b = -m[ 1 ] * inv;
+    let c = -m[ 3 ] * inv;
+    let d = m[ 0 ] * inv;
+    Some( [ a, b, 0.0, c, d, 0.0, -( a * m[ 6 ] + c * m[ 7 ] ), -( b * m[ 6 ] + d * m[ 7 ] ), 1.0 ] )
+  }
+
+  /// Average linear scale factor of a matrix (`sqrt(|det|)`), used to size
+  /// strokes, blur radii and curve tolerances in pixels.
+  #[ must_use ]
+  pub fn mat_scale( m : &Mat3 ) -> f32
+  {
+    ( m[ 0 ] * m[ 4 ] - m[ 3 ] * m[ 1 ] ).abs().sqrt()
+  }
+
+  // ============================================================================
+  // Canvas
+  // ============================================================================
+
+  /// Premultiplied RGBA pixel buffer, row 0 at the top.
+  #[ derive( Debug, Clone ) ]
+  pub struct Canvas
+  {
+    /// Width in pixels.
+    pub width : u32,
+    /// Height in pixels.
+    pub height : u32,
+    /// Premultiplied RGBA pixels, row-major.
+    pub pixels : Vec< [ f32; 4 ] >,
+  }
+
+  impl Canvas
+  {
+    /// Creates a fully transparent canvas.
+    #[ must_use ]
+    pub fn new( width : u32, height : u32 ) -> Self
+    {
+      Self { width, height, pixels : vec![ [ 0.0; 4 ]; width as usize * height as usize ] }
+    }
+
+    /// Fills the whole canvas with a straight-alpha colour.
+    pub fn fill( &mut self, color : [ f32; 4 ] )
+    {
+      self.pixels.fill( premultiply( color ) );
+    }
+
+    /// Composites a premultiplied colour onto pixel `( x, y )`.
+    pub fn blend_pixel( &mut self, x : u32, y : u32, src : [ f32; 4 ], mode : BlendMode )
+    {
+      let index = y as usize * self.width as usize + x as usize;
+      self.pixels[ index ] = blend( self.pixels[ index ], src, mode );
+    }
+
+    /// Composites `layer` over this canvas, scaling each layer pixel by
+    /// `mask` coverage when given.
+    pub fn composite( &mut self, layer : &Canvas, mask : Option< &Mask > )
+    {
+      for ( i, ( dst, src ) ) in self.pixels.iter_mut().zip( &layer.pixels ).enumerate()
+      {
+        let coverage = mask.map_or( 1.0, | m | m.values[ i ] );
+        if src[ 3 ] <= 0.0 || coverage <= 0.0
+        {
+          continue;
+        }
+        *dst = blend( *dst, src.map( | c | c * coverage ), BlendMode::Normal );
+      }
+    }
+
+    /// Converts to straight-alpha RGBA8 bytes.
+    #[ must_use ]
+    pub fn to_rgba8( &self ) -> Vec< u8 >
+    {
+      self.pixels.iter().flat_map( | &p | unpremultiply( p ).map( to_u8 ) ).collect()
+    }
+  }
+
+  /// Per-pixel coverage in `0.0..=1.0`, same layout as a [`Canvas`].
+  #[ derive( Debug, Clone ) ]
+  pub struct Mask
+  {
+    /// Width in pixels.
+    pub width : u32,
+    /// Coverage values, row-major.
+    pub values : Vec< f32 >,
+  }
+
+  impl Mask
+  {
+    /// Rasterizes polygons (non-zero winding) into a full-size mask.
+    #[ must_use ]
+    pub fn from_polygons( polygons : &[ Vec< [ f32; 2 ] > ], width : u32, height : u32, samples : u32 ) -> Self
+    {
+      let mut values = vec![ 0.0; width as usize * height as usize ];
+      rasterize( polygons, width, height, samples, | x, y, coverage |
+      {
+        values[ y as usize * width as usize + x as usize ] = coverage;
+      });
+      Self { width, values }
+    }
+
+    /// Coverage at pixel `( x, y )`.
+    #[ must_use ]
+    pub fn at( &self, x : u32, y : u32 ) -> f32
+    {
+      self.values[ y as usize * self.width as usize + x as usize ]
+    }
+  }
+
+  // ============================================================================
+  // Colour helpers
+  // ============================================================================
+
+  /// Straight → premultiplied alpha.
+  #[ must_use ]
+  pub fn premultiply( c : [ f32; 4 ] ) -> [ f32; 4 ]
+  {
+    let a = c[ 3 ].clamp( 0.0, 1.0 );
+    [ c[ 0 ] * a, c[ 1 ] * a, c[ 2 ] * a, a ]
+  }
+
+  /// Premultiplied → straight alpha.
+  #[ must_use ]
+  pub fn unpremultiply( c : [ f32; 4 ] ) -> [ f32; 4 ]
+  {
+    if c[ 3 ] <= 0.0
+    {
+      return [ 0.0; 4 ];
+    }
+    [ c[ 0 ] / c[ 3 ], c[ 1 ] / c[ 3 ], c[ 2 ] / c[ 3 ], c[ 3 ] ]
+  }
+
+  /// Quantizes a `0.0..=1.0` channel to a byte.
+  #[ must_use ]
+  #[ allow( clippy::cast_sign_loss ) ]
+  pub fn to_u8( c : f32 ) -> u8
+  {
+    ( c.clamp( 0.0, 1.0 ) * 255.0 ).round() as u8
+  }
+
+  /// Composites premultiplied `src` onto premultiplied `dst` using the W3C
+  /// Compositing and Blending Level 1 separable blend formulas (source-over).
+  /// `Add` is `plus-lighter`.
+  #[ must_use ]
+  pub fn blend( dst : [ f32; 4 ], src : [ f32; 4 ], mode : BlendMode ) -> [ f32; 4 ]
+  {
+    let sa = src[ 3 ];
+    let da = dst[ 3 ];
+    if let BlendMode::Normal = mode
+    {
+      return [ 0, 1, 2, 3 ].map( | i | src[ i ] + dst[ i ] * ( 1.0 - sa ) );
+    }
+    if let BlendMode::Add = mode
+    {
+      return [ 0, 1, 2, 3 ].map( | i | ( src[ i ] + dst[ i ] ).min( 1.0 ) );
+    }
+    let s = unpremultiply( src );
+    let d = unpremultiply( dst );
+    let mut out = [ 0.0, 0.0, 0.0, sa + da - sa * da ];
+    for i in 0..3
+    {
+      let mixed = match mode
+      {
+        BlendMode::Multiply => s[ i ] * d[ i ],
+        BlendMode::Screen => s[ i ] + d[ i ] - s[ i ] * d[ i ],
+        BlendMode::Overlay if d[ i ] <= 0.5 => 2.0 * s[ i ] * d[ i ],
+        BlendMode::Overlay => 1.0 - 2.0 * ( 1.0 - s[ i ] ) * ( 1.0 - d[ i ] ),
+        BlendMode::Normal | BlendMode::Add => s[ i ],
+      };
+      out[ i ] = src[ i ] * ( 1.0 - da ) + dst[ i ] * ( 1.0 - sa ) + sa * da * mixed;
+    }
+    out
+  }
+
+  // ============================================================================
+  // Images
+  // ============================================================================
+
+  /// Decoded image, premultiplied RGBA, row 0 at the top.
+  #[ derive( Debug, Clone ) ]
+  pub struct RasterImage
+  {
+    /// Width in pixels.
+    pub width : u32,
+    /// Height in pixels.
+    pub height : u32,
+    /// Premultiplied RGBA texels, row-major.
+    pub pixels : Vec< [ f32; 4 ] >,
+    /// Sampling filter.
+    pub filter : SamplerFilter,
+    /// Wrap mode for coordinates outside the sampled area.
+    pub wrap : WrapMode,
+  }
+
+  impl RasterImage
+  {
+    /// Decodes raw bitmap bytes. Returns `None` when the byte count doesn't
+    /// match `width × height × channels`.
+    #[ must_use ]
+    pub fn from_bitmap( bytes : &[ u8 ], width : u32, height : u32, format : PixelFormat ) -> Option< Self >
+    {
+      let channels = match format
+      {
+        PixelFormat::Rgba8 => 4,
+        PixelFormat::Rgb8 => 3,
+        PixelFormat::Gray8 => 1,
+        PixelFormat::GrayAlpha8 => 2,
+      };
+      if width == 0 || height == 0 || bytes.len() != width as usize * height as usize * channels
+      {
+        return None;
+      }
+      let channel = | b : u8 | f32::from( b ) / 255.0;
+      let pixels = bytes.chunks_exact( channels ).map( | px |
+      {
+        let straight = match format
+        {
+          PixelFormat::Rgba8 => [ channel( px[ 0 ] ), channel( px[ 1 ] ), channel( px[ 2 ] ), channel( px[ 3 ] ) ],
+          PixelFormat::Rgb8 => [ channel( px[ 0 ] ), channel( px[ 1 ] ), channel( px[ 2 ] ), 1.0 ],
+          PixelFormat::Gray8 => [ channel( px[ 0 ] ), channel( px[ 0 ] ), channel( px[ 0 ] ), 1.0 ],
+          PixelFormat::GrayAlpha8 => [ channel( px[ 0 ] ), channel( px[ 0 ] ), channel( px[ 0 ] ), channel( px[ 1 ] ) ],
+        };
+        premultiply( straight )
+      }).collect();
+      Some( Self { width, height, pixels, filter : SamplerFilter::default(), wrap : WrapMode::default() } )
+    }
+
+    /// Samples at image-space `( x, y )` (Y-down, texel centres at `i + 0.5`),
+    /// wrapping within the texel rectangle `bounds = [x0, y0, x1, y1)`.
+    #[ must_use ]
+    #[ allow( clippy::cast_possible_truncation, clippy::cast_sign_loss ) ]
+    pub fn sample( &self, x : f32, y : f32, bounds : [ i64; 4 ] ) -> [ f32; 4 ]
+    {
+      let texel = | tx : i64, ty : i64 |
+      {
+        let tx = wrap_coord( tx, bounds[ 0 ], bounds[ 2 ], self.wrap ).clamp( 0, i64::from( self.width ) - 1 );
+        let ty = wrap_coord( ty, bounds[ 1 ], bounds[ 3 ], self.wrap ).clamp( 0, i64::from( self.height ) - 1 );
+        self.pixels[ ty as usize * self.width as usize + tx as usize ]
+      };
+      match self.filter
+      {
+        SamplerFilter::Nearest => texel( x.floor() as i64, y.floor() as i64 ),
+        SamplerFilter::Linear =>
+        {
+          let fx = x - 0.5;
+          let fy = y - 0.5;
+          let x0 = fx.floor();
+          let y0 = fy.floor();
+          let tx = fx - x0;
+          let ty = fy - y0;
+          let ( x0, y0 ) = ( x0 as i64, y0 as i64 );
+          let top = lerp4( texel( x0, y0 ), texel( x0 + 1, y0 ), tx );
+          let bottom = lerp4( texel( x0, y0 + 1 ), texel( x0 + 1, y0 + 1 ), tx );
+          lerp4( top, bottom, ty )
+        }
+      }
+    }
+
+    /// Full-image texel bounds.
+    #[ must_use ]
+    pub fn bounds( &self ) -> [ i64; 4 ]
+    {
+      [ 0, 0, i64::from( self.width ), i64::from( self.height ) ]
+    }
+  }
+
+  /// Maps texel index `i` into `[lo, hi)` according to the wrap mode.
+  fn wrap_coord( i : i64, lo : i64, hi : i64, wrap : WrapMode ) -> i64
+  {
+    let span = ( hi - lo ).max( 1 );
+    match wrap
+    {
+      WrapMode::Clamp => i.clamp( lo, hi - 1 ),
+      WrapMode::Repeat => lo + ( i - lo ).rem_euclid( span ),
+      WrapMode::Mirror =>
+      {
+        let k = ( i - lo ).rem_euclid( 2 * span );
+        lo + if k < span { k } else { 2 * span - 1 - k }
+      }
+    }
+  }
+
+  fn lerp4( a : [ f32; 4 ], b : [ f32; 4 ], t : f32 ) -> [ f32; 4 ]
+  {
+    [ 0, 1, 2, 3 ].map( | i | a[ i ] + ( b[ i ] - a[ i ] ) * t )
+  }
+
+  // ============================================================================
+  // Paint
+  // ============================================================================
+
+  /// Evaluates a gradient at a point in its own (user-space) coordinates.
+  /// Returns a straight-alpha colour; positions beyond the ends pad with the
+  /// first / last stop, as SVG's default `spreadMethod="pad"` does.
+  #[ must_use ]
+  pub fn gradient_color( kind : &GradientKind, stops : &[ GradientStop ], p : [ f32; 2 ] ) -> [ f32; 4 ]
+  {
+    let t = match *kind
+    {
+      GradientKind::Linear { start, end } =>
+      {
+        let d = [ end[ 0 ] - start[ 0 ], end[ 1 ] - start[ 1 ] ];
+        let len2 = d[ 0 ] * d[ 0 ] + d[ 1 ] * d[ 1 ];
+        if len2 <= 0.0 { 1.0 } else { ( ( p[ 0 ] - start[ 0 ] ) * d[ 0 ] + ( p[ 1 ] - start[ 1 ] ) * d[ 1 ] ) / len2 }
+      }
+      GradientKind::Radial { center, radius, focal } =>
+      {
+        // Distance from the focal point relative to where the ray focal → p
+        // leaves the circle (SVG 1.1 focal-point semantics).
+        let v = [ p[ 0 ] - focal[ 0 ], p[ 1 ] - focal[ 1 ] ];
+        let dist = ( v[ 0 ] * v[ 0 ] + v[ 1 ] * v[ 1 ] ).sqrt();
+        if dist <= 0.0 || radius <= 0.0
+        {
+          0.0
+        }
+        else
+        {
+          let dir = [ v[ 0 ] / dist, v[ 1 ] / dist ];
+          let fc = [ focal[ 0 ] - center[ 0 ], focal[ 1 ] - center[ 1 ] ];
+          let b = dir[ 0 ] * fc[ 0 ] + dir[ 1 ] * fc[ 1 ];
+          let c = fc[ 0 ] * fc[ 0 ] + fc[ 1 ] * fc[ 1 ] - radius * radius;
+          let edge = -b + ( b * b - c ).max( 0.0 ).sqrt();
+          if edge <= 0.0 { 1.0 } else { dist / edge }
+        }
+      }
+    };
+    stop_color( stops, t.clamp( 0.0, 1.0 ) )
+  }
+
+  /// Interpolates a colour from sorted gradient stops.
+  fn stop_color( stops : &[ GradientStop ], t : f32 ) -> [ f32; 4 ]
+  {
+    let ( Some( first ), Some( last ) ) = ( stops.first(), stops.last() ) else { return [ 0.0; 4 ] };
+    if t <= first.offset
+    {
+      return first.color;
+    }
+    for pair in stops.windows( 2 )
+    {
+      if t <= pair[ 1 ].offset
+      {
+        let span = pair[ 1 ].offset - pair[ 0 ].offset;
+        let local = if span <= 0.0 { 1.0 } else { ( t - pair[ 0 ].offset ) / span };
+        return lerp4( pair[ 0 ].color, pair[ 1 ].color, local );
+      }
+    }
+    last.color
+  }
+
+  // ============================================================================
+  // Path flattening
+  // ============================================================================
+
+  /// A flattened subpath.
+  #[ derive( Debug, Clone, Default ) ]
+  pub struct Subpath
+  {
+    /// Polyline vertices.
+    pub points : Vec< [ f32; 2 ] >,
+    /// Whether the subpath was closed with `ClosePath`.
+    pub closed : bool,
+  }
+
+  /// Flattens path commands into polylines in the caller's coordinate space.
+  ///
+  /// `scale` is the pixel size of one path unit; curves are subdivided so
+  /// each chord deviates from the curve by roughly a quarter pixel.
+  #[ derive( Debug, Clone ) ]
+  pub struct PathBuilder
+  {
+    subpaths : Vec< Subpath >,
+    scale : f32,
+  }
+
+  impl PathBuilder
+  {
+    /// Creates an empty builder for paths drawn at `scale` pixels per unit.
+    #[ must_use ]
+    pub fn new( scale : f32 ) -> Self
+    {
+      Self { subpaths : Vec::new(), scale : scale.max( 1e-3 ) }
+    }
+
+    /// Flattens asset path segments.
+    #[ must_use ]
+    pub fn from_segments( segments : &[ PathSegment ], scale : f32 ) -> Vec< Subpath >
+    {
+      let mut builder = Self::new( scale );
+      for segment in segments
+      {
+        match *segment
+        {
+          PathSegment::MoveTo( x, y ) => builder.move_to( [ x, y ] ),
+          PathSegment::LineTo( x, y ) => builder.line_to( [ x, y ] ),
+          PathSegment::QuadTo { cx, cy, x, y } => builder.quad_to( [ cx, cy ], [ x, y ] ),
+          PathSegment::CubicTo { c1x, c1y, c2x, c2y, x, y } => builder.cubic_to( [ c1x, c1y ], [ c2x, c2y ], [ x, y ] ),
+          PathSegment::ArcTo { rx, ry, rotation, large_arc, sweep, x, y } => builder.arc_to( rx, ry, rotation, large_arc, sweep, [ x, y ] ),
+          PathSegment::Close => builder.close(),
+        }
+      }
+      builder.finish()
+    }
+
+    /// Current pen position.
+    #[ must_use ]
+    pub fn pen( &self ) -> [ f32; 2 ]
+    {
+      self.subpaths.last().and_then( | s | s.points.last() ).copied().unwrap_or( [ 0.0, 0.0 ] )
+    }
+
+    /// Starts a new subpath.
+    pub fn move_to( &mut self, p : [ f32; 2 ] )
+    {
+      self.subpaths.push( Subpath { points : vec![ p ], closed : false } );
+    }
+
+    /// Appends a straight segment.
+    pub fn line_to( &mut self, p : [ f32; 2 ] )
+    {
+      match self.subpaths.last_mut()
+      {
+        Some( subpath ) if !subpath.closed => subpath.points.push( p ),
+        _ =>
+        {
+          // Drawing after ClosePath (or without MoveTo) continues from the pen.
+          let pen = self.pen();
+          self.subpaths.push( Subpath { points : vec![ pen, p ], closed : false } );
+        }
+      }
+    }
+
+    /// Appends a quadratic bezier.
+    pub fn quad_to( &mut self, c : [ f32; 2 ], p : [ f32; 2 ] )
+    {
+      let p0 = self.pen();
+      let n = self.segments( &[ p0, c, p ] );
+      for i in 1..=n
+      {
+        let t = i as f32 / n as f32;
+        let u = 1.0 - t;
+        self.line_to( [ 0, 1 ].map( | k | u * u * p0[ k ] + 2.0 * u * t * c[ k ] + t * t * p[ k ] ) );
+      }
+    }
+
+    /// Appends a cubic bezier.
+    pub fn cubic_to( &mut self, c1 : [ f32; 2 ], c2 : [ f32; 2 ], p : [ f32; 2 ] )
+    {
+      let p0 = self.pen();
+      let n = self.segments( &[ p0, c1, c2, p ] );
+      for i in 1..=n
+      {
+        let t = i as f32 / n as f32;
+        let u = 1.0 - t;
+        self.line_to( [ 0, 1 ].map( | k | u * u * u * p0[ k ] + 3.0 * u * u * t * c1[ k ] + 3.0 * u * t * t * c2[ k ] + t * t * t * p[ k ] ) );
+      }
+    }
+
+    /// Appends an SVG endpoint arc (SVG 1.1 implementation notes, F.6.5).
+    pub fn arc_to( &mut self, rx : f32, ry : f32, rotation : f32, large_arc : bool, sweep : bool, p : [ f32; 2 ] )
+    {
+      let [ x1, y1 ] = self.pen();
+      let [ x2, y2 ] = p;
+      let ( mut rx, mut ry ) = ( rx.abs(), ry.abs() );
+      if rx <= 0.0 || ry <= 0.0 || ( ( x1 - x2 ).abs() < f32::EPSILON && ( y1 - y2 ).abs() < f32::EPSILON )
+      {
+        self.line_to( p );
+        return;
+      }
+      let ( sin_phi, cos_phi ) = rotation.sin_cos();
+      let dx = ( x1 - x2 ) * 0.5;
+      let dy = ( y1 - y2 ) * 0.5;
+      let x1p = cos_phi * dx + sin_phi * dy;
+      let y1p = -sin_phi * dx + cos_phi * dy;
+      let lambda = ( x1p * x1p ) / ( rx * rx ) + ( y1p * y1p ) / ( ry * ry );
+      if lambda > 1.0
+      {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+      }
+      let num = rx * rx * ry * ry - rx * rx * y1p * y1p - ry * ry * x1p * x1p;
+      let den = rx * rx * y1p * y1p + ry * ry * x1p * x1p;
+      let mut coef = ( num / den ).max( 0.0 ).sqrt();
+      if large_arc == sweep
+      {
+        coef = -coef;
+      }
+      let cxp = coef * rx * y1p / ry;
+      let cyp = -coef * ry * x1p / rx;
+      let cx = cos_phi * cxp - sin_phi * cyp + ( x1 + x2 ) * 0.5;
+      let cy = sin_phi * cxp + cos_phi * cyp + ( y1 + y2 ) * 0.5;
+
+      let angle = | ux : f32, uy : f32, vx : f32, vy : f32 | ( ux * vy - uy * vx ).atan2( ux * vx + uy * vy );
+      let theta1 = angle( 1.0, 0.0, ( x1p - cxp ) / rx, ( y1p - cyp ) / ry );
+      let mut delta = angle( ( x1p - cxp ) / rx, ( y1p - cyp ) / ry, ( -x1p - cxp ) / rx, ( -y1p - cyp ) / ry );
+      if !sweep && delta > 0.0
+      {
+        delta -= core::f32::consts::TAU;
+      }
+      if sweep && delta < 0.0
+      {
+        delta += core::f32::consts::TAU;
+      }
+
+      let n = segment_count( rx.max( ry ) * delta.abs() * self.scale );
+      for i in 1..=n
+      {
+        let theta = theta1 + delta * i as f32 / n as f32;
+        let ( sin_t, cos_t ) = theta.sin_cos();
+        self.line_to(
+        [
+          cos_phi * rx * cos_t - sin_phi * ry * sin_t + cx,
+          sin_phi * rx * cos_t + cos_phi * ry * sin_t + cy,
+        ]);
+      }
+    }
+
+    /// Closes the current subpath.
+    pub fn close( &mut self )
+    {
+      if let Some( subpath ) = self.subpaths.last_mut()
+      {
+        subpath.closed = true;
+      }
+    }
+
+    /// Returns the flattened subpaths.
+    #[ must_use ]
+    pub fn finish( self ) -> Vec< Subpath >
+    {
+      self.subpaths
+    }
+
+    /// Subdivision count from the control polygon length in pixels.
+    fn segments( &self, control : &[ [ f32; 2 ] ] ) -> u32
+    {
+      let length : f32 = control.windows( 2 ).map( | w | distance( w[ 0 ], w[ 1 ] ) ).sum();
+      segment_count( length * self.scale )
+    }
+  }
+
+  #[ allow( clippy::cast_sign_loss ) ]
+  fn segment_count( pixels : f32 ) -> u32
+  {
+    ( ( pixels / 0.25 ).sqrt().ceil() as u32 ).clamp( 1, 256 )
+  }
+
+  fn distance( a : [ f32; 2 ], b : [ f32; 2 ] ) -> f32
+  {
+    ( ( b[ 0 ] - a[ 0 ] ).powi( 2 ) + ( b[ 1 ] - a[ 1 ] ).powi( 2 ) ).sqrt()
+  }
+
+  // ============================================================================
+  // Stroking
+  // ============================================================================
+
+  /// Stroke parameters in pixel units.
+  #[ derive( Debug, Clone, Copy ) ]
+  pub struct StrokeStyle
+  {
+    /// Stroke width in pixels.
+    pub width : f32,
+    /// Line cap style.
+    pub cap : LineCap,
+    /// Line join style.
+    pub join : LineJoin,
+    /// Dash pattern, already scaled to pixels.
+    pub dash : DashStyle,
+  }
+
+  /// SVG's default `stroke-miterlimit`.
+  const MITER_LIMIT : f32 = 4.0;
+
+  /// Expands pixel-space polylines into fill polygons covering the stroke.
+  ///
+  /// Every polygon is emitted counter-clockwise, so filling the result with
+  /// the non-zero rule yields the union of segments, joins and caps.
+  #[ must_use ]
+  pub fn stroke_polygons( subpaths : &[ Subpath ], style : &StrokeStyle ) -> Vec< Vec< [ f32; 2 ] > >
+  {
+    let half = style.width * 0.5;
+    let mut out = Vec::new();
+    if half <= 0.0
+    {
+      return out;
+    }
+    for subpath in subpaths
+    {
+      for piece in dash_subpath( subpath, &style.dash )
+      {
+        stroke_piece( &piece, half, style, &mut out );
+      }
+    }
+    for polygon in &mut out
+    {
+      if signed_area( polygon ) < 0.0
+      {
+        polygon.reverse();
+      }
+    }
+    out
+  }
+
+  fn stroke_piece( piece : &Subpath, half : f32, style : &StrokeStyle, out : &mut Vec< Vec< [ f32; 2 ] > > )
+  {
+    let mut points : Vec< [ f32; 2 ] > = Vec::with_capacity( piece.points.len() );
+    for &p in &piece.points
+    {
+      if points.last().is_none_or( | &last | distance( last, p ) > 1e-4 )
+      {
+        points.push( p );
+      }
+    }
+    let closed = piece.closed && points.len() > 2;
+    if closed && distance( points[ 0 ], points[ points.len() - 1 ] ) <= 1e-4
+    {
+      points.pop();
+    }
+    if points.len() == 1
+    {
+      // Zero-length subpath: only round / square caps paint anything.
+      match style.cap
+      {
+        LineCap::Round => out.push( circle( points[ 0 ], half ) ),
+        LineCap::Square =>
+        {
+          let [ x, y ] = points[ 0 ];
+          out.push( vec![ [ x - half, y - half ], [ x + half, y - half ], [ x + half, y + half ], [ x - half, y + half ] ] );
+        }
+        LineCap::Butt => {}
+      }
+      return;
+    }
+    if points.len() < 2
+    {
+      return;
+    }
+    if closed
+    {
+      points.push( points[ 0 ] );
+    }
+    if !closed && let LineCap::Square = style.cap
+    {
+      let n = points.len();
+      let start = direction( points[ 1 ], points[ 0 ] );
+      let end = direction( points[ n - 2 ], points[ n - 1 ] );
+      points[ 0 ] = [ points[ 0 ][ 0 ] + start[ 0 ] * half, points[ 0 ][ 1 ] + start[ 1 ] * half ];
+      points[ n - 1 ] = [ points[ n - 1 ][ 0 ] + end[ 0 ] * half, points[ n - 1 ][ 1 ] + end[ 1 ] * half ];
+    }
+
+    for w in points.windows( 2 )
+    {
+      let d = direction( w[ 0 ], w[ 1 ] );
+      let n = [ -d[ 1 ] * half, d[ 0 ] * half ];
+      out.push( vec!
+      [
+        [ w[ 0 ][ 0 ] + n[ 0 ], w[ 0 ][ 1 ] + n[ 1 ] ],
+        [ w[ 1 ][ 0 ] + n[ 0 ], w[ 1 ][ 1 ] + n[ 1 ] ],
+        [ w[ 1 ][ 0 ] - n[ 0 ], w[ 1 ][ 1 ] - n[ 1 ] ],
+        [ w[ 0 ][ 0 ] - n[ 0 ], w[ 0 ][ 1 ] - n[ 1 ] ],
+      ]);
+    }
+
+    // Joins at interior vertices (and at the seam of closed subpaths).
+    let last = points.len() - 1;
+    let joints = if closed { 1..=last } else { 1..=last.saturating_sub( 1 ) };
+    for i in joints
+    {
+      let prev = points[ i - 1 ];
+      let vertex = points[ i ];
+      let next = if i == last { points[ 1 ] } else { points[ i + 1 ] };
+      join( prev, vertex, next, half, style.join, out );
+    }
+
+    if !closed && matches!( style.cap, LineCap::Round )
+    {
+      out.push( circle( points[ 0 ], half ) );
+      out.push( circle( points[ last ], half ) );
+    }
+  }
+
+  fn join( prev : [ f32; 2 ], vertex : [ f32; 2 ], next : [ f32; 2 ], half : f32, kind : LineJoin, out : &mut Vec< Vec< [ f32; 2 ] > > )
+  {
+    let d1 = direction( prev, vertex );
+    let d2 = direction( vertex, next );
+    let cross = d1[ 0 ] * d2[ 1 ] - d1[ 1 ] * d2[ 0 ];
+    if cross.abs() < 1e-6 && d1[ 0 ] * d2[ 0 ] + d1[ 1 ] * d2[ 1 ] > 0.0
+    {
+      return; // Collinear — the segment quads already meet.
+    }
+    if let LineJoin::Round = kind
+    {
+      out.push( circle( vertex, half ) );
+      return;
+    }
+    // The outer side of the turn is opposite the turn direction.
+    let side = if cross > 0.0 { -1.0 } else { 1.0 };
+    let n1 = [ -d1[ 1 ] * half * side, d1[ 0 ] * half * side ];
+    let n2 = [ -d2[ 1 ] * half * side, d2[ 0 ] * half * side ];
+    let a = [ vertex[ 0 ] + n1[ 0 ], vertex[ 1 ] + n1[ 1 ] ];
+    let b = [ vertex[ 0 ] + n2[ 0 ], vertex[ 1 ] + n2[ 1 ] ];
+    let bisector = [ n1[ 0 ] + n2[ 0 ], n1[ 1 ] + n2[ 1 ] ];
+    let bisector_len = ( bisector[ 0 ] * bisector[ 0 ] + bisector[ 1 ] * bisector[ 1 ] ).sqrt();
+    if let LineJoin::Miter = kind
+      && bisector_len > 1e-6
+    {
+      let unit = [ bisector[ 0 ] / bisector_len, bisector[ 1 ] / bisector_len ];
+      let cos_half = ( unit[ 0 ] * n1[ 0 ] + unit[ 1 ] * n1[ 1 ] ) / half;
+      if cos_half > 1.0 / MITER_LIMIT
+      {
+        let length = half / cos_half;
+        let tip = [ vertex[ 0 ] + unit[ 0 ] * length, vertex[ 1 ] + unit[ 1 ] * length ];
+        out.push( vec![ vertex, a, tip, b ] );
+        return;
+      }
+    }
+    out.push( vec![ vertex, a, b ] );
+  }
+
+  /// Splits a subpath into dash pieces. Returns the subpath itself when the
+  /// pattern is empty.
+  fn dash_subpath( subpath : &Subpath, dash : &DashStyle ) -> Vec< Subpath >
+  {
+    let mut pattern : Vec< f32 > = dash.pattern.iter().copied().take_while( | &v | v > 0.0 ).collect();
+    if pattern.is_empty()
+    {
+      return vec![ subpath.clone() ];
+    }
+    if pattern.len() % 2 == 1
+    {
+      // SVG repeats an odd-length dash array to make it even.
+      pattern.extend_from_within( .. );
+    }
+    let period : f32 = pattern.iter().sum();
+    let mut points = subpath.points.clone();
+    if subpath.closed && let Some( &first ) = points.first()
+    {
+      points.push( first );
+    }
+
+    let mut index = 0;
+    let mut remaining = pattern[ 0 ];
+    let mut offset = dash.offset.rem_euclid( period );
+    while offset > 0.0
+    {
+      if offset < remaining
+      {
+        remaining -= offset;
+        break;
+      }
+      offset -= remaining;
+      index = ( index + 1 ) % pattern.len();
+      remaining = pattern[ index ];
+    }
+
+    let mut pieces = Vec::new();
+    let mut current : Option< Subpath > = ( index % 2 == 0 ).then( || Subpath { points : vec![ points[ 0 ] ], closed : false } );
+    for w in points.windows( 2 )
+    {
+      let mut start = w[ 0 ];
+      let mut length = distance( w[ 0 ], w[ 1 ] );
+      while length > 0.0
+      {
+        let step = remaining.min( length );
+        let t = step / length;
+        let point = [ start[ 0 ] + ( w[ 1 ][ 0 ] - start[ 0 ] ) * t, start[ 1 ] + ( w[ 1 ][ 1 ] - start[ 1 ] ) * t ];
+        if let Some( piece ) = current.as_mut()
+        {
+          piece.points.push( point );
+        }
+        length -= step;
+        remaining -= step;
+        start = point;
+        if remaining <= 1e-6
+        {
+          index = ( index + 1 ) % pattern.len();
+          remaining = pattern[ index ];
+          if index % 2 == 0
+          {
+            current = Some( Subpath { points : vec![ point ], closed : false } );
+          }
+          else
+          {
+            pieces.extend( current.take() );
+          }
+        }
+      }
+    }
+    if let Some( piece ) = current
+    {
+      pieces.push( piece );
+    }
+    pieces
+  }
+
+  fn direction( from : [ f32; 2 ], to : [ f32; 2 ] ) -> [ f32; 2 ]
+  {
+    let len = distance( from, to );
+    if len <= 0.0 { [ 1.0, 0.0 ] } else { [ ( to[ 0 ] - from[ 0 ] ) / len, ( to[ 1 ] - from[ 1 ] ) / len ] }
+  }
+
+  /// Polygon approximating a circle.
+  #[ must_use ]
+  #[ allow( clippy::cast_sign_loss ) ]
+  pub fn circle( center : [ f32; 2 ], radius : f32 ) -> Vec< [ f32; 2 ] >
+  {
+    let n = ( ( radius * 4.0 ).ceil() as u32 ).clamp( 8, 128 );
+    ( 0..n ).map( | i |
+    {
+      let theta = core::f32::consts::TAU * i as f32 / n as f32;
+      [ center[ 0 ] + radius * theta.cos(), center[ 1 ] + radius * theta.sin() ]
+    }).collect()
+  }
+
+  /// Shoelace signed area (positive = counter-clockwise in a Y-down space
+  /// reads as clockwise on screen; only the sign consistency matters here).
+  #[ must_use ]
+  pub fn signed_area( polygon : &[ [ f32; 2 ] ] ) -> f32
+  {
+    let mut area = 0.0;
+    for ( i, a ) in polygon.iter().enumerate()
+    {
+      let b = polygon[ ( i + 1 ) % polygon.len() ];
+      area += a[ 0 ] * b[ 1 ] - b[ 0 ] * a[ 1 ];
+    }
+    area * 0.5
+  }
+
+  // ============================================================================
+  // Scanline coverage
+  // ============================================================================
+
+  /// Rasterizes polygons with the non-zero winding rule and reports
+  /// `( x, y, coverage )` for every touched pixel inside the canvas.
+  ///
+  /// `samples` sub-scanlines are taken per pixel row, with exact horizontal
+  /// span coverage on each; `samples == 1` switches to aliased rendering,
+  /// covering a pixel only when its centre lies inside.
+  #[ allow( clippy::cast_sign_loss, clippy::cast_possible_truncation ) ]
+  pub fn rasterize( polygons : &[ Vec< [ f32; 2 ] > ], width : u32, height : u32, samples : u32, mut emit : impl FnMut( u32, u32, f32 ) )
+  {
+    let points = polygons.iter().flatten();
+    let ( mut min_x, mut min_y, mut max_x, mut max_y ) = ( f32::INFINITY, f32::INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY );
+    for p in points
+    {
+      min_x = min_x.min( p[ 0 ] );
+      min_y = min_y.min( p[ 1 ] );
+      max_x = max_x.max( p[ 0 ] );
+      max_y = max_y.max( p[ 1 ] );
+    }
+    if !( min_x.is_finite() && min_y.is_finite() && max_x.is_finite() && max_y.is_finite() )
+    {
+      return;
+    }
+    let x0 = min_x.floor().max( 0.0 ) as u32;
+    let x1 = ( max_x.ceil().max( 0.0 ) as u32 ).min( width );
+    let y0 = min_y.floor().max( 0.0 ) as u32;
+    let y1 = ( max_y.ceil().max( 0.0 ) as u32 ).min( height );
+    if x0 >= x1 || y0 >= y1
+    {
+      return;
+    }
+
+    let aliased = samples <= 1;
+    let samples = samples.max( 1 );
+    let weight = 1.0 / samples as f32;
+    let mut row = vec![ 0.0_f32; ( x1 - x0 ) as usize ];
+    let mut crossings : Vec< ( f32, i32 ) > = Vec::new();
+
+    for y in y0..y1
+    {
+      row.fill( 0.0 );
+      for s in 0..samples
+      {
+        let sample_y = y as f32 + ( s as f32 + 0.5 ) * weight;
+        crossings.clear();
+        for polygon in polygons
+        {
+          for ( i, a ) in polygon.iter().enumerate()
+          {
+            let b = polygon[ ( i + 1 ) % polygon.len() ];
+            if ( a[ 1 ] <= sample_y ) != ( b[ 1 ] <= sample_y )
+            {
+              let t = ( sample_y - a[ 1 ] ) / ( b[ 1 ] - a[ 1 ] );
+              crossings.push( ( a[ 0 ] + t * ( b[ 0 ] - a[ 0 ] ), if b[ 1 ] > a[ 1 ] { 1 } else { -1 } ) );
+            }
+          }
+        }
+        crossings.sort_by( | a, b | a.0.total_cmp( &b.0 ) );
+        let mut winding = 0;
+        for pair in crossings.windows( 2 )
+        {
+          winding += pair[ 0 ].1;
+          if winding == 0
+          {
+            continue;
+          }
+          let ( start, end ) = ( pair[ 0 ].0.max( x0 as f32 ), pair[ 1 ].0.min( x1 as f32 ) );
+          if start >= end
+          {
+            continue;
+          }
+          if aliased
+          {
+            // Pixels whose centre lies in [start, end).
+            let first = ( start - 0.5 ).ceil().max( x0 as f32 ) as u32;
+            let last = ( ( end - 0.5 ).ceil().max( 0.0 ) as u32 ).min( x1 );
+            for x in first..last
+            {
+              row[ ( x - x0 ) as usize ] = 1.0;
+            }
+            continue;
+          }
+          let first = start.floor() as u32;
+          let last = ( end.ceil() as u32 ).min( x1 );
+          for x in first..last
+          {
+            let covered = ( end.min( x as f32 + 1.0 ) - start.max( x as f32 ) ).max( 0.0 );
+            row[ ( x - x0 ) as usize ] += covered * weight;
+          }
+        }
+      }
+      for ( i, &coverage ) in row.iter().enumerate()
+      {
+        if coverage > 0.0
+        {
+          emit( x0 + i as u32, y, coverage.min( 1.0 ) );
+        }
+      }
+    }
+  }
+
+  /// Fills polygons into `canvas`, shading each covered pixel with
+  /// `shade( pixel_centre )` (premultiplied) scaled by coverage and the
+  /// optional clip mask.
+  pub fn fill_polygons
+  (
+    canvas : &mut Canvas,
+    polygons : &[ Vec< [ f32; 2 ] > ],
+    samples : u32,
+    mode : BlendMode,
+    clip : Option< &Mask >,
+    mut shade : impl FnMut( [ f32; 2 ] ) -> [ f32; 4 ],
+  )
+  {
+    let ( width, height ) = ( canvas.width, canvas.height );
+    rasterize( polygons, width, height, samples, | x, y, coverage |
+    {
+      let coverage = coverage * clip.map_or( 1.0, | m | m.at( x, y ) );
+      if coverage <= 0.0
+      {
+        return;
+      }
+      let color = shade( [ x as f32 + 0.5, y as f32 + 0.5 ] );
+      if color[ 3 ] > 0.0
+      {
+        canvas.blend_pixel( x, y, color.map( | c | c * coverage ), mode );
+      }
+    });
+  }
+
+  // ============================================================================
+  // Effects
+  // ============================================================================
+
+  /// Approximates a gaussian blur of standard deviation `sigma` (pixels) with
+  /// three box blurs. Pixels outside the canvas count as transparent, as in
+  /// SVG's `feGaussianBlur`.
+  #[ allow( clippy::cast_sign_loss, clippy::cast_possible_truncation ) ]
+  pub fn gaussian_blur( canvas : &mut Canvas, sigma : f32 )
+  {
+    if sigma <= 0.0 || canvas.pixels.is_empty()
+    {
+      return;
+    }
+    // Box sizes for three passes (W3C filter effects, `feGaussianBlur` note).
+    let d = ( sigma * 3.0 * ( 2.0 * core::f32::consts::PI ).sqrt() / 4.0 + 0.5 ).floor().max( 1.0 ) as usize;
+    let radius = d / 2;
+    let ( w, h ) = ( canvas.width as usize, canvas.height as usize );
+    let mut scratch = vec![ [ 0.0_f32; 4 ]; w.max( h ) ];
+    for _ in 0..3
+    {
+      for y in 0..h
+      {
+        let line : Vec< [ f32; 4 ] > = canvas.pixels[ y * w..( y + 1 ) * w ].to_vec();
+        box_blur_line( &line, &mut scratch[ ..w ], radius );
+        canvas.pixels[ y * w..( y + 1 ) * w ].copy_from_slice( &scratch[ ..w ] );
+      }
+      for x in 0..w
+      {
+        let line : Vec< [ f32; 4 ] > = ( 0..h ).map( | y | canvas.pixels[ y * w + x ] ).collect();
+        box_blur_line( &line, &mut scratch[ ..h ], radius );
+        for ( y, &px ) in scratch[ ..h ].iter().enumerate()
+        {
+          canvas.pixels[ y * w + x ] = px;
+        }
+      }
+    }
+  }
+
+  /// Running-sum box blur of `2 * radius + 1` taps along one line.
+  fn box_blur_line( input : &[ [ f32; 4 ] ], output : &mut [ [ f32; 4 ] ], radius : usize )
+  {
+    let taps = ( 2 * radius + 1 ) as f32;
+    let mut sum = [ 0.0_f32; 4 ];
+    for px in input.iter().take( radius + 1 )
+    {
+      for c in 0..4 { sum[ c ] += px[ c ]; }
+    }
+    for i in 0..input.len()
+    {
+      output[ i ] = sum.map( | s | ( s / taps ).max( 0.0 ) );
+      if let Some( px ) = input.get( i + radius + 1 )
+      {
+        for c in 0..4 { sum[ c ] += px[ c ]; }
+      }
+      if i >= radius
+      {
+        let px = input[ i - radius ];
+        for c in 0..4 { sum[ c ] -= px[ c ]; }
+      }
+    }
+  }
+
+  /// Renders a drop shadow under the canvas content: the alpha channel is
+  /// blurred, shifted by `offset` pixels and tinted with the straight-alpha
+  /// `color`, then the original content is composited on top.
+  #[ allow( clippy::cast_possible_truncation ) ]
+  pub fn drop_shadow( canvas : &mut Canvas, offset : [ f32; 2 ], sigma : f32, color : [ f32; 4 ] )
+  {
+    let ( w, h ) = ( i64::from( canvas.width ), i64::from( canvas.height ) );
+    let ( ox, oy ) = ( offset[ 0 ].round() as i64, offset[ 1 ].round() as i64 );
+    let tint = premultiply( color );
+    let mut shadow = Canvas::new( canvas.width, canvas.height );
+    for y in 0..h
+    {
+      for x in 0..w
+      {
+        let ( sx, sy ) = ( x - ox, y - oy );
+        if sx < 0 || sy < 0 || sx >= w || sy >= h
+        {
+          continue;
+        }
+        #[ allow( clippy::cast_sign_loss ) ]
+        let alpha = canvas.pixels[ ( sy * w + sx ) as usize ][ 3 ];
+        #[ allow( clippy::cast_sign_loss ) ]
+        let index = ( y * w + x ) as usize;
+        shadow.pixels[ index ] = tint.map( | c | c * alpha );
+      }
+    }
+    gaussian_blur( &mut shadow, sigma );
+    shadow.composite( canvas, None );
+    *canvas = shadow;
+  }
+
+  /// Applies an `feColorMatrix`-style 4×5 row-major matrix to straight-alpha
+  /// RGBA (offsets in `0.0..=1.0` units).
+  pub fn color_matrix( canvas : &mut Canvas, m : &[ f32; 20 ] )
+  {
+    for px in &mut canvas.pixels
+    {
+      let c = unpremultiply( *px );
+      let row = | r : usize | ( m[ r * 5 ] * c[ 0 ] + m[ r * 5 + 1 ] * c[ 1 ] + m[ r * 5 + 2 ] * c[ 2 ] + m[ r * 5 + 3 ] * c[ 3 ] + m[ r * 5 + 4 ] ).clamp( 0.0, 1.0 );
+      *px = premultiply( [ row( 0 ), row( 1 ), row( 2 ), row( 3 ) ] );
+    }
+  }
+
+  /// Multiplies the whole canvas by `opacity`.
+  pub fn apply_opacity( canvas : &mut Canvas, opacity : f32 )
+  {
+    let opacity = opacity.clamp( 0.0, 1.0 );
+    for px in &mut canvas.pixels
+    {
+      *px = px.map( | c | c * opacity );
+    }
+  }
+
+  // ============================================================================
+  // Built-in font
+  // ============================================================================
+
+  /// Glyph cell width in font units (3 columns + 1 spacing).
+  pub const GLYPH_ADVANCE : f32 = 4.0;
+
+  /// Glyph height in font units.
+  pub const GLYPH_HEIGHT : f32 = 5.0;
+
+  /// Line height in font units; `BeginText::size` maps to this many units.
+  pub const GLYPH_LINE : f32 = 6.0;
+
+  /// 3×5 bitmap glyphs, one row per byte, bit `0b100` = left column.
+  /// Lowercase letters render with their uppercase glyph.
+  const GLYPHS : &[ ( char, [ u8; 5 ] ) ] =
+  &[
+    ( 'A', [ 0b010, 0b101, 0b111, 0b101, 0b101 ] ),
+    ( 'B', [ 0b110, 0b101, 0b110, 0b101, 0b110 ] ),
+    ( 'C', [ 0b011, 0b100, 0b100, 0b100, 0b011 ] ),
+    ( 'D', [ 0b110, 0b101, 0b101, 0b101, 0b110 ] ),
+    ( 'E', [ 0b111, 0b100, 0b110, 0b100, 0b111 ] ),
+    ( 'F', [ 0b111, 0b100, 0b110, 0b100, 0b100 ] ),
+    ( 'G', [ 0b011, 0b100, 0b101, 0b101, 0b011 ] ),
+    ( 'H', [ 0b101, 0b101, 0b111, 0b101, 0b101 ] ),
+    ( 'I', [ 0b111, 0b010, 0b010, 0b010, 0b111 ] ),
+    ( 'J', [ 0b001, 0b001, 0b001, 0b101, 0b010 ] ),
+    ( 'K', [ 0b101, 0b101, 0b110, 0b101, 0b101 ] ),
+    ( 'L', [ 0b100, 0b100, 0b100, 0b100, 0b111 ] ),
+    ( 'M', [ 0b101, 0b111, 0b111, 0b101, 0b101 ] ),
+    ( 'N', [ 0b110, 0b101, 0b101, 0b101, 0b101 ] ),
+    ( 'O', [ 0b010, 0b101, 0b101, 0b101, 0b010 ] ),
+    ( 'P', [ 0b110, 0b101, 0b110, 0b100, 0b100 ] ),
+    ( 'Q', [ 0b010, 0b101, 0b101, 0b110, 0b011 ] ),
+    ( 'R', [ 0b110, 0b101, 0b110, 0b101, 0b101 ] ),
+    ( 'S', [ 0b011, 0b100, 0b010, 0b001, 0b110 ] ),
+    ( 'T', [ 0b111, 0b010, 0b010, 0b010, 0b010 ] ),
+    ( 'U', [ 0b101, 0b101, 0b101, 0b101, 0b111 ] ),
+    ( 'V', [ 0b101, 0b101, 0b101, 0b101, 0b010 ] ),
+    ( 'W', [ 0b101, 0b101, 0b111, 0b111, 0b101 ] ),
+    ( 'X', [ 0b101, 0b101, 0b010, 0b101, 0b101 ] ),
+    ( 'Y', [ 0b101, 0b101, 0b010, 0b010, 0b010 ] ),
+    ( 'Z', [ 0b111, 0b001, 0b010, 0b100, 0b111 ] ),
+    ( '0', [ 0b111, 0b101, 0b101, 0b101, 0b111 ] ),
+    ( '1', [ 0b010, 0b110, 0b010, 0b010, 0b111 ] ),
+    ( '2', [ 0b110, 0b001, 0b010, 0b100, 0b111 ] ),
+    ( '3', [ 0b110, 0b001, 0b010, 0b001, 0b110 ] ),
+    ( '4', [ 0b101, 0b101, 0b111, 0b001, 0b001 ] ),
+    ( '5', [ 0b111, 0b100, 0b110, 0b001, 0b110 ] ),
+    ( '6', [ 0b011, 0b100, 0b111, 0b101, 0b111 ] ),
+    ( '7', [ 0b111, 0b001, 0b010, 0b010, 0b010 ] ),
+    ( '8', [ 0b111, 0b101, 0b111, 0b101, 0b111 ] ),
+    ( '9', [ 0b111, 0b101, 0b111, 0b001, 0b110 ] ),
+    ( ' ', [ 0b000, 0b000, 0b000, 0b000, 0b000 ] ),
+    ( '.', [ 0b000, 0b000, 0b000, 0b000, 0b010 ] ),
+    ( ',', [ 0b000, 0b000, 0b000, 0b010, 0b100 ] ),
+    ( ':', [ 0b000, 0b010, 0b000, 0b010, 0b000 ] ),
+    ( ';', [ 0b000, 0b010, 0b000, 0b010, 0b100 ] ),
+    ( '!', [ 0b010, 0b010, 0b010, 0b000, 0b010 ] ),
+    ( '?', [ 0b110, 0b001, 0b010, 0b000, 0b010 ] ),
+    ( '-', [ 0b000, 0b000, 0b111, 0b000, 0b000 ] ),
+    ( '+', [ 0b000, 0b010, 0b111, 0b010, 0b000 ] ),
+    ( '/', [ 0b001, 0b001, 0b010, 0b100, 0b100 ] ),
+    ( '(', [ 0b001, 0b010, 0b010, 0b010, 0b001 ] ),
+    ( ')', [ 0b100, 0b010, 0b010, 0b010, 0b100 ] ),
+    ( '[', [ 0b110, 0b100, 0b100, 0b100, 0b110 ] ),
+    ( ']', [ 0b011, 0b001, 0b001, 0b001, 0b011 ] ),
+    ( '<', [ 0b001, 0b010, 0b100, 0b010, 0b001 ] ),
+    ( '>', [ 0b100, 0b010, 0b001, 0b010, 0b100 ] ),
+    ( '=', [ 0b000, 0b111, 0b000, 0b111, 0b000 ] ),
+    ( '_', [ 0b000, 0b000, 0b000, 0b000, 0b111 ] ),
+    ( '*', [ 0b101, 0b010, 0b101, 0b000, 0b000 ] ),
+    ( '#', [ 0b101, 0b111, 0b101, 0b111, 0b101 ] ),
+    ( '%', [ 0b101, 0b001, 0b010, 0b100, 0b101 ] ),
+    ( '\'', [ 0b010, 0b010, 0b000, 0b000, 0b000 ] ),
+    ( '"', [ 0b101, 0b101, 0b000, 0b000, 0b000 ] ),
+  ];
+
+  /// Rows of the built-in glyph for `ch`; unknown characters render as `?`.
+  #[ must_use ]
+  pub fn glyph( ch : char ) -> [ u8; 5 ]
+  {
+    let upper = ch.to_ascii_uppercase();
+    GLYPHS.iter()
+      .find( | ( c, _ ) | *c == upper )
+      .or_else( || GLYPHS.iter().find( | ( c, _ ) | *c == '?' ) )
+      .map_or( [ 0; 5 ], | ( _, rows ) | *rows )
+  }
+
+  /// Lays out `text` with the built-in font as unit squares in font units,
+  /// Y-up, with the top-left corner of the first glyph at the origin.
+  #[ must_use ]
+  pub fn text_squares( text : &str ) -> Vec< [ f32; 2 ] >
+  {
+    let mut squares = Vec::new();
+    for ( i, ch ) in text.chars().enumerate()
+    {
+      let rows = glyph( ch );
+      for ( row, bits ) in rows.iter().enumerate()
+      {
+        for column in 0..3
+        {
+          if bits & ( 0b100 >> column ) != 0
+          {
+            // Bottom-left corner of the unit square.
+            squares.push( [ i as f32 * GLYPH_ADVANCE + column as f32, -( row as f32 ) - 1.0 ] );
+          }
+        }
+      }
+    }
+    squares
+  }
+}
+
+mod_interface::mod_interface!
+{
+  own use Mat3;
+  own use IDENTITY;
+  own use mat_mul;
+  own use mat_apply;
+  own use mat_apply_vector;
+  own use mat_invert;
+  own use mat_scale;
+  own use Canvas;
+  own use Mask;
+  own use premultiply;
+  own use unpremultiply;
+  own use to_u8;
+  own use blend;
+  own use RasterImage;
+  own use gradient_color;
+  own use Subpath;
+  own use PathBuilder;
+  own use StrokeStyle;
+  own use stroke_polygons;
+  own use circle;
+  own use signed_area;
+  own use rasterize;
+  own use fill_polygons;
+  own use gaussian_blur;
+  own use drop_shadow;
+  own use color_matrix;
+  own use apply_opacity;
+  own use GLYPH_ADVANCE;
+  own use GLYPH_HEIGHT;
+  own use GLYPH_LINE;
+  own use glyph;
+  own use text_squares;
+}
@@ -2,6 +2,8 @@
 precision highp float;
 
 in vec2 v_uv;
+in float v_alpha;
+in vec4 v_shift;
 
 uniform sampler2D u_texture;
 uniform vec4 u_tint; // multiply with texture color
@@ -11,5 +13,8 @@ out vec4 frag_color;
 void main()
 {
   vec4 tex = texture( u_texture, v_uv );
-  frag_color = tex * u_tint;
+  vec4 color = tex * u_tint;
+  color.rgb = mix( color.rgb, v_shift.rgb, v_shift.a );
+  color.a *= v_alpha;
+  frag_color = color;
 }
@@ -9,7 +9,22 @@ uniform vec2 u_tex_size;     // sheet dimensions in pixels
 uniform float u_depth;       // Transform::depth; range [-u_max_depth, u_max_depth]
 uniform float u_max_depth;   // RenderConfig::max_depth; defines the usable depth range
 
+// SpriteEffects — neutral values (zero amplitude, min = max = 1) disable a slot.
+uniform float u_time;        // SetTime clock in seconds
+uniform vec4 u_displace;     // axis.xy, amplitude (px), frequency (Hz)
+uniform float u_displace_phase;
+uniform vec4 u_pulse;        // min, max, frequency, phase
+uniform vec4 u_shift;        // target.rgb, amplitude
+uniform vec2 u_shift_wave;   // frequency, phase
+
 out vec2 v_uv;
+out float v_alpha;
+out vec4 v_shift;            // target.rgb, blend amount
+
+float wave( float frequency, float phase )
+{
+  return sin( 6.28318530718 * frequency * ( u_time + phase ) );
+}
 
 void main()
 {
@@ -28,8 +43,14 @@ void main()
     1.0 - ( u_region.y + ( 1.0 - quad.y ) * u_region.w ) / u_tex_size.y
   );
 
+  // Oscillators shared by every vertex of the quad; the fragment stage only
+  // applies them. Displacement scales with quad.y so the bottom edge stays put.
+  v_alpha = mix( u_pulse.x, u_pulse.y, 0.5 + 0.5 * wave( u_pulse.z, u_pulse.w ) );
+  v_shift = vec4( u_shift.rgb, u_shift.a * ( 0.5 + 0.5 * wave( u_shift_wave.x, u_shift_wave.y ) ) );
+  vec2 displace = u_displace.xy * u_displace.z * wave( u_displace.w, u_displace_phase ) * quad.y;
+
   // Scale unit quad to sprite's pixel size (region.zw), then apply transform.
-  vec3 world = u_transform * vec3( quad * u_region.zw, 1.0 );
+  vec3 world = u_transform * vec3( quad * u_region.zw + displace, 1.0 );
 
   // Convert to clip space: pixel coords → -1..1 (Y-up)
   vec2 ndc = ( world.xy / u_viewport ) * 2.0 - 1.0;
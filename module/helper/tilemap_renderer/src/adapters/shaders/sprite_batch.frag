@@ -3,6 +3,8 @@ precision highp float;
 
 in vec2 v_uv;
 in vec4 v_tint;
+in float v_alpha;
+in vec4 v_shift;
 
 uniform sampler2D u_texture;
 
@@ -10,5 +12,8 @@ out vec4 frag_color;
 
 void main()
 {
-  frag_color = texture( u_texture, v_uv ) * v_tint;
+  vec4 color = texture( u_texture, v_uv ) * v_tint;
+  color.rgb = mix( color.rgb, v_shift.rgb, v_shift.a );
+  color.a *= v_alpha;
+  frag_color = color;
 }
@@ -8,15 +8,28 @@ layout( location = 2 ) in vec3 i_transform_2;
 layout( location = 3 ) in vec4 i_region; // x, y, w, h in pixels
 layout( location = 4 ) in vec4 i_tint;
 layout( location = 5 ) in float i_depth;
+// SpriteEffects — neutral values (zero amplitude, min = max = 1) disable a slot.
+layout( location = 6 ) in vec4 i_displace;   // axis.xy, amplitude (px), frequency (Hz)
+layout( location = 7 ) in vec4 i_pulse;      // min, max, frequency, phase
+layout( location = 8 ) in vec4 i_shift;      // target.rgb, amplitude
+layout( location = 9 ) in vec3 i_fx_phase;   // displace phase, shift frequency, shift phase
 
 uniform vec2 u_viewport;
 uniform vec2 u_tex_size; // sheet dimensions in pixels
 uniform mat3 u_parent;   // batch parent transform
 uniform float u_parent_depth;
 uniform float u_max_depth; // RenderConfig::max_depth; defines the usable depth range
+uniform float u_time;      // SetTime clock in seconds
 
 out vec2 v_uv;
 out vec4 v_tint;
+out float v_alpha;
+out vec4 v_shift;          // target.rgb, blend amount
+
+float wave( float frequency, float phase )
+{
+  return sin( 6.28318530718 * frequency * ( u_time + phase ) );
+}
 
 void main()
 {
@@ -37,11 +50,16 @@ void main()
   );
   v_tint = i_tint;
 
+  // Same oscillators as `sprite.vert`, fed per instance.
+  v_alpha = mix( i_pulse.x, i_pulse.y, 0.5 + 0.5 * wave( i_pulse.z, i_pulse.w ) );
+  v_shift = vec4( i_shift.rgb, i_shift.a * ( 0.5 + 0.5 * wave( i_fx_phase.y, i_fx_phase.z ) ) );
+  vec2 displace = i_displace.xy * i_displace.z * wave( i_displace.w, i_fx_phase.x ) * quad.y;
+
   // Instance transform: each i_transform_N is a column of the column-major matrix from Transform::to_mat3().
   mat3 inst = mat3( i_transform_0, i_transform_1, i_transform_2 );
 
   // Scale quad to sprite pixel size, apply instance then parent transform
-  vec3 world = u_parent * inst * vec3( quad * i_region.zw, 1.0 );
+  vec3 world = u_parent * inst * vec3( quad * i_region.zw + displace, 1.0 );
 
   vec2 ndc = ( world.xy / u_viewport ) * 2.0 - 1.0;
 
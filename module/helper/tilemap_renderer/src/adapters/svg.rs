@@ -38,6 +38,8 @@ mod private
     batches : IntMap< ResourceId< Batch >, SvgBatch >,
     /// Map of generated mesh definitions ( packed `geom_id` + topology ) -> `symbol_id`
     mesh_defs : IntMap< u64, String >,
+    /// Sprite region heights in pixels, the pivot span of a vertex displacement.
+    sprite_heights : IntMap< ResourceId< asset::Sprite >, f32 >,
   }
 
   impl SvgResources
@@ -50,6 +52,7 @@ mod private
         geometries : IntMap::default(),
         batches : IntMap::default(),
         mesh_defs : IntMap::default(),
+        sprite_heights : IntMap::default(),
       }
     }
 
@@ -163,6 +166,8 @@ mod private
     viewport_offset : [ f32; 2 ],
     /// Scale applied to all visual elements in the SVG.
     viewport_scale : f32,
+    /// Effect clock from the last `SetTime`; the document opens at this time.
+    time : f32,
   }
 
   impl SvgBackend
@@ -185,6 +190,7 @@ mod private
         recording_batch : None,
         viewport_offset : [ 0.0, 0.0 ],
         viewport_scale : 1.0,
+        time : 0.0,
       }
     }
 
@@ -965,6 +971,7 @@ mod private
             sheet.width, sheet.height
           );
           self.content.push_asset_def( &img_def );
+          self.resources.sprite_heights.insert( sprite.id, sprite.region[ 3 ] );
         }
       }
     }
@@ -1080,6 +1087,130 @@ mod private
       Some( def_id )
     }
 
+    /// `dur` / `begin` / `repeatCount` of one SMIL cycle at `frequency`,
+    /// started `time + phase` seconds in the past so the document opens at
+    /// the backend clock. `None` for a non-positive frequency (no motion).
+    fn smil_timing( frequency : f32, phase : f32, time : f32 ) -> Option< String >
+    {
+      if frequency <= 0.0 || !frequency.is_finite() { return None; }
+      let period = 1.0 / frequency;
+      let elapsed = ( time + phase ).rem_euclid( period );
+      Some( format!( " dur=\"{period}s\" begin=\"-{elapsed}s\" repeatCount=\"indefinite\"" ) )
+    }
+
+    /// `values` list sampling `f( wave )` over one period of
+    /// `wave = sin( 2π u )`; linear interpolation between samples.
+    fn smil_values( f : impl Fn( f32 ) -> String ) -> String
+    {
+      const SAMPLES : u32 = 16;
+      ( 0..=SAMPLES )
+        .map( | i | f( ( core::f32::consts::TAU * i as f32 / SAMPLES as f32 ).sin() ) )
+        .collect::< Vec< _ > >()
+        .join( ";" )
+    }
+
+    /// SMIL equivalent of [`SpriteEffects`] for one sprite `<use>`.
+    ///
+    /// Returns the animation children of the `<use>` (displacement and
+    /// alpha pulse) and, for a colour shift, the `filter` attribute of a
+    /// `<g>` wrapping it — the shift runs after the tint filter, matching
+    /// the GPU order. The displacement shears / stretches around the
+    /// sprite's local origin, i.e. its anchored bottom edge.
+    fn sprite_effects_split
+    (
+      effects : &SpriteEffects,
+      height : f32,
+      time : f32,
+      content : &mut SvgContentManager,
+      counter : &mut u32,
+    ) -> Result< ( String, String ), RenderError >
+    {
+      let mut anims = String::new();
+
+      if let Some( d ) = effects.displace
+        && height > 0.0
+        && let Some( timing ) = Self::smil_timing( d.frequency, d.phase, time )
+      {
+        let k = d.amplitude / height;
+        if d.axis[ 1 ] != 0.0
+        {
+          let values = Self::smil_values( | w | format!( "1,{}", 1.0 + d.axis[ 1 ] * k * w ) );
+          let _ = write!( anims, "<animateTransform attributeName=\"transform\" type=\"scale\" additive=\"sum\" values=\"{values}\"{timing}/>" );
+        }
+        if d.axis[ 0 ] != 0.0
+        {
+          // Listed after the scale, so it applies first — to the unscaled `y`.
+          let values = Self::smil_values( | w | ( d.axis[ 0 ] * k * w ).atan().to_degrees().to_string() );
+          let _ = write!( anims, "<animateTransform attributeName=\"transform\" type=\"skewX\" additive=\"sum\" values=\"{values}\"{timing}/>" );
+        }
+      }
+
+      if let Some( a ) = effects.alpha_pulse
+      {
+        let alpha = | w : f32 | a.min + ( a.max - a.min ) * ( 0.5 + 0.5 * w );
+        match Self::smil_timing( a.frequency, a.phase, time )
+        {
+          Some( timing ) =>
+          {
+            let values = Self::smil_values( | w | alpha( w ).to_string() );
+            let _ = write!( anims, "<animate attributeName=\"opacity\" values=\"{values}\"{timing}/>" );
+          },
+          None => { let _ = write!( anims, "<set attributeName=\"opacity\" to=\"{}\"/>", alpha( 0.0 ) ); },
+        }
+      }
+
+      let mut shift = String::new();
+      if let Some( c ) = effects.color_shift
+      {
+        let [ tr, tg, tb ] = c.target;
+        let matrix = | w : f32 |
+        {
+          let k = c.amplitude * ( 0.5 + 0.5 * w );
+          let keep = 1.0 - k;
+          format!
+          (
+            "{keep} 0 0 0 {} 0 {keep} 0 0 {} 0 0 {keep} 0 {} 0 0 0 1 0",
+            k * tr, k * tg, k * tb
+          )
+        };
+        let id = Self::bump_filter_counter( counter )?;
+        let animate = match Self::smil_timing( c.frequency, c.phase, time )
+        {
+          Some( timing ) => format!( "<animate attributeName=\"values\" values=\"{}\"{timing}/>", Self::smil_values( matrix ) ),
+          None => String::new(),
+        };
+        content.push_frame_def( &format!
+        (
+          "<filter id=\"shift_{id}\" color-interpolation-filters=\"sRGB\"><feColorMatrix type=\"matrix\" values=\"{}\">{animate}</feColorMatrix></filter>",
+          matrix( 0.0 )
+        ));
+        shift = format!( " filter=\"url(#shift_{id})\"" );
+      }
+
+      Ok( ( anims, shift ) )
+    }
+
+    /// Sprite `<use>` with its effect animations. A colour shift moves the
+    /// blend onto the wrapping `<g>`, which is what composites with the
+    /// backdrop.
+    fn sprite_use( sprite : ResourceId< asset::Sprite >, attrs : &str, blend : &str, anims : &str, shift : &str ) -> String
+    {
+      let body = if anims.is_empty() { "/>".to_string() } else { format!( ">{anims}</use>" ) };
+      if shift.is_empty()
+      {
+        format!( "<use href=\"#sprite_{}\"{attrs}{blend}{body}", sprite.inner() )
+      }
+      else
+      {
+        format!( "<g{shift}{blend}><use href=\"#sprite_{}\"{attrs}{body}</g>", sprite.inner() )
+      }
+    }
+
+    fn cmd_set_time( &mut self, t : &SetTime )
+    {
+      self.time = t.seconds;
+    }
+
     fn cmd_clear( &mut self, c : &Clear )
     {
       let color = Self::color_to_svg( &c.color );
@@ -1181,7 +1312,9 @@ mod private
       let clip = Self::clip_attr( s.clip.as_ref() );
       let blend = Self::blend_to_svg( &s.blend );
       let tint = self.tint_filter_attr( &s.tint )?;
-      let sprite = format!( "<use href=\"#sprite_{}\"{}{}{}{}/>", s.sprite.inner(), transform, clip, tint, blend );
+      let height = self.resources.sprite_heights.get( &s.sprite ).copied().unwrap_or( 0.0 );
+      let ( anims, shift ) = Self::sprite_effects_split( &s.effects, height, self.time, &mut self.content, &mut self.filter_counter )?;
+      let sprite = Self::sprite_use( s.sprite, &format!( "{transform}{clip}{tint}" ), blend, &anims, &shift );
       self.content.push_body( &sprite );
       Ok( () )
     }
@@ -1225,7 +1358,7 @@ mod private
         && let Some( SvgBatch::Sprite { instances, .. } ) = self.resources.batches.get_mut( &batch_id )
           && ( si.index as usize ) < instances.len()
           {
-            instances[ si.index as usize ] = AddSpriteInstance { transform : si.transform, sprite : si.sprite, tint : si.tint };
+            instances[ si.index as usize ] = AddSpriteInstance { transform : si.transform, sprite : si.sprite, tint : si.tint, effects : si.effects };
           }
     }
 
@@ -1299,6 +1432,7 @@ mod private
       let resources = &self.resources;
       let content = &mut self.content;
       let filter_counter = &mut self.filter_counter;
+      let time = self.time;
 
       match resources.batch( db.batch )
       {
@@ -1313,11 +1447,9 @@ mod private
           {
             let inst_transform = Self::transform_to_svg_local( &inst.transform );
             let tint = Self::tint_filter_attr_split( &inst.tint, content, filter_counter )?;
-            let sprite = format!
-            (
-              "<use href=\"#sprite_{}\"{}{}{}/>",
-              inst.sprite.inner(), inst_transform, tint, blend
-            );
+            let height = resources.sprite_heights.get( &inst.sprite ).copied().unwrap_or( 0.0 );
+            let ( anims, shift ) = Self::sprite_effects_split( &inst.effects, height, time, content, filter_counter )?;
+            let sprite = Self::sprite_use( inst.sprite, &format!( "{inst_transform}{tint}" ), blend, &anims, &shift );
             content.push_body( &sprite );
           }
           content.push_body( "</g>" );
@@ -1471,6 +1603,7 @@ mod private
         match cmd
         {
           RenderCommand::Clear( c ) => self.cmd_clear( c ),
+          RenderCommand::SetTime( t ) => self.cmd_set_time( t ),
           RenderCommand::BeginPath( bp ) => self.cmd_begin_path( bp ),
           RenderCommand::MoveTo( m ) => self.cmd_move_to( m ),
           RenderCommand::LineTo( l ) => self.cmd_line_to( l ),
@@ -1538,6 +1671,7 @@ mod private
         patterns : true,
         clip_masks : true,
         effects : true,
+        sprite_effects : true,
         blend_modes : true,
         text_on_path : true,
         max_texture_size : 0,
@@ -2271,6 +2405,7 @@ mod private
           tint : [ 1.0, 1.0, 1.0, 1.0 ],
           blend : BlendMode::Normal,
           clip : None,
+          effects : SpriteEffects::default(),
         }),
       ]).unwrap();
 
@@ -2313,6 +2448,7 @@ mod private
           tint : [ 1.0, 1.0, 1.0, 1.0 ],
           blend : BlendMode::Normal,
           clip : None,
+          effects : SpriteEffects::default(),
         }),
       ]).unwrap();
 
@@ -2355,6 +2491,7 @@ mod private
           tint : [ 1.0, 0.0, 0.0, 1.0 ],
           blend : BlendMode::Normal,
           clip : None,
+          effects : SpriteEffects::default(),
         }),
       ]).unwrap();
 
@@ -2395,6 +2532,7 @@ mod private
         tint : [ 1.0, 0.0, 0.0, 1.0 ],
         blend : BlendMode::Normal,
         clip : None,
+        effects : SpriteEffects::default(),
       };
       svg.submit( &[ RenderCommand::Sprite( s ), RenderCommand::Sprite( Sprite { tint : [ 0.0, 1.0, 0.0, 1.0 ], ..s } ) ]).unwrap();
 
@@ -2403,6 +2541,120 @@ mod private
       assert!( b.contains( "url(#tint_1)" ), "body: {b}" );
     }
 
+    // -- sprite effects --
+
+    fn one_sprite_svg() -> SvgBackend
+    {
+      let mut svg = svg800x600();
+      let assets = Assets
+      {
+        images : vec![ ImageAsset
+        {
+          id : ResourceId::new( 0 ),
+          source : ImageSource::Bitmap { bytes : vec![ 0u8; 16 * 16 * 4 ], width : 16, height : 16, format : PixelFormat::Rgba8 },
+          filter : SamplerFilter::Linear,
+          mipmap : MipmapMode::Off,
+          wrap : WrapMode::Clamp,
+        }],
+        sprites : vec![ SpriteAsset
+        {
+          id : ResourceId::new( 0 ),
+          sheet : ResourceId::new( 0 ),
+          region : [ 0.0, 0.0, 16.0, 16.0 ],
+        }],
+        ..empty_assets()
+      };
+      svg.load_assets( &assets ).unwrap();
+      svg
+    }
+
+    fn plain_sprite( effects : SpriteEffects ) -> Sprite
+    {
+      Sprite
+      {
+        transform : Transform::default(),
+        sprite : ResourceId::new( 0 ),
+        tint : [ 1.0; 4 ],
+        blend : BlendMode::Normal,
+        clip : None,
+        effects,
+      }
+    }
+
+    #[ test ]
+    fn displace_and_alpha_pulse_animate_the_use()
+    {
+      let mut svg = one_sprite_svg();
+      let effects = SpriteEffects
+      {
+        displace : Some( DisplaceEffect { axis : [ 1.0, 0.0 ], amplitude : 4.0, frequency : 0.5, phase : 0.25 } ),
+        alpha_pulse : Some( AlphaPulseEffect { min : 0.5, max : 1.0, frequency : 2.0, phase : 0.0 } ),
+        color_shift : None,
+      };
+      svg.submit( &[ RenderCommand::SetTime( SetTime { seconds : 1.0 } ), RenderCommand::Sprite( plain_sprite( effects ) ) ] ).unwrap();
+
+      let b = body( &svg );
+      assert!( b.contains( "<use href=\"#sprite_0\"" ) && b.contains( "</use>" ), "effects nest inside the <use>; body: {b}" );
+      assert!( b.contains( "type=\"skewX\" additive=\"sum\"" ), "X displacement shears from the base; body: {b}" );
+      // Period 2 s, clock + phase = 1.25 s into the cycle.
+      assert!( b.contains( "dur=\"2s\" begin=\"-1.25s\"" ), "body: {b}" );
+      assert!( b.contains( "attributeName=\"opacity\" values=\"0.75;" ), "pulse starts mid-way between min and max; body: {b}" );
+      // Period 0.5 s; 1 s is a whole number of cycles.
+      assert!( b.contains( "dur=\"0.5s\" begin=\"-0s\"" ), "body: {b}" );
+      assert!( !b.contains( "shift_" ), "no colour shift requested; body: {b}" );
+    }
+
+    #[ test ]
+    fn color_shift_wraps_the_use_in_a_filtered_group()
+    {
+      let mut svg = one_sprite_svg();
+      let effects = SpriteEffects
+      {
+        color_shift : Some( ColorShiftEffect { target : [ 1.0, 0.5, 0.0 ], amplitude : 0.4, frequency : 1.0, phase : 0.0 } ),
+        ..SpriteEffects::default()
+      };
+      svg.submit( &[ RenderCommand::Sprite( Sprite { blend : BlendMode::Multiply, ..plain_sprite( effects ) } ) ] ).unwrap();
+
+      let b = body( &svg );
+      let d = defs( &svg );
+      assert!
+      (
+        b.contains( "<g filter=\"url(#shift_0)\" style=\"mix-blend-mode:multiply\"><use href=\"#sprite_0\"" ),
+        "the shift group carries the blend; body: {b}",
+      );
+      assert!( d.contains( "<filter id=\"shift_0\"" ), "defs: {d}" );
+      assert!( d.contains( "<animate attributeName=\"values\"" ), "the matrix animates; defs: {d}" );
+    }
+
+    #[ test ]
+    fn batch_instances_carry_their_effects()
+    {
+      let mut svg = one_sprite_svg();
+      let batch = ResourceId::new( 0 );
+      let pulse = SpriteEffects
+      {
+        alpha_pulse : Some( AlphaPulseEffect { min : 0.2, max : 0.2, frequency : 0.0, phase : 0.0 } ),
+        ..SpriteEffects::default()
+      };
+      svg.submit( &[
+        RenderCommand::CreateSpriteBatch( CreateSpriteBatch
+        {
+          batch,
+          params : SpriteBatchParams { transform : Transform::default(), sheet : ResourceId::new( 0 ), blend : BlendMode::Normal, clip : None },
+        }),
+        RenderCommand::BindBatch( BindBatch { batch } ),
+        RenderCommand::AddSpriteInstance( AddSpriteInstance { transform : Transform::default(), sprite : ResourceId::new( 0 ), tint : [ 1.0; 4 ], effects : pulse } ),
+        RenderCommand::AddSpriteInstance( AddSpriteInstance { transform : Transform::default(), sprite : ResourceId::new( 0 ), tint : [ 1.0; 4 ], effects : SpriteEffects::default() } ),
+        RenderCommand::UnbindBatch( UnbindBatch ),
+        RenderCommand::DrawBatch( DrawBatch { batch } ),
+      ]).unwrap();
+
+      let b = body( &svg );
+      // A zero frequency holds the wave at 0 — a static opacity.
+      assert_eq!( b.matches( "<set attributeName=\"opacity\" to=\"0.2\"/>" ).count(), 1, "body: {b}" );
+      assert_eq!( b.matches( "<use href=\"#sprite_0\"/>" ).count(), 1, "the plain instance stays self-closing; body: {b}" );
+    }
+
     // -- batch lifecycle --
 
     #[ test ]
@@ -2448,12 +2700,14 @@ mod private
           transform : Transform { position : [ 10.0, 20.0 ], ..Default::default() },
           sprite : ResourceId::new( 0 ),
           tint : [ 1.0, 1.0, 1.0, 1.0 ],
+          effects : SpriteEffects::default(),
         }),
         RenderCommand::AddSpriteInstance( AddSpriteInstance
         {
           transform : Transform { position : [ 50.0, 60.0 ], ..Default::default() },
           sprite : ResourceId::new( 0 ),
           tint : [ 1.0, 1.0, 1.0, 1.0 ],
+          effects : SpriteEffects::default(),
         }),
         RenderCommand::UnbindBatch( UnbindBatch ),
         RenderCommand::DrawBatch( DrawBatch { batch : batch_id } ),
@@ -2551,12 +2805,14 @@ mod private
           transform : Transform { position : [ 1.0, 2.0 ], ..Default::default() },
           sprite : ResourceId::new( 0 ),
           tint : [ 1.0, 1.0, 1.0, 1.0 ],
+          effects : SpriteEffects::default(),
         }),
         RenderCommand::AddSpriteInstance( AddSpriteInstance
         {
           transform : Transform { position : [ 3.0, 4.0 ], ..Default::default() },
           sprite : ResourceId::new( 0 ),
           tint : [ 1.0, 1.0, 1.0, 1.0 ],
+          effects : SpriteEffects::default(),
         }),
         RenderCommand::UnbindBatch( UnbindBatch ),
       ]).unwrap();
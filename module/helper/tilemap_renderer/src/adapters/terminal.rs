@@ -1,7 +1,1757 @@
 //! Terminal backend adapter.
 //!
-//! **Status: stub only** — implementation deferred to a follow-up PR.
+//! Rasterises the command stream into a grid of character cells and emits
+//! it as a string — either with ANSI 24-bit ("truecolor") escape sequences
+//! for live preview over SSH, or as plain text for snapshot tests.
+//!
+//! Every cell carries three layers, composed at output time:
+//!
+//! - **Colour layer** — two vertically stacked "pixels" per cell, drawn
+//!   with the upper half block `▀` (foreground = top, background = bottom).
+//!   `Clear`, path fills, meshes and sprites land here.
+//! - **Dot layer** — a 2×4 braille sub-grid per cell (`U+2800`..`U+28FF`).
+//!   Path strokes and line meshes are drawn here with Bresenham lines.
+//! - **Glyph layer** — text characters, one per cell.
+//!
+//! Command coordinates use the usual Y-up viewport of
+//! `RenderConfig::width × RenderConfig::height` pixels, scaled to fit the
+//! character grid, so the same command stream renders on every backend.
+
+mod private
+{
+  use crate::assets::*;
+  use crate::backend::*;
+  use crate::commands::*;
+  use crate::types::*;
+  use core::fmt::Write as _;
+  use nohash_hasher::IntMap;
+
+  /// Braille dot bits indexed by `[row][column]` within a 2×4 cell.
+  const BRAILLE_BITS : [ [ u8; 2 ]; 4 ] = [ [ 0x01, 0x08 ], [ 0x02, 0x10 ], [ 0x04, 0x20 ], [ 0x40, 0x80 ] ];
+
+  /// Luminance ramp used for cells without glyphs or dots in plain-text mode.
+  const LUMA_RAMP : &[ u8 ] = b" .:-=+*#%@";
+
+  /// Segments used to flatten one quadratic / cubic bezier.
+  const CURVE_SEGMENTS : u32 = 12;
+
+  /// Sub-samples per axis when downsampling a sprite into one colour pixel.
+  const SPRITE_SUBSAMPLES : u32 = 3;
+
+  type Mat3 = [ f32; 9 ];
+
+  const IDENTITY : Mat3 = [ 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0 ];
+
+  // ============================================================================
+  // Terminal resource handles
+  // ============================================================================
+
+  /// Internal storage for loaded terminal resources.
+  struct TerminalResources
+  {
+    /// Decoded RGBA8 images.
+    images : IntMap< ResourceId< asset::Image >, TerminalImage >,
+    /// Sprite regions `( sheet, [x, y, w, h] )`.
+    sprites : IntMap< ResourceId< asset::Sprite >, ( ResourceId< asset::Image >, [ f32; 4 ] ) >,
+    /// Decoded geometries.
+    geometries : IntMap< ResourceId< asset::Geometry >, TerminalGeometry >,
+    /// Created batches.
+    batches : IntMap< ResourceId< Batch >, TerminalBatch >,
+  }
+
+  impl TerminalResources
+  {
+    fn new() -> Self
+    {
+      Self
+      {
+        images : IntMap::default(),
+        sprites : IntMap::default(),
+        geometries : IntMap::default(),
+        batches : IntMap::default(),
+      }
+    }
+  }
+
+  /// Decoded image, always stored as RGBA8.
+  struct TerminalImage
+  {
+    width : u32,
+    height : u32,
+    pixels : Vec< [ u8; 4 ] >,
+  }
+
+  impl TerminalImage
+  {
+    /// Decodes raw bitmap bytes. Returns `None` when the byte count doesn't
+    /// match `width × height × channels`.
+    fn from_bitmap( bytes : &[ u8 ], width : u32, height : u32, format : PixelFormat ) -> Option< Self >
+    {
+      let channels = match format
+      {
+        PixelFormat::Rgba8 => 4,
+        PixelFormat::Rgb8 => 3,
+        PixelFormat::Gray8 => 1,
+        PixelFormat::GrayAlpha8 => 2,
+      };
+      if bytes.len() != width as usize * height as usize * channels
+      {
+        return None;
+      }
+      let pixels = bytes.chunks_exact( channels ).map( | px | match format
+      {
+        PixelFormat::Rgba8 => [ px[ 0 ], px[ 1 ], px[ 2 ], px[ 3 ] ],
+        PixelFormat::Rgb8 => [ px[ 0 ], px[ 1 ], px[ 2 ], 255 ],
+        PixelFormat::Gray8 => [ px[ 0 ], px[ 0 ], px[ 0 ], 255 ],
+        PixelFormat::GrayAlpha8 => [ px[ 0 ], px[ 0 ], px[ 0 ], px[ 1 ] ],
+      }).collect();
+      Some( Self { width, height, pixels } )
+    }
+
+    /// Nearest texel at image-space `( x, y )` (Y-down), clamped to the edges.
+    #[ allow( clippy::cast_sign_loss ) ]
+    fn texel( &self, x : f32, y : f32 ) -> [ f32; 4 ]
+    {
+      let column = ( x.max( 0.0 ) as u32 ).min( self.width - 1 );
+      let row = ( y.max( 0.0 ) as u32 ).min( self.height - 1 );
+      let [ r, g, b, a ] = self.pixels[ ( row * self.width + column ) as usize ];
+      [ f32::from( r ) / 255.0, f32::from( g ) / 255.0, f32::from( b ) / 255.0, f32::from( a ) / 255.0 ]
+    }
+  }
+
+  /// Decoded geometry.
+  struct TerminalGeometry
+  {
+    /// Flattened vertex positions [x0, y0, x1, y1, ...].
+    positions : Vec< f32 >,
+    /// Optional vertex indices.
+    indices : Option< Vec< u32 > >,
+  }
+
+  impl TerminalGeometry
+  {
+    /// Vertex positions in draw order (indices resolved).
+    fn vertices( &self ) -> Vec< [ f32; 2 ] >
+    {
+      let vertex = | i : usize | Some( [ *self.positions.get( i * 2 )?, *self.positions.get( i * 2 + 1 )? ] );
+      match &self.indices
+      {
+        Some( indices ) => indices.iter().filter_map( | &i | vertex( i as usize ) ).collect(),
+        None => ( 0..self.positions.len() / 2 ).filter_map( vertex ).collect(),
+      }
+    }
+  }
+
+  /// Internal representation of a batch.
+  enum TerminalBatch
+  {
+    /// A sprite batch.
+    Sprite
+    {
+      /// Instances currently in the batch.
+      instances : Vec< AddSpriteInstance >,
+      /// Parameters common to all instances.
+      params : SpriteBatchParams,
+    },
+    /// A mesh batch.
+    Mesh
+    {
+      /// Instances currently in the batch.
+      instances : Vec< AddMeshInstance >,
+      /// Parameters common to all instances.
+      params : MeshBatchParams,
+    },
+  }
+
+  /// One character cell of the output grid.
+  #[ derive( Clone, Copy ) ]
+  struct Cell
+  {
+    /// Colour of the upper half.
+    top : [ f32; 3 ],
+    /// Colour of the lower half.
+    bottom : [ f32; 3 ],
+    /// Braille dot mask.
+    dots : u8,
+    /// Colour of the braille dots.
+    dot_color : [ f32; 3 ],
+    /// Text glyph drawn over everything else.
+    glyph : Option< char >,
+    /// Colour of the text glyph.
+    glyph_color : [ f32; 3 ],
+  }
+
+  impl Cell
+  {
+    fn filled( color : [ f32; 3 ] ) -> Self
+    {
+      Self { top : color, bottom : color, dots : 0, dot_color : color, glyph : None, glyph_color : color }
+    }
+
+    /// Average of both halves, used behind glyphs and dots.
+    fn background( &self ) -> [ f32; 3 ]
+    {
+      [
+        ( self.top[ 0 ] + self.bottom[ 0 ] ) * 0.5,
+        ( self.top[ 1 ] + self.bottom[ 1 ] ) * 0.5,
+        ( self.top[ 2 ] + self.bottom[ 2 ] ) * 0.5,
+      ]
+    }
+  }
+
+  /// Group state pushed by `BeginGroup`.
+  #[ derive( Clone, Copy ) ]
+  struct GroupState
+  {
+    matrix : Mat3,
+    opacity : f32,
+  }
+
+  // ============================================================================
+  // Backend struct
+  // ============================================================================
+
+  /// Terminal renderer backend.
+  ///
+  /// ```ignore
+  /// let config = RenderConfig { width : 800, height : 600, ..Default::default() };
+  /// let mut term = TerminalBackend::new( config, 80, 30 );
+  /// term.load_assets( &assets )?;
+  /// term.submit( &commands )?;
+  /// let Output::String( frame ) = term.output()? else { unreachable!() };
+  /// print!( "{frame}" );
+  /// ```
+  ///
+  /// # Known limitations
+  ///
+  /// - `Encoded` and `Path` images are decoded only with the `image` feature;
+  ///   without it, drawing a sprite cut from them returns
+  ///   [`RenderError::MissingAsset`].
+  /// - Fills are solid only — gradient and pattern fills, clip masks, blur and
+  ///   drop-shadow effects are ignored. `Effect::Opacity` is honoured.
+  /// - Strokes are one braille dot wide regardless of `stroke_width`, and
+  ///   dash patterns, caps and joins are ignored.
+  /// - Text ignores the font and its size: one character per cell, anchored
+  ///   horizontally at `BeginText::position`. Text-on-path is drawn straight.
+  /// - Only `BlendMode::Normal` (alpha over) is implemented; other modes fall back to it.
+  pub struct TerminalBackend
+  {
+    config : RenderConfig,
+    columns : u32,
+    rows : u32,
+    cells : Vec< Cell >,
+    ansi : bool,
+    resources : TerminalResources,
+    // -- streaming state --
+    subpaths : Vec< Vec< [ f32; 2 ] > >,
+    path_style : Option< BeginPath >,
+    text_buf : String,
+    text_style : Option< BeginText >,
+    groups : Vec< GroupState >,
+    /// Currently bound batch for recording instances.
+    recording_batch : Option< ResourceId< Batch > >,
+  }
+
+  impl TerminalBackend
+  {
+    /// Creates a terminal backend that renders the `config.width × config.height`
+    /// viewport into a grid of `columns × rows` character cells.
+    #[ must_use ]
+    pub fn new( config : RenderConfig, columns : u32, rows : u32 ) -> Self
+    {
+      let background = rgb( &config.background );
+      Self
+      {
+        config,
+        columns,
+        rows,
+        cells : vec![ Cell::filled( background ); columns as usize * rows as usize ],
+        ansi : true,
+        resources : TerminalResources::new(),
+        subpaths : Vec::new(),
+        path_style : None,
+        text_buf : String::new(),
+        text_style : None,
+        groups : Vec::new(),
+        recording_batch : None,
+      }
+    }
+
+    /// Returns the character grid size as `( columns, rows )`.
+    #[ must_use ]
+    pub fn grid_size( &self ) -> ( u32, u32 ) { ( self.columns, self.rows ) }
+
+    /// Returns whether the output contains ANSI truecolor escape sequences.
+    #[ must_use ]
+    pub fn ansi( &self ) -> bool { self.ansi }
+
+    /// Enables or disables ANSI truecolor output. With colour disabled, cells
+    /// without text or strokes are rendered as a luminance ramp (` .:-=+*#%@`),
+    /// which keeps snapshot tests readable and escape-free.
+    pub fn set_ansi( &mut self, ansi : bool )
+    {
+      self.ansi = ansi;
+    }
+
+    // -- coordinate mapping --
+
+    /// Current group matrix.
+    fn group_matrix( &self ) -> Mat3
+    {
+      self.groups.last().map_or( IDENTITY, | g | g.matrix )
+    }
+
+    /// Current group opacity.
+    fn group_opacity( &self ) -> f32
+    {
+      self.groups.last().map_or( 1.0, | g | g.opacity )
+    }
+
+    /// Maps a Y-up viewport point to colour-layer pixel coordinates
+    /// (`columns × rows * 2`, Y-down).
+    fn viewport_to_color( &self, p : [ f32; 2 ] ) -> [ f32; 2 ]
+    {
+      let sx = self.columns as f32 / self.config.width.max( 1 ) as f32;
+      let sy = ( self.rows * 2 ) as f32 / self.config.height.max( 1 ) as f32;
+      [ p[ 0 ] * sx, ( self.config.height as f32 - p[ 1 ] ) * sy ]
+    }
+
+    /// Maps a colour-layer pixel coordinate back to the Y-up viewport.
+    fn color_to_viewport( &self, p : [ f32; 2 ] ) -> [ f32; 2 ]
+    {
+      let sx = self.columns as f32 / self.config.width.max( 1 ) as f32;
+      let sy = ( self.rows * 2 ) as f32 / self.config.height.max( 1 ) as f32;
+      [ p[ 0 ] / sx, self.config.height as f32 - p[ 1 ] / sy ]
+    }
+
+    /// Maps a Y-up viewport point to dot-layer coordinates (`columns * 2 × rows * 4`, Y-down).
+    fn viewport_to_dot( &self, p : [ f32; 2 ] ) -> [ f32; 2 ]
+    {
+      let [ x, y ] = self.viewport_to_color( p );
+      [ x * 2.0, y * 2.0 ]
+    }
+
+    // -- rasterisation primitives --
+
+    /// Alpha-blends `color` into the colour-layer pixel at `( x, y )`.
+    #[ allow( clippy::cast_sign_loss ) ]
+    fn blend_pixel( &mut self, x : i64, y : i64, color : [ f32; 4 ] )
+    {
+      if x < 0 || y < 0 || x >= i64::from( self.columns ) || y >= i64::from( self.rows * 2 )
+      {
+        return;
+      }
+      let cell = &mut self.cells[ ( y / 2 ) as usize * self.columns as usize + x as usize ];
+      let dst = if y % 2 == 0 { &mut cell.top } else { &mut cell.bottom };
+      let a = color[ 3 ].clamp( 0.0, 1.0 );
+      for ( d, s ) in dst.iter_mut().zip( color )
+      {
+        *d = s * a + *d * ( 1.0 - a );
+      }
+    }
+
+    /// Sets the braille dot at dot-layer coordinate `( x, y )`.
+    #[ allow( clippy::cast_sign_loss ) ]
+    fn set_dot( &mut self, x : i64, y : i64, color : [ f32; 3 ] )
+    {
+      if x < 0 || y < 0 || x >= i64::from( self.columns * 2 ) || y >= i64::from( self.rows * 4 )
+      {
+        return;
+      }
+      let cell = &mut self.cells[ ( y / 4 ) as usize * self.columns as usize + ( x / 2 ) as usize ];
+      cell.dots |= BRAILLE_BITS[ ( y % 4 ) as usize ][ ( x % 2 ) as usize ];
+      cell.dot_color = color;
+    }
+
+    /// Draws a viewport-space line into the dot layer (Bresenham), clipped
+    /// to the dot grid first so far off-screen endpoints cost nothing.
+    fn draw_line( &mut self, from : [ f32; 2 ], to : [ f32; 2 ], color : [ f32; 3 ] )
+    {
+      let bounds = [ ( self.columns * 2 ) as f32, ( self.rows * 4 ) as f32 ];
+      let Some( ( [ x0, y0 ], [ x1, y1 ] ) ) = clip_segment( self.viewport_to_dot( from ), self.viewport_to_dot( to ), bounds ) else
+      {
+        return;
+      };
+      let ( mut x, mut y ) = ( x0.floor() as i64, y0.floor() as i64 );
+      let ( x_end, y_end ) = ( x1.floor() as i64, y1.floor() as i64 );
+      let dx = ( x_end - x ).abs();
+      let dy = -( y_end - y ).abs();
+      let step_x = if x < x_end { 1 } else { -1 };
+      let step_y = if y < y_end { 1 } else { -1 };
+      let mut err = dx + dy;
+      loop
+      {
+        self.set_dot( x, y, color );
+        if x == x_end && y == y_end
+        {
+          break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy
+        {
+          err += dy;
+          x += step_x;
+        }
+        if e2 <= dx
+        {
+          err += dx;
+          y += step_y;
+        }
+      }
+    }
+
+    /// Fills the viewport-space polygons with the non-zero winding rule,
+    /// sampling each colour-layer pixel at its centre.
+    fn fill_polygons( &mut self, polygons : &[ Vec< [ f32; 2 ] > ], color : [ f32; 4 ] )
+    {
+      let polygons : Vec< Vec< [ f32; 2 ] > > = polygons.iter()
+        .map( | poly | poly.iter().map( | &p | self.viewport_to_color( p ) ).collect() )
+        .collect();
+      let mut crossings : Vec< ( f32, i32 ) > = Vec::new();
+      for row in 0..self.rows * 2
+      {
+        let sample_y = row as f32 + 0.5;
+        crossings.clear();
+        for poly in &polygons
+        {
+          for ( i, a ) in poly.iter().enumerate()
+          {
+            let b = poly[ ( i + 1 ) % poly.len() ];
+            if ( a[ 1 ] <= sample_y ) != ( b[ 1 ] <= sample_y )
+            {
+              let t = ( sample_y - a[ 1 ] ) / ( b[ 1 ] - a[ 1 ] );
+              crossings.push( ( a[ 0 ] + t * ( b[ 0 ] - a[ 0 ] ), if b[ 1 ] > a[ 1 ] { 1 } else { -1 } ) );
+            }
+          }
+        }
+        crossings.sort_by( | a, b | a.0.total_cmp( &b.0 ) );
+        let mut winding = 0;
+        for pair in crossings.windows( 2 )
+        {
+          winding += pair[ 0 ].1;
+          if winding == 0
+          {
+            continue;
+          }
+          // Pixels whose centre lies in [start, end).
+          let start = ( pair[ 0 ].0 - 0.5 ).ceil() as i64;
+          let end = ( pair[ 1 ].0 - 0.5 ).ceil() as i64;
+          for x in start..end
+          {
+            self.blend_pixel( x, i64::from( row ), color );
+          }
+        }
+      }
+    }
+
+    /// Draws a sprite region of `sheet` mapped through `matrix`, box-filtering
+    /// the texels that fall under each colour-layer pixel.
+    fn draw_sprite( &mut self, sprite : ResourceId< asset::Sprite >, matrix : &Mat3, tint : [ f32; 4 ] ) -> Result< (), RenderError >
+    {
+      let &( sheet, region ) = self.resources.sprites.get( &sprite ).ok_or( RenderError::MissingAsset( sprite.inner() ) )?;
+      if !self.resources.images.contains_key( &sheet )
+      {
+        return Err( RenderError::MissingAsset( sheet.inner() ) );
+      }
+      let Some( inverse ) = mat_invert( matrix ) else { return Ok( () ) };
+      let [ rx, ry, rw, rh ] = region;
+      let corners = [ [ 0.0, 0.0 ], [ rw, 0.0 ], [ 0.0, rh ], [ rw, rh ] ]
+        .map( | p | self.viewport_to_color( mat_apply( matrix, p ) ) );
+      let min_x = corners.iter().map( | p | p[ 0 ] ).fold( f32::INFINITY, f32::min ).floor().max( 0.0 ) as i64;
+      let max_x = corners.iter().map( | p | p[ 0 ] ).fold( f32::NEG_INFINITY, f32::max ).ceil().min( self.columns as f32 ) as i64;
+      let min_y = corners.iter().map( | p | p[ 1 ] ).fold( f32::INFINITY, f32::min ).floor().max( 0.0 ) as i64;
+      let max_y = corners.iter().map( | p | p[ 1 ] ).fold( f32::NEG_INFINITY, f32::max ).ceil().min( ( self.rows * 2 ) as f32 ) as i64;
+      let opacity = self.group_opacity();
+
+      for py in min_y..max_y
+      {
+        for px in min_x..max_x
+        {
+          let mut sum = [ 0.0_f32; 4 ];
+          let mut hits = 0_u32;
+          for sy in 0..SPRITE_SUBSAMPLES
+          {
+            for sx in 0..SPRITE_SUBSAMPLES
+            {
+              let sample = self.color_to_viewport(
+              [
+                px as f32 + ( sx as f32 + 0.5 ) / SPRITE_SUBSAMPLES as f32,
+                py as f32 + ( sy as f32 + 0.5 ) / SPRITE_SUBSAMPLES as f32,
+              ]);
+              let [ lx, ly ] = mat_apply( &inverse, sample );
+              if lx < 0.0 || ly < 0.0 || lx >= rw || ly >= rh
+              {
+                continue;
+              }
+              let image = &self.resources.images[ &sheet ];
+              // Local Y-up: the sprite's top edge (ly = rh) is the region's first row.
+              let texel = image.texel( rx + lx, ry + ( rh - ly ) );
+              // Premultiply so transparent texels don't bleed their colour.
+              for c in 0..3
+              {
+                sum[ c ] += texel[ c ] * texel[ 3 ];
+              }
+              sum[ 3 ] += texel[ 3 ];
+              hits += 1;
+            }
+          }
+          if hits == 0 || sum[ 3 ] <= 0.0
+          {
+            continue;
+          }
+          let coverage = ( SPRITE_SUBSAMPLES * SPRITE_SUBSAMPLES ) as f32;
+          let color =
+          [
+            sum[ 0 ] / sum[ 3 ] * tint[ 0 ],
+            sum[ 1 ] / sum[ 3 ] * tint[ 1 ],
+            sum[ 2 ] / sum[ 3 ] * tint[ 2 ],
+            sum[ 3 ] / coverage * tint[ 3 ] * opacity,
+          ];
+          self.blend_pixel( px, py, color );
+        }
+      }
+      Ok( () )
+    }
+
+    /// Draws geometry mapped through `matrix` with a solid colour.
+    fn draw_geometry( &mut self, geometry : ResourceId< asset::Geometry >, matrix : &Mat3, topology : Topology, color : [ f32; 4 ] ) -> Result< (), RenderError >
+    {
+      let vertices : Vec< [ f32; 2 ] > = self.resources.geometries.get( &geometry )
+        .ok_or( RenderError::MissingAsset( geometry.inner() ) )?
+        .vertices()
+        .into_iter()
+        .map( | v | mat_apply( matrix, v ) )
+        .collect();
+      let color = [ color[ 0 ], color[ 1 ], color[ 2 ], color[ 3 ] * self.group_opacity() ];
+
+      match topology
+      {
+        Topology::TriangleList =>
+        {
+          for tri in vertices.chunks_exact( 3 )
+          {
+            self.fill_polygons( &[ tri.to_vec() ], color );
+          }
+        }
+        Topology::TriangleStrip =>
+        {
+          for tri in vertices.windows( 3 )
+          {
+            self.fill_polygons( &[ tri.to_vec() ], color );
+          }
+        }
+        Topology::LineList =>
+        {
+          for line in vertices.chunks_exact( 2 )
+          {
+            self.draw_line( line[ 0 ], line[ 1 ], rgb( &color ) );
+          }
+        }
+        Topology::LineStrip =>
+        {
+          for line in vertices.windows( 2 )
+          {
+            self.draw_line( line[ 0 ], line[ 1 ], rgb( &color ) );
+          }
+        }
+      }
+      Ok( () )
+    }
+
+    // -- path streaming --
+
+    /// Last point of the current subpath, or the origin.
+    fn pen( &self ) -> [ f32; 2 ]
+    {
+      self.subpaths.last().and_then( | s | s.last() ).copied().unwrap_or( [ 0.0, 0.0 ] )
+    }
+
+    /// Appends a point to the current subpath, starting one at the pen if needed.
+    fn push_point( &mut self, p : [ f32; 2 ] )
+    {
+      if self.subpaths.is_empty()
+      {
+        self.subpaths.push( vec![ [ 0.0, 0.0 ] ] );
+      }
+      if let Some( subpath ) = self.subpaths.last_mut()
+      {
+        subpath.push( p );
+      }
+    }
+
+    fn cmd_move_to( &mut self, m : &MoveTo )
+    {
+      self.subpaths.push( vec![ [ m.0, m.1 ] ] );
+    }
+
+    fn cmd_quad_to( &mut self, q : &QuadTo )
+    {
+      let p0 = self.pen();
+      for i in 1..=CURVE_SEGMENTS
+      {
+        let t = i as f32 / CURVE_SEGMENTS as f32;
+        let u = 1.0 - t;
+        let point = | a : f32, b : f32, c : f32 | u * u * a + 2.0 * u * t * b + t * t * c;
+        self.push_point( [ point( p0[ 0 ], q.cx, q.x ), point( p0[ 1 ], q.cy, q.y ) ] );
+      }
+    }
+
+    fn cmd_cubic_to( &mut self, c : &CubicTo )
+    {
+      let p0 = self.pen();
+      for i in 1..=CURVE_SEGMENTS
+      {
+        let t = i as f32 / CURVE_SEGMENTS as f32;
+        let u = 1.0 - t;
+        let point = | a : f32, b : f32, c : f32, d : f32 | u * u * u * a + 3.0 * u * u * t * b + 3.0 * u * t * t * c + t * t * t * d;
+        self.push_point( [ point( p0[ 0 ], c.c1x, c.c2x, c.x ), point( p0[ 1 ], c.c1y, c.c2y, c.y ) ] );
+      }
+    }
+
+    /// Flattens an SVG-style endpoint arc (SVG 1.1 implementation notes, F.6.5).
+    #[ allow( clippy::cast_sign_loss ) ]
+    fn cmd_arc_to( &mut self, a : &ArcTo )
+    {
+      let [ x1, y1 ] = self.pen();
+      let ( x2, y2 ) = ( a.x, a.y );
+      let ( mut rx, mut ry ) = ( a.rx.abs(), a.ry.abs() );
+      if rx == 0.0 || ry == 0.0 || ( ( x1 - x2 ).abs() < f32::EPSILON && ( y1 - y2 ).abs() < f32::EPSILON )
+      {
+        self.push_point( [ x2, y2 ] );
+        return;
+      }
+      let ( sin_phi, cos_phi ) = a.rotation.sin_cos();
+      let dx = ( x1 - x2 ) * 0.5;
+      let dy = ( y1 - y2 ) * 0.5;
+      let x1p = cos_phi * dx + sin_phi * dy;
+      let y1p = -sin_phi * dx + cos_phi * dy;
+      let lambda = ( x1p * x1p ) / ( rx * rx ) + ( y1p * y1p ) / ( ry * ry );
+      if lambda > 1.0
+      {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+      }
+      let num = rx * rx * ry * ry - rx * rx * y1p * y1p - ry * ry * x1p * x1p;
+      let den = rx * rx * y1p * y1p + ry * ry * x1p * x1p;
+      let mut coef = ( num / den ).max( 0.0 ).sqrt();
+      if a.large_arc == a.sweep
+      {
+        coef = -coef;
+      }
+      let cxp = coef * rx * y1p / ry;
+      let cyp = -coef * ry * x1p / rx;
+      let cx = cos_phi * cxp - sin_phi * cyp + ( x1 + x2 ) * 0.5;
+      let cy = sin_phi * cxp + cos_phi * cyp + ( y1 + y2 ) * 0.5;
+
+      let angle = | ux : f32, uy : f32, vx : f32, vy : f32 | ( ux * vy - uy * vx ).atan2( ux * vx + uy * vy );
+      let theta1 = angle( 1.0, 0.0, ( x1p - cxp ) / rx, ( y1p - cyp ) / ry );
+      let mut delta = angle( ( x1p - cxp ) / rx, ( y1p - cyp ) / ry, ( -x1p - cxp ) / rx, ( -y1p - cyp ) / ry );
+      if !a.sweep && delta > 0.0
+      {
+        delta -= core::f32::consts::TAU;
+      }
+      if a.sweep && delta < 0.0
+      {
+        delta += core::f32::consts::TAU;
+      }
+
+      let segments = ( delta.abs() / core::f32::consts::FRAC_PI_8 ).ceil().max( 1.0 ) as u32;
+      for i in 1..=segments
+      {
+        let theta = theta1 + delta * i as f32 / segments as f32;
+        let ( sin_t, cos_t ) = theta.sin_cos();
+        self.push_point(
+        [
+          cos_phi * rx * cos_t - sin_phi * ry * sin_t + cx,
+          sin_phi * rx * cos_t + cos_phi * ry * sin_t + cy,
+        ]);
+      }
+    }
+
+    fn cmd_close_path( &mut self )
+    {
+      if let Some( first ) = self.subpaths.last().and_then( | s | s.first() ).copied()
+      {
+        self.push_point( first );
+        // Subsequent segments continue from the subpath start.
+        self.subpaths.push( vec![ first ] );
+      }
+    }
+
+    fn cmd_end_path( &mut self )
+    {
+      let Some( style ) = self.path_style.take() else
+      {
+        return;
+      };
+      let matrix = mat_mul( &self.group_matrix(), &style.transform.to_mat3() );
+      let subpaths : Vec< Vec< [ f32; 2 ] > > = core::mem::take( &mut self.subpaths )
+        .into_iter()
+        .filter( | s | s.len() > 1 )
+        .map( | s | s.into_iter().map( | p | mat_apply( &matrix, p ) ).collect() )
+        .collect();
+      let opacity = self.group_opacity();
+
+      if let FillRef::Solid( color ) = style.fill
+      {
+        self.fill_polygons( &subpaths, [ color[ 0 ], color[ 1 ], color[ 2 ], color[ 3 ] * opacity ] );
+      }
+      if style.stroke_width > 0.0 && style.stroke_color[ 3 ] * opacity > 0.0
+      {
+        let color = rgb( &style.stroke_color );
+        for subpath in &subpaths
+        {
+          for segment in subpath.windows( 2 )
+          {
+            self.draw_line( segment[ 0 ], segment[ 1 ], color );
+          }
+        }
+      }
+    }
+
+    // -- text streaming --
+
+    #[ allow( clippy::cast_sign_loss ) ]
+    fn cmd_end_text( &mut self )
+    {
+      let Some( style ) = self.text_style.take() else
+      {
+        return;
+      };
+      let text = core::mem::take( &mut self.text_buf );
+      let position = mat_apply( &self.group_matrix(), style.position );
+      let [ x, y ] = self.viewport_to_color( position );
+      let length = text.chars().count() as f32;
+      let start = match style.anchor
+      {
+        TextAnchor::TopLeft | TextAnchor::CenterLeft | TextAnchor::BottomLeft => x,
+        TextAnchor::TopCenter | TextAnchor::Center | TextAnchor::BottomCenter => x - length * 0.5,
+        TextAnchor::TopRight | TextAnchor::CenterRight | TextAnchor::BottomRight => x - length,
+      }.round() as i64;
+      let row = ( y / 2.0 ).floor() as i64;
+      if row < 0 || row >= i64::from( self.rows )
+      {
+        return;
+      }
+      let color = rgb( &style.color );
+      for ( i, ch ) in ( start.. ).zip( text.chars() )
+      {
+        if i < 0 || i >= i64::from( self.columns ) || ch.is_control()
+        {
+          continue;
+        }
+        let cell = &mut self.cells[ row as usize * self.columns as usize + i as usize ];
+        cell.glyph = Some( ch );
+        cell.glyph_color = color;
+      }
+    }
+
+    // -- single draws --
+
+    fn cmd_clear( &mut self, c : &Clear )
+    {
+      let background = self.cells.first().map_or( [ 0.0; 3 ], | cell | cell.top );
+      let a = c.color[ 3 ].clamp( 0.0, 1.0 );
+      let color = [ 0, 1, 2 ].map( | i | c.color[ i ] * a + background[ i ] * ( 1.0 - a ) );
+      self.cells.fill( Cell::filled( color ) );
+    }
+
+    fn cmd_mesh( &mut self, m : &Mesh ) -> Result< (), RenderError >
+    {
+      let FillRef::Solid( color ) = m.fill else { return Ok( () ) };
+      let matrix = mat_mul( &self.group_matrix(), &m.transform.to_mat3() );
+      self.draw_geometry( m.geometry, &matrix, m.topology, color )
+    }
+
+    fn cmd_sprite( &mut self, s : &Sprite ) -> Result< (), RenderError >
+    {
+      let matrix = mat_mul( &self.group_matrix(), &s.transform.to_mat3() );
+      self.draw_sprite( s.sprite, &matrix, s.tint )
+    }
+
+    // -- batches --
+
+    fn bound_batch( &mut self ) -> Option< &mut TerminalBatch >
+    {
+      let id = self.recording_batch?;
+      self.resources.batches.get_mut( &id )
+    }
+
+    fn cmd_add_sprite_instance( &mut self, si : &AddSpriteInstance )
+    {
+      if let Some( TerminalBatch::Sprite { instances, .. } ) = self.bound_batch()
+      {
+        instances.push( *si );
+      }
+    }
+
+    fn cmd_add_mesh_instance( &mut self, mi : &AddMeshInstance )
+    {
+      if let Some( TerminalBatch::Mesh { instances, .. } ) = self.bound_batch()
+      {
+        instances.push( *mi );
+      }
+    }
+
+    fn cmd_set_sprite_instance( &mut self, si : &SetSpriteInstance )
+    {
+      if let Some( TerminalBatch::Sprite { instances, .. } ) = self.bound_batch()
+        && let Some( slot ) = instances.get_mut( si.index as usize )
+      {
+        *slot = AddSpriteInstance { transform : si.transform, sprite : si.sprite, tint : si.tint, effects : si.effects };
+      }
+    }
+
+    fn cmd_set_mesh_instance( &mut self, mi : &SetMeshInstance )
+    {
+      if let Some( TerminalBatch::Mesh { instances, .. } ) = self.bound_batch()
+        && let Some( slot ) = instances.get_mut( mi.index as usize )
+      {
+        *slot = AddMeshInstance { transform : mi.transform, tint : mi.tint };
+      }
+    }
+
+    fn cmd_remove_instance( &mut self, ri : &RemoveInstance )
+    {
+      let index = ri.index as usize;
+      match self.bound_batch()
+      {
+        Some( TerminalBatch::Sprite { instances, .. } ) if index < instances.len() => { instances.swap_remove( index ); }
+        Some( TerminalBatch::Mesh { instances, .. } ) if index < instances.len() => { instances.swap_remove( index ); }
+        _ => {}
+      }
+    }
+
+    fn cmd_draw_batch( &mut self, db : &DrawBatch ) -> Result< (), RenderError >
+    {
+      let group = self.group_matrix();
+      // Temporarily take the batch out so drawing can borrow `self` mutably.
+      let Some( batch ) = self.resources.batches.remove( &db.batch ) else { return Ok( () ) };
+      let result = match &batch
+      {
+        TerminalBatch::Sprite { instances, params } =>
+        {
+          let parent = mat_mul( &group, &params.transform.to_mat3() );
+          instances.iter().try_for_each( | inst |
+          {
+            self.draw_sprite( inst.sprite, &mat_mul( &parent, &inst.transform.to_mat3() ), inst.tint )
+          })
+        }
+        TerminalBatch::Mesh { instances, params } =>
+        {
+          let FillRef::Solid( fill ) = params.fill else
+          {
+            self.resources.batches.insert( db.batch, batch );
+            return Ok( () );
+          };
+          let parent = mat_mul( &group, &params.transform.to_mat3() );
+          instances.iter().try_for_each( | inst |
+          {
+            let color = [ 0, 1, 2, 3 ].map( | i | fill[ i ] * inst.tint[ i ] );
+            self.draw_geometry( params.geometry, &mat_mul( &parent, &inst.transform.to_mat3() ), params.topology, color )
+          })
+        }
+      };
+      self.resources.batches.insert( db.batch, batch );
+      result
+    }
+
+    // -- groups --
+
+    fn cmd_begin_group( &mut self, bg : &BeginGroup )
+    {
+      let matrix = mat_mul( &self.group_matrix(), &bg.transform.to_mat3() );
+      let mut opacity = self.group_opacity();
+      if let Some( Effect::Opacity( value ) ) = bg.effect
+      {
+        opacity *= value.clamp( 0.0, 1.0 );
+      }
+      self.groups.push( GroupState { matrix, opacity } );
+    }
+
+    // -- output --
+
+    /// Composes a cell into `( character, foreground, background )`.
+    #[ allow( clippy::cast_sign_loss ) ]
+    fn compose( &self, cell : &Cell ) -> ( char, [ f32; 3 ], [ f32; 3 ] )
+    {
+      if let Some( glyph ) = cell.glyph
+      {
+        return ( glyph, cell.glyph_color, cell.background() );
+      }
+      if cell.dots != 0
+      {
+        let braille = char::from_u32( 0x2800 + u32::from( cell.dots ) ).unwrap_or( ' ' );
+        return ( braille, cell.dot_color, cell.background() );
+      }
+      if self.ansi
+      {
+        if to_u8( cell.top ) == to_u8( cell.bottom )
+        {
+          return ( ' ', cell.top, cell.top );
+        }
+        return ( '▀', cell.top, cell.bottom );
+      }
+      let luma = luminance( cell.background() );
+      let index = ( luma * ( LUMA_RAMP.len() - 1 ) as f32 ).round() as usize;
+      ( char::from( LUMA_RAMP[ index.min( LUMA_RAMP.len() - 1 ) ] ), cell.top, cell.bottom )
+    }
+
+    fn render_string( &self ) -> String
+    {
+      let mut out = String::with_capacity( self.cells.len() * if self.ansi { 24 } else { 1 } );
+      for row in self.cells.chunks( self.columns.max( 1 ) as usize )
+      {
+        let mut current : Option< ( [ u8; 3 ], [ u8; 3 ] ) > = None;
+        for cell in row
+        {
+          let ( ch, fg, bg ) = self.compose( cell );
+          if self.ansi
+          {
+            let colors = ( to_u8( fg ), to_u8( bg ) );
+            if current != Some( colors )
+            {
+              let ( [ fr, fgreen, fb ], [ br, bgreen, bb ] ) = colors;
+              let _ = write!( out, "\x1b[38;2;{fr};{fgreen};{fb};48;2;{br};{bgreen};{bb}m" );
+              current = Some( colors );
+            }
+          }
+          out.push( ch );
+        }
+        if self.ansi
+        {
+          out.push_str( "\x1b[0m" );
+        }
+        out.push( '\n' );
+      }
+      out
+    }
+
+    // -- asset loading --
+
+    fn load_images( &mut self, images : &[ ImageAsset ] ) -> Result< (), RenderError >
+    {
+      for img in images
+      {
+        let decoded = match &img.source
+        {
+          ImageSource::Bitmap { bytes, width, height, format } if *width > 0 && *height > 0 =>
+          {
+            TerminalImage::from_bitmap( bytes, *width, *height, *format )
+          }
+          ImageSource::Bitmap { .. } => None,
+          #[ cfg( feature = "image" ) ]
+          ImageSource::Encoded( bytes ) =>
+          {
+            let decoded = image::load_from_memory( bytes )
+              .map_err( | e | RenderError::BackendError( format!( "image {}: {e}", img.id.inner() ) ) )?
+              .to_rgba8();
+            TerminalImage::from_bitmap( decoded.as_raw(), decoded.width(), decoded.height(), PixelFormat::Rgba8 )
+          }
+          #[ cfg( feature = "image" ) ]
+          ImageSource::Path( path ) =>
+          {
+            let decoded = image::open( path )
+              .map_err( | e | RenderError::BackendError( format!( "image {} ({}): {e}", img.id.inner(), path.display() ) ) )?
+              .to_rgba8();
+            TerminalImage::from_bitmap( decoded.as_raw(), decoded.width(), decoded.height(), PixelFormat::Rgba8 )
+          }
+          // Without the `image` feature there is no decoder; sprites cut from
+          // these sheets report `MissingAsset` when drawn.
+          #[ cfg( not( feature = "image" ) ) ]
+          ImageSource::Encoded( _ ) | ImageSource::Path( _ ) => None,
+        };
+        if let Some( decoded ) = decoded
+        {
+          self.resources.images.insert( img.id, decoded );
+        }
+      }
+      Ok( () )
+    }
+
+    fn load_geometries( &mut self, geometries : &[ GeometryAsset ] )
+    {
+      for geom in geometries
+      {
+        // Source::Path geometries are skipped, as in the SVG backend.
+        let Source::Bytes( bytes ) = &geom.positions else { continue };
+        let positions = bytes.chunks_exact( 4 ).map( | b | f32::from_le_bytes( [ b[ 0 ], b[ 1 ], b[ 2 ], b[ 3 ] ] ) ).collect();
+        let indices = match &geom.indices
+        {
+          Some( Source::Bytes( ibytes ) ) => match geom.data_type
+          {
+            DataType::U8 => Some( ibytes.iter().map( | &i | u32::from( i ) ).collect() ),
+            DataType::U16 => Some( ibytes.chunks_exact( 2 ).map( | b | u32::from( u16::from_le_bytes( [ b[ 0 ], b[ 1 ] ] ) ) ).collect() ),
+            DataType::U32 => Some( ibytes.chunks_exact( 4 ).map( | b | u32::from_le_bytes( [ b[ 0 ], b[ 1 ], b[ 2 ], b[ 3 ] ] ) ).collect() ),
+            DataType::F32 => None, // F32 is not a valid index type; documented in DataType::F32 doc
+          },
+          _ => None,
+        };
+        self.resources.geometries.insert( geom.id, TerminalGeometry { positions, indices } );
+      }
+    }
+  }
+
+  // ============================================================================
+  // Math helpers
+  // ============================================================================
+
+  /// Column-major 3x3 affine product `a * b`.
+  /// Clips the segment `a`–`b` to the box `[0, bounds[0]] × [0, bounds[1]]`
+  /// (Liang–Barsky). Returns `None` when nothing of it lies inside or an
+  /// endpoint isn't finite.
+  ///
+  /// Clipped endpoints are placed on the crossed border and the other axis
+  /// follows from the slope, so endpoints far outside don't wash out the
+  /// precision of the visible part.
+  fn clip_segment( a : [ f32; 2 ], b : [ f32; 2 ], bounds : [ f32; 2 ] ) -> Option< ( [ f32; 2 ], [ f32; 2 ] ) >
+  {
+    if !a.iter().chain( &b ).all( | v | v.is_finite() )
+    {
+      return None;
+    }
+    let a = a.map( f64::from );
+    let b = b.map( f64::from );
+    let d = [ b[ 0 ] - a[ 0 ], b[ 1 ] - a[ 1 ] ];
+    // Parameter of each end and the ( axis, border ) that clipped it.
+    let mut enter = ( 0.0_f64, None );
+    let mut leave = ( 1.0_f64, None );
+    for axis in 0..2
+    {
+      let border = f64::from( bounds[ axis ] );
+      for ( p, q, edge ) in [ ( -d[ axis ], a[ axis ], 0.0 ), ( d[ axis ], border - a[ axis ], border ) ]
+      {
+        if p == 0.0
+        {
+          if q < 0.0
+          {
+            return None;
+          }
+          continue;
+        }
+        let t = q / p;
+        let ( tighter, end ) = if p < 0.0 { ( t > enter.0, &mut enter ) } else { ( t < leave.0, &mut leave ) };
+        if tighter
+        {
+          *end = ( t, Some( ( axis, edge ) ) );
+        }
+      }
+    }
+    if enter.0 > leave.0
+    {
+      return None;
+    }
+    let at = | end : [ f64; 2 ], clip : Option< ( usize, f64 ) > | -> [ f32; 2 ]
+    {
+      let Some( ( axis, edge ) ) = clip else { return end.map( | v | v as f32 ) };
+      let other = 1 - axis;
+      let mut p = [ 0.0; 2 ];
+      p[ axis ] = edge;
+      p[ other ] = a[ other ] + ( edge - a[ axis ] ) * d[ other ] / d[ axis ];
+      p.map( | v | v as f32 )
+    };
+    Some( ( at( a, enter.1 ), at( b, leave.1 ) ) )
+  }
+
+  fn mat_mul( a : &Mat3, b : &Mat3 ) -> Mat3
+  {
+    let mut out = [ 0.0; 9 ];
+    for column in 0..3
+    {
+      for row in 0..3
+      {
+        out[ column * 3 + row ] = ( 0..3 ).map( | k | a[ k * 3 + row ] * b[ column * 3 + k ] ).sum();
+      }
+    }
+    out
+  }
+
+  /// Applies a column-major affine matrix to a point.
+  fn mat_apply( m : &Mat3, p : [ f32; 2 ] ) -> [ f32; 2 ]
+  {
+    [ m[ 0 ] * p[ 0 ] + m[ 3 ] * p[ 1 ] + m[ 6 ], m[ 1 ] * p[ 0 ] + m[ 4 ] * p[ 1 ] + m[ 7 ] ]
+  }
+
+  /// Inverts a column-major affine matrix. Returns `None` when it is singular.
+  fn mat_invert( m : &Mat3 ) -> Option< Mat3 >
+  {
+    let det = m[ 0 ] * m[ 4 ] - m[ 3 ] * m[ 1 ];
+    if det.abs() < f32::EPSILON
+    {
+      return None;
+    }
+    let inv = 1.0 / det;
+    let a = m[ 4 ] * inv;
+    let b = -m[ 1 ] * inv;
+    let c = -m[ 3 ] * inv;
+    let d = m[ 0 ] * inv;
+    Some( [ a, b, 0.0, c, d, 0.0, -( a * m[ 6 ] + c * m[ 7 ] ), -( b * m[ 6 ] + d * m[ 7 ] ), 1.0 ] )
+  }
+
+  fn rgb( color : &[ f32; 4 ] ) -> [ f32; 3 ]
+  {
+    [ color[ 0 ], color[ 1 ], color[ 2 ] ]
+  }
+
+  #[ allow( clippy::cast_sign_loss ) ]
+  fn to_u8( color : [ f32; 3 ] ) -> [ u8; 3 ]
+  {
+    color.map( | c | ( c.clamp( 0.0, 1.0 ) * 255.0 ).round() as u8 )
+  }
+
+  /// Rec. 709 relative luminance.
+  fn luminance( color : [ f32; 3 ] ) -> f32
+  {
+    ( 0.2126 * color[ 0 ] + 0.7152 * color[ 1 ] + 0.0722 * color[ 2 ] ).clamp( 0.0, 1.0 )
+  }
+
+  // ============================================================================
+  // Backend trait impl
+  // ============================================================================
+
+  impl Backend for TerminalBackend
+  {
+    fn load_assets( &mut self, assets : &Assets ) -> Result< (), RenderError >
+    {
+      self.resources = TerminalResources::new();
+      self.load_images( &assets.images )?;
+      for sprite in &assets.sprites
+      {
+        self.resources.sprites.insert( sprite.id, ( sprite.sheet, sprite.region ) );
+      }
+      self.load_geometries( &assets.geometries );
+      Ok( () )
+    }
+
+    fn submit( &mut self, commands : &[ RenderCommand ] ) -> Result< (), RenderError >
+    {
+      self.cells.fill( Cell::filled( rgb( &self.config.background ) ) );
+      self.groups.clear();
+      self.recording_batch = None;
+      self.path_style = None;
+      self.subpaths.clear();
+      self.text_style = None;
+      self.text_buf.clear();
+
+      for cmd in commands
+      {
+        match cmd
+        {
+          RenderCommand::Clear( c ) => self.cmd_clear( c ),
+          // A character grid cannot animate; sprite effects are ignored.
+          RenderCommand::SetTime( _ ) => {},
+          RenderCommand::BeginPath( bp ) =>
+          {
+            self.subpaths.clear();
+            self.path_style = Some( *bp );
+          }
+          RenderCommand::MoveTo( m ) => self.cmd_move_to( m ),
+          RenderCommand::LineTo( l ) => self.push_point( [ l.0, l.1 ] ),
+          RenderCommand::QuadTo( q ) => self.cmd_quad_to( q ),
+          RenderCommand::CubicTo( c ) => self.cmd_cubic_to( c ),
+          RenderCommand::ArcTo( a ) => self.cmd_arc_to( a ),
+          RenderCommand::ClosePath( _ ) => self.cmd_close_path(),
+          RenderCommand::EndPath( _ ) => self.cmd_end_path(),
+          RenderCommand::BeginText( bt ) =>
+          {
+            self.text_buf.clear();
+            self.text_style = Some( *bt );
+          }
+          RenderCommand::Char( ch ) => self.text_buf.push( ch.0 ),
+          RenderCommand::EndText( _ ) => self.cmd_end_text(),
+          RenderCommand::Mesh( m ) => self.cmd_mesh( m )?,
+          // There is no camera here, so screen-space sprites draw like world-space ones.
+          RenderCommand::Sprite( s ) | RenderCommand::ScreenSpaceSprite( s ) => self.cmd_sprite( s )?,
+          RenderCommand::CreateSpriteBatch( cb ) =>
+          {
+            self.resources.batches.insert( cb.batch, TerminalBatch::Sprite { instances : Vec::new(), params : cb.params } );
+          }
+          RenderCommand::CreateMeshBatch( cb ) =>
+          {
+            self.resources.batches.insert( cb.batch, TerminalBatch::Mesh { instances : Vec::new(), params : cb.params } );
+          }
+          RenderCommand::BindBatch( bb ) => self.recording_batch = Some( bb.batch ),
+          RenderCommand::AddSpriteInstance( si ) => self.cmd_add_sprite_instance( si ),
+          RenderCommand::AddMeshInstance( mi ) => self.cmd_add_mesh_instance( mi ),
+          RenderCommand::SetSpriteInstance( si ) => self.cmd_set_sprite_instance( si ),
+          RenderCommand::SetMeshInstance( mi ) => self.cmd_set_mesh_instance( mi ),
+          RenderCommand::RemoveInstance( ri ) => self.cmd_remove_instance( ri ),
+          RenderCommand::SetSpriteBatchParams( sp ) =>
+          {
+            if let Some( TerminalBatch::Sprite { params, .. } ) = self.bound_batch()
+            {
+              *params = sp.params;
+            }
+          }
+          RenderCommand::SetMeshBatchParams( mp ) =>
+          {
+            if let Some( TerminalBatch::Mesh { params, .. } ) = self.bound_batch()
+            {
+              *params = mp.params;
+            }
+          }
+          RenderCommand::UnbindBatch( _ ) => self.recording_batch = None,
+          RenderCommand::DrawBatch( db ) => self.cmd_draw_batch( db )?,
+          RenderCommand::DeleteBatch( db ) =>
+          {
+            self.resources.batches.remove( &db.batch );
+            if self.recording_batch == Some( db.batch )
+            {
+              self.recording_batch = None;
+            }
+          }
+          RenderCommand::BeginGroup( bg ) => self.cmd_begin_group( bg ),
+          RenderCommand::EndGroup( _ ) => { self.groups.pop(); }
+        }
+      }
+
+      Ok( () )
+    }
+
+    /// Reallocates the character grid to `width` columns × `height` rows.
+    /// The viewport (`RenderConfig::width × height`) is unchanged and is
+    /// rescaled onto the new grid on the next `submit`.
+    fn resize( &mut self, width : u32, height : u32 )
+    {
+      self.columns = width;
+      self.rows = height;
+      self.cells = vec![ Cell::filled( rgb( &self.config.background ) ); width as usize * height as usize ];
+    }
+
+    fn output( &self ) -> Result< Output, RenderError >
+    {
+      Ok( Output::String( self.render_string() ) )
+    }
+
+    fn capabilities( &self ) -> Capabilities
+    {
+      Capabilities
+      {
+        paths : true,
+        text : true,
+        meshes : true,
+        sprites : true,
+        batches : true,
+        gradients : false,
+        patterns : false,
+        clip_masks : false,
+        effects : false,
+        sprite_effects : false,
+        blend_modes : false,
+        text_on_path : false,
+        max_texture_size : 0,
+        supported_blend_modes : &[ BlendMode::Normal ],
+      }
+    }
+  }
+
+  // ============================================================================
+  // Tests
+  // ============================================================================
+
+  #[ cfg( test ) ]
+  mod tests
+  {
+    use super::*;
+
+    // -- helpers --
+
+    /// 40×20 pixel viewport on a 20×10 grid: one cell = 2×2 px, one colour pixel = 2×1 px.
+    fn term20x10() -> TerminalBackend
+    {
+      let mut term = TerminalBackend::new( RenderConfig { width : 40, height : 20, ..Default::default() }, 20, 10 );
+      term.set_ansi( false );
+      term
+    }
+
+    fn empty_assets() -> Assets
+    {
+      Assets
+      {
+        fonts : vec![],
+        images : vec![],
+        sprites : vec![],
+        geometries : vec![],
+        gradients : vec![],
+        patterns : vec![],
+        clip_masks : vec![],
+        paths : vec![],
+      }
+    }
+
+    fn render( term : &TerminalBackend ) -> String
+    {
+      match term.output().unwrap()
+      {
+        Output::String( s ) => s,
+        _ => panic!( "expected string output" ),
+      }
+    }
+
+    fn lines( term : &TerminalBackend ) -> Vec< String >
+    {
+      render( term ).lines().map( str::to_string ).collect()
+    }
+
+    fn path_style( fill : FillRef, stroke : [ f32; 4 ] ) -> BeginPath
+    {
+      BeginPath
+      {
+        transform : Transform::default(),
+        fill,
+        stroke_color : stroke,
+        stroke_width : 1.0,
+        stroke_cap : LineCap::default(),
+        stroke_join : LineJoin::default(),
+        stroke_dash : DashStyle::default(),
+        blend : BlendMode::Normal,
+        clip : None,
+      }
+    }
+
+    fn red_sheet_assets() -> Assets
+    {
+      // 2×2 sheet: top row red, bottom row blue.
+      let bytes = vec!
+      [
+        255, 0, 0, 255,  255, 0, 0, 255,
+        0, 0, 255, 255,  0, 0, 255, 255,
+      ];
+      Assets
+      {
+        images : vec![ ImageAsset
+        {
+          id : ResourceId::new( 0 ),
+          source : ImageSource::Bitmap { bytes, width : 2, height : 2, format : PixelFormat::Rgba8 },
+          filter : SamplerFilter::Nearest,
+          mipmap : MipmapMode::Off,
+          wrap : WrapMode::Clamp,
+        }],
+        sprites : vec![ SpriteAsset { id : ResourceId::new( 0 ), sheet : ResourceId::new( 0 ), region : [ 0.0, 0.0, 2.0, 2.0 ] } ],
+        ..empty_assets()
+      }
+    }
+
+    // -- grid --
+
+    #[ test ]
+    fn output_has_one_line_per_row()
+    {
+      let mut term = term20x10();
+      term.load_assets( &empty_assets() ).unwrap();
+      term.submit( &[] ).unwrap();
+      let out = lines( &term );
+      assert_eq!( out.len(), 10 );
+      assert!( out.iter().all( | l | l.chars().count() == 20 ) );
+    }
+
+    #[ test ]
+    fn resize_reallocates_grid()
+    {
+      let mut term = term20x10();
+      term.resize( 8, 3 );
+      assert_eq!( term.grid_size(), ( 8, 3 ) );
+      let out = lines( &term );
+      assert_eq!( out.len(), 3 );
+      assert!( out.iter().all( | l | l.chars().count() == 8 ) );
+    }
+
+    // -- clear --
+
+    #[ test ]
+    fn clear_white_fills_every_cell()
+    {
+      let mut term = term20x10();
+      term.load_assets( &empty_assets() ).unwrap();
+      term.submit( &[ RenderCommand::Clear( Clear { color : [ 1.0, 1.0, 1.0, 1.0 ] } ) ] ).unwrap();
+      assert!( render( &term ).lines().all( | l | l.chars().all( | c | c == '@' ) ) );
+    }
+
+    #[ test ]
+    fn clear_emits_truecolor_background()
+    {
+      let mut term = term20x10();
+      term.set_ansi( true );
+      term.load_assets( &empty_assets() ).unwrap();
+      term.submit( &[ RenderCommand::Clear( Clear { color : [ 1.0, 0.0, 0.0, 1.0 ] } ) ] ).unwrap();
+      let out = render( &term );
+      assert!( out.contains( "48;2;255;0;0m" ), "out: {out:?}" );
+      assert!( out.contains( "\x1b[0m\n" ) );
+      // Colour is emitted once per row, not once per cell.
+      assert_eq!( out.matches( "48;2;255;0;0m" ).count(), 10 );
+    }
+
+    // -- paths --
+
+    #[ test ]
+    fn path_stroke_draws_braille_line()
+    {
+      let mut term = term20x10();
+      term.load_assets( &empty_assets() ).unwrap();
+      // Horizontal line across the middle of the viewport (y = 10 in a 20 px tall Y-up viewport).
+      term.submit( &[
+        RenderCommand::BeginPath( path_style( FillRef::None, [ 1.0, 1.0, 1.0, 1.0 ] ) ),
+        RenderCommand::MoveTo( MoveTo( 0.0, 10.0 ) ),
+        RenderCommand::LineTo( LineTo( 39.0, 10.0 ) ),
+        RenderCommand::EndPath( EndPath ),
+      ]).unwrap();
+      let out = lines( &term );
+      // y = 10 → dot row 20 → cell row 5, dot row 0 of the cell (bits 0x01 | 0x08).
+      assert!( out[ 5 ].chars().all( | c | c == '⠉' ), "row: {}", out[ 5 ] );
+      assert!( out[ 4 ].chars().all( | c | c == ' ' ) );
+    }
+
+    #[ test ]
+    fn path_stroke_far_off_screen_is_clipped()
+    {
+      let mut term = term20x10();
+      term.load_assets( &empty_assets() ).unwrap();
+      term.submit( &[
+        RenderCommand::BeginPath( path_style( FillRef::None, [ 1.0, 1.0, 1.0, 1.0 ] ) ),
+        RenderCommand::MoveTo( MoveTo( -1.0e30, 10.0 ) ),
+        RenderCommand::LineTo( LineTo( 1.0e30, 10.0 ) ),
+        RenderCommand::MoveTo( MoveTo( 0.0, 0.0 ) ),
+        RenderCommand::LineTo( LineTo( f32::INFINITY, 5.0 ) ),
+        RenderCommand::EndPath( EndPath ),
+      ]).unwrap();
+      let out = lines( &term );
+      assert!( out[ 5 ].chars().all( | c | c == '⠉' ), "row: {}", out[ 5 ] );
+    }
+
+    #[ test ]
+    fn clip_segment_keeps_inside_part()
+    {
+      assert_eq!( clip_segment( [ -10.0, 1.0 ], [ 10.0, 1.0 ], [ 4.0, 4.0 ] ), Some( ( [ 0.0, 1.0 ], [ 4.0, 1.0 ] ) ) );
+      assert_eq!( clip_segment( [ 1.0, 1.0 ], [ 2.0, 3.0 ], [ 4.0, 4.0 ] ), Some( ( [ 1.0, 1.0 ], [ 2.0, 3.0 ] ) ) );
+      assert_eq!( clip_segment( [ -5.0, -1.0 ], [ 5.0, -1.0 ], [ 4.0, 4.0 ] ), None );
+      assert_eq!( clip_segment( [ 0.0, 0.0 ], [ f32::NAN, 1.0 ], [ 4.0, 4.0 ] ), None );
+    }
+
+    #[ test ]
+    fn path_fill_covers_interior_only()
+    {
+      let mut term = term20x10();
+      term.load_assets( &empty_assets() ).unwrap();
+      // Rectangle over the left half of the viewport.
+      term.submit( &[
+        RenderCommand::BeginPath( path_style( FillRef::Solid( [ 1.0, 1.0, 1.0, 1.0 ] ), [ 0.0; 4 ] ) ),
+        RenderCommand::MoveTo( MoveTo( 0.0, 0.0 ) ),
+        RenderCommand::LineTo( LineTo( 20.0, 0.0 ) ),
+        RenderCommand::LineTo( LineTo( 20.0, 20.0 ) ),
+        RenderCommand::LineTo( LineTo( 0.0, 20.0 ) ),
+        RenderCommand::ClosePath( ClosePath ),
+        RenderCommand::EndPath( EndPath ),
+      ]).unwrap();
+      for line in lines( &term )
+      {
+        assert_eq!( line, format!( "{}{}", "@".repeat( 10 ), " ".repeat( 10 ) ) );
+      }
+    }
+
+    #[ test ]
+    fn path_arc_reaches_endpoint()
+    {
+      let mut term = term20x10();
+      term.submit( &[
+        RenderCommand::BeginPath( path_style( FillRef::None, [ 1.0; 4 ] ) ),
+        RenderCommand::MoveTo( MoveTo( 10.0, 10.0 ) ),
+        RenderCommand::ArcTo( ArcTo { rx : 10.0, ry : 10.0, rotation : 0.0, large_arc : false, sweep : true, x : 30.0, y : 10.0 } ),
+        RenderCommand::EndPath( EndPath ),
+      ]).unwrap();
+      assert!( term.subpaths.is_empty(), "path state is consumed by EndPath" );
+      let drawn : usize = term.cells.iter().map( | c | c.dots.count_ones() as usize ).sum();
+      assert!( drawn > 20, "half circle of radius 10 should light many dots, got {drawn}" );
+    }
+
+    // -- text --
+
+    #[ test ]
+    fn text_is_placed_at_position()
+    {
+      let mut term = term20x10();
+      term.load_assets( &empty_assets() ).unwrap();
+      let text = BeginText
+      {
+        font : ResourceId::new( 0 ),
+        size : 12.0,
+        color : [ 1.0; 4 ],
+        anchor : TextAnchor::Center,
+        position : [ 20.0, 10.0 ],
+        along_path : None,
+        clip : None,
+      };
+      let mut cmds = vec![ RenderCommand::BeginText( text ) ];
+      cmds.extend( "HUD".chars().map( | c | RenderCommand::Char( Char( c ) ) ) );
+      cmds.push( RenderCommand::EndText( EndText ) );
+      term.submit( &cmds ).unwrap();
+      let out = lines( &term );
+      assert_eq!( out[ 5 ].trim(), "HUD" );
+      assert_eq!( out[ 5 ].find( 'H' ), Some( 9 ) );
+    }
+
+    // -- sprites --
+
+    #[ test ]
+    fn sprite_is_downsampled_with_top_row_first()
+    {
+      let mut term = TerminalBackend::new( RenderConfig { width : 4, height : 4, ..Default::default() }, 2, 2 );
+      term.load_assets( &red_sheet_assets() ).unwrap();
+      // Scale the 2×2 sprite to fill the 4×4 viewport.
+      term.submit( &[ RenderCommand::Sprite( Sprite
+      {
+        transform : Transform { scale : [ 2.0, 2.0 ], ..Default::default() },
+        sprite : ResourceId::new( 0 ),
+        tint : [ 1.0; 4 ],
+        blend : BlendMode::Normal,
+        clip : None,
+        effects : SpriteEffects::default(),
+      })]).unwrap();
+      let top = &term.cells[ 0 ];
+      let bottom = &term.cells[ 2 ];
+      assert_eq!( to_u8( top.top ), [ 255, 0, 0 ] );
+      assert_eq!( to_u8( bottom.bottom ), [ 0, 0, 255 ] );
+    }
+
+    #[ test ]
+    fn sprite_box_filter_averages_texels()
+    {
+      // Whole 2×2 sheet collapses into a single colour pixel.
+      let mut term = TerminalBackend::new( RenderConfig { width : 2, height : 2, ..Default::default() }, 1, 1 );
+      term.load_assets( &red_sheet_assets() ).unwrap();
+      term.submit( &[ RenderCommand::Sprite( Sprite
+      {
+        transform : Transform::default(),
+        sprite : ResourceId::new( 0 ),
+        tint : [ 1.0; 4 ],
+        blend : BlendMode::Normal,
+        clip : None,
+        effects : SpriteEffects::default(),
+      })]).unwrap();
+      // Top half of the cell is the red row, bottom half the blue row.
+      assert_eq!( to_u8( term.cells[ 0 ].top ), [ 255, 0, 0 ] );
+      assert_eq!( to_u8( term.cells[ 0 ].bottom ), [ 0, 0, 255 ] );
+      term.set_ansi( true );
+      assert!( render( &term ).contains( '▀' ) );
+    }
+
+    #[ test ]
+    fn sprite_unknown_id_is_missing_asset()
+    {
+      let mut term = term20x10();
+      term.load_assets( &empty_assets() ).unwrap();
+      let result = term.submit( &[ RenderCommand::Sprite( Sprite
+      {
+        transform : Transform::default(),
+        sprite : ResourceId::new( 7 ),
+        tint : [ 1.0; 4 ],
+        blend : BlendMode::Normal,
+        clip : None,
+        effects : SpriteEffects::default(),
+      })]);
+      assert!( matches!( result, Err( RenderError::MissingAsset( 7 ) ) ) );
+    }
+
+    #[ cfg( not( feature = "image" ) ) ]
+    #[ test ]
+    fn sprite_from_undecoded_sheet_is_missing_asset()
+    {
+      let mut assets = red_sheet_assets();
+      assets.images[ 0 ].source = ImageSource::Encoded( vec![ 1, 2, 3 ] );
+      let mut term = term20x10();
+      term.load_assets( &assets ).unwrap();
+      let result = term.submit( &[ RenderCommand::Sprite( Sprite
+      {
+        transform : Transform::default(),
+        sprite : ResourceId::new( 0 ),
+        tint : [ 1.0; 4 ],
+        blend : BlendMode::Normal,
+        clip : None,
+        effects : SpriteEffects::default(),
+      })]);
+      assert!( matches!( result, Err( RenderError::MissingAsset( 0 ) ) ) );
+    }
+
+    #[ cfg( feature = "image" ) ]
+    #[ test ]
+    fn encoded_png_sheet_is_decoded()
+    {
+      let mut png = Vec::new();
+      image::RgbaImage::from_pixel( 2, 2, image::Rgba( [ 0, 255, 0, 255 ] ) )
+        .write_to( &mut std::io::Cursor::new( &mut png ), image::ImageFormat::Png )
+        .unwrap();
+      let mut assets = red_sheet_assets();
+      assets.images[ 0 ].source = ImageSource::Encoded( png );
+      let mut term = TerminalBackend::new( RenderConfig { width : 2, height : 2, ..Default::default() }, 1, 1 );
+      term.load_assets( &assets ).unwrap();
+      term.submit( &[ RenderCommand::Sprite( Sprite
+      {
+        transform : Transform::default(),
+        sprite : ResourceId::new( 0 ),
+        tint : [ 1.0; 4 ],
+        blend : BlendMode::Normal,
+        clip : None,
+        effects : SpriteEffects::default(),
+      })]).unwrap();
+      assert_eq!( to_u8( term.cells[ 0 ].top ), [ 0, 255, 0 ] );
+    }
+
+    #[ cfg( feature = "image" ) ]
+    #[ test ]
+    fn undecodable_image_is_backend_error()
+    {
+      let mut assets = red_sheet_assets();
+      assets.images[ 0 ].source = ImageSource::Encoded( vec![ 1, 2, 3 ] );
+      let mut term = term20x10();
+      assert!( matches!( term.load_assets( &assets ), Err( RenderError::BackendError( _ ) ) ) );
+    }
+
+    // -- batches --
+
+    #[ test ]
+    fn sprite_batch_draws_every_instance()
+    {
+      let mut term = TerminalBackend::new( RenderConfig { width : 8, height : 2, ..Default::default() }, 4, 1 );
+      term.load_assets( &red_sheet_assets() ).unwrap();
+      let batch : ResourceId< Batch > = ResourceId::new( 0 );
+      term.submit( &[
+        RenderCommand::CreateSpriteBatch( CreateSpriteBatch
+        {
+          batch,
+          params : SpriteBatchParams { transform : Transform::default(), sheet : ResourceId::new( 0 ), blend : BlendMode::Normal, clip : None },
+        }),
+        RenderCommand::BindBatch( BindBatch { batch } ),
+        RenderCommand::AddSpriteInstance( AddSpriteInstance { transform : Transform::default(), sprite : ResourceId::new( 0 ), tint : [ 1.0; 4 ], effects : SpriteEffects::default() } ),
+        RenderCommand::AddSpriteInstance( AddSpriteInstance
+        {
+          transform : Transform { position : [ 6.0, 0.0 ], ..Default::default() },
+          sprite : ResourceId::new( 0 ),
+          tint : [ 1.0; 4 ],
+          effects : SpriteEffects::default(),
+        }),
+        RenderCommand::UnbindBatch( UnbindBatch ),
+        RenderCommand::DrawBatch( DrawBatch { batch } ),
+      ]).unwrap();
+      let lit : Vec< bool > = term.cells.iter().map( | c | to_u8( c.top ) != [ 0, 0, 0 ] ).collect();
+      assert_eq!( lit, vec![ true, false, false, true ] );
+    }
+
+    #[ test ]
+    fn sprite_batch_remove_instance_swap_removes()
+    {
+      let mut term = TerminalBackend::new( RenderConfig { width : 8, height : 2, ..Default::default() }, 4, 1 );
+      term.load_assets( &red_sheet_assets() ).unwrap();
+      let batch : ResourceId< Batch > = ResourceId::new( 0 );
+      let at = | x : f32 | RenderCommand::AddSpriteInstance( AddSpriteInstance
+      {
+        transform : Transform { position : [ x, 0.0 ], ..Default::default() },
+        sprite : ResourceId::new( 0 ),
+        tint : [ 1.0; 4 ],
+        effects : SpriteEffects::default(),
+      });
+      term.submit( &[
+        RenderCommand::CreateSpriteBatch( CreateSpriteBatch
+        {
+          batch,
+          params : SpriteBatchParams { transform : Transform::default(), sheet : ResourceId::new( 0 ), blend : BlendMode::Normal, clip : None },
+        }),
+        RenderCommand::BindBatch( BindBatch { batch } ),
+        at( 0.0 ),
+        at( 2.0 ),
+        at( 4.0 ),
+        RenderCommand::RemoveInstance( RemoveInstance { index : 0 } ),
+        RenderCommand::UnbindBatch( UnbindBatch ),
+        RenderCommand::DrawBatch( DrawBatch { batch } ),
+      ]).unwrap();
+      let lit : Vec< bool > = term.cells.iter().map( | c | to_u8( c.top ) != [ 0, 0, 0 ] ).collect();
+      assert_eq!( lit, vec![ false, true, true, false ] );
+    }
+
+    #[ test ]
+    fn mesh_batch_fills_triangles_with_tint()
+    {
+      let mut term = TerminalBackend::new( RenderConfig { width : 4, height : 4, ..Default::default() }, 4, 2 );
+      let positions : Vec< u8 > = [ 0.0_f32, 0.0, 4.0, 0.0, 4.0, 4.0, 0.0, 0.0, 4.0, 4.0, 0.0, 4.0 ]
+        .iter().flat_map( | f | f.to_le_bytes() ).collect();
+      let assets = Assets
+      {
+        geometries : vec![ GeometryAsset
+        {
+          id : ResourceId::new( 0 ),
+          positions : Source::Bytes( positions ),
+          uvs : None,
+          indices : None,
+          data_type : DataType::U32,
+        }],
+        ..empty_assets()
+      };
+      term.load_assets( &assets ).unwrap();
+      let batch : ResourceId< Batch > = ResourceId::new( 1 );
+      term.submit( &[
+        RenderCommand::CreateMeshBatch( CreateMeshBatch
+        {
+          batch,
+          params : MeshBatchParams
+          {
+            transform : Transform::default(),
+            geometry : ResourceId::new( 0 ),
+            fill : FillRef::Solid( [ 1.0; 4 ] ),
+            texture : None,
+            topology : Topology::TriangleList,
+            blend : BlendMode::Normal,
+            clip : None,
+          },
+        }),
+        RenderCommand::BindBatch( BindBatch { batch } ),
+        RenderCommand::AddMeshInstance( AddMeshInstance { transform : Transform::default(), tint : [ 0.0, 1.0, 0.0, 1.0 ] } ),
+        RenderCommand::UnbindBatch( UnbindBatch ),
+        RenderCommand::DrawBatch( DrawBatch { batch } ),
+      ]).unwrap();
+      assert!( term.cells.iter().all( | c | to_u8( c.top ) == [ 0, 255, 0 ] && to_u8( c.bottom ) == [ 0, 255, 0 ] ) );
+    }
+
+    // -- groups --
+
+    #[ test ]
+    fn group_transform_and_opacity_apply_to_children()
+    {
+      let mut term = TerminalBackend::new( RenderConfig { width : 8, height : 2, ..Default::default() }, 4, 1 );
+      term.load_assets( &red_sheet_assets() ).unwrap();
+      term.submit( &[
+        RenderCommand::BeginGroup( BeginGroup
+        {
+          transform : Transform { position : [ 6.0, 0.0 ], ..Default::default() },
+          clip : None,
+          effect : Some( Effect::Opacity( 0.5 ) ),
+        }),
+        RenderCommand::Sprite( Sprite
+        {
+          transform : Transform::default(),
+          sprite : ResourceId::new( 0 ),
+          tint : [ 1.0; 4 ],
+          blend : BlendMode::Normal,
+          clip : None,
+          effects : SpriteEffects::default(),
+        }),
+        RenderCommand::EndGroup( EndGroup ),
+      ]).unwrap();
+      assert_eq!( to_u8( term.cells[ 0 ].top ), [ 0, 0, 0 ] );
+      assert_eq!( to_u8( term.cells[ 3 ].top ), [ 128, 0, 0 ] );
+    }
+
+    // -- math --
 
-mod private {}
+    #[ test ]
+    fn mat_invert_round_trips()
+    {
+      let m = Transform { position : [ 3.0, -2.0 ], rotation : 0.7, scale : [ 2.0, 0.5 ], ..Default::default() }.to_mat3();
+      let inv = mat_invert( &m ).unwrap();
+      let p = mat_apply( &inv, mat_apply( &m, [ 1.5, 4.0 ] ) );
+      assert!( ( p[ 0 ] - 1.5 ).abs() < 1e-4 && ( p[ 1 ] - 4.0 ).abs() < 1e-4, "{p:?}" );
+    }
+  }
+}
 
-mod_interface::mod_interface! {}
+mod_interface::mod_interface!
+{
+  own use TerminalBackend;
+}
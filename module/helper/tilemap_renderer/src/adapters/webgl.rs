@@ -14,6 +14,7 @@ mod private
   use super::webgl_helpers::
   {
     ArrayBuffer,
+    SpriteEffectData,
     SpriteInstanceData,
     MeshInstanceData,
     GpuResources,
@@ -71,7 +72,20 @@ mod private
     ///
     /// `region` is the sprite rect in pixels and `tex_size` is the sheet's dimensions — same
     /// convention as `sprite_batch.vert`, so both shaders normalize UV the same way.
-    fn draw( &self, gl : &gl::GL, transform : &[ f32; 9 ], region : &[ f32; 4 ], tex_size : &[ f32; 2 ], tint : &[ f32; 4 ], viewport : &[ f32; 2 ], depth : f32, max_depth : f32 )
+    fn draw
+    (
+      &self,
+      gl : &gl::GL,
+      transform : &[ f32; 9 ],
+      region : &[ f32; 4 ],
+      tex_size : &[ f32; 2 ],
+      tint : &[ f32; 4 ],
+      effects : &SpriteEffectData,
+      viewport : &[ f32; 2 ],
+      depth : f32,
+      max_depth : f32,
+      time : f32,
+    )
     {
       // Unbind any VAO to prevent stale attribute state from interfering
       gl.bind_vertex_array( None );
@@ -83,11 +97,17 @@ mod private
       self.program.uniform_upload( "u_viewport", viewport );
       self.program.uniform_upload( "u_depth", &depth );
       self.program.uniform_upload( "u_max_depth", &max_depth );
+      self.program.uniform_upload( "u_time", &time );
+      self.program.uniform_upload( "u_displace", &effects.displace );
+      self.program.uniform_upload( "u_displace_phase", &effects.wave[ 0 ] );
+      self.program.uniform_upload( "u_pulse", &effects.pulse );
+      self.program.uniform_upload( "u_shift", &effects.shift );
+      self.program.uniform_upload( "u_shift_wave", &[ effects.wave[ 1 ], effects.wave[ 2 ] ] );
       gl.draw_arrays( gl::TRIANGLE_STRIP, 0, 4 );
     }
 
     /// Draw an instanced sprite batch.
-    fn draw_batch( &self, gl : &gl::GL, batch : &GpuBatch, resources : &GpuResources, viewport : &[ f32; 2 ], max_depth : f32 )
+    fn draw_batch( &self, gl : &gl::GL, batch : &GpuBatch, resources : &GpuResources, viewport : &[ f32; 2 ], max_depth : f32, time : f32 )
     {
       let GpuBatch::Sprite { instances, vao, params, .. } = batch else { return; };
       if instances.is_empty() { return; }
@@ -107,6 +127,7 @@ mod private
       self.batch_program.uniform_matrix_upload( "u_parent", &parent_mat, true );
       self.batch_program.uniform_upload( "u_parent_depth", &params.transform.depth );
       self.batch_program.uniform_upload( "u_max_depth", &max_depth );
+      self.batch_program.uniform_upload( "u_time", &time );
 
       gl.bind_vertex_array( Some( vao ) );
       gl.draw_arrays_instanced( gl::TRIANGLE_STRIP, 0, 4, instances.len() as i32 );
@@ -259,6 +280,9 @@ mod private
 
     // -- batch editing state --
     recording_batch : Option< ResourceId< Batch > >,
+
+    /// Clock from the last `SetTime`, uploaded as `u_time` to both sprite programs.
+    time : f32,
   }
 
   impl WebGlBackend
@@ -321,6 +345,7 @@ mod private
         mesh,
         max_texture_size,
         recording_batch : None,
+        time : 0.0,
       })
     }
 
@@ -357,6 +382,11 @@ mod private
       self.gl.clear( gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT );
     }
 
+    fn cmd_set_time( &mut self, t : &SetTime )
+    {
+      self.time = t.seconds;
+    }
+
     fn cmd_mesh( &self, m : &Mesh, viewport : &[ f32; 2 ] )
     {
       let res = self.resources.borrow();
@@ -395,7 +425,8 @@ mod private
 
       let mat = s.transform.to_mat3();
       apply_blend( &self.gl, &s.blend );
-      self.sprite.draw( &self.gl, &mat, &gpu_sprite.region, &tex_size, &s.tint, viewport, s.transform.depth, self.config.max_depth );
+      let effects = SpriteEffectData::new( &s.effects );
+      self.sprite.draw( &self.gl, &mat, &gpu_sprite.region, &tex_size, &s.tint, &effects, viewport, s.transform.depth, self.config.max_depth, self.time );
     }
 
     fn cmd_create_sprite_batch( &mut self, cmd : &CreateSpriteBatch ) -> Result< (), RenderError >
@@ -477,6 +508,7 @@ mod private
         region,
         tint : si.tint,
         depth : si.transform.depth,
+        effects : SpriteEffectData::new( &si.effects ),
       };
       match res.batch_mut( batch_id )
       {
@@ -540,6 +572,7 @@ mod private
         region,
         tint : si.tint,
         depth : si.transform.depth,
+        effects : SpriteEffectData::new( &si.effects ),
       };
       match res.batch_mut( batch_id )
       {
@@ -741,7 +774,7 @@ mod private
       });
       match gpu_batch
       {
-        GpuBatch::Sprite { .. } => self.sprite.draw_batch( &self.gl, gpu_batch, &res, viewport, self.config.max_depth ),
+        GpuBatch::Sprite { .. } => self.sprite.draw_batch( &self.gl, gpu_batch, &res, viewport, self.config.max_depth, self.time ),
         GpuBatch::Mesh { .. } => self.mesh.draw_batch( &self.gl, gpu_batch, &res, viewport, self.config.max_depth ),
       }
       Ok( () )
@@ -1147,6 +1180,7 @@ mod private
         match cmd
         {
           RenderCommand::Clear( c ) => self.cmd_clear( c ),
+          RenderCommand::SetTime( t ) => self.cmd_set_time( t ),
 
           // Mesh & sprite
           RenderCommand::Mesh( m ) => self.cmd_mesh( m, &viewport ),
@@ -1234,6 +1268,7 @@ mod private
         patterns : false,    // qqq: not yet loaded or rendered
         clip_masks : false,  // qqq: not yet loaded or rendered
         effects : false,     // qqq: requires FBO post-processing
+        sprite_effects : true,
         // `blend_modes` means "all variants correct"; Overlay silently falls back
         // to Normal in `apply_blend` (needs FBO / custom shader), so this is false.
         // Callers needing per-mode info should check `supported_blend_modes`.
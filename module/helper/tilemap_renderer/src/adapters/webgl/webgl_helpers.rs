@@ -12,7 +12,7 @@ mod private
   use minwebgl as gl;
   use nohash_hasher::IntMap;
   use crate::backend::RenderError;
-  use crate::commands::{ SpriteBatchParams, MeshBatchParams };
+  use crate::commands::{ SpriteBatchParams, MeshBatchParams, SpriteEffects };
   use crate::types::{ asset, Batch, BlendMode, ResourceId, SamplerFilter, MipmapMode, Topology, WrapMode };
 
   // ============================================================================
@@ -213,7 +213,50 @@ mod private
   // Instance data for batches
   // ============================================================================
 
-  /// Per-instance data for sprite batches (18 floats = 72 bytes).
+  /// Shader-ready packing of [`SpriteEffects`] (15 floats = 60 bytes).
+  ///
+  /// Disabled slots become neutral values — zero amplitude, unit alpha — so
+  /// the sprite shaders evaluate every oscillator without branching.
+  #[ repr( C ) ]
+  #[ derive( Clone, Copy, bytemuck::Zeroable, bytemuck::Pod ) ]
+  pub struct SpriteEffectData
+  {
+    /// Vertex displacement: `axis.xy, amplitude, frequency`.
+    pub displace : [ f32; 4 ],
+    /// Alpha pulse: `min, max, frequency, phase`.
+    pub pulse : [ f32; 4 ],
+    /// Colour shift: `target.rgb, amplitude`.
+    pub shift : [ f32; 4 ],
+    /// Displacement phase, colour-shift frequency, colour-shift phase.
+    pub wave : [ f32; 3 ],
+  }
+
+  impl SpriteEffectData
+  {
+    /// Packs `effects`, filling unset slots with neutral values.
+    #[ must_use ]
+    pub fn new( effects : &SpriteEffects ) -> Self
+    {
+      let ( displace, displace_phase ) = effects.displace.map_or
+      (
+        ( [ 0.0; 4 ], 0.0 ),
+        | d | ( [ d.axis[ 0 ], d.axis[ 1 ], d.amplitude, d.frequency ], d.phase ),
+      );
+      let pulse = effects.alpha_pulse.map_or
+      (
+        [ 1.0, 1.0, 0.0, 0.0 ],
+        | a | [ a.min, a.max, a.frequency, a.phase ],
+      );
+      let ( shift, shift_wave ) = effects.color_shift.map_or
+      (
+        ( [ 0.0; 4 ], [ 0.0; 2 ] ),
+        | c | ( [ c.target[ 0 ], c.target[ 1 ], c.target[ 2 ], c.amplitude ], [ c.frequency, c.phase ] ),
+      );
+      Self { displace, pulse, shift, wave : [ displace_phase, shift_wave[ 0 ], shift_wave[ 1 ] ] }
+    }
+  }
+
+  /// Per-instance data for sprite batches (33 floats = 132 bytes).
   #[ repr( C ) ]
   #[ derive( Clone, Copy, bytemuck::Zeroable, bytemuck::Pod ) ]
   pub struct SpriteInstanceData
@@ -229,6 +272,8 @@ mod private
     /// by dividing by `RenderConfig::max_depth`. See `Transform::depth` for
     /// the valid range.
     pub depth : f32,
+    /// Per-instance sprite effects, evaluated against `u_time`.
+    pub effects : SpriteEffectData,
   }
 
   impl gl::AsBytes for SpriteInstanceData
@@ -260,7 +305,7 @@ mod private
   }
 
   // Compile-time layout assertions — GPU attrib setup depends on these exact sizes.
-  const _ : () = assert!( core::mem::size_of::< SpriteInstanceData >() == 72 ); // 18 floats × 4
+  const _ : () = assert!( core::mem::size_of::< SpriteInstanceData >() == 132 ); // 33 floats × 4
   const _ : () = assert!( core::mem::size_of::< MeshInstanceData >() == 56 ); // 14 floats × 4
   const _ : () = assert!( core::mem::align_of::< SpriteInstanceData >() == 4 ); // f32 alignment
   const _ : () = assert!( core::mem::align_of::< MeshInstanceData >() == 4 );
@@ -437,7 +482,7 @@ mod private
       gl : gl::GL,
       /// Per-instance sprite data (transform / region / tint / depth).
       instances : ArrayBuffer< SpriteInstanceData >,
-      /// VAO holding the instance attrib bindings (locations 0–5).
+      /// VAO holding the instance attrib bindings (locations 0–9).
       vao : web_sys::WebGlVertexArrayObject,
       /// Batch-wide parameters (atlas / blend).
       params : SpriteBatchParams,
@@ -499,6 +544,18 @@ mod private
     gl.enable_vertex_attrib_array( 5 );
     gl.vertex_attrib_pointer_with_i32( 5, 1, gl::FLOAT, false, stride, 68 );
     gl.vertex_attrib_divisor( 5, 1 );
+    // effects: displace / pulse / shift vec4s at locations 6–8, offsets 72 / 88 / 104
+    for i in 0..3_u32
+    {
+      let location = 6 + i;
+      gl.enable_vertex_attrib_array( location );
+      gl.vertex_attrib_pointer_with_i32( location, 4, gl::FLOAT, false, stride, ( 72 + i * 16 ) as i32 );
+      gl.vertex_attrib_divisor( location, 1 );
+    }
+    // effect phases: vec3 at location 9, offset 120
+    gl.enable_vertex_attrib_array( 9 );
+    gl.vertex_attrib_pointer_with_i32( 9, 3, gl::FLOAT, false, stride, 120 );
+    gl.vertex_attrib_divisor( 9, 1 );
 
     gl.bind_vertex_array( None );
   }
@@ -731,6 +788,7 @@ mod private
 mod_interface::mod_interface!
 {
   own use ArrayBuffer;
+  own use SpriteEffectData;
   own use SpriteInstanceData;
   own use MeshInstanceData;
   own use GpuResources;
@@ -99,6 +99,10 @@ mod private
     pub clip_masks : bool,
     /// Supports visual effects.
     pub effects : bool,
+    /// Animates [`crate::commands::SpriteEffects`] against the
+    /// [`crate::commands::SetTime`] clock. `false` means sprites draw
+    /// unmodified and the clock is ignored.
+    pub sprite_effects : bool,
     /// Supports **all** [`BlendMode`] variants correctly. `false` means at least
     /// one variant falls back / is unsupported — check [`Self::supported_blend_modes`]
     /// for the precise set before submitting a specific mode.
@@ -106,7 +110,7 @@ mod private
     /// The exact set of [`BlendMode`] variants that render correctly on this
     /// backend. Variants not listed here either fall back silently (e.g. WebGL
     /// Overlay → Normal) or are fully unsupported. Empty slice means no
-    /// blending at all (e.g. terminal backend).
+    /// blending at all.
     pub supported_blend_modes : &'static [ BlendMode ],
     /// Supports text on a path.
     pub text_on_path : bool,
@@ -176,7 +180,7 @@ mod private
     /// Resize the output surface.
     /// GPU: recreates swapchain / framebuffer.
     /// SVG: updates viewBox dimensions.
-    /// Terminal: reallocates character buffer.
+    /// Terminal: reallocates character buffer (`width` columns × `height` rows).
     fn resize( &mut self, width : u32, height : u32 );
 
     /// Query backend capabilities.
@@ -23,6 +23,23 @@ mod private
     pub color : [ f32; 4 ],
   }
 
+  // ============================================================================
+  // Frame clock
+  // ============================================================================
+
+  /// Sets the clock that drives [`SpriteEffects`] for every following draw.
+  /// SVG: offsets each SMIL animation's `begin` so the document opens at this time.
+  /// GPU: `u_time` uniform on the sprite programs.
+  ///
+  /// Backends start at `0.0` and keep the last value across `submit` calls,
+  /// so a producer that never animates can omit the command entirely.
+  #[ derive( Debug, Clone, Copy ) ]
+  pub struct SetTime
+  {
+    /// Clock value in seconds.
+    pub seconds : f32,
+  }
+
   // ============================================================================
   // Path commands (unified primitive for lines, curves, arcs)
   // ============================================================================
@@ -183,6 +200,84 @@ mod private
     pub clip : Option< ResourceId< asset::ClipMask > >,
   }
 
+  /// Sinusoidal vertex displacement — trees in wind.
+  ///
+  /// Each quad vertex moves by `axis * amplitude * wave * v`, where
+  /// `wave = sin( 2π * frequency * ( time + phase ) )` and `v` is the
+  /// vertex's height in the quad (`0` bottom, `1` top). The bottom edge
+  /// stays anchored; offsets are in sprite pixels, so they follow the
+  /// sprite's transform (zoom, rotation).
+  #[ derive( Debug, Clone, Copy, PartialEq ) ]
+  pub struct DisplaceEffect
+  {
+    /// Displacement direction in sprite space, Y-up. Usually a unit axis.
+    pub axis : [ f32; 2 ],
+    /// Peak displacement of the top edge, in sprite pixels.
+    pub amplitude : f32,
+    /// Oscillation frequency in Hz.
+    pub frequency : f32,
+    /// Phase offset in seconds, added to the frame clock.
+    pub phase : f32,
+  }
+
+  /// Alpha oscillating between `min` and `max`, multiplied into the tinted
+  /// colour: `alpha *= mix( min, max, 0.5 + 0.5 * wave )`.
+  #[ derive( Debug, Clone, Copy, PartialEq ) ]
+  pub struct AlphaPulseEffect
+  {
+    /// Alpha factor at the wave's trough.
+    pub min : f32,
+    /// Alpha factor at the wave's crest.
+    pub max : f32,
+    /// Oscillation frequency in Hz.
+    pub frequency : f32,
+    /// Phase offset in seconds, added to the frame clock.
+    pub phase : f32,
+  }
+
+  /// Colour drifting toward `target` and back:
+  /// `rgb = mix( rgb, target, amplitude * ( 0.5 + 0.5 * wave ) )`.
+  #[ derive( Debug, Clone, Copy, PartialEq ) ]
+  pub struct ColorShiftEffect
+  {
+    /// Target colour as RGB.
+    pub target : [ f32; 3 ],
+    /// Blend amount toward `target` at the wave's crest, `0..=1`.
+    pub amplitude : f32,
+    /// Oscillation frequency in Hz.
+    pub frequency : f32,
+    /// Phase offset in seconds, added to the frame clock.
+    pub phase : f32,
+  }
+
+  /// Time-driven modifiers evaluated by the backend against the clock set
+  /// with [`SetTime`]. One slot per kind; `None` disables it, so the
+  /// `Default` value draws the sprite unmodified.
+  ///
+  /// Applied after the tint: displacement moves the quad, then the colour
+  /// shift and alpha pulse modulate the tinted texel in that order.
+  #[ derive( Debug, Clone, Copy, Default, PartialEq ) ]
+  pub struct SpriteEffects
+  {
+    /// Vertex displacement.
+    pub displace : Option< DisplaceEffect >,
+    /// Alpha pulse.
+    pub alpha_pulse : Option< AlphaPulseEffect >,
+    /// Colour shift.
+    pub color_shift : Option< ColorShiftEffect >,
+  }
+
+  impl SpriteEffects
+  {
+    /// `true` when no effect is set.
+    #[ inline ]
+    #[ must_use ]
+    pub fn is_none( &self ) -> bool
+    {
+      self.displace.is_none() && self.alpha_pulse.is_none() && self.color_shift.is_none()
+    }
+  }
+
   /// Renders a sprite (sub-region of an image / sprite sheet).
   /// SVG: `<use href="#sprite_N">` referencing a `<symbol viewBox="region">`.
   /// GPU: textured quad with UV coordinates mapped to the sprite's region.
@@ -199,6 +294,8 @@ mod private
     pub blend : BlendMode,
     /// Optional clip mask.
     pub clip : Option< ResourceId< asset::ClipMask > >,
+    /// Time-driven effects. `SpriteEffects::default()` = none.
+    pub effects : SpriteEffects,
   }
 
   // ============================================================================
@@ -332,6 +429,8 @@ mod private
     pub sprite : ResourceId< asset::Sprite >,
     /// Tint color as RGBA.
     pub tint : [ f32; 4 ],
+    /// Time-driven effects. `SpriteEffects::default()` = none.
+    pub effects : SpriteEffects,
   }
 
   /// Appends a new mesh instance to the bound batch.
@@ -370,6 +469,8 @@ mod private
     pub sprite : ResourceId< asset::Sprite >,
     /// Tint color as RGBA.
     pub tint : [ f32; 4 ],
+    /// Time-driven effects. `SpriteEffects::default()` = none.
+    pub effects : SpriteEffects,
   }
 
   /// Updates a mesh instance at `index` within the bound batch.
@@ -549,6 +650,8 @@ mod private
   {
     /// Clear the framebuffer.
     Clear( Clear ),
+    /// Set the effect clock.
+    SetTime( SetTime ),
 
     /// Begin a new path.
     BeginPath( BeginPath ),
@@ -627,6 +730,7 @@ mod private
 mod_interface::mod_interface!
 {
   own use Clear;
+  own use SetTime;
   own use BeginPath;
   own use MoveTo;
   own use LineTo;
@@ -639,6 +743,10 @@ mod_interface::mod_interface!
   own use Char;
   own use EndText;
   own use Mesh;
+  own use DisplaceEffect;
+  own use AlphaPulseEffect;
+  own use ColorShiftEffect;
+  own use SpriteEffects;
   own use Sprite;
   own use SpriteBatchParams;
   own use MeshBatchParams;
@@ -21,6 +21,8 @@ mod private
     Animation,
     AnimationMode,
     AnimationTiming,
+    Effect,
+    EffectKind,
     PhaseOffset,
     SpriteRef,
   };
@@ -180,18 +182,53 @@ mod private
     instance_seed : Option< u32 >,
   ) -> f32
   {
-    match anim.phase_offset
+    phase_seconds( anim.phase_offset, &anim.id, animation_duration_seconds( anim ), pos, instance_seed )
+  }
+
+  /// Resolve an effect's declared phase offset (in seconds), the
+  /// [`declared_phase_seconds`] counterpart for SPEC §4.4 effects.
+  /// `HashCoord` / `Instance` spread over one oscillation period.
+  #[ must_use ]
+  pub fn effect_phase_seconds
+  (
+    effect : &Effect,
+    pos : ( i32, i32 ),
+    instance_seed : Option< u32 >,
+  ) -> f32
+  {
+    let frequency = match &effect.kind
+    {
+      EffectKind::VertexDisplace { frequency, .. }
+      | EffectKind::AlphaPulse { frequency, .. }
+      | EffectKind::ColorShift { frequency, .. } => *frequency,
+    };
+    let period = if frequency > 0.0 { 1.0 / frequency } else { 0.0 };
+    phase_seconds( effect.phase_offset, &effect.id, period, pos, instance_seed )
+  }
+
+  /// Shared `PhaseOffset` resolution. `id` salts the hashed variants so
+  /// two resources on the same cell pick independent phases; `period` is
+  /// the span they spread across.
+  fn phase_seconds
+  (
+    offset : PhaseOffset,
+    id : &str,
+    period : f32,
+    pos : ( i32, i32 ),
+    instance_seed : Option< u32 >,
+  ) -> f32
+  {
+    match offset
     {
       PhaseOffset::None => 0.0,
       PhaseOffset::Fixed( s ) => s,
       PhaseOffset::HashCoord =>
       {
-        let salt = hash_str( &anim.id );
+        let salt = hash_str( id );
         let raw = hash_coord( pos.0, pos.1, salt );
         let unit = ( raw as f32 ) / ( u32::MAX as f32 );
-        // Multiply by the animation's *natural* period so neighbouring tiles
-        // spread across the whole cycle, not just a tiny fraction of it.
-        let period = animation_duration_seconds( anim );
+        // Multiply by the *natural* period so neighbouring tiles spread
+        // across the whole cycle, not just a tiny fraction of it.
         unit * period
       },
       PhaseOffset::Linear { per_q, per_r } =>
@@ -204,13 +241,12 @@ mod private
         // don't have a per-instance seed; fall back to 0.0 so the
         // animation rides the master clock there.
         let Some( seed ) = instance_seed else { return 0.0 };
-        // Mix the seed and the animation id through `hash_coord`'s
-        // avalanche so neighbouring seeds (1, 2, 3 ...) land on
-        // well-separated phases — XOR alone leaves the upper bits
-        // unchanged and collapses unit-magnitude differences.
-        let mixed = hash_coord( seed as i32, 0, hash_str( &anim.id ) );
+        // Mix the seed and the id through `hash_coord`'s avalanche so
+        // neighbouring seeds (1, 2, 3 ...) land on well-separated
+        // phases — XOR alone leaves the upper bits unchanged and
+        // collapses unit-magnitude differences.
+        let mixed = hash_coord( seed as i32, 0, hash_str( id ) );
         let unit = ( mixed as f32 ) / ( u32::MAX as f32 );
-        let period = animation_duration_seconds( anim );
         unit * period
       },
     }
@@ -458,4 +494,5 @@ mod_interface::mod_interface!
   exposed use resolve_animation_frame;
   own use animation_duration_seconds;
   own use declared_phase_seconds;
+  own use effect_phase_seconds;
 }
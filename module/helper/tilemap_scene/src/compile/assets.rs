@@ -6,19 +6,23 @@
 //!   geometries, gradients, patterns, etc. are untouched).
 //! - One [`tilemap_renderer::assets::SpriteAsset`] per **unique** `SpriteRef(asset_id, frame_name)`
 //!   appearing anywhere in an object's layer stack.
+//! - A ladder of textured quads ([`tilemap_renderer::assets::GeometryAsset`]) per asset
+//!   drawn through `ViewportTiling::RepeatWorld`.
 //! - An [`crate::compile::ids::IdMap`] mapping string ids to the
 //!   allocated numeric handles, so [`crate::renderer::Renderer`] can
 //!   resolve draw calls at render time.
 
 mod private
 {
-  use tilemap_renderer::assets::{ Assets, ImageAsset, SpriteAsset };
+  use tilemap_renderer::assets::{ Assets, DataType, GeometryAsset, ImageAsset, Source, SpriteAsset };
   use crate::compile::error::CompileError;
   use crate::compile::ids::IdMap;
   use crate::compile::neighbors::dir_name;
   use crate::compile::resolver::AssetResolver;
+  use crate::compile::viewport::{ WORLD_QUAD_LODS, world_quad_tiles };
+  use crate::layer::TintBehaviour;
   use crate::resource::{ AnimationTiming, AssetKind, SpriteRef };
-  use crate::source::{ NeighborBitmaskSource, SpriteSource, Variant };
+  use crate::source::{ NeighborBitmaskSource, SpriteSource, Variant, ViewportTiling };
   use crate::spec::RenderSpec;
   use tilemap_renderer::types::{ asset, ResourceId };
   use rustc_hash::FxHashMap as HashMap;
@@ -37,6 +41,23 @@ mod private
     /// sprite's scene-anchor pivot instead of the owning object's
     /// normalized `pivot`.
     pub sprite_anchors : HashMap< ResourceId< asset::Sprite >, [ f32; 2 ] >,
+    /// Quads for every asset a `ViewportTiling::RepeatWorld` layer
+    /// draws, keyed by asset id.
+    pub world_quads : HashMap< String, WorldQuads >,
+  }
+
+  /// The quad ladder of one world-tiled asset: quad `lod` spans
+  /// [`world_quad_tiles`]`( lod )` copies of the image per side, with UVs
+  /// running past `1.0` so the texture's `Repeat` wrap tiles it.
+  #[ derive( Debug, Clone ) ]
+  pub struct WorldQuads
+  {
+    /// Image the quads sample.
+    pub image : ResourceId< asset::Image >,
+    /// One tile — the image's size — in world pixels.
+    pub tile_size : ( f32, f32 ),
+    /// Geometry per ladder step, `WORLD_QUAD_LODS` entries.
+    pub geometries : Vec< ResourceId< asset::Geometry > >,
   }
 
   /// Turn a `RenderSpec` into backend-ready assets.
@@ -50,6 +71,8 @@ mod private
   ///   missing asset id.
   /// - [`CompileError::InvalidFrameName`] / [`CompileError::OutOfRange`] if a
   ///   sprite reference names a frame that can't be resolved to a region.
+  /// - [`CompileError::UnsupportedSource`] / [`CompileError::UnsupportedAssetKind`]
+  ///   if a `RepeatWorld` layer's content is not a `Static` ref to a `Single` asset.
   pub fn compile_assets
   (
     spec : &RenderSpec,
@@ -76,7 +99,8 @@ mod private
 
     // Pass 2 — sprites.
     //
-    // Walk every object layer's sprite_source looking for `SpriteRef`s, AND
+    // Walk every object layer's sprite_source (and `Masked` tint mask)
+    // looking for `SpriteRef`s, AND
     // pre-expand every declared animation's frame list. Both paths converge
     // on `ensure_sprite_allocated`, which deduplicates by `(asset, frame)`.
     let mut sprites = Vec::new();
@@ -91,6 +115,10 @@ mod private
         for layer in layers
         {
           collect_sprite_refs( &layer.sprite_source, spec, &mut ids, &mut sprites )?;
+          if let TintBehaviour::Masked { mask, .. } = &layer.behaviour.tint
+          {
+            collect_sprite_refs( mask, spec, &mut ids, &mut sprites )?;
+          }
         }
       }
     }
@@ -110,6 +138,61 @@ mod private
       }
     }
 
+    // Pass 4 — world-tiled quads.
+    let mut geometries = Vec::new();
+    let mut world_quads : HashMap< String, WorldQuads > = HashMap::default();
+    for object in &spec.objects
+    {
+      for layers in object.states.values()
+      {
+        for layer in layers
+        {
+          let SpriteSource::ViewportTiled { content, tiling : ViewportTiling::RepeatWorld, .. } = &layer.sprite_source
+          else { continue };
+          let SpriteSource::Static( SpriteRef { asset : asset_id, .. } ) = content.as_ref()
+          else
+          {
+            return Err( CompileError::UnsupportedSource
+            {
+              object : object.id.clone(),
+              source_kind : "RepeatWorld content other than Static",
+            });
+          };
+          if world_quads.contains_key( asset_id ) { continue; }
+          let unresolved = || CompileError::UnresolvedRef
+          {
+            kind : "asset",
+            id : asset_id.clone(),
+            context : format!( "RepeatWorld layer in object {:?}", object.id ),
+          };
+          let asset = spec.assets.iter().find( | a | a.id == *asset_id ).ok_or_else( unresolved )?;
+          let image = ids.image( asset_id ).ok_or_else( unresolved )?;
+          let AssetKind::Single { size } = asset.kind
+          else
+          {
+            return Err( CompileError::UnsupportedAssetKind
+            {
+              asset : asset_id.clone(),
+              kind : "non-Single (RepeatWorld samples the whole image)",
+            });
+          };
+          let tile_size = ( size.0 as f32, size.1 as f32 );
+          let quads = WorldQuads
+          {
+            image,
+            tile_size,
+            geometries : ( 0..WORLD_QUAD_LODS ).map( | lod |
+            {
+              let id = ResourceId::new( geometries.len() as u32 );
+              geometries.push( world_quad_geometry( id, tile_size, world_quad_tiles( lod ) ) );
+              id
+            }).collect(),
+          };
+          world_quads.insert( asset_id.clone(), quads );
+        }
+      }
+    }
+
     Ok( CompiledAssets
     {
       assets : Assets
@@ -117,7 +200,7 @@ mod private
         fonts : Vec::new(),
         images,
         sprites,
-        geometries : Vec::new(),
+        geometries,
         gradients : Vec::new(),
         patterns : Vec::new(),
         clip_masks : Vec::new(),
@@ -125,9 +208,32 @@ mod private
       },
       ids,
       sprite_anchors,
+      world_quads,
     })
   }
 
+  /// A `tiles × tiles` quad of `tile_size` cells, bottom-left at the
+  /// origin, with one UV unit per tile.
+  fn world_quad_geometry
+  (
+    id : ResourceId< asset::Geometry >,
+    tile_size : ( f32, f32 ),
+    tiles : u32,
+  ) -> GeometryAsset
+  {
+    let n = tiles as f32;
+    let ( w, h ) = ( tile_size.0 * n, tile_size.1 * n );
+    let bytes = | v : &[ f32 ] | v.iter().flat_map( | f | f.to_le_bytes() ).collect::< Vec< u8 > >();
+    GeometryAsset
+    {
+      id,
+      positions : Source::Bytes( bytes( &[ 0.0, 0.0, w, 0.0, w, h, 0.0, h ] ) ),
+      uvs : Some( Source::Bytes( bytes( &[ 0.0, 0.0, n, 0.0, n, n, 0.0, n ] ) ) ),
+      indices : Some( Source::Bytes( vec![ 0, 1, 2, 0, 2, 3 ] ) ),
+      data_type : DataType::U8,
+    }
+  }
+
   /// Recursively collect `SpriteRef`s from any sprite source shape, allocating
   /// `SpriteAsset` entries on first encounter and deduplicating thereafter.
   fn collect_sprite_refs
@@ -164,12 +270,12 @@ mod private
             }
             collect_sprite_refs( fallback, spec, ids, sprites )?;
           },
-          NeighborBitmaskSource::ByAtlas { asset, layout : _ } =>
+          NeighborBitmaskSource::ByAtlas { asset, layout } =>
           {
-            // Layout is `Bitmask6` in 0.2.0 — 64 entries indexed by mask.
-            // Allocate a SpriteAsset for every mask value so frame-time
-            // lookup is a straight `ids.sprite(asset, &mask.to_string())`.
-            for mask in 0_u32..64
+            // One entry per mask value of the layout. Allocate a
+            // SpriteAsset for every one so frame-time lookup is a straight
+            // `ids.sprite(asset, &mask.to_string())`.
+            for mask in 0..layout.entries()
             {
               let name = mask.to_string();
               let sprite_ref = SpriteRef { asset : asset.clone(), frame : name };
@@ -192,11 +298,12 @@ mod private
       },
       SpriteSource::VertexCorners { patterns, asset } =>
       {
-        // Each pattern has a `{rot}` placeholder in 0..3; allocate all three
-        // rotations so the frame pass has a guaranteed lookup per triangle.
+        // Each pattern has a `{rot}` placeholder in 0..corner count (3 for
+        // hex, 4 for square); allocate every rotation so the frame pass has
+        // a guaranteed lookup per vertex.
         for pattern in patterns
         {
-          for rot in 0_u32..3
+          for rot in 0..pattern.corners.as_slice().len()
           {
             let frame_name = pattern.sprite_pattern.replace( "{rot}", &rot.to_string() );
             let sprite_ref = SpriteRef { asset : asset.clone(), frame : frame_name };
@@ -459,5 +566,6 @@ mod private
 mod_interface::mod_interface!
 {
   exposed use CompiledAssets;
+  exposed use WorldQuads;
   exposed use compile_assets;
 }
@@ -1,4 +1,4 @@
-//! Axial-hex → world-pixel conversion with the `tiles_tools` → `tilemap_renderer`
+//! Grid → world-pixel conversion with the `tiles_tools` → `tilemap_renderer`
 //! Y-axis flip baked in.
 //!
 //! Why this module exists: `tiles_tools::coordinates::pixel::Pixel` uses a
@@ -19,6 +19,8 @@ mod private
 {
   use tiles_tools::coordinates::hexagonal::{ Axial, Coordinate, Flat, Pointy };
   use tiles_tools::coordinates::pixel::Pixel;
+  use tiles_tools::coordinates::square::SquareCoord4;
+  use crate::pipeline::TilingStrategy;
 
   /// Axial `( q, r )` on a flat-top hex grid → world-pixel centre with Y-up.
   ///
@@ -64,6 +66,39 @@ mod private
     let sy = ch / 1.5;
     ( pixel.x() * sx, -pixel.y() * sy )
   }
+
+  /// Square-grid `( q, r )` (column, row) → world-pixel centre with Y-up.
+  ///
+  /// Shared by `Square4` and `Square8` — connectivity doesn't change
+  /// geometry. `grid_stride` is the cell pitch in X and Y.
+  #[ inline ]
+  #[ must_use ]
+  pub fn square_to_world_pixel( q : i32, r : i32, grid_stride : ( u32, u32 ) ) -> ( f32, f32 )
+  {
+    // tiles_tools' square grid is already Y-up, but scene rows grow
+    // downward like hex `r`, so the row negates on the way in.
+    let cell = SquareCoord4::new( q, -r );
+    ( cell.x as f32 * grid_stride.0 as f32, cell.y as f32 * grid_stride.1 as f32 )
+  }
+
+  /// World-pixel centre of grid cell `( q, r )` under `tiling`.
+  #[ inline ]
+  #[ must_use ]
+  pub fn cell_to_world_pixel
+  (
+    q : i32,
+    r : i32,
+    tiling : TilingStrategy,
+    grid_stride : ( u32, u32 ),
+  ) -> ( f32, f32 )
+  {
+    match tiling
+    {
+      TilingStrategy::HexFlatTop => hex_to_world_pixel_flat( q, r, grid_stride ),
+      TilingStrategy::HexPointyTop => hex_to_world_pixel_pointy( q, r, grid_stride ),
+      TilingStrategy::Square4 | TilingStrategy::Square8 => square_to_world_pixel( q, r, grid_stride ),
+    }
+  }
 }
 
 #[ cfg( test ) ]
@@ -113,10 +148,19 @@ mod tests
     let ( x1, _ ) = hex_to_world_pixel_pointy( 0, 1, ( 64, 72 ) );
     assert!( ( x1 - x0 ).abs() > 1.0, "expected row shift on pointy top, got x0={x0} x1={x1}" );
   }
+
+  #[ test ]
+  fn square_steps_by_stride_with_rows_growing_down()
+  {
+    assert_eq!( square_to_world_pixel( 2, 0, ( 32, 24 ) ), ( 64.0, 0.0 ) );
+    assert_eq!( square_to_world_pixel( 0, 3, ( 32, 24 ) ), ( 0.0, -72.0 ) );
+  }
 }
 
 mod_interface::mod_interface!
 {
   exposed use hex_to_world_pixel_flat;
   exposed use hex_to_world_pixel_pointy;
+  exposed use square_to_world_pixel;
+  exposed use cell_to_world_pixel;
 }
@@ -1,15 +1,17 @@
 //! Edge-anchored rendering helpers — canonicalisation, pixel midpoint +
 //! rotation, and the 4-bit `EdgeConnectedBitmask` computation (SPEC §5.9).
 //!
-//! An edge is shared by two hexes. Authors may declare `EdgePosition { hex, dir }`
+//! An edge is shared by two cells. Authors may declare `EdgePosition { hex, dir }`
 //! from either side; [`canonical_edge`] reduces both forms to the same
-//! `( canonical_hex, dir )` key so every edge emits once per frame.
+//! `( canonical_hex, dir )` key so every edge emits once per frame. On square
+//! tilings only the four orthogonal directions name edges — `Square8`
+//! diagonal neighbours touch at a corner, not along a side.
 
 mod private
 {
   use rustc_hash::FxHashMap as HashMap;
   use crate::anchor::EdgeDirection;
-  use crate::compile::coords::{ hex_to_world_pixel_flat, hex_to_world_pixel_pointy };
+  use crate::compile::coords::cell_to_world_pixel;
   use crate::compile::neighbors::{ dir_to_index, neighbor_offset_by_dir, VOID_ID };
   use crate::pipeline::TilingStrategy;
   use crate::snapshot::{ EdgeInstance, EdgePosition };
@@ -25,7 +27,7 @@ mod private
   /// Canonicalise an edge so that both sides reduce to the same key.
   ///
   /// Returns `None` when the direction is not valid for the tiling (e.g.
-  /// `E` / `W` on flat-top hex).
+  /// `E` / `W` on flat-top hex, diagonals on either square tiling).
   #[ must_use ]
   pub fn canonical_edge
   (
@@ -33,7 +35,7 @@ mod private
     tiling : TilingStrategy,
   ) -> Option< CanonicalEdge >
   {
-    let offset = neighbor_offset_by_dir( tiling, at.dir )?;
+    let offset = neighbor_offset_by_dir( edge_ring( tiling ), at.dir )?;
     let other_hex = ( at.hex.0 + offset.0, at.hex.1 + offset.1 );
 
     if at.hex <= other_hex
@@ -75,21 +77,22 @@ mod private
   ) -> Option< ( f32, f32 ) >
   {
     let ( hex_a, dir ) = canon;
-    let offset = neighbor_offset_by_dir( tiling, dir )?;
+    let offset = neighbor_offset_by_dir( edge_ring( tiling ), dir )?;
     let hex_b = ( hex_a.0 + offset.0, hex_a.1 + offset.1 );
-    let ca = hex_pixel( hex_a, tiling, grid_stride )?;
-    let cb = hex_pixel( hex_b, tiling, grid_stride )?;
+    let ca = cell_to_world_pixel( hex_a.0, hex_a.1, tiling, grid_stride );
+    let cb = cell_to_world_pixel( hex_b.0, hex_b.1, tiling, grid_stride );
     Some( ( ( ca.0 + cb.0 ) * 0.5, ( ca.1 + cb.1 ) * 0.5 ) )
   }
 
+  /// Direction ring that names edges — the tiling itself, except that
+  /// `Square8` edges use the orthogonal `Square4` ring.
   #[ inline ]
-  fn hex_pixel( pos : ( i32, i32 ), tiling : TilingStrategy, stride : ( u32, u32 ) ) -> Option< ( f32, f32 ) >
+  fn edge_ring( tiling : TilingStrategy ) -> TilingStrategy
   {
     match tiling
     {
-      TilingStrategy::HexFlatTop   => Some( hex_to_world_pixel_flat( pos.0, pos.1, stride ) ),
-      TilingStrategy::HexPointyTop => Some( hex_to_world_pixel_pointy( pos.0, pos.1, stride ) ),
-      TilingStrategy::Square4 | TilingStrategy::Square8 => None,
+      TilingStrategy::Square8 => TilingStrategy::Square4,
+      other => other,
     }
   }
 
@@ -99,7 +102,7 @@ mod private
   /// horizontally); this angle rotates the quad so its long axis matches
   /// the direction perpendicular to the edge normal. Three pairs
   /// (N↔S, NE↔SW, SE↔NW) collapse to three unique orientations — each
-  /// pair shares an angle modulo π.
+  /// pair shares an angle modulo π. Square edges step by π/2 from `N`.
   #[ must_use ]
   pub fn edge_rotation( dir : EdgeDirection, tiling : TilingStrategy ) -> f32
   {
@@ -128,7 +131,13 @@ mod private
         EdgeDirection::NW => 11.0 * PI / 6.0,
         EdgeDirection::N | EdgeDirection::S => 0.0,
       },
-      TilingStrategy::Square4 | TilingStrategy::Square8 => 0.0,
+      TilingStrategy::Square4 | TilingStrategy::Square8 => match dir
+      {
+        EdgeDirection::E => PI / 2.0,
+        EdgeDirection::S => PI,
+        EdgeDirection::W => 3.0 * PI / 2.0,
+        EdgeDirection::N | EdgeDirection::NE | EdgeDirection::SE | EdgeDirection::SW | EdgeDirection::NW => 0.0,
+      },
     }
   }
 
@@ -170,6 +179,9 @@ mod private
   /// Start / end vertex convention: walking the edge direction `dir` from
   /// the canonical hex toward the neighbour hex, "start" is the
   /// CCW-rotation (the vertex on the CCW side), "end" is the CW-rotation.
+  ///
+  /// The layout assumes three edges per vertex, so square tilings (four
+  /// per vertex) always yield `0`; validation rejects `EdgeHex` there.
   #[ must_use ]
   #[ allow( clippy::implicit_hasher ) ]
   #[ allow( clippy::similar_names ) ]
@@ -181,6 +193,7 @@ mod private
     edge_lookup : &HashMap< CanonicalEdge, &EdgeInstance >,
   ) -> u8
   {
+    if matches!( tiling, TilingStrategy::Square4 | TilingStrategy::Square8 ) { return 0; }
     let Some( idx ) = dir_to_index( tiling, canon.1 ) else { return 0 };
     // Six directions make a ring; ±1 mod 6 gives CCW/CW of the current dir.
     let ccw_idx = ( idx + 5 ) % 6;
@@ -271,7 +284,26 @@ mod private
         5 => EdgeDirection::NW,
         _ => return None,
       }),
-      TilingStrategy::Square4 | TilingStrategy::Square8 => None,
+      TilingStrategy::Square4 => Some( match idx
+      {
+        0 => EdgeDirection::N,
+        1 => EdgeDirection::E,
+        2 => EdgeDirection::S,
+        3 => EdgeDirection::W,
+        _ => return None,
+      }),
+      TilingStrategy::Square8 => Some( match idx
+      {
+        0 => EdgeDirection::N,
+        1 => EdgeDirection::NE,
+        2 => EdgeDirection::E,
+        3 => EdgeDirection::SE,
+        4 => EdgeDirection::S,
+        5 => EdgeDirection::SW,
+        6 => EdgeDirection::W,
+        7 => EdgeDirection::NW,
+        _ => return None,
+      }),
     }
   }
 }
@@ -307,6 +339,20 @@ mod tests
     assert!( ( edge_rotation( EdgeDirection::NE, t ) - PI / 3.0 ).abs() < 1e-5 );
     assert!( ( edge_rotation( EdgeDirection::S,  t ) - PI ).abs() < 1e-5 );
   }
+
+  #[ test ]
+  fn square8_diagonal_is_not_an_edge()
+  {
+    let t = TilingStrategy::Square8;
+    let diagonal = EdgePosition { hex : ( 0, 0 ), dir : EdgeDirection::NE };
+    assert!( canonical_edge( diagonal, t ).is_none() );
+    // Orthogonal sides still canonicalise across both cells.
+    let from_a = EdgePosition { hex : ( 0, 0 ), dir : EdgeDirection::E };
+    let from_b = EdgePosition { hex : ( 1, 0 ), dir : EdgeDirection::W };
+    assert_eq!( canonical_edge( from_a, t ), canonical_edge( from_b, t ) );
+    let ( x, y ) = edge_world_pixel( canonical_edge( from_a, t ).unwrap(), t, ( 32, 32 ) ).unwrap();
+    assert!( ( x - 16.0 ).abs() < 1e-5 && y.abs() < 1e-5, "E edge midpoint, got ({x}, {y})" );
+  }
 }
 
 mod_interface::mod_interface!
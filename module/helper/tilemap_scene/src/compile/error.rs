@@ -37,10 +37,9 @@ mod private
       /// Human-readable context (e.g. `"layer sprite_source in object \"grass\""`).
       context : String,
     },
-    /// An object uses an anchor not yet supported by the current slice.
-    ///
-    /// Slice 1 supports `Hex` only. `Edge`, `Vertex`, `Multihex`, `FreePos`,
-    /// `Viewport` land in follow-up slices.
+    /// An object is placed in a way its declared anchor does not support —
+    /// e.g. a `Multihex` object listed as a single-cell tile, or a
+    /// `FreePos` placement of an object that declares another anchor.
     UnsupportedAnchor
     {
       /// Owning object id.
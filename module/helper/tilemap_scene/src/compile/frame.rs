@@ -8,13 +8,24 @@
 
 mod private
 {
-  use tilemap_renderer::commands::{ Clear, RenderCommand, Sprite };
-  use crate::anchor::Anchor;
-  use crate::compile::animation::resolve_animation_frame;
+  use tilemap_renderer::commands::
+  {
+    AlphaPulseEffect,
+    Clear,
+    ColorShiftEffect,
+    DisplaceEffect,
+    Mesh,
+    RenderCommand,
+    SetTime,
+    Sprite,
+    SpriteEffects,
+  };
+  use crate::anchor::{ Anchor, SortYSource };
+  use crate::compile::animation::{ effect_phase_seconds, resolve_animation_frame };
   use crate::compile::assets::CompiledAssets;
   use crate::compile::camera::Camera;
   use crate::compile::conditions::evaluate_condition;
-  use crate::compile::coords::{ hex_to_world_pixel_flat, hex_to_world_pixel_pointy };
+  use crate::compile::coords::cell_to_world_pixel;
   use crate::compile::edges::
   {
     CanonicalEdge,
@@ -37,23 +48,27 @@ mod private
   use crate::compile::vertex::
   {
     canonicalize,
-    enumerate_triangles,
+    enumerate_vertices,
     find_matching_pattern,
     resolve_corners,
   };
   use crate::hash::hash_coord;
-  use crate::layer::{ LayerBehaviour, ObjectLayer };
+  use crate::layer::{ LayerBehaviour, MaskTint, ObjectLayer, TintBehaviour };
   use crate::object::Object;
   use crate::pipeline::{ SortMode, TilingStrategy };
-  use crate::resource::SpriteRef;
-  use crate::compile::viewport::{ tiled_positions, viewport_transform };
+  use crate::resource::{ Axis, EffectKind, SpriteRef, TintRef };
+  use crate::compile::viewport::{ tiled_positions, viewport_transform, world_quad };
   use crate::instance::{ Instance, Placement };
   use crate::scene::Scene;
   use crate::snapshot::{ EdgeInstance, EdgePosition, Tile };
   use crate::source::{ NeighborBitmaskSource, SpriteSource, VariantSelection, ViewportTiling };
   use crate::spec::RenderSpec;
-  use tilemap_renderer::types::Transform;
+  use tilemap_renderer::types::{ FillRef, Topology, Transform };
+  use core::cell::Cell;
   use rustc_hash::FxHashMap as HashMap;
+  use rustc_hash::FxHashSet as HashSet;
+  use crate::compile::vertex::VertexContext;
+  use crate::instance::InstanceHandle;
 
   /// Bundled per-frame context threaded into helper functions.
   ///
@@ -62,6 +77,9 @@ mod private
   struct FrameContext< 'a >
   {
     spec : &'a RenderSpec,
+    /// Runtime-only lookups that have no spec counterpart — currently
+    /// the player palette behind `MaskTint::TeamColor`.
+    scene : &'a Scene,
     compiled : &'a CompiledAssets,
     camera : &'a Camera,
     time_seconds : f32,
@@ -76,6 +94,12 @@ mod private
     /// Resolved global tint multiplier — `[1,1,1,1]` when `pipeline.global_tint`
     /// is `None`. Multiplied into every emitted `Sprite.tint`.
     global_tint : [ f32; 4 ],
+    /// `pipeline.layers` index by bucket id.
+    bucket_index : HashMap< &'a str, u32 >,
+    /// Set whenever an animation frame is resolved against the clock.
+    /// [`FrameEmitter`] clears it per owner to learn which owners must
+    /// be re-emitted when only the clock advances.
+    clock_read : Cell< bool >,
   }
 
   fn make_transform( sx : f32, sy : f32, zoom : f32 ) -> Transform
@@ -90,13 +114,6 @@ mod private
     }
   }
 
-  /// Multiply the alpha channel of a tint by a per-layer alpha factor.
-  #[ inline ]
-  fn tinted( [ r, g, b, a ] : [ f32; 4 ], alpha : f32 ) -> [ f32; 4 ]
-  {
-    [ r, g, b, a * alpha ]
-  }
-
   /// Shift the projected scene-anchor point so the sprite's anchor pixel
   /// lands exactly on the original scene position.
   ///
@@ -142,39 +159,16 @@ mod private
     ( sx - pivot.0 * w * zoom, sy - pivot.1 * h * zoom )
   }
 
-  fn hex_world_pixel
-  (
-    q : i32,
-    r : i32,
-    ctx : &FrameContext< '_ >,
-    object_id : &str,
-  ) -> Result< ( f32, f32 ), CompileError >
+  fn cell_world_pixel( q : i32, r : i32, ctx : &FrameContext< '_ > ) -> ( f32, f32 )
   {
-    match ctx.tiling
-    {
-      TilingStrategy::HexFlatTop   => Ok( hex_to_world_pixel_flat( q, r, ctx.grid_stride ) ),
-      TilingStrategy::HexPointyTop => Ok( hex_to_world_pixel_pointy( q, r, ctx.grid_stride ) ),
-      TilingStrategy::Square4 | TilingStrategy::Square8 =>
-        Err( CompileError::UnsupportedAnchor
-        {
-          object : object_id.to_owned(),
-          anchor : "Square (tiling strategy not implemented)",
-        }),
-    }
+    cell_to_world_pixel( q, r, ctx.tiling, ctx.grid_stride )
   }
 
-  /// Emit dual-mesh triangle sprites for every `VertexCorners` layer that
-  /// routes into `bucket_id`. One sprite per triangle whose canonical
-  /// corner tuple matches at least one pattern.
-  fn compile_vertex_pass
-  (
-    bucket_id : &str,
-    tiles : &[ Tile ],
-    ctx : &FrameContext< '_ >,
-  ) -> Result< Vec< ( f32, f32, Sprite ) >, CompileError >
+  /// Every `VertexCorners` layer in the spec, tagged with its bucket and
+  /// ordered by bucket — the per-vertex half of the dual-mesh pass.
+  fn vertex_layers< 'a >( ctx : &FrameContext< 'a > ) -> Vec< ( u32, &'a Object, &'a ObjectLayer ) >
   {
-    // Gather every VertexCorners layer that belongs in this bucket.
-    let mut layers : Vec< ( &Object, &ObjectLayer ) > = Vec::new();
+    let mut layers : Vec< ( u32, &Object, &ObjectLayer ) > = Vec::new();
     for object in &ctx.spec.objects
     {
       let Some( stack ) = object.states.get( &object.default_state )
@@ -185,75 +179,80 @@ mod private
         {
           continue;
         }
-        let effective = layer.pipeline_layer.as_deref().unwrap_or( object.global_layer.as_str() );
-        if effective == bucket_id
+        if let Some( bucket ) = layer_bucket( object, layer, ctx )
         {
-          layers.push( ( object, layer ) );
+          layers.push( ( bucket, object, layer ) );
         }
       }
     }
+    layers.sort_by_key( | &( bucket, _, _ ) | bucket );
+    layers
+  }
 
-    if layers.is_empty()
-    {
-      return Ok( Vec::new() );
-    }
-
-    let triangles = enumerate_triangles( tiles, ctx.tiling );
-    let mut out : Vec< ( f32, f32, Sprite ) > = Vec::new();
+  /// Emit the dual-mesh sprites of one vertex (hex triangle or square
+  /// quad): one per `VertexCorners` layer whose patterns match the
+  /// vertex's canonical corner tuple.
+  fn emit_vertex
+  (
+    vertex : &VertexContext,
+    layers : &[ ( u32, &Object, &ObjectLayer ) ],
+    ctx : &FrameContext< '_ >,
+    out : &mut Vec< OwnerEmit >,
+  ) -> Result< (), CompileError >
+  {
+    let raw_corners = resolve_corners( vertex, &ctx.tile_lookup, ctx.spec );
+    let ( canonical, rotation ) = canonicalize( raw_corners );
 
-    for tri in &triangles
+    for &( bucket, object, layer ) in layers
     {
-      let raw_corners = resolve_corners( tri, &ctx.tile_lookup, ctx.spec );
-      let ( canonical, rotation ) = canonicalize( raw_corners );
-
-      for ( object, layer ) in &layers
-      {
-        let SpriteSource::VertexCorners { patterns, asset } = &layer.sprite_source
-        else { continue };
-
-        let Some( pattern ) = find_matching_pattern( patterns, &canonical )
-        else { continue };
+      let SpriteSource::VertexCorners { patterns, asset } = &layer.sprite_source
+      else { continue };
 
-        let frame_name = pattern.sprite_pattern.replace( "{rot}", &rotation.to_string() );
-        let sprite_id = ctx.compiled.ids.sprite( asset, &frame_name )
-          .ok_or_else( || CompileError::UnresolvedRef
-          {
-            kind : "sprite",
-            id : format!( "{asset}:{frame_name}" ),
-            context : format!( "object {:?} VertexCorners rotation {rotation}", object.id ),
-          })?;
+      let Some( pattern ) = find_matching_pattern( patterns, &canonical )
+      else { continue };
 
-        // Triangle pixel centre: average the three corner hex pixel centres.
-        let mut sum_x = 0.0_f32;
-        let mut sum_y = 0.0_f32;
-        for corner in &tri.corners
+      let frame_name = pattern.sprite_pattern.replace( "{rot}", &rotation.to_string() );
+      let sprite_id = ctx.compiled.ids.sprite( asset, &frame_name )
+        .ok_or_else( || CompileError::UnresolvedRef
         {
-          let ( cx, cy ) = hex_world_pixel( corner.0, corner.1, ctx, &object.id )?;
-          sum_x += cx;
-          sum_y += cy;
-        }
-        let wx = sum_x / 3.0;
-        let wy = sum_y / 3.0;
-        let ( sx, sy ) = ctx.camera.project( ( wx, wy ) );
-        let ( sx, sy ) = apply_pivot( sx, sy, ctx.camera.zoom, object.pivot, sprite_id, ctx.compiled );
-        let transform = make_transform( sx, sy, ctx.camera.zoom );
+          kind : "sprite",
+          id : format!( "{asset}:{frame_name}" ),
+          context : format!( "object {:?} VertexCorners rotation {rotation}", object.id ),
+        })?;
 
-        out.push
-        ((
-          wx, wy,
-          Sprite
-          {
-            transform,
-            sprite : sprite_id,
-            tint : tinted( ctx.global_tint, layer.behaviour.alpha ),
-            blend : layer.behaviour.blend,
-            clip : None,
-          },
-        ));
+      // Vertex pixel centre: average the corner cell pixel centres.
+      let mut sum_x = 0.0_f32;
+      let mut sum_y = 0.0_f32;
+      for corner in &vertex.corners
+      {
+        let ( cx, cy ) = cell_world_pixel( corner.0, corner.1, ctx );
+        sum_x += cx;
+        sum_y += cy;
       }
+      let count = vertex.corners.len() as f32;
+      let wx = sum_x / count;
+      let wy = sum_y / count;
+      let ( sx, sy ) = ctx.camera.project( ( wx, wy ) );
+      let ( sx, sy ) = apply_pivot( sx, sy, ctx.camera.zoom, object.pivot, sprite_id, ctx.compiled );
+      let transform = make_transform( sx, sy, ctx.camera.zoom );
+
+      out.push( OwnerEmit
+      {
+        bucket,
+        world : ( wx, wy ),
+        sprite : Sprite
+        {
+          transform,
+          sprite : sprite_id,
+          tint : final_tint( ctx.global_tint, flat_tint( object, &layer.behaviour, ctx )?, layer.behaviour.alpha, None ),
+          blend : layer.behaviour.blend,
+          clip : None,
+          effects : layer_effects( object, &layer.behaviour, vertex.corners[ 0 ], None, ctx )?,
+        },
+      });
     }
 
-    Ok( out )
+    Ok( () )
   }
 
   /// Emit sprites for every `EdgeInstance` whose owning `Object` routes
@@ -304,6 +303,7 @@ mod private
         // typically these are Loop animations anyway, so it doesn't matter).
         // No instance seed available here; `PhaseOffset::Instance`
         // falls back to 0.0 on edge sprites by design.
+        ctx.clock_read.set( true );
         resolve_animation_frame( anim, ctx.time_seconds, 0.0, canon.0, None )
       },
       SpriteSource::Variant { variants, selection } =>
@@ -333,7 +333,7 @@ mod private
   /// Dispatches over all non-vertex sources: `Static`, `Animation`,
   /// `Variant`, `NeighborBitmask`. `NeighborCondition` is handled by
   /// [`emit_neighbor_condition`] directly (emits multiple sprites).
-  /// `VertexCorners` is handled by [`compile_vertex_pass`].
+  /// `VertexCorners` is handled by [`emit_vertex`].
   fn resolve_sprite_source
   (
     source : &SpriteSource,
@@ -359,6 +359,7 @@ mod private
         // threads `inst.state_entered_time` through for the correct
         // timing. No instance seed here, so `PhaseOffset::Instance`
         // falls back to 0.0.
+        ctx.clock_read.set( true );
         resolve_animation_frame( anim, ctx.time_seconds, 0.0, pos, None )
       },
       SpriteSource::Variant { variants, selection } =>
@@ -473,7 +474,8 @@ mod private
   /// Parse a `"#rrggbb"` or `"#rrggbbaa"` colour string into linear-ish
   /// `[f32; 4]`. Returns `None` on malformed input — caller decides whether
   /// to error or fall back.
-  fn parse_hex_rgba( s : &str ) -> Option< [ f32; 4 ] >
+  #[ must_use ]
+  pub fn parse_hex_rgba( s : &str ) -> Option< [ f32; 4 ] >
   {
     let s = s.strip_prefix( '#' )?;
     let hex_byte = | i : usize | u8::from_str_radix( s.get( i..i + 2 )?, 16 ).ok();
@@ -549,9 +551,13 @@ mod private
   }
 
   /// Per-bucket emit data. `sprites` is the sort-mode-applied world
-  /// layer; `screen_space` is the viewport pass output.
+  /// layer; `backdrops` and `screen_space` are the viewport pass output.
   pub struct BucketEmits
   {
+    /// World-tiled quads (`ViewportTiling::RepeatWorld`) of this
+    /// bucket's `Viewport` instances. Drawn first — they sit beneath the
+    /// bucket's world sprites.
+    pub backdrops : Vec< Mesh >,
     /// World-space sprites in this bucket, already sorted per the
     /// bucket's `SortMode`. Order is the on-screen draw order.
     pub sprites : Vec< Sprite >,
@@ -563,22 +569,165 @@ mod private
     pub sort : SortMode,
   }
 
+  /// One world-space sprite emitted by a single owner — an instance or
+  /// a dual-mesh vertex — tagged with the bucket it draws in.
+  #[ derive( Debug, Clone, Copy ) ]
+  pub struct OwnerEmit
+  {
+    /// Index into `pipeline.layers`.
+    pub bucket : u32,
+    /// World-space position; the sort key of sorted buckets.
+    pub world : ( f32, f32 ),
+    /// Backend-ready sprite.
+    pub sprite : Sprite,
+  }
+
+  /// Per-owner emission over one frame's context.
+  ///
+  /// Every world-space sprite belongs to exactly one owner: a hex / edge
+  /// / multihex / free instance, or a dual-mesh vertex. Emitting owner by
+  /// owner lets [`gather_frame_emits`] walk the whole scene while the
+  /// renderer re-emits only the owners a mutation touched. Neighbour-aware
+  /// sources read the `tiles` / `edges` the emitter was built from, so an
+  /// incremental caller passes just the cells around its dirty owners.
+  pub struct FrameEmitter< 'a >
+  {
+    ctx : FrameContext< 'a >,
+    vertex_layers : Vec< ( u32, &'a Object, &'a ObjectLayer ) >,
+  }
+
+  impl< 'a > FrameEmitter< 'a >
+  {
+    /// Build the frame context for `scene` viewed through `camera`.
+    ///
+    /// # Errors
+    ///
+    /// [`CompileError::UnresolvedRef`] when the global tint override or
+    /// `pipeline.global_tint` names an undeclared tint.
+    pub fn new
+    (
+      compiled : &'a CompiledAssets,
+      scene : &'a Scene,
+      camera : &'a Camera,
+      tiles : &'a [ Tile ],
+      edges : &'a [ EdgeInstance ],
+    ) -> Result< Self, CompileError >
+    {
+      let spec = scene.spec();
+      let viewport_size = spec.pipeline.viewport_size.unwrap_or( camera.viewport_size );
+      let seed = scene.seed();
+      let scene_seed = ( seed as u32 ) ^ ( ( seed >> 32 ) as u32 );
+      let global_tint = resolve_scene_global_tint( spec, scene )?;
+      let ctx = FrameContext
+      {
+        spec,
+        scene,
+        compiled,
+        camera,
+        time_seconds : scene.clock(),
+        tile_lookup : build_tile_lookup( tiles ),
+        edge_lookup : build_edge_lookup( edges, spec.pipeline.hex.tiling ),
+        tiling : spec.pipeline.hex.tiling,
+        grid_stride : spec.pipeline.hex.grid_stride,
+        viewport_size,
+        scene_seed,
+        global_tint,
+        bucket_index : spec.pipeline.layers.iter().enumerate()
+          .map( | ( i, bucket ) | ( bucket.id.as_str(), i as u32 ) )
+          .collect(),
+        clock_read : Cell::new( false ),
+      };
+      let vertex_layers = vertex_layers( &ctx );
+      Ok( Self { ctx, vertex_layers } )
+    }
+
+    /// Append the world-space sprites of instance `h` to `out`. Hidden,
+    /// stale and viewport-anchored instances emit nothing. Returns
+    /// `true` when the output depends on the scene clock.
+    ///
+    /// # Errors
+    ///
+    /// Unresolved sprite / animation / tint references, or an object
+    /// placed under an anchor / source it does not support.
+    pub fn emit_instance( &self, h : InstanceHandle, out : &mut Vec< OwnerEmit > ) -> Result< bool, CompileError >
+    {
+      let Some( inst ) = self.ctx.scene.instance( h ) else { return Ok( false ) };
+      if !inst.visible { return Ok( false ); }
+      self.ctx.clock_read.set( false );
+      match inst.placement
+      {
+        Placement::Hex { q, r } => emit_hex_instance( ( q, r ), inst, &self.ctx, out )?,
+        Placement::Multihex { anchor } => emit_multihex_instance( anchor, inst, &self.ctx, out )?,
+        Placement::Edge { hex, dir } => emit_edge_instance( h, EdgePosition { hex, dir }, inst, &self.ctx, out )?,
+        Placement::FreePos { x, y } => emit_free_instance( ( x, y ), inst, &self.ctx, out )?,
+        Placement::Viewport => {},
+      }
+      Ok( self.ctx.clock_read.get() )
+    }
+
+    /// Append the dual-mesh sprites of `vertex` to `out`. Returns `true`
+    /// when the output depends on the scene clock.
+    ///
+    /// # Errors
+    ///
+    /// [`CompileError::UnresolvedRef`] when a matched pattern names a
+    /// frame missing from its asset.
+    pub fn emit_vertex( &self, vertex : &VertexContext, out : &mut Vec< OwnerEmit > ) -> Result< bool, CompileError >
+    {
+      self.ctx.clock_read.set( false );
+      emit_vertex( vertex, &self.vertex_layers, &self.ctx, out )?;
+      Ok( self.ctx.clock_read.get() )
+    }
+
+    /// `true` when some `VertexCorners` layer routes into a bucket, i.e.
+    /// vertices emit anything at all.
+    #[ inline ]
+    #[ must_use ]
+    pub fn has_vertex_layers( &self ) -> bool { !self.vertex_layers.is_empty() }
+
+    /// `true` when at least one corner of `vertex` is an occupied cell of
+    /// the tiles this emitter was built from — the condition under which
+    /// the full pass enumerates the vertex.
+    #[ must_use ]
+    pub fn vertex_occupied( &self, vertex : &VertexContext ) -> bool
+    {
+      vertex.corners.iter().any( | c | self.ctx.tile_lookup.contains_key( c ) )
+    }
+
+    /// Output of the scene's viewport instances routed into bucket
+    /// `bucket`: world-tiled backdrop meshes, then screen-space sprites.
+    ///
+    /// # Errors
+    ///
+    /// Unresolved references, a viewport layer that does not use
+    /// `ViewportTiled`, or a `RepeatWorld` layer whose asset has no
+    /// compiled quads.
+    pub fn emit_viewport( &self, bucket : usize ) -> Result< ( Vec< Mesh >, Vec< Sprite > ), CompileError >
+    {
+      let mut backdrops = Vec::new();
+      let mut sprites = Vec::new();
+      if let Some( layer ) = self.ctx.spec.pipeline.layers.get( bucket )
+      {
+        compile_viewport_pass_scene( layer.id.as_str(), self.ctx.scene, &self.ctx, &mut backdrops, &mut sprites )?;
+      }
+      Ok( ( backdrops, sprites ) )
+    }
+  }
+
   /// Walk the scene and produce structured per-bucket emit data without
-  /// flattening to `RenderCommand`s. This is the shared core driving
-  /// both [`render_into`] (per-sprite emission, used by tests and
-  /// fall-back code paths) and the batching renderer.
+  /// flattening to `RenderCommand`s. Drives [`render_into`] (per-sprite
+  /// emission, used by tests) and is the reference the renderer's
+  /// incremental path must agree with.
+  ///
+  /// Within a bucket, sprites come in pass order — hex, multihex, vertex,
+  /// edge, free — then spawn order, before the bucket's sort applies.
   ///
   /// # Errors
   ///
   /// Same error surface as the legacy `compile_frame`: unresolved
-  /// sprite / animation / tint references, unsupported anchor kinds
-  /// (Multihex), unsupported asset kinds, etc.
-  ///
-  /// # Panics
-  ///
-  /// Panics in debug builds if `scene` exposes an instance handle for
-  /// which the underlying `Instance` is missing — only possible if the
-  /// scene's spatial indexes are inconsistent with its slotmap.
+  /// sprite / animation / tint references, an object placed under an
+  /// anchor it does not declare, neighbour-aware sources on multihex
+  /// objects, unsupported asset kinds, etc.
   pub fn gather_frame_emits
   (
     compiled : &CompiledAssets,
@@ -589,105 +738,36 @@ mod private
     let spec = scene.spec();
     let clear_color = spec.pipeline.clear_color.unwrap_or( [ 0.0, 0.0, 0.0, 0.0 ] );
 
-    // Reject Multihex anchors (polish item #9 — not implemented).
-    if let Some( &h ) = scene.multihex_instances().first()
+    let tiles = scene_tiles( scene );
+    let edges = scene_edges( scene );
+    let emitter = FrameEmitter::new( compiled, scene, camera, &tiles, &edges )?;
+
+    let mut emits : Vec< OwnerEmit > = Vec::new();
+    for &h in scene.hex_instances() { emitter.emit_instance( h, &mut emits )?; }
+    for &h in scene.multihex_instances() { emitter.emit_instance( h, &mut emits )?; }
+    if emitter.has_vertex_layers()
     {
-      let inst = scene.instance( h ).expect( "multihex handle live" );
-      return Err( CompileError::UnsupportedAnchor
+      for vertex in enumerate_vertices( &tiles, spec.pipeline.hex.tiling )
       {
-        object : spec.objects[ inst.object.index() as usize ].id.clone(),
-        anchor : "Multihex",
-      });
+        emitter.emit_vertex( &vertex, &mut emits )?;
+      }
     }
+    for &h in scene.edge_instances() { emitter.emit_instance( h, &mut emits )?; }
+    for &h in scene.free_instances() { emitter.emit_instance( h, &mut emits )?; }
 
-    let synthetic_tiles = build_scene_tiles( scene );
-    let synthetic_edges = build_scene_edges( scene, spec );
-
-    let viewport_size = spec.pipeline.viewport_size.unwrap_or( camera.viewport_size );
-    let edge_lookup = build_edge_lookup( &synthetic_edges, spec.pipeline.hex.tiling );
-    let seed = scene.seed();
-    let scene_seed = ( seed as u32 ) ^ ( ( seed >> 32 ) as u32 );
-    let global_tint = resolve_scene_global_tint( spec, scene )?;
-    let ctx = FrameContext
+    let mut draws : Vec< Vec< ( f32, f32, Sprite ) > > = vec![ Vec::new(); spec.pipeline.layers.len() ];
+    for e in emits
     {
-      spec,
-      compiled,
-      camera,
-      time_seconds : scene.clock(),
-      tile_lookup : build_tile_lookup( &synthetic_tiles ),
-      edge_lookup,
-      tiling : spec.pipeline.hex.tiling,
-      grid_stride : spec.pipeline.hex.grid_stride,
-      viewport_size,
-      scene_seed,
-      global_tint,
-    };
+      draws[ e.bucket as usize ].push( ( e.world.0, e.world.1, e.sprite ) );
+    }
 
     let mut buckets = Vec::with_capacity( spec.pipeline.layers.len() );
-
-    for bucket in &spec.pipeline.layers
+    for ( i, ( bucket, mut draws ) ) in spec.pipeline.layers.iter().zip( draws ).enumerate()
     {
-      let mut draws : Vec< ( f32, f32, Sprite ) > = Vec::new();
-
-      for &handle in scene.hex_instances()
-      {
-        let inst = scene.instance( handle ).expect( "hex handle live" );
-        if !inst.visible { continue; }
-
-        let object = &spec.objects[ inst.object.index() as usize ];
-        match object.anchor
-        {
-          Anchor::Hex => {},
-          Anchor::Multihex { .. } => return Err( CompileError::UnsupportedAnchor
-          {
-            object : object.id.clone(),
-            anchor : "Multihex",
-          }),
-          _ => continue,
-        }
-
-        let state_name = scene.state_name( inst.state ).ok_or_else( || CompileError::MissingDefaultState
-        {
-          object : object.id.clone(),
-        })?;
-        let stack = object.states.get( state_name ).ok_or_else( || CompileError::MissingDefaultState
-        {
-          object : object.id.clone(),
-        })?;
-
-        let Placement::Hex { q, r } = inst.placement else { continue };
-
-        for layer in stack
-        {
-          let effective = layer.pipeline_layer.as_deref().unwrap_or( object.global_layer.as_str() );
-          if effective != bucket.id { continue; }
-
-          let layer_draws = compile_instance_layer( object, layer, ( q, r ), inst, &ctx )?;
-          draws.extend( layer_draws );
-        }
-      }
-
-      draws.extend( compile_vertex_pass( bucket.id.as_str(), &synthetic_tiles, &ctx )? );
-      draws.extend( compile_edge_pass_scene( bucket.id.as_str(), scene, &ctx )? );
-      draws.extend( compile_free_pass_scene( bucket.id.as_str(), scene, &ctx )? );
-
       apply_sort_mode( &mut draws, bucket.sort );
-
       let sprites : Vec< Sprite > = draws.into_iter().map( | ( _, _, s ) | s ).collect();
-
-      // Viewport pass currently writes `ScreenSpaceSprite` commands to a
-      // `Vec<RenderCommand>`; unwrap them back into raw `Sprite`s so the
-      // renderer can decide whether to wrap in `ScreenSpaceSprite` or
-      // (eventually) batch viewport sprites too.
-      let mut tmp : Vec< RenderCommand > = Vec::new();
-      compile_viewport_pass_scene( bucket.id.as_str(), scene, &ctx, &mut tmp )?;
-      let screen_space : Vec< Sprite > = tmp.into_iter().filter_map( | c | match c
-      {
-        RenderCommand::ScreenSpaceSprite( s ) => Some( s ),
-        _ => None,
-      }).collect();
-
-      buckets.push( BucketEmits { sprites, screen_space, sort : bucket.sort } );
+      let ( backdrops, screen_space ) = emitter.emit_viewport( i )?;
+      buckets.push( BucketEmits { backdrops, sprites, screen_space, sort : bucket.sort } );
     }
 
     Ok( FrameEmits { clear_color, buckets } )
@@ -711,52 +791,84 @@ mod private
   {
     let emits = gather_frame_emits( compiled, scene, camera )?;
     out.push( RenderCommand::Clear( Clear { color : emits.clear_color } ) );
+    out.push( RenderCommand::SetTime( SetTime { seconds : scene.clock() } ) );
     for bucket in emits.buckets
     {
+      for m in bucket.backdrops { out.push( RenderCommand::Mesh( m ) ); }
       for s in bucket.sprites { out.push( RenderCommand::Sprite( s ) ); }
       for s in bucket.screen_space { out.push( RenderCommand::ScreenSpaceSprite( s ) ); }
     }
     Ok( () )
   }
 
+  /// The synthetic [`Tile`] at `pos`: visible hex-placed instances in
+  /// the cell's index order. `None` for a cell with no such instance.
+  fn cell_tile( scene : &Scene, pos : ( i32, i32 ) ) -> Option< Tile >
+  {
+    let spec = scene.spec();
+    let objects : Vec< String > = scene.instances_at_hex( pos.0, pos.1 )
+      .filter_map( | h | scene.instance( h ) )
+      .filter( | inst | inst.visible && matches!( inst.placement, Placement::Hex { .. } ) )
+      .map( | inst | spec.objects[ inst.object.index() as usize ].id.clone() )
+      .collect();
+    ( !objects.is_empty() ).then_some( Tile { pos, objects } )
+  }
+
   /// Build a synthetic `Vec<Tile>` from the scene's hex spatial index.
   ///
   /// Only hex-placed instances contribute (edge / multihex / free / viewport
-  /// stay in their own passes). Object ids are looked up via the spec; the
-  /// returned tiles are owned and used as the source for `tile_lookup`
-  /// in [`render_into`].
-  fn build_scene_tiles( scene : &Scene ) -> Vec< Tile >
+  /// stay in their own passes). Tiles come in first-spawn order of their
+  /// cells; the result backs `tile_lookup` and the vertex pass.
+  #[ must_use ]
+  pub fn scene_tiles( scene : &Scene ) -> Vec< Tile >
   {
-    let spec = scene.spec();
-    let mut by_cell : HashMap< ( i32, i32 ), Vec< String > > = HashMap::default();
+    let mut seen : HashSet< ( i32, i32 ) > = HashSet::default();
+    let mut tiles = Vec::new();
     for &handle in scene.hex_instances()
     {
-      let inst = scene.instance( handle ).expect( "hex handle live" );
-      if !inst.visible { continue; }
+      let Some( inst ) = scene.instance( handle ) else { continue };
       let Placement::Hex { q, r } = inst.placement else { continue };
-      let id = spec.objects[ inst.object.index() as usize ].id.clone();
-      by_cell.entry( ( q, r ) ).or_default().push( id );
+      if seen.insert( ( q, r ) )
+      {
+        tiles.extend( cell_tile( scene, ( q, r ) ) );
+      }
     }
-    by_cell
-      .into_iter()
-      .map( | ( pos, objects ) | Tile { pos, objects } )
-      .collect()
+    tiles
   }
 
-  /// Build a synthetic `Vec<EdgeInstance>` from the scene's edge handles.
-  fn build_scene_edges( scene : &Scene, spec : &RenderSpec ) -> Vec< EdgeInstance >
+  /// [`scene_tiles`] restricted to `cells` — the neighbourhood an
+  /// incremental re-emit reads. `cells` must not repeat.
+  pub fn scene_tiles_at( scene : &Scene, cells : impl IntoIterator< Item = ( i32, i32 ) > ) -> Vec< Tile >
   {
-    scene.edge_instances().iter().map( | &h |
+    cells.into_iter().filter_map( | pos | cell_tile( scene, pos ) ).collect()
+  }
+
+  fn edge_instance( scene : &Scene, h : InstanceHandle ) -> Option< EdgeInstance >
+  {
+    let inst = scene.instance( h )?;
+    let Placement::Edge { hex, dir } = inst.placement else { return None };
+    Some( EdgeInstance
     {
-      let inst = scene.instance( h ).expect( "edge handle live" );
-      let Placement::Edge { hex, dir } = inst.placement else { unreachable!() };
-      EdgeInstance
-      {
-        at : EdgePosition { hex, dir },
-        object : spec.objects[ inst.object.index() as usize ].id.clone(),
-        animation : None,
-      }
-    }).collect()
+      at : EdgePosition { hex, dir },
+      object : scene.spec().objects[ inst.object.index() as usize ].id.clone(),
+      animation : None,
+    })
+  }
+
+  /// Build a synthetic `Vec<EdgeInstance>` from the scene's edge handles.
+  #[ must_use ]
+  pub fn scene_edges( scene : &Scene ) -> Vec< EdgeInstance >
+  {
+    scene.edge_instances().iter().filter_map( | &h | edge_instance( scene, h ) ).collect()
+  }
+
+  /// Edge instances owned by a hex in `cells`. `cells` must not repeat.
+  pub fn scene_edges_at( scene : &Scene, cells : impl IntoIterator< Item = ( i32, i32 ) > ) -> Vec< EdgeInstance >
+  {
+    cells.into_iter()
+      .flat_map( | ( q, r ) | scene.instances_at_hex( q, r ) )
+      .filter_map( | h | edge_instance( scene, h ) )
+      .collect()
   }
 
   /// Resolve the effective global tint, honouring `Scene`'s runtime override.
@@ -764,13 +876,25 @@ mod private
   {
     let tint_ref = scene.global_tint().cloned().or_else( || spec.pipeline.global_tint.clone() );
     let Some( tint_ref ) = tint_ref else { return Ok( [ 1.0, 1.0, 1.0, 1.0 ] ); };
+    resolve_tint( spec, &tint_ref, || "scene.global_tint / pipeline.global_tint".into() )
+  }
+
+  /// Resolve a declared tint to its multiplier: the colour pulled toward
+  /// white by `1 - strength`.
+  fn resolve_tint
+  (
+    spec : &RenderSpec,
+    tint_ref : &TintRef,
+    context : impl FnOnce() -> String,
+  ) -> Result< [ f32; 4 ], CompileError >
+  {
     let id = &tint_ref.0;
     let tint = spec.tints.iter().find( | t | &t.id == id )
       .ok_or_else( || CompileError::UnresolvedRef
       {
         kind : "tint",
         id : id.clone(),
-        context : "scene.global_tint / pipeline.global_tint".into(),
+        context : context(),
       })?;
     let [ r, g, b, a ] = parse_hex_rgba( &tint.color ).ok_or_else( || CompileError::UnresolvedRef
     {
@@ -788,8 +912,9 @@ mod private
     ])
   }
 
-  /// Apply a bucket's sort mode to the draw list.
-  fn apply_sort_mode( draws : &mut [ ( f32, f32, Sprite ) ], sort : SortMode )
+  /// Apply a bucket's sort mode to the draw list. Every mode sorts
+  /// stably, so ties keep their incoming order.
+  pub fn apply_sort_mode( draws : &mut [ ( f32, f32, Sprite ) ], sort : SortMode )
   {
     use core::cmp::Ordering;
     let cmp_f = | a : f32, b : f32 | a.partial_cmp( &b ).unwrap_or( Ordering::Equal );
@@ -847,24 +972,22 @@ mod private
           context : format!( "object {:?} external slot {slot:?}", object.id ),
         })?;
       let ( q, r ) = pos;
-      let ( wx, wy ) = hex_world_pixel( q, r, ctx, &object.id )?;
+      let ( wx, wy ) = cell_world_pixel( q, r, ctx );
       let ( sx, sy ) = ctx.camera.project( ( wx, wy ) );
       let ( sx, sy ) = apply_pivot( sx, sy, ctx.camera.zoom, object.pivot, sprite_id, ctx.compiled );
       let transform = make_transform( sx, sy, ctx.camera.zoom );
-      return Ok( vec!
-      [
-        (
-          wx, wy,
-          Sprite
-          {
-            transform,
-            sprite : sprite_id,
-            tint : final_tint( ctx.global_tint, layer.behaviour.alpha, inst.tint ),
-            blend : layer.behaviour.blend,
-            clip : None,
-          },
-        ),
-      ]);
+      let body = Sprite
+      {
+        transform,
+        sprite : sprite_id,
+        tint : final_tint( ctx.global_tint, flat_tint( object, &layer.behaviour, ctx )?, layer.behaviour.alpha, inst.tint ),
+        blend : layer.behaviour.blend,
+        clip : None,
+        effects : layer_effects( object, &layer.behaviour, pos, Some( inst.instance_phase_seed ), ctx )?,
+      };
+      let mut out = Vec::with_capacity( 2 );
+      push_with_mask( &mut out, ( wx, wy ), body, object, &layer.behaviour, pos, inst, ctx )?;
+      return Ok( out );
     }
 
     let sprite_ref = resolve_sprite_source_with_phase
@@ -881,25 +1004,23 @@ mod private
       })?;
 
     let ( q, r ) = pos;
-    let ( wx, wy ) = hex_world_pixel( q, r, ctx, &object.id )?;
+    let ( wx, wy ) = cell_world_pixel( q, r, ctx );
     let ( sx, sy ) = ctx.camera.project( ( wx, wy ) );
     let ( sx, sy ) = apply_pivot( sx, sy, ctx.camera.zoom, object.pivot, sprite_id, ctx.compiled );
     let transform = make_transform( sx, sy, ctx.camera.zoom );
 
-    Ok( vec!
-    [
-      (
-        wx, wy,
-        Sprite
-        {
-          transform,
-          sprite : sprite_id,
-          tint : final_tint( ctx.global_tint, layer.behaviour.alpha, inst.tint ),
-          blend : layer.behaviour.blend,
-          clip : None,
-        },
-      ),
-    ])
+    let body = Sprite
+    {
+      transform,
+      sprite : sprite_id,
+      tint : final_tint( ctx.global_tint, flat_tint( object, &layer.behaviour, ctx )?, layer.behaviour.alpha, inst.tint ),
+      blend : layer.behaviour.blend,
+      clip : None,
+      effects : layer_effects( object, &layer.behaviour, pos, Some( inst.instance_phase_seed ), ctx )?,
+    };
+    let mut out = Vec::with_capacity( 2 );
+    push_with_mask( &mut out, ( wx, wy ), body, object, &layer.behaviour, pos, inst, ctx )?;
+    Ok( out )
   }
 
   /// Variant of `resolve_sprite_source` that threads per-instance overrides
@@ -931,6 +1052,7 @@ mod private
             id : anim_ref.0.clone(),
             context : format!( "object {:?} layer sprite_source", object.id ),
           })?;
+        ctx.clock_read.set( true );
         match phase_override
         {
           Some( phase ) =>
@@ -981,7 +1103,7 @@ mod private
   {
     let current_priority = tile_max_priority( tile, ctx.spec );
     let ( q, r ) = tile.pos;
-    let ( wx, wy ) = hex_world_pixel( q, r, ctx, &object.id )?;
+    let ( wx, wy ) = cell_world_pixel( q, r, ctx );
     let ( raw_sx, raw_sy ) = ctx.camera.project( ( wx, wy ) );
 
     let mut out = Vec::new();
@@ -1011,9 +1133,10 @@ mod private
         {
           transform,
           sprite : sprite_id,
-          tint : final_tint( ctx.global_tint, behaviour.alpha, inst.tint ),
+          tint : final_tint( ctx.global_tint, flat_tint( object, behaviour, ctx )?, behaviour.alpha, inst.tint ),
           blend : behaviour.blend,
           clip : None,
+          effects : layer_effects( object, behaviour, tile.pos, Some( inst.instance_phase_seed ), ctx )?,
         },
       ));
     }
@@ -1021,12 +1144,19 @@ mod private
   }
 
   /// Compose the per-sprite tint as
-  /// `global * layer_alpha (alpha-channel only) * instance_tint`.
+  /// `global * layer_tint * layer_alpha (alpha-channel only) * instance_tint`.
   #[ inline ]
-  fn final_tint( global : [ f32; 4 ], layer_alpha : f32, inst : Option< [ f32; 4 ] > ) -> [ f32; 4 ]
+  fn final_tint
+  (
+    global : [ f32; 4 ],
+    layer_tint : [ f32; 4 ],
+    layer_alpha : f32,
+    inst : Option< [ f32; 4 ] >,
+  ) -> [ f32; 4 ]
   {
     let [ gr, gg, gb, ga ] = global;
-    let composed = [ gr, gg, gb, ga * layer_alpha ];
+    let [ lr, lg, lb, la ] = layer_tint;
+    let composed = [ gr * lr, gg * lg, gb * lb, ga * la * layer_alpha ];
     match inst
     {
       None => composed,
@@ -1040,226 +1170,485 @@ mod private
     }
   }
 
-  /// Emit sprites for every Scene edge handle whose owning Object routes
-  /// into `bucket_id`. Mirrors `compile_edge_pass` but iterates Scene's
-  /// handle list and applies per-instance overrides.
-  fn compile_edge_pass_scene
+  /// Body multiplier of a layer's `TintBehaviour`. SPEC §6.1.
+  ///
+  /// Only `Flat` recolours the body; `Masked` leaves it untouched and
+  /// colours the separate overlay built by [`push_with_mask`].
+  fn flat_tint( object : &Object, behaviour : &LayerBehaviour, ctx : &FrameContext< '_ > ) -> Result< [ f32; 4 ], CompileError >
+  {
+    match &behaviour.tint
+    {
+      TintBehaviour::Flat( tint_ref ) =>
+        resolve_tint( ctx.spec, tint_ref, || format!( "object {:?} layer behaviour.tint", object.id ) ),
+      _ => Ok( [ 1.0, 1.0, 1.0, 1.0 ] ),
+    }
+  }
+
+  /// Push `body`, followed by its mask overlay when the layer is
+  /// `TintBehaviour::Masked`. SPEC §6.1.
+  ///
+  /// The overlay draws the mask sprite with the body's transform, blend
+  /// and effects, tinted by the mask colour — colour lands only where
+  /// the mask is opaque. Both share `( wx, wy )`, and sorting is stable,
+  /// so the overlay stays directly above its body. Team colour lives in
+  /// the per-instance tint, so units that differ only in owner still
+  /// share a batch.
+  ///
+  /// No overlay is drawn when the colour does not resolve: an unowned
+  /// instance, an owner without a palette entry, or `FogDependent`
+  /// (the scene carries no fog state).
+  #[ allow( clippy::too_many_arguments ) ]
+  fn push_with_mask
   (
-    bucket_id : &str,
-    scene : &Scene,
+    out : &mut Vec< ( f32, f32, Sprite ) >,
+    ( wx, wy ) : ( f32, f32 ),
+    body : Sprite,
+    object : &Object,
+    behaviour : &LayerBehaviour,
+    pos : ( i32, i32 ),
+    inst : &Instance,
     ctx : &FrameContext< '_ >,
-  ) -> Result< Vec< ( f32, f32, Sprite ) >, CompileError >
+  ) -> Result< (), CompileError >
   {
-    let mut out = Vec::new();
-    let mut seen : rustc_hash::FxHashSet< CanonicalEdge > = rustc_hash::FxHashSet::default();
-
-    for &handle in scene.edge_instances()
+    out.push( ( wx, wy, body ) );
+    let TintBehaviour::Masked { mask, tint } = &behaviour.tint else { return Ok( () ) };
+    let color = match tint
     {
-      let inst = scene.instance( handle ).expect( "edge handle live" );
-      if !inst.visible { continue; }
-      let Placement::Edge { hex, dir } = inst.placement else { unreachable!() };
-
-      let Some( canon ) = canonical_edge( EdgePosition { hex, dir }, ctx.tiling ) else { continue };
-      if !seen.insert( canon ) { continue; }
-
-      let object = &ctx.spec.objects[ inst.object.index() as usize ];
-      if !matches!( object.anchor, Anchor::Edge )
+      MaskTint::Ref( tint_ref ) =>
+        resolve_tint( ctx.spec, tint_ref, || format!( "object {:?} layer behaviour.tint.tint", object.id ) )?,
+      MaskTint::TeamColor => match inst.owner.and_then( | p | ctx.scene.player_color( p ) )
       {
-        return Err( CompileError::UnsupportedAnchor
-        {
-          object : object.id.clone(),
-          anchor : "Edge (object declares a different anchor)",
-        });
-      }
-
-      let state_name = scene.state_name( inst.state ).ok_or_else( || CompileError::MissingDefaultState
+        Some( c ) => c,
+        None => return Ok( () ),
+      },
+      MaskTint::FogDependent => return Ok( () ),
+    };
+    let sprite_ref = resolve_sprite_source_with_phase
+    (
+      mask, object, pos, inst.phase_offset, inst.state_entered_time,
+      Some( inst.instance_phase_seed ), ctx,
+    )?;
+    let sprite = ctx.compiled.ids.sprite( &sprite_ref.asset, &sprite_ref.frame )
+      .ok_or_else( || CompileError::UnresolvedRef
       {
-        object : object.id.clone(),
+        kind : "sprite",
+        id : format!( "{}:{}", sprite_ref.asset, sprite_ref.frame ),
+        context : format!( "object {:?} layer mask", object.id ),
       })?;
-      let stack = object.states.get( state_name ).ok_or_else( || CompileError::MissingDefaultState
+    out.push
+    ((
+      wx, wy,
+      Sprite
       {
-        object : object.id.clone(),
-      })?;
+        sprite,
+        tint : final_tint( ctx.global_tint, color, behaviour.alpha, inst.tint ),
+        ..body
+      },
+    ));
+    Ok( () )
+  }
 
-      for layer in stack
+  /// Resolve a layer's `effects` into the backend's per-sprite
+  /// [`SpriteEffects`]. SPEC §6.3.
+  ///
+  /// `pos` / `instance_seed` feed each effect's `PhaseOffset`, as for
+  /// animations. The backend has one slot per kind, so a later reference
+  /// of the same kind replaces an earlier one.
+  fn layer_effects
+  (
+    object : &Object,
+    behaviour : &LayerBehaviour,
+    pos : ( i32, i32 ),
+    instance_seed : Option< u32 >,
+    ctx : &FrameContext< '_ >,
+  ) -> Result< SpriteEffects, CompileError >
+  {
+    let mut out = SpriteEffects::default();
+    for effect_ref in &behaviour.effects
+    {
+      let effect = ctx.spec.effects.iter().find( | e | e.id == effect_ref.0 )
+        .ok_or_else( || CompileError::UnresolvedRef
+        {
+          kind : "effect",
+          id : effect_ref.0.clone(),
+          context : format!( "object {:?} layer behaviour.effects", object.id ),
+        })?;
+      let phase = effect_phase_seconds( effect, pos, instance_seed );
+      match &effect.kind
       {
-        let effective = layer.pipeline_layer.as_deref().unwrap_or( object.global_layer.as_str() );
-        if effective != bucket_id { continue; }
-
-        let sprite_ref = resolve_edge_sprite_source( &layer.sprite_source, object, canon, ctx )?;
-        let sprite_id = ctx.compiled.ids.sprite( &sprite_ref.asset, &sprite_ref.frame )
-          .ok_or_else( || CompileError::UnresolvedRef
-          {
-            kind : "sprite",
-            id : format!( "{}:{}", sprite_ref.asset, sprite_ref.frame ),
-            context : format!( "object {:?} edge layer", object.id ),
-          })?;
-
-        let Some( ( wx, wy ) ) = edge_world_pixel( canon, ctx.tiling, ctx.grid_stride )
-        else
+        EffectKind::VertexDisplace { axis, amplitude, frequency } =>
         {
-          return Err( CompileError::UnsupportedAnchor
-          {
-            object : object.id.clone(),
-            anchor : "Edge (direction not valid for tiling)",
-          });
-        };
-        let ( sx, sy ) = ctx.camera.project( ( wx, wy ) );
-        let ( sx, sy ) = apply_pivot( sx, sy, ctx.camera.zoom, object.pivot, sprite_id, ctx.compiled );
-
-        let transform = Transform
+          let axis = match axis { Axis::X => [ 1.0, 0.0 ], Axis::Y => [ 0.0, 1.0 ] };
+          out.displace = Some( DisplaceEffect { axis, amplitude : *amplitude, frequency : *frequency, phase } );
+        },
+        EffectKind::AlphaPulse { min, max, frequency } =>
         {
-          position : [ sx, sy ],
-          rotation : edge_rotation( canon.1, ctx.tiling ),
-          scale : [ ctx.camera.zoom, ctx.camera.zoom ],
-          skew : [ 0.0, 0.0 ],
-          depth : 0.0,
-        };
-
-        out.push
-        ((
-          wx, wy,
-          Sprite
+          out.alpha_pulse = Some( AlphaPulseEffect { min : *min, max : *max, frequency : *frequency, phase } );
+        },
+        EffectKind::ColorShift { target, amplitude, frequency } =>
+        {
+          let [ r, g, b, _ ] = parse_hex_rgba( target ).ok_or_else( || CompileError::UnresolvedRef
           {
-            transform,
-            sprite : sprite_id,
-            tint : final_tint( ctx.global_tint, layer.behaviour.alpha, inst.tint ),
-            blend : layer.behaviour.blend,
-            clip : None,
-          },
-        ));
+            kind : "effect color",
+            id : target.clone(),
+            context : format!( "effect {:?}", effect.id ),
+          })?;
+          out.color_shift = Some( ColorShiftEffect { target : [ r, g, b ], amplitude : *amplitude, frequency : *frequency, phase } );
+        },
       }
     }
     Ok( out )
   }
 
-  /// Emit sprites for every Scene free-pos handle. Mirrors `compile_free_pass`.
-  fn compile_free_pass_scene
+  /// Pipeline bucket a layer routes into — its `pipeline_layer`, else
+  /// the object's `global_layer`. `None` when no bucket has that id.
+  fn layer_bucket( object : &Object, layer : &ObjectLayer, ctx : &FrameContext< '_ > ) -> Option< u32 >
+  {
+    let effective = layer.pipeline_layer.as_deref().unwrap_or( object.global_layer.as_str() );
+    ctx.bucket_index.get( effective ).copied()
+  }
+
+  /// The layer stack of the instance's current state.
+  fn instance_stack< 'o >
   (
-    bucket_id : &str,
-    scene : &Scene,
+    object : &'o Object,
+    inst : &Instance,
     ctx : &FrameContext< '_ >,
-  ) -> Result< Vec< ( f32, f32, Sprite ) >, CompileError >
+  ) -> Result< &'o Vec< ObjectLayer >, CompileError >
   {
-    let mut out = Vec::new();
-    for &handle in scene.free_instances()
+    let state_name = ctx.scene.state_name( inst.state ).ok_or_else( || CompileError::MissingDefaultState
     {
-      let inst = scene.instance( handle ).expect( "free handle live" );
-      if !inst.visible { continue; }
-      let Placement::FreePos { x, y } = inst.placement else { unreachable!() };
+      object : object.id.clone(),
+    })?;
+    object.states.get( state_name ).ok_or_else( || CompileError::MissingDefaultState
+    {
+      object : object.id.clone(),
+    })
+  }
 
-      let object = &ctx.spec.objects[ inst.object.index() as usize ];
-      if !matches!( object.anchor, Anchor::FreePos )
+  /// Tag a layer's `( wx, wy, sprite )` draws with its bucket.
+  fn owned( bucket : u32, draws : Vec< ( f32, f32, Sprite ) > ) -> impl Iterator< Item = OwnerEmit >
+  {
+    draws.into_iter().map( move | ( wx, wy, sprite ) | OwnerEmit { bucket, world : ( wx, wy ), sprite } )
+  }
+
+  /// Emit the layers of one hex-placed instance at `pos`.
+  fn emit_hex_instance
+  (
+    pos : ( i32, i32 ),
+    inst : &Instance,
+    ctx : &FrameContext< '_ >,
+    out : &mut Vec< OwnerEmit >,
+  ) -> Result< (), CompileError >
+  {
+    let object = &ctx.spec.objects[ inst.object.index() as usize ];
+    match object.anchor
+    {
+      Anchor::Hex => {},
+      Anchor::Multihex { .. } => return Err( CompileError::UnsupportedAnchor
+      {
+        object : object.id.clone(),
+        anchor : "Multihex (placed as a single-cell tile)",
+      }),
+      _ => return Ok( () ),
+    }
+
+    for layer in instance_stack( object, inst, ctx )?
+    {
+      let Some( bucket ) = layer_bucket( object, layer, ctx ) else { continue };
+      out.extend( owned( bucket, compile_instance_layer( object, layer, pos, inst, ctx )? ) );
+    }
+    Ok( () )
+  }
+
+  /// The instance drawn on `canon` when several visible edge instances
+  /// share it: the first one indexed at the canonical hex, then at the
+  /// hex across the edge. Depends only on those two cells, so a renderer
+  /// re-emitting an edge's neighbourhood settles ties the same way a full
+  /// walk does.
+  fn edge_winner( canon : CanonicalEdge, ctx : &FrameContext< '_ > ) -> Option< InstanceHandle >
+  {
+    let across = neighbor_offset_by_dir( ctx.tiling, canon.1 )
+      .map( | ( dq, dr ) | ( canon.0.0 + dq, canon.0.1 + dr ) );
+    core::iter::once( canon.0 ).chain( across )
+      .flat_map( | ( q, r ) | ctx.scene.instances_at_hex( q, r ) )
+      .find( | &h | ctx.scene.instance( h ).is_some_and( | inst |
+      {
+        let Placement::Edge { hex, dir } = inst.placement else { return false };
+        inst.visible && canonical_edge( EdgePosition { hex, dir }, ctx.tiling ) == Some( canon )
+      }))
+  }
+
+  /// Emit the layers of one edge-placed instance. Mirrors
+  /// `compile_edge_pass` but applies per-instance overrides.
+  fn emit_edge_instance
+  (
+    handle : InstanceHandle,
+    at : EdgePosition,
+    inst : &Instance,
+    ctx : &FrameContext< '_ >,
+    out : &mut Vec< OwnerEmit >,
+  ) -> Result< (), CompileError >
+  {
+    let Some( canon ) = canonical_edge( at, ctx.tiling ) else { return Ok( () ) };
+    if edge_winner( canon, ctx ) != Some( handle ) { return Ok( () ); }
+
+    let object = &ctx.spec.objects[ inst.object.index() as usize ];
+    if !matches!( object.anchor, Anchor::Edge )
+    {
+      return Err( CompileError::UnsupportedAnchor
+      {
+        object : object.id.clone(),
+        anchor : "Edge (object declares a different anchor)",
+      });
+    }
+
+    for layer in instance_stack( object, inst, ctx )?
+    {
+      let Some( bucket ) = layer_bucket( object, layer, ctx ) else { continue };
+
+      let sprite_ref = resolve_edge_sprite_source( &layer.sprite_source, object, canon, ctx )?;
+      let sprite_id = ctx.compiled.ids.sprite( &sprite_ref.asset, &sprite_ref.frame )
+        .ok_or_else( || CompileError::UnresolvedRef
+        {
+          kind : "sprite",
+          id : format!( "{}:{}", sprite_ref.asset, sprite_ref.frame ),
+          context : format!( "object {:?} edge layer", object.id ),
+        })?;
+
+      let Some( ( wx, wy ) ) = edge_world_pixel( canon, ctx.tiling, ctx.grid_stride )
+      else
       {
         return Err( CompileError::UnsupportedAnchor
         {
           object : object.id.clone(),
-          anchor : "FreePos (object declares a different anchor)",
+          anchor : "Edge (direction not valid for tiling)",
         });
-      }
+      };
+      let ( sx, sy ) = ctx.camera.project( ( wx, wy ) );
+      let ( sx, sy ) = apply_pivot( sx, sy, ctx.camera.zoom, object.pivot, sprite_id, ctx.compiled );
 
-      let state_name = scene.state_name( inst.state ).ok_or_else( || CompileError::MissingDefaultState
+      let transform = Transform
       {
-        object : object.id.clone(),
-      })?;
-      let stack = object.states.get( state_name ).ok_or_else( || CompileError::MissingDefaultState
+        position : [ sx, sy ],
+        rotation : edge_rotation( canon.1, ctx.tiling ),
+        scale : [ ctx.camera.zoom, ctx.camera.zoom ],
+        skew : [ 0.0, 0.0 ],
+        depth : 0.0,
+      };
+
+      let body = Sprite
       {
-        object : object.id.clone(),
-      })?;
+        transform,
+        sprite : sprite_id,
+        tint : final_tint( ctx.global_tint, flat_tint( object, &layer.behaviour, ctx )?, layer.behaviour.alpha, inst.tint ),
+        blend : layer.behaviour.blend,
+        clip : None,
+        effects : layer_effects( object, &layer.behaviour, canon.0, Some( inst.instance_phase_seed ), ctx )?,
+      };
+      let mut drawn = Vec::with_capacity( 2 );
+      push_with_mask( &mut drawn, ( wx, wy ), body, object, &layer.behaviour, canon.0, inst, ctx )?;
+      out.extend( owned( bucket, drawn ) );
+    }
+    Ok( () )
+  }
 
-      for layer in stack
+  /// Emit the layers of one free-pos instance at world point `( x, y )`.
+  fn emit_free_instance
+  (
+    ( x, y ) : ( f32, f32 ),
+    inst : &Instance,
+    ctx : &FrameContext< '_ >,
+    out : &mut Vec< OwnerEmit >,
+  ) -> Result< (), CompileError >
+  {
+    let object = &ctx.spec.objects[ inst.object.index() as usize ];
+    if !matches!( object.anchor, Anchor::FreePos )
+    {
+      return Err( CompileError::UnsupportedAnchor
       {
-        let effective = layer.pipeline_layer.as_deref().unwrap_or( object.global_layer.as_str() );
-        if effective != bucket_id { continue; }
+        object : object.id.clone(),
+        anchor : "FreePos (object declares a different anchor)",
+      });
+    }
 
-        match &layer.sprite_source
-        {
-          SpriteSource::NeighborBitmask { .. }
-          | SpriteSource::NeighborCondition { .. }
-          | SpriteSource::VertexCorners { .. }
-          | SpriteSource::EdgeConnectedBitmask { .. }
-          | SpriteSource::ViewportTiled { .. } =>
-          {
-            return Err( CompileError::UnsupportedSource
-            {
-              object : object.id.clone(),
-              source_kind : source_name( &layer.sprite_source ),
-            });
-          },
-          _ => {}
-        }
+    for layer in instance_stack( object, inst, ctx )?
+    {
+      let Some( bucket ) = layer_bucket( object, layer, ctx ) else { continue };
 
-        // External slot resolution for free-pos.
-        if let SpriteSource::External { slot } = &layer.sprite_source
+      match &layer.sprite_source
+      {
+        SpriteSource::NeighborBitmask { .. }
+        | SpriteSource::NeighborCondition { .. }
+        | SpriteSource::VertexCorners { .. }
+        | SpriteSource::EdgeConnectedBitmask { .. }
+        | SpriteSource::ViewportTiled { .. } =>
         {
-          let Some( sprite_ref ) = inst.external_sprites.get( slot ) else { continue };
-          let sprite_id = ctx.compiled.ids.sprite( &sprite_ref.asset, &sprite_ref.frame )
-            .ok_or_else( || CompileError::UnresolvedRef
-            {
-              kind : "sprite",
-              id : format!( "{}:{}", sprite_ref.asset, sprite_ref.frame ),
-              context : format!( "object {:?} free-pos external slot {slot:?}", object.id ),
-            })?;
-          let ( wx, wy ) = ( x, y );
-          let ( sx, sy ) = ctx.camera.project( ( wx, wy ) );
-          let ( sx, sy ) = apply_pivot( sx, sy, ctx.camera.zoom, object.pivot, sprite_id, ctx.compiled );
-          let transform = make_transform( sx, sy, ctx.camera.zoom );
-          out.push((
-            wx, wy,
-            Sprite
-            {
-              transform,
-              sprite : sprite_id,
-              tint : final_tint( ctx.global_tint, layer.behaviour.alpha, inst.tint ),
-              blend : layer.behaviour.blend,
-              clip : None,
-            },
-          ));
-          continue;
-        }
+          return Err( CompileError::UnsupportedSource
+          {
+            object : object.id.clone(),
+            source_kind : source_name( &layer.sprite_source ),
+          });
+        },
+        _ => {}
+      }
 
-        let sprite_ref = resolve_sprite_source_with_phase
-        (
-          &layer.sprite_source, object, ( 0, 0 ), inst.phase_offset, inst.state_entered_time,
-          Some( inst.instance_phase_seed ), ctx,
-        )?;
+      // External slot resolution for free-pos.
+      if let SpriteSource::External { slot } = &layer.sprite_source
+      {
+        let Some( sprite_ref ) = inst.external_sprites.get( slot ) else { continue };
         let sprite_id = ctx.compiled.ids.sprite( &sprite_ref.asset, &sprite_ref.frame )
           .ok_or_else( || CompileError::UnresolvedRef
           {
             kind : "sprite",
             id : format!( "{}:{}", sprite_ref.asset, sprite_ref.frame ),
-            context : format!( "object {:?} free layer", object.id ),
+            context : format!( "object {:?} free-pos external slot {slot:?}", object.id ),
           })?;
-
         let ( wx, wy ) = ( x, y );
         let ( sx, sy ) = ctx.camera.project( ( wx, wy ) );
         let ( sx, sy ) = apply_pivot( sx, sy, ctx.camera.zoom, object.pivot, sprite_id, ctx.compiled );
         let transform = make_transform( sx, sy, ctx.camera.zoom );
+        let body = Sprite
+        {
+          transform,
+          sprite : sprite_id,
+          tint : final_tint( ctx.global_tint, flat_tint( object, &layer.behaviour, ctx )?, layer.behaviour.alpha, inst.tint ),
+          blend : layer.behaviour.blend,
+          clip : None,
+          effects : layer_effects( object, &layer.behaviour, ( 0, 0 ), Some( inst.instance_phase_seed ), ctx )?,
+        };
+        let mut drawn = Vec::with_capacity( 2 );
+        push_with_mask( &mut drawn, ( wx, wy ), body, object, &layer.behaviour, ( 0, 0 ), inst, ctx )?;
+        out.extend( owned( bucket, drawn ) );
+        continue;
+      }
 
-        out.push
-        ((
-          wx, wy,
-          Sprite
-          {
-            transform,
-            sprite : sprite_id,
-            tint : final_tint( ctx.global_tint, layer.behaviour.alpha, inst.tint ),
-            blend : layer.behaviour.blend,
-            clip : None,
-          },
-        ));
+      let sprite_ref = resolve_sprite_source_with_phase
+      (
+        &layer.sprite_source, object, ( 0, 0 ), inst.phase_offset, inst.state_entered_time,
+        Some( inst.instance_phase_seed ), ctx,
+      )?;
+      let sprite_id = ctx.compiled.ids.sprite( &sprite_ref.asset, &sprite_ref.frame )
+        .ok_or_else( || CompileError::UnresolvedRef
+        {
+          kind : "sprite",
+          id : format!( "{}:{}", sprite_ref.asset, sprite_ref.frame ),
+          context : format!( "object {:?} free layer", object.id ),
+        })?;
+
+      let ( wx, wy ) = ( x, y );
+      let ( sx, sy ) = ctx.camera.project( ( wx, wy ) );
+      let ( sx, sy ) = apply_pivot( sx, sy, ctx.camera.zoom, object.pivot, sprite_id, ctx.compiled );
+      let transform = make_transform( sx, sy, ctx.camera.zoom );
+
+      let body = Sprite
+      {
+        transform,
+        sprite : sprite_id,
+        tint : final_tint( ctx.global_tint, flat_tint( object, &layer.behaviour, ctx )?, layer.behaviour.alpha, inst.tint ),
+        blend : layer.behaviour.blend,
+        clip : None,
+        effects : layer_effects( object, &layer.behaviour, ( 0, 0 ), Some( inst.instance_phase_seed ), ctx )?,
+      };
+      let mut drawn = Vec::with_capacity( 2 );
+      push_with_mask( &mut drawn, ( wx, wy ), body, object, &layer.behaviour, ( 0, 0 ), inst, ctx )?;
+      out.extend( owned( bucket, drawn ) );
+    }
+    Ok( () )
+  }
+
+  /// Emit the layers of one multihex instance. SPEC §3.4.
+  ///
+  /// One sprite per layer, positioned at the anchor cell. An instance is
+  /// culled only when none of its shape cells is on screen. The sort key
+  /// is the anchor's world Y, or the lowest cell Y of the shape under
+  /// [`SortYSource::BottomOfShape`].
+  fn emit_multihex_instance
+  (
+    anchor : ( i32, i32 ),
+    inst : &Instance,
+    ctx : &FrameContext< '_ >,
+    out : &mut Vec< OwnerEmit >,
+  ) -> Result< (), CompileError >
+  {
+    let object = &ctx.spec.objects[ inst.object.index() as usize ];
+    let Anchor::Multihex { shape } = &object.anchor
+    else
+    {
+      return Err( CompileError::UnsupportedAnchor
+      {
+        object : object.id.clone(),
+        anchor : "Multihex (object declares a different anchor)",
+      });
+    };
+
+    let cells : Vec< ( i32, i32 ) > = if shape.is_empty()
+    {
+      vec![ anchor ]
+    }
+    else
+    {
+      shape.iter().map( | &( dq, dr ) | ( anchor.0 + dq, anchor.1 + dr ) ).collect()
+    };
+    if !cells.iter().any( | &( q, r ) | cell_on_screen( q, r, ctx ) ) { return Ok( () ); }
+
+    let stack = instance_stack( object, inst, ctx )?;
+
+    let sort_y = match object.sort_y_source
+    {
+      SortYSource::Anchor => None,
+      SortYSource::BottomOfShape => cells.iter()
+        .map( | &( q, r ) | cell_world_pixel( q, r, ctx ).1 )
+        .reduce( f32::min ),
+    };
+
+    for layer in stack
+    {
+      let Some( bucket ) = layer_bucket( object, layer, ctx ) else { continue };
+
+      match &layer.sprite_source
+      {
+        SpriteSource::Static( _ )
+        | SpriteSource::Variant { .. }
+        | SpriteSource::Animation( _ )
+        | SpriteSource::External { .. } => {},
+        other => return Err( CompileError::UnsupportedSource
+        {
+          object : object.id.clone(),
+          source_kind : source_name( other ),
+        }),
+      }
+
+      for ( wx, wy, sprite ) in compile_instance_layer( object, layer, anchor, inst, ctx )?
+      {
+        out.push( OwnerEmit { bucket, world : ( wx, sort_y.unwrap_or( wy ) ), sprite } );
       }
     }
-    Ok( out )
+    Ok( () )
   }
 
-  /// Emit `ScreenSpaceSprite` commands for every Scene viewport handle.
+  /// `true` when the cell at `( q, r )` overlaps the viewport. The cell is
+  /// taken as a full grid stride around its centre on each side, which
+  /// leaves room for sprites that overhang their cell.
+  fn cell_on_screen( q : i32, r : i32, ctx : &FrameContext< '_ > ) -> bool
+  {
+    let ( sx, sy ) = ctx.camera.project( cell_world_pixel( q, r, ctx ) );
+    let hx = ctx.grid_stride.0 as f32 * ctx.camera.zoom;
+    let hy = ctx.grid_stride.1 as f32 * ctx.camera.zoom;
+    let ( vw, vh ) = ( ctx.viewport_size.0 as f32, ctx.viewport_size.1 as f32 );
+    sx + hx >= 0.0 && sx - hx <= vw && sy + hy >= 0.0 && sy - hy <= vh
+  }
+
+  /// Emit the layers of every Scene viewport handle routed into
+  /// `bucket_id`: `RepeatWorld` layers as one backdrop `Mesh` each, the
+  /// rest as screen-space sprites.
   fn compile_viewport_pass_scene
   (
     bucket_id : &str,
     scene : &Scene,
     ctx : &FrameContext< '_ >,
-    commands : &mut Vec< RenderCommand >,
+    backdrops : &mut Vec< Mesh >,
+    sprites : &mut Vec< Sprite >,
   ) -> Result< (), CompileError >
   {
     for &handle in scene.viewport_instances()
@@ -1301,6 +1690,38 @@ mod private
           });
         };
 
+        if let ViewportTiling::RepeatWorld = vtiling
+        {
+          let SpriteSource::Static( SpriteRef { asset, .. } ) = content.as_ref()
+          else
+          {
+            return Err( CompileError::UnsupportedSource
+            {
+              object : object.id.clone(),
+              source_kind : "RepeatWorld content other than Static",
+            });
+          };
+          let quads = ctx.compiled.world_quads.get( asset ).ok_or_else( || CompileError::UnresolvedRef
+          {
+            kind : "world quad",
+            id : asset.clone(),
+            context : format!( "object {:?} RepeatWorld layer", object.id ),
+          })?;
+          let ( lod, transform ) = world_quad( quads.tile_size, ctx.camera );
+          let tint = final_tint( ctx.global_tint, flat_tint( object, &layer.behaviour, ctx )?, layer.behaviour.alpha, inst.tint );
+          backdrops.push( Mesh
+          {
+            transform,
+            geometry : quads.geometries[ lod as usize ],
+            fill : FillRef::Solid( tint ),
+            texture : Some( quads.image ),
+            topology : Topology::TriangleList,
+            blend : layer.behaviour.blend,
+            clip : None,
+          });
+          continue;
+        }
+
         let sprite_ref = resolve_sprite_source( content, object, ( 0, 0 ), ctx )?;
         let sprite_id = ctx.compiled.ids.sprite( &sprite_ref.asset, &sprite_ref.frame )
           .ok_or_else( || CompileError::UnresolvedRef
@@ -1314,6 +1735,8 @@ mod private
           .find( | s | s.id == sprite_id )
           .map_or( ( 1.0, 1.0 ), | s | ( s.region[ 2 ], s.region[ 3 ] ) );
 
+        let effects = layer_effects( object, &layer.behaviour, ( 0, 0 ), Some( inst.instance_phase_seed ), ctx )?;
+        let tint = final_tint( ctx.global_tint, flat_tint( object, &layer.behaviour, ctx )?, layer.behaviour.alpha, inst.tint );
         let is_repeat = matches!
         (
           vtiling,
@@ -1334,14 +1757,15 @@ mod private
               skew : [ 0.0, 0.0 ],
               depth : 0.0,
             };
-            commands.push( RenderCommand::ScreenSpaceSprite( Sprite
+            sprites.push( Sprite
             {
               transform,
               sprite : sprite_id,
-              tint : final_tint( ctx.global_tint, layer.behaviour.alpha, inst.tint ),
+              tint,
               blend : layer.behaviour.blend,
               clip : None,
-            }));
+              effects,
+            });
           }
         }
         else
@@ -1355,14 +1779,15 @@ mod private
               source_kind : "ViewportTiled (unsupported tiling)",
             });
           };
-          commands.push( RenderCommand::ScreenSpaceSprite( Sprite
+          sprites.push( Sprite
           {
             transform,
             sprite : sprite_id,
-            tint : final_tint( ctx.global_tint, layer.behaviour.alpha, inst.tint ),
+            tint,
             blend : layer.behaviour.blend,
             clip : None,
-          }));
+            effects,
+          });
         }
       }
     }
@@ -1373,7 +1798,15 @@ mod private
 mod_interface::mod_interface!
 {
   own use render_into;
+  own use parse_hex_rgba;
   own use gather_frame_emits;
   own use FrameEmits;
   own use BucketEmits;
+  own use FrameEmitter;
+  own use OwnerEmit;
+  own use apply_sort_mode;
+  own use scene_tiles;
+  own use scene_tiles_at;
+  own use scene_edges;
+  own use scene_edges_at;
 }
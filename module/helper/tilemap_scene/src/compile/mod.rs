@@ -10,7 +10,7 @@
 //!   (see `tiles_tools/src/coordinates/pixel.rs:8`); this crate's render
 //!   backends are Y-up (see `tilemap_renderer/src/lib.rs:22`). The flip
 //!   happens exactly once, in [`coords::hex_to_world_pixel_flat`] /
-//!   [`coords::hex_to_world_pixel_pointy`].
+//!   [`coords::hex_to_world_pixel_pointy`] / [`coords::square_to_world_pixel`].
 //! - **Asset resolution** — the spec declares asset paths; who loads the bytes
 //!   (disk, in-memory, browser fetch) is a caller concern. The
 //!   [`AssetResolver`] trait exposes that choice.
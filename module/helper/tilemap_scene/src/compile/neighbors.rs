@@ -1,8 +1,9 @@
-//! Hex-neighbour iteration, bitmask computation, and per-tile priority lookup.
+//! Grid-neighbour iteration, bitmask computation, and per-tile priority lookup.
 //!
 //! The canonical direction ordering is SPEC §2.3: flat-top runs clockwise
-//! from `N` at bit 0, pointy-top runs clockwise from `NE`. The bitmask
-//! produced by [`compute_neighbor_bitmask`] uses those indices.
+//! from `N` at bit 0, pointy-top runs clockwise from `NE`, both square
+//! tilings run clockwise from `N`. The bitmask produced by
+//! [`compute_neighbor_bitmask`] uses those indices.
 
 mod private
 {
@@ -40,19 +41,53 @@ mod private
     (  0, -1 ),
   ];
 
-  /// Axial offset from the current hex to its neighbour at direction index
-  /// `0..6` (SPEC §2.3 ordering).
+  /// `Square4` neighbour `( q, r )` offsets in SPEC §2.3 order. Rows grow
+  /// downward, so `N` is `r - 1`.
+  /// Index 0 = N, 1 = E, 2 = S, 3 = W.
+  pub const SQUARE4_NEIGHBORS : [ ( i32, i32 ); 4 ] =
+  [
+    (  0, -1 ),
+    (  1,  0 ),
+    (  0,  1 ),
+    ( -1,  0 ),
+  ];
+
+  /// `Square8` neighbour `( q, r )` offsets in SPEC §2.3 order.
+  /// Index 0 = N, 1 = NE, 2 = E, 3 = SE, 4 = S, 5 = SW, 6 = W, 7 = NW.
+  pub const SQUARE8_NEIGHBORS : [ ( i32, i32 ); 8 ] =
+  [
+    (  0, -1 ),
+    (  1, -1 ),
+    (  1,  0 ),
+    (  1,  1 ),
+    (  0,  1 ),
+    ( -1,  1 ),
+    ( -1,  0 ),
+    ( -1, -1 ),
+  ];
+
+  /// Every neighbour offset of the tiling, indexed by SPEC §2.3 direction.
+  /// The slice length is the bitmask width (6, 4 or 8).
   #[ inline ]
   #[ must_use ]
-  pub fn neighbor_offset( tiling : TilingStrategy, dir_index : usize ) -> Option< ( i32, i32 ) >
+  pub fn neighbor_offsets( tiling : TilingStrategy ) -> &'static [ ( i32, i32 ) ]
   {
-    let table : &[ ( i32, i32 ); 6 ] = match tiling
+    match tiling
     {
       TilingStrategy::HexFlatTop => &FLAT_NEIGHBORS,
       TilingStrategy::HexPointyTop => &POINTY_NEIGHBORS,
-      TilingStrategy::Square4 | TilingStrategy::Square8 => return None,
-    };
-    table.get( dir_index ).copied()
+      TilingStrategy::Square4 => &SQUARE4_NEIGHBORS,
+      TilingStrategy::Square8 => &SQUARE8_NEIGHBORS,
+    }
+  }
+
+  /// Offset from the current cell to its neighbour at SPEC §2.3 direction
+  /// index `dir_index`.
+  #[ inline ]
+  #[ must_use ]
+  pub fn neighbor_offset( tiling : TilingStrategy, dir_index : usize ) -> Option< ( i32, i32 ) >
+  {
+    neighbor_offsets( tiling ).get( dir_index ).copied()
   }
 
   /// Axial offset for a named [`EdgeDirection`] under the given tiling.
@@ -66,7 +101,7 @@ mod private
 
   /// Map an [`EdgeDirection`] to its bitmask index under the current tiling.
   /// Returns `None` for directions not defined by the tiling (e.g. `E`/`W`
-  /// on flat-top or `N`/`S` on pointy-top).
+  /// on flat-top, `N`/`S` on pointy-top, diagonals on `Square4`).
   #[ must_use ]
   pub fn dir_to_index( tiling : TilingStrategy, dir : EdgeDirection ) -> Option< usize >
   {
@@ -92,7 +127,25 @@ mod private
         EdgeDirection::NW => Some( 5 ),
         EdgeDirection::N | EdgeDirection::S => None,
       },
-      TilingStrategy::Square4 | TilingStrategy::Square8 => None,
+      TilingStrategy::Square4 => match dir
+      {
+        EdgeDirection::N => Some( 0 ),
+        EdgeDirection::E => Some( 1 ),
+        EdgeDirection::S => Some( 2 ),
+        EdgeDirection::W => Some( 3 ),
+        EdgeDirection::NE | EdgeDirection::SE | EdgeDirection::SW | EdgeDirection::NW => None,
+      },
+      TilingStrategy::Square8 => Some( match dir
+      {
+        EdgeDirection::N  => 0,
+        EdgeDirection::NE => 1,
+        EdgeDirection::E  => 2,
+        EdgeDirection::SE => 3,
+        EdgeDirection::S  => 4,
+        EdgeDirection::SW => 5,
+        EdgeDirection::W  => 6,
+        EdgeDirection::NW => 7,
+      }),
     }
   }
 
@@ -126,7 +179,8 @@ mod private
     tiles.iter().map( | t | ( t.pos, t ) ).collect()
   }
 
-  /// Compute a 6-bit neighbour bitmask for a tile at `pos`.
+  /// Compute the neighbour bitmask for a tile at `pos` — 6 bits on hex,
+  /// 4 on `Square4`, 8 on `Square8`.
   ///
   /// Bit `i` is 1 iff the neighbour at SPEC §2.3 direction `i` contains an
   /// object whose id is in `connects_with`. Off-map / empty neighbours
@@ -141,15 +195,8 @@ mod private
     tile_lookup : &HashMap< ( i32, i32 ), &Tile >,
   ) -> u8
   {
-    let table : &[ ( i32, i32 ); 6 ] = match tiling
-    {
-      TilingStrategy::HexFlatTop => &FLAT_NEIGHBORS,
-      TilingStrategy::HexPointyTop => &POINTY_NEIGHBORS,
-      TilingStrategy::Square4 | TilingStrategy::Square8 => return 0,
-    };
-
     let mut mask : u8 = 0;
-    for ( i, ( dq, dr ) ) in table.iter().enumerate()
+    for ( i, ( dq, dr ) ) in neighbor_offsets( tiling ).iter().enumerate()
     {
       let neighbour_pos = ( pos.0 + dq, pos.1 + dr );
       let connects = match tile_lookup.get( &neighbour_pos )
@@ -247,6 +294,9 @@ mod_interface::mod_interface!
   exposed use VOID_ID;
   exposed use FLAT_NEIGHBORS;
   exposed use POINTY_NEIGHBORS;
+  exposed use SQUARE4_NEIGHBORS;
+  exposed use SQUARE8_NEIGHBORS;
+  exposed use neighbor_offsets;
   exposed use neighbor_offset;
   exposed use neighbor_offset_by_dir;
   exposed use dir_to_index;
@@ -1,14 +1,15 @@
-//! Dual-mesh triangle enumeration and pattern matching.
+//! Dual-mesh vertex enumeration and pattern matching.
 //!
-//! Covers `SpriteSource::VertexCorners` — for every vertex of the hex grid
-//! where three hexes meet, emit a sprite based on the sorted tuple of
-//! corner terrain ids.
+//! Covers `SpriteSource::VertexCorners` — for every grid vertex, emit a
+//! sprite based on the sorted tuple of corner terrain ids. Three hexes meet
+//! at a hex vertex (a dual-mesh triangle); four squares meet at a square
+//! vertex (a dual-mesh quad).
 //!
 //! Two halves:
 //!
-//! - [`enumerate_triangles`] walks `scene.tiles` and yields each unique
-//!   triangle of the dual mesh exactly once (a triangle is shared by three
-//!   hexes; dedup via `HashSet<TriCoord>`).
+//! - [`enumerate_vertices`] walks `scene.tiles` and yields each unique
+//!   vertex exactly once (hex: dedup via `HashSet<TriCoord>`; square: keyed
+//!   by the north-west cell of the 2×2 block around the vertex).
 //! - [`canonicalize`] + [`find_matching_pattern`] implement the lexicographic
 //!   sort + wildcard specificity matching from SPEC §5.6 / §9.
 
@@ -24,23 +25,36 @@ mod private
   use crate::source::TriBlendPattern;
   use crate::spec::RenderSpec;
 
-  /// A triangle of the dual mesh together with its three hex corners.
+  /// A vertex of the dual mesh together with the cells meeting at it.
   ///
-  /// `corners` are in the order [`tiles_tools`] returns them from
-  /// `TriCoord::dual()` — used for deriving the pixel centre of the
-  /// triangle as the average of the three hex pixel centres.
+  /// Hex `corners` are in the order [`tiles_tools`] returns them from
+  /// `TriCoord::dual()`; square corners run clockwise from the north-west
+  /// cell. The vertex's pixel centre is the average of the corner cells'.
   #[ derive( Debug, Clone ) ]
-  pub struct TriangleContext
+  pub struct VertexContext
   {
-    /// The three corner hex positions in axial `( q, r )` coordinates.
-    pub corners : [ ( i32, i32 ); 3 ],
+    /// Corner cell positions in `( q, r )` coordinates — three for hex,
+    /// four for square.
+    pub corners : Vec< ( i32, i32 ) >,
   }
 
-  /// Enumerate every unique dual-mesh triangle touched by at least one tile
-  /// in the scene. Each triangle is yielded once even though three hexes
+  /// Corner count of one vertex under `tiling` (SPEC §2.1).
+  #[ inline ]
+  #[ must_use ]
+  pub fn vertex_corner_count( tiling : TilingStrategy ) -> usize
+  {
+    match tiling
+    {
+      TilingStrategy::HexFlatTop | TilingStrategy::HexPointyTop => 3,
+      TilingStrategy::Square4 | TilingStrategy::Square8 => 4,
+    }
+  }
+
+  /// Enumerate every unique dual-mesh vertex touched by at least one tile
+  /// in the scene. Each vertex is yielded once even though several cells
   /// share it.
   #[ must_use ]
-  pub fn enumerate_triangles( tiles : &[ Tile ], tiling : TilingStrategy ) -> Vec< TriangleContext >
+  pub fn enumerate_vertices( tiles : &[ Tile ], tiling : TilingStrategy ) -> Vec< VertexContext >
   {
     match tiling
     {
@@ -49,11 +63,34 @@ mod private
       TilingStrategy::HexPointyTop =>
         enumerate::< Pointy, FlatTopped >( tiles ),
       TilingStrategy::Square4 | TilingStrategy::Square8 =>
-        Vec::new(),   // unsupported — caller should already have rejected earlier
+        enumerate_square( tiles ),
+    }
+  }
+
+  /// Square dual mesh: every cell touches the four vertices at its corners.
+  /// A vertex is keyed by the north-west cell of the 2×2 block around it.
+  fn enumerate_square( tiles : &[ Tile ] ) -> Vec< VertexContext >
+  {
+    let mut seen : HashSet< ( i32, i32 ) > = HashSet::default();
+    let mut out = Vec::new();
+
+    for tile in tiles
+    {
+      let ( q, r ) = tile.pos;
+      for key in [ ( q - 1, r - 1 ), ( q, r - 1 ), ( q, r ), ( q - 1, r ) ]
+      {
+        if !seen.insert( key ) { continue; }
+        let ( kq, kr ) = key;
+        out.push( VertexContext
+        {
+          corners : vec![ ( kq, kr ), ( kq + 1, kr ), ( kq + 1, kr + 1 ), ( kq, kr + 1 ) ],
+        });
+      }
     }
+    out
   }
 
-  fn enumerate< HO, TO >( tiles : &[ Tile ] ) -> Vec< TriangleContext >
+  fn enumerate< HO, TO >( tiles : &[ Tile ] ) -> Vec< VertexContext >
   where
     HexCoordinate< Axial, HO > : ToDual< TriCoordinate< TO > >,
     TriCoordinate< TO > : ToDual< HexCoordinate< Axial, HO > > + core::hash::Hash + Eq + Clone,
@@ -67,77 +104,63 @@ mod private
       for tri in hex.dual()
       {
         if !seen.insert( tri ) { continue; }
-        let hex_corners = tri.dual();
-        let mut corners = [ ( 0_i32, 0_i32 ); 3 ];
-        for ( i, corner ) in hex_corners.iter().enumerate().take( 3 )
-        {
-          corners[ i ] = ( corner.q, corner.r );
-        }
-        out.push( TriangleContext { corners } );
+        let corners = tri.dual().iter().take( 3 ).map( | corner | ( corner.q, corner.r ) ).collect();
+        out.push( VertexContext { corners } );
       }
     }
     out
   }
 
-  /// Resolve the three corner "terrain" ids of a triangle against the scene,
-  /// using [`tile_terrain_id`] for each corner. Corners outside the scene
+  /// Resolve the corner "terrain" ids of a vertex against the scene, using
+  /// [`tile_terrain_id`] for each corner. Corners outside the scene
   /// resolve to [`VOID_ID`].
   #[ must_use ]
   #[ allow( clippy::implicit_hasher ) ]
   pub fn resolve_corners
   (
-    tri : &TriangleContext,
+    vertex : &VertexContext,
     tile_lookup : &HashMap< ( i32, i32 ), &Tile >,
     spec : &RenderSpec,
-  ) -> [ String; 3 ]
+  ) -> Vec< String >
   {
-    tri.corners.map( | pos |
+    vertex.corners.iter().map( | pos |
     {
-      match tile_lookup.get( &pos )
+      match tile_lookup.get( pos )
       {
         Some( t ) => tile_terrain_id( t, spec ).unwrap_or( VOID_ID ).to_owned(),
         None => VOID_ID.to_owned(),
       }
-    })
+    }).collect()
   }
 
-  /// Canonicalise three corner terrain ids: sort lexicographically so pattern
-  /// matching is insensitive to triangle rotation. Returns the sorted triple
-  /// and a `rotation` u8 in `0..3` capturing which original slot landed in
-  /// slot 0 of the canonical form (for `{rot}` sprite substitution).
+  /// Canonicalise corner terrain ids: sort lexicographically so pattern
+  /// matching is insensitive to vertex rotation. Returns the sorted ids and
+  /// a `rotation` u8 in `0..corner_count` capturing which original slot
+  /// landed in slot 0 of the canonical form (for `{rot}` sprite
+  /// substitution).
   #[ must_use ]
-  #[ allow( clippy::needless_pass_by_value ) ]
-  pub fn canonicalize( raw : [ String; 3 ] ) -> ( [ String; 3 ], u8 )
+  pub fn canonicalize( raw : Vec< String > ) -> ( Vec< String >, u8 )
   {
     // Pair each value with its original index, sort, then record the
     // permutation by reading out original indices in sorted order.
-    let mut indexed : [ ( usize, String ); 3 ] =
-    [
-      ( 0, raw[ 0 ].clone() ),
-      ( 1, raw[ 1 ].clone() ),
-      ( 2, raw[ 2 ].clone() ),
-    ];
+    let mut indexed : Vec< ( usize, String ) > = raw.into_iter().enumerate().collect();
     indexed.sort_by( | a, b | a.1.cmp( &b.1 ) );
 
     // Rotation: where did the original corner 0 end up?
-    // We report it modulo 3 — SPEC says rotation ∈ {0, 1, 2}. If the sort
-    // produces a non-cyclic permutation (e.g. a swap), we still collapse to
-    // the mod-3 index of the original-0 slot; that covers the common case
-    // of cyclic rotations and is a pragmatic default for the others.
+    // SPEC says rotation ∈ {0, 1, 2} for hex ({0..3} for square). If the
+    // sort produces a non-cyclic permutation (e.g. a swap), we still report
+    // the index of the original-0 slot; that covers the common case of
+    // cyclic rotations and is a pragmatic default for the others.
     let rotation = indexed.iter().position( | ( orig, _ ) | *orig == 0 ).unwrap_or( 0 ) as u8;
 
-    let sorted =
-    [
-      indexed[ 0 ].1.clone(),
-      indexed[ 1 ].1.clone(),
-      indexed[ 2 ].1.clone(),
-    ];
+    let sorted = indexed.into_iter().map( | ( _, id ) | id ).collect();
     ( sorted, rotation )
   }
 
-  /// Find the best-matching pattern from `patterns` against a canonicalised
-  /// corner triple. Matches use multiset-subset matching with `"*"` wildcards
-  /// absorbing any leftover canonical entries (SPEC §5.6).
+  /// Find the best-matching pattern from `patterns` against canonicalised
+  /// corner ids. Only patterns with the same corner count take part.
+  /// Matches use multiset-subset matching with `"*"` wildcards absorbing
+  /// any leftover canonical entries (SPEC §5.6).
   ///
   /// Resolution order (SPEC §9):
   /// 1. Specificity — fewer wildcards wins.
@@ -149,7 +172,7 @@ mod private
   pub fn find_matching_pattern< 'p >
   (
     patterns : &'p [ TriBlendPattern ],
-    canonical : &[ String; 3 ],
+    canonical : &[ String ],
   ) -> Option< &'p TriBlendPattern >
   {
     let mut best : Option< &TriBlendPattern > = None;
@@ -162,7 +185,7 @@ mod private
       {
         continue;
       }
-      let specificity = 3_i32 - wildcards_in( pattern );
+      let specificity = canonical.len() as i32 - wildcards_in( pattern );
       let better = match best
       {
         None => true,
@@ -182,15 +205,19 @@ mod private
     best
   }
 
-  /// A pattern matches a canonical triple when every concrete entry in the
+  /// A pattern matches a canonical tuple when every concrete entry in the
   /// pattern can be paired to a unique canonical entry of the same value;
   /// the remaining (unpaired) canonical slots are absorbed by `"*"`
   /// wildcards. Positional order is ignored — the canonical is already
   /// sorted, and wildcards can live anywhere in the pattern.
-  fn pattern_matches( pattern : &TriBlendPattern, canonical : &[ String; 3 ] ) -> bool
+  fn pattern_matches( pattern : &TriBlendPattern, canonical : &[ String ] ) -> bool
   {
-    let pat = [ &pattern.corners.0, &pattern.corners.1, &pattern.corners.2 ];
-    let mut used = [ false; 3 ];
+    let pat = pattern.corners.as_slice();
+    if pat.len() != canonical.len()
+    {
+      return false;
+    }
+    let mut used = vec![ false; canonical.len() ];
 
     for value in pat.iter().filter( | v | v.as_str() != "*" )
     {
@@ -210,15 +237,14 @@ mod private
       }
     }
     // Remaining canonical slots are implicitly covered by wildcards:
-    // the pattern has exactly `3 - pat_concrete_count` wildcards, and
+    // the pattern has exactly `len - pat_concrete_count` wildcards, and
     // that equals the number of unused canonical slots.
     true
   }
 
   fn wildcards_in( pattern : &TriBlendPattern ) -> i32
   {
-    let c = &pattern.corners;
-    [ &c.0, &c.1, &c.2 ].iter().filter( | s | s.as_str() == "*" ).count() as i32
+    pattern.corners.as_slice().iter().filter( | s | s.as_str() == "*" ).count() as i32
   }
 }
 
@@ -226,13 +252,13 @@ mod private
 mod tests
 {
   use super::private::*;
-  use crate::source::TriBlendPattern;
+  use crate::source::{ Corners, TriBlendPattern };
 
   fn pattern( a : &str, b : &str, c : &str, priority : i32, sprite : &str ) -> TriBlendPattern
   {
     TriBlendPattern
     {
-      corners : ( a.into(), b.into(), c.into() ),
+      corners : Corners::tri( a, b, c ),
       sprite_pattern : sprite.into(),
       priority,
       animation : None,
@@ -242,7 +268,7 @@ mod tests
   #[ test ]
   fn canonicalize_sorts_ids()
   {
-    let ( sorted, _rot ) = canonicalize( [ "water".into(), "grass".into(), "sand".into() ] );
+    let ( sorted, _rot ) = canonicalize( vec![ "water".into(), "grass".into(), "sand".into() ] );
     assert_eq!( sorted, [ "grass".to_string(), "sand".into(), "water".into() ] );
   }
 
@@ -280,12 +306,47 @@ mod tests
     let canonical = [ "grass".into(), "grass".into(), "water".into() ];
     assert!( find_matching_pattern( &patterns, &canonical ).is_none() );
   }
+
+  #[ test ]
+  fn quad_patterns_only_match_four_corner_vertices()
+  {
+    let quad = TriBlendPattern
+    {
+      corners : Corners::quad( "grass", "grass", "*", "*" ),
+      sprite_pattern : "quad_gg".into(),
+      priority : 0,
+      animation : None,
+    };
+    let patterns = [ pattern( "grass", "grass", "*", 0, "tri_gg" ), quad ];
+    let square = [ "grass".into(), "grass".into(), "water".into(), "water".into() ];
+    let found = find_matching_pattern( &patterns, &square );
+    assert!( matches!( found, Some( p ) if p.sprite_pattern == "quad_gg" ) );
+    let hex = [ "grass".into(), "grass".into(), "water".into() ];
+    let found = find_matching_pattern( &patterns, &hex );
+    assert!( matches!( found, Some( p ) if p.sprite_pattern == "tri_gg" ) );
+  }
+
+  #[ test ]
+  fn square_vertices_are_shared_by_four_cells()
+  {
+    use crate::pipeline::TilingStrategy;
+    use crate::snapshot::Tile;
+    // A 2×2 block touches 9 distinct vertices; only the centre one has
+    // all four corners inside the block.
+    let tiles : Vec< Tile > = [ ( 0, 0 ), ( 1, 0 ), ( 0, 1 ), ( 1, 1 ) ].into_iter()
+      .map( | pos | Tile { pos, objects : vec![ "grass".into() ] } )
+      .collect();
+    let vertices = enumerate_vertices( &tiles, TilingStrategy::Square4 );
+    assert_eq!( vertices.len(), 9 );
+    assert!( vertices.iter().any( | v | v.corners == [ ( 0, 0 ), ( 1, 0 ), ( 1, 1 ), ( 0, 1 ) ] ) );
+  }
 }
 
 mod_interface::mod_interface!
 {
-  exposed use TriangleContext;
-  exposed use enumerate_triangles;
+  exposed use VertexContext;
+  exposed use vertex_corner_count;
+  exposed use enumerate_vertices;
   exposed use resolve_corners;
   exposed use canonicalize;
   exposed use find_matching_pattern;
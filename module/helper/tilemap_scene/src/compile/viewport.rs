@@ -4,13 +4,20 @@
 //! Slice 4 covers `ViewportTiling::{ Center, Stretch, Fit }` — each produces
 //! exactly one [`tilemap_renderer::commands::RenderCommand::ScreenSpaceSprite`] per
 //! viewport instance. The tiled variants (`Repeat2D` / `RepeatX` / `RepeatY`)
-//! need a `Mesh` command with wrap-enabled UVs and are deferred.
+//! emit one sprite per tile covering the viewport. `RepeatWorld` draws one
+//! repeat-wrapped quad from a fixed ladder of sizes, placed by
+//! [`world_quad`].
 
 mod private
 {
+  use crate::compile::camera::Camera;
   use crate::source::{ ViewportAnchorPoint, ViewportTiling };
   use tilemap_renderer::types::Transform;
 
+  /// Number of quad sizes a `RepeatWorld` layer compiles to — see
+  /// [`world_quad_tiles`].
+  pub const WORLD_QUAD_LODS : u32 = 12;
+
   /// Compute the screen-space [`Transform`] for a viewport-anchored sprite.
   ///
   /// - `sprite_size` is the sprite's natural pixel dimensions (from its atlas
@@ -67,9 +74,44 @@ mod private
         // at origin is convenient for callers that want the common case.
         Some( make_transform( ( 0.0, 0.0 ), ( 1.0, 1.0 ) ) )
       },
+      // World-space tiling has no viewport-relative transform; see
+      // [`world_quad`].
+      ViewportTiling::RepeatWorld => None,
     }
   }
 
+  /// Tiles per side of the `lod`-th `RepeatWorld` quad: `2, 4, … 8192`.
+  #[ inline ]
+  #[ must_use ]
+  pub fn world_quad_tiles( lod : u32 ) -> u32 { 2 << lod }
+
+  /// Pick the `RepeatWorld` quad covering `camera`'s view and its
+  /// screen-space transform.
+  ///
+  /// The quad is `world_quad_tiles( lod )` tiles of `tile_size` world
+  /// pixels per side, its bottom-left corner snapped down to the tile
+  /// grid so the texture stays pinned to world `(0, 0)` while the camera
+  /// pans. Returns the smallest `lod` whose quad covers the view; views
+  /// wider than the largest quad get that quad and leave the rest bare.
+  #[ must_use ]
+  pub fn world_quad( tile_size : ( f32, f32 ), camera : &Camera ) -> ( u32, Transform )
+  {
+    let zoom = camera.zoom;
+    let ( tw, th ) = ( tile_size.0.max( 1.0 ), tile_size.1.max( 1.0 ) );
+    let ( cx, cy ) = camera.world_center;
+    let half_w = camera.viewport_size.0 as f32 * 0.5 / zoom;
+    let half_h = camera.viewport_size.1 as f32 * 0.5 / zoom;
+
+    let ox = ( ( cx - half_w ) / tw ).floor() * tw;
+    let oy = ( ( cy - half_h ) / th ).floor() * th;
+    let need = ( ( cx + half_w - ox ) / tw ).ceil().max( ( ( cy + half_h - oy ) / th ).ceil() );
+    let lod = ( 0..WORLD_QUAD_LODS )
+      .find( | &lod | world_quad_tiles( lod ) as f32 >= need )
+      .unwrap_or( WORLD_QUAD_LODS - 1 );
+
+    ( lod, make_transform( camera.project( ( ox, oy ) ), ( zoom, zoom ) ) )
+  }
+
   /// Enumerate screen-space positions for each tile in a `Repeat*` viewport
   /// layout. Coordinates follow the Y-up convention (`(0, 0)` = viewport's
   /// bottom-left); see [`anchor_position`].
@@ -179,6 +221,7 @@ mod private
 mod tests
 {
   use super::private::*;
+  use crate::compile::camera::Camera;
   use crate::source::{ ViewportAnchorPoint, ViewportTiling };
 
   #[ test ]
@@ -259,6 +302,36 @@ mod tests
     }
   }
 
+  #[ test ]
+  fn world_quad_snaps_to_the_tile_grid_and_covers_the_view()
+  {
+    let tile = ( 64.0, 32.0 );
+    let camera = Camera { world_center : ( 100.0, 10.0 ), zoom : 1.0, viewport_size : ( 800, 600 ) };
+    let ( lod, t ) = world_quad( tile, &camera );
+    // View spans world x -300..500, y -290..310 → origin ( -320, -320 ).
+    assert!( ( t.position[ 0 ] + 20.0 ).abs() < 1e-5 );
+    assert!( ( t.position[ 1 ] + 30.0 ).abs() < 1e-5 );
+    assert!( ( t.scale[ 0 ] - 1.0 ).abs() < 1e-5 );
+    // 20 rows of 32 px are needed; 32 is the smallest ladder size.
+    assert_eq!( world_quad_tiles( lod ), 32 );
+
+    // Panning by one whole tile moves the origin, not the pattern.
+    let panned = Camera { world_center : ( 164.0, 10.0 ), ..camera };
+    let ( _, t2 ) = world_quad( tile, &panned );
+    assert!( ( t2.position[ 0 ] - t.position[ 0 ] ).abs() < 1e-5 );
+  }
+
+  #[ test ]
+  fn world_quad_grows_as_the_camera_zooms_out()
+  {
+    let near = Camera { zoom : 1.0, ..Camera::default() };
+    let far = Camera { zoom : 0.125, ..Camera::default() };
+    let ( lod_near, _ ) = world_quad( ( 32.0, 32.0 ), &near );
+    let ( lod_far, t ) = world_quad( ( 32.0, 32.0 ), &far );
+    assert!( lod_far > lod_near );
+    assert!( ( t.scale[ 0 ] - 0.125 ).abs() < 1e-5 );
+  }
+
   #[ test ]
   fn fit_preserves_aspect()
   {
@@ -284,4 +357,7 @@ mod_interface::mod_interface!
   exposed use viewport_transform;
   exposed use tiled_positions;
   exposed use anchor_position;
+  exposed use world_quad;
+  exposed use world_quad_tiles;
+  exposed use WORLD_QUAD_LODS;
 }
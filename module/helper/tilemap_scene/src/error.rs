@@ -85,6 +85,8 @@ mod private
       kind : &'static str,
       /// The duplicated id.
       id : String,
+      /// Spec path of the repeated declaration (e.g. `assets["terrain"]`).
+      path : String,
     },
     /// A reference points to an id that was not declared in this spec.
     UnresolvedRef
@@ -93,8 +95,9 @@ mod private
       kind : &'static str,
       /// The referenced id that could not be resolved.
       id : String,
-      /// Human-readable location where the reference appeared.
-      context : String,
+      /// Spec path where the reference appeared (e.g.
+      /// `objects["grass"].states["default"][0].sprite_source`).
+      path : String,
     },
     /// A composite sprite source was nested inside another composite source.
     ///
@@ -107,15 +110,34 @@ mod private
       outer : &'static str,
       /// The inner composite source kind found in its leaf slot.
       inner : &'static str,
+      /// Spec path of the nested source.
+      path : String,
     },
     /// The spec requests a tiling strategy not supported by this implementation.
     ///
-    /// Version 0.2.0 implements `HexFlatTop` and `HexPointyTop` only; the
-    /// `Square4` / `Square8` values are reserved. This variant is declared
-    /// for the future SPEC §16 tiling check but is not yet constructed —
-    /// square specs currently pass [`crate::load::load`] and fail at render
-    /// time with [`crate::compile::CompileError::UnsupportedAnchor`].
-    UnsupportedTiling( String ),
+    /// Every [`crate::pipeline::TilingStrategy`] value is implemented, so
+    /// validation no longer reports this; it is kept for tiling strategies
+    /// added ahead of their implementation.
+    UnsupportedTiling
+    {
+      /// The requested tiling strategy.
+      tiling : String,
+      /// Spec path of the tiling (`pipeline.hex.tiling`).
+      path : String,
+    },
+    /// A sprite source does not fit the pipeline's tiling strategy — an
+    /// autotile layout of the wrong width, a `VertexCorners` pattern with
+    /// the wrong corner count, a `NeighborCondition` side the tiling has
+    /// no neighbour on, or `EdgeConnectedBitmask` on a square tiling.
+    TilingMismatch
+    {
+      /// `pipeline.hex.tiling` (`"Square4"`, `"HexFlatTop"`, …).
+      tiling : &'static str,
+      /// What does not fit (`"autotile layout Bitmask6"`, …).
+      detail : String,
+      /// Spec path of the offending value.
+      path : String,
+    },
     /// A sprite source is not valid for the declaring object's anchor type.
     ///
     /// For example, `NeighborBitmask` only works on `Hex` anchors;
@@ -126,6 +148,8 @@ mod private
       anchor : &'static str,
       /// The source kind attempted on it.
       source_kind : &'static str,
+      /// Spec path of the offending source.
+      path : String,
     },
     /// The object's `default_state` is not present in its `states` map.
     MissingDefaultState
@@ -134,6 +158,18 @@ mod private
       object : String,
       /// The state name that was set as default but not declared.
       state : String,
+      /// Spec path of the `default_state` field.
+      path : String,
+    },
+    /// A `ViewportTiling::RepeatWorld` layer's content cannot tile as one
+    /// repeat-wrapped texture: it must be a `Static` ref to a `Single`
+    /// asset declared with `wrap: Repeat`.
+    UnrepeatableContent
+    {
+      /// What is wrong (`"asset \"sea\" is not Single"`, …).
+      detail : String,
+      /// Spec path of the layer's `content`.
+      path : String,
     },
     /// A reserved id was used in a user declaration.
     ///
@@ -142,6 +178,8 @@ mod private
     {
       /// The reserved id that was illegally declared.
       id : String,
+      /// Spec path of the declaration's `id` field.
+      path : String,
     },
   }
 
@@ -152,18 +190,22 @@ mod private
     {
       match self
       {
-        Self::DuplicateId { kind, id } => write!( f, "duplicate {kind} id: {id:?}" ),
-        Self::UnresolvedRef { kind, id, context } =>
-          write!( f, "unresolved {kind} reference {id:?} in {context}" ),
-        Self::IllegalSourceNesting { outer, inner } =>
-          write!( f, "composite source {inner} cannot be nested inside {outer}" ),
-        Self::UnsupportedTiling( name ) =>
-          write!( f, "unsupported tiling strategy: {name}" ),
-        Self::AnchorSourceMismatch { anchor, source_kind } =>
-          write!( f, "sprite source {source_kind} is not valid for anchor {anchor}" ),
-        Self::MissingDefaultState { object, state } =>
-          write!( f, "object {object:?} declares default_state {state:?} but has no such entry" ),
-        Self::ReservedId { id } => write!( f, "reserved id used in declaration: {id:?}" ),
+        Self::DuplicateId { kind, id, path } => write!( f, "{path}: duplicate {kind} id {id:?}" ),
+        Self::UnresolvedRef { kind, id, path } =>
+          write!( f, "{path}: unresolved {kind} reference {id:?}" ),
+        Self::IllegalSourceNesting { outer, inner, path } =>
+          write!( f, "{path}: composite source {inner} cannot be nested inside {outer}" ),
+        Self::UnsupportedTiling { tiling, path } =>
+          write!( f, "{path}: unsupported tiling strategy {tiling}" ),
+        Self::TilingMismatch { tiling, detail, path } =>
+          write!( f, "{path}: {detail} does not fit tiling {tiling}" ),
+        Self::AnchorSourceMismatch { anchor, source_kind, path } =>
+          write!( f, "{path}: sprite source {source_kind} is not valid for anchor {anchor}" ),
+        Self::MissingDefaultState { object : _, state, path } =>
+          write!( f, "{path}: {state:?} is not an entry of states" ),
+        Self::UnrepeatableContent { detail, path } =>
+          write!( f, "{path}: {detail}; RepeatWorld needs a Static ref to a Single asset with wrap Repeat" ),
+        Self::ReservedId { id : _, path } => write!( f, "{path}: reserved id used in declaration" ),
       }
     }
   }
@@ -196,6 +238,15 @@ mod private
       /// `(row, col)` of the cell in the ASCII grid.
       pos : ( i32, i32 ),
     },
+    /// A `players[]` entry carries a colour that is not `"#rrggbb"` /
+    /// `"#rrggbbaa"`.
+    InvalidPlayerColor
+    {
+      /// The player's id.
+      player : u32,
+      /// The malformed colour string.
+      color : String,
+    },
   }
 
   impl fmt::Display for SnapshotLoadError
@@ -209,6 +260,8 @@ mod private
           write!( f, "snapshot references object {id:?} in {context} but the spec does not declare it" ),
         Self::UnknownPaletteChar { ch, pos : ( q, r ) } =>
           write!( f, "ASCII map cell at (q={q}, r={r}) uses character {ch:?} which is not in the palette" ),
+        Self::InvalidPlayerColor { player, color } =>
+          write!( f, "player {player} has colour {color:?}, expected \"#rrggbb\" or \"#rrggbbaa\"" ),
       }
     }
   }
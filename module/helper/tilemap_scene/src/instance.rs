@@ -73,7 +73,7 @@ mod private
   /// whose variant is incompatible with the owning object's declared
   /// sprite sources (e.g. `Placement::FreePos` paired with
   /// `SpriteSource::NeighborBitmask`).
-  #[ derive( Debug, Clone, Copy ) ]
+  #[ derive( Debug, Clone, Copy, PartialEq ) ]
   pub enum Placement
   {
     /// One hex cell. Position = `( q, r )` in axial coordinates.
@@ -114,8 +114,9 @@ mod private
 
   impl Placement
   {
-    /// Returns the hex coordinate this placement occupies, if any. Used by
-    /// `Scene`'s spatial index for fast `instances_at_hex` lookups.
+    /// Returns the hex coordinate this placement is anchored at, if any —
+    /// for `Multihex` that is the anchor cell only; `Scene`'s spatial index
+    /// expands it over the object's shape for `instances_at_hex` lookups.
     #[ inline ]
     #[ must_use ]
     pub fn hex_coord( &self ) -> Option< ( i32, i32 ) >
@@ -150,6 +151,10 @@ mod private
     /// Optional per-instance tint multiplier. `None` falls back to whatever
     /// the layer / global tint pipeline yields.
     pub tint : Option< [ f32; 4 ] >,
+    /// Owning player, resolved against the scene's player palette by
+    /// `MaskTint::TeamColor`. `None` for unowned instances (terrain,
+    /// neutral props) — their team-colour masks are not drawn.
+    pub owner : Option< u32 >,
     /// Per-instance animation phase offset in seconds. Overrides the
     /// animation's declared [`crate::resource::PhaseOffset`] for this
     /// instance when set — lets `OneShot` animations start "now" without
@@ -32,18 +32,16 @@ mod private
 
     /// Loads, parses, and validates a render spec from a file on disk.
     ///
-    /// Validation enforces a partial subset of SPEC §16 — see [`Validate`]
-    /// for the per-type list of rules currently checked. Rules not yet
-    /// implemented stay as `// TODO SPEC §16` markers in
-    /// `tilemap_scene/src/validate.rs`; a successful load proves only the
-    /// implemented rules pass.
+    /// Validation runs the SPEC §16 rules listed on [`Validate`] and
+    /// reports every violation at once, each tagged with its path into the
+    /// spec.
     ///
     /// # Errors
     ///
     /// - [`LoadError::Io`] if the file cannot be read.
     /// - [`LoadError::Ron`] if parsing fails.
-    /// - [`LoadError::Validation`] when one or more enforced SPEC §16 rules
-    ///   reject the spec (see [`Validate`] for the current rule set).
+    /// - [`LoadError::Validation`] when one or more SPEC §16 rules reject
+    ///   the spec (see [`Validate`] for the rule set).
     #[ inline ]
     pub fn load( path : impl AsRef< Path > ) -> Result< Self, LoadError >
     {
@@ -110,13 +110,8 @@ mod private
     /// perfect equilateral triangle ratio, construct `HexConfig`
     /// directly with the empirically-tuned `grid_stride` instead.
     ///
-    /// `Square4` / `Square8` are accepted without panic (stride
-    /// defaults to `(w, h)`) but are not yet implemented. Load-time
-    /// rejection of unsupported tilings is a tracked TODO in
-    /// [`crate::validate`] (SPEC §16), so [`crate::load::load`]
-    /// currently returns `Ok( () )` for square specs; compilation
-    /// later fails at render time with
-    /// [`crate::compile::CompileError::UnsupportedAnchor`].
+    /// `Square4` / `Square8`: cells butt edge to edge, so the stride is
+    /// the bounding box `( w, h )` itself.
     #[ inline ]
     #[ must_use ]
     pub fn from_hex_size( w : u32, h : u32, tiling : TilingStrategy ) -> Self
@@ -125,9 +120,6 @@ mod private
       {
         TilingStrategy::HexFlatTop   => ( w * 3 / 4, h ),
         TilingStrategy::HexPointyTop => ( w, h * 3 / 4 ),
-        // Square tilings are not yet implemented; we still produce a
-        // sane default (1:1 with the bounding box) rather than panic so
-        // callers exploring the API don't trip a destructive failure.
         TilingStrategy::Square4 | TilingStrategy::Square8 => ( w, h ),
       };
       Self { tiling, grid_stride }
@@ -137,12 +129,10 @@ mod private
   /// Tiling strategy — determines neighbour ordering, dual-mesh shape, and
   /// pixel-conversion. See SPEC §2.1.
   ///
-  /// Version 0.2.0 implements the two hex variants; the square variants are
-  /// reserved. Load-time validation of [`TilingStrategy`] is a tracked TODO
-  /// in [`crate::validate`] (SPEC §16) — [`crate::error::ValidationError::UnsupportedTiling`]
-  /// is declared for that future check but is not yet constructed.
-  /// Square specs therefore pass [`crate::load::load`] today and fail at
-  /// render time with [`crate::compile::CompileError::UnsupportedAnchor`].
+  /// Square variants share pixel conversion and the quad dual mesh; they
+  /// differ only in neighbour sets (`Square8` adds the diagonals). Edges
+  /// are hex-only — `EdgeConnectedBitmask` on a square tiling fails
+  /// validation with [`crate::error::ValidationError::TilingMismatch`].
   #[ derive( Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize ) ]
   #[ non_exhaustive ]
   pub enum TilingStrategy
@@ -151,9 +141,10 @@ mod private
     HexFlatTop,
     /// Pointy-top hex — six neighbours, dual-mesh triangles.
     HexPointyTop,
-    /// 4-neighbour square grid (reserved, not implemented).
+    /// 4-neighbour square grid — orthogonal neighbours, dual-mesh quads.
     Square4,
-    /// 8-neighbour square grid (reserved, not implemented).
+    /// 8-neighbour square grid — orthogonal and diagonal neighbours,
+    /// dual-mesh quads.
     Square8,
   }
 }
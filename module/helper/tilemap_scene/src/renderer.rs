@@ -15,6 +15,18 @@
 //! backend.submit( cmds );
 //! ```
 //!
+//! **Incremental re-emission.** Every world-space sprite has an *owner* —
+//! the instance that placed it, or the dual-mesh vertex it blends. The
+//! renderer caches each owner's sprites and gives each one a stable slot
+//! in its `(sheet, blend, clip)` batch. A render after a mutation reads
+//! [`Scene::changes_since`] and re-emits only the changed owners, plus
+//! the neighbourhood whose autotiles or vertices read a cell that gained
+//! or lost an instance, so moving one unit in a 50k-tile world costs a
+//! handful of `SetSpriteInstance`s instead of a full walk. A clock advance
+//! re-emits just the owners whose output read the clock. Camera changes,
+//! scene-wide setters and the first frame re-emit every owner — still
+//! through the slots, so only sprites that actually changed are sent.
+//!
 //! **Multi-renderer / multi-backend.** Each renderer is an independent
 //! algorithm instance: two `Renderer`s built from the same spec can
 //! render the same `Scene` into different backends simultaneously
@@ -22,8 +34,10 @@
 
 mod private
 {
+  use alloc::collections::BTreeSet;
   use core::mem::Discriminant;
   use rustc_hash::FxHashMap as HashMap;
+  use rustc_hash::FxHashSet as HashSet;
   use tilemap_renderer::assets::Assets;
   use tilemap_renderer::commands::
   {
@@ -33,9 +47,11 @@ mod private
     CreateSpriteBatch,
     DeleteBatch,
     DrawBatch,
+    Mesh,
     RemoveInstance,
     RenderCommand,
     SetSpriteInstance,
+    SetTime,
     Sprite,
     SpriteBatchParams,
     UnbindBatch,
@@ -51,10 +67,23 @@ mod private
   use crate::compile::assets::{ CompiledAssets, compile_assets };
   use crate::compile::camera::Camera;
   use crate::compile::error::CompileError;
-  use crate::compile::frame::{ BucketEmits, gather_frame_emits };
+  use crate::compile::frame::
+  {
+    FrameEmitter,
+    OwnerEmit,
+    apply_sort_mode,
+    scene_edges,
+    scene_edges_at,
+    scene_tiles,
+    scene_tiles_at,
+  };
+  use crate::compile::neighbors::{ neighbor_offset_by_dir, neighbor_offsets };
   use crate::compile::resolver::AssetResolver;
-  use crate::pipeline::SortMode;
+  use crate::compile::vertex::{ VertexContext, enumerate_vertices };
+  use crate::instance::{ InstanceHandle, ObjectHandle, Placement };
+  use crate::pipeline::{ SortMode, TilingStrategy };
   use crate::scene::Scene;
+  use crate::snapshot::Tile;
   use crate::spec::RenderSpec;
 
   /// Bit-equal fingerprint of a [`Camera`] used by [`Renderer`]'s per-frame
@@ -105,27 +134,26 @@ mod private
     clip : Option< ResourceId< asset::ClipMask > >,
   }
 
-  /// Emission strategy for one bucket, chosen per render.
+  /// Emission strategy for one sorted bucket, chosen whenever the
+  /// bucket is reassembled. (`SortMode::None` buckets always use slot
+  /// batches.)
   ///
-  /// - `Batched` — `SortMode::None`: sprites can be reordered within
-  ///   the batch buffer, so we group by `(sheet, blend, clip)`.
-  /// - `BatchedPreserveOrder` — sorted bucket where every sprite shares
-  ///   one `(sheet, blend, clip)`: a single batch suffices and the
+  /// - `BatchedPreserveOrder` — every sprite shares one
+  ///   `(sheet, blend, clip)`: a single batch suffices and the
   ///   GPU's instance-buffer order equals the sort order, so visual
   ///   correctness is preserved without per-sprite emission.
-  /// - `PerSprite` — sorted bucket with multiple keys: cannot be
-  ///   batched without backend range support (`DrawBatch` draws the
-  ///   whole buffer), so fall back to one `Sprite` command per emit.
+  /// - `PerSprite` — multiple keys: cannot be batched without backend
+  ///   range support (`DrawBatch` draws the whole buffer), so fall back
+  ///   to one `Sprite` command per emit.
   #[ derive( Debug, Clone, Copy ) ]
   enum BucketDispatch
   {
-    Batched,
     BatchedPreserveOrder { key : BatchKey },
     PerSprite,
   }
 
-  /// Cached state of one allocated GPU batch, kept alive across renders
-  /// so a mutation only emits the diff against `instances`.
+  /// Cached state of one sorted-bucket GPU batch, kept alive across
+  /// renders so a mutation only emits the diff against `instances`.
   struct BatchEntry
   {
     id : ResourceId< Batch >,
@@ -143,12 +171,15 @@ mod private
   /// them to keep the comparison cheap. Floating-point fields are
   /// compared via `to_bits` so the result depends only on the bit
   /// pattern (`+0.0 != -0.0`; bit-identical `NaN`s compare equal).
+  /// `effects` uses its derived `PartialEq`: its parameters are copied
+  /// from the spec and a per-instance phase, not recomputed per frame.
   #[ inline ]
   fn sprite_instance_eq( a : &Sprite, b : &Sprite ) -> bool
   {
     a.sprite == b.sprite
       && tint_bit_eq( &a.tint, &b.tint )
       && transform_bit_eq( &a.transform, &b.transform )
+      && a.effects == b.effects
   }
 
   #[ inline ]
@@ -173,6 +204,597 @@ mod private
       && a.depth.to_bits() == b.depth.to_bits()
   }
 
+
+  /// Filler for the unused corners of a three-corner (hex) [`VertexKey`].
+  const VERTEX_PAD : ( i32, i32 ) = ( i32::MIN, i32::MIN );
+
+  /// Identity of a dual-mesh vertex: its corner cells in enumeration
+  /// order, padded with [`VERTEX_PAD`] on hex tilings.
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+  struct VertexKey( [ ( i32, i32 ); 4 ] );
+
+  impl VertexKey
+  {
+    fn of( vertex : &VertexContext ) -> Self
+    {
+      let mut corners = [ VERTEX_PAD; 4 ];
+      for ( slot, &corner ) in corners.iter_mut().zip( &vertex.corners )
+      {
+        *slot = corner;
+      }
+      Self( corners )
+    }
+
+    fn context( &self ) -> VertexContext
+    {
+      VertexContext { corners : self.0.iter().copied().filter( | &c | c != VERTEX_PAD ).collect() }
+    }
+  }
+
+  /// Source of a group of world-space sprites — the unit the renderer
+  /// caches and re-emits.
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+  enum Owner
+  {
+    Instance( InstanceHandle ),
+    Vertex( VertexKey ),
+  }
+
+  /// The part of an instance its neighbours can observe: a change here
+  /// re-emits the surrounding autotiles, vertices and edges.
+  #[ derive( Debug, Clone, Copy, PartialEq ) ]
+  struct Footprint
+  {
+    object : ObjectHandle,
+    placement : Placement,
+    visible : bool,
+  }
+
+  impl Footprint
+  {
+    fn of( scene : &Scene, h : InstanceHandle ) -> Option< Self >
+    {
+      scene.instance( h ).map( | inst | Self
+      {
+        object : inst.object,
+        placement : inst.placement,
+        visible : inst.visible,
+      })
+    }
+
+    /// Cells whose tile / edge lookups include this footprint.
+    fn cells( &self, tiling : TilingStrategy, out : &mut HashSet< ( i32, i32 ) > )
+    {
+      match self.placement
+      {
+        Placement::Hex { q, r } => { out.insert( ( q, r ) ); },
+        Placement::Edge { hex, dir } =>
+        {
+          out.insert( hex );
+          if let Some( ( dq, dr ) ) = neighbor_offset_by_dir( tiling, dir )
+          {
+            out.insert( ( hex.0 + dq, hex.1 + dr ) );
+          }
+        },
+        Placement::Multihex { .. } | Placement::FreePos { .. } | Placement::Viewport => {},
+      }
+    }
+  }
+
+  /// Everything the renderer remembers about one owner.
+  struct OwnerRecord
+  {
+    /// Creation order — the tie-break of sorted buckets, matching the
+    /// pass / spawn order of a full walk.
+    seq : u64,
+    /// `None` for vertices.
+    footprint : Option< Footprint >,
+    /// Sprites from the last emission, in emission order.
+    emits : Vec< OwnerEmit >,
+    /// Parallel to `emits`: the batch slot holding each sprite, or
+    /// `None` for sprites of sorted buckets.
+    slots : Vec< Option< ( BatchKey, u32 ) > >,
+    /// Value of [`SlotCache::epoch`] when last emitted; a full re-emit
+    /// drops records it did not reach.
+    epoch : u64,
+  }
+
+  /// A `SortMode::None` batch whose instances are owned slot by slot.
+  ///
+  /// Released slots are blanked (zero scale, zero alpha) and reused by
+  /// the next allocation; trailing free slots are trimmed at flush.
+  struct SlotBatch
+  {
+    id : ResourceId< Batch >,
+    instances : Vec< Sprite >,
+    free : BTreeSet< u32 >,
+    /// Instance count the backend holds as of the last flush.
+    flushed_len : usize,
+    /// Slots below `flushed_len` written since the last flush.
+    dirty : Vec< u32 >,
+    /// `false` until `CreateSpriteBatch` has been emitted.
+    created : bool,
+  }
+
+  impl SlotBatch
+  {
+    fn write( &mut self, slot : u32, sprite : Sprite )
+    {
+      let i = slot as usize;
+      if sprite_instance_eq( &self.instances[ i ], &sprite ) { return; }
+      self.instances[ i ] = sprite;
+      if i < self.flushed_len { self.dirty.push( slot ); }
+    }
+
+    fn alloc( &mut self, sprite : Sprite ) -> u32
+    {
+      if let Some( slot ) = self.free.pop_first()
+      {
+        self.write( slot, sprite );
+        slot
+      }
+      else
+      {
+        self.instances.push( sprite );
+        ( self.instances.len() - 1 ) as u32
+      }
+    }
+
+    fn release( &mut self, slot : u32 )
+    {
+      let old = self.instances[ slot as usize ];
+      let blank = Sprite
+      {
+        transform : Transform { scale : [ 0.0, 0.0 ], ..old.transform },
+        tint : [ 0.0; 4 ],
+        ..old
+      };
+      self.write( slot, blank );
+      self.free.insert( slot );
+    }
+  }
+
+  /// Cached assembly of one sorted bucket.
+  struct SortedBucket
+  {
+    /// Owners with at least one sprite in this bucket.
+    members : HashSet< Owner >,
+    /// Set when a member's sprites changed since `sprites` was built.
+    dirty : bool,
+    /// Sort-mode-applied sprites from the last assembly.
+    sprites : Vec< Sprite >,
+    dispatch : BucketDispatch,
+  }
+
+  /// Per-owner emission cache plus the slot batches it writes into.
+  struct SlotCache
+  {
+    owners : HashMap< Owner, OwnerRecord >,
+    /// Owners whose last emission read the scene clock.
+    clock_owners : HashSet< Owner >,
+    next_seq : u64,
+    epoch : u64,
+    /// Owners re-emitted by the most recent non-cached render.
+    reemitted : usize,
+    slot_batches : HashMap< BatchKey, SlotBatch >,
+    /// Slot batches per bucket, in creation order — their draw order.
+    bucket_batches : Vec< Vec< BatchKey > >,
+    /// `Some` for sorted buckets, indexed like `pipeline.layers`.
+    sorted : Vec< Option< SortedBucket > >,
+    /// `sprite_id → sheet_id` lookup built once at construction from
+    /// `compiled.assets.sprites`. Derives each emitted `Sprite`'s batch
+    /// key in O(1) instead of scanning the sprites vector per emit.
+    sprite_to_sheet : HashMap< ResourceId< asset::Sprite >, ResourceId< asset::Image > >,
+    /// Monotonic id allocator for `ResourceId<Batch>`. Caller-supplied
+    /// ids per `tilemap_renderer/src/commands.rs:276-484`; never reused.
+    next_batch_id : u32,
+  }
+
+  /// Bit-equal comparison of two emission lists, including the batch-key
+  /// fields [`sprite_instance_eq`] skips.
+  fn emits_eq( a : &[ OwnerEmit ], b : &[ OwnerEmit ] ) -> bool
+  {
+    a.len() == b.len() && a.iter().zip( b ).all( | ( x, y ) |
+    {
+      x.bucket == y.bucket
+        && x.world.0.to_bits() == y.world.0.to_bits()
+        && x.world.1.to_bits() == y.world.1.to_bits()
+        && sprite_instance_eq( &x.sprite, &y.sprite )
+        && core::mem::discriminant( &x.sprite.blend ) == core::mem::discriminant( &y.sprite.blend )
+        && x.sprite.clip == y.sprite.clip
+    })
+  }
+
+  impl SlotCache
+  {
+    fn new( spec : &RenderSpec, sprite_to_sheet : HashMap< ResourceId< asset::Sprite >, ResourceId< asset::Image > > ) -> Self
+    {
+      Self
+      {
+        owners : HashMap::default(),
+        clock_owners : HashSet::default(),
+        next_seq : 0,
+        epoch : 0,
+        reemitted : 0,
+        slot_batches : HashMap::default(),
+        bucket_batches : vec![ Vec::new(); spec.pipeline.layers.len() ],
+        sorted : spec.pipeline.layers.iter().map( | layer |
+        {
+          ( !matches!( layer.sort, SortMode::None ) ).then( || SortedBucket
+          {
+            members : HashSet::default(),
+            dirty : true,
+            sprites : Vec::new(),
+            dispatch : BucketDispatch::PerSprite,
+          })
+        }).collect(),
+        sprite_to_sheet,
+        next_batch_id : 0,
+      }
+    }
+
+    /// Forget every owner and slot batch, keeping the bucket layout and
+    /// the batch id counter.
+    fn reset( &mut self )
+    {
+      self.owners.clear();
+      self.clock_owners.clear();
+      self.slot_batches.clear();
+      for keys in &mut self.bucket_batches { keys.clear(); }
+      for sb in self.sorted.iter_mut().flatten()
+      {
+        sb.members.clear();
+        sb.dirty = true;
+        sb.sprites.clear();
+        sb.dispatch = BucketDispatch::PerSprite;
+      }
+    }
+
+    fn alloc_batch_id( &mut self ) -> ResourceId< Batch >
+    {
+      let id = ResourceId::new( self.next_batch_id );
+      self.next_batch_id += 1;
+      id
+    }
+
+    /// Slot-batch key of a sprite in an unsorted bucket; `None` for
+    /// sorted buckets (and, defensively, for sprite ids missing from the
+    /// compiled assets).
+    fn slot_key( &self, e : &OwnerEmit ) -> Option< BatchKey >
+    {
+      if self.sorted[ e.bucket as usize ].is_some() { return None; }
+      let &sheet = self.sprite_to_sheet.get( &e.sprite.sprite )?;
+      Some( BatchKey
+      {
+        bucket_idx : e.bucket,
+        sheet,
+        blend : core::mem::discriminant( &e.sprite.blend ),
+        clip : e.sprite.clip,
+      })
+    }
+
+    fn reemit_instance( &mut self, emitter : &FrameEmitter< '_ >, scene : &Scene, h : InstanceHandle ) -> Result< (), CompileError >
+    {
+      let mut emits = Vec::new();
+      let clock_dependent = emitter.emit_instance( h, &mut emits )?;
+      self.store( Owner::Instance( h ), Footprint::of( scene, h ), clock_dependent, emits );
+      Ok( () )
+    }
+
+    fn reemit_vertex( &mut self, emitter : &FrameEmitter< '_ >, key : VertexKey ) -> Result< (), CompileError >
+    {
+      let vertex = key.context();
+      let mut emits = Vec::new();
+      let clock_dependent = emitter.vertex_occupied( &vertex ) && emitter.emit_vertex( &vertex, &mut emits )?;
+      self.store( Owner::Vertex( key ), None, clock_dependent, emits );
+      Ok( () )
+    }
+
+    /// Replace `owner`'s cached sprites with `emits`, rewriting only the
+    /// slots whose payload changed. An owner with no footprint and no
+    /// sprites (despawned instance, vanished vertex) is dropped.
+    fn store
+    (
+      &mut self,
+      owner : Owner,
+      footprint : Option< Footprint >,
+      clock_dependent : bool,
+      emits : Vec< OwnerEmit >,
+    )
+    {
+      self.reemitted += 1;
+      let old = self.owners.remove( &owner );
+      let seq = old.as_ref().map_or( self.next_seq, | r | r.seq );
+      if old.is_none() { self.next_seq += 1; }
+      let ( old_emits, old_slots ) = old.map( | r | ( r.emits, r.slots ) ).unwrap_or_default();
+
+      if !emits_eq( &old_emits, &emits )
+      {
+        for e in &old_emits
+        {
+          if let Some( sb ) = &mut self.sorted[ e.bucket as usize ]
+          {
+            sb.members.remove( &owner );
+            sb.dirty = true;
+          }
+        }
+        for e in &emits
+        {
+          if let Some( sb ) = &mut self.sorted[ e.bucket as usize ]
+          {
+            sb.members.insert( owner );
+            sb.dirty = true;
+          }
+        }
+      }
+
+      let slots = self.assign_slots( &old_slots, &emits );
+
+      if clock_dependent { self.clock_owners.insert( owner ); }
+      else { self.clock_owners.remove( &owner ); }
+
+      if footprint.is_none() && emits.is_empty() { return; }
+      self.owners.insert( owner, OwnerRecord { seq, footprint, emits, slots, epoch : self.epoch } );
+    }
+
+    /// Move an owner's sprites from `old_slots` into slots for `emits`.
+    ///
+    /// When a batch keeps the same number of the owner's sprites, they
+    /// stay in the same slots. Otherwise the old slots are released and
+    /// fresh ones allocated in ascending order, so a layer's mask still
+    /// draws after its body.
+    fn assign_slots
+    (
+      &mut self,
+      old_slots : &[ Option< ( BatchKey, u32 ) > ],
+      emits : &[ OwnerEmit ],
+    ) -> Vec< Option< ( BatchKey, u32 ) > >
+    {
+      let mut old_by_key : Vec< ( BatchKey, Vec< u32 > ) > = Vec::new();
+      for &( key, slot ) in old_slots.iter().flatten()
+      {
+        match old_by_key.iter_mut().find( | ( k, _ ) | *k == key )
+        {
+          Some( ( _, slots ) ) => slots.push( slot ),
+          None => old_by_key.push( ( key, vec![ slot ] ) ),
+        }
+      }
+      let mut new_by_key : Vec< ( BatchKey, Vec< usize > ) > = Vec::new();
+      for ( i, e ) in emits.iter().enumerate()
+      {
+        let Some( key ) = self.slot_key( e ) else { continue };
+        match new_by_key.iter_mut().find( | ( k, _ ) | *k == key )
+        {
+          Some( ( _, idx ) ) => idx.push( i ),
+          None => new_by_key.push( ( key, vec![ i ] ) ),
+        }
+      }
+
+      let mut slots : Vec< Option< ( BatchKey, u32 ) > > = vec![ None; emits.len() ];
+      let mut pending : Vec< ( BatchKey, Vec< usize > ) > = Vec::new();
+      for ( key, idx ) in new_by_key
+      {
+        let reuse = old_by_key.iter().position( | ( k, s ) | *k == key && s.len() == idx.len() );
+        match reuse
+        {
+          Some( p ) =>
+          {
+            let ( _, old ) = old_by_key.swap_remove( p );
+            let batch = self.slot_batches.get_mut( &key ).expect( "slot batch of a live owner" );
+            for ( &i, slot ) in idx.iter().zip( old )
+            {
+              batch.write( slot, emits[ i ].sprite );
+              slots[ i ] = Some( ( key, slot ) );
+            }
+          },
+          None => pending.push( ( key, idx ) ),
+        }
+      }
+      for ( key, old ) in old_by_key
+      {
+        let batch = self.slot_batches.get_mut( &key ).expect( "slot batch of a live owner" );
+        for slot in old { batch.release( slot ); }
+      }
+      for ( key, idx ) in pending
+      {
+        if !self.slot_batches.contains_key( &key )
+        {
+          let id = self.alloc_batch_id();
+          self.bucket_batches[ key.bucket_idx as usize ].push( key );
+          self.slot_batches.insert( key, SlotBatch
+          {
+            id,
+            instances : Vec::new(),
+            free : BTreeSet::new(),
+            flushed_len : 0,
+            dirty : Vec::new(),
+            created : false,
+          });
+        }
+        let batch = self.slot_batches.get_mut( &key ).expect( "just inserted" );
+        for i in idx
+        {
+          slots[ i ] = Some( ( key, batch.alloc( emits[ i ].sprite ) ) );
+        }
+      }
+      slots
+    }
+
+    /// Re-emit every owner in full-walk order, then drop the records no
+    /// owner reached.
+    fn reemit_all( &mut self, emitter : &FrameEmitter< '_ >, scene : &Scene, tiles : &[ Tile ] ) -> Result< (), CompileError >
+    {
+      self.epoch += 1;
+      for &h in scene.hex_instances() { self.reemit_instance( emitter, scene, h )?; }
+      for &h in scene.multihex_instances() { self.reemit_instance( emitter, scene, h )?; }
+      if emitter.has_vertex_layers()
+      {
+        for vertex in enumerate_vertices( tiles, scene.spec().pipeline.hex.tiling )
+        {
+          self.reemit_vertex( emitter, VertexKey::of( &vertex ) )?;
+        }
+      }
+      for &h in scene.edge_instances() { self.reemit_instance( emitter, scene, h )?; }
+      for &h in scene.free_instances() { self.reemit_instance( emitter, scene, h )?; }
+
+      let epoch = self.epoch;
+      let stale : Vec< Owner > = self.owners.iter()
+        .filter( | ( _, r ) | r.epoch != epoch )
+        .map( | ( o, _ ) | *o )
+        .collect();
+      for owner in stale
+      {
+        self.store( owner, None, false, Vec::new() );
+      }
+      Ok( () )
+    }
+
+    /// Owners to re-emit for `changed` handles: the handles themselves,
+    /// the clock readers when `clock_moved`, and — around every cell that
+    /// gained or lost an instance — the hex and edge instances one ring
+    /// out plus the vertices touching it.
+    fn dirty_owners
+    (
+      &self,
+      scene : &Scene,
+      changed : &[ InstanceHandle ],
+      clock_moved : bool,
+    ) -> Vec< Owner >
+    {
+      let tiling = scene.spec().pipeline.hex.tiling;
+      let mut seen : HashSet< Owner > = HashSet::default();
+      let mut dirty : Vec< Owner > = Vec::new();
+      let mut add = | owner : Owner, dirty : &mut Vec< Owner > |
+      {
+        if seen.insert( owner ) { dirty.push( owner ); }
+      };
+
+      let mut structural : HashSet< ( i32, i32 ) > = HashSet::default();
+      for &h in changed
+      {
+        let before = self.owners.get( &Owner::Instance( h ) ).and_then( | r | r.footprint );
+        let after = Footprint::of( scene, h );
+        if before != after
+        {
+          for fp in before.iter().chain( after.iter() ) { fp.cells( tiling, &mut structural ); }
+        }
+        add( Owner::Instance( h ), &mut dirty );
+      }
+      if clock_moved
+      {
+        for &owner in &self.clock_owners { add( owner, &mut dirty ); }
+      }
+
+      for &( q, r ) in &structural
+      {
+        let ring = neighbor_offsets( tiling ).iter().map( | &( dq, dr ) | ( q + dq, r + dr ) );
+        for ( cq, cr ) in core::iter::once( ( q, r ) ).chain( ring )
+        {
+          for h in scene.instances_at_hex( cq, cr )
+          {
+            let neighbour_aware = scene.instance( h )
+              .is_some_and( | inst | matches!( inst.placement, Placement::Hex { .. } | Placement::Edge { .. } ) );
+            if neighbour_aware { add( Owner::Instance( h ), &mut dirty ); }
+          }
+        }
+      }
+      if !structural.is_empty()
+      {
+        let cells : Vec< Tile > = structural.iter().map( | &pos | Tile { pos, objects : Vec::new() } ).collect();
+        for vertex in enumerate_vertices( &cells, tiling )
+        {
+          add( Owner::Vertex( VertexKey::of( &vertex ) ), &mut dirty );
+        }
+      }
+      dirty
+    }
+
+    /// Re-emit `dirty` against tile / edge lookups covering just the
+    /// cells those owners read.
+    fn reemit_dirty
+    (
+      &mut self,
+      compiled : &CompiledAssets,
+      scene : &Scene,
+      camera : &Camera,
+      dirty : &[ Owner ],
+    ) -> Result< (), CompileError >
+    {
+      let tiling = scene.spec().pipeline.hex.tiling;
+      let ring = neighbor_offsets( tiling );
+      let mut need : HashSet< ( i32, i32 ) > = HashSet::default();
+      let around = | ( q, r ) : ( i32, i32 ), need : &mut HashSet< ( i32, i32 ) > |
+      {
+        need.insert( ( q, r ) );
+        need.extend( ring.iter().map( | &( dq, dr ) | ( q + dq, r + dr ) ) );
+      };
+      for owner in dirty
+      {
+        match owner
+        {
+          Owner::Instance( h ) => match scene.instance( *h ).map( | inst | inst.placement )
+          {
+            Some( Placement::Hex { q, r } ) => around( ( q, r ), &mut need ),
+            Some( Placement::Edge { hex, dir } ) =>
+            {
+              around( hex, &mut need );
+              if let Some( ( dq, dr ) ) = neighbor_offset_by_dir( tiling, dir )
+              {
+                around( ( hex.0 + dq, hex.1 + dr ), &mut need );
+              }
+            },
+            _ => {},
+          },
+          Owner::Vertex( key ) =>
+          {
+            need.extend( key.0.iter().copied().filter( | &c | c != VERTEX_PAD ) );
+          },
+        }
+      }
+
+      let tiles = scene_tiles_at( scene, need.iter().copied() );
+      let edges = scene_edges_at( scene, need.iter().copied() );
+      let emitter = FrameEmitter::new( compiled, scene, camera, &tiles, &edges )?;
+      for &owner in dirty
+      {
+        match owner
+        {
+          Owner::Instance( h ) => self.reemit_instance( &emitter, scene, h )?,
+          Owner::Vertex( key ) if emitter.has_vertex_layers() => self.reemit_vertex( &emitter, key )?,
+          Owner::Vertex( _ ) => {},
+        }
+      }
+      Ok( () )
+    }
+
+    /// Sprites of sorted bucket `bucket` in draw order: members in
+    /// creation order, each owner's sprites in emission order, then the
+    /// bucket's stable sort.
+    fn assemble_sorted( &self, bucket : usize, members : &HashSet< Owner >, sort : SortMode ) -> Vec< Sprite >
+    {
+      let mut keyed : Vec< ( u64, usize, ( f32, f32, Sprite ) ) > = Vec::new();
+      for owner in members
+      {
+        let Some( record ) = self.owners.get( owner ) else { continue };
+        for ( i, e ) in record.emits.iter().enumerate()
+        {
+          if e.bucket as usize == bucket
+          {
+            keyed.push( ( record.seq, i, ( e.world.0, e.world.1, e.sprite ) ) );
+          }
+        }
+      }
+      keyed.sort_unstable_by_key( | &( seq, i, _ ) | ( seq, i ) );
+      let mut draws : Vec< ( f32, f32, Sprite ) > = keyed.into_iter().map( | ( _, _, d ) | d ).collect();
+      apply_sort_mode( &mut draws, sort );
+      draws.into_iter().map( | ( _, _, s ) | s ).collect()
+    }
+  }
+
+  /// One bucket's viewport-pass output: world-tiled backdrop meshes and
+  /// screen-space sprites.
+  type ViewportOutput = ( Vec< Mesh >, Vec< Sprite > );
+
   /// Retained-mode renderer.
   ///
   /// Holds the compiled asset table plus reusable per-frame buffers.
@@ -196,6 +818,9 @@ mod private
     // captured on the previous `render()` call, the cached `cmd_buf` is
     // still valid and we hand it back without re-walking the scene.
     // ──────────────────────────────────────────────────────────────────
+    /// Identity of the scene rendered last — a different scene never
+    /// takes the idle or incremental path.
+    last_scene_id : u64,
     /// Snapshot of `scene.revision()` at the most recent successful render.
     last_scene_revision : u64,
     /// Snapshot of `scene.clock()` at the most recent successful render.
@@ -204,6 +829,8 @@ mod private
     last_camera_signature : CameraSignature,
     /// `false` until the first successful `render()` populates the cache;
     /// guards against returning an empty `cmd_buf` as a "cached" replay.
+    /// Also cleared by a failed render, so the next one re-emits every
+    /// owner.
     has_rendered : bool,
     /// Number of `render()` calls served from the cache without
     /// re-walking the scene. Useful introspection for consumers
@@ -216,28 +843,23 @@ mod private
     //
     // `SortMode::None` buckets (terrain / vertex / edge — the bulk of
     // hex count in a typical Slay map) get collapsed into instanced
-    // batches grouped by `(sheet, blend, clip)`. Each batch survives
-    // across frames so a per-instance mutation emits one
-    // `SetSpriteInstance` rather than a full rebuild. Sorted buckets
-    // (`YAsc`, `XDesc`, …) are also batched when every emitted sprite
-    // shares one `(sheet, blend, clip)`: the batch's instance buffer
-    // order matches the sort order, so a single `DrawBatch` preserves
-    // visual correctness. Sorted multi-key buckets fall back to per-
-    // sprite `Sprite` commands — `DrawBatch` draws the whole buffer,
-    // and run-splitting would defeat batch reuse (see roadmap §2).
+    // batches grouped by `(sheet, blend, clip)`, where each owner keeps
+    // stable slots (see `SlotCache`), so a per-instance mutation emits
+    // one `SetSpriteInstance` per changed sprite. Sorted buckets
+    // (`YAsc`, `XDesc`, …) are reassembled from the owner cache when a
+    // member changed, and batched when every sprite shares one
+    // `(sheet, blend, clip)`: the batch's instance buffer order matches
+    // the sort order, so a single `DrawBatch` preserves visual
+    // correctness. Sorted multi-key buckets fall back to per-sprite
+    // `Sprite` commands — `DrawBatch` draws the whole buffer, and
+    // run-splitting would defeat batch reuse (see roadmap §2).
     // ──────────────────────────────────────────────────────────────────
-    /// Live batches keyed by `(bucket_idx, sheet, blend, clip)`. Entries
-    /// are added on first encounter and removed via `DeleteBatch` when
-    /// a render no longer emits any instance for that key.
+    /// Sorted-bucket batches keyed by `(bucket_idx, sheet, blend, clip)`.
+    /// Removed via `DeleteBatch` when their bucket stops dispatching to
+    /// them.
     batches : HashMap< BatchKey, BatchEntry >,
-    /// Monotonic id allocator for `ResourceId<Batch>`. Caller-supplied
-    /// ids per `tilemap_renderer/src/commands.rs:276-484`; never reused.
-    next_batch_id : u32,
-    /// `sprite_id → sheet_id` lookup built once at construction from
-    /// `compiled.assets.sprites`. Lets the batch grouping walk derive
-    /// each emitted `Sprite`'s batch key in O(1) instead of scanning
-    /// the sprites vector per emit.
-    sprite_to_sheet : HashMap< ResourceId< asset::Sprite >, ResourceId< asset::Image > >,
+    /// Owner cache and the `SortMode::None` slot batches.
+    slots : SlotCache,
   }
 
   impl Renderer
@@ -270,6 +892,7 @@ mod private
       {
         compiled,
         cmd_buf : Vec::new(),
+        last_scene_id : 0,
         last_scene_revision : 0,
         last_clock : 0.0,
         last_camera_signature : CameraSignature
@@ -281,8 +904,7 @@ mod private
         has_rendered : false,
         cache_hits : 0,
         batches : HashMap::default(),
-        next_batch_id : 0,
-        sprite_to_sheet,
+        slots : SlotCache::new( spec, sprite_to_sheet ),
       })
     }
 
@@ -293,11 +915,19 @@ mod private
     #[ must_use ]
     pub fn cache_hits( &self ) -> u64 { self.cache_hits }
 
+    /// Number of owners — instances and dual-mesh vertices — whose
+    /// sprites the most recent non-cached render re-emitted. Equals the
+    /// scene's world-space owner count after a full re-emit, and stays
+    /// near the mutation count on the incremental path.
+    #[ inline ]
+    #[ must_use ]
+    pub fn reemitted_owners( &self ) -> usize { self.slots.reemitted }
+
     /// Drain every live batch into a stream of `DeleteBatch` commands.
     ///
     /// Returns the commands to submit to the backend; the renderer
-    /// itself remains usable, but its batch table and idle-replay
-    /// cache are reset so the next [`Renderer::render`] is a
+    /// itself remains usable, but its batch table, owner cache and
+    /// idle-replay cache are reset so the next [`Renderer::render`] is a
     /// guaranteed miss that allocates fresh batches.
     ///
     /// Submit the returned slice before destroying the renderer (or
@@ -307,11 +937,19 @@ mod private
     #[ must_use = "ignoring the cleanup stream leaks GPU batches in the backend" ]
     pub fn cleanup( &mut self ) -> Vec< RenderCommand >
     {
-      let mut out : Vec< RenderCommand > = Vec::with_capacity( self.batches.len() );
+      let mut out : Vec< RenderCommand > = Vec::with_capacity( self.batches.len() + self.slots.slot_batches.len() );
       for ( _, entry ) in self.batches.drain()
       {
         out.push( RenderCommand::DeleteBatch( DeleteBatch { batch : entry.id } ) );
       }
+      for ( _, batch ) in self.slots.slot_batches.drain()
+      {
+        if batch.created
+        {
+          out.push( RenderCommand::DeleteBatch( DeleteBatch { batch : batch.id } ) );
+        }
+      }
+      self.slots.reset();
       // Reset the idle-replay cache — a stale `cmd_buf` would reference
       // batch ids we just deleted.
       self.has_rendered = false;
@@ -339,6 +977,11 @@ mod private
     /// consumer drives a continuous redraw loop but the world hasn't
     /// changed.
     ///
+    /// **Incremental path.** With the same scene and camera as last
+    /// time, only the owners named by [`Scene::changes_since`] (plus
+    /// their affected neighbours, and the clock readers when the clock
+    /// moved) are re-emitted; see the module docs.
+    ///
     /// # Errors
     ///
     /// Returns [`CompileError`] when a sprite source references an
@@ -347,11 +990,10 @@ mod private
     ///
     /// # Panics
     ///
-    /// Panics in debug builds on inconsistent internal state — e.g. a
-    /// batch entry inserted just above is somehow missing from the
-    /// `batches` map when looked up. The renderer maintains this
-    /// invariant directly; a panic here indicates a bug in the
-    /// renderer itself.
+    /// Panics on inconsistent internal state — e.g. a slot recorded for
+    /// a live owner pointing at a batch that no longer exists. The
+    /// renderer maintains this invariant directly; a panic here
+    /// indicates a bug in the renderer itself.
     pub fn render
     (
       &mut self,
@@ -362,99 +1004,74 @@ mod private
       let scene_revision = scene.revision();
       let clock = scene.clock();
       let camera_signature = CameraSignature::from( camera );
+      let same_view = self.has_rendered
+        && scene.id() == self.last_scene_id
+        && camera_signature == self.last_camera_signature;
 
-      if self.has_rendered
+      if same_view
         && scene_revision == self.last_scene_revision
         && clock.to_bits() == self.last_clock.to_bits()
-        && camera_signature == self.last_camera_signature
       {
         // Idle replay — buffer from previous render is still valid.
         self.cache_hits += 1;
         return Ok( &self.cmd_buf );
       }
 
-      let emits = gather_frame_emits( &self.compiled, scene, camera )?;
+      let changed : Option< Vec< InstanceHandle > > = if same_view
+      {
+        scene.changes_since( self.last_scene_revision ).map( Iterator::collect )
+      }
+      else
+      {
+        None
+      };
+
+      // Any failure below leaves the owner cache partially updated; the
+      // next render must then re-emit everything.
+      self.has_rendered = false;
+      self.slots.reemitted = 0;
+      let viewport = self.reemit( scene, camera, changed.as_deref(), clock.to_bits() != self.last_clock.to_bits() )?;
 
       self.cmd_buf.clear();
-      self.cmd_buf.push( RenderCommand::Clear( Clear { color : emits.clear_color } ) );
+      let clear_color = scene.spec().pipeline.clear_color.unwrap_or( [ 0.0, 0.0, 0.0, 0.0 ] );
+      self.cmd_buf.push( RenderCommand::Clear( Clear { color : clear_color } ) );
+      self.cmd_buf.push( RenderCommand::SetTime( SetTime { seconds : clock } ) );
 
-      // Track which batches were touched this frame; anything in
-      // `self.batches` not in this set at the end gets `DeleteBatch`'d.
-      let mut used_keys : Vec< BatchKey > = Vec::new();
+      // `DeleteBatch`es do not draw — emitting them at the end is fine
+      // and avoids interleaving GC with the inline draw stream.
+      let mut deletes : Vec< ResourceId< Batch > > = Vec::new();
 
-      for ( bucket_idx, bucket ) in emits.buckets.into_iter().enumerate()
+      for ( bucket_idx, ( backdrops, screen ) ) in viewport.into_iter().enumerate()
       {
-        let bucket_idx_u32 = bucket_idx as u32;
-
-        // Decide emission strategy. `DrawBatch` is emitted inline at
-        // the end of each bucket's prep so the cross-bucket draw order
-        // (batched vs per-sprite) follows the consumer's declared
-        // pipeline.
-        let dispatch = self.classify_bucket( bucket_idx_u32, &bucket );
+        // World-tiled backdrops sit beneath the bucket's world sprites.
+        for mesh in backdrops
+        {
+          self.cmd_buf.push( RenderCommand::Mesh( mesh ) );
+        }
 
-        match dispatch
+        if self.slots.sorted[ bucket_idx ].is_some()
         {
-          BucketDispatch::Batched =>
-          {
-            // Group all same-(sheet, blend, clip) sprites. Order
-            // within a group is the bucket's pre-sorted order, which for
-            // `SortMode::None` is the spawn / iteration order from
-            // `Scene` — stable across frames given identical state.
-            let groups = self.group_sprites( bucket_idx_u32, &bucket.sprites );
-            for ( key, sprites_in_group ) in groups
-            {
-              self.emit_or_update_batch( key, sprites_in_group );
-              used_keys.push( key );
-              let id = self.batches.get( &key ).expect( "just inserted" ).id;
-              self.cmd_buf.push( RenderCommand::DrawBatch( DrawBatch { batch : id } ) );
-            }
-          },
-          BucketDispatch::BatchedPreserveOrder { key } =>
-          {
-            // Single-key sorted bucket: one batch, instance-buffer
-            // order matches sort order so a single `DrawBatch`
-            // preserves visual correctness without per-sprite
-            // emission.
-            self.emit_or_update_batch( key, bucket.sprites );
-            used_keys.push( key );
-            let id = self.batches.get( &key ).expect( "just inserted" ).id;
-            self.cmd_buf.push( RenderCommand::DrawBatch( DrawBatch { batch : id } ) );
-          },
-          BucketDispatch::PerSprite =>
-          {
-            // Sorted multi-key bucket: cannot be batched without
-            // backend `DrawBatch` range support, so emit per-sprite
-            // and preserve sort order via command stream position.
-            for sprite in bucket.sprites
-            {
-              self.cmd_buf.push( RenderCommand::Sprite( sprite ) );
-            }
-          },
+          self.flush_sorted_bucket( bucket_idx, scene.spec().pipeline.layers[ bucket_idx ].sort, &mut deletes );
+        }
+        else
+        {
+          self.flush_slot_bucket( bucket_idx, &mut deletes );
         }
 
         // Viewport sprites — always per-sprite (viewport-pass batching
         // is not yet implemented).
-        for sprite in bucket.screen_space
+        for sprite in screen
         {
           self.cmd_buf.push( RenderCommand::ScreenSpaceSprite( sprite ) );
         }
       }
 
-      // Garbage-collect batches that received no instances this frame.
-      // `DeleteBatch`es do not draw — emitting them at the end is fine
-      // and avoids interleaving GC with the inline draw stream above.
-      let used_set : rustc_hash::FxHashSet< BatchKey > =
-        used_keys.iter().copied().collect();
-      let stale : Vec< ( BatchKey, ResourceId< Batch > ) > = self.batches.iter()
-        .filter( | ( k, _ ) | !used_set.contains( k ) )
-        .map( | ( k, v ) | ( *k, v.id ) )
-        .collect();
-      for ( key, id ) in stale
+      for batch in deletes
       {
-        self.cmd_buf.push( RenderCommand::DeleteBatch( DeleteBatch { batch : id } ) );
-        self.batches.remove( &key );
+        self.cmd_buf.push( RenderCommand::DeleteBatch( DeleteBatch { batch } ) );
       }
 
+      self.last_scene_id = scene.id();
       self.last_scene_revision = scene_revision;
       self.last_clock = clock;
       self.last_camera_signature = camera_signature;
@@ -462,41 +1079,219 @@ mod private
       Ok( &self.cmd_buf )
     }
 
-    /// Pick the emission strategy for one bucket.
+    /// Bring the owner cache up to date — every owner when `changed` is
+    /// `None`, otherwise just the dirty ones — and return each bucket's
+    /// viewport output: backdrop meshes and screen-space sprites.
+    fn reemit
+    (
+      &mut self,
+      scene : &Scene,
+      camera : &Camera,
+      changed : Option< &[ InstanceHandle ] >,
+      clock_moved : bool,
+    ) -> Result< Vec< ViewportOutput >, CompileError >
+    {
+      let ( emitter_tiles, emitter_edges ) = if let Some( changed ) = changed
+      {
+        let dirty = self.slots.dirty_owners( scene, changed, clock_moved );
+        self.slots.reemit_dirty( &self.compiled, scene, camera, &dirty )?;
+        ( Vec::new(), Vec::new() )
+      }
+      else
+      {
+        let tiles = scene_tiles( scene );
+        let edges = scene_edges( scene );
+        let emitter = FrameEmitter::new( &self.compiled, scene, camera, &tiles, &edges )?;
+        self.slots.reemit_all( &emitter, scene, &tiles )?;
+        ( tiles, edges )
+      };
+
+      // The viewport pass reads no lookups and covers a handful of
+      // instances, so it simply re-runs every frame.
+      let emitter = FrameEmitter::new( &self.compiled, scene, camera, &emitter_tiles, &emitter_edges )?;
+      ( 0..scene.spec().pipeline.layers.len() )
+        .map( | bucket | emitter.emit_viewport( bucket ) )
+        .collect()
+    }
+
+    /// Emit the pending slot writes of one `SortMode::None` bucket, then
+    /// its `DrawBatch`es. Batches left without a live slot are queued in
+    /// `deletes`.
+    fn flush_slot_bucket( &mut self, bucket_idx : usize, deletes : &mut Vec< ResourceId< Batch > > )
+    {
+      let keys = core::mem::take( &mut self.slots.bucket_batches[ bucket_idx ] );
+      let mut kept : Vec< BatchKey > = Vec::with_capacity( keys.len() );
+      for key in keys
+      {
+        let batch = self.slots.slot_batches.get_mut( &key ).expect( "listed slot batch" );
+
+        // Trim free slots off the tail.
+        while let Some( &last ) = batch.free.last()
+        {
+          if last as usize + 1 != batch.instances.len() { break; }
+          batch.free.pop_last();
+          batch.instances.pop();
+        }
+        if batch.instances.is_empty()
+        {
+          if batch.created { deletes.push( batch.id ); }
+          self.slots.slot_batches.remove( &key );
+          continue;
+        }
+
+        if batch.created
+        {
+          let old_n = batch.flushed_len;
+          let new_n = batch.instances.len();
+          let common = old_n.min( new_n );
+          batch.dirty.sort_unstable();
+          batch.dirty.dedup();
+          batch.dirty.retain( | &i | ( i as usize ) < common );
+          if !batch.dirty.is_empty() || old_n != new_n
+          {
+            self.cmd_buf.push( RenderCommand::BindBatch( BindBatch { batch : batch.id } ) );
+            for &i in &batch.dirty
+            {
+              let s = &batch.instances[ i as usize ];
+              self.cmd_buf.push( RenderCommand::SetSpriteInstance( SetSpriteInstance
+              {
+                index : i,
+                transform : s.transform,
+                sprite : s.sprite,
+                tint : s.tint,
+                effects : s.effects,
+              }));
+            }
+            // Trim from the tail (swap-remove semantics — see
+            // `tilemap_renderer/src/commands.rs:418-422`). Walking from
+            // the highest index down keeps later indices stable.
+            for i in ( new_n..old_n ).rev()
+            {
+              self.cmd_buf.push( RenderCommand::RemoveInstance( RemoveInstance { index : i as u32 } ) );
+            }
+            for s in batch.instances.iter().skip( common )
+            {
+              self.cmd_buf.push( RenderCommand::AddSpriteInstance( AddSpriteInstance
+              {
+                transform : s.transform,
+                sprite : s.sprite,
+                tint : s.tint,
+                effects : s.effects,
+              }));
+            }
+            self.cmd_buf.push( RenderCommand::UnbindBatch( UnbindBatch ) );
+          }
+        }
+        else
+        {
+          // Recover the live `BlendMode` from a sprite (the
+          // discriminant in `key` is a comparison helper, not a value).
+          self.cmd_buf.push( RenderCommand::CreateSpriteBatch( CreateSpriteBatch
+          {
+            batch : batch.id,
+            params : SpriteBatchParams
+            {
+              transform : Transform::default(),
+              sheet : key.sheet,
+              blend : batch.instances[ 0 ].blend,
+              clip : key.clip,
+            },
+          }));
+          self.cmd_buf.push( RenderCommand::BindBatch( BindBatch { batch : batch.id } ) );
+          for s in &batch.instances
+          {
+            self.cmd_buf.push( RenderCommand::AddSpriteInstance( AddSpriteInstance
+            {
+              transform : s.transform,
+              sprite : s.sprite,
+              tint : s.tint,
+              effects : s.effects,
+            }));
+          }
+          self.cmd_buf.push( RenderCommand::UnbindBatch( UnbindBatch ) );
+          batch.created = true;
+        }
+        batch.dirty.clear();
+        batch.flushed_len = batch.instances.len();
+        self.cmd_buf.push( RenderCommand::DrawBatch( DrawBatch { batch : batch.id } ) );
+        kept.push( key );
+      }
+      self.slots.bucket_batches[ bucket_idx ] = kept;
+    }
+
+    /// Reassemble a sorted bucket if a member changed, update its batch,
+    /// and emit its draw commands.
+    fn flush_sorted_bucket( &mut self, bucket_idx : usize, sort : SortMode, deletes : &mut Vec< ResourceId< Batch > > )
+    {
+      let mut sb = self.slots.sorted[ bucket_idx ].take().expect( "sorted bucket" );
+      if sb.dirty
+      {
+        sb.sprites = self.slots.assemble_sorted( bucket_idx, &sb.members, sort );
+        let dispatch = self.classify_sorted( bucket_idx as u32, &sb.sprites );
+        if let BucketDispatch::BatchedPreserveOrder { key : old } = sb.dispatch
+          && !matches!( dispatch, BucketDispatch::BatchedPreserveOrder { key } if key == old )
+          && let Some( entry ) = self.batches.remove( &old )
+        {
+          deletes.push( entry.id );
+        }
+        if let BucketDispatch::BatchedPreserveOrder { key } = dispatch
+        {
+          self.emit_or_update_batch( key, sb.sprites.clone() );
+        }
+        sb.dispatch = dispatch;
+        sb.dirty = false;
+      }
+      match sb.dispatch
+      {
+        BucketDispatch::BatchedPreserveOrder { key } =>
+        {
+          let id = self.batches.get( &key ).expect( "sorted batch" ).id;
+          self.cmd_buf.push( RenderCommand::DrawBatch( DrawBatch { batch : id } ) );
+        },
+        BucketDispatch::PerSprite =>
+        {
+          // Sorted multi-key bucket: cannot be batched without
+          // backend `DrawBatch` range support, so emit per-sprite
+          // and preserve sort order via command stream position.
+          for sprite in &sb.sprites
+          {
+            self.cmd_buf.push( RenderCommand::Sprite( *sprite ) );
+          }
+        },
+      }
+      self.slots.sorted[ bucket_idx ] = Some( sb );
+    }
+
+    /// Pick the emission strategy for one sorted bucket.
     ///
-    /// `SortMode::None` always maps to [`BucketDispatch::Batched`].
-    /// Sorted buckets are scanned once for a shared
-    /// `(sheet, blend, clip)` triple — single-key sorted buckets get
+    /// The sprites are scanned once for a shared `(sheet, blend, clip)`
+    /// triple — single-key buckets get
     /// [`BucketDispatch::BatchedPreserveOrder`], everything else falls
-    /// back to [`BucketDispatch::PerSprite`]. An empty sorted bucket
-    /// returns `PerSprite`; the choice is moot (no commands emit)
-    /// but avoids inventing a key from an empty slice.
-    fn classify_bucket
+    /// back to [`BucketDispatch::PerSprite`]. An empty bucket returns
+    /// `PerSprite`; the choice is moot (no commands emit) but avoids
+    /// inventing a key from an empty slice.
+    fn classify_sorted
     (
       &self,
       bucket_idx : u32,
-      bucket : &BucketEmits,
+      sprites : &[ Sprite ],
     ) -> BucketDispatch
     {
-      if matches!( bucket.sort, SortMode::None )
-      {
-        return BucketDispatch::Batched;
-      }
-      let Some( first ) = bucket.sprites.first()
+      let Some( first ) = sprites.first()
       else
       {
         return BucketDispatch::PerSprite;
       };
-      let Some( &sheet ) = self.sprite_to_sheet.get( &first.sprite )
+      let Some( &sheet ) = self.slots.sprite_to_sheet.get( &first.sprite )
       else
       {
         return BucketDispatch::PerSprite;
       };
       let blend = core::mem::discriminant( &first.blend );
       let clip = first.clip;
-      for s in &bucket.sprites[ 1.. ]
+      for s in &sprites[ 1.. ]
       {
-        let Some( &s_sheet ) = self.sprite_to_sheet.get( &s.sprite )
+        let Some( &s_sheet ) = self.slots.sprite_to_sheet.get( &s.sprite )
         else
         {
           return BucketDispatch::PerSprite;
@@ -514,49 +1309,6 @@ mod private
       }
     }
 
-    /// Group a bucket's pre-sorted sprite stream by batch key while
-    /// preserving original order. Two non-adjacent runs with the same
-    /// key end up in the same returned group (sprites are appended to
-    /// the running entry for that key).
-    fn group_sprites
-    (
-      &self,
-      bucket_idx : u32,
-      sprites : &[ Sprite ],
-    ) -> Vec< ( BatchKey, Vec< Sprite > ) >
-    {
-      let mut groups : Vec< ( BatchKey, Vec< Sprite > ) > = Vec::new();
-      let mut index_of : HashMap< BatchKey, usize > = HashMap::default();
-      for sprite in sprites
-      {
-        let Some( &sheet ) = self.sprite_to_sheet.get( &sprite.sprite )
-        else
-        {
-          // Sprite id not in the compiled assets — should be impossible
-          // since `gather_frame_emits` only emits sprite ids from the
-          // same `compiled` table. Skip defensively.
-          continue;
-        };
-        let key = BatchKey
-        {
-          bucket_idx,
-          sheet,
-          blend : core::mem::discriminant( &sprite.blend ),
-          clip : sprite.clip,
-        };
-        if let Some( &i ) = index_of.get( &key )
-        {
-          groups[ i ].1.push( *sprite );
-        }
-        else
-        {
-          index_of.insert( key, groups.len() );
-          groups.push( ( key, vec![ *sprite ] ) );
-        }
-      }
-      groups
-    }
-
     /// Reuse an existing batch under `key` (emitting `Bind` + the
     /// minimal Set / Remove / Add diff + `Unbind`) or allocate a fresh
     /// one (emitting `CreateSpriteBatch` + `Bind` + N×`Add` + `Unbind`).
@@ -608,6 +1360,7 @@ mod private
             transform : s.transform,
             sprite : s.sprite,
             tint : s.tint,
+            effects : s.effects,
           }));
         }
         // Trim from the tail (swap-remove semantics — see
@@ -625,6 +1378,7 @@ mod private
             transform : s.transform,
             sprite : s.sprite,
             tint : s.tint,
+            effects : s.effects,
           }));
         }
         self.cmd_buf.push( RenderCommand::UnbindBatch( UnbindBatch ) );
@@ -632,8 +1386,7 @@ mod private
       }
       else
       {
-        let id : ResourceId< Batch > = ResourceId::new( self.next_batch_id );
-        self.next_batch_id += 1;
+        let id = self.slots.alloc_batch_id();
         // Recover the live `BlendMode` from the first sprite (the
         // discriminant in `key` is a comparison helper, not a value).
         let blend = sprites.first().map_or( BlendMode::default(), | s | s.blend );
@@ -656,6 +1409,7 @@ mod private
             transform : s.transform,
             sprite : s.sprite,
             tint : s.tint,
+            effects : s.effects,
           }));
         }
         self.cmd_buf.push( RenderCommand::UnbindBatch( UnbindBatch ) );
@@ -26,10 +26,12 @@
 mod private
 {
   use alloc::sync::Arc;
+  use core::sync::atomic::{ AtomicU64, Ordering };
   use rustc_hash::FxHashMap as HashMap;
   use slotmap::SlotMap;
-  use crate::anchor::EdgeDirection;
+  use crate::anchor::{ Anchor, EdgeDirection };
   use crate::compile::animation::{ animation_duration_seconds, declared_phase_seconds };
+  use crate::compile::frame::parse_hex_rgba;
   use crate::error::SnapshotLoadError;
   use crate::event::SceneEvent;
   use crate::instance::{ Instance, InstanceHandle, ObjectHandle, Placement, StateHandle };
@@ -38,9 +40,21 @@ mod private
   use crate::source::SpriteSource;
   use crate::spec::RenderSpec;
 
+  /// Upper bound on [`Scene`]'s per-instance change log. When full, the
+  /// older half is dropped; a reader that fell further behind than that
+  /// gets `None` from [`Scene::changes_since`] and rescans instead.
+  const CHANGE_LOG_CAPACITY : usize = 1 << 16;
+
+  /// Source of [`Scene`] identities.
+  static NEXT_SCENE_ID : AtomicU64 = AtomicU64::new( 0 );
+
   /// Retained-mode rendering scene.
   pub struct Scene
   {
+    /// Process-unique identity. Lets a renderer tell "same scene, later
+    /// revision" from "a different scene" before trusting
+    /// [`Self::changes_since`].
+    id : u64,
     spec : Arc< RenderSpec >,
     instances : SlotMap< InstanceHandle, Instance >,
 
@@ -54,9 +68,9 @@ mod private
     /// without a per-tick linear scan over `spec.animations`.
     animation_by_id : HashMap< String, usize >,
 
-    /// Hex spatial index — `( q, r )` → instances anchored at that cell.
-    /// Includes `Placement::Hex`, `Placement::Multihex` (anchor cell), and
-    /// `Placement::Edge` (owning hex). Multiple instances per cell are
+    /// Hex spatial index — `( q, r )` → instances occupying that cell.
+    /// Includes `Placement::Hex`, `Placement::Multihex` (every cell of the
+    /// object's shape), and `Placement::Edge` (owning hex). Multiple instances per cell are
     /// preserved (tile object stacks).
     instances_at_hex : HashMap< ( i32, i32 ), Vec< InstanceHandle > >,
 
@@ -81,18 +95,38 @@ mod private
     /// the spec's declared global tint (or no tint if neither is set).
     global_tint_override : Option< TintRef >,
 
+    /// Team colour per player id, consumed by `MaskTint::TeamColor`.
+    /// Keyed by id rather than indexed so sparse ids (e.g. a neutral
+    /// player `99`) don't allocate gaps.
+    player_colors : HashMap< u32, [ f32; 4 ] >,
+
     /// 64-bit pseudo-random seed for `VariantSelection::Random`. Folded
     /// down to `u32` by the renderer.
     seed : u64,
 
     /// Monotonic mutation counter. Bumped exactly once per successful
     /// state-changing call (`spawn` / `despawn` / `move_to` / `set_state` /
-    /// `set_visible` / `set_tint` / `set_phase_offset` /
-    /// `set_external_sprite` / `set_global_tint` / `set_seed`). `tick`
+    /// `set_visible` / `set_tint` / `set_owner` / `set_phase_offset` /
+    /// `set_external_sprite` / `set_global_tint` / `set_player_color` /
+    /// `set_seed`). `tick`
     /// does NOT bump — clock advance is a separate signal exposed via
     /// [`Self::clock`].
     revision : u64,
 
+    /// Per-instance change log — `( revision, handle )` pairs appended by
+    /// every per-instance mutation, in revision order. Lets a renderer
+    /// ask "which instances changed since revision N" via
+    /// [`Self::changes_since`] instead of rescanning the world.
+    /// Trimmed to `CHANGE_LOG_CAPACITY` entries; see `change_log_floor`.
+    change_log : Vec< ( u64, InstanceHandle ) >,
+    /// Every change with a revision above this value is still in
+    /// `change_log`. Raised when old entries are trimmed.
+    change_log_floor : u64,
+    /// Revision of the most recent scene-wide mutation (`set_global_tint`
+    /// / `set_player_color` / `set_seed`). Such a change can touch any
+    /// instance, so it invalidates every `changes_since` query below it.
+    global_change_revision : u64,
+
     /// Counter that produces a unique `instance_phase_seed` for each
     /// spawned instance. Hashed once at spawn so the seed is varied
     /// enough that mixing it with `hash_str(anim.id)` gives independent
@@ -118,9 +152,8 @@ mod private
     /// form), edges, multihex / free / viewport instances and `entities`
     /// vector and `spawn`s each one. `initial_global_tint` and `seed`
     /// from the snapshot are applied via the corresponding setters.
-    /// The snapshot's `players` field — game-logic concept — is
-    /// **ignored**; consumers that need team colours read them from
-    /// their own model.
+    /// Each `players[]` colour seeds the team-colour palette, and each
+    /// entity's `owner` becomes its instance owner.
     ///
     /// # Errors
     ///
@@ -128,6 +161,8 @@ mod private
     ///   references an object id the spec does not declare.
     /// - [`SnapshotLoadError::UnknownPaletteChar`] when an ASCII `map`
     ///   cell uses a character missing from `palette`.
+    /// - [`SnapshotLoadError::InvalidPlayerColor`] when a player's
+    ///   colour is not a hex colour string.
     pub fn from_snapshot
     (
       snap : &SceneSnapshot,
@@ -203,8 +238,19 @@ mod private
         scene.spawn( obj, Placement::Viewport );
       }
 
+      for player in &snap.players
+      {
+        let color = parse_hex_rgba( &player.color ).ok_or_else( || SnapshotLoadError::InvalidPlayerColor
+        {
+          player : player.id,
+          color : player.color.clone(),
+        })?;
+        scene.set_player_color( player.id, Some( color ) );
+      }
+
       // Entities are hex-anchored game pieces in the snapshot model;
-      // in the retained Scene they're just regular Placement::Hex instances.
+      // in the retained Scene they're regular Placement::Hex instances
+      // that carry an owner.
       for ent in &snap.entities
       {
         let obj = scene.object( &ent.object ).ok_or_else( || SnapshotLoadError::UnknownObject
@@ -212,7 +258,8 @@ mod private
           id : ent.object.clone(),
           context : "entity".into(),
         })?;
-        scene.spawn( obj, Placement::Hex { q : ent.at.0, r : ent.at.1 } );
+        let h = scene.spawn( obj, Placement::Hex { q : ent.at.0, r : ent.at.1 } );
+        scene.set_owner( h, Some( ent.owner ) );
       }
 
       if let Some( tint_id ) = snap.initial_global_tint.as_ref()
@@ -277,6 +324,7 @@ mod private
 
       Self
       {
+        id : NEXT_SCENE_ID.fetch_add( 1, Ordering::Relaxed ),
         spec,
         instances : SlotMap::with_key(),
         objects,
@@ -290,8 +338,12 @@ mod private
         multihex_instances : Vec::new(),
         clock : 0.0,
         global_tint_override : None,
+        player_colors : HashMap::default(),
         seed : 0,
         revision : 0,
+        change_log : Vec::new(),
+        change_log_floor : 0,
+        global_change_revision : 0,
         next_phase_seed : 0,
       }
     }
@@ -400,6 +452,7 @@ mod private
         state,
         visible : true,
         tint : None,
+        owner : None,
         phase_offset : None,
         spawn_time : self.clock,
         state_entered_time : self.clock,
@@ -407,8 +460,8 @@ mod private
         instance_phase_seed,
       };
       let handle = self.instances.insert( instance );
-      self.index_insert( handle, placement );
-      self.revision += 1;
+      self.index_insert( handle, object, placement );
+      self.touch( handle );
       handle
     }
 
@@ -422,8 +475,8 @@ mod private
         debug_assert!( false, "despawn on stale handle {h:?}" );
         return;
       };
-      self.index_remove( h, inst.placement );
-      self.revision += 1;
+      self.index_remove( h, inst.object, inst.placement );
+      self.touch( h );
     }
 
     /// Move an existing instance to a new placement. The new placement
@@ -438,10 +491,11 @@ mod private
         return;
       };
       let old = inst.placement;
+      let object = inst.object;
       inst.placement = placement;
-      self.index_remove( h, old );
-      self.index_insert( h, placement );
-      self.revision += 1;
+      self.index_remove( h, object, old );
+      self.index_insert( h, object, placement );
+      self.touch( h );
     }
 
     /// Switch the active state of `h`. `state` must belong to the same
@@ -470,7 +524,7 @@ mod private
       {
         inst.state = state;
         inst.state_entered_time = self.clock;
-        self.revision += 1;
+        self.touch( h );
       }
     }
 
@@ -481,7 +535,7 @@ mod private
       if let Some( inst ) = self.instances.get_mut( h )
       {
         inst.visible = on;
-        self.revision += 1;
+        self.touch( h );
       }
       else { debug_assert!( false, "set_visible on stale handle {h:?}" ); }
     }
@@ -492,11 +546,23 @@ mod private
       if let Some( inst ) = self.instances.get_mut( h )
       {
         inst.tint = tint;
-        self.revision += 1;
+        self.touch( h );
       }
       else { debug_assert!( false, "set_tint on stale handle {h:?}" ); }
     }
 
+    /// Assign the owning player. `None` marks the instance unowned.
+    /// Drives `MaskTint::TeamColor` via [`Self::set_player_color`].
+    pub fn set_owner( &mut self, h : InstanceHandle, owner : Option< u32 > )
+    {
+      if let Some( inst ) = self.instances.get_mut( h )
+      {
+        inst.owner = owner;
+        self.touch( h );
+      }
+      else { debug_assert!( false, "set_owner on stale handle {h:?}" ); }
+    }
+
     /// Override the animation phase offset for this instance in seconds.
     /// `None` falls back to the animation's declared `PhaseOffset`.
     pub fn set_phase_offset( &mut self, h : InstanceHandle, t : Option< f32 > )
@@ -504,7 +570,7 @@ mod private
       if let Some( inst ) = self.instances.get_mut( h )
       {
         inst.phase_offset = t;
-        self.revision += 1;
+        self.touch( h );
       }
       else { debug_assert!( false, "set_phase_offset on stale handle {h:?}" ); }
     }
@@ -522,7 +588,7 @@ mod private
       if let Some( inst ) = self.instances.get_mut( h )
       {
         inst.external_sprites.insert( slot.to_owned(), sprite );
-        self.revision += 1;
+        self.touch( h );
       }
       else
       {
@@ -536,7 +602,21 @@ mod private
     pub fn set_global_tint( &mut self, t : Option< TintRef > )
     {
       self.global_tint_override = t;
-      self.revision += 1;
+      self.touch_global();
+    }
+
+    /// Set (or with `None`, clear) the team colour of `player`. Every
+    /// instance owned by `player` picks the new colour up on the next
+    /// render — recolouring a faction is one call, not one per unit.
+    #[ inline ]
+    pub fn set_player_color( &mut self, player : u32, color : Option< [ f32; 4 ] > )
+    {
+      match color
+      {
+        Some( c ) => { self.player_colors.insert( player, c ); },
+        None => { self.player_colors.remove( &player ); },
+      }
+      self.touch_global();
     }
 
     /// Set the seed consumed by `VariantSelection::Random`. Stays stable
@@ -545,7 +625,7 @@ mod private
     pub fn set_seed( &mut self, seed : u64 )
     {
       self.seed = seed;
-      self.revision += 1;
+      self.touch_global();
     }
 
     /// Advance the master clock by `dt` seconds and return every
@@ -736,11 +816,44 @@ mod private
     #[ must_use ]
     pub fn revision( &self ) -> u64 { self.revision }
 
+    /// Process-unique identity of this scene.
+    #[ inline ]
+    #[ must_use ]
+    pub( crate ) fn id( &self ) -> u64 { self.id }
+
+    /// Handles mutated after `revision`, oldest change first. A handle
+    /// appears once per mutation, and despawned handles are included so
+    /// a reader can drop what it cached for them.
+    ///
+    /// Returns `None` when the answer is not available from the change
+    /// log: a scene-wide setter (`set_global_tint` / `set_player_color`
+    /// / `set_seed`) ran after `revision`, or the entries after
+    /// `revision` have already been trimmed. Callers treat `None` as
+    /// "everything may have changed".
+    #[ must_use ]
+    pub fn changes_since( &self, revision : u64 ) -> Option< impl Iterator< Item = InstanceHandle > + '_ >
+    {
+      if revision < self.change_log_floor || revision < self.global_change_revision
+      {
+        return None;
+      }
+      let start = self.change_log.partition_point( | &( rev, _ ) | rev <= revision );
+      Some( self.change_log[ start.. ].iter().map( | &( _, h ) | h ) )
+    }
+
     /// The override for `pipeline.global_tint`, if set.
     #[ inline ]
     #[ must_use ]
     pub fn global_tint( &self ) -> Option< &TintRef > { self.global_tint_override.as_ref() }
 
+    /// Team colour of `player`, if one is set.
+    #[ inline ]
+    #[ must_use ]
+    pub fn player_color( &self, player : u32 ) -> Option< [ f32; 4 ] >
+    {
+      self.player_colors.get( &player ).copied()
+    }
+
     /// `VariantSelection::Random` seed value.
     #[ inline ]
     #[ must_use ]
@@ -761,8 +874,9 @@ mod private
       self.instances.iter()
     }
 
-    /// Live handles anchored at `( q, r )`. Returns an empty iterator
-    /// when the cell has no instances.
+    /// Live handles occupying `( q, r )`. A multihex instance is returned
+    /// for every cell its shape covers, not just its anchor. Returns an
+    /// empty iterator when the cell has no instances.
     pub fn instances_at_hex
     (
       &self,
@@ -816,7 +930,47 @@ mod private
     // Spatial-index maintenance — private helpers
     // ════════════════════════════════════════════════════════════════
 
-    fn index_insert( &mut self, h : InstanceHandle, p : Placement )
+    /// Cells `p` occupies for `object`: the anchor plus every shape offset
+    /// for a multihex placement (anchor only when the shape is empty),
+    /// the single [`Placement::hex_coord`] otherwise.
+    fn occupied_cells( &self, object : ObjectHandle, p : Placement ) -> Vec< ( i32, i32 ) >
+    {
+      let shape = match ( p, self.spec.objects.get( object.0 as usize ).map( | o | &o.anchor ) )
+      {
+        ( Placement::Multihex { .. }, Some( Anchor::Multihex { shape } ) ) if !shape.is_empty() => shape,
+        _ => return p.hex_coord().into_iter().collect(),
+      };
+      let Some( ( q, r ) ) = p.hex_coord() else { return Vec::new() };
+      let mut cells : Vec< ( i32, i32 ) > = Vec::with_capacity( shape.len() );
+      for &( dq, dr ) in shape
+      {
+        let cell = ( q + dq, r + dr );
+        if !cells.contains( &cell ) { cells.push( cell ); }
+      }
+      cells
+    }
+
+    /// Bump `revision` and record `h` as changed at the new revision.
+    fn touch( &mut self, h : InstanceHandle )
+    {
+      self.revision += 1;
+      if self.change_log.len() >= CHANGE_LOG_CAPACITY
+      {
+        let cut = CHANGE_LOG_CAPACITY / 2;
+        self.change_log_floor = self.change_log[ cut - 1 ].0;
+        self.change_log.drain( ..cut );
+      }
+      self.change_log.push( ( self.revision, h ) );
+    }
+
+    /// Bump `revision` for a scene-wide change that no single handle covers.
+    fn touch_global( &mut self )
+    {
+      self.revision += 1;
+      self.global_change_revision = self.revision;
+    }
+
+    fn index_insert( &mut self, h : InstanceHandle, object : ObjectHandle, p : Placement )
     {
       match p
       {
@@ -826,13 +980,13 @@ mod private
         Placement::Viewport        => self.viewport_instances.push( h ),
         Placement::Multihex { .. } => self.multihex_instances.push( h ),
       }
-      if let Some( cell ) = p.hex_coord()
+      for cell in self.occupied_cells( object, p )
       {
         self.instances_at_hex.entry( cell ).or_default().push( h );
       }
     }
 
-    fn index_remove( &mut self, h : InstanceHandle, p : Placement )
+    fn index_remove( &mut self, h : InstanceHandle, object : ObjectHandle, p : Placement )
     {
       let bucket : &mut Vec< InstanceHandle > = match p
       {
@@ -844,11 +998,13 @@ mod private
       };
       bucket.retain( | &x | x != h );
 
-      if let Some( cell ) = p.hex_coord()
-        && let Some( vec ) = self.instances_at_hex.get_mut( &cell )
+      for cell in self.occupied_cells( object, p )
       {
-        vec.retain( | &x | x != h );
-        if vec.is_empty() { self.instances_at_hex.remove( &cell ); }
+        if let Some( vec ) = self.instances_at_hex.get_mut( &cell )
+        {
+          vec.retain( | &x | x != h );
+          if vec.is_empty() { self.instances_at_hex.remove( &cell ); }
+        }
       }
     }
   }
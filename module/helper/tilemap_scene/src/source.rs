@@ -189,13 +189,34 @@ mod private
     },
   }
 
-  /// Width of a [`NeighborBitmaskSource::ByAtlas`].
-  #[ derive( Debug, Clone, Copy, Serialize, Deserialize ) ]
+  /// Width of a [`NeighborBitmaskSource::ByAtlas`]. Must match the
+  /// pipeline's tiling strategy (SPEC §2.3).
+  #[ derive( Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize ) ]
   #[ non_exhaustive ]
   pub enum AutotileLayout
   {
     /// 6-bit hex neighbour mask (flat-top or pointy-top). 64 entries.
     Bitmask6,
+    /// 4-bit orthogonal mask for `Square4`. 16 entries.
+    Bitmask4,
+    /// 8-bit orthogonal + diagonal mask for `Square8`. 256 entries.
+    Bitmask8,
+  }
+
+  impl AutotileLayout
+  {
+    /// Number of atlas entries — one per mask value.
+    #[ inline ]
+    #[ must_use ]
+    pub fn entries( self ) -> u32
+    {
+      match self
+      {
+        Self::Bitmask6 => 64,
+        Self::Bitmask4 => 16,
+        Self::Bitmask8 => 256,
+      }
+    }
   }
 
   /// Bit-layout convention for [`SpriteSource::EdgeConnectedBitmask`].
@@ -209,13 +230,17 @@ mod private
   }
 
   /// One matching rule inside [`SpriteSource::VertexCorners`].
+  ///
+  /// Hex vertices have three corners, square vertices four; a pattern only
+  /// matches vertices with the same corner count as its [`Corners`].
   #[ derive( Debug, Clone, Serialize, Deserialize ) ]
   pub struct TriBlendPattern
   {
-    /// Canonicalised, sorted triple of corner object ids. Use `"*"` for
-    /// single-slot wildcards.
-    pub corners : ( String, String, String ),
-    /// Sprite name template with `{rot}` placeholder (rotation 0..2).
+    /// Corner object ids, compared against the sorted corners of each
+    /// vertex. Use `"*"` for single-slot wildcards.
+    pub corners : Corners,
+    /// Sprite name template with `{rot}` placeholder (rotation `0..3` for
+    /// hex, `0..4` for square).
     pub sprite_pattern : String,
     /// Match priority; higher wins ties of equal specificity.
     #[ serde( default ) ]
@@ -225,6 +250,86 @@ mod private
     pub animation : Option< AnimationRef >,
   }
 
+  /// Corner ids of a [`TriBlendPattern`] — three for hex tilings, four for
+  /// square ones.
+  ///
+  /// Written in RON as a plain tuple, `( "grass", "sand", "water" )` or
+  /// `( "grass", "grass", "water", "water" )`.
+  #[ derive( Debug, Clone, PartialEq, Eq, Serialize, Deserialize ) ]
+  #[ serde( from = "CornerTuple", into = "CornerTuple" ) ]
+  pub struct Corners( Vec< String > );
+
+  impl Corners
+  {
+    /// Three corners of a hex dual-mesh triangle.
+    #[ inline ]
+    #[ must_use ]
+    pub fn tri( a : impl Into< String >, b : impl Into< String >, c : impl Into< String > ) -> Self
+    {
+      Self( vec![ a.into(), b.into(), c.into() ] )
+    }
+
+    /// Four corners of a square-grid vertex.
+    #[ inline ]
+    #[ must_use ]
+    pub fn quad
+    (
+      a : impl Into< String >,
+      b : impl Into< String >,
+      c : impl Into< String >,
+      d : impl Into< String >,
+    ) -> Self
+    {
+      Self( vec![ a.into(), b.into(), c.into(), d.into() ] )
+    }
+
+    /// Corner ids in declaration order.
+    #[ inline ]
+    #[ must_use ]
+    pub fn as_slice( &self ) -> &[ String ]
+    {
+      &self.0
+    }
+  }
+
+  /// Serde shape of [`Corners`]: a 3- or 4-tuple.
+  #[ derive( Serialize, Deserialize ) ]
+  #[ serde( untagged ) ]
+  enum CornerTuple
+  {
+    Tri( String, String, String ),
+    Quad( String, String, String, String ),
+  }
+
+  impl From< CornerTuple > for Corners
+  {
+    #[ inline ]
+    fn from( value : CornerTuple ) -> Self
+    {
+      match value
+      {
+        CornerTuple::Tri( a, b, c ) => Self::tri( a, b, c ),
+        CornerTuple::Quad( a, b, c, d ) => Self::quad( a, b, c, d ),
+      }
+    }
+  }
+
+  impl From< Corners > for CornerTuple
+  {
+    #[ inline ]
+    fn from( value : Corners ) -> Self
+    {
+      let mut ids = value.0.into_iter();
+      let mut next = || ids.next().unwrap_or_default();
+      let ( a, b, c ) = ( next(), next(), next() );
+      match ids.next()
+      {
+        Some( d ) => Self::Quad( a, b, c, d ),
+        None => Self::Tri( a, b, c ),
+      }
+    }
+  }
+
   /// How a [`SpriteSource::ViewportTiled`] lays its inner content across the viewport.
   #[ derive( Debug, Clone, Copy, Serialize, Deserialize ) ]
   #[ non_exhaustive ]
@@ -236,6 +341,11 @@ mod private
     RepeatX,
     /// Tile in Y, anchored in X.
     RepeatY,
+    /// Tile in both axes in **world** space: the texture grid is pinned to
+    /// world pixel `(0, 0)`, so it pans and zooms with the camera like the
+    /// map above it. Drawn as one repeat-wrapped `Mesh`; the content must
+    /// be a `Static` ref to a `Single` asset declared with `wrap: Repeat`.
+    RepeatWorld,
     /// Stretch to fill viewport (may distort aspect).
     Stretch,
     /// Scale to fit viewport preserving aspect (may letterbox).
@@ -281,6 +391,7 @@ mod_interface::mod_interface!
   exposed use AutotileLayout;
   exposed use EdgeConnectedLayout;
   exposed use TriBlendPattern;
+  exposed use Corners;
   exposed use ViewportTiling;
   exposed use ViewportAnchorPoint;
 }
@@ -2,31 +2,37 @@
 //!
 //! Validation collects all violations and reports them together — it does
 //! not early-return on the first — so a caller can present the entire
-//! failure set at once.
+//! failure set at once. Every [`ValidationError`] carries a path into the
+//! spec (`objects["grass"].states["default"][0].sprite_source`, …) so a
+//! hand-authored `render_spec.ron` can be fixed without a debugger.
+//! Declarations are addressed by id, anonymous list entries by index.
 //!
-//! Coverage status: the partial set already enforced is listed on each
-//! `impl Validate` below; the remaining rules stay as `// TODO SPEC §16`
-//! markers in the same place to make the next contribution obvious.
+//! Coverage status: every `RenderSpec` rule listed in SPEC §16 is enforced
+//! except the animation ↔ mask frame-count consistency check; the
+//! Scene-internal rules on `SceneSnapshot` stay as `// TODO SPEC §16`
+//! markers.
 
 mod private
 {
   use rustc_hash::FxHashSet as HashSet;
-  use crate::compile::neighbors::VOID_ID;
+  use crate::anchor::Anchor;
+  use crate::compile::neighbors::{ VOID_ID, dir_to_index };
+  use crate::compile::vertex::vertex_corner_count;
   use crate::error::ValidationError;
-  use crate::resource::AnimationTiming;
+  use crate::layer::{ MaskTint, ObjectLayer, TintBehaviour };
+  use crate::pipeline::TilingStrategy;
+  use crate::resource::{ AnimationTiming, Asset, AssetKind, SpriteRef };
   use crate::snapshot::SceneSnapshot;
-  use crate::source::{ NeighborBitmaskSource, SpriteSource };
+  use crate::source::{ AutotileLayout, Condition, NeighborBitmaskSource, SpriteSource, ViewportTiling };
+  use tilemap_renderer::types::WrapMode;
   use crate::spec::RenderSpec;
 
   /// Trait implemented by types that validate their own content against the
   /// SPEC §16 rule set.
   ///
-  /// **Partial enforcement.** A subset of the SPEC §16 rules is enforced
-  /// today — see each `impl Validate` for the per-type list. Rules not yet
+  /// See each `impl Validate` for the per-type list of rules. Rules not yet
   /// implemented are tracked as `// TODO SPEC §16` markers in the impl
-  /// bodies and in `roadmap.md`. A successful `validate()` therefore proves
-  /// the input is free of the implemented violations but does **not** prove
-  /// full SPEC §16 conformance.
+  /// bodies and in `roadmap.md`.
   pub trait Validate
   {
     /// Runs every implemented validation rule, collecting every violation
@@ -43,134 +49,148 @@ mod private
   {
     /// Enforces:
     ///
+    /// - **Unique ids.** `assets`, `tints`, `animations`, `effects`,
+    ///   `objects` and `pipeline.layers` each declare every id once.
+    /// - **Reserved ids.** The reserved id `"void"` is not declared as a
+    ///   user object id.
     /// - **Pipeline-layer references resolve.** Every `Object.global_layer`
     ///   and every `ObjectLayer.pipeline_layer` (when set) names a
     ///   declared `pipeline.layers[*].id`.
-    /// - **Asset references resolve.** Every `SpriteRef.asset` reachable
-    ///   from any sprite source — including `Static`, `Variant` (recursive),
-    ///   `NeighborBitmask::ByMapping` (recursive) / `ByAtlas`,
-    ///   `NeighborCondition`, `VertexCorners`, `EdgeConnectedBitmask`,
-    ///   `ViewportTiled` — and every asset id named by an `AnimationTiming`
-    ///   variant resolves to a declared `assets[*].id`. `Animation` /
-    ///   `External` sources stop the walk at their boundary; animation
-    ///   bodies are validated separately when iterating `spec.animations`.
+    /// - **Resource references resolve.** Every `SpriteRef.asset` and asset
+    ///   id reachable from a sprite source, a layer mask or an
+    ///   `AnimationTiming`; every `AnimationRef` (including
+    ///   `TriBlendPattern.animation`); every `TintRef` (layer tints, mask
+    ///   tints, bucket `tint_mask`, `global_tint`); every `EffectRef`.
+    /// - **Object-id references resolve.** `connects_with`,
+    ///   `Condition::NeighborIs` and `TriBlendPattern.corners` entries name a
+    ///   declared object, `"void"`, or (corners only) the `"*"` wildcard.
+    /// - **No composite-in-composite nesting.** A composite source's inner
+    ///   slots — reached directly or through `Variant` — hold leaf sources
+    ///   only (SPEC §5).
+    /// - **Anchor ↔ source compatibility.** Each layer's source (looking
+    ///   through `Variant`) is one the object's anchor can draw (SPEC §3).
     /// - **Default state exists.** Every object's `default_state` names a
     ///   key present in its `states` map.
-    /// - **Reserved ids.** The reserved id `"void"` is not declared as a
-    ///   user object id.
+    /// - **Sources fit the tiling.** `ByAtlas` layouts match
+    ///   `pipeline.hex.tiling` (SPEC §2.3), `VertexCorners` patterns name
+    ///   one corner per cell meeting at a vertex, `NeighborCondition` sides
+    ///   are directions of the tiling, and `EdgeConnectedBitmask` is
+    ///   hex-only.
+    /// - **World tiling repeats.** A `RepeatWorld` layer's content is a
+    ///   `Static` ref to a `Single` asset declared with `wrap: Repeat`.
     fn validate( &self ) -> Result< (), Vec< ValidationError > >
     {
       let mut errors : Vec< ValidationError > = Vec::new();
 
-      let asset_ids : HashSet< &str > =
-        self.assets.iter().map( | a | a.id.as_str() ).collect();
-      let layer_ids : HashSet< &str > =
-        self.pipeline.layers.iter().map( | l | l.id.as_str() ).collect();
+      let asset_ids = unique_ids( "asset", "assets", self.assets.iter().map( | a | a.id.as_str() ), &mut errors );
+      let tint_ids = unique_ids( "tint", "tints", self.tints.iter().map( | t | t.id.as_str() ), &mut errors );
+      let animation_ids = unique_ids
+      (
+        "animation", "animations",
+        self.animations.iter().map( | a | a.id.as_str() ),
+        &mut errors,
+      );
+      let effect_ids = unique_ids( "effect", "effects", self.effects.iter().map( | e | e.id.as_str() ), &mut errors );
+      let mut object_ids = unique_ids( "object", "objects", self.objects.iter().map( | o | o.id.as_str() ), &mut errors );
+      let layer_ids = unique_ids
+      (
+        "pipeline layer", "pipeline.layers",
+        self.pipeline.layers.iter().map( | l | l.id.as_str() ),
+        &mut errors,
+      );
+      object_ids.insert( VOID_ID );
+
+      let mut check = SpecCheck
+      {
+        assets : &self.assets,
+        asset_ids,
+        tint_ids,
+        animation_ids,
+        effect_ids,
+        object_ids,
+        layer_ids,
+        tiling : self.pipeline.hex.tiling,
+        errors,
+      };
 
       for object in &self.objects
       {
-        if !layer_ids.contains( object.global_layer.as_str() )
+        let object_path = format!( "objects[{:?}]", object.id );
+
+        if object.id == VOID_ID
+        {
+          check.errors.push( ValidationError::ReservedId { id : object.id.clone(), path : format!( "{object_path}.id" ) } );
+        }
+        check.resolve( RefKind::PipelineLayer, &object.global_layer, || format!( "{object_path}.global_layer" ) );
+        if !object.states.contains_key( &object.default_state )
         {
-          errors.push( ValidationError::UnresolvedRef
+          check.errors.push( ValidationError::MissingDefaultState
           {
-            kind : "pipeline layer",
-            id : object.global_layer.clone(),
-            context : format!( "object {:?} global_layer", object.id ),
+            object : object.id.clone(),
+            state : object.default_state.clone(),
+            path : format!( "{object_path}.default_state" ),
           });
         }
 
-        for ( state_name, layers ) in &object.states
+        // Sorted so the error list is stable across runs despite the
+        // hash-map storage.
+        let mut states : Vec< _ > = object.states.iter().collect();
+        states.sort_unstable_by_key( | ( name, _ ) | name.as_str() );
+        for ( state_name, layers ) in states
         {
-          for layer in layers
+          for ( i, layer ) in layers.iter().enumerate()
           {
+            let layer_path = format!( "{object_path}.states[{state_name:?}][{i}]" );
             if let Some( pl ) = layer.pipeline_layer.as_deref()
-              && !layer_ids.contains( pl )
             {
-              errors.push( ValidationError::UnresolvedRef
-              {
-                kind : "pipeline layer",
-                id : pl.to_owned(),
-                context : format!
-                (
-                  "object {:?} state {:?} layer pipeline_layer override",
-                  object.id, state_name,
-                ),
-              });
+              check.resolve( RefKind::PipelineLayer, pl, || format!( "{layer_path}.pipeline_layer" ) );
             }
-
-            visit_asset_refs( &layer.sprite_source, &mut | asset, where_ |
-            {
-              if !asset_ids.contains( asset )
-              {
-                errors.push( ValidationError::UnresolvedRef
-                {
-                  kind : "asset",
-                  id : asset.to_owned(),
-                  context : format!
-                  (
-                    "object {:?} state {:?} sprite_source {where_}",
-                    object.id, state_name,
-                  ),
-                });
-              }
-            });
+            check.anchor( &object.anchor, &layer.sprite_source, &format!( "{layer_path}.sprite_source" ) );
+            check.layer( layer, &layer_path );
           }
         }
       }
 
       for anim in &self.animations
       {
-        let mut visit = | asset : &str |
-        {
-          if !asset_ids.contains( asset )
-          {
-            errors.push( ValidationError::UnresolvedRef
-            {
-              kind : "asset",
-              id : asset.to_owned(),
-              context : format!( "animation {:?}", anim.id ),
-            });
-          }
-        };
+        let path = format!( "animations[{:?}].timing", anim.id );
         match &anim.timing
         {
           AnimationTiming::Regular { frames, .. } =>
           {
-            for f in frames { visit( &f.asset ); }
+            for ( i, f ) in frames.iter().enumerate()
+            {
+              check.resolve( RefKind::Asset, &f.asset, || format!( "{path}.frames[{i}]" ) );
+            }
           },
-          AnimationTiming::FromSheet { asset, .. } => visit( asset ),
+          AnimationTiming::FromSheet { asset, .. } =>
+            check.resolve( RefKind::Asset, asset, || format!( "{path}.asset" ) ),
           AnimationTiming::Irregular { frames } =>
           {
-            for f in frames { visit( &f.sprite.asset ); }
+            for ( i, f ) in frames.iter().enumerate()
+            {
+              check.resolve( RefKind::Asset, &f.sprite.asset, || format!( "{path}.frames[{i}].sprite" ) );
+            }
           },
         }
       }
 
-      for object in &self.objects
+      for layer in &self.pipeline.layers
       {
-        if !object.states.contains_key( &object.default_state )
-        {
-          errors.push( ValidationError::MissingDefaultState
-          {
-            object : object.id.clone(),
-            state : object.default_state.clone(),
-          });
-        }
-        if object.id == VOID_ID
+        if let Some( tint ) = &layer.tint_mask
         {
-          errors.push( ValidationError::ReservedId { id : object.id.clone() } );
+          check.resolve( RefKind::Tint, &tint.0, || format!( "pipeline.layers[{:?}].tint_mask", layer.id ) );
         }
       }
+      if let Some( tint ) = &self.pipeline.global_tint
+      {
+        check.resolve( RefKind::Tint, &tint.0, || "pipeline.global_tint".to_owned() );
+      }
 
-      // TODO SPEC §16: every Asset.id / Tint.id / Animation.id / Effect.id / Object.id unique within its kind.
-      // TODO SPEC §16: every TintRef / AnimationRef / EffectRef resolves.
-      // TODO SPEC §16: every NeighborBitmask.connects_with entry is a declared object id or "void".
-      // TODO SPEC §16: anchor ↔ sprite-source compatibility (SPEC §3, §5).
-      // TODO SPEC §16: composite-in-composite illegal nesting detection.
-      // TODO SPEC §16: pipeline.tiling is one of the supported values
-      //                (HexFlatTop / HexPointyTop in 0.2.0; Square4 / Square8 rejected).
+      // TODO SPEC §16: referenced animation frame counts consistent with
+      //                masks sharing their slot (SPEC §7.3).
 
-      if errors.is_empty() { Ok( () ) } else { Err( errors ) }
+      if check.errors.is_empty() { Ok( () ) } else { Err( check.errors ) }
     }
   }
 
@@ -199,40 +219,402 @@ mod private
     }
   }
 
-  /// Walks a [`SpriteSource`] tree and calls `f( asset_id, where_ )` for
-  /// every asset id directly named by a node. `Animation` and `External`
-  /// nodes are leaves of this walk — animation bodies are validated
-  /// separately, external sources are runtime-supplied.
-  fn visit_asset_refs( source : &SpriteSource, f : &mut impl FnMut( &str, &str ) )
+  /// Collects the ids of one declaration list, reporting every repeat as
+  /// [`ValidationError::DuplicateId`] at `collection[id]`.
+  fn unique_ids< 'a >
+  (
+    kind : &'static str,
+    collection : &str,
+    ids : impl Iterator< Item = &'a str >,
+    errors : &mut Vec< ValidationError >,
+  ) -> HashSet< &'a str >
   {
-    match source
+    let mut seen = HashSet::default();
+    for id in ids
     {
-      SpriteSource::Static( sr ) => f( &sr.asset, "Static.sprite.asset" ),
-      SpriteSource::Variant { variants, .. } =>
+      if !seen.insert( id )
       {
-        for v in variants { visit_asset_refs( &v.sprite, f ); }
-      },
-      SpriteSource::NeighborCondition { asset, .. } =>
-        f( asset, "NeighborCondition.asset" ),
-      SpriteSource::VertexCorners { asset, .. } =>
-        f( asset, "VertexCorners.asset" ),
-      SpriteSource::NeighborBitmask { source, .. }
-      | SpriteSource::EdgeConnectedBitmask { source, .. } =>
+        errors.push( ValidationError::DuplicateId
+        {
+          kind,
+          id : id.to_owned(),
+          path : format!( "{collection}[{id:?}]" ),
+        });
+      }
+    }
+    seen
+  }
+
+  /// Declared-id tables plus the error sink shared by the per-layer walks
+  /// of [`Validate for RenderSpec`](Validate).
+  struct SpecCheck< 'a >
+  {
+    assets : &'a [ Asset ],
+    asset_ids : HashSet< &'a str >,
+    tint_ids : HashSet< &'a str >,
+    animation_ids : HashSet< &'a str >,
+    effect_ids : HashSet< &'a str >,
+    /// Declared object ids plus `"void"`.
+    object_ids : HashSet< &'a str >,
+    layer_ids : HashSet< &'a str >,
+    tiling : TilingStrategy,
+    errors : Vec< ValidationError >,
+  }
+
+  impl SpecCheck< '_ >
+  {
+    /// Pushes [`ValidationError::UnresolvedRef`] when `id` is not declared
+    /// for `kind`. `path` is only built on failure.
+    fn resolve( &mut self, kind : RefKind, id : &str, path : impl FnOnce() -> String )
+    {
+      let declared = match kind
+      {
+        RefKind::Asset => &self.asset_ids,
+        RefKind::Tint => &self.tint_ids,
+        RefKind::Animation => &self.animation_ids,
+        RefKind::Effect => &self.effect_ids,
+        RefKind::Object => &self.object_ids,
+        RefKind::PipelineLayer => &self.layer_ids,
+      };
+      if !declared.contains( id )
+      {
+        self.errors.push( ValidationError::UnresolvedRef { kind : kind.name(), id : id.to_owned(), path : path() } );
+      }
+    }
+
+    /// Pushes [`ValidationError::TilingMismatch`] for `detail` at `path`.
+    fn mismatch( &mut self, detail : String, path : impl FnOnce() -> String )
+    {
+      self.errors.push( ValidationError::TilingMismatch
+      {
+        tiling : tiling_name( self.tiling ),
+        detail,
+        path : path(),
+      });
+    }
+
+    /// Checks the sprite source, tint and effects of one object layer.
+    fn layer( &mut self, layer : &ObjectLayer, path : &str )
+    {
+      self.source( &layer.sprite_source, &format!( "{path}.sprite_source" ), None );
+
+      let tint_path = format!( "{path}.behaviour.tint" );
+      match &layer.behaviour.tint
       {
-        match source
+        TintBehaviour::None => {},
+        TintBehaviour::Flat( tint ) => self.resolve( RefKind::Tint, &tint.0, || tint_path.clone() ),
+        TintBehaviour::Masked { mask, tint } =>
         {
-          NeighborBitmaskSource::ByMapping { mapping, fallback } =>
+          self.source( mask, &format!( "{tint_path}.mask" ), None );
+          if let MaskTint::Ref( tint ) = tint
           {
-            for inner in mapping.values() { visit_asset_refs( inner, f ); }
-            visit_asset_refs( fallback, f );
-          },
-          NeighborBitmaskSource::ByAtlas { asset, .. } =>
-            f( asset, "NeighborBitmaskSource::ByAtlas.asset" ),
+            self.resolve( RefKind::Tint, &tint.0, || format!( "{tint_path}.tint" ) );
+          }
+        },
+      }
+
+      for ( i, effect ) in layer.behaviour.effects.iter().enumerate()
+      {
+        self.resolve( RefKind::Effect, &effect.0, || format!( "{path}.behaviour.effects[{i}]" ) );
+      }
+    }
+
+    /// Walks a [`SpriteSource`] tree, resolving every id it names and
+    /// flagging composites found under `enclosing` — the nearest composite
+    /// whose inner slot is being walked. `Variant` is transparent: its
+    /// entries inherit the caller's `enclosing`.
+    fn source( &mut self, source : &SpriteSource, path : &str, enclosing : Option< &'static str > )
+    {
+      let kind = source_kind( source );
+      let composite = is_composite( source );
+      if composite && let Some( outer ) = enclosing
+      {
+        self.errors.push( ValidationError::IllegalSourceNesting
+        {
+          outer,
+          inner : kind,
+          path : path.to_owned(),
+        });
+      }
+      let inner = if composite { Some( kind ) } else { enclosing };
+
+      match source
+      {
+        SpriteSource::Static( sr ) => self.resolve( RefKind::Asset, &sr.asset, || path.to_owned() ),
+        SpriteSource::Variant { variants, .. } =>
+        {
+          for ( i, v ) in variants.iter().enumerate()
+          {
+            self.source( &v.sprite, &format!( "{path}.variants[{i}].sprite" ), inner );
+          }
+        },
+        SpriteSource::Animation( anim ) => self.resolve( RefKind::Animation, &anim.0, || path.to_owned() ),
+        SpriteSource::NeighborBitmask { connects_with, source : bitmask }
+        | SpriteSource::EdgeConnectedBitmask { connects_with, source : bitmask, .. } =>
+        {
+          let is_edge = matches!( source, SpriteSource::EdgeConnectedBitmask { .. } );
+          if is_edge && is_square( self.tiling )
+          {
+            self.mismatch( "EdgeConnectedBitmask".to_owned(), || path.to_owned() );
+          }
+          for ( i, id ) in connects_with.iter().enumerate()
+          {
+            self.resolve( RefKind::Object, id, || format!( "{path}.connects_with[{i}]" ) );
+          }
+          match bitmask
+          {
+            NeighborBitmaskSource::ByMapping { mapping, fallback } =>
+            {
+              let mut masks : Vec< _ > = mapping.iter().collect();
+              masks.sort_unstable_by_key( | ( mask, _ ) | **mask );
+              for ( mask, entry ) in masks
+              {
+                self.source( entry, &format!( "{path}.source.mapping[{mask}]" ), inner );
+              }
+              self.source( fallback, &format!( "{path}.source.fallback" ), inner );
+            },
+            NeighborBitmaskSource::ByAtlas { asset, layout } =>
+            {
+              self.resolve( RefKind::Asset, asset, || format!( "{path}.source.asset" ) );
+              // Edge masks are always `Bitmask6`; their tiling is checked above.
+              if !is_edge && *layout != autotile_layout( self.tiling )
+              {
+                self.mismatch( format!( "autotile layout {layout:?}" ), || format!( "{path}.source.layout" ) );
+              }
+            },
+          }
+        },
+        SpriteSource::NeighborCondition { condition, sides, asset, .. } =>
+        {
+          self.condition( condition, &format!( "{path}.condition" ) );
+          for ( i, side ) in sides.iter().enumerate()
+          {
+            if dir_to_index( self.tiling, *side ).is_none()
+            {
+              self.mismatch( format!( "side {side:?}" ), || format!( "{path}.sides[{i}]" ) );
+            }
+          }
+          self.resolve( RefKind::Asset, asset, || format!( "{path}.asset" ) );
+        },
+        SpriteSource::VertexCorners { patterns, asset } =>
+        {
+          for ( i, pattern ) in patterns.iter().enumerate()
+          {
+            let corners = pattern.corners.as_slice();
+            if corners.len() != vertex_corner_count( self.tiling )
+            {
+              self.mismatch( format!( "{}-corner pattern", corners.len() ), || format!( "{path}.patterns[{i}].corners" ) );
+            }
+            for ( slot, id ) in corners.iter().enumerate()
+            {
+              if id != "*"
+              {
+                self.resolve( RefKind::Object, id, || format!( "{path}.patterns[{i}].corners.{slot}" ) );
+              }
+            }
+            if let Some( anim ) = &pattern.animation
+            {
+              self.resolve( RefKind::Animation, &anim.0, || format!( "{path}.patterns[{i}].animation" ) );
+            }
+          }
+          self.resolve( RefKind::Asset, asset, || format!( "{path}.asset" ) );
+        },
+        SpriteSource::ViewportTiled { content, tiling, .. } =>
+        {
+          self.source( content, &format!( "{path}.content" ), inner );
+          if let ViewportTiling::RepeatWorld = tiling
+          {
+            self.world_tiling( content, &format!( "{path}.content" ) );
+          }
+        },
+        SpriteSource::External { .. } => {},
+      }
+    }
+
+    /// Flags `RepeatWorld` content that cannot be drawn as one
+    /// repeat-wrapped texture. Unresolved assets are left to
+    /// [`Self::resolve`].
+    fn world_tiling( &mut self, content : &SpriteSource, path : &str )
+    {
+      let detail = match content
+      {
+        SpriteSource::Static( SpriteRef { asset : id, .. } ) =>
+        {
+          let Some( asset ) = self.assets.iter().find( | a | a.id == *id ) else { return };
+          if !matches!( asset.kind, AssetKind::Single { .. } )
+          {
+            format!( "asset {id:?} is not Single" )
+          }
+          else if !matches!( asset.wrap, WrapMode::Repeat )
+          {
+            format!( "asset {id:?} does not declare wrap Repeat" )
+          }
+          else { return }
+        },
+        other => format!( "{} content", source_kind( other ) ),
+      };
+      self.errors.push( ValidationError::UnrepeatableContent { detail, path : path.to_owned() } );
+    }
+
+    /// Resolves the object ids named anywhere in a [`Condition`] tree.
+    fn condition( &mut self, condition : &Condition, path : &str )
+    {
+      match condition
+      {
+        Condition::NeighborIs( ids ) =>
+        {
+          for ( i, id ) in ids.iter().enumerate()
+          {
+            self.resolve( RefKind::Object, id, || format!( "{path}.NeighborIs[{i}]" ) );
+          }
+        },
+        Condition::AnyOf( inner ) | Condition::AllOf( inner ) =>
+        {
+          for ( i, c ) in inner.iter().enumerate()
+          {
+            self.condition( c, &format!( "{path}[{i}]" ) );
+          }
+        },
+        Condition::Not( inner ) => self.condition( inner, &format!( "{path}.Not" ) ),
+        Condition::NoNeighbor | Condition::NeighborPriorityLower => {},
+      }
+    }
+
+    /// Flags a layer source the object's anchor cannot draw. `Variant`
+    /// entries are checked individually, except on `Viewport` where the
+    /// layer must be a `ViewportTiled` itself.
+    fn anchor( &mut self, anchor : &Anchor, source : &SpriteSource, path : &str )
+    {
+      if let SpriteSource::Variant { variants, .. } = source
+        && !matches!( anchor, Anchor::Viewport )
+      {
+        for ( i, v ) in variants.iter().enumerate()
+        {
+          self.anchor( anchor, &v.sprite, &format!( "{path}.variants[{i}].sprite" ) );
         }
-      },
-      SpriteSource::ViewportTiled { content, .. } =>
-        visit_asset_refs( content, f ),
-      SpriteSource::Animation( _ ) | SpriteSource::External { .. } => {},
+        return;
+      }
+
+      let accepted = match source
+      {
+        SpriteSource::Static( _ )
+        | SpriteSource::Variant { .. }
+        | SpriteSource::Animation( _ )
+        | SpriteSource::External { .. } => !matches!( anchor, Anchor::Viewport ),
+        SpriteSource::NeighborBitmask { .. }
+        | SpriteSource::NeighborCondition { .. } => matches!( anchor, Anchor::Hex ),
+        SpriteSource::VertexCorners { .. } => matches!( anchor, Anchor::Hex | Anchor::Vertex ),
+        SpriteSource::EdgeConnectedBitmask { .. } => matches!( anchor, Anchor::Edge ),
+        SpriteSource::ViewportTiled { .. } => matches!( anchor, Anchor::Viewport ),
+      };
+      if !accepted
+      {
+        self.errors.push( ValidationError::AnchorSourceMismatch
+        {
+          anchor : anchor_kind( anchor ),
+          source_kind : source_kind( source ),
+          path : path.to_owned(),
+        });
+      }
+    }
+  }
+
+  /// Declaration list a reference is resolved against.
+  #[ derive( Debug, Clone, Copy ) ]
+  enum RefKind
+  {
+    Asset,
+    Tint,
+    Animation,
+    Effect,
+    Object,
+    PipelineLayer,
+  }
+
+  impl RefKind
+  {
+    /// Name reported in [`ValidationError::UnresolvedRef::kind`].
+    fn name( self ) -> &'static str
+    {
+      match self
+      {
+        Self::Asset         => "asset",
+        Self::Tint          => "tint",
+        Self::Animation     => "animation",
+        Self::Effect        => "effect",
+        Self::Object        => "object",
+        Self::PipelineLayer => "pipeline layer",
+      }
+    }
+  }
+
+  fn is_square( tiling : TilingStrategy ) -> bool
+  {
+    matches!( tiling, TilingStrategy::Square4 | TilingStrategy::Square8 )
+  }
+
+  /// The `ByAtlas` layout a `NeighborBitmask` needs under `tiling`.
+  fn autotile_layout( tiling : TilingStrategy ) -> AutotileLayout
+  {
+    match tiling
+    {
+      TilingStrategy::HexFlatTop | TilingStrategy::HexPointyTop => AutotileLayout::Bitmask6,
+      TilingStrategy::Square4 => AutotileLayout::Bitmask4,
+      TilingStrategy::Square8 => AutotileLayout::Bitmask8,
+    }
+  }
+
+  fn tiling_name( tiling : TilingStrategy ) -> &'static str
+  {
+    match tiling
+    {
+      TilingStrategy::HexFlatTop   => "HexFlatTop",
+      TilingStrategy::HexPointyTop => "HexPointyTop",
+      TilingStrategy::Square4      => "Square4",
+      TilingStrategy::Square8      => "Square8",
+    }
+  }
+
+  /// `true` for the composite sources of SPEC §5.
+  fn is_composite( source : &SpriteSource ) -> bool
+  {
+    matches!
+    (
+      source,
+      SpriteSource::NeighborBitmask { .. }
+      | SpriteSource::NeighborCondition { .. }
+      | SpriteSource::VertexCorners { .. }
+      | SpriteSource::EdgeConnectedBitmask { .. }
+      | SpriteSource::ViewportTiled { .. }
+    )
+  }
+
+  fn source_kind( source : &SpriteSource ) -> &'static str
+  {
+    match source
+    {
+      SpriteSource::Static( _ )                 => "Static",
+      SpriteSource::Variant { .. }              => "Variant",
+      SpriteSource::Animation( _ )              => "Animation",
+      SpriteSource::External { .. }             => "External",
+      SpriteSource::NeighborBitmask { .. }      => "NeighborBitmask",
+      SpriteSource::NeighborCondition { .. }    => "NeighborCondition",
+      SpriteSource::VertexCorners { .. }        => "VertexCorners",
+      SpriteSource::EdgeConnectedBitmask { .. } => "EdgeConnectedBitmask",
+      SpriteSource::ViewportTiled { .. }        => "ViewportTiled",
+    }
+  }
+
+  fn anchor_kind( anchor : &Anchor ) -> &'static str
+  {
+    match anchor
+    {
+      Anchor::Hex             => "Hex",
+      Anchor::Edge            => "Edge",
+      Anchor::Vertex          => "Vertex",
+      Anchor::Multihex { .. } => "Multihex",
+      Anchor::FreePos         => "FreePos",
+      Anchor::Viewport        => "Viewport",
     }
   }
 }
@@ -21,6 +21,7 @@ use tilemap_renderer::commands::
   RenderCommand,
   Sprite,
   SpriteBatchParams,
+  SpriteEffects,
 };
 use tilemap_renderer::types::{ asset, Batch, ResourceId };
 
@@ -30,6 +31,7 @@ struct Instance
   transform : tilemap_renderer::types::Transform,
   sprite : ResourceId< asset::Sprite >,
   tint : [ f32; 4 ],
+  effects : SpriteEffects,
 }
 
 struct BatchState
@@ -88,6 +90,7 @@ impl BatchFlattener
             transform : a.transform,
             sprite : a.sprite,
             tint : a.tint,
+            effects : a.effects,
           });
         },
         RenderCommand::SetSpriteInstance( s ) =>
@@ -101,6 +104,7 @@ impl BatchFlattener
             transform : s.transform,
             sprite : s.sprite,
             tint : s.tint,
+            effects : s.effects,
           };
         },
         RenderCommand::RemoveInstance( r ) =>
@@ -121,6 +125,7 @@ impl BatchFlattener
               transform : inst.transform,
               sprite : inst.sprite,
               tint : inst.tint,
+              effects : inst.effects,
               blend : state.params.blend,
               clip : state.params.clip,
             }));
@@ -20,15 +20,18 @@ extern crate alloc;
 use alloc::sync::Arc;
 use rustc_hash::FxHashMap as HashMap;
 
-use tilemap_renderer::commands::RenderCommand;
+use tilemap_renderer::commands::{ RenderCommand, Sprite };
 use tilemap_scene::
 {
   Anchor,
   Asset,
   AssetKind,
+  AutotileLayout,
   Camera,
   HexConfig,
   LayerBehaviour,
+  MaskTint,
+  NeighborBitmaskSource,
   Object,
   ObjectLayer,
   PathResolver,
@@ -42,6 +45,7 @@ use tilemap_scene::
   SpriteRef,
   SpriteSource,
   TilingStrategy,
+  TintBehaviour,
 };
 use tilemap_renderer::types::{ MipmapMode, SamplerFilter, WrapMode };
 
@@ -555,12 +559,9 @@ fn unchanged_batch_emits_no_set_on_cache_miss()
 #[ test ]
 fn single_move_emits_fewer_sets_than_full_repopulate()
 {
-  // Bit-equal elision provides a strict lower bound: at most one Set per
-  // slot whose sprite payload actually differs between frames. Because
-  // some compile passes iterate scene state in non-deterministic
-  // HashMap order (e.g. `build_scene_tiles` for tile_lookup), more than
-  // one slot may differ after a `move_to` — but it must never be the
-  // pre-optimisation N (= full common-prefix repopulate).
+  // Each instance keeps its batch slot across frames, so moving one
+  // tile rewrites exactly that tile's slot — never the pre-optimisation
+  // N (= full common-prefix repopulate).
   let spec = build_spec();
   let mut renderer = Renderer::new( &spec, &PathResolver ).expect( "renderer" );
   let mut scene = Scene::new( Arc::new( spec ) );
@@ -577,16 +578,11 @@ fn single_move_emits_fewer_sets_than_full_repopulate()
   scene.move_to( handles[ 2 ], Placement::Hex { q : 5, r : 5 } );
   let cmds = renderer.render( &scene, &camera ).expect( "after move" ).to_vec();
 
-  let sets = count_cmd( &cmds, | c | matches!( c, RenderCommand::SetSpriteInstance( _ ) ) );
-  assert!
-  (
-    sets >= 1,
-    "moved tile's slot must emit one Set (got {sets})",
-  );
-  assert!
+  assert_eq!
   (
-    sets < 5,
-    "elision must skip at least one unchanged slot in a 5-tile batch (got {sets})",
+    count_cmd( &cmds, | c | matches!( c, RenderCommand::SetSpriteInstance( _ ) ) ),
+    1,
+    "only the moved tile's slot is rewritten",
   );
 }
 
@@ -672,3 +668,235 @@ fn cleanup_emits_delete_for_every_live_batch()
     "post-cleanup render re-allocates the same number of batches",
   );
 }
+
+// ────────────────────────────────────────────────────────────────────────────
+// 10. Team colour — `Masked( _, TeamColor )` overlays ride the per-instance
+// tint, so units of different owners share one batch and recolouring a
+// player only rewrites that player's overlay slots.
+// ────────────────────────────────────────────────────────────────────────────
+
+fn team_spec() -> RenderSpec
+{
+  let mut spec = build_spec();
+  let knight = spec.objects.iter_mut().find( | o | o.id == "knight" ).unwrap();
+  let layer = &mut knight.states.get_mut( "default" ).unwrap()[ 0 ];
+  layer.behaviour.tint = TintBehaviour::Masked
+  {
+    mask : Box::new( SpriteSource::Static( SpriteRef { asset : "terrain".into(), frame : "2".into() } ) ),
+    tint : MaskTint::TeamColor,
+  };
+  spec
+}
+
+#[ test ]
+fn team_colour_units_share_one_batch()
+{
+  let spec = team_spec();
+  let mut renderer = Renderer::new( &spec, &PathResolver ).expect( "renderer" );
+  let mut scene = Scene::new( Arc::new( spec ) );
+  let knight = scene.object( "knight" ).unwrap();
+  let red = scene.spawn( knight, Placement::Hex { q : 0, r : 0 } );
+  let blue = scene.spawn( knight, Placement::Hex { q : 1, r : 0 } );
+  scene.set_owner( red, Some( 0 ) );
+  scene.set_owner( blue, Some( 1 ) );
+  scene.set_player_color( 0, Some( [ 1.0, 0.0, 0.0, 1.0 ] ) );
+  scene.set_player_color( 1, Some( [ 0.0, 0.0, 1.0, 1.0 ] ) );
+  let camera = Camera::default();
+
+  let cmds = renderer.render( &scene, &camera ).expect( "prime" ).to_vec();
+  assert_eq!( count_cmd( &cmds, | c | matches!( c, RenderCommand::CreateSpriteBatch( _ ) ) ), 1 );
+
+  let sprites = common::flat_sprites( &cmds );
+  assert_eq!( sprites.len(), 4, "two bodies, two overlays" );
+  let overlay_tints : Vec< [ f32; 4 ] > = sprites.iter().skip( 1 ).step_by( 2 ).map( | s | s.tint ).collect();
+  assert_eq!( overlay_tints, vec![ [ 1.0, 0.0, 0.0, 1.0 ], [ 0.0, 0.0, 1.0, 1.0 ] ] );
+  assert_eq!( sprites[ 0 ].tint, [ 1.0, 1.0, 1.0, 1.0 ], "Masked leaves the body untinted" );
+}
+
+#[ test ]
+fn recolouring_a_player_emits_one_set_per_owned_unit()
+{
+  let spec = team_spec();
+  let mut renderer = Renderer::new( &spec, &PathResolver ).expect( "renderer" );
+  let mut scene = Scene::new( Arc::new( spec ) );
+  let knight = scene.object( "knight" ).unwrap();
+  for ( q, owner ) in [ ( 0, 0 ), ( 1, 1 ), ( 2, 1 ) ]
+  {
+    let h = scene.spawn( knight, Placement::Hex { q, r : 0 } );
+    scene.set_owner( h, Some( owner ) );
+  }
+  scene.set_player_color( 0, Some( [ 1.0, 0.0, 0.0, 1.0 ] ) );
+  scene.set_player_color( 1, Some( [ 0.0, 0.0, 1.0, 1.0 ] ) );
+  let camera = Camera::default();
+
+  let _ = renderer.render( &scene, &camera ).expect( "prime" );
+
+  scene.set_player_color( 1, Some( [ 0.0, 1.0, 0.0, 1.0 ] ) );
+  let cmds = renderer.render( &scene, &camera ).expect( "after recolour" ).to_vec();
+  assert_eq!( count_cmd( &cmds, | c | matches!( c, RenderCommand::CreateSpriteBatch( _ ) ) ), 0 );
+  assert_eq!
+  (
+    count_cmd( &cmds, | c | matches!( c, RenderCommand::SetSpriteInstance( _ ) ) ),
+    2,
+    "only player 1's two overlays change",
+  );
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// 11. Incremental re-emission — a mutation re-emits only the owners whose
+// sprites it can affect (the mutated instance plus its neighbourhood),
+// and the per-frame cost no longer scales with the scene size.
+// ────────────────────────────────────────────────────────────────────────────
+
+#[ test ]
+fn moving_one_unit_in_a_large_map_reemits_its_neighbourhood_only()
+{
+  let spec = build_spec();
+  let mut renderer = Renderer::new( &spec, &PathResolver ).expect( "renderer" );
+  let mut scene = Scene::new( Arc::new( spec ) );
+  let grass = scene.object( "grass" ).unwrap();
+  let knight = scene.object( "knight" ).unwrap();
+  for r in 0..64
+  {
+    for q in 0..64 { scene.spawn( grass, Placement::Hex { q, r } ); }
+  }
+  let unit = scene.spawn( knight, Placement::Hex { q : 10, r : 10 } );
+  let camera = Camera::default();
+
+  let _ = renderer.render( &scene, &camera ).expect( "prime" );
+  assert_eq!( renderer.reemitted_owners(), 64 * 64 + 1, "first render emits every owner" );
+
+  scene.move_to( unit, Placement::Hex { q : 11, r : 10 } );
+  let cmds = renderer.render( &scene, &camera ).expect( "after move" ).to_vec();
+  let reemitted = renderer.reemitted_owners();
+  assert!( reemitted <= 15, "old and new cell plus their rings at most (got {reemitted})" );
+  assert_eq!( count_cmd( &cmds, | c | matches!( c, RenderCommand::SetSpriteInstance( _ ) ) ), 1 );
+  assert_eq!( count_cmd( &cmds, | c | matches!( c, RenderCommand::AddSpriteInstance( _ ) ) ), 0 );
+  assert_eq!( count_cmd( &cmds, | c | matches!( c, RenderCommand::RemoveInstance( _ ) ) ), 0 );
+}
+
+#[ test ]
+fn tick_on_a_static_scene_reemits_nothing()
+{
+  let spec = build_spec();
+  let mut renderer = Renderer::new( &spec, &PathResolver ).expect( "renderer" );
+  let mut scene = Scene::new( Arc::new( spec ) );
+  let grass = scene.object( "grass" ).unwrap();
+  for q in 0..8 { scene.spawn( grass, Placement::Hex { q, r : 0 } ); }
+  let camera = Camera::default();
+
+  let _ = renderer.render( &scene, &camera ).expect( "prime" );
+  let _ = scene.tick( 0.5 );
+  let _ = renderer.render( &scene, &camera ).expect( "after tick" );
+  assert_eq!( renderer.reemitted_owners(), 0, "no owner reads the clock" );
+}
+
+#[ test ]
+fn despawn_blanks_inner_slots_and_trims_the_tail()
+{
+  let spec = build_spec();
+  let mut renderer = Renderer::new( &spec, &PathResolver ).expect( "renderer" );
+  let mut scene = Scene::new( Arc::new( spec ) );
+  let grass = scene.object( "grass" ).unwrap();
+  let handles : Vec< _ > = ( 0..3 ).map( | q | scene.spawn( grass, Placement::Hex { q, r : 0 } ) ).collect();
+  let camera = Camera::default();
+  let mut flat = common::BatchFlattener::new();
+
+  let cmds = renderer.render( &scene, &camera ).expect( "prime" ).to_vec();
+  let _ = flat.apply( &cmds );
+
+  // The middle slot is blanked in place so the last tile keeps its index.
+  scene.despawn( handles[ 1 ] );
+  let cmds = renderer.render( &scene, &camera ).expect( "after inner despawn" ).to_vec();
+  let sets : Vec< _ > = cmds.iter()
+    .filter_map( | c | if let RenderCommand::SetSpriteInstance( s ) = c { Some( *s ) } else { None } )
+    .collect();
+  assert_eq!( sets.len(), 1 );
+  assert_eq!( sets[ 0 ].index, 1 );
+  assert_eq!( sets[ 0 ].tint[ 3 ], 0.0, "blanked slot draws nothing" );
+  assert_eq!( count_cmd( &cmds, | c | matches!( c, RenderCommand::RemoveInstance( _ ) ) ), 0 );
+  assert_eq!( visible( &flat.apply( &cmds ) ).len(), 2 );
+
+  // Despawning the last tile frees the tail — both trailing slots go.
+  scene.despawn( handles[ 2 ] );
+  let cmds = renderer.render( &scene, &camera ).expect( "after tail despawn" ).to_vec();
+  assert_eq!( count_cmd( &cmds, | c | matches!( c, RenderCommand::RemoveInstance( _ ) ) ), 2 );
+  assert_eq!( visible( &flat.apply( &cmds ) ).len(), 1 );
+}
+
+/// World-space sprites that actually draw — blanked free slots dropped —
+/// sorted into a canonical order so incremental and fresh outputs
+/// compare as multisets.
+fn visible( cmds : &[ RenderCommand ] ) -> Vec< String >
+{
+  let mut out : Vec< String > = cmds.iter()
+    .filter_map( | c | if let RenderCommand::Sprite( s ) = c { Some( s ) } else { None } )
+    .filter( | s : &&Sprite | s.tint[ 3 ] != 0.0 )
+    .map( | s | format!( "{:?}", ( s.transform.position, s.sprite, s.tint ) ) )
+    .collect();
+  out.sort();
+  out
+}
+
+fn wall_spec() -> RenderSpec
+{
+  let mut spec = build_spec();
+  let mut wall_states = HashMap::default();
+  wall_states.insert( "default".into(), vec![ ObjectLayer
+  {
+    id : None,
+    sprite_source : SpriteSource::NeighborBitmask
+    {
+      connects_with : vec![ "wall".into() ],
+      source : NeighborBitmaskSource::ByAtlas { asset : "terrain".into(), layout : AutotileLayout::Bitmask6 },
+    },
+    behaviour : LayerBehaviour::default(),
+    z_in_object : 0,
+    pipeline_layer : None,
+  } ] );
+  let mut wall = spec.objects[ 0 ].clone();
+  wall.id = "wall".into();
+  wall.states = wall_states;
+  spec.objects.push( wall );
+  spec
+}
+
+#[ test ]
+fn incremental_output_matches_a_fresh_render_for_autotiles()
+{
+  let spec = wall_spec();
+  let mut renderer = Renderer::new( &spec, &PathResolver ).expect( "renderer" );
+  let mut scene = Scene::new( Arc::new( spec.clone() ) );
+  let wall = scene.object( "wall" ).unwrap();
+  let grass = scene.object( "grass" ).unwrap();
+  let mut walls : Vec< _ > = [ ( 0, 0 ), ( 1, 0 ), ( 2, 0 ), ( 1, 1 ), ( 3, -1 ) ].iter()
+    .map( | &( q, r ) | scene.spawn( wall, Placement::Hex { q, r } ) )
+    .collect();
+  let tile = scene.spawn( grass, Placement::Hex { q : 4, r : 4 } );
+  let camera = Camera::default();
+  let mut flat = common::BatchFlattener::new();
+
+  let check = | renderer : &mut Renderer, flat : &mut common::BatchFlattener, scene : &Scene, step : &str |
+  {
+    let cmds = renderer.render( scene, &camera ).expect( "incremental" ).to_vec();
+    let incremental = visible( &flat.apply( &cmds ) );
+    let mut fresh_renderer = Renderer::new( &spec, &PathResolver ).expect( "fresh renderer" );
+    let fresh = fresh_renderer.render( scene, &camera ).expect( "fresh" ).to_vec();
+    assert_eq!( incremental, visible( &common::flatten_to_sprites( &fresh ) ), "diverged after {step}" );
+  };
+
+  check( &mut renderer, &mut flat, &scene, "prime" );
+  scene.move_to( walls[ 1 ], Placement::Hex { q : 2, r : 1 } );
+  check( &mut renderer, &mut flat, &scene, "move" );
+  scene.despawn( walls.remove( 0 ) );
+  check( &mut renderer, &mut flat, &scene, "despawn" );
+  walls.push( scene.spawn( wall, Placement::Hex { q : 1, r : 0 } ) );
+  check( &mut renderer, &mut flat, &scene, "spawn" );
+  scene.set_visible( walls[ 2 ], false );
+  check( &mut renderer, &mut flat, &scene, "hide" );
+  scene.set_tint( tile, Some( [ 0.2, 0.4, 0.6, 1.0 ] ) );
+  scene.move_to( tile, Placement::Hex { q : 2, r : 2 } );
+  check( &mut renderer, &mut flat, &scene, "tint and move" );
+  scene.set_visible( walls[ 2 ], true );
+  check( &mut renderer, &mut flat, &scene, "show" );
+}
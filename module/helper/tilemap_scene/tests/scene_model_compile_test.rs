@@ -23,7 +23,7 @@ use rustc_hash::FxHashMap as HashMap;
 
 mod common;
 
-use tilemap_renderer::commands::RenderCommand;
+use tilemap_renderer::commands::{ ColorShiftEffect, DisplaceEffect, RenderCommand };
 use tilemap_scene::
 {
   Anchor,
@@ -35,35 +35,46 @@ use tilemap_scene::
   AssetKind,
   AssetResolver,
   AutotileLayout,
+  Axis,
   BlendMode,
   Bounds,
   Camera,
   CompileError,
   Condition,
+  Corners,
   EdgeConnectedLayout,
   EdgeDirection,
   EdgeInstance,
   EdgePosition,
+  Effect,
+  EffectKind,
+  EffectRef,
+  Entity,
   FreeInstance,
   HexConfig,
   LayerBehaviour,
+  MaskTint,
+  MultihexInstance,
   NeighborBitmaskSource,
   Object,
   ObjectLayer,
   PathResolver,
   PhaseOffset,
   PipelineLayer,
+  Player,
   Renderer,
   RenderPipeline,
   RenderSpec,
   Scene,
   SceneSnapshot,
   SortMode,
+  SortYSource,
   SpriteRef,
   SpriteSource,
   Tile,
   TilingStrategy,
   Tint,
+  TintBehaviour,
   TintRef,
   TriBlendPattern,
   Variant,
@@ -584,10 +595,10 @@ fn palette_expansion_produces_same_tiles_as_explicit()
 // ────────────────────────────────────────────────────────────────────────────
 
 #[ test ]
-fn rejects_multihex_anchor()
+fn rejects_multihex_object_placed_as_tile()
 {
-  // Edge / FreePos / Viewport are now supported (Slice 4). Multihex is the
-  // last anchor still rejected at compile time.
+  // A Multihex object needs a multihex placement to know its shape's
+  // anchor; listing it in a tile stack is an authoring error.
   let mut spec = minimal_spec();
   spec.objects[ 0 ].anchor = Anchor::Multihex { shape : vec![ ( 0, 0 ), ( 1, 0 ) ] };
   let scene = SceneSnapshot
@@ -599,7 +610,7 @@ fn rejects_multihex_anchor()
   let err = try_compile( &spec, &scene, &Camera::default() ).unwrap_err();
   assert!
   (
-    matches!( err, CompileError::UnsupportedAnchor { ref anchor, .. } if *anchor == "Multihex" ),
+    matches!( err, CompileError::UnsupportedAnchor { ref anchor, .. } if anchor.starts_with( "Multihex" ) ),
     "expected UnsupportedAnchor/Multihex, got {err:?}",
   );
 }
@@ -1301,7 +1312,7 @@ fn vertex_corners_three_way_blend()
               [
                 TriBlendPattern
                 {
-                  corners : ( "grass".into(), "sand".into(), "water".into() ),
+                  corners : Corners::tri( "grass", "sand", "water" ),
                   sprite_pattern : "tri_gsw_{rot}".into(),
                   priority : 10,
                   animation : None,
@@ -1400,7 +1411,7 @@ fn vertex_corners_wildcard_edge_fade()
               [
                 TriBlendPattern
                 {
-                  corners : ( "*".into(), "*".into(), "void".into() ),
+                  corners : Corners::tri( "*", "*", "void" ),
                   sprite_pattern : "edge_fade_{rot}".into(),
                   priority : 0,
                   animation : None,
@@ -1441,6 +1452,173 @@ fn vertex_corners_wildcard_edge_fade()
   assert!( any_fade, "expected wildcard fade to match at least one triangle; emitted = {emitted:?}" );
 }
 
+// ────────────────────────────────────────────────────────────────────────────
+// Square tilings — Square4 / Square8 neighbours, pixels and quad vertices.
+// ────────────────────────────────────────────────────────────────────────────
+
+/// `wall_spec` switched to a square tiling with a matching atlas layout.
+fn square_wall_spec( tiling : TilingStrategy, layout : AutotileLayout ) -> RenderSpec
+{
+  let mut spec = wall_spec();
+  spec.pipeline.hex = HexConfig::from_hex_size( 32, 32, tiling );
+  let wall = spec.objects.iter_mut().find( | o | o.id == "stone_wall" ).unwrap();
+  let stack = wall.states.get_mut( "default" ).unwrap();
+  if let SpriteSource::NeighborBitmask { source : NeighborBitmaskSource::ByAtlas { layout : l, .. }, .. } = &mut stack[ 0 ].sprite_source
+  {
+    *l = layout;
+  }
+  spec
+}
+
+#[ test ]
+fn square_cells_step_by_stride()
+{
+  let mut spec = minimal_spec();
+  spec.pipeline.hex = HexConfig::from_hex_size( 32, 32, TilingStrategy::Square4 );
+  let scene = SceneSnapshot
+  {
+    tiles : vec!
+    [
+      Tile { pos : ( 0, 0 ), objects : vec![ "grass".into() ] },
+      Tile { pos : ( 1, 0 ), objects : vec![ "grass".into() ] },
+      Tile { pos : ( 0, 1 ), objects : vec![ "grass".into() ] },
+    ],
+    ..minimal_scene_3x3()
+  };
+  let cmds = compile( &spec, &scene, &Camera::default() );
+  let sprites : Vec< _ > = cmds.iter().filter( | c | matches!( c, RenderCommand::Sprite( _ ) ) ).collect();
+  assert_eq!( sprites.len(), 3 );
+  let ( x0, y0 ) = ( sprite_x( sprites[ 0 ] ), sprite_y( sprites[ 0 ] ) );
+  let ( x1, y1 ) = ( sprite_x( sprites[ 1 ] ), sprite_y( sprites[ 1 ] ) );
+  let ( x2, y2 ) = ( sprite_x( sprites[ 2 ] ), sprite_y( sprites[ 2 ] ) );
+  assert!( ( x1 - x0 - 32.0 ).abs() < 1e-3 && ( y1 - y0 ).abs() < 1e-3, "E step: ({x0},{y0}) → ({x1},{y1})" );
+  assert!( ( x2 - x0 ).abs() < 1e-3 && ( y0 - y2 - 32.0 ).abs() < 1e-3, "S step: ({x0},{y0}) → ({x2},{y2})" );
+}
+
+#[ test ]
+fn square4_autotile_two_cell_line()
+{
+  // Square4 order is [N, E, S, W]: (0,0) sees its wall to the E (bit 1),
+  // (1,0) sees its wall to the W (bit 3).
+  let spec = square_wall_spec( TilingStrategy::Square4, AutotileLayout::Bitmask4 );
+  let scene = SceneSnapshot
+  {
+    tiles : vec!
+    [
+      Tile { pos : ( 0, 0 ), objects : vec![ "stone_wall".into() ] },
+      Tile { pos : ( 1, 0 ), objects : vec![ "stone_wall".into() ] },
+    ],
+    ..minimal_scene_3x3()
+  };
+  let compiled = compile_assets( &spec, &PathResolver ).expect( "assets" );
+  let sprite_ids = sprite_ids_from( compile_at_time( &spec, &scene, &Camera::default(), 0.0 ) );
+  assert_eq!( sprite_ids.len(), 2 );
+  assert!( sprite_ids.contains( &compiled.ids.sprite( "walls", "2" ).unwrap() ), "no E mask 2 in {sprite_ids:?}" );
+  assert!( sprite_ids.contains( &compiled.ids.sprite( "walls", "8" ).unwrap() ), "no W mask 8 in {sprite_ids:?}" );
+}
+
+#[ test ]
+fn square8_autotile_connects_diagonally()
+{
+  // Square8 order is [N, NE, E, SE, S, SW, W, NW]: the diagonal pair
+  // (0,0) / (1,1) sets SE (bit 3) and NW (bit 7). Square4 would see
+  // neither.
+  let spec = square_wall_spec( TilingStrategy::Square8, AutotileLayout::Bitmask8 );
+  let scene = SceneSnapshot
+  {
+    tiles : vec!
+    [
+      Tile { pos : ( 0, 0 ), objects : vec![ "stone_wall".into() ] },
+      Tile { pos : ( 1, 1 ), objects : vec![ "stone_wall".into() ] },
+    ],
+    ..minimal_scene_3x3()
+  };
+  let compiled = compile_assets( &spec, &PathResolver ).expect( "assets" );
+  let sprite_ids = sprite_ids_from( compile_at_time( &spec, &scene, &Camera::default(), 0.0 ) );
+  assert!( sprite_ids.contains( &compiled.ids.sprite( "walls", "8" ).unwrap() ), "no SE mask 8 in {sprite_ids:?}" );
+  assert!( sprite_ids.contains( &compiled.ids.sprite( "walls", "128" ).unwrap() ), "no NW mask 128 in {sprite_ids:?}" );
+
+  let spec = square_wall_spec( TilingStrategy::Square4, AutotileLayout::Bitmask4 );
+  let compiled = compile_assets( &spec, &PathResolver ).expect( "assets" );
+  let sprite_ids = sprite_ids_from( compile_at_time( &spec, &scene, &Camera::default(), 0.0 ) );
+  let isolated = compiled.ids.sprite( "walls", "0" ).unwrap();
+  assert!( sprite_ids.iter().all( | id | *id == isolated ), "Square4 must ignore diagonals: {sprite_ids:?}" );
+}
+
+#[ test ]
+fn square_vertex_corners_emit_one_quad_per_corner()
+{
+  // An isolated grass square has four vertices, each with one grass and
+  // three void corners; a four-corner pattern covers every one of them.
+  let mut spec = minimal_spec();
+  spec.pipeline.hex = HexConfig::from_hex_size( 32, 32, TilingStrategy::Square4 );
+  spec.assets.push
+  (
+    Asset
+    {
+      id : "fades".into(),
+      path : "fades.png".into(),
+      kind : atlas_with_frames
+      (
+        8,
+        &[
+          ( "quad_fade_0", ( 0, 0 ) ),
+          ( "quad_fade_1", ( 1, 0 ) ),
+          ( "quad_fade_2", ( 2, 0 ) ),
+          ( "quad_fade_3", ( 3, 0 ) ),
+        ],
+      ),
+      filter : Default::default(),
+      mipmap : Default::default(),
+      wrap : Default::default(),
+    }
+  );
+  let mut fade = grass_with_source( SpriteSource::VertexCorners
+  {
+    patterns : vec!
+    [
+      // A triangle pattern never matches a square vertex.
+      TriBlendPattern
+      {
+        corners : Corners::tri( "*", "*", "void" ),
+        sprite_pattern : "quad_fade_0".into(),
+        priority : 99,
+        animation : None,
+      },
+      TriBlendPattern
+      {
+        corners : Corners::quad( "grass", "void", "void", "void" ),
+        sprite_pattern : "quad_fade_{rot}".into(),
+        priority : 0,
+        animation : None,
+      },
+    ],
+    asset : "fades".into(),
+  });
+  fade.id = "fade".into();
+  spec.objects.push( fade );
+
+  let scene = SceneSnapshot
+  {
+    tiles : vec![ Tile { pos : ( 0, 0 ), objects : vec![ "grass".into(), "fade".into() ] } ],
+    ..minimal_scene_3x3()
+  };
+  let compiled = compile_assets( &spec, &PathResolver ).expect( "assets" );
+  let cmds = compile_at_time( &spec, &scene, &Camera::default(), 0.0 );
+  let fades : Vec< _ > = ( 0..4 ).filter_map( | r | compiled.ids.sprite( "fades", &format!( "quad_fade_{r}" ) ) ).collect();
+  let quads : Vec< _ > = sprite_commands( &cmds ).into_iter().filter( | s | fades.contains( &s.sprite ) ).collect();
+  assert_eq!( quads.len(), 4, "one quad per corner of the isolated cell" );
+
+  // Vertex centres sit half a stride off the cell centre on both axes.
+  let spread = | axis : usize |
+  {
+    let values = quads.iter().map( | s | s.transform.position[ axis ] );
+    values.clone().fold( f32::MIN, f32::max ) - values.fold( f32::MAX, f32::min )
+  };
+  assert!( ( spread( 0 ) - 32.0 ).abs() < 1e-3, "x spread {}", spread( 0 ) );
+  assert!( ( spread( 1 ) - 32.0 ).abs() < 1e-3, "y spread {}", spread( 1 ) );
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // Slice 4 — Edge / FreePos / Viewport anchors.
 // ────────────────────────────────────────────────────────────────────────────
@@ -1914,6 +2092,122 @@ fn viewport_repeat2d_tiles_to_cover_viewport()
   assert_eq!( screen.len(), 13 * 11, "expected full grid of screen-space sprites" );
 }
 
+/// `minimal_spec` plus a 64×64 repeat-wrapped `sea` image drawn by a
+/// `RepeatWorld` viewport object, and a scene showing just that object.
+fn world_sea_spec() -> ( RenderSpec, SceneSnapshot )
+{
+  let mut spec = minimal_spec();
+  spec.assets.push( Asset
+  {
+    id : "sea".into(),
+    path : "sea.png".into(),
+    kind : AssetKind::Single { size : ( 64, 64 ) },
+    filter : SamplerFilter::Linear,
+    mipmap : MipmapMode::Off,
+    wrap : WrapMode::Repeat,
+  });
+  let mut states = HashMap::default();
+  states.insert
+  (
+    "default".into(),
+    vec!
+    [
+      ObjectLayer
+      {
+        id : None,
+        sprite_source : SpriteSource::ViewportTiled
+        {
+          content : Box::new( SpriteSource::Static( SpriteRef { asset : "sea".into(), frame : String::new() } ) ),
+          tiling : ViewportTiling::RepeatWorld,
+          anchor_point : ViewportAnchorPoint::default(),
+        },
+        behaviour : LayerBehaviour::default(),
+        z_in_object : 0,
+        pipeline_layer : None,
+      },
+    ],
+  );
+  spec.objects.push( Object
+  {
+    id : "sea".into(),
+    anchor : Anchor::Viewport,
+    global_layer : "terrain".into(),
+    priority : None,
+    sort_y_source : Default::default(),
+    pivot : ( 0.5, 0.5 ),
+    default_state : "default".into(),
+    states,
+  });
+  let scene = SceneSnapshot
+  {
+    tiles : Vec::new(),
+    viewport_instances : vec![ ViewportInstance { object : "sea".into(), animation : None } ],
+    ..minimal_scene_3x3()
+  };
+  ( spec, scene )
+}
+
+fn mesh_commands( commands : &[ RenderCommand ] ) -> Vec< tilemap_renderer::commands::Mesh >
+{
+  commands.iter().filter_map( | c | if let RenderCommand::Mesh( m ) = c { Some( *m ) } else { None } ).collect()
+}
+
+#[ test ]
+fn viewport_repeat_world_draws_one_textured_mesh()
+{
+  let ( spec, scene ) = world_sea_spec();
+  let compiled = compile_assets( &spec, &PathResolver ).expect( "assets" );
+  let quads = &compiled.world_quads[ "sea" ];
+  assert_eq!( quads.geometries.len(), tilemap_scene::WORLD_QUAD_LODS as usize );
+  assert_eq!( compiled.assets.geometries.len(), quads.geometries.len() );
+
+  let camera = Camera { world_center : ( 0.0, 0.0 ), zoom : 1.0, viewport_size : ( 800, 600 ) };
+  let commands = compile( &spec, &scene, &camera );
+  assert!( screen_space_commands( &commands ).is_empty(), "no per-tile sprites" );
+  let meshes = mesh_commands( &commands );
+  assert_eq!( meshes.len(), 1 );
+  assert_eq!( meshes[ 0 ].texture, Some( compiled.ids.image( "sea" ).unwrap() ) );
+  // View spans world -400..400 × -300..300; the grid origin snaps to
+  // ( -448, -320 ), i.e. screen ( -48, -20 ).
+  assert_eq!( meshes[ 0 ].transform.position, [ -48.0, -20.0 ] );
+  assert!( quads.geometries.contains( &meshes[ 0 ].geometry ) );
+}
+
+#[ test ]
+fn viewport_repeat_world_pans_and_zooms_with_the_camera()
+{
+  let ( spec, scene ) = world_sea_spec();
+  let position = | camera : Camera | mesh_commands( &compile( &spec, &scene, &camera ) )[ 0 ].transform;
+  let base = Camera { world_center : ( 0.0, 0.0 ), zoom : 1.0, viewport_size : ( 800, 600 ) };
+
+  // Half a tile of pan shifts the texture on screen by half a tile…
+  let half = position( Camera { world_center : ( 32.0, 0.0 ), ..base } );
+  assert_eq!( half.position, [ -16.0, -20.0 ] );
+  // …a whole tile brings the same pattern back.
+  let whole = position( Camera { world_center : ( 64.0, 0.0 ), ..base } );
+  assert_eq!( whole.position, position( base ).position );
+
+  let zoomed = position( Camera { zoom : 0.5, ..base } );
+  assert_eq!( zoomed.scale, [ 0.5, 0.5 ] );
+}
+
+#[ test ]
+fn viewport_repeat_world_rejects_atlas_content()
+{
+  let ( mut spec, scene ) = world_sea_spec();
+  let sea = spec.objects.iter_mut().find( | o | o.id == "sea" ).unwrap();
+  let layer = &mut sea.states.get_mut( "default" ).unwrap()[ 0 ];
+  layer.sprite_source = SpriteSource::ViewportTiled
+  {
+    content : Box::new( SpriteSource::Static( SpriteRef { asset : "terrain".into(), frame : "0".into() } ) ),
+    tiling : ViewportTiling::RepeatWorld,
+    anchor_point : ViewportAnchorPoint::default(),
+  };
+  let camera = Camera::default();
+  let err = try_compile( &spec, &scene, &camera ).unwrap_err();
+  assert!( matches!( err, CompileError::UnsupportedAssetKind { ref asset, .. } if asset == "terrain" ), "{err:?}" );
+}
+
 #[ test ]
 fn global_tint_multiplies_into_every_sprite()
 {
@@ -2054,6 +2348,95 @@ fn layer_behaviour_alpha_composes_with_global_tint()
   assert!( ( tint[ 3 ] - 0.5 ).abs() < 1e-5, "alpha = layer.alpha * global.alpha = 0.5: {tint:?}" );
 }
 
+#[ test ]
+fn flat_tint_multiplies_into_layer_sprite()
+{
+  let mut spec = minimal_spec();
+  spec.tints.push( Tint
+  {
+    id : "half_red".into(),
+    color : "#ff0000".into(),
+    strength : 0.5,
+    mode : BlendMode::Multiply,
+  });
+  let stack = spec.objects[ 0 ].states.get_mut( "default" ).expect( "default state" );
+  stack[ 0 ].behaviour.tint = TintBehaviour::Flat( TintRef( "half_red".into() ) );
+
+  let scene = SceneSnapshot
+  {
+    tiles : vec![ Tile { pos : ( 0, 0 ), objects : vec![ "grass".into() ] } ],
+    ..minimal_scene_3x3()
+  };
+  let commands = compile_at_time( &spec, &scene, &Camera::default(), 0.0 );
+  let sprites = sprite_commands( &commands );
+  assert_eq!( sprites.len(), 1 );
+  assert_eq!( sprites[ 0 ].tint, [ 1.0, 0.5, 0.5, 1.0 ], "strength 0.5 pulls red halfway toward white" );
+}
+
+/// Grass with a team-colour mask on its only layer, placed once as an
+/// owned entity and once as an unowned tile.
+fn team_colour_scene( tint : MaskTint ) -> ( RenderSpec, SceneSnapshot )
+{
+  let mut spec = minimal_spec();
+  let stack = spec.objects[ 0 ].states.get_mut( "default" ).expect( "default state" );
+  stack[ 0 ].behaviour.tint = TintBehaviour::Masked
+  {
+    mask : Box::new( SpriteSource::Static( SpriteRef { asset : "terrain".into(), frame : "1".into() } ) ),
+    tint,
+  };
+  let scene = SceneSnapshot
+  {
+    tiles : vec![ Tile { pos : ( 0, 0 ), objects : vec![ "grass".into() ] } ],
+    entities : vec!
+    [
+      Entity { at : ( 1, 0 ), object : "grass".into(), owner : 3, animation : None, facing : None },
+    ],
+    players : vec![ Player { id : 3, color : "#0000ff".into(), name : "Blue".into() } ],
+    ..minimal_scene_3x3()
+  };
+  ( spec, scene )
+}
+
+#[ test ]
+fn masked_team_color_overlays_owned_instances_only()
+{
+  let ( spec, scene ) = team_colour_scene( MaskTint::TeamColor );
+  let compiled = compile_assets( &spec, &PathResolver ).expect( "assets" );
+  let commands = compile_at_time( &spec, &scene, &Camera::default(), 0.0 );
+  let sprites = sprite_commands( &commands );
+
+  assert_eq!( sprites.len(), 3, "unowned tile body + owned entity body + its overlay" );
+  let mask_id = compiled.ids.sprite( "terrain", "1" );
+  let overlays : Vec< _ > = sprites.iter().filter( | s | Some( s.sprite ) == mask_id ).collect();
+  assert_eq!( overlays.len(), 1, "only the owned entity gets a mask overlay" );
+  assert_eq!( overlays[ 0 ].tint, [ 0.0, 0.0, 1.0, 1.0 ], "overlay carries the owner's colour" );
+
+  let body_index = sprites.iter().position( | s | Some( s.sprite ) != mask_id && s.transform.position == overlays[ 0 ].transform.position );
+  let overlay_index = sprites.iter().position( | s | Some( s.sprite ) == mask_id );
+  assert_eq!( body_index.map( | i | i + 1 ), overlay_index, "overlay draws directly above its body" );
+}
+
+#[ test ]
+fn masked_tint_ref_overlays_every_instance()
+{
+  let ( mut spec, scene ) = team_colour_scene( MaskTint::Ref( TintRef( "sepia".into() ) ) );
+  spec.tints.push( Tint { id : "sepia".into(), color : "#704214".into(), strength : 1.0, mode : BlendMode::Multiply } );
+  let compiled = compile_assets( &spec, &PathResolver ).expect( "assets" );
+  let commands = compile_at_time( &spec, &scene, &Camera::default(), 0.0 );
+  let mask_id = compiled.ids.sprite( "terrain", "1" );
+  let overlays = sprite_commands( &commands ).into_iter().filter( | s | Some( s.sprite ) == mask_id ).count();
+  assert_eq!( overlays, 2, "a declared mask tint needs no owner" );
+}
+
+#[ test ]
+fn invalid_player_color_is_a_load_error()
+{
+  let ( spec, mut scene ) = team_colour_scene( MaskTint::TeamColor );
+  scene.players[ 0 ].color = "blue".into();
+  let err = Scene::from_snapshot( &scene, Arc::new( spec ) ).err().expect( "load error" );
+  assert!( matches!( err, tilemap_scene::SnapshotLoadError::InvalidPlayerColor { player : 3, .. } ), "{err}" );
+}
+
 #[ test ]
 fn viewport_layer_behaviour_propagates_to_screen_space_sprite()
 {
@@ -2126,3 +2509,195 @@ fn viewport_layer_behaviour_propagates_to_screen_space_sprite()
     "viewport ScreenSpaceSprite must carry LayerBehaviour.alpha as tint[3], got {:?}", screen[ 0 ].tint,
   );
 }
+
+#[ test ]
+fn set_time_follows_clear_with_scene_clock()
+{
+  let cmds = compile_at_time( &minimal_spec(), &minimal_scene_3x3(), &Camera::default(), 1.25 );
+  assert!( matches!( cmds[ 0 ], RenderCommand::Clear( _ ) ) );
+  match cmds[ 1 ]
+  {
+    RenderCommand::SetTime( t ) => assert!( ( t.seconds - 1.25 ).abs() < 1e-6, "clock: {}", t.seconds ),
+    ref other => panic!( "expected SetTime after Clear, got {other:?}" ),
+  }
+}
+
+#[ test ]
+fn layer_effects_reach_sprite_command()
+{
+  let mut spec = minimal_spec();
+  spec.effects.push( Effect
+  {
+    id : "sway".into(),
+    kind : EffectKind::VertexDisplace { axis : Axis::X, amplitude : 2.0, frequency : 0.5 },
+    phase_offset : PhaseOffset::None,
+  });
+  spec.effects.push( Effect
+  {
+    id : "glow".into(),
+    kind : EffectKind::ColorShift { target : "#00ff00".into(), amplitude : 0.5, frequency : 2.0 },
+    phase_offset : PhaseOffset::None,
+  });
+  let stack = spec.objects[ 0 ].states.get_mut( "default" ).expect( "default state" );
+  stack[ 0 ].behaviour.effects = vec![ EffectRef( "sway".into() ), EffectRef( "glow".into() ) ];
+
+  let scene = SceneSnapshot
+  {
+    tiles : vec![ Tile { pos : ( 0, 0 ), objects : vec![ "grass".into() ] } ],
+    ..minimal_scene_3x3()
+  };
+  let commands = compile_at_time( &spec, &scene, &Camera::default(), 0.0 );
+  let sprites = sprite_commands( &commands );
+  assert_eq!( sprites.len(), 1 );
+  let effects = sprites[ 0 ].effects;
+  assert_eq!
+  (
+    effects.displace,
+    Some( DisplaceEffect { axis : [ 1.0, 0.0 ], amplitude : 2.0, frequency : 0.5, phase : 0.0 } ),
+  );
+  assert_eq!
+  (
+    effects.color_shift,
+    Some( ColorShiftEffect { target : [ 0.0, 1.0, 0.0 ], amplitude : 0.5, frequency : 2.0, phase : 0.0 } ),
+  );
+  assert!( effects.alpha_pulse.is_none() );
+}
+
+#[ test ]
+fn effect_phase_offset_hashcoord_spreads_across_tiles()
+{
+  let mut spec = minimal_spec();
+  spec.effects.push( Effect
+  {
+    id : "flicker".into(),
+    kind : EffectKind::AlphaPulse { min : 0.5, max : 1.0, frequency : 2.0 },
+    phase_offset : PhaseOffset::HashCoord,
+  });
+  let stack = spec.objects[ 0 ].states.get_mut( "default" ).expect( "default state" );
+  stack[ 0 ].behaviour.effects = vec![ EffectRef( "flicker".into() ) ];
+
+  let tiles : Vec< Tile > =
+    ( 0..16 ).map( | q | Tile { pos : ( q, 0 ), objects : vec![ "grass".into() ] } ).collect();
+  let scene = SceneSnapshot { tiles, ..minimal_scene_3x3() };
+  let commands = compile_at_time( &spec, &scene, &Camera::default(), 0.0 );
+  let phases : Vec< f32 > = sprite_commands( &commands ).iter()
+    .map( | s | s.effects.alpha_pulse.expect( "alpha pulse on every tile" ).phase )
+    .collect();
+
+  assert_eq!( phases.len(), 16 );
+  assert!( phases.iter().all( | p | ( 0.0..0.5 ).contains( p ) ), "phase within one period: {phases:?}" );
+  let distinct : std::collections::HashSet< u32 > = phases.iter().map( | p | p.to_bits() ).collect();
+  assert!( distinct.len() >= 2, "HashCoord should spread effect phases; saw {}", distinct.len() );
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Multihex anchor — anchor-cell position, any-cell culling, shape Y-sort.
+// ────────────────────────────────────────────────────────────────────────────
+
+fn castle_spec( shape : Vec< ( i32, i32 ) > ) -> RenderSpec
+{
+  let mut spec = minimal_spec();
+  spec.objects.push( static_object_with_anchor
+  (
+    "castle",
+    Anchor::Multihex { shape },
+    SpriteRef { asset : "terrain".into(), frame : "1".into() },
+  ));
+  spec
+}
+
+fn castle_at( anchor : ( i32, i32 ) ) -> MultihexInstance
+{
+  MultihexInstance { anchor, object : "castle".into(), animation : None }
+}
+
+#[ test ]
+fn multihex_emits_one_sprite_at_anchor_cell()
+{
+  let spec = castle_spec( vec![ ( 0, 0 ), ( 1, 0 ), ( 0, 1 ), ( 1, 1 ) ] );
+  let scene = SceneSnapshot
+  {
+    tiles : Vec::new(),
+    multihex_instances : vec![ castle_at( ( 0, 0 ) ) ],
+    ..minimal_scene_3x3()
+  };
+  let compiled = compile_assets( &spec, &PathResolver ).expect( "assets" );
+  let commands = compile_at_time( &spec, &scene, &Camera::default(), 0.0 );
+  let sprites = sprite_commands( &commands );
+  assert_eq!( sprites.len(), 1, "one sprite covers the whole shape" );
+  assert_eq!( sprites[ 0 ].sprite, compiled.ids.sprite( "terrain", "1" ).unwrap() );
+  // Anchor (0,0) projects to the viewport centre (400, 300); the 72x64
+  // sprite's centre pivot shifts it by (-36, -32).
+  assert!( ( sprites[ 0 ].transform.position[ 0 ] - 364.0 ).abs() < 1e-3 );
+  assert!( ( sprites[ 0 ].transform.position[ 1 ] - 268.0 ).abs() < 1e-3 );
+}
+
+#[ test ]
+fn multihex_is_culled_only_when_every_cell_is_off_screen()
+{
+  // Anchor (-10, 0) sits 720 px left of the viewport centre — off screen.
+  // A shape reaching back to (0, 0) keeps the castle drawn.
+  let scene = SceneSnapshot
+  {
+    tiles : Vec::new(),
+    multihex_instances : vec![ castle_at( ( -10, 0 ) ) ],
+    ..minimal_scene_3x3()
+  };
+  let bridge = castle_spec( vec![ ( 0, 0 ), ( 10, 0 ) ] );
+  assert_eq!( sprite_commands( &compile( &bridge, &scene, &Camera::default() ) ).len(), 1 );
+
+  let tower = castle_spec( vec![ ( 0, 0 ) ] );
+  assert!( sprite_commands( &compile( &tower, &scene, &Camera::default() ) ).is_empty() );
+}
+
+#[ test ]
+fn multihex_bottom_of_shape_sorts_by_lowest_cell()
+{
+  // Grass at (0,1) sits one row below the castle's anchor but above the
+  // shape's bottom cell (0,2). Under YDesc (far rows first) the castle
+  // draws before the grass when sorted by its anchor, after it when
+  // sorted by the bottom of its shape.
+  let draw_order = | sort_y_source : SortYSource |
+  {
+    let mut spec = castle_spec( vec![ ( 0, 0 ), ( 0, 1 ), ( 0, 2 ) ] );
+    spec.pipeline.layers[ 0 ].sort = SortMode::YDesc;
+    spec.objects.iter_mut().find( | o | o.id == "castle" ).unwrap().sort_y_source = sort_y_source;
+    let scene = SceneSnapshot
+    {
+      tiles : vec![ Tile { pos : ( 0, 1 ), objects : vec![ "grass".into() ] } ],
+      multihex_instances : vec![ castle_at( ( 0, 0 ) ) ],
+      ..minimal_scene_3x3()
+    };
+    let compiled = compile_assets( &spec, &PathResolver ).expect( "assets" );
+    let castle = compiled.ids.sprite( "terrain", "1" ).unwrap();
+    sprite_commands( &compile( &spec, &scene, &Camera::default() ) ).iter()
+      .map( | s | s.sprite == castle )
+      .collect::< Vec< _ > >()
+  };
+  assert_eq!( draw_order( SortYSource::Anchor ), vec![ true, false ] );
+  assert_eq!( draw_order( SortYSource::BottomOfShape ), vec![ false, true ] );
+}
+
+#[ test ]
+fn multihex_rejects_neighbour_aware_source()
+{
+  let mut spec = castle_spec( vec![ ( 0, 0 ), ( 1, 0 ) ] );
+  let castle = spec.objects.iter_mut().find( | o | o.id == "castle" ).unwrap();
+  castle.states.get_mut( "default" ).unwrap()[ 0 ].sprite_source = SpriteSource::NeighborBitmask
+  {
+    connects_with : vec![ "castle".into() ],
+    source : NeighborBitmaskSource::ByAtlas { asset : "terrain".into(), layout : AutotileLayout::Bitmask6 },
+  };
+  let scene = SceneSnapshot
+  {
+    tiles : Vec::new(),
+    multihex_instances : vec![ castle_at( ( 0, 0 ) ) ],
+    ..minimal_scene_3x3()
+  };
+  let err = try_compile( &spec, &scene, &Camera::default() ).unwrap_err();
+  assert!
+  (
+    matches!( err, CompileError::UnsupportedSource { ref source_kind, .. } if *source_kind == "NeighborBitmask" ),
+    "expected UnsupportedSource/NeighborBitmask, got {err:?}",
+  );
+}
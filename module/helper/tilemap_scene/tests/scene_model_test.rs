@@ -291,8 +291,8 @@ fn validate_rejects_missing_default_state()
     errs.iter().any( | e | matches!
     (
       e,
-      tilemap_scene::ValidationError::MissingDefaultState { object, state }
-        if object == "grass" && state == "missing"
+      tilemap_scene::ValidationError::MissingDefaultState { object, state, path }
+        if object == "grass" && state == "missing" && path == r#"objects["grass"].default_state"#
     )),
     "expected MissingDefaultState for object 'grass' / state 'missing', got {errs:?}",
   );
@@ -328,8 +328,8 @@ fn validate_rejects_reserved_id()
     errs.iter().any( | e | matches!
     (
       e,
-      tilemap_scene::ValidationError::ReservedId { id }
-        if id == "void"
+      tilemap_scene::ValidationError::ReservedId { id, path }
+        if id == "void" && path == r#"objects["void"].id"#
     )),
     "expected ReservedId for 'void', got {errs:?}",
   );
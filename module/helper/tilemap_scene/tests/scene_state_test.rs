@@ -263,6 +263,41 @@ fn instances_at_hex_returns_only_matching()
   assert_eq!( scene.instances_at_hex( 9, 9 ).count(), 0 );
 }
 
+#[ test ]
+fn multihex_is_indexed_at_every_shape_cell()
+{
+  let mut spec = ( *build_spec() ).clone();
+  let mut castle_states = HashMap::default();
+  castle_states.insert( "default".into(), vec![ make_layer( "terrain", "0" ) ] );
+  spec.objects.push( Object
+  {
+    id : "castle".into(),
+    anchor : Anchor::Multihex { shape : vec![ ( 0, 0 ), ( 1, 0 ), ( 0, 1 ) ] },
+    global_layer : "terrain".into(),
+    priority : None,
+    sort_y_source : Default::default(),
+    pivot : ( 0.5, 0.5 ),
+    default_state : "default".into(),
+    states : castle_states,
+  });
+  let mut scene = Scene::new( Arc::new( spec ) );
+  let castle = scene.object( "castle" ).unwrap();
+  let h = scene.spawn( castle, Placement::Multihex { anchor : ( 2, 2 ) } );
+
+  for ( q, r ) in [ ( 2, 2 ), ( 3, 2 ), ( 2, 3 ) ]
+  {
+    assert_eq!( scene.instances_at_hex( q, r ).collect::< Vec< _ > >(), vec![ h ], "cell ({q},{r})" );
+  }
+  assert_eq!( scene.instances_at_hex( 3, 3 ).count(), 0 );
+
+  // Moving shifts the whole footprint; despawning clears it.
+  scene.move_to( h, Placement::Multihex { anchor : ( 0, 0 ) } );
+  assert_eq!( scene.instances_at_hex( 3, 2 ).count(), 0 );
+  assert_eq!( scene.instances_at_hex( 1, 0 ).collect::< Vec< _ > >(), vec![ h ] );
+  scene.despawn( h );
+  assert!( [ ( 0, 0 ), ( 1, 0 ), ( 0, 1 ) ].iter().all( | &( q, r ) | scene.instances_at_hex( q, r ).count() == 0 ) );
+}
+
 // ────────────────────────────────────────────────────────────────────────────
 // Mutation API persistence
 // ────────────────────────────────────────────────────────────────────────────
@@ -308,6 +343,24 @@ fn set_tint_and_phase_offset_persist()
   assert_eq!( scene.instance( h ).unwrap().phase_offset, None );
 }
 
+#[ test ]
+fn owner_and_player_palette_round_trip()
+{
+  let mut scene = Scene::new( build_spec() );
+  let knight = scene.object( "knight" ).unwrap();
+  let h = scene.spawn( knight, Placement::Hex { q : 0, r : 0 } );
+
+  assert_eq!( scene.instance( h ).unwrap().owner, None, "fresh instances are unowned" );
+  scene.set_owner( h, Some( 2 ) );
+  assert_eq!( scene.instance( h ).unwrap().owner, Some( 2 ) );
+
+  assert_eq!( scene.player_color( 2 ), None );
+  scene.set_player_color( 2, Some( [ 0.0, 0.0, 1.0, 1.0 ] ) );
+  assert_eq!( scene.player_color( 2 ), Some( [ 0.0, 0.0, 1.0, 1.0 ] ) );
+  scene.set_player_color( 2, None );
+  assert_eq!( scene.player_color( 2 ), None );
+}
+
 #[ test ]
 fn external_sprite_round_trip()
 {
@@ -439,6 +492,10 @@ fn every_mutator_bumps_revision_exactly_once()
   scene.set_tint( h, Some( [ 0.5, 1.0, 1.0, 1.0 ] ) );
   check( &scene, "set_tint" );
 
+  // set_owner
+  scene.set_owner( h, Some( 1 ) );
+  check( &scene, "set_owner" );
+
   // set_phase_offset
   scene.set_phase_offset( h, Some( 0.25 ) );
   check( &scene, "set_phase_offset" );
@@ -452,6 +509,10 @@ fn every_mutator_bumps_revision_exactly_once()
   scene.set_global_tint( Some( tilemap_scene::TintRef( "foo".into() ) ) );
   check( &scene, "set_global_tint" );
 
+  // set_player_color
+  scene.set_player_color( 1, Some( [ 1.0, 0.0, 0.0, 1.0 ] ) );
+  check( &scene, "set_player_color" );
+
   // set_seed
   scene.set_seed( 42 );
   check( &scene, "set_seed" );
@@ -515,3 +576,44 @@ fn set_state_with_foreign_state_does_not_bump_revision()
     "rejected set_state must not bump revision",
   );
 }
+
+// ────────────────────────────────────────────────────────────────────────────
+// Change log — `changes_since` names the handles a renderer must re-emit.
+// ────────────────────────────────────────────────────────────────────────────
+
+#[ test ]
+fn changes_since_lists_mutated_handles_in_order()
+{
+  let mut scene = Scene::new( build_spec() );
+  let grass = scene.object( "grass" ).unwrap();
+  let a = scene.spawn( grass, Placement::Hex { q : 0, r : 0 } );
+  let b = scene.spawn( grass, Placement::Hex { q : 1, r : 0 } );
+  let spawned : Vec< _ > = scene.changes_since( 0 ).expect( "log covers spawns" ).collect();
+  assert_eq!( spawned, vec![ a, b ] );
+
+  let r = scene.revision();
+  assert_eq!( scene.changes_since( r ).expect( "nothing since now" ).count(), 0 );
+
+  scene.set_tint( b, Some( [ 0.5, 0.5, 0.5, 1.0 ] ) );
+  scene.move_to( a, Placement::Hex { q : 2, r : 0 } );
+  scene.despawn( b );
+  let changed : Vec< _ > = scene.changes_since( r ).expect( "per-instance changes only" ).collect();
+  assert_eq!( changed, vec![ b, a, b ], "one entry per mutation, despawns included" );
+}
+
+#[ test ]
+fn scene_wide_setters_invalidate_earlier_revisions()
+{
+  let mut scene = Scene::new( build_spec() );
+  let grass = scene.object( "grass" ).unwrap();
+  scene.spawn( grass, Placement::Hex { q : 0, r : 0 } );
+  let before = scene.revision();
+
+  scene.set_seed( 7 );
+  assert!( scene.changes_since( before ).is_none(), "set_seed can touch every instance" );
+  let after = scene.revision();
+  assert_eq!( scene.changes_since( after ).expect( "clean again" ).count(), 0 );
+
+  scene.set_player_color( 1, Some( [ 1.0, 0.0, 0.0, 1.0 ] ) );
+  assert!( scene.changes_since( after ).is_none() );
+}
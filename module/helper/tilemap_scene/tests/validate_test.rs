@@ -0,0 +1,528 @@
+//! SPEC §16 validation of `RenderSpec` — duplicate ids, reference
+//! resolution, source nesting, anchor compatibility and source ↔ tiling
+//! fit. Every violation must be reported in one pass, each with a
+//! path into the spec.
+
+#![ allow( clippy::min_ident_chars ) ]
+
+use tilemap_scene::{ RenderSpec, Validate, ValidationError };
+
+/// Wraps `objects` and `extra` top-level fields in a spec that is otherwise
+/// valid: one atlas `"terrain"`, one tint `"night"`, one animation `"wave"`,
+/// one effect `"sway"`, pipeline layers `"terrain"` / `"sky"`.
+fn spec_with( objects : &str, extra : &str, tiling : &str ) -> RenderSpec
+{
+  let text = format!
+  (
+    r##"
+    RenderSpec(
+        version: "0.2.0",
+        assets: [
+            Asset( id: "terrain", path: "t.png", kind: Atlas( tile_size: ( 72, 64 ), columns: 8 ) ),
+        ],
+        tints: [ Tint( id: "night", color: "#203060", strength: 0.5 ) ],
+        animations: [
+            Animation(
+                id: "wave",
+                timing: Regular( frames: [ ( "terrain", "0" ), ( "terrain", "1" ) ], fps: 4.0 ),
+                mode: Loop,
+            ),
+        ],
+        effects: [ Effect( id: "sway", kind: AlphaPulse( min: 0.5, max: 1.0, frequency: 1.0 ) ) ],
+        objects: [ {objects} ],
+        pipeline: (
+            hex: ( tiling: {tiling}, grid_stride: ( 72, 64 ) ),
+            layers: [ ( id: "terrain" ), ( id: "sky" ) ],
+            {extra}
+        ),
+    )
+    "##
+  );
+  ron::from_str( &text ).expect( "spec parses" )
+}
+
+const GRASS : &str = r#"
+  Object(
+      id: "grass",
+      anchor: Hex,
+      global_layer: "terrain",
+      states: { "default": [ ( sprite_source: Static( ( "terrain", "0" ) ) ) ] },
+  ),
+"#;
+
+fn errors_of( spec : &RenderSpec ) -> Vec< ValidationError >
+{
+  spec.validate().expect_err( "spec must be rejected" )
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Baseline
+// ────────────────────────────────────────────────────────────────────────────
+
+#[ test ]
+fn every_reference_kind_resolving_validates_clean()
+{
+  // Exercises tint / effect / animation refs, connects_with incl. "void",
+  // a Variant inside a ByMapping slot and a Viewport layer in one spec.
+  let spec = spec_with
+  (
+    &format!( r#"{GRASS}
+      Object(
+          id: "wall",
+          anchor: Hex,
+          global_layer: "terrain",
+          states: {{ "default": [ (
+              sprite_source: NeighborBitmask(
+                  connects_with: [ "wall", "void" ],
+                  source: ByMapping(
+                      mapping: {{ 0: Variant(
+                          variants: [ ( sprite: Animation( ( "wave" ) ), weight: 1 ) ],
+                          selection: HashCoord,
+                      ) }},
+                      fallback: Static( ( "terrain", "2" ) ),
+                  ),
+              ),
+              behaviour: ( tint: Flat( ( "night" ) ), effects: [ ( "sway" ) ] ),
+          ) ] }},
+      ),
+      Object(
+          id: "clouds",
+          anchor: Viewport,
+          global_layer: "sky",
+          states: {{ "default": [ (
+              sprite_source: ViewportTiled(
+                  content: Static( ( "terrain", "3" ) ),
+                  tiling: Repeat2D,
+                  anchor_point: Center,
+              ),
+          ) ] }},
+      ),
+    "# ),
+    r#"global_tint: Some( ( "night" ) ),"#,
+    "HexFlatTop",
+  );
+  spec.validate().expect( "fully resolved spec validates clean" );
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Duplicate ids and unresolved refs
+// ────────────────────────────────────────────────────────────────────────────
+
+#[ test ]
+fn duplicate_object_id_is_reported_by_id()
+{
+  let spec = spec_with( &format!( "{GRASS}{GRASS}" ), "", "HexFlatTop" );
+  let errs = errors_of( &spec );
+  assert!
+  (
+    errs.iter().any( | e | matches!
+    (
+      e,
+      ValidationError::DuplicateId { kind, id, path }
+        if *kind == "object" && id == "grass" && path == r#"objects["grass"]"#
+    )),
+    "expected DuplicateId at objects[\"grass\"], got {errs:?}",
+  );
+}
+
+#[ test ]
+fn unresolved_tint_animation_effect_refs_carry_paths()
+{
+  let spec = spec_with
+  (
+    r#"
+      Object(
+          id: "knight",
+          anchor: FreePos,
+          global_layer: "terrain",
+          states: { "default": [ (
+              sprite_source: Animation( ( "ghost_walk" ) ),
+              behaviour: (
+                  tint: Masked( mask: Static( ( "terrain", "9" ) ), tint: Ref( ( "dusk" ) ) ),
+                  effects: [ ( "sway" ), ( "wobble" ) ],
+              ),
+          ) ] },
+      ),
+    "#,
+    r#"global_tint: Some( ( "noon" ) ),"#,
+    "HexFlatTop",
+  );
+  let errs = errors_of( &spec );
+  let layer = r#"objects["knight"].states["default"][0]"#;
+  let expected =
+  [
+    ( "animation", "ghost_walk", format!( "{layer}.sprite_source" ) ),
+    ( "tint", "dusk", format!( "{layer}.behaviour.tint.tint" ) ),
+    ( "effect", "wobble", format!( "{layer}.behaviour.effects[1]" ) ),
+    ( "tint", "noon", "pipeline.global_tint".to_owned() ),
+  ];
+  for ( want_kind, want_id, want_path ) in expected
+  {
+    assert!
+    (
+      errs.iter().any( | e | matches!
+      (
+        e,
+        ValidationError::UnresolvedRef { kind, id, path }
+          if *kind == want_kind && id == want_id && *path == want_path
+      )),
+      "expected unresolved {want_kind} {want_id:?} at {want_path}, got {errs:?}",
+    );
+  }
+  assert_eq!( errs.len(), 4, "exactly the four bad refs are reported: {errs:?}" );
+}
+
+#[ test ]
+fn connects_with_must_name_object_or_void()
+{
+  let spec = spec_with
+  (
+    &format!( r#"{GRASS}
+      Object(
+          id: "road",
+          anchor: Hex,
+          global_layer: "terrain",
+          states: {{ "default": [ (
+              sprite_source: NeighborBitmask(
+                  connects_with: [ "road", "void", "bridge" ],
+                  source: ByAtlas( asset: "terrain", layout: Bitmask6 ),
+              ),
+          ) ] }},
+      ),
+    "# ),
+    "",
+    "HexFlatTop",
+  );
+  let errs = errors_of( &spec );
+  assert_eq!( errs.len(), 1, "{errs:?}" );
+  assert!
+  (
+    matches!
+    (
+      &errs[ 0 ],
+      ValidationError::UnresolvedRef { kind, id, path }
+        if *kind == "object" && id == "bridge"
+          && path == r#"objects["road"].states["default"][0].sprite_source.connects_with[2]"#
+    ),
+    "{errs:?}",
+  );
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Source nesting and anchor compatibility
+// ────────────────────────────────────────────────────────────────────────────
+
+#[ test ]
+fn composite_inside_composite_slot_is_rejected_through_variant()
+{
+  // NeighborCondition hidden behind a Variant inside a ByMapping slot.
+  let spec = spec_with
+  (
+    &format!( r#"{GRASS}
+      Object(
+          id: "wall",
+          anchor: Hex,
+          global_layer: "terrain",
+          states: {{ "default": [ (
+              sprite_source: NeighborBitmask(
+                  connects_with: [ "wall" ],
+                  source: ByMapping(
+                      mapping: {{}},
+                      fallback: Variant(
+                          variants: [ ( sprite: NeighborCondition(
+                              condition: NoNeighbor,
+                              sides: [ N ],
+                              sprite_pattern: "skirt_{{dir}}",
+                              asset: "terrain",
+                          ), weight: 1 ) ],
+                          selection: HashCoord,
+                      ),
+                  ),
+              ),
+          ) ] }},
+      ),
+    "# ),
+    "",
+    "HexFlatTop",
+  );
+  let errs = errors_of( &spec );
+  assert_eq!( errs.len(), 1, "{errs:?}" );
+  assert!
+  (
+    matches!
+    (
+      &errs[ 0 ],
+      ValidationError::IllegalSourceNesting { outer, inner, path }
+        if *outer == "NeighborBitmask" && *inner == "NeighborCondition"
+          && path.ends_with( ".sprite_source.source.fallback.variants[0].sprite" )
+    ),
+    "{errs:?}",
+  );
+}
+
+#[ test ]
+fn anchor_source_mismatches_are_reported_per_layer()
+{
+  // Edge object using a hex-only autotile; FreePos object using a
+  // vertex blend inside a Variant; Viewport object with a plain Static.
+  let spec = spec_with
+  (
+    &format!( r#"{GRASS}
+      Object(
+          id: "fence",
+          anchor: Edge,
+          global_layer: "terrain",
+          states: {{ "default": [ (
+              sprite_source: NeighborBitmask(
+                  connects_with: [ "fence" ],
+                  source: ByAtlas( asset: "terrain", layout: Bitmask6 ),
+              ),
+          ) ] }},
+      ),
+      Object(
+          id: "spark",
+          anchor: FreePos,
+          global_layer: "terrain",
+          states: {{ "default": [ (
+              sprite_source: Variant(
+                  variants: [
+                      ( sprite: Static( ( "terrain", "0" ) ), weight: 1 ),
+                      ( sprite: VertexCorners( patterns: [], asset: "terrain" ), weight: 1 ),
+                  ],
+                  selection: HashCoord,
+              ),
+          ) ] }},
+      ),
+      Object(
+          id: "sky",
+          anchor: Viewport,
+          global_layer: "sky",
+          states: {{ "default": [ ( sprite_source: Static( ( "terrain", "0" ) ) ) ] }},
+      ),
+    "# ),
+    "",
+    "HexFlatTop",
+  );
+  let errs = errors_of( &spec );
+  let mismatches : Vec< _ > = errs.iter().filter_map( | e | match e
+  {
+    ValidationError::AnchorSourceMismatch { anchor, source_kind, path } =>
+      Some( ( *anchor, *source_kind, path.as_str() ) ),
+    _ => None,
+  }).collect();
+  assert_eq!
+  (
+    mismatches,
+    vec!
+    [
+      ( "Edge", "NeighborBitmask", r#"objects["fence"].states["default"][0].sprite_source"# ),
+      ( "FreePos", "VertexCorners", r#"objects["spark"].states["default"][0].sprite_source.variants[1].sprite"# ),
+      ( "Viewport", "Static", r#"objects["sky"].states["default"][0].sprite_source"# ),
+    ],
+  );
+}
+
+// ────────────────────────────────────────────────────────────────────────────
+// Source ↔ tiling fit and collect-all behaviour
+// ────────────────────────────────────────────────────────────────────────────
+
+#[ test ]
+fn square_sources_fit_square_tilings()
+{
+  let spec = spec_with
+  (
+    &format!( r#"{GRASS}
+      Object(
+          id: "wall",
+          anchor: Hex,
+          global_layer: "terrain",
+          states: {{ "default": [
+              (
+                  sprite_source: NeighborBitmask(
+                      connects_with: [ "wall" ],
+                      source: ByAtlas( asset: "terrain", layout: Bitmask4 ),
+                  ),
+              ),
+              (
+                  sprite_source: NeighborCondition(
+                      condition: NoNeighbor,
+                      sides: [ N, E, S, W ],
+                      sprite_pattern: "edge_{{dir}}",
+                      asset: "terrain",
+                  ),
+              ),
+          ] }},
+      ),
+      Object(
+          id: "blend",
+          anchor: Vertex,
+          global_layer: "terrain",
+          states: {{ "default": [ (
+              sprite_source: VertexCorners(
+                  patterns: [ ( corners: ( "grass", "wall", "*", "*" ), sprite_pattern: "q_{{rot}}" ) ],
+                  asset: "terrain",
+              ),
+          ) ] }},
+      ),
+    "# ),
+    "",
+    "Square4",
+  );
+  spec.validate().expect( "square-shaped sources validate clean on Square4" );
+}
+
+#[ test ]
+fn sources_not_fitting_the_tiling_are_reported()
+{
+  // Hex-width atlas, a diagonal side and a triangle pattern on Square4,
+  // plus an edge autotile, which square tilings have no edges for.
+  let spec = spec_with
+  (
+    &format!( r#"{GRASS}
+      Object(
+          id: "wall",
+          anchor: Hex,
+          global_layer: "terrain",
+          states: {{ "default": [
+              (
+                  sprite_source: NeighborBitmask(
+                      connects_with: [ "wall" ],
+                      source: ByAtlas( asset: "terrain", layout: Bitmask6 ),
+                  ),
+              ),
+              (
+                  sprite_source: NeighborCondition(
+                      condition: NoNeighbor,
+                      sides: [ N, NE ],
+                      sprite_pattern: "edge_{{dir}}",
+                      asset: "terrain",
+                  ),
+              ),
+          ] }},
+      ),
+      Object(
+          id: "blend",
+          anchor: Vertex,
+          global_layer: "terrain",
+          states: {{ "default": [ (
+              sprite_source: VertexCorners(
+                  patterns: [ ( corners: ( "grass", "wall", "*" ), sprite_pattern: "t_{{rot}}" ) ],
+                  asset: "terrain",
+              ),
+          ) ] }},
+      ),
+      Object(
+          id: "fence",
+          anchor: Edge,
+          global_layer: "terrain",
+          states: {{ "default": [ (
+              sprite_source: EdgeConnectedBitmask(
+                  connects_with: [ "fence" ],
+                  source: ByAtlas( asset: "terrain", layout: Bitmask6 ),
+                  layout: EdgeHex,
+              ),
+          ) ] }},
+      ),
+    "# ),
+    "",
+    "Square4",
+  );
+  let rendered : Vec< String > = errors_of( &spec ).iter().map( ToString::to_string ).collect();
+  assert_eq!
+  (
+    rendered,
+    vec!
+    [
+      r#"objects["wall"].states["default"][0].sprite_source.source.layout: autotile layout Bitmask6 does not fit tiling Square4"#,
+      r#"objects["wall"].states["default"][1].sprite_source.sides[1]: side NE does not fit tiling Square4"#,
+      r#"objects["blend"].states["default"][0].sprite_source.patterns[0].corners: 3-corner pattern does not fit tiling Square4"#,
+      r#"objects["fence"].states["default"][0].sprite_source: EdgeConnectedBitmask does not fit tiling Square4"#,
+    ],
+  );
+}
+
+#[ test ]
+fn repeat_world_content_must_be_a_repeat_wrapped_single_image()
+{
+  let world_layer = | id : &str, content : &str | format!( r#"
+      Object(
+          id: "{id}",
+          anchor: Viewport,
+          global_layer: "sky",
+          states: {{ "default": [ (
+              sprite_source: ViewportTiled( content: {content}, tiling: RepeatWorld, anchor_point: Center ),
+          ) ] }},
+      ),"# );
+  let mut spec = spec_with
+  (
+    &[
+      world_layer( "sea", r#"Static( ( "sea", "" ) )"# ),
+      world_layer( "fog", r#"Animation( ( "wave" ) )"# ),
+      world_layer( "sand", r#"Static( ( "terrain", "0" ) )"# ),
+    ].concat(),
+    "",
+    "HexFlatTop",
+  );
+  spec.assets.push( ron::from_str( r#"Asset( id: "sea", path: "sea.png", kind: Single( size: ( 64, 64 ) ) )"# ).unwrap() );
+
+  let rendered : Vec< String > = errors_of( &spec ).iter().map( ToString::to_string ).collect();
+  let suffix = "; RepeatWorld needs a Static ref to a Single asset with wrap Repeat";
+  assert_eq!
+  (
+    rendered,
+    vec!
+    [
+      format!( r#"objects["sea"].states["default"][0].sprite_source.content: asset "sea" does not declare wrap Repeat{suffix}"# ),
+      format!( r#"objects["fog"].states["default"][0].sprite_source.content: Animation content{suffix}"# ),
+      format!( r#"objects["sand"].states["default"][0].sprite_source.content: asset "terrain" is not Single{suffix}"# ),
+    ],
+  );
+
+  spec.objects.truncate( 1 );
+  spec.assets[ 1 ].wrap = tilemap_renderer::types::WrapMode::Repeat;
+  spec.validate().expect( "repeat-wrapped Single image tiles in world space" );
+}
+
+#[ test ]
+fn all_violations_are_reported_together()
+{
+  // Unknown layer, missing default state, reserved id, duplicate tint and
+  // an unresolved global tint — five independent rules in one pass.
+  let spec : RenderSpec = ron::from_str( r##"
+    RenderSpec(
+        version: "0.2.0",
+        tints: [
+            Tint( id: "night", color: "#203060", strength: 0.5 ),
+            Tint( id: "night", color: "#000000", strength: 1.0 ),
+        ],
+        objects: [
+            Object(
+                id: "void",
+                anchor: Hex,
+                global_layer: "ghost",
+                default_state: "idle",
+                states: { "default": [] },
+            ),
+        ],
+        pipeline: (
+            hex: ( tiling: Square8, grid_stride: ( 32, 32 ) ),
+            layers: [ ( id: "terrain" ) ],
+            global_tint: Some( ( "dusk" ) ),
+        ),
+    )
+  "## ).expect( "spec parses" );
+  let errs = errors_of( &spec );
+  let rendered : Vec< String > = errs.iter().map( ToString::to_string ).collect();
+  assert_eq!
+  (
+    rendered,
+    vec!
+    [
+      r#"tints["night"]: duplicate tint id "night""#,
+      r#"objects["void"].id: reserved id used in declaration"#,
+      r#"objects["void"].global_layer: unresolved pipeline layer reference "ghost""#,
+      r#"objects["void"].default_state: "idle" is not an entry of states"#,
+      r#"pipeline.global_tint: unresolved tint reference "dusk""#,
+    ],
+  );
+}
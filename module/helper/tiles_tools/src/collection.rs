@@ -2,6 +2,7 @@
 //! mapped to hexagonal coordinates. It is built upon `ndarray` and supports arbitrary
 //! coordinate ranges and different hexagonal systems and orientations.
 
+use crate::coordinates::{ hexagonal, isometric, square, triangular };
 use crate::coordinates::hexagonal::Coordinate;
 use ndarray_cg::nd::iter::IterMut;
 use ndarray_cg::{ Array2, I64x2, nd::iter::Iter };
@@ -9,6 +10,79 @@ use std::fmt::Debug;
 use std::marker::PhantomData;
 use std::ops::{ Index, IndexMut };
 
+/// Maps a coordinate of any grid system onto a dense `( column, row )` cell.
+///
+/// `Grid2D` addresses its storage by hexagonal `( q, r )` pairs. This trait lets
+/// algorithms that store per-cell data in a `Grid2D` (such as flow fields) accept
+/// square, isometric and triangular coordinates as well. The mapping must be a
+/// bijection, so that `from_cell( to_cell( c ) ) == c`.
+pub trait GridCell : Sized
+{
+  /// Returns the `( column, row )` cell occupied by this coordinate.
+  fn to_cell( &self ) -> ( i32, i32 );
+
+  /// Reconstructs the coordinate that occupies the `( column, row )` cell.
+  fn from_cell( column : i32, row : i32 ) -> Self;
+}
+
+impl< System, Orientation > GridCell for hexagonal::Coordinate< System, Orientation >
+{
+  fn to_cell( &self ) -> ( i32, i32 )
+  {
+    ( self.q, self.r )
+  }
+
+  fn from_cell( column : i32, row : i32 ) -> Self
+  {
+    Self::new_uncheked( column, row )
+  }
+}
+
+impl< Connectivity > GridCell for square::Coordinate< Connectivity >
+{
+  fn to_cell( &self ) -> ( i32, i32 )
+  {
+    ( self.x, self.y )
+  }
+
+  fn from_cell( column : i32, row : i32 ) -> Self
+  {
+    Self::new( column, row )
+  }
+}
+
+impl< Projection > GridCell for isometric::Coordinate< Projection >
+{
+  fn to_cell( &self ) -> ( i32, i32 )
+  {
+    ( self.x, self.y )
+  }
+
+  fn from_cell( column : i32, row : i32 ) -> Self
+  {
+    Self::new( column, row )
+  }
+}
+
+/// Triangles come in pairs sharing the same `( a, b )`: the `c` component is
+/// implied by whether the triangle points up/right (`a + b + c == 2`) or
+/// down/left (`a + b + c == 1`). Each pair therefore occupies two adjacent columns.
+impl< Orientation > GridCell for triangular::Coordinate< Orientation >
+{
+  fn to_cell( &self ) -> ( i32, i32 )
+  {
+    let upper = i32::from( self.is_up_or_right() );
+    ( self.a * 2 + upper, self.b )
+  }
+
+  fn from_cell( column : i32, row : i32 ) -> Self
+  {
+    let a = column.div_euclid( 2 );
+    let sum = column.rem_euclid( 2 ) + 1;
+    Self::new_unchecked( a, row, sum - a - row )
+  }
+}
+
 /// A generic 2D grid for storing data associated with hexagonal coordinates.
 ///
 /// The grid is defined by a rectangular region of coordinates and can store any type `T`.
@@ -54,6 +128,18 @@ impl< System, Orientation, T > Grid2D< System, Orientation, T >
     }
   }
 
+  /// Returns `true` if the coordinate lies within the grid's bounds.
+  pub fn in_bounds< C >( &self, coord : C ) -> bool
+  where
+    C : Into< Coordinate< System, Orientation > >,
+  {
+    let coord : Coordinate< System, Orientation > = coord.into();
+    let ( rows, columns ) = self.data.dim();
+    let i = coord.r as i64 - self.min[ 1 ];
+    let j = coord.q as i64 - self.min[ 0 ];
+    ( 0..rows as i64 ).contains( &i ) && ( 0..columns as i64 ).contains( &j )
+  }
+
   /// Returns an iterator over the values in the grid.
   pub fn iter( &self ) -> Iter< '_, T, ndarray_cg::Dim< [ usize; 2 ] > >
   {
@@ -6,7 +6,7 @@
 //!
 //! # Component Categories
 //!
-//! - **Spatial**: Position, Movement capabilities, Size/Shape
+//! - **Spatial**: Position, Movement capabilities and intents, Size/Shape, Obstacles
 //! - **Gameplay**: Health, Stats, Inventory, Teams
 //! - **Visual**: Sprites, Animations, Visibility
 //! - **Behavioral**: AI, Player control, Triggers
@@ -194,6 +194,87 @@ impl Size
   }
 }
 
+/// Marker component for static entities that block the tile they stand on.
+///
+/// Walls, rocks and similar scenery carry this component. Movement intents
+/// route around obstacles unless the mover can pass through them.
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize ) ]
+pub struct Obstacle;
+
+/// Marker component for entities that occupy their tile.
+///
+/// Units carry this component so that two of them never end up on the same
+/// tile. Unlike obstacles, occupants move, so a path is only planned again
+/// when an occupant stands on its next step.
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize ) ]
+pub struct Occupant;
+
+/// Movement intent component asking the world to walk an entity to a target.
+///
+/// Intents are resolved by `World::update`: the entity advances one tile per
+/// update along a path planned with `pathfind::astar_advanced`. The intent is
+/// removed once the entity arrives or movement fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use tiles_tools::ecs::MoveIntent;
+/// use tiles_tools::coordinates::square::{ Coordinate as SquareCoord, FourConnected };
+///
+/// let intent = MoveIntent::new( SquareCoord::< FourConnected >::new( 4, 2 ) ).with_max_replans( 3 );
+/// assert!( intent.path.is_empty() );
+/// ```
+#[ derive( Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize ) ]
+pub struct MoveIntent< C >
+{
+  /// Coordinate the entity is heading to
+  pub target : C,
+  /// Remaining steps of the planned path, next step first
+  pub path : Vec< C >,
+  /// Number of times the path was planned again after being blocked
+  pub replans : u32,
+  /// Number of re-plans allowed before movement fails
+  pub max_replans : u32,
+  /// How much farther than the target the planner may search, which keeps
+  /// unreachable targets from searching an unbounded grid forever
+  pub max_detour : u32,
+}
+
+impl< C > MoveIntent< C >
+{
+  /// Default number of re-plans allowed for a single intent.
+  pub const DEFAULT_MAX_REPLANS : u32 = 8;
+  /// Default search distance allowed beyond the target.
+  pub const DEFAULT_MAX_DETOUR : u32 = 16;
+
+  /// Creates an intent to move to the specified coordinate.
+  pub fn new( target : C ) -> Self
+  {
+    Self
+    {
+      target,
+      path : Vec::new(),
+      replans : 0,
+      max_replans : Self::DEFAULT_MAX_REPLANS,
+      max_detour : Self::DEFAULT_MAX_DETOUR,
+    }
+  }
+
+  /// Sets how many times the path may be planned again after being blocked.
+  pub fn with_max_replans( mut self, max_replans : u32 ) -> Self
+  {
+    self.max_replans = max_replans;
+    self
+  }
+
+  /// Sets how much farther than the target the planner may search.
+  pub fn with_max_detour( mut self, max_detour : u32 ) -> Self
+  {
+    self.max_detour = max_detour;
+    self
+  }
+}
+
 // =============================================================================
 // Gameplay Components
 // =============================================================================
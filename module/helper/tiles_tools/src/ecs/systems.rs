@@ -20,8 +20,9 @@
 
 use crate::ecs::components::*;
 use crate::coordinates::{Distance, Neighbors};
-use crate::pathfind::astar;
-use std::collections::HashMap;
+use crate::pathfind::{astar, astar_advanced, PathfindingConfig};
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 
 // =============================================================================
 // Movement Systems
@@ -66,6 +67,143 @@ impl MovementSystem {
     results
   }
 
+  /// Advances every movement intent for coordinate type `C` by one step.
+  ///
+  /// Entities with a `Position<C>`, `Movable` and `MoveIntent<C>` move one tile
+  /// per call along a path planned with [`astar_advanced`]. Tiles holding an
+  /// [`Obstacle`] or another [`Occupant`] are avoided, as allowed by
+  /// [`Movable`], and the search stays within `MoveIntent::max_detour` tiles
+  /// beyond the target. When the planned path gets blocked it is planned again,
+  /// up to `MoveIntent::max_replans` times. If only occupants block every route,
+  /// the entity waits in place for them to move on, which also uses up a re-plan.
+  /// Intents are removed from entities that arrive or fail.
+  pub fn process_intents<C>(world: &mut hecs::World) -> Vec<MovementUpdate>
+  where
+    C: Distance + Neighbors + Clone + Eq + Hash + Send + Sync + 'static,
+  {
+    let obstacles: HashSet<C> = world
+      .query::<(&Position<C>, &Obstacle)>()
+      .iter()
+      .map(|(_, (position, _))| position.coord.clone())
+      .collect();
+    let mut occupants: HashMap<C, hecs::Entity> = world
+      .query::<(&Position<C>, &Occupant)>()
+      .iter()
+      .map(|(entity, (position, _))| (position.coord.clone(), entity))
+      .collect();
+    let movers: Vec<hecs::Entity> = world
+      .query::<(&Position<C>, &MoveIntent<C>)>()
+      .iter()
+      .map(|(entity, _)| entity)
+      .collect();
+
+    let mut updates = Vec::new();
+    let mut finished = Vec::new();
+
+    for entity in movers {
+      let Ok((position, movable, intent)) =
+        world.query_one_mut::<(&mut Position<C>, Option<&Movable>, &mut MoveIntent<C>)>(entity)
+      else {
+        continue;
+      };
+
+      let Some(movable) = movable.copied() else {
+        updates.push(MovementUpdate::Failed { entity, reason: MovementFailure::NotMovable });
+        finished.push(entity);
+        continue;
+      };
+
+      if position.coord == intent.target {
+        updates.push(MovementUpdate::Arrived { entity });
+        finished.push(entity);
+        continue;
+      }
+
+      let is_obstacle = |coord: &C| !movable.can_pass_through_obstacles && obstacles.contains(coord);
+      let is_occupied = |coord: &C| {
+        !movable.can_pass_through_entities
+          && occupants.get(coord).is_some_and(|occupant| *occupant != entity)
+      };
+
+      let blocked = match intent.path.first() {
+        Some(next) => is_occupied(next) || intent.path.iter().any(is_obstacle),
+        None => false,
+      };
+
+      if blocked {
+        intent.replans += 1;
+        if intent.replans > intent.max_replans {
+          updates.push(MovementUpdate::Failed { entity, reason: MovementFailure::Blocked });
+          finished.push(entity);
+          continue;
+        }
+      }
+
+      if intent.path.is_empty() || blocked {
+        let reach = position.coord.distance(&intent.target).saturating_add(intent.max_detour);
+        let mut static_config = PathfindingConfig::new().with_max_distance(reach);
+        if !movable.can_pass_through_obstacles {
+          static_config = static_config.with_obstacles(obstacles.iter().cloned());
+        }
+        let mut config = static_config.clone();
+        if !movable.can_pass_through_entities {
+          for (coord, occupant) in &occupants {
+            if *occupant != entity {
+              config = config.with_blocking_entity(coord.clone(), occupant.id());
+            }
+          }
+        }
+
+        let Some((path, _cost)) = astar_advanced(&position.coord, &intent.target, &config) else {
+          // Occupants move on, so a route blocked only by them is worth waiting for
+          let Some((path, _cost)) = astar_advanced(&position.coord, &intent.target, &static_config) else {
+            updates.push(MovementUpdate::Failed { entity, reason: MovementFailure::NoPath });
+            finished.push(entity);
+            continue;
+          };
+
+          if !blocked {
+            intent.replans += 1;
+            if intent.replans > intent.max_replans {
+              updates.push(MovementUpdate::Failed { entity, reason: MovementFailure::Blocked });
+              finished.push(entity);
+              continue;
+            }
+          }
+          intent.path = path.into_iter().skip(1).collect();
+          updates.push(MovementUpdate::Replanned { entity });
+          continue;
+        };
+
+        // The planned path starts at the current position
+        intent.path = path.into_iter().skip(1).collect();
+        if blocked {
+          updates.push(MovementUpdate::Replanned { entity });
+        }
+      }
+
+      let next = intent.path.remove(0);
+      if occupants.get(&position.coord) == Some(&entity) {
+        occupants.remove(&position.coord);
+        occupants.insert(next.clone(), entity);
+      }
+      position.coord = next;
+      updates.push(MovementUpdate::Moved { entity });
+
+      if position.coord == intent.target {
+        updates.push(MovementUpdate::Arrived { entity });
+        finished.push(entity);
+      }
+    }
+
+    for entity in finished {
+      // The intent is known to be there, it was just queried
+      let _ = world.remove_one::<MoveIntent<C>>(entity);
+    }
+
+    updates
+  }
+
   /// Calculates movement path and validates movement request.
   fn calculate_movement<C>(
     current: &C,
@@ -141,6 +279,44 @@ pub enum MovementResult<C> {
   NoPathFound,
 }
 
+/// Change made to an entity while resolving its movement intent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementUpdate {
+  /// Entity moved one tile along its path
+  Moved {
+    /// Entity that moved
+    entity: hecs::Entity,
+  },
+  /// Path of the entity was blocked and planned again
+  Replanned {
+    /// Entity whose path was planned again
+    entity: hecs::Entity,
+  },
+  /// Entity reached its target
+  Arrived {
+    /// Entity that arrived
+    entity: hecs::Entity,
+  },
+  /// Movement was abandoned
+  Failed {
+    /// Entity that failed to move
+    entity: hecs::Entity,
+    /// Why movement was abandoned
+    reason: MovementFailure,
+  },
+}
+
+/// Reason a movement intent was abandoned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MovementFailure {
+  /// Entity has no `Movable` component
+  NotMovable,
+  /// No path leads to the target
+  NoPath,
+  /// Path kept getting blocked and ran out of re-plans
+  Blocked,
+}
+
 // =============================================================================
 // Combat Systems  
 // =============================================================================
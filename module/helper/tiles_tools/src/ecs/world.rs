@@ -40,8 +40,12 @@
 //! ```
 
 use crate::ecs::{ components::*, systems::* };
-use crate::coordinates::Distance;
-use std::collections::HashMap;
+use crate::coordinates::{ Distance, Neighbors };
+use core::any::TypeId;
+use core::hash::Hash;
+
+/// Resolves movement intents of one coordinate type.
+type MovementResolver = fn( &mut hecs::World ) -> Vec< MovementUpdate >;
 
 /// Game world containing entities, components, and systems.
 ///
@@ -52,10 +56,8 @@ pub struct World
 {
   /// The underlying HECS world for entity-component storage
   pub hecs_world : hecs::World,
-  /// Pending movement requests from various sources (AI, player input, etc.)
-  /// Note: In a real implementation, this would use a proper enum or trait object
-  /// for type-safe movement requests across coordinate systems
-  movement_requests : HashMap< hecs::Entity, String >, // Simplified for now
+  /// Movement intent resolvers, one per registered coordinate type
+  movement_resolvers : Vec< ( TypeId, MovementResolver ) >,
   /// Game events generated this frame
   events : Vec< GameEvent >,
   /// Total elapsed time
@@ -70,7 +72,7 @@ impl World
     Self
     {
       hecs_world : hecs::World::new(),
-      movement_requests : HashMap::new(),
+      movement_resolvers : Vec::new(),
       events : Vec::new(),
       elapsed_time : 0.0,
     }
@@ -125,8 +127,8 @@ impl World
     // Update AI systems
     AISystem::update_ai( &mut self.hecs_world, dt );
 
-    // Process movement requests
-    self.process_movement_requests();
+    // Advance movement intents
+    self.process_movement_intents();
 
     // Process combat
     let combat_events = CombatSystem::process_combat( &mut self.hecs_world );
@@ -141,12 +143,32 @@ impl World
   }
 
   /// Requests movement for an entity to a specific coordinate.
-  /// Note: Simplified implementation - in practice would handle proper coordinate types
-  pub fn request_movement< C >( &mut self, entity : hecs::Entity, _target : C )
+  ///
+  /// Attaches a [`MoveIntent`] to the entity, replacing any previous one. The
+  /// entity then moves one tile per [`Self::update`] and reports the outcome
+  /// with [`GameEvent::MovementArrived`] or [`GameEvent::MovementFailed`].
+  pub fn request_movement< C >( &mut self, entity : hecs::Entity, target : C ) -> Result< (), hecs::NoSuchEntity >
   where
-    C : 'static + Clone,
+    C : Distance + Neighbors + Clone + Eq + Hash + Send + Sync + 'static,
   {
-    self.movement_requests.insert( entity, "movement_requested".to_string() );
+    self.hecs_world.insert_one( entity, MoveIntent::new( target ) )?;
+    self.register_movement::< C >();
+    Ok( () )
+  }
+
+  /// Enables resolution of [`MoveIntent`]s for coordinate type `C`.
+  ///
+  /// [`Self::request_movement`] registers its coordinate type on its own, this
+  /// is only needed for intents inserted directly as components.
+  pub fn register_movement< C >( &mut self )
+  where
+    C : Distance + Neighbors + Clone + Eq + Hash + Send + Sync + 'static,
+  {
+    let type_id = TypeId::of::< C >();
+    if self.movement_resolvers.iter().all( | ( registered, _ ) | *registered != type_id )
+    {
+      self.movement_resolvers.push( ( type_id, MovementSystem::process_intents::< C > ) );
+    }
   }
 
   /// Gets all events generated this frame.
@@ -196,7 +218,7 @@ impl World
   #[ allow( dead_code ) ]
   fn process_ai_actions< C >( &mut self, actions : Vec< AIAction< C > > )
   where
-    C : 'static + Clone,
+    C : Distance + Neighbors + Clone + Eq + Hash + Send + Sync + 'static,
   {
     for action in actions
     {
@@ -204,7 +226,8 @@ impl World
       {
         AIAction::MoveToward { entity, target_position } =>
         {
-          self.request_movement( entity, target_position );
+          // Entities despawned since the decision was made have nowhere to go
+          let _ = self.request_movement( entity, target_position );
         }
         AIAction::Attack { entity, target } =>
         {
@@ -222,13 +245,23 @@ impl World
     }
   }
 
-  /// Processes pending movement requests.
-  fn process_movement_requests( &mut self )
+  /// Advances movement intents of every registered coordinate type.
+  fn process_movement_intents( &mut self )
   {
-    // This is a simplified version - in reality we'd need to handle different coordinate types
-    // For now, we'll clear the requests to prevent memory leaks
-    // TODO: Implement proper type-safe movement request processing
-    self.movement_requests.clear();
+    for ( _, resolve ) in &self.movement_resolvers
+    {
+      for update in resolve( &mut self.hecs_world )
+      {
+        let event = match update
+        {
+          MovementUpdate::Moved { entity } => GameEvent::EntityMoved { entity },
+          MovementUpdate::Replanned { entity } => GameEvent::MovementReplanned { entity },
+          MovementUpdate::Arrived { entity } => GameEvent::MovementArrived { entity },
+          MovementUpdate::Failed { entity, reason } => GameEvent::MovementFailed { entity, reason },
+        };
+        self.events.push( event );
+      }
+    }
   }
 
   /// Processes combat events.
@@ -319,6 +352,26 @@ pub enum GameEvent
     /// Entity that moved
     entity : hecs::Entity,
   },
+  /// A blocked path was planned again
+  MovementReplanned
+  {
+    /// Entity whose path was planned again
+    entity : hecs::Entity,
+  },
+  /// An entity reached the target of its movement intent
+  MovementArrived
+  {
+    /// Entity that arrived
+    entity : hecs::Entity,
+  },
+  /// An entity gave up its movement intent
+  MovementFailed
+  {
+    /// Entity that failed to move
+    entity : hecs::Entity,
+    /// Why movement was abandoned
+    reason : MovementFailure,
+  },
   /// A trigger was activated
   TriggerActivated
   {
@@ -349,6 +402,7 @@ impl EntityBuilder
       stats,
       team,
       Size::single(),
+      Occupant,
     )
   }
 
@@ -371,6 +425,7 @@ impl EntityBuilder
       Team::new( 0 ), // Player team
       PlayerControlled::new( player_id ),
       Size::single(),
+      Occupant,
     )
   }
 
@@ -393,6 +448,7 @@ impl EntityBuilder
       team,
       AI::new( 1.0 ), // 1 second decision interval
       Size::single(),
+      Occupant,
     )
   }
 
@@ -404,6 +460,7 @@ impl EntityBuilder
     (
       Position::new( position ),
       Size::single(),
+      Obstacle,
     )
   }
 
@@ -302,11 +302,18 @@ impl FieldOfView
   }
 
   /// Calculates line of sight between two specific points.
+  ///
+  /// With [`FOVAlgorithm::Bresenham`] only the line between the points is
+  /// traced, other algorithms compute the whole field of view around `from`.
   pub fn line_of_sight< C, F >( &self, from : &C, to : &C, blocks_sight : F ) -> bool
   where
     C : Distance + Neighbors + Clone + std::hash::Hash + Eq,
     F : Fn( &C ) -> bool,
   {
+    if self.algorithm == FOVAlgorithm::Bresenham
+    {
+      return self.check_bresenham_line( from, to, &blocks_sight );
+    }
     let distance = from.distance( to ) as u32;
     let visibility = self.calculate_fov( from, distance + 1, blocks_sight );
     visibility.is_visible( to )
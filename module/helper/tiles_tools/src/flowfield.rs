@@ -12,171 +12,317 @@
 //! 2. **Flow Field**: Determine best direction from each tile
 //! 3. **Unit Movement**: Units follow flow directions to reach goal
 //!
+//! Fields are stored in [`Grid2D`] and addressed through [`GridCell`], so the same
+//! field works with hexagonal, square, isometric and triangular coordinates.
+//! Flow directions are expressed as offsets between `GridCell` cells.
+//!
 //! # Examples
 //!
 //! ```rust
-//! use tiles_tools::flowfield::FlowDirection;
+//! use tiles_tools::flowfield::{ FlowField, FlowDirection };
+//! use tiles_tools::coordinates::square::{ Coordinate, FourConnected };
+//!
+//! let mut field = FlowField::< (), () >::new( 10, 10 );
+//! let goal = Coordinate::< FourConnected >::new( 9, 5 );
+//! field.calculate_flow( &goal, | _ | true, | _ | 1 );
 //!
-//! // Flow directions indicate where units should move
-//! let north = FlowDirection::Move(0, -1); // Move north (negative y)
-//! let east = FlowDirection::Move(1, 0);   // Move east (positive x)
-//! let none = FlowDirection::None;         // No movement needed
-//! 
-//! println!("Directions: {:?}, {:?}, {:?}", north, east, none);
-//! 
-//! // In a complete implementation, FlowField would:
-//! // 1. Calculate integration field (cost-to-goal for every tile)
-//! // 2. Generate flow directions (steepest descent toward goal)
-//! // 3. Provide movement guidance for multiple units efficiently
+//! let unit = Coordinate::< FourConnected >::new( 0, 5 );
+//! assert_eq!( field.get_flow_direction( &unit ), Some( FlowDirection::Move( 1, 0 ) ) );
+//! assert_eq!( field.apply_flow( &unit ), Some( Coordinate::new( 1, 5 ) ) );
+//! assert_eq!( field.get_flow_direction( &goal ), Some( FlowDirection::None ) );
 //! ```
 
 use crate::coordinates::{ Distance, Neighbors };
-use crate::collection::Grid2D;
-// Note: BinaryHeap and Reverse would be used in full Dijkstra implementation
+use crate::coordinates::hexagonal::Coordinate;
+use crate::collection::{ Grid2D, GridCell };
+use std::cmp::Reverse;
+use std::collections::{ BinaryHeap, HashSet };
 
 /// Direction vectors for flow field navigation.
-#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash ) ]
+#[ derive( Debug, Clone, Copy, PartialEq, Eq, Hash, Default ) ]
 pub enum FlowDirection
 {
   /// No movement - already at goal or blocked
+  #[ default ]
   None,
   /// Move to adjacent coordinate (relative x, y offset)
   Move( i32, i32 ),
 }
 
+/// Cell key used for `Grid2D` storage.
+type Cell = ( i32, i32 );
+
+/// Frontier of a Dijkstra wavefront, ordered by lowest cost first.
+type Frontier = BinaryHeap< Reverse< ( u32, Cell ) > >;
+
+/// Converts a cell into the coordinate type `Grid2D` is indexed by.
+fn grid_key< System, Orientation >( ( column, row ) : Cell ) -> Coordinate< System, Orientation >
+{
+  Coordinate::new( column, row )
+}
+
+/// Returns the in-bounds neighbor cells of `cell` under the neighborhood of `C`.
+fn neighbor_cells< C, System, Orientation, T >( grid : &Grid2D< System, Orientation, T >, cell : Cell ) -> Vec< Cell >
+where
+  C : GridCell + Neighbors,
+{
+  C::from_cell( cell.0, cell.1 )
+  .neighbors()
+  .iter()
+  .map( GridCell::to_cell )
+  .filter( | n | grid.in_bounds( grid_key::< System, Orientation >( *n ) ) )
+  .collect()
+}
+
 /// Integration field storing cost-to-goal for each position.
 ///
 /// This field contains the minimum cost to reach the goal from each
 /// position on the grid. Used as intermediate step for flow field calculation.
-#[ derive( Debug, Clone ) ]
+/// The cost of a position is the sum of the movement costs of every position
+/// on the path to the goal, excluding the goal itself.
+#[ derive( Debug ) ]
 pub struct IntegrationField< System, Orientation >
 {
   /// Maximum cost value (unreachable positions)
   pub max_cost : u32,
-  /// Phantom marker for system type
-  _phantom_system : std::marker::PhantomData< System >,
-  /// Phantom marker for orientation type
-  _phantom_orientation : std::marker::PhantomData< Orientation >,
+  /// Cost-to-goal of every cell
+  costs : Grid2D< System, Orientation, u32 >,
 }
 
-/// Flow field for efficient multi-unit pathfinding.
-///
-/// Stores the optimal movement direction from each grid position toward
-/// a target destination. Particularly effective for RTS games where many
-/// units move toward the same goal.
-#[ derive( Debug, Clone ) ]
-pub struct FlowField< System, Orientation >
+impl< System, Orientation > Clone for IntegrationField< System, Orientation >
 {
-  /// Integration field with costs to goal
-  #[ allow( dead_code ) ]
-  integration : IntegrationField< System, Orientation >,
-  /// Grid dimensions
-  #[ allow( dead_code ) ]
-  width : i32,
-  #[ allow( dead_code ) ]
-  height : i32,
-  /// Phantom marker for system type
-  _phantom_system : std::marker::PhantomData< System >,
-  /// Phantom marker for orientation type
-  _phantom_orientation : std::marker::PhantomData< Orientation >,
+  fn clone( &self ) -> Self
+  {
+    Self
+    {
+      max_cost : self.max_cost,
+      costs : self.costs.clone(),
+    }
+  }
 }
 
 impl< System, Orientation > IntegrationField< System, Orientation >
 {
-  /// Creates a new integration field with the specified dimensions.
-  pub fn new( _width : i32, _height : i32 ) -> Self
+  /// Creates a new integration field covering cells `( 0, 0 )` to `( width - 1, height - 1 )`.
+  ///
+  /// Every position starts unreachable.
+  pub fn new( width : i32, height : i32 ) -> Self
   {
-    // Simplified stub implementation for testing
+    Self::with_bounds( ( 0, 0 ), ( width.max( 0 ), height.max( 0 ) ) )
+  }
+
+  /// Creates a new integration field covering the cells in `min_inclusive..max_exclusive`.
+  ///
+  /// Useful for maps whose coordinates go negative, such as axial hex maps centred on the origin.
+  ///
+  /// # Panics
+  /// Panics if `max_exclusive` is smaller than `min_inclusive` on either axis.
+  pub fn with_bounds( min_inclusive : ( i32, i32 ), max_exclusive : ( i32, i32 ) ) -> Self
+  {
+    let max_cost = u32::MAX;
     Self
     {
-      max_cost : u32::MAX,
-      _phantom_system : std::marker::PhantomData,
-      _phantom_orientation : std::marker::PhantomData,
+      max_cost,
+      costs : Grid2D::with_size_and_fn( grid_key( min_inclusive ), grid_key( max_exclusive ), || max_cost ),
     }
   }
 
   /// Gets the integration cost at a specific coordinate.
-  pub fn get_cost< C >( &self, _coord : &C ) -> u32
+  ///
+  /// Positions outside the field are reported as unreachable (`max_cost`).
+  pub fn get_cost< C >( &self, coord : &C ) -> u32
   where
-    C : Clone,
-    Grid2D< System, Orientation, u32 > : std::ops::Index< C, Output = u32 >,
+    C : GridCell,
   {
-    // Simplified stub implementation - would access Grid2D
-    0
+    self.cell_cost( coord.to_cell() )
   }
 
   /// Sets the integration cost at a specific coordinate.
-  pub fn set_cost< C >( &mut self, _coord : &C, _cost : u32 )
+  ///
+  /// Positions outside the field are ignored.
+  pub fn set_cost< C >( &mut self, coord : &C, cost : u32 )
   where
-    C : Clone,
-    Grid2D< System, Orientation, u32 > : std::ops::IndexMut< C, Output = u32 >,
+    C : GridCell,
   {
-    // Simplified stub implementation - would modify Grid2D
+    self.set_cell_cost( coord.to_cell(), cost );
   }
 
   /// Checks if a position is within valid bounds.
-  pub fn in_bounds< C >( &self, _coord : &C ) -> bool
+  pub fn in_bounds< C >( &self, coord : &C ) -> bool
   where
-    C : Clone,
-    Grid2D< System, Orientation, u32 > : std::ops::Index< C, Output = u32 >,
+    C : GridCell,
+  {
+    self.costs.in_bounds( grid_key::< System, Orientation >( coord.to_cell() ) )
+  }
+
+  /// Marks every position as unreachable.
+  pub fn reset( &mut self )
+  {
+    let max_cost = self.max_cost;
+    self.costs.iter_mut().for_each( | cost | *cost = max_cost );
+  }
+
+  fn cell_cost( &self, cell : Cell ) -> u32
+  {
+    let key = grid_key::< System, Orientation >( cell );
+    if self.costs.in_bounds( key ) { self.costs[ key ] } else { self.max_cost }
+  }
+
+  fn set_cell_cost( &mut self, cell : Cell, cost : u32 )
+  {
+    let key = grid_key::< System, Orientation >( cell );
+    if self.costs.in_bounds( key )
+    {
+      self.costs[ key ] = cost;
+    }
+  }
+
+  /// Runs Dijkstra from the cells already on the `frontier`, lowering costs
+  /// anywhere a cheaper route is found. Every cell whose cost is lowered is
+  /// recorded in `changed`.
+  fn propagate< C, Fa, Fc >
+  (
+    &mut self,
+    mut frontier : Frontier,
+    is_passable : &Fa,
+    get_cost : &Fc,
+    changed : &mut HashSet< Cell >,
+  )
+  where
+    C : GridCell + Neighbors,
+    Fa : Fn( &C ) -> bool,
+    Fc : Fn( &C ) -> u32,
+  {
+    while let Some( Reverse( ( cost, cell ) ) ) = frontier.pop()
+    {
+      if cost > self.cell_cost( cell )
+      {
+        continue;
+      }
+
+      for next in neighbor_cells::< C, _, _, _ >( &self.costs, cell )
+      {
+        let coord = C::from_cell( next.0, next.1 );
+        if !is_passable( &coord )
+        {
+          continue;
+        }
+        let candidate = cost.saturating_add( get_cost( &coord ) );
+        if candidate < self.max_cost && candidate < self.cell_cost( next )
+        {
+          self.set_cell_cost( next, candidate );
+          changed.insert( next );
+          frontier.push( Reverse( ( candidate, next ) ) );
+        }
+      }
+    }
+  }
+}
+
+/// Flow field for efficient multi-unit pathfinding.
+///
+/// Stores the optimal movement direction from each grid position toward
+/// a target destination. Particularly effective for RTS games where many
+/// units move toward the same goal.
+#[ derive( Debug ) ]
+pub struct FlowField< System, Orientation >
+{
+  /// Integration field with costs to goal
+  integration : IntegrationField< System, Orientation >,
+  /// Movement direction of every cell
+  directions : Grid2D< System, Orientation, FlowDirection >,
+  /// Cell of the goal the field was last calculated for
+  goal : Option< Cell >,
+  /// Grid width in cells
+  pub width : i32,
+  /// Grid height in cells
+  pub height : i32,
+}
+
+impl< System, Orientation > Clone for FlowField< System, Orientation >
+{
+  fn clone( &self ) -> Self
   {
-    // Simple bounds checking - in a full implementation this would use coordinate bounds
-    true
+    Self
+    {
+      integration : self.integration.clone(),
+      directions : self.directions.clone(),
+      goal : self.goal,
+      width : self.width,
+      height : self.height,
+    }
   }
 }
 
 impl< System, Orientation > FlowField< System, Orientation >
 {
   /// Creates a new flow field with the specified dimensions.
+  ///
+  /// The field covers cells `( 0, 0 )` to `( width - 1, height - 1 )`.
   pub fn new( width : i32, height : i32 ) -> Self
   {
-    // Simplified stub implementation for testing
-    let integration = IntegrationField::new( width, height );
+    Self::with_bounds( ( 0, 0 ), ( width.max( 0 ), height.max( 0 ) ) )
+  }
+
+  /// Creates a new flow field covering the cells in `min_inclusive..max_exclusive`.
+  ///
+  /// # Panics
+  /// Panics if `max_exclusive` is smaller than `min_inclusive` on either axis.
+  pub fn with_bounds( min_inclusive : ( i32, i32 ), max_exclusive : ( i32, i32 ) ) -> Self
+  {
     Self
     {
-      integration,
-      width,
-      height,
-      _phantom_system : std::marker::PhantomData,
-      _phantom_orientation : std::marker::PhantomData,
+      integration : IntegrationField::with_bounds( min_inclusive, max_exclusive ),
+      directions : Grid2D::with_size_and_default( grid_key( min_inclusive ), grid_key( max_exclusive ) ),
+      goal : None,
+      width : max_exclusive.0 - min_inclusive.0,
+      height : max_exclusive.1 - min_inclusive.1,
     }
   }
 
+  /// Returns the integration field the flow directions were derived from.
+  pub fn integration( &self ) -> &IntegrationField< System, Orientation >
+  {
+    &self.integration
+  }
+
   /// Calculates the flow field toward a goal position.
   ///
   /// This is a two-phase algorithm:
   /// 1. Calculate integration field (cost to reach goal from each position)
   /// 2. Generate flow directions (steepest descent toward goal)
+  ///
+  /// `get_cost` is the cost of entering a position. Positions rejected by
+  /// `is_passable`, and every position when the goal is out of bounds or
+  /// impassable, end up with [`FlowDirection::None`].
   pub fn calculate_flow< C, Fa, Fc >( &mut self, goal : &C, is_passable : Fa, get_cost : Fc )
   where
-    C : Distance + Neighbors + Clone + PartialEq + std::hash::Hash + Ord,
+    C : GridCell + Neighbors,
     Fa : Fn( &C ) -> bool,
     Fc : Fn( &C ) -> u32,
-    Grid2D< System, Orientation, u32 > : std::ops::Index< C, Output = u32 > + std::ops::IndexMut< C, Output = u32 >,
-    Grid2D< System, Orientation, FlowDirection > : std::ops::IndexMut< C, Output = FlowDirection >,
   {
     // Phase 1: Calculate integration field using Dijkstra's algorithm
     self.calculate_integration_field( goal, &is_passable, &get_cost );
 
     // Phase 2: Generate flow directions from integration field
-    self.generate_flow_directions( &is_passable );
+    self.generate_flow_directions::< C >();
   }
 
   /// Gets the flow direction at a specific position.
-  pub fn get_flow_direction< C >( &self, _coord : &C ) -> Option< FlowDirection >
+  ///
+  /// Returns `None` for positions outside the field.
+  pub fn get_flow_direction< C >( &self, coord : &C ) -> Option< FlowDirection >
   where
-    C : Clone,
-    Grid2D< System, Orientation, FlowDirection > : std::ops::Index< C, Output = FlowDirection >,
+    C : GridCell,
   {
-    // Simplified stub implementation - would access Grid2D
-    None
+    let key = grid_key::< System, Orientation >( coord.to_cell() );
+    self.directions.in_bounds( key ).then( || self.directions[ key ] )
   }
 
   /// Gets multiple flow directions for batch processing.
   pub fn get_flow_directions_batch< C >( &self, coords : &[ C ] ) -> Vec< Option< FlowDirection > >
   where
-    C : Clone,
-    Grid2D< System, Orientation, FlowDirection > : std::ops::Index< C, Output = FlowDirection >,
+    C : GridCell,
   {
     coords.iter()
       .map( | coord | self.get_flow_direction( coord ) )
@@ -184,62 +330,164 @@ impl< System, Orientation > FlowField< System, Orientation >
   }
 
   /// Calculates integration field using modified Dijkstra's algorithm.
-  fn calculate_integration_field< C, Fa, Fc >( &mut self, _goal : &C, _is_passable : &Fa, _get_cost : &Fc )
+  fn calculate_integration_field< C, Fa, Fc >( &mut self, goal : &C, is_passable : &Fa, get_cost : &Fc )
   where
-    C : Distance + Neighbors + Clone + PartialEq + std::hash::Hash + Ord,
+    C : GridCell + Neighbors,
     Fa : Fn( &C ) -> bool,
     Fc : Fn( &C ) -> u32,
-    Grid2D< System, Orientation, u32 > : std::ops::Index< C, Output = u32 > + std::ops::IndexMut< C, Output = u32 >,
   {
-    // Simplified implementation for demonstration
-    // In a full implementation, this would use Dijkstra's algorithm to calculate
-    // the minimum cost to reach the goal from every position on the grid
-    
-    // The algorithm would:
-    // 1. Initialize all positions to infinite cost
-    // 2. Set goal position to cost 0
-    // 3. Use priority queue to propagate costs outward
-    // 4. Calculate minimum path cost to every reachable position
+    self.integration.reset();
+    self.goal = None;
+
+    if !self.integration.in_bounds( goal ) || !is_passable( goal )
+    {
+      return;
+    }
+
+    let goal = goal.to_cell();
+    self.goal = Some( goal );
+    self.integration.set_cell_cost( goal, 0 );
+
+    let mut frontier = Frontier::new();
+    frontier.push( Reverse( ( 0, goal ) ) );
+    self.integration.propagate( frontier, is_passable, get_cost, &mut HashSet::new() );
   }
 
   /// Generates flow directions from the integration field.
-  fn generate_flow_directions< C, Fa >( &mut self, _is_passable : &Fa )
+  fn generate_flow_directions< C >( &mut self )
+  where
+    C : GridCell + Neighbors,
+  {
+    let cells : Vec< Cell > = self.directions
+    .indexed_iter()
+    .map( | ( coord, _ ) | ( coord.q, coord.r ) )
+    .collect();
+
+    for cell in cells
+    {
+      self.update_direction::< C >( cell );
+    }
+  }
+
+  /// Points `cell` at its neighbor with the lowest integration cost.
+  fn update_direction< C >( &mut self, cell : Cell )
+  where
+    C : GridCell + Neighbors,
+  {
+    let key = grid_key::< System, Orientation >( cell );
+    if !self.directions.in_bounds( key )
+    {
+      return;
+    }
+
+    let current = self.integration.cell_cost( cell );
+    let mut direction = FlowDirection::None;
+    if current != 0 && current < self.integration.max_cost
+    {
+      let mut best_cost = current;
+      for next in neighbor_cells::< C, _, _, _ >( &self.directions, cell )
+      {
+        let cost = self.integration.cell_cost( next );
+        if cost < best_cost
+        {
+          best_cost = cost;
+          direction = FlowDirection::Move( next.0 - cell.0, next.1 - cell.1 );
+        }
+      }
+    }
+    self.directions[ key ] = direction;
+  }
+
+  /// Returns the cell `cell` flows into, if any.
+  fn flow_target( &self, cell : Cell ) -> Option< Cell >
+  {
+    let key = grid_key::< System, Orientation >( cell );
+    if !self.directions.in_bounds( key )
+    {
+      return None;
+    }
+    match self.directions[ key ]
+    {
+      FlowDirection::None => None,
+      FlowDirection::Move( dx, dy ) => Some( ( cell.0 + dx, cell.1 + dy ) ),
+    }
+  }
+
+  /// Repairs the field after the cost or passability of `dirty` cells changed.
+  ///
+  /// Only the dirty cells and the cells whose flow passes through them are
+  /// invalidated. They are refilled by a wavefront seeded from the intact cells
+  /// bordering that region, which also lowers costs elsewhere when a dirty cell
+  /// became cheaper. Returns the number of cells whose cost was recomputed.
+  fn repair< C, Fa, Fc >( &mut self, dirty : &HashSet< Cell >, is_passable : &Fa, get_cost : &Fc ) -> usize
   where
-    C : Neighbors + Clone,
+    C : GridCell + Neighbors,
     Fa : Fn( &C ) -> bool,
-    Grid2D< System, Orientation, u32 > : std::ops::Index< C, Output = u32 >,
-    Grid2D< System, Orientation, FlowDirection > : std::ops::IndexMut< C, Output = FlowDirection >,
-  {
-    // Note: In a full implementation, we'd iterate over all grid positions
-    // For each position, find the neighbor with the lowest integration cost
-    // and set the flow direction toward that neighbor
-    
-    // This is a simplified version that would need proper grid iteration
-    // The actual implementation would look like:
-    /*
-    for each position in grid {
-      let current_cost = integration.get_cost(position);
-      let mut best_neighbor = None;
-      let mut best_cost = current_cost;
-      
-      for neighbor in position.neighbors() {
-        if is_passable(neighbor) {
-          let neighbor_cost = integration.get_cost(neighbor);
-          if neighbor_cost < best_cost {
-            best_cost = neighbor_cost;
-            best_neighbor = Some(neighbor);
-          }
+    Fc : Fn( &C ) -> u32,
+  {
+    let Some( goal ) = self.goal else { return 0 };
+
+    // Collect every cell whose path to the goal runs through a dirty cell.
+    let mut affected = HashSet::new();
+    let mut stack : Vec< Cell > = dirty
+    .iter()
+    .copied()
+    .filter( | cell | self.directions.in_bounds( grid_key::< System, Orientation >( *cell ) ) )
+    .collect();
+    while let Some( cell ) = stack.pop()
+    {
+      if !affected.insert( cell )
+      {
+        continue;
+      }
+      for upstream in neighbor_cells::< C, _, _, _ >( &self.directions, cell )
+      {
+        if self.flow_target( upstream ) == Some( cell )
+        {
+          stack.push( upstream );
         }
       }
-      
-      if let Some(target) = best_neighbor {
-        let direction = calculate_direction_vector(position, target);
-        flow_directions[position] = FlowDirection::Move(direction.0, direction.1);
-      } else {
-        flow_directions[position] = FlowDirection::None;
+    }
+
+    for cell in &affected
+    {
+      self.integration.set_cell_cost( *cell, self.integration.max_cost );
+    }
+
+    // Seed the wavefront from the goal and from the intact border of the region.
+    let mut frontier = Frontier::new();
+    if affected.contains( &goal ) && is_passable( &C::from_cell( goal.0, goal.1 ) )
+    {
+      self.integration.set_cell_cost( goal, 0 );
+      frontier.push( Reverse( ( 0, goal ) ) );
+    }
+    for cell in &affected
+    {
+      for border in neighbor_cells::< C, _, _, _ >( &self.directions, *cell )
+      {
+        let cost = self.integration.cell_cost( border );
+        if !affected.contains( &border ) && cost < self.integration.max_cost
+        {
+          frontier.push( Reverse( ( cost, border ) ) );
+        }
       }
     }
-    */
+
+    let mut changed = affected.clone();
+    self.integration.propagate( frontier, is_passable, get_cost, &mut changed );
+
+    let mut refresh = HashSet::new();
+    for cell in &changed
+    {
+      refresh.insert( *cell );
+      refresh.extend( neighbor_cells::< C, _, _, _ >( &self.directions, *cell ) );
+    }
+    for cell in refresh
+    {
+      self.update_direction::< C >( cell );
+    }
+
+    affected.len()
   }
 
   /// Applies flow field to move a unit toward the goal.
@@ -248,20 +496,10 @@ impl< System, Orientation > FlowField< System, Orientation >
   /// the unit is already at the goal or blocked.
   pub fn apply_flow< C >( &self, current_pos : &C ) -> Option< C >
   where
-    C : Neighbors + Clone,
-    Grid2D< System, Orientation, FlowDirection > : std::ops::Index< C, Output = FlowDirection >,
+    C : GridCell,
   {
-    match self.get_flow_direction( current_pos )?
-    {
-      FlowDirection::None => None,
-      FlowDirection::Move( _dx, _dy ) =>
-      {
-        // Find the neighbor that matches the flow direction
-        // This is simplified - in a full implementation we'd calculate the actual coordinate
-        let neighbors = current_pos.neighbors();
-        neighbors.into_iter().next() // Simplified: return first neighbor
-      }
-    }
+    let ( column, row ) = self.flow_target( current_pos.to_cell() )?;
+    Some( C::from_cell( column, row ) )
   }
 
   /// Calculates flow field influence for group movement.
@@ -270,8 +508,7 @@ impl< System, Orientation > FlowField< System, Orientation >
   /// prevent clustering and improve group movement behavior.
   pub fn calculate_group_flow< C >( &self, unit_positions : &[ C ] ) -> Vec< Option< C > >
   where
-    C : Distance + Neighbors + Clone,
-    Grid2D< System, Orientation, FlowDirection > : std::ops::Index< C, Output = FlowDirection >,
+    C : Distance + GridCell,
   {
     unit_positions.iter()
       .map( | pos |
@@ -296,17 +533,52 @@ impl FlowFieldAnalyzer
   ///
   /// Returns diagnostic information about the flow field including
   /// unreachable areas, flow convergence, and potential bottlenecks.
+  /// A position is a convergence point when two or more neighbors flow into
+  /// it, and a bottleneck when three or more do.
   pub fn analyze_flow< System, Orientation >
   (
-    _field : &FlowField< System, Orientation >
+    field : &FlowField< System, Orientation >
   ) -> FlowFieldAnalysis
   {
+    let max_cost = field.integration.max_cost;
+    let mut unreachable_positions = 0;
+    let mut total_cost = 0_u64;
+    let mut reachable = 0_u64;
+    for cost in field.integration.costs.iter()
+    {
+      if *cost >= max_cost
+      {
+        unreachable_positions += 1;
+      }
+      else
+      {
+        total_cost += u64::from( *cost );
+        reachable += 1;
+      }
+    }
+
+    let mut inflow = rustc_hash::FxHashMap::< Cell, u32 >::default();
+    for ( coord, _ ) in field.directions.indexed_iter()
+    {
+      if let Some( target ) = field.flow_target( ( coord.q, coord.r ) )
+      {
+        *inflow.entry( target ).or_default() += 1;
+      }
+    }
+    let convergence_points = inflow.values().filter( | count | **count >= 2 ).count() as u32;
+    let mut bottleneck_positions : Vec< _ > = inflow
+    .into_iter()
+    .filter( | ( _, count ) | *count >= 3 )
+    .map( | ( cell, _ ) | cell )
+    .collect();
+    bottleneck_positions.sort_unstable();
+
     FlowFieldAnalysis
     {
-      unreachable_positions : 0, // Would count positions with FlowDirection::None
-      convergence_points : 0,     // Points where multiple flows converge
-      average_path_length : 0.0,  // Average distance to goal across all positions
-      bottleneck_positions : Vec::new(), // Positions that create traffic jams
+      unreachable_positions,
+      convergence_points,
+      average_path_length : if reachable == 0 { 0.0 } else { total_cost as f32 / reachable as f32 },
+      bottleneck_positions,
     }
   }
 
@@ -342,20 +614,33 @@ pub struct FlowFieldAnalysis
 ///
 /// Useful for scenarios where units need to reach any of several destinations,
 /// such as resource gathering or multiple capture points.
-#[ derive( Debug, Clone ) ]
+#[ derive( Debug ) ]
 pub struct MultiGoalFlowField< System, Orientation >
 {
   /// Individual flow fields for each goal
   pub goal_fields : Vec< FlowField< System, Orientation > >,
+  /// Direction toward the cheapest goal for every cell
+  combined : Grid2D< System, Orientation, FlowDirection >,
+  /// First cell covered by the field
+  min : Cell,
   /// Grid dimensions
-  #[ allow( dead_code ) ]
   width : i32,
-  #[ allow( dead_code ) ]
   height : i32,
-  /// Phantom marker for system type
-  _phantom_system : std::marker::PhantomData< System >,
-  /// Phantom marker for orientation type
-  _phantom_orientation : std::marker::PhantomData< Orientation >,
+}
+
+impl< System, Orientation > Clone for MultiGoalFlowField< System, Orientation >
+{
+  fn clone( &self ) -> Self
+  {
+    Self
+    {
+      goal_fields : self.goal_fields.clone(),
+      combined : self.combined.clone(),
+      min : self.min,
+      width : self.width,
+      height : self.height,
+    }
+  }
 }
 
 impl< System, Orientation > MultiGoalFlowField< System, Orientation >
@@ -363,30 +648,33 @@ impl< System, Orientation > MultiGoalFlowField< System, Orientation >
   /// Creates a new multi-goal flow field.
   pub fn new( width : i32, height : i32 ) -> Self
   {
-    // Simplified stub implementation for testing
+    Self::with_bounds( ( 0, 0 ), ( width.max( 0 ), height.max( 0 ) ) )
+  }
+
+  /// Creates a new multi-goal flow field covering the cells in `min_inclusive..max_exclusive`.
+  pub fn with_bounds( min_inclusive : ( i32, i32 ), max_exclusive : ( i32, i32 ) ) -> Self
+  {
     Self
     {
       goal_fields : Vec::new(),
-      width,
-      height,
-      _phantom_system : std::marker::PhantomData,
-      _phantom_orientation : std::marker::PhantomData,
+      combined : Grid2D::with_size_and_default( grid_key( min_inclusive ), grid_key( max_exclusive ) ),
+      min : min_inclusive,
+      width : max_exclusive.0 - min_inclusive.0,
+      height : max_exclusive.1 - min_inclusive.1,
     }
   }
 
   /// Adds a goal to the multi-goal flow field.
   pub fn add_goal< C, Fa, Fc >( &mut self, goal : &C, is_passable : Fa, get_cost : Fc )
   where
-    C : Distance + Neighbors + Clone + PartialEq + std::hash::Hash + Ord,
-    Fa : Fn( &C ) -> bool + Clone,
-    Fc : Fn( &C ) -> u32 + Clone,
-    Grid2D< System, Orientation, u32 > : std::ops::Index< C, Output = u32 > + std::ops::IndexMut< C, Output = u32 >,
-    Grid2D< System, Orientation, FlowDirection > : std::ops::IndexMut< C, Output = FlowDirection >,
+    C : GridCell + Neighbors,
+    Fa : Fn( &C ) -> bool,
+    Fc : Fn( &C ) -> u32,
   {
-    let mut goal_field = FlowField::new
+    let mut goal_field = FlowField::with_bounds
     (
-      self.width,
-      self.height
+      self.min,
+      ( self.min.0 + self.width, self.min.1 + self.height )
     );
     goal_field.calculate_flow( goal, is_passable, get_cost );
     self.goal_fields.push( goal_field );
@@ -394,20 +682,33 @@ impl< System, Orientation > MultiGoalFlowField< System, Orientation >
   }
 
   /// Recalculates the combined flow field from all individual goal fields.
+  ///
+  /// Every cell takes the direction of the goal field with the lowest
+  /// integration cost there, i.e. it heads for the nearest goal.
   fn recalculate_combined_field( &mut self )
   {
-    // Implementation would combine multiple flow fields by choosing
-    // the direction toward the nearest goal at each position
+    let cells : Vec< Cell > = self.combined
+    .indexed_iter()
+    .map( | ( coord, _ ) | ( coord.q, coord.r ) )
+    .collect();
+
+    for cell in cells
+    {
+      let key = grid_key::< System, Orientation >( cell );
+      self.combined[ key ] = self.goal_fields
+      .iter()
+      .min_by_key( | field | field.integration.cell_cost( cell ) )
+      .map_or( FlowDirection::None, | field | field.directions[ key ] );
+    }
   }
 
   /// Gets the optimal flow direction considering all goals.
-  pub fn get_optimal_direction< C >( &self, _pos : &C ) -> Option< FlowDirection >
+  pub fn get_optimal_direction< C >( &self, pos : &C ) -> Option< FlowDirection >
   where
-    C : Clone,
-    Grid2D< System, Orientation, FlowDirection > : std::ops::Index< C, Output = FlowDirection >,
+    C : GridCell,
   {
-    // Simplified stub implementation - would combine all goal fields
-    None
+    let key = grid_key::< System, Orientation >( pos.to_cell() );
+    self.combined.in_bounds( key ).then( || self.combined[ key ] )
   }
 }
 
@@ -419,19 +720,31 @@ impl< System, Orientation > MultiGoalFlowField< System, Orientation >
 ///
 /// More efficient than full recalculation for dynamic environments
 /// where obstacles appear and disappear frequently.
-#[ derive( Debug, Clone ) ]
+#[ derive( Debug ) ]
 pub struct DynamicFlowField< System, Orientation >
 {
+  /// Flow field being maintained
+  field : FlowField< System, Orientation >,
   /// Positions that need recalculation
-  dirty_positions : std::collections::HashSet< ( i32, i32 ) >,
+  dirty_positions : HashSet< ( i32, i32 ) >,
   /// Grid width in cells
   pub width : i32,
-  /// Grid height in cells  
+  /// Grid height in cells
   pub height : i32,
-  /// Phantom marker for system type
-  _phantom_system : std::marker::PhantomData< System >,
-  /// Phantom marker for orientation type
-  _phantom_orientation : std::marker::PhantomData< Orientation >,
+}
+
+impl< System, Orientation > Clone for DynamicFlowField< System, Orientation >
+{
+  fn clone( &self ) -> Self
+  {
+    Self
+    {
+      field : self.field.clone(),
+      dirty_positions : self.dirty_positions.clone(),
+      width : self.width,
+      height : self.height,
+    }
+  }
 }
 
 impl< System, Orientation > DynamicFlowField< System, Orientation >
@@ -439,34 +752,64 @@ impl< System, Orientation > DynamicFlowField< System, Orientation >
   /// Creates a new dynamic flow field.
   pub fn new( width : i32, height : i32 ) -> Self
   {
-    // Simplified stub implementation for testing
+    Self::from_field( FlowField::new( width, height ) )
+  }
+
+  /// Creates a new dynamic flow field covering the cells in `min_inclusive..max_exclusive`.
+  pub fn with_bounds( min_inclusive : ( i32, i32 ), max_exclusive : ( i32, i32 ) ) -> Self
+  {
+    Self::from_field( FlowField::with_bounds( min_inclusive, max_exclusive ) )
+  }
+
+  fn from_field( field : FlowField< System, Orientation > ) -> Self
+  {
     Self
     {
-      dirty_positions : std::collections::HashSet::new(),
-      width,
-      height,
-      _phantom_system : std::marker::PhantomData,
-      _phantom_orientation : std::marker::PhantomData,
+      width : field.width,
+      height : field.height,
+      field,
+      dirty_positions : HashSet::new(),
     }
   }
 
+  /// Returns the maintained flow field.
+  pub fn field( &self ) -> &FlowField< System, Orientation >
+  {
+    &self.field
+  }
+
+  /// Fully recalculates the flow field toward `goal` and clears pending changes.
+  pub fn calculate_flow< C, Fa, Fc >( &mut self, goal : &C, is_passable : Fa, get_cost : Fc )
+  where
+    C : GridCell + Neighbors,
+    Fa : Fn( &C ) -> bool,
+    Fc : Fn( &C ) -> u32,
+  {
+    self.field.calculate_flow( goal, is_passable, get_cost );
+    self.dirty_positions.clear();
+  }
+
   /// Marks a position as changed (obstacle added/removed).
+  ///
+  /// `pos` is the [`GridCell::to_cell`] of the changed coordinate.
   pub fn mark_dirty( &mut self, pos : ( i32, i32 ) )
   {
     self.dirty_positions.insert( pos );
   }
 
   /// Incrementally updates the flow field for changed positions.
-  pub fn incremental_update< C, Fa, Fc >( &mut self, _is_passable : Fa, _get_cost : Fc )
+  ///
+  /// Only the region whose flow runs through a dirty position is recomputed,
+  /// see [`FlowField::calculate_flow`] for the meaning of the callbacks.
+  /// Returns the number of positions that were recomputed.
+  pub fn incremental_update< C, Fa, Fc >( &mut self, is_passable : Fa, get_cost : Fc ) -> usize
   where
-    C : Distance + Neighbors + Clone + PartialEq + std::hash::Hash,
+    C : GridCell + Neighbors,
     Fa : Fn( &C ) -> bool,
     Fc : Fn( &C ) -> u32,
-    Grid2D< System, Orientation, u32 > : std::ops::Index< C, Output = u32 > + std::ops::IndexMut< C, Output = u32 >,
-    Grid2D< System, Orientation, FlowDirection > : std::ops::IndexMut< C, Output = FlowDirection >,
   {
-    // Implementation would use wavefront propagation to update only affected areas
-    self.dirty_positions.clear();
+    let dirty = std::mem::take( &mut self.dirty_positions );
+    self.field.repair( &dirty, &is_passable, &get_cost )
   }
 }
 
@@ -474,7 +817,7 @@ impl< System, Orientation > DynamicFlowField< System, Orientation >
 mod tests
 {
   use super::*;
-  // use crate::coordinates::square::{ Coordinate as SquareCoord, FourConnected };
+  use crate::coordinates::square::{ Coordinate as SquareCoord, FourConnected };
 
   #[ test ]
   fn test_flow_field_creation()
@@ -491,6 +834,22 @@ mod tests
     assert_eq!( integration.max_cost, u32::MAX );
   }
 
+  #[ test ]
+  fn test_integration_field_costs_and_bounds()
+  {
+    let mut integration = IntegrationField::< (), () >::new( 5, 5 );
+    let inside = SquareCoord::< FourConnected >::new( 4, 4 );
+    let outside = SquareCoord::< FourConnected >::new( 5, 0 );
+
+    assert!( integration.in_bounds( &inside ) );
+    assert!( !integration.in_bounds( &outside ) );
+
+    integration.set_cost( &inside, 7 );
+    integration.set_cost( &outside, 7 );
+    assert_eq!( integration.get_cost( &inside ), 7 );
+    assert_eq!( integration.get_cost( &outside ), u32::MAX );
+  }
+
   #[ test ]
   fn test_flow_direction_enum()
   {
@@ -520,4 +879,33 @@ mod tests
     dynamic_field.mark_dirty( ( 3, 3 ) );
     assert!( dynamic_field.dirty_positions.contains( &( 3, 3 ) ) );
   }
-}
\ No newline at end of file
+
+  #[ test ]
+  fn test_incremental_update_matches_full_recompute()
+  {
+    let goal = SquareCoord::< FourConnected >::new( 9, 9 );
+    let mut walls = HashSet::new();
+    let mut dynamic_field = DynamicFlowField::< (), () >::new( 10, 10 );
+    dynamic_field.calculate_flow( &goal, | _ | true, | _ | 1 );
+
+    for y in 0..8
+    {
+      walls.insert( ( 5, y ) );
+      dynamic_field.mark_dirty( ( 5, y ) );
+    }
+    let is_passable = | c : &SquareCoord< FourConnected > | !walls.contains( &( c.x, c.y ) );
+    let repaired = dynamic_field.incremental_update( is_passable, | _ | 1 );
+    assert!( repaired > 0 && repaired < 100 );
+
+    let mut full = FlowField::< (), () >::new( 10, 10 );
+    full.calculate_flow( &goal, is_passable, | _ | 1 );
+    for x in 0..10
+    {
+      for y in 0..10
+      {
+        let coord = SquareCoord::< FourConnected >::new( x, y );
+        assert_eq!( dynamic_field.field().integration().get_cost( &coord ), full.integration().get_cost( &coord ) );
+      }
+    }
+  }
+}
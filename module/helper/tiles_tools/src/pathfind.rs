@@ -153,6 +153,17 @@ use crate::coordinates::{ Distance, Neighbors };
 use std::hash::Hash;
 use std::collections::{ HashMap, HashSet };
 
+/// Hierarchical pathfinding over clusters of cells for large maps.
+pub mod hierarchical;
+/// Jump point search for uniform square grids.
+pub mod jump_point;
+/// Any-angle pathfinding with Theta* and Lazy Theta*.
+pub mod any_angle;
+
+pub use hierarchical::HierarchicalGraph;
+pub use jump_point::{ jps, JumpConnectivity };
+pub use any_angle::{ theta_star, lazy_theta_star };
+
 /// Finds the shortest path between a start and goal coordinate using the A* algorithm.
 ///
 /// This function is a generic wrapper around the `pathfinding::prelude::astar` function,
@@ -366,6 +377,22 @@ where
     self.allow_diagonal = false;
     self
   }
+
+  /// Returns `true` if `coord` cannot be entered by a search starting at `origin`.
+  pub( crate ) fn is_blocked( &self, origin : &C, coord : &C ) -> bool
+  where
+    C : Distance,
+  {
+    self.obstacles.contains( coord )
+    || self.blocking_entities.contains_key( coord )
+    || self.max_distance.is_some_and( | max_dist | origin.distance( coord ) > max_dist )
+  }
+
+  /// Returns the cost of moving onto `coord`.
+  pub( crate ) fn move_cost( &self, coord : &C ) -> u32
+  {
+    self.base_cost + self.terrain_costs.get( coord ).copied().unwrap_or( 0 )
+  }
 }
 
 /// Advanced pathfinding with comprehensive configuration support.
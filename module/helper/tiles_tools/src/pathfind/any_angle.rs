@@ -0,0 +1,424 @@
+//! Any-angle pathfinding with Theta* and Lazy Theta*.
+//!
+//! Grid searches return staircase paths that look mechanical when units move
+//! in continuous space. Theta* searches the same grid as A*, but lets a cell
+//! take the parent of its predecessor as its own whenever the two can see each
+//! other, so the result is a short list of waypoints joined by straight lines.
+//! Lazy Theta* postpones the line of sight check until a cell is expanded,
+//! which saves most of the checks at the price of occasionally re-parenting.
+//!
+//! Visibility between waypoints is decided by
+//! [`FieldOfView::line_of_sight`]. A shortcut is only taken when no cell on the
+//! line is an obstacle, a blocking entity or a cell with a terrain cost, so
+//! terrain costs are paid exactly as in [`astar_advanced`](super::astar_advanced).
+//! A segment between two waypoints costs the grid distance between them times
+//! the base cost, plus the terrain cost of its last cell. This matches the
+//! cost of walking it when the line is traced cell by cell, as
+//! [`FOVAlgorithm::Bresenham`] does, which is also the cheapest algorithm
+//! to use here.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tiles_tools::pathfind::{ theta_star, PathfindingConfig };
+//! use tiles_tools::field_of_view::{ FieldOfView, FOVAlgorithm };
+//! use tiles_tools::coordinates::square::{ Coordinate, EightConnected };
+//!
+//! let fov = FieldOfView::with_algorithm( FOVAlgorithm::Bresenham );
+//! let config = PathfindingConfig::new()
+//!   .with_obstacles( ( -5..=5 ).map( | y | Coordinate::< EightConnected >::new( 10, y ) ) );
+//!
+//! let start = Coordinate::< EightConnected >::new( 0, 0 );
+//! let goal = Coordinate::< EightConnected >::new( 20, 0 );
+//! let ( waypoints, cost ) = theta_star( &start, &goal, &config, &fov ).unwrap();
+//!
+//! assert_eq!( waypoints.first(), Some( &start ) );
+//! assert_eq!( waypoints.last(), Some( &goal ) );
+//! assert!( waypoints.len() < cost as usize );
+//! ```
+
+use super::PathfindingConfig;
+use crate::coordinates::{ Distance, Neighbors };
+use crate::field_of_view::FieldOfView;
+use rustc_hash::FxHashMap;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::hash::Hash;
+
+/// Finds an any-angle path using Theta*.
+///
+/// Returns the waypoints from `start` to `goal`, both included, and the total
+/// cost of the path. Consecutive waypoints are in line of sight of each other
+/// according to `fov`. Returns `None` if the goal cannot be reached.
+///
+/// The grid is unbounded, so without `max_distance` the search is confined to
+/// cells at most two steps farther from `start` than `goal` and every cell
+/// mentioned in `config`. Beyond that all cells are open and alike, and an
+/// enclosed goal is reported as unreachable instead of searching forever.
+pub fn theta_star< C >
+(
+  start : &C,
+  goal : &C,
+  config : &PathfindingConfig< C >,
+  fov : &FieldOfView,
+)
+-> Option< ( Vec< C >, u32 ) >
+where
+  C : Distance + Neighbors + Eq + Clone + Hash,
+{
+  AnyAngleSearch::new( start, goal, config, fov ).run( false )
+}
+
+/// Finds an any-angle path using Lazy Theta*.
+///
+/// Works like [`theta_star`], but checks line of sight once per expanded cell
+/// instead of once per generated neighbour. Paths are usually the same, and
+/// the search is considerably cheaper when line of sight checks are expensive.
+pub fn lazy_theta_star< C >
+(
+  start : &C,
+  goal : &C,
+  config : &PathfindingConfig< C >,
+  fov : &FieldOfView,
+)
+-> Option< ( Vec< C >, u32 ) >
+where
+  C : Distance + Neighbors + Eq + Clone + Hash,
+{
+  AnyAngleSearch::new( start, goal, config, fov ).run( true )
+}
+
+/// Nodes and search state of one Theta* query.
+struct AnyAngleSearch< 'a, C >
+where
+  C : Eq + Clone + Hash,
+{
+  start : &'a C,
+  goal : &'a C,
+  config : &'a PathfindingConfig< C >,
+  fov : &'a FieldOfView,
+  /// Coordinates of the discovered nodes
+  nodes : Vec< C >,
+  index : FxHashMap< C, usize >,
+  /// Best known cost of every node
+  g : Vec< u32 >,
+  /// Parent of every node, the start is its own parent
+  parent : Vec< usize >,
+  closed : Vec< bool >,
+  /// Farthest distance from the start the search goes
+  reach : u32,
+}
+
+impl< 'a, C > AnyAngleSearch< 'a, C >
+where
+  C : Distance + Neighbors + Eq + Clone + Hash,
+{
+  fn new( start : &'a C, goal : &'a C, config : &'a PathfindingConfig< C >, fov : &'a FieldOfView ) -> Self
+  {
+    Self
+    {
+      start,
+      goal,
+      config,
+      fov,
+      nodes : Vec::new(),
+      index : FxHashMap::default(),
+      g : Vec::new(),
+      parent : Vec::new(),
+      closed : Vec::new(),
+      reach : config.max_distance.unwrap_or_else( || Self::default_reach( start, goal, config ) ),
+    }
+  }
+
+  /// Two steps beyond the farthest of the goal and the cells mentioned in `config`,
+  /// so that a free ring of cells around all of them can be walked on any grid
+  fn default_reach( start : &C, goal : &C, config : &PathfindingConfig< C > ) -> u32
+  {
+    config.obstacles.iter()
+    .chain( config.blocking_entities.keys() )
+    .chain( config.terrain_costs.keys() )
+    .chain( core::iter::once( goal ) )
+    .map( | coord | start.distance( coord ) )
+    .max()
+    .unwrap_or( 0 )
+    .saturating_add( 2 )
+  }
+
+  fn run( mut self, lazy : bool ) -> Option< ( Vec< C >, u32 ) >
+  {
+    if self.config.is_blocked( self.start, self.goal ) || self.start.distance( self.goal ) > self.reach
+    {
+      return None;
+    }
+
+    let mut open = BinaryHeap::new();
+    let start = self.node( self.start.clone() );
+    self.g[ start ] = 0;
+    open.push( Reverse( ( self.heuristic( start ), Reverse( 0 ), start ) ) );
+
+    // Among equal estimates the deepest node goes first, which keeps the
+    // search from flooding the many equally short paths of a grid
+    while let Some( Reverse( ( _, _, current ) ) ) = open.pop()
+    {
+      if self.closed[ current ]
+      {
+        continue;
+      }
+      if lazy
+      {
+        self.repair_parent( current );
+      }
+      if self.nodes[ current ] == *self.goal
+      {
+        return Some( self.waypoints( current ) );
+      }
+      self.closed[ current ] = true;
+
+      for neighbor in self.nodes[ current ].neighbors()
+      {
+        if self.config.is_blocked( self.start, &neighbor ) || self.start.distance( &neighbor ) > self.reach
+        {
+          continue;
+        }
+        let next = self.node( neighbor );
+        if self.closed[ next ]
+        {
+          continue;
+        }
+
+        // Line of sight is only checked when the shortcut is at least as good,
+        // ties go to the shortcut so that paths stay straight
+        let grandparent = self.parent[ current ];
+        let step = self.g[ current ] + self.config.move_cost( &self.nodes[ next ] );
+        let shortcut = self.g[ grandparent ] + self.segment_cost( grandparent, next );
+        let ( parent, cost ) = if grandparent != current
+          && shortcut <= step.min( self.g[ next ] )
+          && self.parent[ next ] != grandparent
+          && ( lazy || self.line_of_sight( grandparent, next ) )
+        {
+          ( grandparent, shortcut )
+        }
+        else
+        {
+          ( current, step )
+        };
+
+        if cost < self.g[ next ]
+        {
+          self.g[ next ] = cost;
+          self.parent[ next ] = parent;
+          open.push( Reverse( ( cost + self.heuristic( next ), Reverse( cost ), next ) ) );
+        }
+        else if cost == self.g[ next ] && parent == grandparent
+        {
+          self.parent[ next ] = parent;
+        }
+      }
+    }
+
+    None
+  }
+
+  /// Returns the index of `coord`, adding it as an unvisited node if needed.
+  fn node( &mut self, coord : C ) -> usize
+  {
+    if let Some( &index ) = self.index.get( &coord )
+    {
+      return index;
+    }
+    let index = self.nodes.len();
+    self.index.insert( coord.clone(), index );
+    self.nodes.push( coord );
+    self.g.push( u32::MAX );
+    self.parent.push( index );
+    self.closed.push( false );
+    index
+  }
+
+  fn heuristic( &self, node : usize ) -> u32
+  {
+    self.nodes[ node ].distance( self.goal ) * self.config.base_cost
+  }
+
+  fn line_of_sight( &self, from : usize, to : usize ) -> bool
+  {
+    let blocks = | coord : &C | self.config.is_blocked( self.start, coord ) || self.config.terrain_costs.contains_key( coord );
+    self.fov.line_of_sight( &self.nodes[ from ], &self.nodes[ to ], blocks )
+  }
+
+  /// Cost of the straight segment from `from` to `to`, assuming it is in line of sight.
+  fn segment_cost( &self, from : usize, to : usize ) -> u32
+  {
+    let steps = self.nodes[ from ].distance( &self.nodes[ to ] );
+    steps.saturating_sub( 1 ) * self.config.base_cost + self.config.move_cost( &self.nodes[ to ] )
+  }
+
+  /// Lazy Theta* assumes line of sight when a node is generated; if the guess
+  /// was wrong, takes the cheapest expanded neighbour as the parent instead.
+  fn repair_parent( &mut self, node : usize )
+  {
+    let parent = self.parent[ node ];
+    if parent == node || self.line_of_sight( parent, node )
+    {
+      return;
+    }
+
+    let step_cost = self.config.move_cost( &self.nodes[ node ] );
+    let best = self.nodes[ node ].neighbors().iter()
+    .filter_map( | neighbor | self.index.get( neighbor ).copied() )
+    .filter( | &neighbor | self.closed[ neighbor ] )
+    .min_by_key( | &neighbor | self.g[ neighbor ] );
+    if let Some( best ) = best
+    {
+      self.parent[ node ] = best;
+      self.g[ node ] = self.g[ best ] + step_cost;
+    }
+  }
+
+  fn waypoints( &self, goal : usize ) -> ( Vec< C >, u32 )
+  {
+    let mut chain = vec![ goal ];
+    let mut current = goal;
+    while self.parent[ current ] != current
+    {
+      current = self.parent[ current ];
+      chain.push( current );
+    }
+    let waypoints = chain.into_iter().rev().map( | node | self.nodes[ node ].clone() ).collect();
+    ( waypoints, self.g[ goal ] )
+  }
+}
+
+#[ cfg( test ) ]
+mod tests
+{
+  use super::*;
+  use crate::pathfind::astar_advanced;
+  use crate::field_of_view::FOVAlgorithm;
+  use crate::coordinates::square::{ Coordinate as SquareCoord, EightConnected };
+  use crate::coordinates::hexagonal::{ Coordinate as HexCoord, Axial, Pointy };
+
+  type Square = SquareCoord< EightConnected >;
+
+  fn assert_segments_visible< C >( waypoints : &[ C ], config : &PathfindingConfig< C >, fov : &FieldOfView )
+  where
+    C : Distance + Neighbors + Eq + Clone + Hash + std::fmt::Debug,
+  {
+    let start = &waypoints[ 0 ];
+    for pair in waypoints.windows( 2 )
+    {
+      let visible = fov.line_of_sight( &pair[ 0 ], &pair[ 1 ], | c | config.is_blocked( start, c ) || config.terrain_costs.contains_key( c ) );
+      assert!( visible, "{:?} cannot see {:?}", pair[ 0 ], pair[ 1 ] );
+    }
+  }
+
+  fn wall_config() -> PathfindingConfig< Square >
+  {
+    PathfindingConfig::new()
+    .with_obstacles( ( -6..=6 ).map( | y | Square::new( 8, y ) ) )
+    .with_obstacles( ( 2..=14 ).map( | x | Square::new( x, 6 ) ) )
+  }
+
+  #[ test ]
+  fn test_open_grid_is_one_segment()
+  {
+    let fov = FieldOfView::with_algorithm( FOVAlgorithm::Bresenham );
+    let config = PathfindingConfig::new();
+    let start = Square::new( 0, 0 );
+    let goal = Square::new( 17, -6 );
+
+    for search in [ theta_star::< Square >, lazy_theta_star::< Square > ]
+    {
+      let ( waypoints, cost ) = search( &start, &goal, &config, &fov ).unwrap();
+      assert_eq!( waypoints, vec![ start, goal ] );
+      assert_eq!( cost, 17 );
+    }
+  }
+
+  #[ test ]
+  fn test_paths_around_walls_keep_optimal_cost()
+  {
+    let fov = FieldOfView::with_algorithm( FOVAlgorithm::Bresenham );
+    let config = wall_config();
+    let start = Square::new( 0, 0 );
+    let goal = Square::new( 16, 2 );
+    let ( grid_path, grid_cost ) = astar_advanced( &start, &goal, &config ).unwrap();
+
+    for search in [ theta_star::< Square >, lazy_theta_star::< Square > ]
+    {
+      let ( waypoints, cost ) = search( &start, &goal, &config, &fov ).unwrap();
+      assert_eq!( waypoints.first(), Some( &start ) );
+      assert_eq!( waypoints.last(), Some( &goal ) );
+      assert!( waypoints.len() < grid_path.len() / 3 );
+      assert_eq!( cost, grid_cost );
+      assert_segments_visible( &waypoints, &config, &fov );
+    }
+  }
+
+  #[ test ]
+  fn test_terrain_costs_are_paid()
+  {
+    let fov = FieldOfView::with_algorithm( FOVAlgorithm::Bresenham );
+    let mut config = PathfindingConfig::new().with_max_distance( 20 );
+    for x in 3..=6
+    {
+      for y in -8..=8
+      {
+        config = config.with_terrain_cost( Square::new( x, y ), 4 );
+      }
+    }
+    let start = Square::new( 0, 0 );
+    let goal = Square::new( 10, 0 );
+    let ( _, grid_cost ) = astar_advanced( &start, &goal, &config ).unwrap();
+
+    for search in [ theta_star::< Square >, lazy_theta_star::< Square > ]
+    {
+      let ( waypoints, cost ) = search( &start, &goal, &config, &fov ).unwrap();
+      assert!( cost >= grid_cost );
+      assert!( cost > 10 );
+      assert_segments_visible( &waypoints, &config, &fov );
+    }
+  }
+
+  #[ test ]
+  fn test_hexagonal_grid()
+  {
+    type Hex = HexCoord< Axial, Pointy >;
+    let fov = FieldOfView::with_algorithm( FOVAlgorithm::Bresenham );
+    let config = PathfindingConfig::new().with_obstacles( ( -4..=4 ).map( | r | Hex::new( 4, r ) ) );
+    let start = Hex::new( 0, 0 );
+    let goal = Hex::new( 8, -1 );
+    let ( grid_path, grid_cost ) = astar_advanced( &start, &goal, &config ).unwrap();
+
+    let ( waypoints, cost ) = theta_star( &start, &goal, &config, &fov ).unwrap();
+    assert_eq!( cost, grid_cost );
+    assert!( waypoints.len() < grid_path.len() );
+    assert_segments_visible( &waypoints, &config, &fov );
+  }
+
+  #[ test ]
+  fn test_unreachable_goal()
+  {
+    let fov = FieldOfView::default();
+    let start = Square::new( 0, 0 );
+    let goal = Square::new( 30, 0 );
+
+    let limited = PathfindingConfig::new().with_max_distance( 10 );
+    assert!( theta_star( &start, &goal, &limited, &fov ).is_none() );
+
+    let enclosed = PathfindingConfig::new()
+    .with_obstacles( start.neighbors() )
+    .with_max_distance( 40 );
+    assert!( theta_star( &start, &goal, &enclosed, &fov ).is_none() );
+    assert!( lazy_theta_star( &start, &goal, &enclosed, &fov ).is_none() );
+
+    // Without a distance limit blocked and enclosed goals still end the search
+    let fov = FieldOfView::with_algorithm( FOVAlgorithm::Bresenham );
+    let blocked = PathfindingConfig::new().with_obstacle( goal );
+    assert!( theta_star( &start, &goal, &blocked, &fov ).is_none() );
+
+    let walled_in = PathfindingConfig::new().with_obstacles( goal.neighbors() );
+    assert!( theta_star( &start, &goal, &walled_in, &fov ).is_none() );
+    assert!( lazy_theta_star( &start, &goal, &walled_in, &fov ).is_none() );
+  }
+}
@@ -0,0 +1,711 @@
+//! Hierarchical pathfinding (HPA*) for large maps.
+//!
+//! [`HierarchicalGraph`] splits the map into square clusters of cells and keeps
+//! an abstract graph over the entrances between neighbouring clusters. A query
+//! searches the small abstract graph first and then refines each abstract edge
+//! with a search confined to one cluster, so it never explores the whole map.
+//!
+//! Cells are addressed through [`GridCell`], so the same graph works with
+//! hexagonal, square, isometric and triangular coordinates. Terrain is read
+//! through `is_passable` and `get_cost` callbacks, like
+//! [`DynamicFlowField`](crate::flowfield::DynamicFlowField). When terrain
+//! changes, [`HierarchicalGraph::mark_dirty`] and
+//! [`HierarchicalGraph::incremental_update`] rebuild only the touched clusters.
+//!
+//! Paths are near-optimal: they always pass through entrance cells, which may
+//! make them slightly longer than the ones found by [`astar`](super::astar).
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tiles_tools::pathfind::HierarchicalGraph;
+//! use tiles_tools::coordinates::square::{ Coordinate, FourConnected };
+//!
+//! let is_passable = | c : &Coordinate< FourConnected > | c.x != 8 || c.y == 30;
+//! let mut graph = HierarchicalGraph::new( 32, 32, 8 );
+//! graph.build( is_passable, | _ | 1 );
+//!
+//! let start = Coordinate::< FourConnected >::new( 0, 0 );
+//! let goal = Coordinate::< FourConnected >::new( 31, 0 );
+//! let ( path, cost ) = graph.find_path( &start, &goal, is_passable, | _ | 1 ).unwrap();
+//! assert_eq!( path.first(), Some( &start ) );
+//! assert_eq!( path.last(), Some( &goal ) );
+//! assert_eq!( cost as usize, path.len() - 1 );
+//! ```
+
+use crate::coordinates::{ Distance, Neighbors };
+use crate::collection::GridCell;
+use rustc_hash::FxHashMap;
+use std::cmp::Reverse;
+use std::collections::{ BinaryHeap, HashMap, HashSet };
+use std::marker::PhantomData;
+
+/// Cell of the map, see [`GridCell::to_cell`].
+type Cell = ( i32, i32 );
+
+/// Cluster index, counted in clusters from the minimum corner of the map.
+type Cluster = ( i32, i32 );
+
+/// Edges leaving an abstract node, with the cost of taking each.
+type Edges = Vec< ( Cell, u32 ) >;
+
+/// Border runs at least this long get a transition at both ends instead of one in the middle.
+const LONG_ENTRANCE : usize = 6;
+
+/// Abstract graph of cluster entrances for hierarchical pathfinding.
+///
+/// The graph covers the cells in `min_inclusive..max_exclusive` and must be
+/// built with [`Self::build`] before the first query. The same `is_passable`
+/// and `get_cost` callbacks have to be passed to every method; `get_cost`
+/// returns the cost of moving onto a cell, as in [`astar`](super::astar).
+#[ derive( Debug ) ]
+pub struct HierarchicalGraph< C >
+{
+  /// Cluster side length in cells
+  cluster_size : i32,
+  /// Minimum covered cell
+  min : Cell,
+  /// Cell past the maximum covered cell
+  max : Cell,
+  /// Entrance nodes of every cluster with their outgoing edges
+  nodes : HashMap< Cluster, HashMap< Cell, Edges > >,
+  /// Transitions between pairs of clusters, the lower cluster first in both key and value
+  transitions : HashMap< ( Cluster, Cluster ), Vec< ( Cell, Cell ) > >,
+  /// Clusters whose terrain changed since the last update
+  dirty : HashSet< Cluster >,
+  _marker : PhantomData< C >,
+}
+
+impl< C > Clone for HierarchicalGraph< C >
+{
+  fn clone( &self ) -> Self
+  {
+    Self
+    {
+      cluster_size : self.cluster_size,
+      min : self.min,
+      max : self.max,
+      nodes : self.nodes.clone(),
+      transitions : self.transitions.clone(),
+      dirty : self.dirty.clone(),
+      _marker : PhantomData,
+    }
+  }
+}
+
+impl< C > HierarchicalGraph< C >
+where
+  C : GridCell + Neighbors + Distance,
+{
+  /// Creates a graph covering cells `( 0, 0 )` to `( width - 1, height - 1 )`.
+  ///
+  /// # Panics
+  /// Panics if `cluster_size` is zero.
+  pub fn new( width : i32, height : i32, cluster_size : u32 ) -> Self
+  {
+    Self::with_bounds( ( 0, 0 ), ( width.max( 0 ), height.max( 0 ) ), cluster_size )
+  }
+
+  /// Creates a graph covering the cells in `min_inclusive..max_exclusive`.
+  ///
+  /// # Panics
+  /// Panics if `cluster_size` is zero or does not fit in `i32`.
+  pub fn with_bounds( min_inclusive : ( i32, i32 ), max_exclusive : ( i32, i32 ), cluster_size : u32 ) -> Self
+  {
+    assert!( cluster_size > 0, "Cluster size must be positive" );
+    Self
+    {
+      cluster_size : i32::try_from( cluster_size ).expect( "Cluster size is too large" ),
+      min : min_inclusive,
+      max : ( max_exclusive.0.max( min_inclusive.0 ), max_exclusive.1.max( min_inclusive.1 ) ),
+      nodes : HashMap::new(),
+      transitions : HashMap::new(),
+      dirty : HashSet::new(),
+      _marker : PhantomData,
+    }
+  }
+
+  /// Returns the cluster side length in cells.
+  pub fn cluster_size( &self ) -> u32
+  {
+    self.cluster_size.unsigned_abs()
+  }
+
+  /// Returns the cluster containing `coord`, or `None` outside the graph.
+  pub fn cluster_of( &self, coord : &C ) -> Option< ( i32, i32 ) >
+  {
+    let cell = coord.to_cell();
+    self.in_bounds( cell ).then( || self.cluster( cell ) )
+  }
+
+  /// Returns the number of entrance nodes in the abstract graph.
+  pub fn node_count( &self ) -> usize
+  {
+    self.nodes.values().map( HashMap::len ).sum()
+  }
+
+  /// Returns the number of edges in the abstract graph.
+  pub fn edge_count( &self ) -> usize
+  {
+    self.nodes.values().flat_map( HashMap::values ).map( Vec::len ).sum()
+  }
+
+  /// Builds the whole abstract graph from scratch and clears pending changes.
+  pub fn build< Fa, Fc >( &mut self, is_passable : Fa, get_cost : Fc )
+  where
+    Fa : Fn( &C ) -> bool,
+    Fc : Fn( &C ) -> u32,
+  {
+    self.nodes.clear();
+    self.transitions.clear();
+    self.dirty = self.clusters().collect();
+    self.incremental_update( is_passable, get_cost );
+  }
+
+  /// Marks the cluster containing `coord` as changed.
+  ///
+  /// Call this whenever passability or cost of `coord` changes; positions
+  /// outside the graph are ignored.
+  pub fn mark_dirty( &mut self, coord : &C )
+  {
+    let cell = coord.to_cell();
+    if self.in_bounds( cell )
+    {
+      self.dirty.insert( self.cluster( cell ) );
+    }
+  }
+
+  /// Rebuilds the clusters marked dirty since the last update.
+  ///
+  /// Entrances on the borders of a dirty cluster are recomputed, so its
+  /// neighbours get their internal edges recomputed too. Returns the number of
+  /// clusters that were rebuilt.
+  pub fn incremental_update< Fa, Fc >( &mut self, is_passable : Fa, get_cost : Fc ) -> usize
+  where
+    Fa : Fn( &C ) -> bool,
+    Fc : Fn( &C ) -> u32,
+  {
+    let dirty = std::mem::take( &mut self.dirty );
+    let mut touched = dirty.clone();
+
+    for &cluster in &dirty
+    {
+      for ( other, edges ) in self.border_edges( cluster, &is_passable )
+      {
+        touched.insert( other );
+        let key = if cluster < other { ( cluster, other ) } else { ( other, cluster ) };
+        if dirty.contains( &other ) && other < cluster
+        {
+          // Already computed from the other side
+          continue;
+        }
+        let transitions = Self::entrances( edges, cluster < other );
+        if transitions.is_empty()
+        {
+          self.transitions.remove( &key );
+        }
+        else
+        {
+          self.transitions.insert( key, transitions );
+        }
+      }
+    }
+
+    for &cluster in &touched
+    {
+      self.rebuild_cluster( cluster, &is_passable, &get_cost );
+    }
+
+    touched.len()
+  }
+
+  /// Finds a path from `start` to `goal` through the abstract graph.
+  ///
+  /// The path includes both ends and has the same shape as the one returned by
+  /// [`astar`](super::astar), together with its total cost. Returns `None` if
+  /// either end lies outside the graph, `goal` is impassable, or no path exists.
+  pub fn find_path< Fa, Fc >( &self, start : &C, goal : &C, is_passable : Fa, get_cost : Fc ) -> Option< ( Vec< C >, u32 ) >
+  where
+    Fa : Fn( &C ) -> bool,
+    Fc : Fn( &C ) -> u32,
+  {
+    let source = start.to_cell();
+    let target = goal.to_cell();
+    if !self.in_bounds( source ) || !self.in_bounds( target ) || !is_passable( goal )
+    {
+      return None;
+    }
+    if source == target
+    {
+      return Some( ( vec![ C::from_cell( source.0, source.1 ) ], 0 ) );
+    }
+
+    let source_cluster = self.cluster( source );
+    let target_cluster = self.cluster( target );
+    let empty = HashMap::new();
+    let source_nodes = self.nodes.get( &source_cluster ).unwrap_or( &empty );
+    let target_nodes = self.nodes.get( &target_cluster ).unwrap_or( &empty );
+
+    // Connect both ends to the entrances of their clusters
+    let from_source = self.local_costs( source, true, &is_passable, &get_cost );
+    let mut source_edges : Edges = source_nodes
+    .keys()
+    .filter_map( | node | from_source.get( node ).map( | cost | ( *node, *cost ) ) )
+    .collect();
+    if let Some( cost ) = from_source.get( &target )
+    {
+      source_edges.push( ( target, *cost ) );
+    }
+    let to_target = self.local_costs( target, false, &is_passable, &get_cost );
+
+    let ( nodes, _ ) = pathfinding::prelude::astar
+    (
+      &source,
+      | node |
+      {
+        let mut successors = Vec::new();
+        if *node == source
+        {
+          successors.extend( source_edges.iter().copied() );
+        }
+        if let Some( edges ) = self.nodes.get( &self.cluster( *node ) ).and_then( | nodes | nodes.get( node ) )
+        {
+          successors.extend( edges.iter().copied() );
+        }
+        if target_nodes.contains_key( node )
+        {
+          if let Some( cost ) = to_target.get( node )
+          {
+            successors.push( ( target, *cost ) );
+          }
+        }
+        successors
+      },
+      | node | C::from_cell( node.0, node.1 ).distance( goal ),
+      | node | *node == target,
+    )?;
+
+    let mut path = vec![ source ];
+    for pair in nodes.windows( 2 )
+    {
+      let ( from, to ) = ( pair[ 0 ], pair[ 1 ] );
+      if self.cluster( from ) == self.cluster( to )
+      {
+        let ( segment, _ ) = self.local_path( from, to, &is_passable, &get_cost )?;
+        path.extend( segment.into_iter().skip( 1 ) );
+      }
+      else
+      {
+        path.push( to );
+      }
+    }
+
+    let path : Vec< C > = path.into_iter().map( | ( column, row ) | C::from_cell( column, row ) ).collect();
+    let cost = path.iter().skip( 1 ).map( &get_cost ).sum();
+    Some( ( path, cost ) )
+  }
+
+  /// Returns `true` if the cell lies within the graph.
+  fn in_bounds( &self, ( column, row ) : Cell ) -> bool
+  {
+    ( self.min.0..self.max.0 ).contains( &column ) && ( self.min.1..self.max.1 ).contains( &row )
+  }
+
+  /// Returns the cluster containing an in-bounds cell.
+  fn cluster( &self, ( column, row ) : Cell ) -> Cluster
+  {
+    (
+      ( column - self.min.0 ).div_euclid( self.cluster_size ),
+      ( row - self.min.1 ).div_euclid( self.cluster_size ),
+    )
+  }
+
+  /// Returns every cluster of the graph.
+  fn clusters( &self ) -> impl Iterator< Item = Cluster >
+  {
+    let columns = ( self.max.0 - self.min.0 + self.cluster_size - 1 ) / self.cluster_size;
+    let rows = ( self.max.1 - self.min.1 + self.cluster_size - 1 ) / self.cluster_size;
+    ( 0..columns ).flat_map( move | x | ( 0..rows ).map( move | y | ( x, y ) ) )
+  }
+
+  /// Returns the in-bounds cells of a cluster.
+  fn cells( &self, ( x, y ) : Cluster ) -> impl Iterator< Item = Cell >
+  {
+    let columns = ( self.min.0 + x * self.cluster_size )..( self.min.0 + ( x + 1 ) * self.cluster_size ).min( self.max.0 );
+    let rows = ( self.min.1 + y * self.cluster_size )..( self.min.1 + ( y + 1 ) * self.cluster_size ).min( self.max.1 );
+    columns.flat_map( move | column | rows.clone().map( move | row | ( column, row ) ) )
+  }
+
+  /// Returns the in-bounds neighbour cells of `cell` under the neighbourhood of `C`.
+  fn neighbor_cells( &self, cell : Cell ) -> impl Iterator< Item = Cell > + '_
+  {
+    C::from_cell( cell.0, cell.1 )
+    .neighbors()
+    .into_iter()
+    .map( | neighbor | neighbor.to_cell() )
+    .filter( | neighbor | self.in_bounds( *neighbor ) )
+  }
+
+  /// Returns the passable edges leaving `cluster`, grouped by the cluster they enter.
+  fn border_edges< Fa >( &self, cluster : Cluster, is_passable : &Fa ) -> HashMap< Cluster, Vec< ( Cell, Cell ) > >
+  where
+    Fa : Fn( &C ) -> bool,
+  {
+    let passable = | cell : Cell | is_passable( &C::from_cell( cell.0, cell.1 ) );
+    let mut edges : HashMap< Cluster, Vec< ( Cell, Cell ) > > = HashMap::new();
+    for cell in self.cells( cluster )
+    {
+      let inside = passable( cell );
+      for neighbor in self.neighbor_cells( cell )
+      {
+        let other = self.cluster( neighbor );
+        if other == cluster
+        {
+          continue;
+        }
+        // Neighbouring clusters are rebuilt even when no edge survives
+        let entry = edges.entry( other ).or_default();
+        if inside && passable( neighbor )
+        {
+          entry.push( ( cell, neighbor ) );
+        }
+      }
+    }
+    edges
+  }
+
+  /// Picks transitions out of the edges between two clusters.
+  ///
+  /// Edges whose cells touch each other form one entrance. Short entrances get
+  /// a single transition in their middle, long ones get one at each end.
+  /// Transitions are returned lower cluster first; `inner_first` tells whether
+  /// `edges` already start in the lower cluster.
+  fn entrances( mut edges : Vec< ( Cell, Cell ) >, inner_first : bool ) -> Vec< ( Cell, Cell ) >
+  {
+    if !inner_first
+    {
+      edges = edges.into_iter().map( | ( inner, outer ) | ( outer, inner ) ).collect();
+    }
+    edges.sort_unstable();
+    edges.dedup();
+
+    // Edges sharing or touching a cell on either side belong to the same entrance
+    let mut by_cell : HashMap< Cell, Vec< usize > > = HashMap::new();
+    for ( index, &( inner, outer ) ) in edges.iter().enumerate()
+    {
+      by_cell.entry( inner ).or_default().push( index );
+      by_cell.entry( outer ).or_default().push( index );
+    }
+
+    let mut visited = vec![ false; edges.len() ];
+    let mut transitions = Vec::new();
+    for first in 0..edges.len()
+    {
+      if visited[ first ]
+      {
+        continue;
+      }
+      visited[ first ] = true;
+      let mut run = vec![ first ];
+      let mut index = 0;
+      while let Some( &current ) = run.get( index )
+      {
+        index += 1;
+        let ( inner, outer ) = edges[ current ];
+        for cell in [ inner, outer ]
+        {
+          let around = C::from_cell( cell.0, cell.1 ).neighbors().into_iter().map( | neighbor | neighbor.to_cell() );
+          for other in std::iter::once( cell ).chain( around ).filter_map( | near | by_cell.get( &near ) ).flatten()
+          {
+            if !visited[ *other ]
+            {
+              visited[ *other ] = true;
+              run.push( *other );
+            }
+          }
+        }
+      }
+
+      run.sort_unstable();
+      if run.len() >= LONG_ENTRANCE
+      {
+        transitions.push( edges[ run[ 0 ] ] );
+        transitions.push( edges[ run[ run.len() - 1 ] ] );
+      }
+      else
+      {
+        transitions.push( edges[ run[ run.len() / 2 ] ] );
+      }
+    }
+    transitions
+  }
+
+  /// Recomputes the entrance nodes of a cluster and all edges leaving them.
+  fn rebuild_cluster< Fa, Fc >( &mut self, cluster : Cluster, is_passable : &Fa, get_cost : &Fc )
+  where
+    Fa : Fn( &C ) -> bool,
+    Fc : Fn( &C ) -> u32,
+  {
+    let cost_of = | cell : Cell | get_cost( &C::from_cell( cell.0, cell.1 ) );
+
+    let mut nodes : HashMap< Cell, Edges > = HashMap::new();
+    let neighbours : HashSet< Cluster > = self.cells( cluster )
+    .flat_map( | cell | self.neighbor_cells( cell ).collect::< Vec< _ > >() )
+    .map( | cell | self.cluster( cell ) )
+    .filter( | other | *other != cluster )
+    .collect();
+    for other in neighbours
+    {
+      let key = if cluster < other { ( cluster, other ) } else { ( other, cluster ) };
+      for &( lower, upper ) in self.transitions.get( &key ).into_iter().flatten()
+      {
+        let ( inner, outer ) = if cluster < other { ( lower, upper ) } else { ( upper, lower ) };
+        nodes.entry( inner ).or_default().push( ( outer, cost_of( outer ) ) );
+      }
+    }
+
+    let entrances : Vec< Cell > = nodes.keys().copied().collect();
+    for &entrance in &entrances
+    {
+      let costs = self.local_costs( entrance, true, is_passable, get_cost );
+      let edges = nodes.get_mut( &entrance ).expect( "Entrance was just inserted" );
+      for &other in &entrances
+      {
+        if other != entrance
+        {
+          if let Some( cost ) = costs.get( &other )
+          {
+            edges.push( ( other, *cost ) );
+          }
+        }
+      }
+    }
+
+    if nodes.is_empty()
+    {
+      self.nodes.remove( &cluster );
+    }
+    else
+    {
+      self.nodes.insert( cluster, nodes );
+    }
+  }
+
+  /// Runs Dijkstra inside the cluster of `origin`.
+  ///
+  /// With `outward` the result holds the cost of reaching each cell from
+  /// `origin`, otherwise the cost of reaching `origin` from each cell.
+  fn local_costs< Fa, Fc >( &self, origin : Cell, outward : bool, is_passable : &Fa, get_cost : &Fc ) -> FxHashMap< Cell, u32 >
+  where
+    Fa : Fn( &C ) -> bool,
+    Fc : Fn( &C ) -> u32,
+  {
+    let cluster = self.cluster( origin );
+    let mut costs = FxHashMap::default();
+    costs.insert( origin, 0_u32 );
+    let mut frontier = BinaryHeap::from( [ Reverse( ( 0_u32, origin ) ) ] );
+
+    while let Some( Reverse( ( cost, cell ) ) ) = frontier.pop()
+    {
+      if costs.get( &cell ).is_some_and( | best | *best < cost )
+      {
+        continue;
+      }
+      let coord = C::from_cell( cell.0, cell.1 );
+      for neighbor in self.neighbor_cells( cell )
+      {
+        let candidate = C::from_cell( neighbor.0, neighbor.1 );
+        if self.cluster( neighbor ) != cluster || !is_passable( &candidate )
+        {
+          continue;
+        }
+        let step = if outward { get_cost( &candidate ) } else { get_cost( &coord ) };
+        let next = cost.saturating_add( step );
+        if costs.get( &neighbor ).map_or( true, | best | next < *best )
+        {
+          costs.insert( neighbor, next );
+          frontier.push( Reverse( ( next, neighbor ) ) );
+        }
+      }
+    }
+
+    costs
+  }
+
+  /// Finds the cheapest path between two cells of the same cluster without leaving it.
+  fn local_path< Fa, Fc >( &self, from : Cell, to : Cell, is_passable : &Fa, get_cost : &Fc ) -> Option< ( Vec< Cell >, u32 ) >
+  where
+    Fa : Fn( &C ) -> bool,
+    Fc : Fn( &C ) -> u32,
+  {
+    let cluster = self.cluster( from );
+    let goal = C::from_cell( to.0, to.1 );
+    pathfinding::prelude::astar
+    (
+      &from,
+      | cell |
+      {
+        self.neighbor_cells( *cell )
+        .filter( | neighbor | self.cluster( *neighbor ) == cluster )
+        .filter_map( | neighbor |
+        {
+          let coord = C::from_cell( neighbor.0, neighbor.1 );
+          is_passable( &coord ).then( || ( neighbor, get_cost( &coord ) ) )
+        } )
+        .collect::< Vec< _ > >()
+      },
+      | cell | C::from_cell( cell.0, cell.1 ).distance( &goal ),
+      | cell | *cell == to,
+    )
+  }
+}
+
+#[ cfg( test ) ]
+mod tests
+{
+  use super::*;
+  use crate::pathfind::astar;
+  use crate::coordinates::square::{ Coordinate as SquareCoord, FourConnected, EightConnected };
+  use crate::coordinates::hexagonal::{ Coordinate as HexCoord, Axial, Pointy };
+
+  type Square = SquareCoord< FourConnected >;
+
+  fn assert_valid_path< C >( path : &[ C ], start : &C, goal : &C, is_passable : impl Fn( &C ) -> bool )
+  where
+    C : Neighbors + PartialEq + std::fmt::Debug,
+  {
+    assert_eq!( path.first(), Some( start ) );
+    assert_eq!( path.last(), Some( goal ) );
+    for pair in path.windows( 2 )
+    {
+      assert!( pair[ 0 ].neighbors().contains( &pair[ 1 ] ), "{:?} -> {:?} is not a step", pair[ 0 ], pair[ 1 ] );
+      assert!( is_passable( &pair[ 1 ] ), "{:?} is blocked", pair[ 1 ] );
+    }
+  }
+
+  #[ test ]
+  fn test_open_map_matches_astar()
+  {
+    let mut graph = HierarchicalGraph::< Square >::new( 40, 40, 8 );
+    graph.build( | _ | true, | _ | 1 );
+    assert!( graph.node_count() > 0 );
+
+    let start = Square::new( 1, 2 );
+    let goal = Square::new( 37, 33 );
+    let ( path, cost ) = graph.find_path( &start, &goal, | _ | true, | _ | 1 ).unwrap();
+    assert_valid_path( &path, &start, &goal, | _ | true );
+    let ( _, optimal ) = astar( &start, &goal, | _ | true, | _ | 1 ).unwrap();
+    assert_eq!( cost, optimal );
+    assert_eq!( cost as usize, path.len() - 1 );
+  }
+
+  #[ test ]
+  fn test_path_through_single_gap()
+  {
+    // Vertical wall at x = 12 with one gap
+    let is_passable = | c : &Square | c.x != 12 || c.y == 25;
+    let mut graph = HierarchicalGraph::new( 32, 32, 8 );
+    graph.build( is_passable, | _ | 1 );
+
+    let start = Square::new( 2, 2 );
+    let goal = Square::new( 30, 2 );
+    let ( path, cost ) = graph.find_path( &start, &goal, is_passable, | _ | 1 ).unwrap();
+    assert_valid_path( &path, &start, &goal, is_passable );
+    assert!( path.contains( &Square::new( 12, 25 ) ) );
+    assert_eq!( cost as usize, path.len() - 1 );
+  }
+
+  #[ test ]
+  fn test_same_cluster_and_unreachable()
+  {
+    let is_passable = | c : &Square | c.x != 5;
+    let mut graph = HierarchicalGraph::new( 16, 16, 4 );
+    graph.build( is_passable, | _ | 1 );
+
+    let start = Square::new( 0, 0 );
+    let near = Square::new( 2, 3 );
+    let ( path, cost ) = graph.find_path( &start, &near, is_passable, | _ | 1 ).unwrap();
+    assert_valid_path( &path, &start, &near, is_passable );
+    assert_eq!( cost, 5 );
+
+    assert!( graph.find_path( &start, &Square::new( 10, 10 ), is_passable, | _ | 1 ).is_none() );
+    assert!( graph.find_path( &start, &Square::new( 5, 5 ), is_passable, | _ | 1 ).is_none() );
+    assert!( graph.find_path( &start, &Square::new( 16, 0 ), is_passable, | _ | 1 ).is_none() );
+  }
+
+  #[ test ]
+  fn test_incremental_update_rebuilds_touched_clusters()
+  {
+    let mut walls : HashSet< Cell > = ( 0..32 ).map( | y | ( 12, y ) ).collect();
+    let mut graph = HierarchicalGraph::< Square >::new( 32, 32, 8 );
+    {
+      let is_passable = | c : &Square | !walls.contains( &( c.x, c.y ) );
+      graph.build( is_passable, | _ | 1 );
+      assert!( graph.find_path( &Square::new( 0, 0 ), &Square::new( 31, 31 ), is_passable, | _ | 1 ).is_none() );
+    }
+
+    walls.remove( &( 12, 20 ) );
+    graph.mark_dirty( &Square::new( 12, 20 ) );
+    let is_passable = | c : &Square | !walls.contains( &( c.x, c.y ) );
+    let rebuilt = graph.incremental_update( is_passable, | _ | 1 );
+    assert!( rebuilt > 1 && rebuilt < 16, "rebuilt {rebuilt} clusters" );
+
+    let start = Square::new( 0, 0 );
+    let goal = Square::new( 31, 31 );
+    let ( path, _ ) = graph.find_path( &start, &goal, is_passable, | _ | 1 ).unwrap();
+    assert_valid_path( &path, &start, &goal, is_passable );
+
+    let mut fresh = HierarchicalGraph::< Square >::new( 32, 32, 8 );
+    fresh.build( is_passable, | _ | 1 );
+    assert_eq!( fresh.node_count(), graph.node_count() );
+    assert_eq!( fresh.edge_count(), graph.edge_count() );
+  }
+
+  #[ test ]
+  fn test_terrain_costs()
+  {
+    // Expensive band with a cheap corridor at y = 15
+    let get_cost = | c : &SquareCoord< EightConnected > | if ( 8..24 ).contains( &c.x ) && c.y != 15 { 10 } else { 1 };
+    let mut graph = HierarchicalGraph::new( 32, 32, 8 );
+    graph.build( | _ | true, get_cost );
+
+    let start = SquareCoord::< EightConnected >::new( 0, 15 );
+    let goal = SquareCoord::< EightConnected >::new( 31, 15 );
+    let ( path, cost ) = graph.find_path( &start, &goal, | _ | true, get_cost ).unwrap();
+    assert_valid_path( &path, &start, &goal, | _ | true );
+    assert_eq!( cost, path.iter().skip( 1 ).map( get_cost ).sum::< u32 >() );
+    assert!( cost < 40, "path should follow the corridor, cost {cost}" );
+  }
+
+  #[ test ]
+  fn test_hexagonal_map()
+  {
+    type Hex = HexCoord< Axial, Pointy >;
+    let is_passable = | c : &Hex | c.q != 0 || c.r == 6;
+    let mut graph = HierarchicalGraph::with_bounds( ( -10, -10 ), ( 10, 10 ), 5 );
+    graph.build( is_passable, | _ | 1 );
+
+    let start = Hex::new( -6, 0 );
+    let goal = Hex::new( 6, -2 );
+    let ( path, cost ) = graph.find_path( &start, &goal, is_passable, | _ | 1 ).unwrap();
+    assert_valid_path( &path, &start, &goal, is_passable );
+    assert!( path.contains( &Hex::new( 0, 6 ) ) );
+    assert_eq!( cost as usize, path.len() - 1 );
+  }
+
+  #[ test ]
+  fn test_triangular_map()
+  {
+    use crate::coordinates::triangular::{ Coordinate as TriCoord, FlatSided };
+    type Tri = TriCoord< FlatSided >;
+
+    let mut graph = HierarchicalGraph::< Tri >::new( 24, 12, 4 );
+    graph.build( | _ | true, | _ | 1 );
+
+    let start = Tri::from_cell( 0, 0 );
+    let goal = Tri::from_cell( 23, 11 );
+    let ( path, cost ) = graph.find_path( &start, &goal, | _ | true, | _ | 1 ).unwrap();
+    assert_valid_path( &path, &start, &goal, | _ | true );
+    assert_eq!( cost as usize, path.len() - 1 );
+  }
+}
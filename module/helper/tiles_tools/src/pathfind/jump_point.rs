@@ -0,0 +1,500 @@
+//! Jump point search (JPS) for square grids.
+//!
+//! On a grid where most moves cost the same, plain A* pushes every cell of
+//! every equally short path into its open list. JPS prunes those symmetric
+//! paths: the search jumps along straight and diagonal lines and only stops at
+//! cells where an obstacle forces a turn, so the open list holds a handful of
+//! jump points instead of whole areas.
+//!
+//! [`jps`] accepts the same [`PathfindingConfig`] as
+//! [`astar_advanced`](super::astar_advanced) and returns the same result: the
+//! full cell-by-cell path and its cost. Obstacles, blocking entities and the
+//! search distance limit are honoured. Cells with a terrain cost break the
+//! symmetry JPS relies on, so jumps stop next to them and the search expands
+//! their surroundings like A* would; maps with few costed cells keep most of
+//! the speed-up.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use tiles_tools::pathfind::{ jps, astar_advanced, PathfindingConfig };
+//! use tiles_tools::coordinates::square::{ Coordinate, EightConnected };
+//!
+//! let config = PathfindingConfig::new()
+//!   .with_obstacles( ( -5..=5 ).map( | y | Coordinate::< EightConnected >::new( 10, y ) ) );
+//!
+//! let start = Coordinate::< EightConnected >::new( 0, 0 );
+//! let goal = Coordinate::< EightConnected >::new( 20, 0 );
+//! let ( path, cost ) = jps( &start, &goal, &config ).unwrap();
+//!
+//! assert_eq!( path.first(), Some( &start ) );
+//! assert_eq!( path.last(), Some( &goal ) );
+//! assert_eq!( Some( cost ), astar_advanced( &start, &goal, &config ).map( | ( _, cost ) | cost ) );
+//! ```
+
+use super::PathfindingConfig;
+use crate::coordinates::square::{ Coordinate, FourConnected, EightConnected };
+use crate::coordinates::Distance;
+use rustc_hash::FxHashMap;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Cell of the grid as `( x, y )`.
+type Cell = ( i32, i32 );
+
+/// Unit step between two cells.
+type Direction = ( i32, i32 );
+
+const STRAIGHT : [ Direction; 4 ] = [ ( 1, 0 ), ( -1, 0 ), ( 0, 1 ), ( 0, -1 ) ];
+const DIAGONAL : [ Direction; 4 ] = [ ( 1, 1 ), ( -1, 1 ), ( 1, -1 ), ( -1, -1 ) ];
+
+/// Square grid connectivity supported by [`jps`].
+pub trait JumpConnectivity : Copy + Eq + std::hash::Hash
+{
+  /// Whether diagonal moves are allowed.
+  const DIAGONAL : bool;
+}
+
+impl JumpConnectivity for FourConnected
+{
+  const DIAGONAL : bool = false;
+}
+
+impl JumpConnectivity for EightConnected
+{
+  const DIAGONAL : bool = true;
+}
+
+/// Finds the shortest path on a square grid using jump point search.
+///
+/// Returns the full path from `start` to `goal`, both included, and its total
+/// cost, exactly like [`astar_advanced`](super::astar_advanced) with the same
+/// `config`. Moving onto a cell costs the configured base cost plus the
+/// terrain cost of that cell.
+///
+/// The grid is unbounded, so the search is confined to the bounding box of
+/// `start`, `goal` and every cell mentioned in `config`, grown by one cell.
+/// No shortest path ever needs to leave that box.
+pub fn jps< K >
+(
+  start : &Coordinate< K >,
+  goal : &Coordinate< K >,
+  config : &PathfindingConfig< Coordinate< K > >,
+)
+-> Option< ( Vec< Coordinate< K > >, u32 ) >
+where
+  K : JumpConnectivity,
+  Coordinate< K > : Distance,
+{
+  if let Some( max_dist ) = config.max_distance
+  {
+    if start.distance( goal ) > max_dist
+    {
+      return None;
+    }
+  }
+
+  let search = JumpSearch::new( start, goal, config );
+  let start_cell = ( start.x, start.y );
+  let goal_cell = ( goal.x, goal.y );
+
+  let mut g : FxHashMap< Cell, u32 > = FxHashMap::default();
+  let mut parents : FxHashMap< Cell, Cell > = FxHashMap::default();
+  let mut open = BinaryHeap::new();
+  g.insert( start_cell, 0 );
+  open.push( Reverse( ( search.heuristic( start_cell ), start_cell ) ) );
+
+  while let Some( Reverse( ( estimate, cell ) ) ) = open.pop()
+  {
+    let cost = g[ &cell ];
+    if estimate > cost + search.heuristic( cell )
+    {
+      continue;
+    }
+    if cell == goal_cell
+    {
+      return Some( ( search.unfold( &parents, start_cell, goal_cell ), cost ) );
+    }
+
+    let parent = parents.get( &cell ).copied();
+    for direction in search.successors( cell, parent )
+    {
+      let Some( jump_point ) = search.jump( cell, direction ) else { continue };
+      let next_cost = cost + search.segment_cost( cell, jump_point );
+      if g.get( &jump_point ).map_or( true, | &known | next_cost < known )
+      {
+        g.insert( jump_point, next_cost );
+        parents.insert( jump_point, cell );
+        open.push( Reverse( ( next_cost + search.heuristic( jump_point ), jump_point ) ) );
+      }
+    }
+  }
+
+  None
+}
+
+/// State shared by the steps of one jump point search.
+struct JumpSearch< 'a, K >
+where
+  K : JumpConnectivity,
+{
+  start : &'a Coordinate< K >,
+  goal : Cell,
+  config : &'a PathfindingConfig< Coordinate< K > >,
+  /// Minimum cell the search may enter
+  min : Cell,
+  /// Maximum cell the search may enter
+  max : Cell,
+}
+
+impl< 'a, K > JumpSearch< 'a, K >
+where
+  K : JumpConnectivity,
+  Coordinate< K > : Distance,
+{
+  fn new
+  (
+    start : &'a Coordinate< K >,
+    goal : &Coordinate< K >,
+    config : &'a PathfindingConfig< Coordinate< K > >,
+  )
+  -> Self
+  {
+    let mut min = ( start.x.min( goal.x ), start.y.min( goal.y ) );
+    let mut max = ( start.x.max( goal.x ), start.y.max( goal.y ) );
+    let mentioned = config.obstacles.iter()
+    .chain( config.blocking_entities.keys() )
+    .chain( config.terrain_costs.keys() );
+    for coord in mentioned
+    {
+      min = ( min.0.min( coord.x ), min.1.min( coord.y ) );
+      max = ( max.0.max( coord.x ), max.1.max( coord.y ) );
+    }
+
+    Self
+    {
+      start,
+      goal : ( goal.x, goal.y ),
+      config,
+      min : ( min.0 - 1, min.1 - 1 ),
+      max : ( max.0 + 1, max.1 + 1 ),
+    }
+  }
+
+  fn heuristic( &self, cell : Cell ) -> u32
+  {
+    Coordinate::< K >::new( cell.0, cell.1 ).distance( &Coordinate::new( self.goal.0, self.goal.1 ) ) * self.config.base_cost
+  }
+
+  fn is_blocked( &self, cell : Cell ) -> bool
+  {
+    cell.0 < self.min.0 || cell.1 < self.min.1 || cell.0 > self.max.0 || cell.1 > self.max.1
+    || self.config.is_blocked( self.start, &Coordinate::new( cell.0, cell.1 ) )
+  }
+
+  /// Returns `true` if a cell around `cell`, or the cell itself, has a terrain cost.
+  fn is_uneven( &self, cell : Cell ) -> bool
+  {
+    if self.config.terrain_costs.is_empty()
+    {
+      return false;
+    }
+    ( -1..=1 ).any( | dx | ( -1..=1 ).any( | dy |
+    {
+      self.config.terrain_costs.contains_key( &Coordinate::new( cell.0 + dx, cell.1 + dy ) )
+    }))
+  }
+
+  /// Directions worth jumping to from `cell`, given the jump point it was reached from.
+  fn successors( &self, cell : Cell, parent : Option< Cell > ) -> Vec< Direction >
+  {
+    let all = || if K::DIAGONAL { [ STRAIGHT, DIAGONAL ].concat() } else { STRAIGHT.to_vec() };
+    let Some( parent ) = parent else { return all() };
+    if self.is_uneven( cell )
+    {
+      return all();
+    }
+
+    let ( dx, dy ) = ( ( cell.0 - parent.0 ).signum(), ( cell.1 - parent.1 ).signum() );
+    let mut directions = vec![ ( dx, dy ) ];
+    if K::DIAGONAL
+    {
+      if dx != 0 && dy != 0
+      {
+        directions.extend( [ ( dx, 0 ), ( 0, dy ) ] );
+        if self.is_blocked( ( cell.0 - dx, cell.1 ) )
+        {
+          directions.push( ( -dx, dy ) );
+        }
+        if self.is_blocked( ( cell.0, cell.1 - dy ) )
+        {
+          directions.push( ( dx, -dy ) );
+        }
+      }
+      else
+      {
+        // Sideways neighbours of a straight move, turned into forced diagonals by an obstacle
+        let ( sx, sy ) = ( dy, dx );
+        for side in [ 1, -1 ]
+        {
+          if self.is_blocked( ( cell.0 + sx * side, cell.1 + sy * side ) )
+          {
+            directions.push( ( dx + sx * side, dy + sy * side ) );
+          }
+        }
+      }
+    }
+    else if dx != 0
+    {
+      // Horizontal runs may turn vertical anywhere
+      directions.extend( [ ( 0, 1 ), ( 0, -1 ) ] );
+    }
+    else
+    {
+      for side in [ 1, -1 ]
+      {
+        // A vertical run turns where the wall beside it ends
+        if self.is_blocked( ( cell.0 + side, cell.1 - dy ) )
+        {
+          directions.push( ( side, 0 ) );
+        }
+      }
+    }
+    directions
+  }
+
+  /// Jumps from `cell` towards `direction` and returns the next jump point, if any.
+  fn jump( &self, cell : Cell, direction : Direction ) -> Option< Cell >
+  {
+    let ( dx, dy ) = direction;
+    let mut previous = cell;
+    loop
+    {
+      let next = ( previous.0 + dx, previous.1 + dy );
+      if self.is_blocked( next )
+      {
+        return None;
+      }
+      if next == self.goal || self.is_uneven( next ) || self.has_forced_neighbour( previous, next, direction )
+      {
+        return Some( next );
+      }
+
+      // Diagonal runs in 8-connected grids and horizontal runs in 4-connected ones
+      // branch into the perpendicular directions at every cell
+      let branches = if K::DIAGONAL && dx != 0 && dy != 0
+      {
+        [ ( dx, 0 ), ( 0, dy ) ]
+      }
+      else if !K::DIAGONAL && dx != 0
+      {
+        [ ( 0, 1 ), ( 0, -1 ) ]
+      }
+      else
+      {
+        previous = next;
+        continue;
+      };
+      if branches.iter().any( | &branch | self.jump( next, branch ).is_some() )
+      {
+        return Some( next );
+      }
+      previous = next;
+    }
+  }
+
+  /// Returns `true` if an obstacle next to the move from `previous` to `next` forces a turn at `next`.
+  fn has_forced_neighbour( &self, previous : Cell, next : Cell, ( dx, dy ) : Direction ) -> bool
+  {
+    if K::DIAGONAL
+    {
+      if dx != 0 && dy != 0
+      {
+        ( self.is_blocked( ( next.0 - dx, next.1 ) ) && !self.is_blocked( ( next.0 - dx, next.1 + dy ) ) )
+        || ( self.is_blocked( ( next.0, next.1 - dy ) ) && !self.is_blocked( ( next.0 + dx, next.1 - dy ) ) )
+      }
+      else
+      {
+        let ( sx, sy ) = ( dy, dx );
+        [ 1, -1 ].iter().any( | &side |
+        {
+          self.is_blocked( ( next.0 + sx * side, next.1 + sy * side ) )
+          && !self.is_blocked( ( next.0 + dx + sx * side, next.1 + dy + sy * side ) )
+        })
+      }
+    }
+    else if dy != 0
+    {
+      // A vertical run may turn once the wall beside it ends
+      [ 1, -1 ].iter().any( | &side |
+      {
+        self.is_blocked( ( previous.0 + side, previous.1 ) ) && !self.is_blocked( ( next.0 + side, next.1 ) )
+      })
+    }
+    else
+    {
+      false
+    }
+  }
+
+  /// Cost of walking the straight or diagonal segment from `from` to `to`.
+  fn segment_cost( &self, from : Cell, to : Cell ) -> u32
+  {
+    walk( from, to ).map( | cell | self.config.move_cost( &Coordinate::new( cell.0, cell.1 ) ) ).sum()
+  }
+
+  /// Expands the chain of jump points ending at `goal` into a full path.
+  fn unfold( &self, parents : &FxHashMap< Cell, Cell >, start : Cell, goal : Cell ) -> Vec< Coordinate< K > >
+  {
+    let mut jump_points = vec![ goal ];
+    while let Some( &parent ) = parents.get( jump_points.last().unwrap() )
+    {
+      jump_points.push( parent );
+    }
+    jump_points.reverse();
+    debug_assert_eq!( jump_points.first(), Some( &start ) );
+
+    let mut path = vec![ Coordinate::new( start.0, start.1 ) ];
+    for pair in jump_points.windows( 2 )
+    {
+      path.extend( walk( pair[ 0 ], pair[ 1 ] ).map( | cell | Coordinate::new( cell.0, cell.1 ) ) );
+    }
+    path
+  }
+}
+
+/// Cells of the straight or diagonal segment after `from` up to and including `to`.
+fn walk( from : Cell, to : Cell ) -> impl Iterator< Item = Cell >
+{
+  let ( dx, dy ) = ( ( to.0 - from.0 ).signum(), ( to.1 - from.1 ).signum() );
+  let steps = ( to.0 - from.0 ).abs().max( ( to.1 - from.1 ).abs() );
+  ( 1..=steps ).map( move | step | ( from.0 + dx * step, from.1 + dy * step ) )
+}
+
+#[ cfg( test ) ]
+mod tests
+{
+  use super::*;
+  use crate::pathfind::astar_advanced;
+  use crate::coordinates::Neighbors;
+
+  /// Deterministic generator for test maps.
+  struct Lcg( u64 );
+
+  impl Lcg
+  {
+    fn next( &mut self, bound : u32 ) -> u32
+    {
+      self.0 = self.0.wrapping_mul( 6364136223846793005 ).wrapping_add( 1442695040888963407 );
+      ( ( self.0 >> 33 ) % bound as u64 ) as u32
+    }
+  }
+
+  fn random_config< K >( rng : &mut Lcg, size : i32, with_terrain : bool ) -> PathfindingConfig< Coordinate< K > >
+  where
+    K : JumpConnectivity,
+  {
+    let mut config = PathfindingConfig::new();
+    for x in 0..size
+    {
+      for y in 0..size
+      {
+        let coord = Coordinate::new( x, y );
+        match rng.next( 10 )
+        {
+          0..=2 => config = config.with_obstacle( coord ),
+          3 if with_terrain => config = config.with_terrain_cost( coord, rng.next( 5 ) + 1 ),
+          _ => {}
+        }
+      }
+    }
+    // Keep searches finite on the unbounded grid
+    config.with_max_distance( size as u32 + 4 )
+  }
+
+  fn assert_matches_astar< K >( seed : u64, with_terrain : bool )
+  where
+    K : JumpConnectivity + std::fmt::Debug,
+    Coordinate< K > : Distance + Neighbors,
+  {
+    let mut rng = Lcg( seed );
+    for _ in 0..40
+    {
+      let size = 24;
+      let mut config = random_config::< K >( &mut rng, size, with_terrain );
+      let start = Coordinate::< K >::new( rng.next( size as u32 ) as i32, rng.next( size as u32 ) as i32 );
+      let goal = Coordinate::< K >::new( rng.next( size as u32 ) as i32, rng.next( size as u32 ) as i32 );
+      config.obstacles.remove( &start );
+      config.obstacles.remove( &goal );
+
+      let expected = astar_advanced( &start, &goal, &config );
+      let actual = jps( &start, &goal, &config );
+      assert_eq!( actual.as_ref().map( | ( _, cost ) | *cost ), expected.map( | ( _, cost ) | cost ), "{start:?} -> {goal:?}" );
+
+      if let Some( ( path, cost ) ) = actual
+      {
+        assert_eq!( path.first(), Some( &start ) );
+        assert_eq!( path.last(), Some( &goal ) );
+        let mut walked = 0;
+        for pair in path.windows( 2 )
+        {
+          assert!( pair[ 0 ].neighbors().contains( &pair[ 1 ] ), "{:?} -> {:?} is not a step", pair[ 0 ], pair[ 1 ] );
+          assert!( !config.is_blocked( &start, &pair[ 1 ] ) );
+          walked += config.move_cost( &pair[ 1 ] );
+        }
+        assert_eq!( walked, cost );
+      }
+    }
+  }
+
+  #[ test ]
+  fn test_eight_connected_matches_astar()
+  {
+    assert_matches_astar::< EightConnected >( 7, false );
+  }
+
+  #[ test ]
+  fn test_four_connected_matches_astar()
+  {
+    assert_matches_astar::< FourConnected >( 11, false );
+  }
+
+  #[ test ]
+  fn test_terrain_costs_match_astar()
+  {
+    assert_matches_astar::< EightConnected >( 13, true );
+    assert_matches_astar::< FourConnected >( 17, true );
+  }
+
+  #[ test ]
+  fn test_open_grid_and_trivial_cases()
+  {
+    let config = PathfindingConfig::< Coordinate< EightConnected > >::new();
+    let start = Coordinate::new( 0, 0 );
+    let goal = Coordinate::new( 50, 20 );
+    let ( path, cost ) = jps( &start, &goal, &config ).unwrap();
+    assert_eq!( cost, 50 );
+    assert_eq!( path.len(), 51 );
+
+    assert_eq!( jps( &start, &start, &config ), Some( ( vec![ start ], 0 ) ) );
+
+    let walled = config.with_obstacle( goal );
+    assert!( jps( &start, &goal, &walled ).is_none() );
+
+    let limited = PathfindingConfig::new().with_max_distance( 10 );
+    assert!( jps( &start, &goal, &limited ).is_none() );
+  }
+
+  #[ test ]
+  fn test_blocking_entities_are_avoided()
+  {
+    let config = PathfindingConfig::< Coordinate< FourConnected > >::new()
+    .with_blocking_entity( Coordinate::new( 1, 0 ), 1 )
+    .with_max_distance( 10 );
+    let start = Coordinate::new( 0, 0 );
+    let goal = Coordinate::new( 2, 0 );
+    let ( path, cost ) = jps( &start, &goal, &config ).unwrap();
+    assert_eq!( cost, 4 );
+    assert!( !path.contains( &Coordinate::new( 1, 0 ) ) );
+  }
+}
@@ -10,8 +10,9 @@
 //! - **Game State Serialization**: Complete game world persistence
 //! - **Configuration Management**: Settings and preferences persistence
 //! - **Checkpoint System**: Automatic and manual save points
-//! - **Version Management**: Backward compatibility for save files
-//! - **Compression Support**: Efficient storage of large game states
+//! - **Version Management**: Registered migration steps upgrade old saves on load
+//! - **Compression Support**: DEFLATE or LZ4 compression of large game states
+//! - **Integrity Checks**: Header with version and format, CRC-32 over the whole save
 //! - **Incremental Saves**: Delta-based serialization for performance
 //!
 //! # Supported Formats
@@ -54,6 +55,7 @@ use std::io::{Read, Write, BufReader, BufWriter};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH, Duration};
 use serde::{Serialize, Deserialize, Serializer, Deserializer};
+use serde_json::Value;
 
 /// Version information for save file compatibility.
 #[ derive( Debug, Clone, Serialize, Deserialize ) ]
@@ -93,6 +95,17 @@ impl SaveVersion
   pub fn is_compatible_with(&self, other: &SaveVersion) -> bool {
     self.major == other.major && self.minor >= other.minor
   }
+
+  /// Version number without the timestamp, for ordering and comparison.
+  pub fn number(&self) -> (u32, u32, u32) {
+    (self.major, self.minor, self.patch)
+  }
+}
+
+impl std::fmt::Display for SaveVersion {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+  }
 }
 
 impl Default for SaveVersion {
@@ -371,7 +384,7 @@ pub struct GameStatistics {
 }
 
 /// Serialization format options.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SerializationFormat {
   /// JSON format - human readable
   Json,
@@ -381,10 +394,227 @@ pub enum SerializationFormat {
   Ron,
 }
 
+impl SerializationFormat {
+  fn to_id(self) -> u8 {
+    match self {
+      SerializationFormat::Json => 0,
+      SerializationFormat::Binary => 1,
+      SerializationFormat::Ron => 2,
+    }
+  }
+
+  fn from_id(id: u8) -> Result<Self, SerializationError> {
+    match id {
+      0 => Ok(SerializationFormat::Json),
+      1 => Ok(SerializationFormat::Binary),
+      2 => Ok(SerializationFormat::Ron),
+      _ => Err(SerializationError::UnknownFormat(id)),
+    }
+  }
+}
+
+/// Compression applied to the serialized payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompressionFormat {
+  /// Payload is stored as is
+  None,
+  /// DEFLATE - better ratio, slower
+  Deflate,
+  /// LZ4 block format - very fast, lower ratio
+  Lz4,
+}
+
+impl CompressionFormat {
+  fn to_id(self) -> u8 {
+    match self {
+      CompressionFormat::None => 0,
+      CompressionFormat::Deflate => 1,
+      CompressionFormat::Lz4 => 2,
+    }
+  }
+
+  fn from_id(id: u8) -> Result<Self, SerializationError> {
+    match id {
+      0 => Ok(CompressionFormat::None),
+      1 => Ok(CompressionFormat::Deflate),
+      2 => Ok(CompressionFormat::Lz4),
+      _ => Err(SerializationError::InvalidCompressionFormat),
+    }
+  }
+
+  fn compress(self, data: Vec<u8>) -> Vec<u8> {
+    match self {
+      CompressionFormat::None => data,
+      CompressionFormat::Deflate => miniz_oxide::deflate::compress_to_vec(&data, 6),
+      CompressionFormat::Lz4 => lz4_flex::block::compress(&data),
+    }
+  }
+
+  /// Largest size `payload` can expand to, so a forged header can't request a huge allocation.
+  fn max_uncompressed_size(self, payload: &[u8]) -> usize {
+    match self {
+      CompressionFormat::None => payload.len(),
+      CompressionFormat::Deflate => payload.len().saturating_mul(1032),
+      CompressionFormat::Lz4 => payload.len().saturating_mul(255),
+    }
+  }
+
+  fn decompress(self, payload: &[u8], size: usize) -> Result<Vec<u8>, SerializationError> {
+    if size > self.max_uncompressed_size(payload) {
+      return Err(SerializationError::CorruptedData);
+    }
+
+    let data = match self {
+      CompressionFormat::None => payload.to_vec(),
+      CompressionFormat::Deflate => miniz_oxide::inflate::decompress_to_vec_with_limit(payload, size)
+        .map_err(|e| SerializationError::Decompression(format!("{:?}", e.status)))?,
+      CompressionFormat::Lz4 => lz4_flex::block::decompress(payload, size)
+        .map_err(|e| SerializationError::Decompression(e.to_string()))?,
+    };
+
+    if data.len() != size {
+      return Err(SerializationError::CorruptedData);
+    }
+    Ok(data)
+  }
+}
+
+/// Header written in front of every serialized game state.
+///
+/// Layout ( little endian, 48 bytes ): magic `TTSV`, header version, format id,
+/// compression id, reserved byte, save version major, minor and patch as `u32`,
+/// save version timestamp, uncompressed size and payload size as `u64`, and a
+/// CRC-32 over the preceding header bytes and the payload.
+#[derive(Debug, Clone)]
+pub struct SaveHeader {
+  /// Version of the saved data
+  pub version: SaveVersion,
+  /// Format of the payload
+  pub format: SerializationFormat,
+  /// Compression of the payload
+  pub compression: CompressionFormat,
+  /// Size of the payload after decompression
+  pub uncompressed_size: u64,
+  /// Size of the payload as stored
+  pub payload_size: u64,
+  /// CRC-32 of the header and the payload
+  pub checksum: u32,
+}
+
+impl SaveHeader {
+  /// Magic bytes every save starts with.
+  pub const MAGIC: [u8; 4] = *b"TTSV";
+  /// Version of the header layout.
+  pub const LAYOUT_VERSION: u8 = 1;
+  /// Size of the encoded header in bytes.
+  pub const SIZE: usize = 48;
+
+  /// Encodes the header, without the trailing checksum.
+  fn encode_unchecked(&self) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(Self::SIZE);
+    bytes.extend_from_slice(&Self::MAGIC);
+    bytes.extend_from_slice(&[Self::LAYOUT_VERSION, self.format.to_id(), self.compression.to_id(), 0]);
+    bytes.extend_from_slice(&self.version.major.to_le_bytes());
+    bytes.extend_from_slice(&self.version.minor.to_le_bytes());
+    bytes.extend_from_slice(&self.version.patch.to_le_bytes());
+    bytes.extend_from_slice(&self.version.timestamp.to_le_bytes());
+    bytes.extend_from_slice(&self.uncompressed_size.to_le_bytes());
+    bytes.extend_from_slice(&self.payload_size.to_le_bytes());
+    bytes
+  }
+
+  /// Checks whether `data` can't start with a header, as saves written before headers existed.
+  pub fn is_headerless(data: &[u8]) -> bool {
+    !data.is_empty() && !Self::MAGIC.starts_with(&data[..data.len().min(4)])
+  }
+
+  /// Reads and validates the header of `data`: magic, layout, sizes and checksum.
+  pub fn read(data: &[u8]) -> Result<Self, SerializationError> {
+    if data.len() < Self::SIZE {
+      if Self::is_headerless(data) {
+        return Err(SerializationError::InvalidMagic);
+      }
+      return Err(SerializationError::Truncated { expected: Self::SIZE, found: data.len() });
+    }
+    if data[0..4] != Self::MAGIC {
+      return Err(SerializationError::InvalidMagic);
+    }
+    if data[4] != Self::LAYOUT_VERSION {
+      return Err(SerializationError::UnsupportedHeader(data[4]));
+    }
+
+    let u32_at = |offset: usize| u32::from_le_bytes([data[offset], data[offset + 1], data[offset + 2], data[offset + 3]]);
+    let u64_at = |offset: usize| (u64::from(u32_at(offset + 4)) << 32) | u64::from(u32_at(offset));
+
+    let header = Self {
+      version: SaveVersion {
+        major: u32_at(8),
+        minor: u32_at(12),
+        patch: u32_at(16),
+        timestamp: u64_at(20),
+      },
+      format: SerializationFormat::from_id(data[5])?,
+      compression: CompressionFormat::from_id(data[6])?,
+      uncompressed_size: u64_at(28),
+      payload_size: u64_at(36),
+      checksum: u32_at(44),
+    };
+
+    let expected = usize::try_from(header.payload_size)
+      .ok()
+      .and_then(|size| size.checked_add(Self::SIZE))
+      .unwrap_or(usize::MAX);
+    if data.len() < expected {
+      return Err(SerializationError::Truncated { expected, found: data.len() });
+    }
+    if data.len() > expected {
+      return Err(SerializationError::TrailingData(data.len() - expected));
+    }
+
+    let found = checksum(&data[..Self::SIZE - 4], &data[Self::SIZE..]);
+    if found != header.checksum {
+      return Err(SerializationError::ChecksumMismatch { expected: header.checksum, found });
+    }
+
+    Ok(header)
+  }
+}
+
+/// CRC-32 of the header bytes followed by the payload.
+fn checksum(header: &[u8], payload: &[u8]) -> u32 {
+  let mut hasher = crc32fast::Hasher::new();
+  hasher.update(header);
+  hasher.update(payload);
+  hasher.finalize()
+}
+
+/// Outcome of [`SaveManager::verify_save`].
+#[derive(Debug, Clone)]
+pub enum SaveVerification {
+  /// Header, sizes and checksum of the save are valid
+  Verified(SaveHeader),
+  /// Save was written before [`SaveHeader`] existed and has no checksum, so there is nothing to verify
+  Headerless,
+}
+
+impl SaveVerification {
+  /// Returns the verified header, `None` for headerless saves.
+  pub fn header(&self) -> Option<&SaveHeader> {
+    match self {
+      SaveVerification::Verified(header) => Some(header),
+      SaveVerification::Headerless => None,
+    }
+  }
+}
+
 /// Game state serializer with compression and format options.
+///
+/// Output always starts with a [`SaveHeader`], so deserialization picks format and
+/// compression from the data itself and detects truncated or tampered input.
+/// Saves written before headers existed are still read, in the configured format.
 pub struct GameStateSerializer {
   format: SerializationFormat,
-  compress: bool,
+  compression: CompressionFormat,
   version: SaveVersion,
 }
 
@@ -393,7 +623,7 @@ impl GameStateSerializer {
   pub fn new() -> Self {
     Self {
       format: SerializationFormat::Json,
-      compress: false,
+      compression: CompressionFormat::None,
       version: SaveVersion::current(),
     }
   }
@@ -404,19 +634,35 @@ impl GameStateSerializer {
     self
   }
 
-  /// Enables or disables compression.
+  /// Enables or disables compression, [`CompressionFormat::Deflate`] is used when enabled.
   pub fn with_compression(mut self, compress: bool) -> Self {
-    self.compress = compress;
+    self.compression = if compress { CompressionFormat::Deflate } else { CompressionFormat::None };
     self
   }
 
+  /// Sets the compression format.
+  pub fn with_compression_format(mut self, compression: CompressionFormat) -> Self {
+    self.compression = compression;
+    self
+  }
+
+  /// Returns the compression format used for serialization.
+  pub fn compression(&self) -> CompressionFormat {
+    self.compression
+  }
+
+  /// Returns the version written to and expected from saves.
+  pub fn version(&self) -> &SaveVersion {
+    &self.version
+  }
+
   /// Sets a custom version.
   pub fn with_version(mut self, version: SaveVersion) -> Self {
     self.version = version;
     self
   }
 
-  /// Serializes a game state to bytes.
+  /// Serializes a game state to bytes, prefixed with a [`SaveHeader`].
   pub fn serialize_game_state(&self, state: &SerializableGameState) -> Result<Vec<u8>, SerializationError> {
     let data = match self.format {
       SerializationFormat::Json => serde_json::to_vec(state)?,
@@ -424,22 +670,92 @@ impl GameStateSerializer {
       SerializationFormat::Ron => ron::ser::to_string(state)?.into_bytes(),
     };
 
-    if self.compress {
-      Ok(self.compress_data(data))
-    } else {
-      Ok(data)
-    }
+    Ok(self.frame(data))
+  }
+
+  /// Compresses encoded data and puts it behind a [`SaveHeader`].
+  fn frame(&self, data: Vec<u8>) -> Vec<u8> {
+    let uncompressed_size = data.len() as u64;
+    let payload = self.compression.compress(data);
+    let mut header = SaveHeader {
+      version: self.version.clone(),
+      format: self.format,
+      compression: self.compression,
+      uncompressed_size,
+      payload_size: payload.len() as u64,
+      checksum: 0,
+    };
+
+    let mut bytes = header.encode_unchecked();
+    header.checksum = checksum(&bytes, &payload);
+    bytes.extend_from_slice(&header.checksum.to_le_bytes());
+    bytes.extend(payload);
+    bytes
   }
 
   /// Deserializes a game state from bytes.
+  ///
+  /// Format and compression are taken from the header. Data is rejected if it is
+  /// truncated, fails the checksum or has a version this serializer can't read.
+  /// Headerless data is read as a save written before headers existed, in the configured
+  /// format, with its version taken from the save metadata.
   pub fn deserialize_game_state(&self, data: &[u8]) -> Result<SerializableGameState, SerializationError> {
-    let data = if self.compress {
-      self.decompress_data(data)?
-    } else {
-      data.to_vec()
-    };
+    if SaveHeader::is_headerless(data) {
+      let state: SerializableGameState = Self::decode(self.format, Self::legacy_payload(data)?)
+        .map_err(|_| SerializationError::InvalidMagic)?;
+      if !self.version.is_compatible_with(&state.metadata.version) {
+        return Err(SerializationError::IncompatibleVersion { found: state.metadata.version, expected: self.version.clone() });
+      }
+      return Ok(state);
+    }
+
+    let (header, data) = Self::payload(data)?;
+    if !self.version.is_compatible_with(&header.version) {
+      return Err(SerializationError::IncompatibleVersion { found: header.version, expected: self.version.clone() });
+    }
+
+    Self::decode(header.format, data)
+  }
+
+  /// Decodes a save of any version into an intermediate value tree, used by [`SaveMigrator`].
+  ///
+  /// Binary saves are not self-describing and can't be decoded this way. For headerless saves
+  /// the returned header is made up from the configured format and the save metadata.
+  pub fn decode_value(&self, data: &[u8]) -> Result<(SaveHeader, Value), SerializationError> {
+    if SaveHeader::is_headerless(data) {
+      let data = Self::legacy_payload(data)?;
+      if self.format == SerializationFormat::Binary {
+        return Err(SerializationError::UnsupportedMigrationFormat(self.format));
+      }
+      let size = data.len() as u64;
+      let value: Value = Self::decode(self.format, data).map_err(|_| SerializationError::InvalidMagic)?;
+      let version = value
+        .pointer("/metadata/version")
+        .and_then(|version| SaveVersion::deserialize(version).ok())
+        .ok_or(SerializationError::InvalidMagic)?;
+      let header = SaveHeader {
+        version,
+        format: self.format,
+        compression: CompressionFormat::None,
+        uncompressed_size: size,
+        payload_size: size,
+        checksum: 0,
+      };
+      return Ok((header, value));
+    }
 
-    let state = match self.format {
+    let (header, data) = Self::payload(data)?;
+    if header.format == SerializationFormat::Binary {
+      return Err(SerializationError::UnsupportedMigrationFormat(header.format));
+    }
+    let value = Self::decode(header.format, data)?;
+
+    Ok((header, value))
+  }
+
+  /// Decodes a payload in `format`.
+  fn decode<T: for<'de> Deserialize<'de>>(format: SerializationFormat, data: Vec<u8>) -> Result<T, SerializationError> {
+    let value = match format {
       SerializationFormat::Json => serde_json::from_slice(&data)?,
       SerializationFormat::Binary => bincode::deserialize(&data)?,
       SerializationFormat::Ron => {
@@ -451,7 +767,30 @@ impl GameStateSerializer {
       }
     };
 
-    Ok(state)
+    Ok(value)
+  }
+
+  /// Validates the header and returns the decompressed payload.
+  fn payload(data: &[u8]) -> Result<(SaveHeader, Vec<u8>), SerializationError> {
+    let header = SaveHeader::read(data)?;
+    let size = usize::try_from(header.uncompressed_size).map_err(|_| SerializationError::CorruptedData)?;
+    let payload = header.compression.decompress(&data[SaveHeader::SIZE..], size)?;
+    Ok((header, payload))
+  }
+
+  /// Returns the payload of a save written before headers existed, which is either raw
+  /// or behind the `0xC0 'M' 'P'` marker and its `u32` size.
+  fn legacy_payload(data: &[u8]) -> Result<Vec<u8>, SerializationError> {
+    const MARKER: [u8; 3] = [0xC0, 0x4D, 0x50];
+
+    if data.len() < 7 || data[0..3] != MARKER {
+      return Ok(data.to_vec());
+    }
+    let size = u32::from_le_bytes([data[3], data[4], data[5], data[6]]) as usize;
+    if data.len() - 7 != size {
+      return Err(SerializationError::CorruptedData);
+    }
+    Ok(data[7..].to_vec())
   }
 
   /// Creates a basic game state for testing.
@@ -464,35 +803,224 @@ impl GameStateSerializer {
       custom_data: HashMap::new(),
     }
   }
+}
+
+impl Default for GameStateSerializer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+/// Migration step function, edits a save represented as a value tree in place.
+pub type MigrationFn = Box<dyn Fn(&mut Value) -> Result<(), String> + Send + Sync>;
+
+/// Registered migration step from one save version to the next one.
+pub struct Migration {
+  from: SaveVersion,
+  to: SaveVersion,
+  description: String,
+  apply: MigrationFn,
+}
+
+impl Migration {
+  /// Version the step reads.
+  pub fn from(&self) -> &SaveVersion {
+    &self.from
+  }
+
+  /// Version the step produces.
+  pub fn to(&self) -> &SaveVersion {
+    &self.to
+  }
+
+  /// Human-readable description of the step.
+  pub fn description(&self) -> &str {
+    &self.description
+  }
+}
 
-  // Private compression methods (stubbed for now - would use flate2 or similar)
-  fn compress_data(&self, data: Vec<u8>) -> Vec<u8> {
-    // In a real implementation, this would use flate2 or similar
-    // For now, just return the data unchanged with a marker
-    let mut compressed = vec![0xC0, 0x4D, 0x50]; // "CMP" marker
-    compressed.extend_from_slice(&(data.len() as u32).to_le_bytes());
-    compressed.extend(data);
-    compressed
+impl std::fmt::Debug for Migration {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Migration")
+      .field("from", &self.from)
+      .field("to", &self.to)
+      .field("description", &self.description)
+      .finish()
   }
+}
 
-  fn decompress_data(&self, data: &[u8]) -> Result<Vec<u8>, SerializationError> {
-    // Check for compression marker
-    if data.len() < 7 || data[0..3] != [0xC0, 0x4D, 0x50] {
-      return Err(SerializationError::InvalidCompressionFormat);
+/// Single difference in a value tree made by a migration step.
+///
+/// Paths are JSON pointers, e.g. `/progress/statistics/deaths`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValueChange {
+  /// Value appeared at path
+  Added { path: String, value: Value },
+  /// Value disappeared from path
+  Removed { path: String, value: Value },
+  /// Value at path was replaced
+  Changed { path: String, before: Value, after: Value },
+}
+
+impl ValueChange {
+  /// Path of the changed value.
+  pub fn path(&self) -> &str {
+    match self {
+      ValueChange::Added { path, .. } | ValueChange::Removed { path, .. } | ValueChange::Changed { path, .. } => path,
     }
+  }
+}
 
-    let original_size = u32::from_le_bytes([data[3], data[4], data[5], data[6]]) as usize;
-    if data.len() != original_size + 7 {
-      return Err(SerializationError::CorruptedData);
+/// Changes made by one migration step.
+#[derive(Debug, Clone)]
+pub struct MigrationStepReport {
+  /// Version the step read
+  pub from: SaveVersion,
+  /// Version the step produced
+  pub to: SaveVersion,
+  /// Description of the step
+  pub description: String,
+  /// Differences between the value tree before and after the step
+  pub changes: Vec<ValueChange>,
+}
+
+/// Report of all migration steps applied to a save.
+#[derive(Debug, Clone, Default)]
+pub struct MigrationReport {
+  /// Applied steps in order
+  pub steps: Vec<MigrationStepReport>,
+}
+
+impl MigrationReport {
+  /// Returns true if no migration step was needed.
+  pub fn is_empty(&self) -> bool {
+    self.steps.is_empty()
+  }
+}
+
+/// Registry of migration steps that upgrade old saves to the current version.
+///
+/// Every step converts a save from one version to a newer one. Steps are chained
+/// starting from the version of the save, so a 1.0.0 save is carried through
+/// 1.1.0 and 1.2.0 when steps for both are registered.
+#[derive(Debug, Default)]
+pub struct SaveMigrator {
+  migrations: Vec<Migration>,
+}
+
+impl SaveMigrator {
+  /// Creates a migrator without steps.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Adds a migration step, see [`Self::register`].
+  pub fn with_migration<F>(mut self, from: SaveVersion, to: SaveVersion, description: &str, apply: F) -> Self
+  where
+    F: Fn(&mut Value) -> Result<(), String> + Send + Sync + 'static,
+  {
+    self.register(from, to, description, apply);
+    self
+  }
+
+  /// Registers a migration step from `from` to `to`.
+  /// A step registered earlier for the same `from` version is replaced.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `to` is not newer than `from`.
+  pub fn register<F>(&mut self, from: SaveVersion, to: SaveVersion, description: &str, apply: F)
+  where
+    F: Fn(&mut Value) -> Result<(), String> + Send + Sync + 'static,
+  {
+    assert!(to.number() > from.number(), "Migration must go to a newer version: {} -> {}", from, to);
+    self.migrations.retain(|migration| migration.from.number() != from.number());
+    self.migrations.push(Migration { from, to, description: description.to_string(), apply: Box::new(apply) });
+  }
+
+  /// Returns registered steps.
+  pub fn migrations(&self) -> &[Migration] {
+    &self.migrations
+  }
+
+  /// Returns steps that carry a save from version `from` to a version readable as `to`.
+  pub fn plan(&self, from: &SaveVersion, to: &SaveVersion) -> Result<Vec<&Migration>, SerializationError> {
+    let mut steps = Vec::new();
+    let mut current = from.clone();
+    while let Some(step) = self.migrations.iter().find(|migration| {
+      migration.from.number() == current.number() && migration.to.number() <= to.number()
+    }) {
+      steps.push(step);
+      current = step.to.clone();
     }
 
-    Ok(data[7..].to_vec())
+    if !to.is_compatible_with(&current) {
+      if steps.is_empty() {
+        return Err(SerializationError::IncompatibleVersion { found: current, expected: to.clone() });
+      }
+      return Err(SerializationError::MissingMigration { from: current, to: to.clone() });
+    }
+    Ok(steps)
+  }
+
+  /// Applies steps from [`Self::plan`] to `value` and reports what each step changed.
+  pub fn migrate(&self, value: &mut Value, from: &SaveVersion, to: &SaveVersion) -> Result<MigrationReport, SerializationError> {
+    let mut report = MigrationReport::default();
+    for step in self.plan(from, to)? {
+      let before = value.clone();
+      (step.apply)(value).map_err(|message| SerializationError::Migration {
+        from: step.from.clone(),
+        to: step.to.clone(),
+        message,
+      })?;
+
+      let mut changes = Vec::new();
+      value_diff(String::new(), &before, value, &mut changes);
+      report.steps.push(MigrationStepReport {
+        from: step.from.clone(),
+        to: step.to.clone(),
+        description: step.description.clone(),
+        changes,
+      });
+    }
+    Ok(report)
   }
 }
 
-impl Default for GameStateSerializer {
-  fn default() -> Self {
-    Self::new()
+/// Collects differences between `before` and `after` at `path`.
+fn value_diff(path: String, before: &Value, after: &Value, changes: &mut Vec<ValueChange>) {
+  if before == after {
+    return;
+  }
+
+  match (before, after) {
+    (Value::Object(before), Value::Object(after)) => {
+      for (key, old) in before {
+        let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+        match after.get(key) {
+          Some(new) => value_diff(child, old, new, changes),
+          None => changes.push(ValueChange::Removed { path: child, value: old.clone() }),
+        }
+      }
+      for (key, new) in after {
+        if !before.contains_key(key) {
+          let child = format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+          changes.push(ValueChange::Added { path: child, value: new.clone() });
+        }
+      }
+    }
+    (Value::Array(before), Value::Array(after)) => {
+      for index in 0..before.len().max(after.len()) {
+        let child = format!("{}/{}", path, index);
+        match (before.get(index), after.get(index)) {
+          (Some(old), Some(new)) => value_diff(child, old, new, changes),
+          (Some(old), None) => changes.push(ValueChange::Removed { path: child, value: old.clone() }),
+          (None, Some(new)) => changes.push(ValueChange::Added { path: child, value: new.clone() }),
+          (None, None) => {}
+        }
+      }
+    }
+    _ => changes.push(ValueChange::Changed { path, before: before.clone(), after: after.clone() }),
   }
 }
 
@@ -500,6 +1028,7 @@ impl Default for GameStateSerializer {
 pub struct SaveManager {
   saves_directory: PathBuf,
   serializer: GameStateSerializer,
+  migrator: SaveMigrator,
 }
 
 impl SaveManager {
@@ -508,6 +1037,7 @@ impl SaveManager {
     Self {
       saves_directory: saves_directory.as_ref().to_path_buf(),
       serializer: GameStateSerializer::new(),
+      migrator: SaveMigrator::new(),
     }
   }
 
@@ -517,6 +1047,12 @@ impl SaveManager {
     self
   }
 
+  /// Sets migration steps applied to old saves on load.
+  pub fn with_migrator(mut self, migrator: SaveMigrator) -> Self {
+    self.migrator = migrator;
+    self
+  }
+
   /// Saves a game state to a file.
   pub fn save_game_state(&self, save_name: &str, state: &SerializableGameState) -> Result<(), SerializationError> {
     create_dir_all(&self.saves_directory)?;
@@ -530,6 +1066,7 @@ impl SaveManager {
     // Create updated metadata with actual size
     let mut metadata = state.metadata.clone();
     metadata.size_bytes = serialized_data.len() as u64;
+    metadata.compressed = self.serializer.compression() != CompressionFormat::None;
 
     // Write save file
     let mut save_file = BufWriter::new(File::create(save_path)?);
@@ -546,7 +1083,49 @@ impl SaveManager {
   }
 
   /// Loads a game state from a file.
+  ///
+  /// Saves of older versions are upgraded with the registered migration steps.
   pub fn load_game_state(&self, save_name: &str) -> Result<SerializableGameState, SerializationError> {
+    let data = self.read_save(save_name)?;
+    let version = self.save_version(&data)?;
+
+    if self.migrator.plan(&version, self.serializer.version())?.is_empty() {
+      self.serializer.deserialize_game_state(&data)
+    } else {
+      self.migrate_data(&data).map(|(state, _)| state)
+    }
+  }
+
+  /// Runs migration steps on a save without writing anything and reports what each step changed.
+  /// The report is empty if the save is already of a readable version.
+  pub fn dry_run_migration(&self, save_name: &str) -> Result<MigrationReport, SerializationError> {
+    let data = self.read_save(save_name)?;
+    let version = self.save_version(&data)?;
+
+    if self.migrator.plan(&version, self.serializer.version())?.is_empty() {
+      return Ok(MigrationReport::default());
+    }
+    self.migrate_data(&data).map(|(_, report)| report)
+  }
+
+  /// Version a save was written with, from its header or, for headerless saves, from their metadata.
+  fn save_version(&self, data: &[u8]) -> Result<SaveVersion, SerializationError> {
+    if !SaveHeader::is_headerless(data) {
+      return SaveHeader::read(data).map(|header| header.version);
+    }
+
+    match self.serializer.decode_value(data) {
+      Ok((header, _)) => Ok(header.version),
+      // Binary saves can't be decoded into a value tree, but they can't be migrated either
+      Err(SerializationError::UnsupportedMigrationFormat(_)) => {
+        self.serializer.deserialize_game_state(data).map(|state| state.metadata.version)
+      }
+      Err(e) => Err(e),
+    }
+  }
+
+  /// Reads the raw bytes of a save file.
+  fn read_save(&self, save_name: &str) -> Result<Vec<u8>, SerializationError> {
     let save_path = self.saves_directory.join(format!("{}.save", save_name));
     
     if !save_path.exists() {
@@ -556,8 +1135,37 @@ impl SaveManager {
     let mut save_file = BufReader::new(File::open(save_path)?);
     let mut data = Vec::new();
     save_file.read_to_end(&mut data)?;
+    Ok(data)
+  }
 
-    self.serializer.deserialize_game_state(&data)
+  /// Decodes a save into a value tree, migrates it and checks the result is a valid game state.
+  fn migrate_data(&self, data: &[u8]) -> Result<(SerializableGameState, MigrationReport), SerializationError> {
+    let target = self.serializer.version();
+    let (header, mut value) = self.serializer.decode_value(data)?;
+    let report = self.migrator.migrate(&mut value, &header.version, target)?;
+
+    let mut state: SerializableGameState = serde_json::from_value(value)?;
+    state.metadata.version = target.clone();
+    Ok((state, report))
+  }
+
+  /// Checks a save file for truncation and tampering without deserializing it.
+  ///
+  /// Saves written before [`SaveHeader`] existed carry no sizes or checksum. They are
+  /// reported as [`SaveVerification::Headerless`] rather than as damaged, and can still
+  /// be loaded with [`Self::load_game_state`].
+  pub fn verify_save(&self, save_name: &str) -> Result<SaveVerification, SerializationError> {
+    let save_path = self.saves_directory.join(format!("{}.save", save_name));
+
+    if !save_path.exists() {
+      return Err(SerializationError::SaveNotFound(save_name.to_string()));
+    }
+
+    let data = std::fs::read(save_path)?;
+    if SaveHeader::is_headerless(&data) {
+      return Ok(SaveVerification::Headerless);
+    }
+    SaveHeader::read(&data).map(SaveVerification::Verified)
   }
 
   /// Loads save metadata without loading the full save.
@@ -707,6 +1315,26 @@ pub enum SerializationError {
   InvalidCompressionFormat,
   /// Corrupted save data
   CorruptedData,
+  /// Data doesn't start with the save magic bytes
+  InvalidMagic,
+  /// Header layout version is not supported
+  UnsupportedHeader(u8),
+  /// Unknown serialization format id in the header
+  UnknownFormat(u8),
+  /// Data is shorter than the header says
+  Truncated { expected: usize, found: usize },
+  /// Data has extra bytes after the payload
+  TrailingData(usize),
+  /// Checksum of header and payload doesn't match the stored one
+  ChecksumMismatch { expected: u32, found: u32 },
+  /// Payload couldn't be decompressed
+  Decompression(String),
+  /// No migration step continues the chain from this version
+  MissingMigration { from: SaveVersion, to: SaveVersion },
+  /// Migration step failed
+  Migration { from: SaveVersion, to: SaveVersion, message: String },
+  /// Saves of this format can't be decoded into a value tree for migration
+  UnsupportedMigrationFormat(SerializationFormat),
   /// Version incompatibility
   IncompatibleVersion { found: SaveVersion, expected: SaveVersion },
 }
@@ -723,6 +1351,26 @@ impl std::fmt::Display for SerializationError {
       SerializationError::MetadataNotFound(name) => write!(f, "Metadata for save '{}' not found", name),
       SerializationError::InvalidCompressionFormat => write!(f, "Invalid compression format"),
       SerializationError::CorruptedData => write!(f, "Save data is corrupted"),
+      SerializationError::InvalidMagic => write!(f, "Data is not a save file"),
+      SerializationError::UnsupportedHeader(version) => write!(f, "Unsupported save header version {}", version),
+      SerializationError::UnknownFormat(id) => write!(f, "Unknown serialization format id {}", id),
+      SerializationError::Truncated { expected, found } => {
+        write!(f, "Save data is truncated: expected {} bytes, found {}", expected, found)
+      }
+      SerializationError::TrailingData(extra) => write!(f, "Save data has {} unexpected trailing bytes", extra),
+      SerializationError::ChecksumMismatch { expected, found } => {
+        write!(f, "Save checksum mismatch: expected {:08x}, found {:08x}", expected, found)
+      }
+      SerializationError::Decompression(message) => write!(f, "Decompression error: {}", message),
+      SerializationError::MissingMigration { from, to } => {
+        write!(f, "No migration from save version {} towards {}", from, to)
+      }
+      SerializationError::Migration { from, to, message } => {
+        write!(f, "Migration from {} to {} failed: {}", from, to, message)
+      }
+      SerializationError::UnsupportedMigrationFormat(format) => {
+        write!(f, "Saves in {:?} format can't be migrated", format)
+      }
       SerializationError::IncompatibleVersion { found, expected } => {
         write!(f, "Incompatible save version: found {}.{}.{}, expected {}.{}.{}",
           found.major, found.minor, found.patch,
@@ -832,6 +1480,300 @@ mod tests {
     assert_eq!(game_state.metadata.description, decompressed.metadata.description);
   }
 
+  #[test]
+  fn test_compression_formats() {
+    let game_state = GameStateSerializer::create_basic_game_state("Formats Test".to_string());
+    let plain = GameStateSerializer::new().serialize_game_state(&game_state).unwrap();
+
+    for compression in [CompressionFormat::Deflate, CompressionFormat::Lz4] {
+      for format in [SerializationFormat::Json, SerializationFormat::Binary, SerializationFormat::Ron] {
+        let serializer = GameStateSerializer::new()
+          .with_format(format)
+          .with_compression_format(compression);
+
+        let data = serializer.serialize_game_state(&game_state).unwrap();
+        let header = SaveHeader::read(&data).unwrap();
+        assert_eq!(header.format, format);
+        assert_eq!(header.compression, compression);
+
+        // Any serializer reads the format and compression from the header
+        let restored = GameStateSerializer::new().deserialize_game_state(&data).unwrap();
+        assert_eq!(restored.world_data, game_state.world_data);
+      }
+
+      let compressed = GameStateSerializer::new()
+        .with_compression_format(compression)
+        .serialize_game_state(&game_state)
+        .unwrap();
+      assert!(compressed.len() < plain.len());
+    }
+  }
+
+  #[test]
+  fn test_rejects_damaged_data() {
+    let serializer = GameStateSerializer::new().with_compression(true);
+    let game_state = GameStateSerializer::create_basic_game_state("Damage Test".to_string());
+    let data = serializer.serialize_game_state(&game_state).unwrap();
+
+    let result = serializer.deserialize_game_state(&data[..data.len() - 1]);
+    assert!(matches!(result, Err(SerializationError::Truncated { found, .. }) if found == data.len() - 1));
+
+    let result = serializer.deserialize_game_state(&data[..10]);
+    assert!(matches!(result, Err(SerializationError::Truncated { expected: SaveHeader::SIZE, found: 10 })));
+
+    let mut tampered = data.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 0xFF;
+    let result = serializer.deserialize_game_state(&tampered);
+    assert!(matches!(result, Err(SerializationError::ChecksumMismatch { .. })));
+
+    let mut tampered = data.clone();
+    tampered[8] ^= 0x01; // Save version major
+    let result = serializer.deserialize_game_state(&tampered);
+    assert!(matches!(result, Err(SerializationError::ChecksumMismatch { .. })));
+
+    let mut extended = data.clone();
+    extended.push(0);
+    let result = serializer.deserialize_game_state(&extended);
+    assert!(matches!(result, Err(SerializationError::TrailingData(1))));
+
+    let result = serializer.deserialize_game_state(b"{\"metadata\": {}}");
+    assert!(matches!(result, Err(SerializationError::InvalidMagic)));
+  }
+
+  #[test]
+  fn test_rejects_forged_uncompressed_size() {
+    let serializer = GameStateSerializer::new();
+    let payload = lz4_flex::block::compress(&[0u8; 64]);
+    let mut header = SaveHeader {
+      version: serializer.version().clone(),
+      format: SerializationFormat::Json,
+      compression: CompressionFormat::Lz4,
+      uncompressed_size: u64::MAX / 2,
+      payload_size: payload.len() as u64,
+      checksum: 0,
+    };
+    let mut data = header.encode_unchecked();
+    header.checksum = checksum(&data, &payload);
+    data.extend_from_slice(&header.checksum.to_le_bytes());
+    data.extend(payload);
+
+    let result = serializer.deserialize_game_state(&data);
+    assert!(matches!(result, Err(SerializationError::CorruptedData)));
+  }
+
+  #[test]
+  fn test_reads_headerless_saves() {
+    let game_state = GameStateSerializer::create_basic_game_state("Legacy Test".to_string());
+    let raw = serde_json::to_vec(&game_state).unwrap();
+    let serializer = GameStateSerializer::new();
+
+    let state = serializer.deserialize_game_state(&raw).unwrap();
+    assert_eq!(state.metadata.description, "Legacy Test");
+
+    // compressed saves were stored behind a marker and their size
+    let mut marked = vec![0xC0, 0x4D, 0x50];
+    marked.extend_from_slice(&(raw.len() as u32).to_le_bytes());
+    marked.extend_from_slice(&raw);
+    let state = serializer.deserialize_game_state(&marked).unwrap();
+    assert_eq!(state.metadata.description, "Legacy Test");
+
+    let (header, value) = serializer.decode_value(&marked).unwrap();
+    assert_eq!(header.version.number(), game_state.metadata.version.number());
+    assert_eq!(value["metadata"]["description"], "Legacy Test");
+
+    let result = serializer.deserialize_game_state(&marked[..marked.len() - 1]);
+    assert!(matches!(result, Err(SerializationError::CorruptedData)));
+  }
+
+  #[test]
+  fn test_rejects_incompatible_version() {
+    let game_state = GameStateSerializer::create_basic_game_state("Version Test".to_string());
+    let data = GameStateSerializer::new()
+      .with_version(SaveVersion::new(2, 0, 0))
+      .serialize_game_state(&game_state)
+      .unwrap();
+
+    let result = GameStateSerializer::new().deserialize_game_state(&data);
+    assert!(matches!(result, Err(SerializationError::IncompatibleVersion { found, .. }) if found.major == 2));
+  }
+
+  #[test]
+  fn test_save_manager_rejects_tampered_save() {
+    let temp_dir = TempDir::new().unwrap();
+    let save_manager = SaveManager::new(temp_dir.path())
+      .with_serializer(GameStateSerializer::new().with_compression_format(CompressionFormat::Lz4));
+
+    let game_state = GameStateSerializer::create_basic_game_state("Tamper Test".to_string());
+    save_manager.save_game_state("tamper", &game_state).unwrap();
+    assert!(save_manager.load_save_metadata("tamper").unwrap().compressed);
+    assert_eq!(save_manager.verify_save("tamper").unwrap().header().unwrap().compression, CompressionFormat::Lz4);
+
+    let save_path = temp_dir.path().join("tamper.save");
+    let mut data = fs::read(&save_path).unwrap();
+    let middle = SaveHeader::SIZE + (data.len() - SaveHeader::SIZE) / 2;
+    data[middle] ^= 0x55;
+    fs::write(&save_path, &data).unwrap();
+
+    let result = save_manager.load_game_state("tamper");
+    assert!(matches!(result, Err(SerializationError::ChecksumMismatch { .. })));
+
+    data.truncate(data.len() / 2);
+    fs::write(&save_path, &data).unwrap();
+    let result = save_manager.verify_save("tamper");
+    assert!(matches!(result, Err(SerializationError::Truncated { .. })));
+  }
+
+  /// Writes a JSON save with arbitrary content, as an older game version would.
+  fn write_legacy_save(dir: &Path, name: &str, version: SaveVersion, value: &Value) {
+    let data = GameStateSerializer::new()
+      .with_version(version)
+      .frame(serde_json::to_vec(value).unwrap());
+    fs::write(dir.join(format!("{}.save", name)), data).unwrap();
+  }
+
+  /// State in the 1.0.0 schema: `progress.level` was `player_level`, `statistics.deaths` was absent.
+  fn legacy_state() -> Value {
+    let mut value = serde_json::to_value(GameStateSerializer::create_basic_game_state("Old".to_string())).unwrap();
+    let progress = value["progress"].as_object_mut().unwrap();
+    progress.remove("level");
+    progress.insert("player_level".to_string(), Value::from(7));
+    value["progress"]["statistics"].as_object_mut().unwrap().remove("deaths");
+    value
+  }
+
+  fn migrator() -> SaveMigrator {
+    SaveMigrator::new()
+      .with_migration(SaveVersion::new(1, 0, 0), SaveVersion::new(1, 1, 0), "Rename player_level", |value| {
+        let progress = value["progress"].as_object_mut().ok_or("progress is not an object")?;
+        let level = progress.remove("player_level").ok_or("player_level is missing")?;
+        progress.insert("level".to_string(), level);
+        Ok(())
+      })
+      .with_migration(SaveVersion::new(1, 1, 0), SaveVersion::new(1, 2, 0), "Track deaths", |value| {
+        value["progress"]["statistics"]["deaths"] = Value::from(0);
+        Ok(())
+      })
+  }
+
+  #[test]
+  fn test_migration_on_load() {
+    let temp_dir = TempDir::new().unwrap();
+    write_legacy_save(temp_dir.path(), "old", SaveVersion::new(1, 0, 0), &legacy_state());
+
+    let save_manager = SaveManager::new(temp_dir.path())
+      .with_serializer(GameStateSerializer::new().with_version(SaveVersion::new(1, 2, 0)));
+    assert!(matches!(save_manager.load_game_state("old"), Err(SerializationError::Json(_))));
+
+    let save_manager = save_manager.with_migrator(migrator());
+    let state = save_manager.load_game_state("old").unwrap();
+    assert_eq!(state.progress.level, 7);
+    assert_eq!(state.progress.statistics.deaths, 0);
+    assert_eq!(state.metadata.version.number(), (1, 2, 0));
+
+    let ron_save = GameStateSerializer::new()
+      .with_format(SerializationFormat::Ron)
+      .with_version(SaveVersion::new(1, 0, 0))
+      .frame(ron::ser::to_string(&legacy_state()).unwrap().into_bytes());
+    fs::write(temp_dir.path().join("old_ron.save"), ron_save).unwrap();
+    assert_eq!(save_manager.dry_run_migration("old_ron").unwrap().steps.len(), 2);
+    assert_eq!(save_manager.load_game_state("old_ron").unwrap().progress.level, 7);
+
+    // Saves of the current version load without migration
+    save_manager.save_game_state("new", &state).unwrap();
+    assert!(save_manager.dry_run_migration("new").unwrap().is_empty());
+    assert_eq!(save_manager.load_game_state("new").unwrap().progress.level, 7);
+  }
+
+  #[test]
+  fn test_save_manager_migrates_headerless_saves() {
+    let temp_dir = TempDir::new().unwrap();
+    let raw = serde_json::to_vec(&legacy_state()).unwrap();
+    fs::write(temp_dir.path().join("raw.save"), &raw).unwrap();
+    let mut marked = vec![0xC0, 0x4D, 0x50];
+    marked.extend_from_slice(&(raw.len() as u32).to_le_bytes());
+    marked.extend_from_slice(&raw);
+    fs::write(temp_dir.path().join("marked.save"), &marked).unwrap();
+
+    let save_manager = SaveManager::new(temp_dir.path())
+      .with_serializer(GameStateSerializer::new().with_version(SaveVersion::new(1, 2, 0)))
+      .with_migrator(migrator());
+    for name in ["raw", "marked"] {
+      assert!(matches!(save_manager.verify_save(name), Ok(SaveVerification::Headerless)));
+      let report = save_manager.dry_run_migration(name).unwrap();
+      assert_eq!(report.steps.len(), 2);
+      assert_eq!(report.steps[0].from.number(), (1, 0, 0));
+
+      let state = save_manager.load_game_state(name).unwrap();
+      assert_eq!(state.progress.level, 7);
+      assert_eq!(state.metadata.version.number(), (1, 2, 0));
+    }
+  }
+
+  #[test]
+  fn test_migration_dry_run() {
+    let temp_dir = TempDir::new().unwrap();
+    write_legacy_save(temp_dir.path(), "old", SaveVersion::new(1, 0, 0), &legacy_state());
+    let before = fs::read(temp_dir.path().join("old.save")).unwrap();
+
+    let save_manager = SaveManager::new(temp_dir.path())
+      .with_serializer(GameStateSerializer::new().with_version(SaveVersion::new(1, 2, 0)))
+      .with_migrator(migrator());
+    let report = save_manager.dry_run_migration("old").unwrap();
+
+    assert_eq!(report.steps.len(), 2);
+    assert_eq!(report.steps[0].description, "Rename player_level");
+    assert_eq!(report.steps[0].from.number(), (1, 0, 0));
+    assert_eq!(
+      report.steps[0].changes,
+      vec![
+        ValueChange::Removed { path: "/progress/player_level".to_string(), value: Value::from(7) },
+        ValueChange::Added { path: "/progress/level".to_string(), value: Value::from(7) },
+      ]
+    );
+    assert_eq!(
+      report.steps[1].changes,
+      vec![ValueChange::Added { path: "/progress/statistics/deaths".to_string(), value: Value::from(0) }]
+    );
+
+    assert_eq!(fs::read(temp_dir.path().join("old.save")).unwrap(), before);
+  }
+
+  #[test]
+  fn test_migration_errors() {
+    let temp_dir = TempDir::new().unwrap();
+    write_legacy_save(temp_dir.path(), "ancient", SaveVersion::new(0, 9, 0), &legacy_state());
+    write_legacy_save(temp_dir.path(), "old", SaveVersion::new(1, 0, 0), &legacy_state());
+    let serializer = || GameStateSerializer::new().with_version(SaveVersion::new(1, 2, 0));
+
+    let save_manager = SaveManager::new(temp_dir.path()).with_serializer(serializer()).with_migrator(migrator());
+    let result = save_manager.load_game_state("ancient");
+    assert!(matches!(result, Err(SerializationError::IncompatibleVersion { found, .. }) if found.number() == (0, 9, 0)));
+
+    let save_manager = save_manager.with_migrator(
+      SaveMigrator::new().with_migration(SaveVersion::new(0, 9, 0), SaveVersion::new(0, 10, 0), "Partial", |_| Ok(()))
+    );
+    let result = save_manager.load_game_state("ancient");
+    assert!(matches!(result, Err(SerializationError::MissingMigration { from, .. }) if from.number() == (0, 10, 0)));
+
+    let save_manager = save_manager.with_migrator(
+      SaveMigrator::new().with_migration(SaveVersion::new(1, 0, 0), SaveVersion::new(1, 2, 0), "Broken", |_| {
+        Err("unexpected layout".to_string())
+      })
+    );
+    let result = save_manager.dry_run_migration("old");
+    assert!(matches!(result, Err(SerializationError::Migration { message, .. }) if message == "unexpected layout"));
+
+    let binary = GameStateSerializer::new()
+      .with_format(SerializationFormat::Binary)
+      .with_version(SaveVersion::new(1, 0, 0))
+      .serialize_game_state(&GameStateSerializer::create_basic_game_state("Binary".to_string()))
+      .unwrap();
+    fs::write(temp_dir.path().join("binary.save"), binary).unwrap();
+    let result = save_manager.load_game_state("binary");
+    assert!(matches!(result, Err(SerializationError::UnsupportedMigrationFormat(SerializationFormat::Binary))));
+  }
+
   #[test]
   fn test_save_manager() {
     let temp_dir = TempDir::new().unwrap();
@@ -43,7 +43,8 @@
 
 use tiles_tools::ecs::{
   World, Position, Health, Movable, Stats, Team, AI, PlayerControlled, 
-  EntityBuilder, Animation, Sprite, Size
+  EntityBuilder, Animation, Sprite, Size, GameEvent, MoveIntent, MovementFailure,
+  Obstacle, Occupant
 };
 use tiles_tools::coordinates::{
   square::{Coordinate as SquareCoord, FourConnected, EightConnected},
@@ -391,14 +392,162 @@ fn test_movement_requests()
   ));
   
   // Request movement
-  world.request_movement(entity, SquareCoord::<FourConnected>::new(3, 3));
+  world.request_movement(entity, SquareCoord::<FourConnected>::new(3, 3)).unwrap();
   
-  // Update should process the movement request
+  // One tile per update
   world.update(0.016);
+  let pos = *world.get::<Position<SquareCoord<FourConnected>>>(entity).unwrap();
+  assert_eq!(pos.coord.distance(&SquareCoord::<FourConnected>::new(1, 1)), 1);
+  assert!(world.events().contains(&GameEvent::EntityMoved { entity }));
   
-  // Note: The current implementation just clears requests
-  // In a full implementation, position would be updated
-  // This test verifies the API works without errors
+  for _ in 0..3 {
+    world.update(0.016);
+  }
+  
+  let pos = *world.get::<Position<SquareCoord<FourConnected>>>(entity).unwrap();
+  assert_eq!(pos.coord, SquareCoord::<FourConnected>::new(3, 3));
+  assert!(world.events().contains(&GameEvent::MovementArrived { entity }));
+  assert!(world.get::<MoveIntent<SquareCoord<FourConnected>>>(entity).is_err());
+}
+
+#[ test ]
+fn test_movement_avoids_obstacles()
+{
+  let mut world = World::new();
+  
+  // Wall between the unit and its target, open at both ends
+  let wall: Vec<_> = (-2..3).map(|y| SquareCoord::<FourConnected>::new(2, y)).collect();
+  for coord in &wall {
+    world.spawn(EntityBuilder::obstacle(*coord));
+  }
+  let entity = world.spawn((
+    Position::new(SquareCoord::<FourConnected>::new(0, 0)),
+    Movable::new(5),
+  ));
+  
+  world.request_movement(entity, SquareCoord::<FourConnected>::new(4, 0)).unwrap();
+  
+  let mut arrived = false;
+  for _ in 0..20 {
+    world.update(0.016);
+    let pos = *world.get::<Position<SquareCoord<FourConnected>>>(entity).unwrap();
+    assert!(!wall.contains(&pos.coord), "unit must not enter the wall, got {:?}", pos.coord);
+    if world.events().contains(&GameEvent::MovementArrived { entity }) {
+      arrived = true;
+      break;
+    }
+  }
+  
+  assert!(arrived);
+}
+
+#[ test ]
+fn test_movement_replans_around_occupant()
+{
+  let mut world = World::new();
+  
+  let entity = world.spawn((
+    Position::new(SquareCoord::<FourConnected>::new(0, 0)),
+    Movable::new(5),
+    Occupant,
+  ));
+  world.request_movement(entity, SquareCoord::<FourConnected>::new(3, 0)).unwrap();
+  world.update(0.016);
+  assert_eq!(world.get::<Position<SquareCoord<FourConnected>>>(entity).unwrap().coord, SquareCoord::<FourConnected>::new(1, 0));
+  
+  // Another unit steps onto the planned path
+  world.spawn((Position::new(SquareCoord::<FourConnected>::new(2, 0)), Occupant));
+  world.update(0.016);
+  
+  assert!(world.events().contains(&GameEvent::MovementReplanned { entity }));
+  let pos = *world.get::<Position<SquareCoord<FourConnected>>>(entity).unwrap();
+  assert_ne!(pos.coord, SquareCoord::<FourConnected>::new(2, 0));
+  
+  for _ in 0..10 {
+    world.update(0.016);
+  }
+  assert_eq!(world.get::<Position<SquareCoord<FourConnected>>>(entity).unwrap().coord, SquareCoord::<FourConnected>::new(3, 0));
+}
+
+#[ test ]
+fn test_movement_waits_for_occupant_in_corridor()
+{
+  let mut world = World::new();
+  
+  // Dead-end corridor one tile wide, a unit stands in it on the only route
+  for x in -1..6 {
+    world.spawn(EntityBuilder::obstacle(SquareCoord::<FourConnected>::new(x, 1)));
+    world.spawn(EntityBuilder::obstacle(SquareCoord::<FourConnected>::new(x, -1)));
+  }
+  world.spawn(EntityBuilder::obstacle(SquareCoord::<FourConnected>::new(-1, 0)));
+  world.spawn(EntityBuilder::obstacle(SquareCoord::<FourConnected>::new(5, 0)));
+  let entity = world.spawn((
+    Position::new(SquareCoord::<FourConnected>::new(0, 0)),
+    Movable::new(5),
+    Occupant,
+  ));
+  let blocker = world.spawn((Position::new(SquareCoord::<FourConnected>::new(2, 0)), Occupant));
+  world.request_movement(entity, SquareCoord::<FourConnected>::new(4, 0)).unwrap();
+  
+  world.update(0.016);
+  assert!(world.events().contains(&GameEvent::MovementReplanned { entity }));
+  assert!(world.get::<MoveIntent<SquareCoord<FourConnected>>>(entity).is_ok());
+  assert_eq!(world.get::<Position<SquareCoord<FourConnected>>>(entity).unwrap().coord, SquareCoord::<FourConnected>::new(0, 0));
+  
+  // The blocker passes through and leaves the corridor
+  world.despawn(blocker).unwrap();
+  for _ in 0..5 {
+    world.update(0.016);
+  }
+  assert_eq!(world.get::<Position<SquareCoord<FourConnected>>>(entity).unwrap().coord, SquareCoord::<FourConnected>::new(4, 0));
+  
+  // A blocker that never leaves uses up the re-plan budget
+  let stuck = world.spawn((
+    Position::new(SquareCoord::<FourConnected>::new(0, 0)),
+    Movable::new(5),
+    Occupant,
+  ));
+  world.spawn((Position::new(SquareCoord::<FourConnected>::new(2, 0)), Occupant));
+  world.request_movement(stuck, SquareCoord::<FourConnected>::new(3, 0)).unwrap();
+  let mut failed = false;
+  for _ in 0..20 {
+    world.update(0.016);
+    failed |= world.events().contains(&GameEvent::MovementFailed { entity : stuck, reason : MovementFailure::Blocked });
+  }
+  assert!(failed);
+  assert!(world.get::<MoveIntent<SquareCoord<FourConnected>>>(stuck).is_err());
+}
+
+#[ test ]
+fn test_movement_failures()
+{
+  let mut world = World::new();
+  
+  // Target is walled in
+  let target = SquareCoord::<FourConnected>::new(5, 5);
+  for neighbor in target.neighbors() {
+    world.spawn((Position::new(neighbor), Obstacle));
+  }
+  let walker = world.spawn((
+    Position::new(SquareCoord::<FourConnected>::new(0, 0)),
+    Movable::new(5),
+  ));
+  let ghost = world.spawn((
+    Position::new(SquareCoord::<FourConnected>::new(0, 1)),
+    Movable::new(5).with_obstacle_passthrough(),
+  ));
+  let statue = world.spawn((Position::new(SquareCoord::<FourConnected>::new(9, 9)),));
+  
+  world.request_movement(walker, target).unwrap();
+  world.request_movement(ghost, target).unwrap();
+  world.request_movement(statue, target).unwrap();
+  world.update(0.016);
+  
+  assert!(world.events().contains(&GameEvent::MovementFailed { entity : walker, reason : MovementFailure::NoPath }));
+  assert!(world.events().contains(&GameEvent::MovementFailed { entity : statue, reason : MovementFailure::NotMovable }));
+  assert!(world.events().contains(&GameEvent::EntityMoved { entity : ghost }));
+  assert!(world.get::<MoveIntent<SquareCoord<FourConnected>>>(walker).is_err());
+  assert!(world.get::<MoveIntent<SquareCoord<FourConnected>>>(ghost).is_ok());
 }
 
 // =============================================================================
@@ -10,6 +10,7 @@
 //! | FF1.1   | Square    | Field Creation | Success        |
 //! | FF2.1   | Square    | Flow Calc      | Valid Dirs     |
 //! | FF2.2   | Hex       | Flow Calc      | Valid Dirs     |
+//! | FF2.3   | Tri / Iso | Flow Calc      | Valid Dirs     |
 //! | FF3.1   | Multi     | Batch Process  | Efficient      |
 //! | FF4.1   | Dynamic   | Incremental    | Fast Update    |
 
@@ -37,13 +38,30 @@
 #![allow(clippy::unreadable_literal)]
 #![allow(clippy::redundant_else)]
 
-use tiles_tools::flowfield::{FlowField, FlowDirection, IntegrationField, MultiGoalFlowField};
+use tiles_tools::flowfield::{FlowField, FlowDirection, IntegrationField, MultiGoalFlowField, DynamicFlowField, FlowFieldAnalyzer};
 use tiles_tools::coordinates::{
-  Distance, Neighbors,
+  Distance,
   square::{Coordinate as SquareCoord, FourConnected, EightConnected},
   hexagonal::{Coordinate as HexCoord, Axial, Pointy},
+  triangular::{Coordinate as TriCoord, FlatSided},
+  isometric::{Coordinate as IsoCoord, Diamond},
 };
 
+/// Follows the flow from `start` and returns the visited positions, goal included.
+fn follow_flow<C>(field: &FlowField<(), ()>, start: C, max_steps: usize) -> Vec<C>
+where
+  C: tiles_tools::collection::GridCell + Copy,
+{
+  let mut path = vec![start];
+  let mut current = start;
+  while let Some(next) = field.apply_flow(&current) {
+    path.push(next);
+    current = next;
+    assert!(path.len() <= max_steps, "flow does not converge");
+  }
+  path
+}
+
 // =============================================================================
 // Basic Flow Field Tests
 // =============================================================================
@@ -53,6 +71,7 @@ fn test_integration_field_creation()
 {
   let integration = IntegrationField::<(), ()>::new(10, 10);
   assert_eq!(integration.max_cost, u32::MAX);
+  assert_eq!(integration.get_cost(&SquareCoord::<FourConnected>::new(3, 3)), u32::MAX);
 }
 
 #[ test ]
@@ -92,16 +111,21 @@ fn test_square_grid_flow_field_basic()
 {
   let mut flow_field = FlowField::<(), ()>::new(5, 5);
   
-  // Test basic properties
-  assert_eq!(flow_field.width, 5);
-  assert_eq!(flow_field.height, 5);
-  
-  // Create a simple goal and calculate flow
   let goal = SquareCoord::<FourConnected>::new(2, 2);
   flow_field.calculate_flow(&goal, |_| true, |_| 1);
   
-  // Test should complete without panicking
-  // In a full implementation, would verify actual flow directions
+  // Integration cost equals the Manhattan distance on an open 4-connected grid
+  for x in 0..5 {
+    for y in 0..5 {
+      let coord = SquareCoord::<FourConnected>::new(x, y);
+      assert_eq!(flow_field.integration().get_cost(&coord), coord.distance(&goal));
+    }
+  }
+  
+  assert_eq!(flow_field.get_flow_direction(&goal), Some(FlowDirection::None));
+  let path = follow_flow(&flow_field, SquareCoord::<FourConnected>::new(0, 0), 25);
+  assert_eq!(path.last(), Some(&goal));
+  assert_eq!(path.len(), 5);
 }
 
 #[ test ]
@@ -111,7 +135,6 @@ fn test_square_grid_obstacles()
   
   let goal = SquareCoord::<FourConnected>::new(6, 6);
   
-  // Define obstacles
   let obstacles = vec![
     SquareCoord::<FourConnected>::new(3, 3),
     SquareCoord::<FourConnected>::new(3, 4),
@@ -123,8 +146,13 @@ fn test_square_grid_obstacles()
     |_| 1
   );
   
-  // Verify flow field handles obstacles correctly
-  // Units should path around obstacles
+  // Obstacles have no flow and are never stepped on
+  for obstacle in &obstacles {
+    assert_eq!(flow_field.get_flow_direction(obstacle), Some(FlowDirection::None));
+  }
+  let path = follow_flow(&flow_field, SquareCoord::<FourConnected>::new(2, 2), 64);
+  assert_eq!(path.last(), Some(&goal));
+  assert!(path.iter().all(|step| !obstacles.contains(step)));
 }
 
 #[ test ]
@@ -134,7 +162,6 @@ fn test_square_grid_terrain_costs()
   
   let goal = SquareCoord::<EightConnected>::new(5, 5);
   
-  // Define terrain with varying costs
   let get_terrain_cost = |coord: &SquareCoord<EightConnected>| -> u32 {
     match ( coord.x + coord.y ) % 3
     {
@@ -147,7 +174,12 @@ fn test_square_grid_terrain_costs()
   
   flow_field.calculate_flow(&goal, |_| true, get_terrain_cost);
   
-  // Flow should prefer lower cost paths
+  // Following the flow accumulates exactly the integration cost of the start
+  let start = SquareCoord::<EightConnected>::new(0, 0);
+  let path = follow_flow(&flow_field, start, 36);
+  let walked: u32 = path.iter().skip(1).take(path.len() - 2).map(get_terrain_cost).sum();
+  assert_eq!(path.last(), Some(&goal));
+  assert_eq!(flow_field.integration().get_cost(&start), walked + get_terrain_cost(&start));
 }
 
 // =============================================================================
@@ -157,25 +189,25 @@ fn test_square_grid_terrain_costs()
 #[ test ]
 fn test_hexagonal_grid_flow_field()
 {
-  let mut flow_field = FlowField::<(), ()>::new(7, 7);
+  let mut flow_field = FlowField::<(), ()>::with_bounds((-3, -3), (4, 4));
   
   let goal = HexCoord::<Axial, Pointy>::new(0, 0);
   
   flow_field.calculate_flow(&goal, |_| true, |_| 1);
   
-  // Test hexagonal neighbor relationships
   let test_pos = HexCoord::<Axial, Pointy>::new(2, -1);
-  // In full implementation would verify flow direction points toward goal
+  assert_eq!(flow_field.integration().get_cost(&test_pos), 2);
+  let next = flow_field.apply_flow(&test_pos).expect("flow toward goal");
+  assert_eq!(Distance::distance(&next, &goal), 1);
 }
 
 #[ test ]
 fn test_hex_grid_with_water_obstacles()
 {
-  let mut flow_field = FlowField::<(), ()>::new(10, 10);
+  let mut flow_field = FlowField::<(), ()>::with_bounds((-5, -5), (6, 6));
   
   let goal = HexCoord::<Axial, Pointy>::new(4, -2);
   
-  // Define water hexes as impassable
   let water_hexes = vec![
     HexCoord::<Axial, Pointy>::new(1, 0),
     HexCoord::<Axial, Pointy>::new(2, -1),
@@ -187,7 +219,43 @@ fn test_hex_grid_with_water_obstacles()
     |_| 1
   );
   
-  // Units should path around water
+  // Units path around the water
+  let path = follow_flow(&flow_field, HexCoord::<Axial, Pointy>::new(0, 0), 121);
+  assert_eq!(path.last(), Some(&goal));
+  assert!(path.iter().all(|step| !water_hexes.contains(step)));
+}
+
+// =============================================================================
+// Triangular and Isometric Grid Flow Field Tests
+// =============================================================================
+
+#[ test ]
+fn test_triangular_grid_flow_field()
+{
+  let mut flow_field = FlowField::<(), ()>::with_bounds((-10, -5), (10, 5));
+  
+  let goal = TriCoord::<FlatSided>::new(0, 0, 1).unwrap();
+  let start = TriCoord::<FlatSided>::new(3, -2, 1).unwrap();
+  
+  flow_field.calculate_flow(&goal, |_| true, |_| 1);
+  
+  let path = follow_flow(&flow_field, start, 200);
+  assert_eq!(path.last(), Some(&goal));
+  assert_eq!(flow_field.integration().get_cost(&start), start.distance(&goal));
+}
+
+#[ test ]
+fn test_isometric_grid_flow_field()
+{
+  let mut flow_field = FlowField::<(), ()>::new(6, 6);
+  
+  let goal = IsoCoord::<Diamond>::new(5, 0);
+  flow_field.calculate_flow(&goal, |_| true, |_| 1);
+  
+  let start = IsoCoord::<Diamond>::new(0, 5);
+  let path = follow_flow(&flow_field, start, 36);
+  assert_eq!(path.last(), Some(&goal));
+  assert_eq!(path.len() as u32 - 1, start.distance(&goal));
 }
 
 // =============================================================================
@@ -197,24 +265,29 @@ fn test_hex_grid_with_water_obstacles()
 #[ test ]
 fn test_batch_flow_direction_queries()
 {
-  let flow_field = FlowField::<(), ()>::new(12, 12);
+  let mut flow_field = FlowField::<(), ()>::new(12, 12);
+  flow_field.calculate_flow(&SquareCoord::<FourConnected>::new(11, 11), |_| true, |_| 1);
   
   let test_coordinates = vec![
     SquareCoord::<FourConnected>::new(1, 1),
     SquareCoord::<FourConnected>::new(3, 5),
     SquareCoord::<FourConnected>::new(7, 2),
     SquareCoord::<FourConnected>::new(9, 8),
+    SquareCoord::<FourConnected>::new(20, 20),
   ];
   
-  // This would test batch processing in a full implementation
   let directions = flow_field.get_flow_directions_batch(&test_coordinates);
   assert_eq!(directions.len(), test_coordinates.len());
+  assert!(directions[..4].iter().all(|dir| matches!(dir, Some(FlowDirection::Move(_, _)))));
+  assert_eq!(directions[4], None);
 }
 
 #[ test ]
 fn test_group_movement_flow_application()
 {
-  let flow_field = FlowField::<(), ()>::new(15, 15);
+  let mut flow_field = FlowField::<(), ()>::new(15, 15);
+  let goal = SquareCoord::<FourConnected>::new(14, 0);
+  flow_field.calculate_flow(&goal, |_| true, |_| 1);
   
   let unit_positions = vec![
     SquareCoord::<FourConnected>::new(2, 3),
@@ -226,8 +299,11 @@ fn test_group_movement_flow_application()
   let group_flow = flow_field.calculate_group_flow(&unit_positions);
   assert_eq!(group_flow.len(), unit_positions.len());
   
-  // Each unit should get movement suggestion
-  // Group flow helps prevent clustering
+  // Every unit steps one tile closer to the goal
+  for (unit, next) in unit_positions.iter().zip(group_flow) {
+    let next = next.expect("unit should move");
+    assert_eq!(next.distance(&goal) + 1, unit.distance(&goal));
+  }
 }
 
 // =============================================================================
@@ -246,7 +322,6 @@ fn test_multi_goal_resource_gathering()
 {
   let mut multi_field = MultiGoalFlowField::<(), ()>::new(25, 25);
   
-  // Add multiple resource nodes as goals
   let resource_nodes = vec![
     SquareCoord::<FourConnected>::new(5, 5),
     SquareCoord::<FourConnected>::new(15, 8),
@@ -259,30 +334,76 @@ fn test_multi_goal_resource_gathering()
   
   assert_eq!(multi_field.goal_fields.len(), 3);
   
-  // Test that flow points toward nearest resource
-  let worker_pos = SquareCoord::<FourConnected>::new(10, 10);
-  let _optimal_direction = multi_field.get_optimal_direction(&worker_pos);
+  // Worker at (14, 10) is closest to (15, 8), two steps up-right
+  let worker_pos = SquareCoord::<FourConnected>::new(14, 10);
+  let direction = multi_field.get_optimal_direction(&worker_pos);
+  assert!(matches!(direction, Some(FlowDirection::Move(1, 0) | FlowDirection::Move(0, -1))));
 }
 
 #[ test ]
 fn test_multi_goal_capture_points()
 {
-  let mut multi_field = MultiGoalFlowField::<(), ()>::new(30, 30);
+  let mut multi_field = MultiGoalFlowField::<(), ()>::with_bounds((-15, -15), (15, 15));
   
-  // Add capture points for RTS scenario
   let capture_points = vec![
     HexCoord::<Axial, Pointy>::new(-3, 2),
     HexCoord::<Axial, Pointy>::new(4, -1),
     HexCoord::<Axial, Pointy>::new(1, 3),
   ];
   
-  for point in capture_points {
-    multi_field.add_goal(&point, |_| true, |_| 1);
+  for point in &capture_points {
+    multi_field.add_goal(point, |_| true, |_| 1);
   }
   
-  // Units should move toward nearest capturable point
+  for point in &capture_points {
+    assert_eq!(multi_field.get_optimal_direction(point), Some(FlowDirection::None));
+  }
   let unit_pos = HexCoord::<Axial, Pointy>::new(0, 0);
-  let _direction = multi_field.get_optimal_direction(&unit_pos);
+  assert!(matches!(multi_field.get_optimal_direction(&unit_pos), Some(FlowDirection::Move(_, _))));
+}
+
+// =============================================================================
+// Dynamic Flow Field Tests
+// =============================================================================
+
+#[ test ]
+fn test_dynamic_flow_field_obstacle_repair()
+{
+  let goal = SquareCoord::<EightConnected>::new(19, 10);
+  let mut dynamic_field = DynamicFlowField::<(), ()>::new(20, 20);
+  dynamic_field.calculate_flow(&goal, |_| true, |_| 1);
+  
+  // A wall appears in the middle of the map
+  let wall: Vec<_> = (3..17).map(|y| SquareCoord::<EightConnected>::new(10, y)).collect();
+  for cell in &wall {
+    dynamic_field.mark_dirty((cell.x, cell.y));
+  }
+  let is_passable = |coord: &SquareCoord<EightConnected>| !wall.contains(coord);
+  let repaired = dynamic_field.incremental_update(is_passable, |_| 1);
+  
+  // Only the region behind the wall is repaired
+  assert!(repaired < 400);
+  
+  let mut full = FlowField::<(), ()>::new(20, 20);
+  full.calculate_flow(&goal, is_passable, |_| 1);
+  for x in 0..20 {
+    for y in 0..20 {
+      let coord = SquareCoord::<EightConnected>::new(x, y);
+      assert_eq!(
+        dynamic_field.field().integration().get_cost(&coord),
+        full.integration().get_cost(&coord),
+        "cost mismatch at {:?}", (x, y)
+      );
+    }
+  }
+  
+  // Removing the wall again restores the open-field costs
+  for cell in &wall {
+    dynamic_field.mark_dirty((cell.x, cell.y));
+  }
+  dynamic_field.incremental_update(|_: &SquareCoord<EightConnected>| true, |_| 1);
+  let start = SquareCoord::<EightConnected>::new(0, 10);
+  assert_eq!(dynamic_field.field().integration().get_cost(&start), start.distance(&goal));
 }
 
 // =============================================================================
@@ -296,21 +417,20 @@ fn test_large_grid_performance()
   
   let goal = SquareCoord::<FourConnected>::new(50, 50);
   
-  // Test large grid calculation performance
   let start_time = std::time::Instant::now();
   flow_field.calculate_flow(&goal, |_| true, |_| 1);
   let calculation_time = start_time.elapsed();
   
-  // Should complete within reasonable time
   assert!(calculation_time.as_millis() < 5000); // 5 second max
+  assert_eq!(flow_field.integration().get_cost(&SquareCoord::<FourConnected>::new(0, 0)), 100);
 }
 
 #[ test ]
 fn test_many_units_batch_processing()
 {
-  let flow_field = FlowField::<(), ()>::new(50, 50);
+  let mut flow_field = FlowField::<(), ()>::new(50, 50);
+  flow_field.calculate_flow(&SquareCoord::<FourConnected>::new(49, 49), |_| true, |_| 1);
   
-  // Create many unit positions
   let mut unit_positions = Vec::new();
   for x in 0..40 {
     for y in 0..40 {
@@ -320,15 +440,15 @@ fn test_many_units_batch_processing()
     }
   }
   
-  // Test batch processing performance
   let start_time = std::time::Instant::now();
-  let _directions = flow_field.get_flow_directions_batch(&unit_positions);
+  let directions = flow_field.get_flow_directions_batch(&unit_positions);
   let batch_time = start_time.elapsed();
   
   println!("Processed {} units in {}ms", 
            unit_positions.len(), batch_time.as_millis());
   
   assert!(batch_time.as_millis() < 100); // Should be very fast
+  assert!(directions.iter().all(|dir| matches!(dir, Some(FlowDirection::Move(_, _)))));
 }
 
 // =============================================================================  
@@ -341,10 +461,10 @@ fn test_flow_field_with_no_goal()
   let flow_field = FlowField::<(), ()>::new(5, 5);
   
   let test_pos = SquareCoord::<FourConnected>::new(2, 2);
-  let direction = flow_field.get_flow_direction(&test_pos);
   
-  // Should return None for positions with no flow
   // In uninitialized flow field, all directions are None
+  assert_eq!(flow_field.get_flow_direction(&test_pos), Some(FlowDirection::None));
+  assert_eq!(flow_field.apply_flow(&test_pos), None);
 }
 
 #[ test ]
@@ -371,10 +491,12 @@ fn test_flow_field_unreachable_goal()
     |_| 1
   );
   
-  // Positions outside barrier should have no flow toward unreachable goal
+  // Positions outside barrier have no flow toward the unreachable goal
   let outside_pos = SquareCoord::<FourConnected>::new(1, 1);
-  let direction = flow_field.get_flow_direction(&outside_pos);
-  // Should be None or indicate no valid path
+  assert_eq!(flow_field.get_flow_direction(&outside_pos), Some(FlowDirection::None));
+  
+  let analysis = FlowFieldAnalyzer::analyze_flow(&flow_field);
+  assert_eq!(analysis.unreachable_positions, 99);
 }
 
 #[ test ]
@@ -383,6 +505,7 @@ fn test_zero_dimension_flow_field()
   let flow_field = FlowField::<(), ()>::new(0, 0);
   assert_eq!(flow_field.width, 0);
   assert_eq!(flow_field.height, 0);
+  assert_eq!(flow_field.get_flow_direction(&SquareCoord::<FourConnected>::new(0, 0)), None);
 }
 
 #[ test ]
@@ -393,9 +516,8 @@ fn test_single_cell_flow_field()
   let goal = SquareCoord::<FourConnected>::new(0, 0);
   flow_field.calculate_flow(&goal, |_| true, |_| 1);
   
-  // Single cell should be its own goal with no movement needed
-  let direction = flow_field.get_flow_direction(&goal);
-  // Should be None (no movement needed at goal)
+  // Single cell is its own goal with no movement needed
+  assert_eq!(flow_field.get_flow_direction(&goal), Some(FlowDirection::None));
 }
 
 // =============================================================================
@@ -408,37 +530,35 @@ fn test_flow_field_ecs_integration()
   use tiles_tools::ecs::{World, Position, Movable};
   
   let mut world = World::new();
-  let flow_field = FlowField::<(), ()>::new(20, 20);
+  let mut flow_field = FlowField::<(), ()>::new(20, 20);
+  flow_field.calculate_flow(&SquareCoord::<FourConnected>::new(19, 19), |_| true, |_| 1);
   
-  // Spawn some units
-  let unit1 = world.spawn((
+  world.spawn((
     Position::new(SquareCoord::<FourConnected>::new(3, 3)),
     Movable::new(2),
   ));
   
-  let unit2 = world.spawn((
+  world.spawn((
     Position::new(SquareCoord::<FourConnected>::new(7, 5)),
     Movable::new(3),
   ));
   
   // Collect unit positions for batch flow processing
   let mut unit_positions = Vec::new();
-  let query = world.query::<&Position<SquareCoord<FourConnected>>>();
+  let mut query = world.query::<&Position<SquareCoord<FourConnected>>>();
   for (_entity, pos) in query.iter() {
     unit_positions.push(pos.coord);
   }
   
-  // Get flow directions for all units
   let flow_directions = flow_field.get_flow_directions_batch(&unit_positions);
   assert_eq!(flow_directions.len(), 2);
-  
-  // Flow field integration with ECS works correctly
+  assert!(flow_directions.iter().all(|dir| matches!(dir, Some(FlowDirection::Move(_, _)))));
 }
 
 #[ test ]
 fn test_rts_scenario_simulation()
 {
-  use tiles_tools::ecs::{World, Position, Team};
+  use tiles_tools::ecs::{World, Position, Team, Movable};
   
   let mut world = World::new();
   let mut flow_field = FlowField::<(), ()>::new(40, 40);
@@ -462,7 +582,7 @@ fn test_rts_scenario_simulation()
   
   // All units should flow toward enemy base
   let mut unit_positions = Vec::new();
-  let query = world.query::<(&Position<SquareCoord<FourConnected>>, &Team)>();
+  let mut query = world.query::<(&Position<SquareCoord<FourConnected>>, &Team)>();
   for (_entity, (pos, team)) in query.iter() {
     if team.id == player_team.id {
       unit_positions.push(pos.coord);
@@ -472,9 +592,8 @@ fn test_rts_scenario_simulation()
   let group_flow = flow_field.calculate_group_flow(&unit_positions);
   assert_eq!(group_flow.len(), 15); // 5x3 = 15 units
   
-  // Each unit gets movement direction toward enemy base
-  for movement in group_flow {
-    // In full implementation, would verify direction points toward goal
-    // For now, just verify we get Some result for each unit
+  for (unit, movement) in unit_positions.iter().zip(group_flow) {
+    let next = movement.expect("unit should move toward the base");
+    assert!(next.distance(&enemy_base) < unit.distance(&enemy_base));
   }
-}
\ No newline at end of file
+}
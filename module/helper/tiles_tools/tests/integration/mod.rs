@@ -32,8 +32,7 @@ mod conversion_tests;
 mod coordinates_tests;
 mod ecs_tests;
 mod field_of_view_tests;
-// Temporarily disabled until flowfield generic constraints are resolved
-// mod flowfield_tests;
+mod flowfield_tests;
 mod isometric_coords_tests;
 mod square_coords_tests;
 mod triangular_coords_tests;